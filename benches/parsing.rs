@@ -0,0 +1,99 @@
+//! Benchmarks of the parsing stages on synthetic statements (see
+//! structs::text_items::synth::generate_statement). Run with `cargo bench`.
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use std::path::Path;
+use transtractor::checkers::check_statement_data;
+use transtractor::configs::typer::StatementTyper;
+use transtractor::fixers::fix_statement_data;
+use transtractor::parsers::flows::config_json_file_to_config::{from_json_file, from_json_str};
+use transtractor::parsers::flows::layout_to_text_items::layout_to_text_items;
+use transtractor::parsers::flows::text_items_to_statement_data::text_items_to_statement_data;
+use transtractor::structs::StatementConfig;
+use transtractor::structs::text_items::synth::generate_statement;
+use transtractor::structs::text_items::tokenise_items;
+
+const CONFIG: &str = include_str!("../tests/fixtures/test3_config.json");
+
+fn config() -> StatementConfig {
+    from_json_str(CONFIG).unwrap()
+}
+
+/// All JSON configs in a directory and its subdirectories
+fn configs_in(dir: &Path, configs: &mut Vec<StatementConfig>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            configs_in(&path, configs);
+        } else if path.extension().is_some_and(|ext| ext == "json")
+            && let Ok(cfg) = from_json_file(&path)
+        {
+            configs.push(cfg);
+        }
+    }
+}
+
+fn bench_layout_to_text_items(c: &mut Criterion) {
+    let layout = generate_statement(2_000);
+    c.bench_function("layout_to_text_items 2k rows", |b| {
+        b.iter(|| layout_to_text_items(black_box(&layout)).unwrap())
+    });
+}
+
+fn bench_identify(c: &mut Criterion) {
+    // The packaged configs, the examples and the test fixtures
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut configs = Vec::new();
+    for dir in [
+        "python/transtractor/configs",
+        "examples/data",
+        "tests/fixtures",
+    ] {
+        configs_in(&root.join(dir), &mut configs);
+    }
+    let mut typer = StatementTyper::new();
+    for cfg in &configs {
+        typer.add_account_terms(&cfg.key, &cfg.account_terms, cfg.terms_case_insensitive);
+    }
+    let items = layout_to_text_items(&generate_statement(500)).unwrap();
+    c.bench_function("StatementTyper::identify 500 rows", |b| {
+        b.iter(|| typer.identify(black_box(&items)))
+    });
+}
+
+fn bench_text_items_to_statement_data(c: &mut Criterion) {
+    let cfg = config();
+    let items = tokenise_items(&layout_to_text_items(&generate_statement(2_000)).unwrap());
+    c.bench_function("text_items_to_statement_data 2k rows", |b| {
+        b.iter(|| text_items_to_statement_data(&cfg, black_box(&items)))
+    });
+}
+
+fn bench_fix_and_check(c: &mut Criterion) {
+    let cfg = config();
+    let items = tokenise_items(&layout_to_text_items(&generate_statement(10_000)).unwrap());
+    let sd = text_items_to_statement_data(&cfg, &items);
+    c.bench_function("fix_statement_data + check_statement_data 10k rows", |b| {
+        b.iter_batched(
+            || sd.clone(),
+            |mut sd| {
+                fix_statement_data(&mut sd, &cfg);
+                check_statement_data(&mut sd, &cfg);
+                sd
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_layout_to_text_items,
+    bench_identify,
+    bench_text_items_to_statement_data,
+    bench_fix_and_check
+);
+criterion_main!(benches);
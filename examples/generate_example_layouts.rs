@@ -0,0 +1,25 @@
+//! Regenerate the layouts of the synthetic example configs in examples/data with the
+//! statement synthesizer (see structs::text_items::synth). Run with
+//! `cargo run --example generate_example_layouts`.
+
+use std::fs;
+use std::path::Path;
+use transtractor::structs::text_items::synth::{
+    generate_example_checking, generate_example_credit_card,
+};
+
+fn main() -> std::io::Result<()> {
+    let data_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/data");
+    for (key, layout) in [
+        ("zz__example__checking__1", generate_example_checking()),
+        (
+            "zz__example__credit_card__1",
+            generate_example_credit_card(),
+        ),
+    ] {
+        let path = data_dir.join(format!("{}_layout.txt", key));
+        fs::write(&path, layout + "\n")?;
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
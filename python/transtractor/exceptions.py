@@ -4,3 +4,22 @@ some exceptions are defined in the Rust extension module and exposed"""
 
 class StatementNotSupported(Exception):
     """Raised when the statement format is unsupported or unidentifiable."""
+
+
+class PdfExtractError(Exception):
+    """Raised when no text items can be extracted from the PDF. Base class of the
+    specific reasons below."""
+
+
+class PdfEncrypted(PdfExtractError):
+    """Raised when the PDF is password-protected or its encryption forbids text
+    extraction."""
+
+
+class PdfNoTextContent(PdfExtractError):
+    """Raised when the PDF has no text on any page, e.g., a scanned (image-only)
+    statement that needs OCR first."""
+
+
+class PdfMalformed(PdfExtractError):
+    """Raised when the PDF is corrupt or not a PDF file."""
@@ -1,13 +1,26 @@
 """Python wrapper for the Transtractor PDF bank statement parser."""
 
+import json
+import time
+from collections.abc import Iterator
 from typing import cast
 
 from .exceptions import StatementNotSupported
 from .structs.statement_data import StatementData
-from .transtractor import LibParser
+from .structs.transaction import Transaction
+from .transtractor import ConfigAccessError, LibParser
+from .utils.accounts import accounts_by_number
+from .utils.atomic import atomic_open
+from .utils.combined import append_statements_to_csv, statements_to_csv
 from .utils.default_configs import get_base_config_db
-from .utils.extract import pdf_to_text_items
-from .utils.testing import run_test_protocol
+from .utils.extract import input_kind, pdf_to_text_items
+from .utils.redact import redaction_patterns
+from .utils.testing import (
+    DirectoryReport,
+    ProgressCallback,
+    find_pdf_files,
+    run_test_protocol,
+)
 
 
 class Parser:
@@ -25,20 +38,95 @@ class Parser:
         statement_data = parser.parse('statement.pdf')
         print(statement_data)
         statement_data.to_csv('output.csv')
+
+    Set cache_capacity to cache the text items and applicable configs of the most
+    recently used PDFs, keyed by file content, so that repeated calls on the same
+    file (e.g., identify then parse) skip text extraction and statement typing.
+
+    Methods reading a PDF raise a PdfExtractError (PdfEncrypted, PdfNoTextContent
+    or PdfMalformed) if its text cannot be extracted, rather than reporting the
+    statement as unsupported. They read a layout file (see layout) in place of the
+    PDF if its name ends in ".txt", in any case (see utils.extract.input_kind).
     """
 
-    def __init__(self):
-        """Initialise the Parser with default configurations."""
-        self._inner = LibParser()
+    def __init__(
+        self,
+        cache_capacity: int = 0,
+        locale_filter: bool = False,
+        match_threshold: float = 1.0,
+    ):
+        """Initialise the Parser with default configurations.
+
+        :param cache_capacity: Number of PDFs whose text items and applicable
+            configs are cached. Defaults to 0 (cache disabled)
+        :param locale_filter: Skip the configurations of countries whose
+            statements are written in a different script (e.g., CJK) from the
+            statement when identifying it (see inspect). Configurations are never
+            skipped if the script is uncertain. Defaults to False
+        :param match_threshold: Fraction of the account terms of a configuration
+            that must be found to identify the statement, above 0.0 and at most 1.0.
+            Below 1.0, statements with reworded or missing terms are still
+            identified, best match first, and debug shows the identification score
+            of near misses. Defaults to 1.0 (every term found)
+        :raises ValueError: match_threshold is out of range
+        """
+        self._inner = LibParser(cache_capacity, locale_filter, match_threshold)
+        self._locale_filter = locale_filter
+        self._match_threshold = match_threshold
+        # Category rules (JSON) and case insensitivity, to set in the worker
+        # processes of test_report
+        self._categories: tuple[str | None, bool] = (None, True)
         for key in get_base_config_db().get_all_config_keys():
             account_terms = get_base_config_db().get_account_terms(key)
-            self._inner.add_account_terms(key, account_terms)
+            case_insensitive = get_base_config_db().get_terms_case_insensitive(key)
+            self._inner.add_account_terms(key, account_terms, case_insensitive)
+
+    def _extract(self, pdf_file_path: str) -> tuple[list[dict], bytes | None]:
+        """Extract text items from the PDF, using the cache if enabled, or read them
+        from a layout file (see input_kind). Return the text items and the file
+        content if cached, otherwise None.
+        """
+        if input_kind(pdf_file_path) == "layout":
+            return self._read_layout(pdf_file_path), None
+        if not self._inner.cache_stats()["capacity"]:
+            return pdf_to_text_items(pdf_file_path), None
+        with open(pdf_file_path, "rb") as fh:
+            content = fh.read()
+        py_text_items = self._inner.get_cached_py_text_items(content)
+        if py_text_items is None:
+            py_text_items = pdf_to_text_items(pdf_file_path)
+            self._inner.cache_py_text_items(content, py_text_items)
+        return py_text_items, content
+
+    def _read_layout(self, layout_file_path: str) -> list[dict]:
+        """Read the text items of a layout file.
 
-    def _identify(self, py_text_items: list[dict]) -> list[str]:
+        :raises LayoutParseError: The layout text is invalid
+        """
+        with open(layout_file_path, encoding="utf-8") as fh:
+            return self._inner.py_layout_py_str_to_py_text_items(fh.read())
+
+    def _identify(
+        self,
+        py_text_items: list[dict],
+        timings: dict[str, float] | None = None,
+        content: bytes | None = None,
+        keys: list[str] | None = None,
+    ) -> list[str]:
         """Identify keys applicable to the given text items. Update the
         internal config DB with any uncached configs from the base config DB.
+        The time taken is recorded as the "statement_typing" stage in timings,
+        if provided. If the content of the source file is provided, the keys are
+        cached with its text items. If keys are provided, they are used instead
+        of identifying the statement.
         """
-        applicable_keys = self._inner.get_applicable_config_keys(py_text_items)
+        started = time.perf_counter()
+        if keys is not None:
+            applicable_keys = self._check_keys(keys)
+        else:
+            applicable_keys = self._inner.get_applicable_config_keys(
+                py_text_items, content
+            )
         if not applicable_keys:
             raise StatementNotSupported(
                 "No applicable statement extraction configuration found. Create and "
@@ -48,95 +136,1118 @@ class Parser:
         for key in uncached_keys:
             json_str = get_base_config_db().get_config_json_str(key)
             self._inner.register_config_from_json_str(json_str)
+        if timings is not None:
+            timings["statement_typing"] = time.perf_counter() - started
         return applicable_keys
 
-    def parse(self, pdf_file_path: str) -> StatementData:
+    def _check_keys(self, keys: list[str]) -> list[str]:
+        """Check that every key is a loaded or base config and return the keys.
+
+        :raises ConfigAccessError: A key is neither loaded nor a base config, or no
+            keys are given
+        """
+        if not keys:
+            raise ConfigAccessError("No config keys given")
+        base_config_db = get_base_config_db()
+        for key in self._inner.get_unregistered_config_keys(keys):
+            if not base_config_db.has_config(key):
+                raise ConfigAccessError(
+                    f"Config '{key}' is neither loaded nor a base config. Load it or "
+                    "check the key (see catalog)"
+                )
+        return list(keys)
+
+    def identify(self, pdf_file_path: str) -> list[str]:
+        """Identify the statement extraction configurations applicable to the PDF.
+
+        :param pdf_file_path: Path to the PDF file to be processed
+        :return: Keys of the applicable configurations
+        :raises StatementNotSupported: Statement format is unsupported or not properly
+            identified
+        """
+        py_text_items, content = self._extract(pdf_file_path)
+        return self._identify(py_text_items, content=content)
+
+    def inspect(self, pdf_file_path: str) -> dict:
+        """Return cheap language and locale statistics of the PDF text, as used by
+        the locale filter (see __init__).
+
+        :param pdf_file_path: Path to the PDF file to be processed
+        :return: Dict with the letter counts of each script ("scripts", e.g.,
+            {"Latin": 1520}), the statement "script" ("Latin", "CJK", "Arabic" or
+            "Cyrillic", or None if uncertain), the counts of "currency_symbols"
+            ($, €, £, ₹ and ¥), and the numbers of amounts written with
+            "decimal_commas" (e.g., "1.234,56") and "decimal_points"
+        """
+        py_text_items, _ = self._extract(pdf_file_path)
+        return cast(dict, self._inner.py_text_items_to_py_locale_profile(py_text_items))
+
+    def parse(
+        self,
+        pdf_file_path: str,
+        expected_account_number: str | None = None,
+        date_timezone: str | None = None,
+        fixers: str = "all",
+        retry_text_order: bool = False,
+        keys: list[str] | None = None,
+        strict: bool = False,
+    ) -> StatementData:
         """Parse the bank statement PDF and return a StatementData object.
 
         :param pdf_file_path: Path to the PDF file to be processed
+        :param expected_account_number: Optional account number that the statement
+            must match, ignoring spaces and hyphens, or a regex pattern prefixed with
+            "re:" (e.g., "re:\\d{12}4567")
+        :param date_timezone: Optional IANA timezone (e.g., "Australia/Sydney") in
+            which transaction timestamps represent local midnight. Overrides the
+            date_timezone of the statement config
+        :param fixers: Fixers applied before the quality checks. "all" (default)
+            applies all fixers, "safe" only reorders transactions and sets their
+            daily indices, and "none" checks the parsed data unchanged
+        :param retry_text_order: Retry configs whose fix_text_order reordering gives
+            errors in the original text order, keeping the result with fewer errors.
+            The variant used is recorded in the metadata and warnings
+        :param keys: Optional keys of the configurations to use, in order of
+            preference, instead of identifying the statement (e.g., to force one of
+            two configurations matching the same statement)
+        :param strict: Report an error if more lines of the transaction table than
+            allowed are not read into any field, as if every configuration set
+            transaction_strict (e.g., to catch skipped transactions). The unread
+            lines are listed in the debug output either way
         :return: StatementData object representing the parsed bank statement data
+        :raises ConfigAccessError: A key is neither loaded nor a base config
         :raises NoErrorFreeStatementData: Statement format recognised but could be
-            processed without failing quality checks
+            processed without failing quality checks. Its breakdown attribute lists
+            the errors of each attempted config
+        :raises PdfExtractError: The text of the PDF cannot be extracted
         :raises StatementNotSupported: Statement format is unsupported or not properly
             identified
         """
-        py_text_items = pdf_to_text_items(pdf_file_path)
-        applicable_keys = self._identify(py_text_items)
-        sd: StatementData = cast(
+        started = time.perf_counter()
+        py_text_items, content = self._extract(pdf_file_path)
+        timings = {"text_extraction": time.perf_counter() - started}
+        sd = self._parse_statement(
+            py_text_items,
+            timings,
+            content,
+            expected_account_number,
+            date_timezone,
+            fixers,
+            retry_text_order,
+            keys,
+            strict,
+        )
+        sd.set_filename(pdf_file_path)
+        return sd
+
+    def _parse_statement(
+        self,
+        py_text_items: list[dict],
+        timings: dict[str, float],
+        content: bytes | None,
+        expected_account_number: str | None,
+        date_timezone: str | None,
+        fixers: str,
+        retry_text_order: bool,
+        keys: list[str] | None,
+        strict: bool,
+    ) -> StatementData:
+        """Identify the statement and parse its text items into a StatementData, as
+        for parse. The options are as for parse.
+        """
+        applicable_keys = self._identify(py_text_items, timings, content, keys)
+        return cast(
             StatementData,
             self._inner.py_text_items_to_py_statement_data(
-                py_text_items, applicable_keys
+                py_text_items,
+                applicable_keys,
+                expected_account_number,
+                date_timezone,
+                fixers,
+                retry_text_order,
+                timings,
+                strict=strict,
             ),
         )
-        sd.set_filename(pdf_file_path)
+
+    def parse_all(
+        self,
+        pdf_file_path: str,
+        expected_account_number: str | None = None,
+        date_timezone: str | None = None,
+        fixers: str = "all",
+        retry_text_order: bool = False,
+        keys: list[str] | None = None,
+        strict: bool = False,
+    ) -> list[dict]:
+        """Parse the bank statement PDF with every applicable configuration and
+        return all candidate results, including those that fail the quality checks.
+        Useful when developing a configuration to see which checks failed.
+
+        :param pdf_file_path: Path to the PDF file to be processed
+        :param expected_account_number: See parse
+        :param date_timezone: See parse
+        :param fixers: See parse
+        :param retry_text_order: See parse
+        :param keys: See parse
+        :param strict: See parse
+        :return: List of dicts, one per applicable configuration in the order
+            identified (as in the debug output), with the config "key",
+            "config_status", "passed" (True if error-free), "errors", "warnings",
+            "account_number" and the "date", "description", "amount", "balance",
+            "amount_source", "balance_source" and "category" lists of the
+            transactions (None where a value could not be parsed, "" for untagged
+            categories, see set_categories), and a "metadata" dict of the
+            statement "key", "account_number", "opening_balance", "closing_balance",
+            "start_date" and "errors" (None where not found), kept apart from the
+            column lists so that these can be passed to a DataFrame
+        :raises ConfigAccessError: A key is neither loaded nor a base config
+        :raises StatementNotSupported: Statement format is unsupported or not properly
+            identified
+        """
+        py_text_items, content = self._extract(pdf_file_path)
+        applicable_keys = self._identify(py_text_items, content=content, keys=keys)
+        return cast(
+            list[dict],
+            self._inner.py_text_items_to_py_candidates(
+                py_text_items,
+                applicable_keys,
+                expected_account_number,
+                date_timezone,
+                fixers,
+                retry_text_order,
+                strict=strict,
+            ),
+        )
+
+    def parse_accounts(
+        self,
+        pdf_file_path: str,
+        expected_account_number: str | None = None,
+        date_timezone: str | None = None,
+        fixers: str = "all",
+        retry_text_order: bool = False,
+        keys: list[str] | None = None,
+        strict: bool = False,
+    ) -> dict[str, StatementData]:
+        """Parse a bank statement PDF that may contain several accounts and return
+        the StatementData of each account. Configs with multi_account set split the
+        statement into account sections, each parsed and checked separately. Other
+        statements give a single account. The sections are parsed as by
+        parse_statements, and only keyed by account here.
+
+        :param pdf_file_path: Path to the PDF file to be processed
+        :param expected_account_number: See parse. Not checked for the accounts of
+            multi-account statements
+        :param date_timezone: See parse
+        :param fixers: See parse
+        :param retry_text_order: See parse
+        :param keys: See parse
+        :param strict: See parse
+        :return: Dict of account number to StatementData, in statement order.
+            Accounts without an account number are keyed by their section number
+            (e.g., "section_2"). Export with utils.accounts.accounts_to_csv
+        :raises ConfigAccessError: A key is neither loaded nor a base config
+        :raises NoErrorFreeStatementData: No config gave error-free statement data
+            for every account
+        :raises StatementNotSupported: Statement format is unsupported or not properly
+            identified
+        """
+        return accounts_by_number(
+            self.parse_statements(
+                pdf_file_path,
+                expected_account_number,
+                date_timezone,
+                fixers,
+                retry_text_order,
+                keys,
+                strict,
+            )
+        )
+
+    def parse_statements(
+        self,
+        pdf_file_path: str,
+        expected_account_number: str | None = None,
+        date_timezone: str | None = None,
+        fixers: str = "all",
+        retry_text_order: bool = False,
+        keys: list[str] | None = None,
+        strict: bool = False,
+    ) -> list[StatementData]:
+        """Parse a PDF that may contain several consecutive statements of an account
+        (e.g., monthly statements combined into one file) and return the StatementData
+        of each statement. Configs with multi_statement set split the document at each
+        complete header block, each statement parsed and checked separately. Other
+        documents give a single statement.
+
+        :param pdf_file_path: Path to the PDF file to be processed
+        :param expected_account_number: See parse
+        :param date_timezone: See parse
+        :param fixers: See parse
+        :param retry_text_order: See parse
+        :param keys: See parse
+        :param strict: See parse
+        :return: List of StatementData, ordered by start date, with the statement
+            number in metadata["statement_section"] if the document was split
+        :raises ConfigAccessError: A key is neither loaded nor a base config
+        :raises NoErrorFreeStatementData: No config gave error-free statement data
+            for every statement
+        :raises StatementNotSupported: Statement format is unsupported or not properly
+            identified
+        """
+        started = time.perf_counter()
+        py_text_items, content = self._extract(pdf_file_path)
+        timings = {"text_extraction": time.perf_counter() - started}
+        sds = self._parse_sections(
+            py_text_items,
+            timings,
+            content,
+            expected_account_number,
+            date_timezone,
+            fixers,
+            retry_text_order,
+            keys,
+            strict,
+        )
+        for sd in sds:
+            sd.set_filename(pdf_file_path)
+        return sds
+
+    def _parse_sections(
+        self,
+        py_text_items: list[dict],
+        timings: dict[str, float],
+        content: bytes | None,
+        expected_account_number: str | None,
+        date_timezone: str | None,
+        fixers: str,
+        retry_text_order: bool,
+        keys: list[str] | None,
+        strict: bool,
+    ) -> list[StatementData]:
+        """Identify the statement and parse its text items into the StatementData of
+        each account or statement section, as for parse_statements. The options are
+        as for parse.
+        """
+        applicable_keys = self._identify(py_text_items, timings, content, keys)
+        return cast(
+            list[StatementData],
+            self._inner.py_text_items_to_py_statement_datas(
+                py_text_items,
+                applicable_keys,
+                expected_account_number,
+                date_timezone,
+                fixers,
+                retry_text_order,
+                timings,
+                strict=strict,
+            ),
+        )
+
+    def iter_transactions(
+        self,
+        pdf_file_path: str,
+        date_timezone: str | None = None,
+        keys: list[str] | None = None,
+    ) -> Iterator[Transaction]:
+        """Parse the bank statement PDF incrementally, yielding each transaction
+        once parsed, so that memory use does not grow with the number of
+        transactions. The first applicable configuration is used. Implicit dates
+        and balances are filled from the previous transaction, but other fixers
+        and the quality checks are not applied: use parse to check the statement.
+
+        :param pdf_file_path: Path to the PDF file to be processed
+        :param date_timezone: See parse
+        :param keys: See parse
+        :return: Iterator over the transactions, in statement order
+        :raises ConfigAccessError: A key is neither loaded nor a base config
+        :raises RuntimeError: A transaction could not be completed (e.g., no date
+            or balance), raised when it is reached
+        :raises StatementNotSupported: Statement format is unsupported or not properly
+            identified
+        """
+        py_text_items, content = self._extract(pdf_file_path)
+        applicable_keys = self._identify(py_text_items, content=content, keys=keys)
+        yield from self._inner.py_text_items_to_transaction_stream(
+            py_text_items, applicable_keys, date_timezone
+        )
+
+    def to_json(
+        self,
+        pdf_file_path: str,
+        output_file: str,
+        include_incomplete: bool = False,
+        expected_account_number: str | None = None,
+        date_timezone: str | None = None,
+        fixers: str = "all",
+        retry_text_order: bool = False,
+        keys: list[str] | None = None,
+        strict: bool = False,
+        redact: bool = False,
+        redact_patterns: list[str] | None = None,
+    ) -> str:
+        """Parse the bank statement PDF and write the statement data as JSON, with
+        the config key, account number, balances, start date, errors, warnings,
+        numbers of computed balances and inferred amounts, and transactions with the
+        source of their amount and balance (see Transaction). Dates are ISO-8601
+        (e.g., "2024-01-31"). The file is written atomically, as for
+        StatementData.to_csv.
+
+        :param pdf_file_path: Path to the PDF file to be processed
+        :param output_file: Path to the output JSON file
+        :param include_incomplete: Emit transactions missing a date, amount or
+            balance with nulls instead of skipping them
+        :param expected_account_number: See parse
+        :param date_timezone: See parse
+        :param fixers: See parse
+        :param retry_text_order: See parse
+        :param keys: See parse
+        :param strict: See parse
+        :param redact: Mask all but the last four characters of the account number,
+            after parsing and checking, so that the output can be shared
+        :param redact_patterns: Regex patterns of text to replace with "****" in the
+            transaction descriptions, errors and warnings (e.g., names). Setting them
+            also redacts the account number.
+        :return: The JSON string written to the output file
+        :raises ConfigAccessError: A key is neither loaded nor a base config
+        :raises NoErrorFreeStatementData: Statement format recognised but could be
+            processed without failing quality checks
+        :raises StatementNotSupported: Statement format is unsupported or not properly
+            identified
+        """
+        py_text_items, content = self._extract(pdf_file_path)
+        applicable_keys = self._identify(py_text_items, content=content, keys=keys)
+        json_str: str = self._inner.py_text_items_to_json_py_str(
+            py_text_items,
+            applicable_keys,
+            expected_account_number,
+            date_timezone,
+            fixers,
+            retry_text_order,
+            include_incomplete,
+            strict=strict,
+            redact_patterns=redaction_patterns(redact, redact_patterns),
+        )
+        with atomic_open(output_file) as fh:
+            fh.write(json_str)
+        return json_str
+
+    def to_csv_append(
+        self, pdf_file_path: str, output_csv: str, tolerance: float = 0.0
+    ) -> list[str]:
+        """Parse the bank statement PDF and add its transactions to a combined CSV
+        of several statements, with a "source_file" and "statement_key" column,
+        creating the file if it does not exist. Rows are kept in date order with a
+        single header, and parsing the same PDF again replaces its rows. See
+        transtractor.utils.combined.append_statements_to_csv.
+
+        :param pdf_file_path: Path to the PDF file to be processed
+        :param output_csv: Path to the combined CSV file
+        :param tolerance: Largest difference between the closing balance of a
+            statement and the opening balance of the next that is not a gap
+        :return: Warnings of the balance gaps between consecutive statements in the
+            combined CSV (e.g., a missing month)
+        :raises NoErrorFreeStatementData: See parse
+        :raises StatementNotSupported: See parse
+        :raises ValueError: The existing file is not a combined CSV
+        """
+        sd = self.parse(pdf_file_path)
+        return append_statements_to_csv([sd], output_csv, tolerance)
+
+    def directory_to_csv(
+        self, pdf_dir: str, output_csv: str, tolerance: float = 0.0
+    ) -> list[str]:
+        """Parse all PDFs in a directory and its sub-directories, found as for test,
+        and write their transactions to one combined CSV (see to_csv_append),
+        replacing any existing file. PDFs that cannot be parsed are left out with a
+        warning rather than stopping the export.
+
+        :param pdf_dir: Path to the directory containing the PDF files
+        :param output_csv: Path to the combined CSV file
+        :param tolerance: See to_csv_append
+        :return: Warnings of the PDFs and directories left out, then of the balance
+            gaps between consecutive statements
+        """
+        pdf_files, skipped = find_pdf_files(pdf_dir)
+        warnings = [f"Skipped directory {note}" for note in skipped]
+        sds = []
+        for pdf_file in pdf_files:
+            try:
+                sds.append(self.parse(pdf_file))
+            except Exception as e:
+                warnings.append(f"Left out {pdf_file}: {type(e).__name__}: {e}")
+        return warnings + statements_to_csv(sds, output_csv, tolerance)
+
+    def parse_parts(
+        self,
+        pdf_file_paths: list[str],
+        expected_account_number: str | None = None,
+        date_timezone: str | None = None,
+        fixers: str = "all",
+        retry_text_order: bool = False,
+        keys: list[str] | None = None,
+        strict: bool = False,
+    ) -> StatementData:
+        """Parse a bank statement split across multiple PDF files (e.g., "part 1
+        of 2" and "part 2 of 2") and return a single StatementData object.
+
+        Parts are ordered using "part X of Y" or "page X of Y" markers if present,
+        otherwise in the order supplied.
+
+        :param pdf_file_paths: Paths to the PDF files making up the statement
+        :param expected_account_number: See parse
+        :param date_timezone: See parse
+        :param fixers: See parse
+        :param retry_text_order: See parse
+        :param keys: See parse
+        :param strict: See parse
+        :return: StatementData object representing the parsed bank statement data
+        :raises ConfigAccessError: A key is neither loaded nor a base config
+        :raises NoErrorFreeStatementData: Statement format recognised but could be
+            processed without failing quality checks
+        :raises StatementNotSupported: Statement format is unsupported or not properly
+            identified
+        """
+        started = time.perf_counter()
+        py_text_item_parts = [self._extract(path)[0] for path in pdf_file_paths]
+        py_text_items = self._inner.py_text_item_parts_to_py_text_items(
+            py_text_item_parts
+        )
+        timings = {"text_extraction": time.perf_counter() - started}
+        sd = self._parse_statement(
+            py_text_items,
+            timings,
+            None,
+            expected_account_number,
+            date_timezone,
+            fixers,
+            retry_text_order,
+            keys,
+            strict,
+        )
+        sd.set_filename(", ".join(pdf_file_paths))
         return sd
 
-    def parse_layout(self, layout_file_path: str) -> StatementData:
-        """Parse the bank statement layout string and return a StatementData object.
+    def parse_layout(
+        self,
+        layout_file_path: str,
+        expected_account_number: str | None = None,
+        date_timezone: str | None = None,
+        fixers: str = "all",
+        retry_text_order: bool = False,
+        keys: list[str] | None = None,
+        strict: bool = False,
+    ) -> StatementData:
+        """Parse the bank statement layout file and return a StatementData object.
 
         :param layout_file_path: Path to the layout file to be processed
+        :param expected_account_number: See parse
+        :param date_timezone: See parse
+        :param fixers: See parse
+        :param retry_text_order: See parse
+        :param keys: See parse
+        :param strict: See parse
         :return: StatementData object representing the parsed bank statement data
+        :raises ConfigAccessError: A key is neither loaded nor a base config
+        :raises LayoutParseError: The layout text is invalid
         :raises NoErrorFreeStatementData: Statement format recognised but could be
             processed without failing quality checks
         :raises StatementNotSupported: Statement format is unsupported or not properly
             identified
         """
         py_layout_str = open(layout_file_path, encoding="utf-8").read()
-        py_text_items = self._inner.py_layout_py_str_to_py_text_items(py_layout_str)
-        applicable_keys = self._identify(py_text_items)
+        return self.parse_layout_str(
+            py_layout_str,
+            expected_account_number,
+            date_timezone,
+            fixers,
+            retry_text_order,
+            keys,
+            strict=strict,
+        )
+
+    def parse_layout_str(
+        self,
+        layout_str: str,
+        expected_account_number: str | None = None,
+        date_timezone: str | None = None,
+        fixers: str = "all",
+        retry_text_order: bool = False,
+        keys: list[str] | None = None,
+        strict: bool = False,
+    ) -> StatementData:
+        """Parse bank statement layout text (e.g., from layout_text) and return a
+        StatementData object, without reading or writing files.
+
+        :param layout_str: Layout text to be processed
+        :param expected_account_number: See parse
+        :param date_timezone: See parse
+        :param fixers: See parse
+        :param retry_text_order: See parse
+        :param keys: See parse
+        :param strict: See parse
+        :return: StatementData object representing the parsed bank statement data
+        :raises ConfigAccessError: A key is neither loaded nor a base config
+        :raises LayoutParseError: The layout text is invalid
+        :raises NoErrorFreeStatementData: Statement format recognised but could be
+            processed without failing quality checks
+        :raises StatementNotSupported: Statement format is unsupported or not properly
+            identified
+        """
+        started = time.perf_counter()
+        py_text_items = self._inner.py_layout_py_str_to_py_text_items(layout_str)
+        timings = {"text_extraction": time.perf_counter() - started}
+        return self._parse_statement(
+            py_text_items,
+            timings,
+            None,
+            expected_account_number,
+            date_timezone,
+            fixers,
+            retry_text_order,
+            keys,
+            strict,
+        )
+
+    def parse_layout_all(
+        self,
+        layout_file_path: str,
+        expected_account_number: str | None = None,
+        date_timezone: str | None = None,
+        fixers: str = "all",
+        retry_text_order: bool = False,
+        keys: list[str] | None = None,
+        strict: bool = False,
+    ) -> list[dict]:
+        """Parse the bank statement layout file with every applicable configuration
+        and return all candidate results. See parse_all.
+
+        :param layout_file_path: Path to the layout file to be processed
+        :param expected_account_number: See parse
+        :param date_timezone: See parse
+        :param fixers: See parse
+        :param retry_text_order: See parse
+        :param keys: See parse
+        :param strict: See parse
+        :return: List of candidate dicts, as for parse_all
+        :raises ConfigAccessError: A key is neither loaded nor a base config
+        :raises StatementNotSupported: Statement format is unsupported or not properly
+            identified
+        """
+        py_text_items = self._read_layout(layout_file_path)
+        applicable_keys = self._identify(py_text_items, keys=keys)
+        return cast(
+            list[dict],
+            self._inner.py_text_items_to_py_candidates(
+                py_text_items,
+                applicable_keys,
+                expected_account_number,
+                date_timezone,
+                fixers,
+                retry_text_order,
+                strict=strict,
+            ),
+        )
+
+    def parse_layout_accounts(
+        self,
+        layout_file_path: str,
+        expected_account_number: str | None = None,
+        date_timezone: str | None = None,
+        fixers: str = "all",
+        retry_text_order: bool = False,
+        keys: list[str] | None = None,
+        strict: bool = False,
+    ) -> dict[str, StatementData]:
+        """Parse a bank statement layout file that may contain several accounts and
+        return the StatementData of each account. See parse_accounts.
+
+        :param layout_file_path: Path to the layout file to be processed
+        :param expected_account_number: See parse_accounts
+        :param date_timezone: See parse
+        :param fixers: See parse
+        :param retry_text_order: See parse
+        :param keys: See parse
+        :param strict: See parse
+        :return: Dict of account number to StatementData, as for parse_accounts
+        :raises ConfigAccessError: A key is neither loaded nor a base config
+        :raises NoErrorFreeStatementData: No config gave error-free statement data
+            for every account
+        :raises StatementNotSupported: Statement format is unsupported or not properly
+            identified
+        """
+        return accounts_by_number(
+            self.parse_layout_statements(
+                layout_file_path,
+                expected_account_number,
+                date_timezone,
+                fixers,
+                retry_text_order,
+                keys,
+                strict,
+            )
+        )
+
+    def parse_layout_statements(
+        self,
+        layout_file_path: str,
+        expected_account_number: str | None = None,
+        date_timezone: str | None = None,
+        fixers: str = "all",
+        retry_text_order: bool = False,
+        keys: list[str] | None = None,
+        strict: bool = False,
+    ) -> list[StatementData]:
+        """Parse a layout file that may contain several consecutive statements of an
+        account and return the StatementData of each statement. See parse_statements.
+
+        :param layout_file_path: Path to the layout file to be processed
+        :param expected_account_number: See parse
+        :param date_timezone: See parse
+        :param fixers: See parse
+        :param retry_text_order: See parse
+        :param keys: See parse
+        :param strict: See parse
+        :return: List of StatementData, as for parse_statements
+        :raises ConfigAccessError: A key is neither loaded nor a base config
+        :raises NoErrorFreeStatementData: No config gave error-free statement data
+            for every statement
+        :raises StatementNotSupported: Statement format is unsupported or not properly
+            identified
+        """
+        started = time.perf_counter()
+        py_text_items = self._read_layout(layout_file_path)
+        timings = {"text_extraction": time.perf_counter() - started}
+        return self._parse_sections(
+            py_text_items,
+            timings,
+            None,
+            expected_account_number,
+            date_timezone,
+            fixers,
+            retry_text_order,
+            keys,
+            strict,
+        )
+
+    def iter_layout_transactions(
+        self,
+        layout_file_path: str,
+        date_timezone: str | None = None,
+        keys: list[str] | None = None,
+    ) -> Iterator[Transaction]:
+        """Parse the bank statement layout file incrementally, yielding each
+        transaction once parsed. See iter_transactions.
+
+        :param layout_file_path: Path to the layout file to be processed
+        :param date_timezone: See parse
+        :param keys: See parse
+        :return: Iterator over the transactions, in statement order
+        :raises ConfigAccessError: A key is neither loaded nor a base config
+        :raises RuntimeError: A transaction could not be completed
+        :raises StatementNotSupported: Statement format is unsupported or not properly
+            identified
+        """
+        py_text_items = self._read_layout(layout_file_path)
+        applicable_keys = self._identify(py_text_items, keys=keys)
+        yield from self._inner.py_text_items_to_transaction_stream(
+            py_text_items, applicable_keys, date_timezone
+        )
+
+    def parse_layout_parts(
+        self,
+        layout_file_paths: list[str],
+        expected_account_number: str | None = None,
+        date_timezone: str | None = None,
+        fixers: str = "all",
+        retry_text_order: bool = False,
+        keys: list[str] | None = None,
+        strict: bool = False,
+    ) -> StatementData:
+        """Parse a bank statement split across multiple layout files and return a
+        single StatementData object. See parse_parts for how parts are ordered.
+
+        :param layout_file_paths: Paths to the layout files making up the statement
+        :param expected_account_number: See parse
+        :param date_timezone: See parse
+        :param fixers: See parse
+        :param retry_text_order: See parse
+        :param keys: See parse
+        :param strict: See parse
+        :return: StatementData object representing the parsed bank statement data
+        :raises ConfigAccessError: A key is neither loaded nor a base config
+        :raises NoErrorFreeStatementData: Statement format recognised but could be
+            processed without failing quality checks
+        :raises StatementNotSupported: Statement format is unsupported or not properly
+            identified
+        """
+        started = time.perf_counter()
+        py_text_item_parts = [self._read_layout(path) for path in layout_file_paths]
+        py_text_items = self._inner.py_text_item_parts_to_py_text_items(
+            py_text_item_parts
+        )
+        timings = {"text_extraction": time.perf_counter() - started}
+        return self._parse_statement(
+            py_text_items,
+            timings,
+            None,
+            expected_account_number,
+            date_timezone,
+            fixers,
+            retry_text_order,
+            keys,
+            strict,
+        )
+
+    def parse_csv(
+        self,
+        csv_file_path: str,
+        account_number: str | None = None,
+        opening_balance: float | None = None,
+        closing_balance: float | None = None,
+        columns: dict[str, str] | None = None,
+        date_formats: list[str] | None = None,
+        key: str = "csv",
+    ) -> StatementData:
+        """Read a bank CSV export (e.g., for a period without a PDF statement) and
+        return a StatementData object, with the same fixers and quality checks as
+        for a parsed statement. Each row is a transaction; exports listing the
+        newest transaction first are reversed.
+
+        :param csv_file_path: Path to the CSV file, with a header row
+        :param account_number: Account number of the statement, which is required
+            by the quality checks
+        :param opening_balance: Opening balance. Required if the export has no
+            balance column, otherwise inferred from the first balance and amount
+        :param closing_balance: Closing balance. The last balance by default
+        :param columns: Headers of the "date", "description", "amount" and
+            "balance" columns, if not "Date", "Description", "Amount" and "Balance".
+            Set "balance" to "" for an export without balances.
+        :param date_formats: Names of the date formats of the date column (see
+            the configuration date formats). ["format13", "format4"] (YYYY-MM-DD
+            and DD/MM/YYYY) by default
+        :param key: Key recorded on the statement data
+        :return: StatementData object representing the exported transactions
+        :raises NoErrorFreeStatementData: The statement data fails the quality
+            checks (e.g., the balances do not add up)
+        :raises ValueError: A column is missing or a value cannot be read
+        """
+        with open(csv_file_path, encoding="utf-8") as fh:
+            csv_str = fh.read()
         sd: StatementData = cast(
             StatementData,
-            self._inner.py_text_items_to_py_statement_data(
-                py_text_items, applicable_keys
+            LibParser.csv_py_str_to_py_statement_data(
+                csv_str,
+                columns,
+                date_formats,
+                account_number,
+                opening_balance,
+                closing_balance,
+                key,
             ),
         )
+        sd.set_filename(csv_file_path)
         return sd
 
-    def debug(self, pdf_file_path: str, output_file: str) -> str:
-        """Write a summary of the statement data and quality checks for
-        each statement extraction configuration applied.
+    def try_config(self, json_str: str, file_path: str) -> dict:
+        """Parse a statement with a draft configuration only, to iterate on the
+        draft without loading it. The draft is validated as by load, but is not
+        registered, so the configurations of the parser are unchanged and each call
+        reflects only the given JSON. The statement type is not identified: the
+        draft is assumed to apply.
+
+        :param json_str: JSON string of the draft configuration
+        :param file_path: Path to the PDF file, or to a layout file (see
+            input_kind)
+        :return: Dict with the config "key", "passed" (True if error-free), the
+            "errors" and "warnings" of the statement data, the "config_warnings"
+            (likely mistakes in the draft), "statement_datas" (a StatementData per
+            account section, a single one unless multi_account) and "trace" (the
+            debug summary of each result)
+        :raises ConfigLoadError: The draft configuration is invalid
+        """
+        py_text_items, _ = self._extract(file_path)
+        result = cast(dict, LibParser.try_config(json_str, py_text_items))
+        for sd in result["statement_datas"]:
+            sd.set_filename(file_path)
+        return result
+
+    def verify(
+        self,
+        file_path: str,
+        expected_csv: str,
+        tolerance: float = 0.005,
+        normalise_descriptions: bool = True,
+        keys: list[str] | None = None,
+    ) -> dict:
+        """Parse a statement, including incomplete transactions, and compare its
+        transactions with an expected CSV, e.g., one checked by hand, to test a
+        configuration. The CSV has a header naming any of the "date" (YYYY-MM-DD),
+        "description", "amount" and "balance" columns, in any case and order, as
+        written by StatementData.to_csv. Only the fields with a column are compared.
+        Transactions are matched in order, so a missing row is reported once rather
+        than as mismatches of all rows that follow.
+
+        :param file_path: Path to the PDF file, or to a layout file (see
+            input_kind)
+        :param expected_csv: Path to the expected CSV file
+        :param tolerance: Largest difference between equal amounts or balances
+        :param normalise_descriptions: Compare descriptions ignoring case and runs of
+            whitespace
+        :param keys: See parse
+        :return: Dict with "passed" (True if the transactions are those expected),
+            "expected_count", "actual_count", "missing" and "extra" (lists of dicts
+            with the row "number", from 1, and "transaction"), "mismatched" (list of
+            dicts with the "expected_number" and "actual_number" of the row and its
+            field "mismatches") and "text" (the report as human-readable text)
+        :raises ValueError: The expected CSV cannot be read
+        :raises NoErrorFreeStatementData: See parse
+        :raises StatementNotSupported: See parse
+        """
+        py_text_items, content = self._extract(file_path)
+        applicable_keys = self._identify(py_text_items, content=content, keys=keys)
+        json_str: str = self._inner.py_text_items_to_json_py_str(
+            py_text_items, applicable_keys, include_incomplete=True
+        )
+        with open(expected_csv, encoding="utf-8-sig") as fh:
+            expected_csv_str = fh.read()
+        report_json, text = LibParser.verify_json_py_strs(
+            json_str, expected_csv_str, tolerance, normalise_descriptions
+        )
+        report = cast(dict, json.loads(report_json))
+        report["text"] = text
+        return report
+
+    def lint(self, key: str) -> list[str]:
+        """Check a loaded or base configuration for combinations of values that are
+        each valid but unlikely to parse as intended, e.g., a field in
+        transaction_formats without headers, or account_terms that are all account
+        terms of another configuration. Run it on a new configuration before
+        submitting it.
+
+        :param key: Key of the configuration to check
+        :return: Warnings as "field: message" strings, empty if none
+        :raises ConfigAccessError: The key is neither loaded nor a base config
+        """
+        return self._inner.lint_config(key, get_base_config_db())
+
+    def debug(
+        self,
+        pdf_file_path: str,
+        output_file: str,
+        retry_text_order: bool = False,
+        sources: bool = False,
+        max_size: int | None = None,
+        max_section_lines: int = 10000,
+        redact: bool = False,
+        redact_patterns: list[str] | None = None,
+    ) -> str:
+        """Write a summary of the statement data, quality checks and parse stage
+        timings for each statement extraction configuration applied. The output is
+        streamed to the file as each result is formatted, and written atomically.
 
         :param pdf_file_path: Path to the PDF file to be processed
         :param output_file: Path to the output debug text file
+        :param retry_text_order: Retry configs whose fix_text_order reordering gives
+            errors in the original text order (see parse)
+        :param sources: List the text items (page, coordinates and text) read into
+            each statement field and transaction, to trace mis-parses. Off by default
+            as the items are copied while parsing.
+        :param max_size: Maximum size of the output in bytes, after which it is
+            truncated with a marker. Unlimited by default.
+        :param max_section_lines: Maximum number of lines of each section of a result
+            (transactions, errors, page coverage, sources and warnings), after which
+            the section is truncated with a marker
+        :param redact: Mask all but the last four characters of the account number
+            of each result, also in the errors, warnings and sources, so that the
+            output can be shared (e.g., in an issue). Runs of six or more digits in
+            the nearest text of unidentified statements are masked too
+        :param redact_patterns: See to_json
         :return: The debug string written to the output file
+        :raises PdfExtractError: The text of the PDF cannot be extracted
+        :raises StatementNotSupported: Statement format is unsupported or not properly
+            identified. The account terms found and missing for each loaded
+            configuration, with the text nearest to each missing term, are written to
+            the output file first.
+        """
+        started = time.perf_counter()
+        py_text_items, content = self._extract(pdf_file_path)
+        timings = {"text_extraction": time.perf_counter() - started}
+        try:
+            applicable_keys = self._identify(py_text_items, timings, content)
+        except StatementNotSupported:
+            self._inner.py_text_items_to_debug_file(
+                py_text_items, [], output_file, max_bytes=max_size
+            )
+            raise
+        self._inner.py_text_items_to_debug_file(
+            py_text_items,
+            applicable_keys,
+            output_file,
+            retry_text_order,
+            timings,
+            sources,
+            max_size,
+            max_section_lines,
+            redaction_patterns(redact, redact_patterns),
+        )
+        with open(output_file, encoding="utf-8") as fh:
+            return fh.read()
+
+    def debug_dict(
+        self,
+        pdf_file_path: str,
+        retry_text_order: bool = False,
+        redact: bool = False,
+        redact_patterns: list[str] | None = None,
+    ) -> dict:
+        """Return the debug report as a dictionary, for assertions in tests. The
+        report holds the same results as the debug text output.
+
+        :param pdf_file_path: Path to the PDF file to be processed
+        :param retry_text_order: Retry configs whose fix_text_order reordering gives
+            errors in the original text order (see parse)
+        :param redact: See debug
+        :param redact_patterns: See to_json
+        :return: Dictionary with "error" (None unless the text items could not be
+            parsed) and "results", a list with a dictionary per configuration applied
+            with "key", "account_number", "start_date" and "end_date" (YYYY-MM-DD),
+            "opening_balance", "closing_balance", "transaction_count", "errors",
+            "warnings" and "timings_ms" (milliseconds by parse stage)
+        :raises PdfExtractError: The text of the PDF cannot be extracted
         :raises StatementNotSupported: Statement format is unsupported or not properly
             identified
         """
-        py_text_items = pdf_to_text_items(pdf_file_path)
-        applicable_keys = self._identify(py_text_items)
-        result = self._inner.py_text_items_to_debug_py_str(
-            py_text_items, applicable_keys
+        started = time.perf_counter()
+        py_text_items, content = self._extract(pdf_file_path)
+        timings = {"text_extraction": time.perf_counter() - started}
+        applicable_keys = self._identify(py_text_items, timings, content)
+        json_str = self._inner.py_text_items_to_debug_json_py_str(
+            py_text_items,
+            applicable_keys,
+            retry_text_order,
+            timings,
+            redaction_patterns(redact, redact_patterns),
         )
-        with open(output_file, "w", encoding="utf-8") as fh:
-            fh.write(result)
-        return result
+        return json.loads(json_str)
+
+    def explain(
+        self,
+        pdf_file_path: str,
+        output_file: str,
+        json_file: str | None = None,
+        retry_text_order: bool = False,
+    ) -> str:
+        """Write a report explaining the parse of the PDF with every applicable
+        statement extraction configuration: the account terms found and missing for
+        each configuration with any term found, then for each configuration a
+        summary, the transaction column bounds, the transaction rows dropped for
+        missing fields, the fixer warnings and checker errors, the page coverage and
+        the parse stage timings. Unlike debug, an unidentified statement is reported
+        rather than raising StatementNotSupported.
+
+        :param pdf_file_path: Path to the PDF file to be processed
+        :param output_file: Path to the output report text file
+        :param json_file: Path to write the report as JSON, if provided
+        :param retry_text_order: Retry configs whose fix_text_order reordering gives
+            errors in the original text order (see parse)
+        :return: The report string written to the output file
+        """
+        started = time.perf_counter()
+        py_text_items, content = self._extract(pdf_file_path)
+        timings = {"text_extraction": time.perf_counter() - started}
+        try:
+            applicable_keys = self._identify(py_text_items, timings, content)
+        except StatementNotSupported:
+            applicable_keys = []
+        text, json_str = self._inner.py_text_items_to_explain_py_strs(
+            py_text_items, applicable_keys, retry_text_order, timings
+        )
+        with atomic_open(output_file) as fh:
+            fh.write(text)
+        if json_file is not None:
+            with atomic_open(json_file) as fh:
+                fh.write(json_str)
+        return text
 
-    def layout(self, pdf_file_path: str, output_file: str, y_bin=0.0, x_gap=0.0) -> str:
-        """Extract, write and return a text layout representation of the PDF page.
+    def explain_terms(self, layout_file_path: str) -> str:
+        """Report, for each loaded configuration, the account terms found and missing
+        in a layout file, with the text nearest to each missing term (e.g., the term
+        in a different case, or with words split or joined differently by the text
+        extraction), to see why a new configuration does not identify a statement.
+
+        :param layout_file_path: Path to the layout file (see layout)
+        :return: The report, most terms found first
+        :raises LayoutParseError: The layout text is invalid
+        """
+        return self._inner.explain_file(layout_file_path)
+
+    def layout(
+        self,
+        pdf_file_path: str,
+        output_file: str,
+        y_bin=0.0,
+        x_gap=0.0,
+        header=True,
+        timestamp=True,
+    ) -> str:
+        """Extract, write and return a text layout representation of the PDF page
+        (see layout_text).
 
         :param pdf_file_path: Path to the PDF file to be processed
         :param y_bin: Y coordinate bin size for sorting/merging text items
         :param x_gap: X coordinate gap size in number of characters for merging text
             items
+        :param header: Start the layout with "#" comment lines recording the crate
+            version, generation time, y_bin and x_gap, most common line height and
+            text item counts. Comment lines are ignored when the layout is parsed.
+        :param timestamp: Include the generation time in the header. Set to False
+            for byte-stable output (e.g., test fixtures).
         :return: A string representing the text layout of the page
 
         Note: The values of y_bin and x_gap are same same as those used for the
         "fix_text_order" parameter in the configuration JSON files.
         """
-        py_text_items = pdf_to_text_items(pdf_file_path)
-        layout_str: str = self._inner.py_text_items_to_layout_py_str(
-            py_text_items, y_bin, x_gap
-        )
-        with open(output_file, "w", encoding="utf-8") as fh:
+        layout_str = self.layout_text(pdf_file_path, y_bin, x_gap, header, timestamp)
+        with atomic_open(output_file) as fh:
             fh.write(layout_str)
         return layout_str
 
+    def layout_text(
+        self,
+        pdf_file_path: str,
+        y_bin=0.0,
+        x_gap=0.0,
+        header=True,
+        timestamp=True,
+    ) -> str:
+        """Extract and return a text layout representation of the PDF page without
+        writing a file, e.g., to cache extractions or build test fixtures in
+        Python. Parse the layout text with parse_layout_str.
+
+        :param pdf_file_path: Path to the PDF file to be processed
+        :param y_bin: See layout
+        :param x_gap: See layout
+        :param header: See layout
+        :param timestamp: See layout
+        :return: A string representing the text layout of the page
+        """
+        py_text_items, _ = self._extract(pdf_file_path)
+        return cast(
+            str,
+            self._inner.py_text_items_to_layout_py_str(
+                py_text_items, y_bin, x_gap, header, timestamp
+            ),
+        )
+
     def load(self, json_file_path: str) -> None:
         """Load a custom parsing configuration from a JSON file.
 
         Configurations loaded via this method will be registered in the
         internal configuration database and will overwrite any existing
-        configuration with the same key.
+        configuration with the same key. The configs identified for cached PDFs
+        are forgotten, but their text items are kept.
 
         :param json_file_path: Path to the JSON configuration file
         :return: None
@@ -147,12 +1258,130 @@ class Parser:
         """
         self._inner.import_config_from_file(json_file_path)
 
+    def set_categories(
+        self,
+        categories: list[tuple[str, str]] | str | None,
+        case_insensitive: bool = True,
+    ) -> None:
+        """Tag the transactions of every subsequent parse with a category by
+        regex patterns of their descriptions, e.g., to tell groceries from fuel.
+        Rules are tried in order and the first matching pattern sets the category,
+        so list more specific rules first. Untagged transactions have an empty
+        category. The categories are set after the quality checks, from the
+        descriptions as parsed, and are exported with the "category" field of
+        StatementData.to_csv and to_pandas_dict.
+
+        :param categories: List of (category, pattern) pairs, a JSON string of a
+            list of [category, pattern] pairs, or None to stop tagging
+        :param case_insensitive: Match the patterns ignoring case
+        :raises ValueError: The JSON string or a pattern is invalid
+
+        Example usage::
+
+            parser.set_categories([
+                ("salary", r"^SALARY"),
+                ("groceries", r"WOOLWORTHS|COLES"),
+            ])
+            statement_data = parser.parse('statement.pdf')
+            statement_data.to_csv('tagged.csv', fields=['date', 'amount', 'category'])
+        """
+        if categories is not None and not isinstance(categories, str):
+            categories = json.dumps([list(rule) for rule in categories])
+        self._inner.set_categorizer_from_json_str(categories, case_insensitive)
+        self._categories = (categories, case_insensitive)
+
+    def _worker_state(self) -> dict:
+        """Return the state of the parser to recreate in the worker processes of
+        test_report (see utils.testing._init_worker): its options, the JSON of every
+        config of its database, however imported, and its category rules.
+
+        :raises ConfigAccessError: A config cannot be written as JSON, or formats are
+            registered at runtime, which worker processes would not have
+        """
+        registered_formats = LibParser.registered_formats()
+        if registered_formats:
+            raise ConfigAccessError(
+                "Formats registered at runtime cannot be sent to worker processes: "
+                + ", ".join(registered_formats)
+            )
+        return {
+            "locale_filter": self._locale_filter,
+            "match_threshold": self._match_threshold,
+            "configs": [
+                self._inner.get_config_json(config["key"])
+                for config in self._inner.list_configs()
+            ],
+            "categories": self._categories,
+        }
+
+    def catalog(self) -> dict[str, dict[str, list[dict]]]:
+        """Return the supported statement configurations, including any loaded
+        custom configurations, for display to end users.
+
+        :return: Dict of country code (e.g., "AU") to a dict of bank name to a list
+            of dicts with the "account_type", "key", "status" and
+            "account_examples" of each configuration. Countries and banks are
+            sorted alphabetically, and configurations by account type then key
+        :raises ConfigAccessError: A configuration file cannot be read
+        """
+        return cast(
+            dict[str, dict[str, list[dict]]],
+            self._inner.catalog(get_base_config_db()),
+        )
+
+    def list_configs(self) -> list[dict]:
+        """Return the supported statement configurations, including any loaded
+        custom configurations, e.g., to show a list of supported banks.
+
+        :return: List of dicts, sorted by key, with the "key", "bank_name",
+            "account_type", "account_examples", "country" (e.g., "AU", from the key
+            prefix), "status" and "source" of each configuration. The source is
+            "embedded" for configurations packaged with the library and "user" for
+            loaded configurations, which replace packaged ones with the same key
+        :raises ConfigAccessError: A configuration file cannot be read
+        """
+        return cast(list[dict], self._inner.list_configs(get_base_config_db()))
+
+    def get_config_json(self, key: str) -> str:
+        """Return a supported statement configuration as JSON, with every field set
+        including those left at their defaults.
+
+        :param key: Key of a loaded or packaged configuration
+        :return: Pretty-printed configuration JSON, which can be loaded as a
+            configuration file
+        :raises ConfigAccessError: The key is neither loaded nor a base config
+        """
+        return self._inner.get_config_json(key, get_base_config_db())
+
+    def supported_statements(self) -> str:
+        """Return a text listing of the supported statements (see catalog), with a
+        line per country and a line per bank listing its account types, e.g.
+        "  Commonwealth Bank of Australia (Credit Card, Loan, Savings)".
+
+        :return: The supported statements text
+        :raises ConfigAccessError: A configuration file cannot be read
+        """
+        return self._inner.catalog_str(get_base_config_db())
+
+    def cache_stats(self) -> dict[str, int]:
+        """Return statistics of the text item cache for monitoring.
+
+        :return: Dict with the cache "capacity", number of cached PDFs ("size") and
+            the number of lookups that found ("hits") or did not find ("misses")
+            cached text items or configs
+        """
+        return self._inner.cache_stats()
+
+    def clear_cache(self) -> None:
+        """Remove all PDFs from the text item cache, and all prepared configs."""
+        self._inner.clear_cache()
+
     def test(
         self, pdf_dir: str, output_file: str = "", log_level: str = "INFO"
     ) -> None:
         """Try to parse all PDFs in a given directory and sub-directories
         using the current parser configuration database. Optionally outputs
-        a CSV file summarising the test results.
+        a CSV file summarising the test results. See test_report for the summary.
 
         :param pdf_dir: Path to the directory containing PDF files to be tested
         :param output_file: Optional path to output CSV file for test results
@@ -161,4 +1390,36 @@ class Parser:
 
         Note: Set log_level to "WARNING" or higher to suppress terminal output.
         """
-        run_test_protocol(pdf_dir, self, output_file, log_level)
+        self.test_report(pdf_dir, output_file, log_level)
+
+    def test_report(
+        self,
+        pdf_dir: str,
+        output_file: str = "",
+        log_level: str = "INFO",
+        max_workers: int | None = None,
+        progress: ProgressCallback | None = None,
+    ) -> DirectoryReport:
+        """Test all PDFs in a given directory and sub-directories as for test, in
+        parallel worker processes, and return a report of the results. Each worker
+        has its own parser with the default configurations, the configurations
+        loaded into this parser and its categories. The report gives the number of
+        passes and failures of each config key, the total and average parse time
+        and the files that matched no config, and its summary is logged at the end.
+
+        :param pdf_dir: Path to the directory containing PDF files to be tested
+        :param output_file: Optional path to output CSV file for test results
+        :param log_level: Logging level for test output (e.g., "INFO", "WARNING")
+        :param max_workers: Maximum number of worker processes. Defaults to the
+            number of CPUs. Set to 1 to test the files one at a time with this
+            parser, without worker processes.
+        :param progress: Callback called with a ProgressEvent (file path, index,
+            total number of files, result and elapsed time) once each file is
+            tested, in place of logging the file, e.g., to update a progress bar in a
+            GUI. Returning False cancels the run, and the report has the results of
+            the files tested so far.
+        :return: Report of the results, in file path order
+        """
+        return run_test_protocol(
+            pdf_dir, self, output_file, log_level, max_workers, progress
+        )
@@ -2,9 +2,27 @@
 subsequent processing in Python."""
 
 import csv
+import re
 
+from ..utils.atomic import atomic_open
+from ..utils.corrections import apply_csv_corrections
+from ..utils.diff import diff
+from ..utils.reconcile import reconcile
+from ..utils.redact import mask_account_number, redact_description, redaction_patterns
 from .transaction import Transaction
 
+# Statement-level fields, repeated on every transaction row when exported
+STATEMENT_FIELDS = {
+    "key",
+    "filename",
+    "account_number",
+    "opening_balance",
+    "closing_balance",
+    "start_date",
+    "end_date",
+    "currency",
+}
+
 
 def validate_fields(fields: list[str]) -> None:
     """Validate that the provided fields are valid Transaction attributes.
@@ -20,9 +38,12 @@ def validate_fields(fields: list[str]) -> None:
         "description",
         "amount",
         "balance",
-        "key",
-        "filename",
-        "account_number",
+        "timestamp",
+        "amount_source",
+        "balance_source",
+        "description_source",
+        "category",
+        *STATEMENT_FIELDS,
     }
     for field in fields:
         if field not in valid_fields:
@@ -48,6 +69,7 @@ class StatementData:
         self._filename = ""
         self._account_number = None
         self._transactions = []
+        self._metadata = {}
 
         # Use setters to enforce types
         self.set_key(key)
@@ -82,6 +104,24 @@ class StatementData:
         """Get the list of transactions."""
         return self._transactions
 
+    @property
+    def metadata(self) -> dict:
+        """Get parse metadata. Contains 'warnings' (list of warning strings),
+        'opening_balance', 'closing_balance', 'start_date' and 'end_date' (statement
+        values, None if not set), 'page_coverage' (list of dicts with 'page',
+        'text_items', 'consumed_items' and 'transactions' for each page),
+        'config_status' (status of the matched config: 'None', 'Limited', 'Full' or
+        'Example'), 'no_transactions_declared' (the statement states that it has
+        no transactions), 'coordinate_scale' (detected scale of the text coordinates
+        relative to PDF points), 'currency' (currency symbol of the config, else the
+        most common currency symbol of the document, None if not found),
+        'minimum_payment' and 'payment_due_date' (credit card statements only, else
+        None), 'balances_computed' and 'amounts_inferred' (number of transaction
+        balances computed and amount signs inferred rather than parsed, see
+        Transaction) and 'timings' (dict of the duration in seconds of each parse stage,
+        and the 'total')."""
+        return self._metadata
+
     def set_key(self, key: str) -> None:
         """Set the key for the statement data.
 
@@ -117,6 +157,17 @@ class StatementData:
             )
         self._account_number = account_number
 
+    def set_metadata(self, metadata: dict) -> None:
+        """Set the parse metadata for the statement data.
+
+        :param metadata: Parse metadata dictionary
+        :type metadata: dict
+        :raises TypeError: If metadata is not a dict
+        """
+        if not isinstance(metadata, dict):
+            raise TypeError(f"metadata must be a dict, got {type(metadata).__name__}")
+        self._metadata = metadata
+
     def set_transactions(self, transactions: list[Transaction]) -> None:
         """Set the transactions for the statement data.
 
@@ -138,6 +189,48 @@ class StatementData:
 
         self._transactions = transactions
 
+    def _statement_field(self, field: str):
+        """Get the value of a statement-level field. The statement balances and start
+        date are read from the metadata, and are None if not set."""
+        if field in {"key", "filename", "account_number"}:
+            return getattr(self, f"_{field}", None)
+        return self._metadata.get(field)
+
+    def _rows(
+        self,
+        fields: tuple[str, ...] | list[str],
+        redact: bool,
+        redact_patterns: list[str] | None,
+    ) -> list[list]:
+        """Get the values of the fields for each transaction, redacted if requested
+        (see to_csv).
+
+        :raises ValueError: A redaction pattern is not a valid regex
+        """
+        patterns = redaction_patterns(redact, redact_patterns)
+        compiled = []
+        for pattern in patterns or []:
+            try:
+                compiled.append(re.compile(pattern))
+            except re.error as e:
+                raise ValueError(f"Invalid redaction pattern '{pattern}': {e}") from e
+        rows = []
+        for transaction in self._transactions:
+            row = []
+            for field in fields:
+                if field in STATEMENT_FIELDS:
+                    value = self._statement_field(field)
+                else:
+                    value = getattr(transaction, field, None)
+                if patterns is not None and isinstance(value, str):
+                    if field == "account_number":
+                        value = mask_account_number(value)
+                    elif field == "description":
+                        value = redact_description(value, compiled)
+                row.append(value)
+            rows.append(row)
+        return rows
+
     def to_csv(
         self,
         file_path: str,
@@ -147,16 +240,30 @@ class StatementData:
             "amount",
             "balance",
         ),
+        redact: bool = False,
+        redact_patterns: list[str] | None = None,
     ) -> None:
-        """Export the statement data to a CSV file.
+        """Export the statement data to a CSV file. The file is written atomically:
+        if writing fails, no partial file is left and any existing file is unchanged.
 
         :param file_path: Path to the output CSV file
         :type file_path: str
         :param fields: Fields to include in the CSV. Defaults to
             ('date', 'description', 'amount', 'balance'). Valid fields are:
             'date', 'date_index', 'description', 'amount', 'balance',
-            'key', 'filename', 'account_number'.
+            'timestamp', 'category', 'key', 'filename', 'account_number',
+            'opening_balance', 'closing_balance', 'start_date', 'end_date',
+            'currency'. Statement-level fields are repeated on every row, and written
+            as empty values if not set. 'category' is empty for transactions not
+            tagged (see Parser.set_categories).
         :type fields: Union[tuple[str, ...], list[str]]
+        :param redact: Mask all but the last four characters of the account number
+            (e.g., "****5678"), so that the file can be shared
+        :type redact: bool
+        :param redact_patterns: Regex patterns of text to replace with "****" in the
+            descriptions (e.g., names). Setting them also redacts the account number.
+        :type redact_patterns: list[str] | None
+        :raises ValueError: A redaction pattern is not a valid regex
 
         Example usage::
 
@@ -169,24 +276,25 @@ class StatementData:
                 fields=['date', 'date_index', 'description', 'amount',
                         'balance', 'key', 'filename', 'account_number']
             )
+
+            # Export with the statement details for reconciliation
+            statement_data.to_csv(
+                'reconcile.csv',
+                fields=['key', 'account_number', 'start_date', 'opening_balance',
+                        'closing_balance', 'date', 'description', 'amount',
+                        'balance']
+            )
         """
         # Validate fields
         validate_fields(list(fields))
+        rows = self._rows(fields, redact, redact_patterns)
 
-        with open(file_path, mode="w", newline="", encoding="utf-8") as csvfile:
+        with atomic_open(file_path, newline="") as csvfile:
             writer = csv.writer(csvfile)
             # Write header
             writer.writerow(fields)
             # Write transaction data
-            for transaction in self._transactions:
-                row = []
-                for field in fields:
-                    if field in {"key", "filename", "account_number"}:
-                        value = getattr(self, f"_{field}", None)
-                    else:
-                        value = getattr(transaction, field, None)
-                    row.append(value)
-                writer.writerow(row)
+            writer.writerows(rows)
 
     def to_pandas_dict(
         self,
@@ -196,11 +304,15 @@ class StatementData:
             "amount",
             "balance",
         ),
+        redact: bool = False,
+        redact_patterns: list[str] | None = None,
     ) -> dict[str, list]:
         """Convert the statement data to a dictionary suitable for pandas DataFrame.
 
         :param fields: Fields to include in the dictionary. Defaults to
             ('date', 'description', 'amount', 'balance').
+        :param redact: See to_csv
+        :param redact_patterns: See to_csv
         :return: Dictionary with keys as field names and values as lists of field values
         :rtype: dict[str, list]
 
@@ -219,12 +331,80 @@ class StatementData:
         validate_fields(list(fields))
         data_dict = {field: [] for field in fields}
 
-        for transaction in self._transactions:
-            for field in fields:
-                if field in {"key", "filename", "account_number"}:
-                    value = getattr(self, f"_{field}", None)
-                else:
-                    value = getattr(transaction, field, None)
+        for row in self._rows(fields, redact, redact_patterns):
+            for field, value in zip(fields, row):
                 data_dict[field].append(value)
 
         return data_dict
+
+    def reconcile(
+        self,
+        csv_path: str,
+        columns: dict[str, str] | None = None,
+        date_format: str = "%Y-%m-%d",
+        date_tolerance_days: int = 1,
+    ) -> dict[str, list]:
+        """Reconcile the transactions against an external transactions CSV.
+
+        Transactions are matched on exact amount with dates within
+        date_tolerance_days of each other. Duplicate amounts are matched in
+        statement order to the closest date, then the earliest CSV row.
+
+        :param csv_path: Path to the external CSV file
+        :type csv_path: str
+        :param columns: Mapping of 'date', 'description' and 'amount' to the
+            column names in the CSV. Defaults to the same names.
+        :type columns: dict[str, str] | None
+        :param date_format: strptime format of the CSV date column
+        :type date_format: str
+        :param date_tolerance_days: Maximum difference in days between matched dates
+        :type date_tolerance_days: int
+        :return: Dictionary with 'matched', 'statement_only' and 'external_only'
+            lists. Matched entries hold 'statement' and 'external' transactions.
+        :rtype: dict[str, list]
+
+        Example usage::
+
+            report = statement_data.reconcile(
+                'bank_export.csv',
+                columns={'date': 'Posted', 'description': 'Details', 'amount': 'Value'},
+                date_format='%d/%m/%Y',
+            )
+            print(len(report['statement_only']))
+
+        Use transtractor.utils.reconcile.reconciliation_to_csv and
+        reconciliation_to_text to write the report to a CSV or text.
+        """
+        return reconcile(self, csv_path, columns, date_format, date_tolerance_days)
+
+    def diff(self, other: "StatementData") -> dict[str, list]:
+        """Compare with another parse of the same statement, e.g., made with a later
+        version of the package. See transtractor.utils.diff.diff for the result,
+        and diff_to_text to format it.
+
+        :param other: The new parse
+        :return: Dict of the changes from this parse to the other
+        """
+        return diff(self, other)
+
+    def apply_csv_corrections(self, csv_path: str) -> dict:
+        """Apply the corrections of an edited CSV export (see to_csv) to the
+        transactions, without re-parsing the statement. The corrected values are
+        flagged as "corrected" in the description_source, amount_source and
+        balance_source of the transactions. See
+        transtractor.utils.corrections.apply_csv_corrections for the matching of the
+        CSV rows and the result.
+
+        :param csv_path: Path to the corrections CSV file
+        :return: Dict summarising the corrections applied, the CSV rows not matched
+            and the check errors added or removed
+
+        Example usage::
+
+            statement_data.to_csv('review.csv', fields=['date', 'date_index',
+                                  'description', 'amount', 'balance'])
+            # ... edit review.csv ...
+            summary = statement_data.apply_csv_corrections('review.csv')
+            statement_data.to_csv('corrected.csv')
+        """
+        return apply_csv_corrections(self, csv_path)
@@ -14,6 +14,11 @@ class Transaction:
     description: str
     amount: float
     balance: float
+    timestamp: int | None
+    amount_source: str
+    balance_source: str
+    description_source: str
+    category: str
 
     def __init__(
         self,
@@ -22,6 +27,11 @@ class Transaction:
         description: str,
         amount: float,
         balance: float,
+        timestamp: int | None = None,
+        amount_source: str = "parsed",
+        balance_source: str = "parsed",
+        description_source: str = "parsed",
+        category: str = "",
     ):
         """Initialize a Transaction.
 
@@ -30,13 +40,32 @@ class Transaction:
         :param description: Transaction description
         :param amount: Transaction amount (will be rounded to 2 decimal places)
         :param balance: Account balance (will be rounded to 2 decimal places)
+        :param timestamp: Milliseconds since epoch of midnight on the transaction
+            date in the statement's date timezone. Defaults to date if it is an int.
+        :param amount_source: "parsed" if the amount is as read from the statement,
+            "inferred" if its sign was reversed to agree with the balances, or
+            "corrected" if manually corrected (see StatementData.apply_csv_corrections)
+        :param balance_source: "parsed" if the balance is as read from the statement,
+            "computed" from the previous balance and the amount (e.g., credit card
+            statements without balances), or "corrected" if manually corrected
+        :param description_source: "parsed" if the description is as read from the
+            statement, or "corrected" if manually corrected
+        :param category: Category of the first matching rule set by
+            Parser.set_categories, or "" if untagged
         """
         if isinstance(date, int):
             # Convert milliseconds since epoch to date
             self.date = datetime.fromtimestamp(date / 1000.0).date()
+            if timestamp is None:
+                timestamp = date
         else:
             self.date = date
+        self.timestamp = timestamp
         self.date_index = date_index
         self.description = description
         self.amount = round(amount, 2)
         self.balance = round(balance, 2)
+        self.amount_source = amount_source
+        self.balance_source = balance_source
+        self.description_source = description_source
+        self.category = category
@@ -42,6 +42,18 @@ class LibConfigDB:
         :raises ConfigAccessError: If the configuration key is not found
         """
 
+    def get_config_json(self, key: str) -> str:
+        """
+        Get the JSON of a registered config with every field set, including those
+        left at their defaults.
+
+        :param key: The configuration key to retrieve
+        :type key: str
+        :returns: Pretty-printed config JSON
+        :rtype: str
+        :raises ConfigAccessError: If the configuration key is not found
+        """
+
     def get_account_terms(self, key: str) -> list[str]:
         """
         Get list of all account_terms from the registered config by key.
@@ -53,6 +65,17 @@ class LibConfigDB:
         :raises ConfigAccessError: If the configuration key is not found
         """
 
+    def get_terms_case_insensitive(self, key: str) -> bool:
+        """
+        Get whether the terms of the registered config are matched ignoring case.
+
+        :param key: The configuration key
+        :type key: str
+        :returns: True if terms are matched ignoring case
+        :rtype: bool
+        :raises ConfigAccessError: If the configuration key is not found
+        """
+
     def get_all_config_keys(self) -> list[str]:
         """
         Get list of all registered config keys.
@@ -71,16 +94,136 @@ class LibConfigDB:
         :rtype: bool
         """
 
+    def list_configs(self) -> list[dict]:
+        """
+        Get the registered configs, sorted by key.
+
+        :returns: List of dicts with "key", "bank_name", "account_type",
+            "account_examples", "country" (from the key prefix), "status" and
+            "source" ("embedded")
+        :rtype: list[dict]
+        :raises ConfigAccessError: If a configuration cannot be loaded
+        """
+
+    def catalog(self) -> dict[str, dict[str, list[dict]]]:
+        """
+        Get the catalog of registered configs, grouped by country code then bank
+        name, with sorted keys.
+
+        :returns: Dict of country code to a dict of bank name to a list of dicts
+            with "account_type", "key", "status" and "account_examples"
+        :rtype: dict[str, dict[str, list[dict]]]
+        :raises ConfigAccessError: If a configuration cannot be loaded
+        """
+
+    def catalog_str(self) -> str:
+        """
+        Get the catalog of registered configs as text, with a line per country and
+        a line per bank listing its account types.
+
+        :returns: Catalog text
+        :rtype: str
+        :raises ConfigAccessError: If a configuration cannot be loaded
+        """
+
+    def lint_config(self, key: str) -> list[str]:
+        """
+        Get the warnings of the cross-field checks of a registered config, and of
+        its account terms against the other registered configs.
+
+        :param key: The configuration key to check
+        :type key: str
+        :returns: Warnings as "field: message" strings
+        :rtype: list[str]
+        :raises ConfigAccessError: If the key is not found
+        """
+
 class LibParser:
     """Parser for extracting statement data from text items."""
 
-    def __init__(self) -> None:
-        """Create a new LibParser instance."""
+    def __init__(
+        self,
+        cache_capacity: int = 0,
+        locale_filter: bool = False,
+        match_threshold: float = 1.0,
+    ) -> None:
+        """
+        Create a new LibParser instance.
+
+        :param cache_capacity: Number of documents whose text items and applicable
+            config keys are cached, keyed by content hash. 0 disables the cache
+        :type cache_capacity: int
+        :param locale_filter: Skip configs of countries whose statements are in a
+            different script from the document when identifying it
+        :type locale_filter: bool
+        :param match_threshold: Fraction of the account terms of a config that must
+            be found to identify it, above 0.0 and at most 1.0. Identified configs
+            are ranked by the fraction found
+        :type match_threshold: float
+        :raises ValueError: match_threshold is out of range
+        """
+
+    def set_cache_capacity(self, capacity: int) -> None:
+        """
+        Set the cache capacity, evicting the least recently used documents if
+        needed. 0 disables the cache.
+
+        :param capacity: Maximum number of cached documents
+        :type capacity: int
+        """
+
+    def cache_stats(self) -> dict[str, int]:
+        """
+        Return the cache "capacity", number of cached documents ("size") and the
+        number of lookups that "hits" and "misses" the cache.
+
+        :returns: Cache statistics
+        :rtype: dict[str, int]
+        """
+
+    def clear_cache(self) -> None:
+        """Remove all cached documents and prepared configs."""
+
+    def get_cached_py_text_items(self, content: bytes) -> list[dict] | None:
+        """
+        Return the cached text items of the document with the given content.
+
+        :param content: Content of the document file
+        :type content: bytes
+        :returns: List of text item dictionaries, or None if not cached
+        :rtype: list[dict] | None
+        """
+
+    def cache_py_text_items(self, content: bytes, py_text_items: list[dict]) -> None:
+        """
+        Cache the text items extracted from the document with the given content.
+        Ignored if the cache is disabled.
+
+        :param content: Content of the document file
+        :type content: bytes
+        :param py_text_items: List of text item dictionaries
+        :type py_text_items: list[dict]
+        """
+
+    def set_categorizer_from_json_str(
+        self, json_str: str | None, case_insensitive: bool = True
+    ) -> None:
+        """
+        Set the categorizer applied to the transactions of every parse, or remove
+        it if None. Rules are tried in order, and the first whose pattern matches
+        the description sets the category.
+
+        :param json_str: JSON array of [category, pattern] pairs, or None
+        :type json_str: str | None
+        :param case_insensitive: Match the patterns ignoring case
+        :type case_insensitive: bool
+        :raises ValueError: The JSON or a pattern is invalid
+        """
 
     def import_config_from_json_str(self, py_json_str: str) -> None:
         """
         Import JSON configuration string into the parser database and update the
-        StatementTyper.
+        StatementTyper. Clears the cache.
 
         :param py_json_str: JSON string containing the configuration
         :type py_json_str: str
@@ -100,33 +243,140 @@ class LibParser:
     def import_config_from_file(self, py_file_path: str) -> None:
         """
         Import JSON configuration file into the parser database and update the
-        StatementTyper.
+        StatementTyper. Clears the cache.
 
         :param py_file_path: Path to the JSON configuration file
         :type py_file_path: str
         :raises ConfigLoadError: If the configuration file cannot be loaded
         """
 
-    def add_account_terms(self, key: str, terms: list[str]) -> None:
+    def add_account_terms(
+        self, key: str, terms: list[str], terms_case_insensitive: bool = False
+    ) -> None:
         """
-        Add account terms to the StatementTyper for a given config key.
+        Add account terms to the StatementTyper for a given config key. Clears the
+        cache.
 
         :param key: The configuration key
         :type key: str
         :param terms: List of account term strings
         :type terms: list[str]
+        :param terms_case_insensitive: Match the terms ignoring case
+        :type terms_case_insensitive: bool
         """
 
-    def get_applicable_config_keys(self, py_text_items: list[dict]) -> list[str]:
+    def py_text_items_to_py_locale_profile(self, py_text_items: list[dict]) -> dict:
         """
-        Return a list of keys applicable to the provided text items.
+        Return the locale profile of the text items.
 
         :param py_text_items: List of text item dictionaries
         :type py_text_items: list[dict]
+        :return: Dict with the letter counts of each script ("scripts"), the
+            document "script" (None if uncertain), the counts of
+            "currency_symbols", and "decimal_commas" and "decimal_points"
+        :rtype: dict
+        """
+
+    def get_applicable_config_keys(
+        self, py_text_items: list[dict], content: bytes | None = None
+    ) -> list[str]:
+        """
+        Return a list of keys applicable to the provided text items. If the content
+        of the source document is provided, the keys are cached with its text items.
+
+        :param py_text_items: List of text item dictionaries
+        :type py_text_items: list[dict]
+        :param content: Optional content of the document file
+        :type content: bytes | None
         :returns: List of applicable config keys
         :rtype: list[str]
         """
 
+    def list_configs(self, base_config_db: LibConfigDB | None = None) -> list[dict]:
+        """
+        Return the configs of the optional base config database, with the source
+        "embedded", and of the parser database, with the source "user" (see
+        LibConfigDB.list_configs). Parser configs replace base configs with the same
+        key.
+
+        :param base_config_db: Optional base config database
+        :type base_config_db: LibConfigDB | None
+        :returns: List of config dicts, sorted by key
+        :rtype: list[dict]
+        :raises ConfigAccessError: If a configuration cannot be loaded
+        """
+
+    def get_config_json(
+        self, key: str, base_config_db: LibConfigDB | None = None
+    ) -> str:
+        """
+        Return the JSON of a config of the parser database, or else of the optional
+        base config database, with every field set (see LibConfigDB.get_config_json).
+
+        :param key: The configuration key to retrieve
+        :type key: str
+        :param base_config_db: Optional base config database
+        :type base_config_db: LibConfigDB | None
+        :returns: Pretty-printed config JSON
+        :rtype: str
+        :raises ConfigAccessError: If the key is in neither database
+        """
+
+    @staticmethod
+    def registered_formats() -> list[str]:
+        """
+        Return the names of the amount and date formats registered at runtime. The
+        registries are process-wide, so these formats are missing from new
+        processes.
+
+        :returns: Amount format names, then date format names, each sorted
+        :rtype: list[str]
+        """
+
+    def catalog(
+        self, base_config_db: LibConfigDB | None = None
+    ) -> dict[str, dict[str, list[dict]]]:
+        """
+        Return the catalog of configs in the parser database and the optional base
+        config database (see LibConfigDB.catalog). Parser configs replace base
+        configs with the same key.
+
+        :param base_config_db: Optional base config database
+        :type base_config_db: LibConfigDB | None
+        :returns: Catalog dict
+        :rtype: dict[str, dict[str, list[dict]]]
+        :raises ConfigAccessError: If a configuration cannot be loaded
+        """
+
+    def catalog_str(self, base_config_db: LibConfigDB | None = None) -> str:
+        """
+        Return the catalog (see catalog) as text, with a line per country and a
+        line per bank listing its account types.
+
+        :param base_config_db: Optional base config database
+        :type base_config_db: LibConfigDB | None
+        :returns: Catalog text
+        :rtype: str
+        :raises ConfigAccessError: If a configuration cannot be loaded
+        """
+
+    def lint_config(
+        self, key: str, base_config_db: LibConfigDB | None = None
+    ) -> list[str]:
+        """
+        Return the warnings of the cross-field checks of a config (see
+        LibConfigDB.lint_config), checking its account terms against the configs of
+        the parser database and the optional base config database.
+
+        :param key: The configuration key to check
+        :type key: str
+        :param base_config_db: Optional base config database
+        :type base_config_db: LibConfigDB | None
+        :returns: Warnings as "field: message" strings
+        :rtype: list[str]
+        :raises ConfigAccessError: If the config is in neither database
+        """
+
     def get_unregistered_config_keys(self, keys: list[str]) -> list[str]:
         """
         Return a list of keys not yet registered in the parser database.
@@ -138,7 +388,15 @@ class LibParser:
         """
 
     def py_text_items_to_py_statement_data(
-        self, py_text_items: list[dict], applicable_config_keys: list[str]
+        self,
+        py_text_items: list[dict],
+        applicable_config_keys: list[str],
+        expected_account_number: str | None = None,
+        date_timezone: str | None = None,
+        fixers: str = "all",
+        retry_text_order: bool = False,
+        timings: dict[str, float] | None = None,
+        strict: bool = False,
     ) -> object:
         """
         Process a Python list of text items and return statement data.
@@ -147,27 +405,410 @@ class LibParser:
         :type py_text_items: list[dict]
         :param applicable_config_keys: List of applicable config keys
         :type applicable_config_keys: list[str]
-        :returns: StatementData object
+        :param expected_account_number: Optional account number the statement must
+            match, compared ignoring spaces and hyphens, or a regex pattern prefixed
+            with "re:"
+        :type expected_account_number: str | None
+        :param date_timezone: Optional IANA timezone overriding the date_timezone of
+            every applicable config
+        :type date_timezone: str | None
+        :param fixers: Fixers mode, "all", "safe" (reorder and index only) or "none"
+        :type fixers: str
+        :param retry_text_order: Retry configs whose fix_text_order reordering gives
+            errors in the original text order, keeping the better result
+        :type retry_text_order: bool
+        :param timings: Optional durations in seconds of the stages before parsing
+            ("text_extraction" and "statement_typing"), added to the timings of
+            each result
+        :type timings: dict[str, float] | None
+        :param strict: Parse as if every config set transaction_strict
+        :type strict: bool
+        :returns: StatementData object, with the duration in seconds of each parse
+            stage in metadata["timings"]
         :rtype: object
+        :raises NoErrorFreeStatementData: No error-free statement data could be found.
+            Its breakdown attribute is a list of dicts with "key", "config_status",
+            "error_count", "errors" (first few error strings) and "timings" for each
+            attempted config.
+        """
+
+    def py_text_items_to_py_statement_datas(
+        self,
+        py_text_items: list[dict],
+        applicable_config_keys: list[str],
+        expected_account_number: str | None = None,
+        date_timezone: str | None = None,
+        fixers: str = "all",
+        retry_text_order: bool = False,
+        timings: dict[str, float] | None = None,
+        strict: bool = False,
+    ) -> list[object]:
+        """
+        Process a Python list of text items and return the statement data of each
+        account section or statement of the first config whose sections are all
+        error-free. Only configs with multi_account set give more than one account
+        section, and only configs with multi_statement set more than one statement.
+
+        :param py_text_items: List of text item dictionaries
+        :type py_text_items: list[dict]
+        :param applicable_config_keys: List of applicable config keys
+        :type applicable_config_keys: list[str]
+        :param expected_account_number: See py_text_items_to_py_statement_data. Not
+            checked for account sections
+        :type expected_account_number: str | None
+        :param date_timezone: See py_text_items_to_py_statement_data
+        :type date_timezone: str | None
+        :param fixers: See py_text_items_to_py_statement_data
+        :type fixers: str
+        :param retry_text_order: See py_text_items_to_py_statement_data
+        :type retry_text_order: bool
+        :param timings: See py_text_items_to_py_statement_data
+        :type timings: dict[str, float] | None
+        :returns: List of StatementData objects, in statement order, with the
+            section number in metadata["account_section"], or for several
+            statements, in start date order with the statement number in
+            metadata["statement_section"]
+        :rtype: list[object]
+        :raises NoErrorFreeStatementData: No error-free statement data could be found
+        """
+
+    def py_text_items_to_transaction_stream(
+        self,
+        py_text_items: list[dict],
+        applicable_config_keys: list[str],
+        date_timezone: str | None = None,
+    ) -> LibTransactionStream:
+        """
+        Process a Python list of text items and return an iterator over its
+        transactions, parsed incrementally with the first applicable config.
+        Implicit dates and balances are filled as transactions are parsed, but other
+        fixers and the quality checks are not applied.
+
+        :param py_text_items: List of text item dictionaries
+        :type py_text_items: list[dict]
+        :param applicable_config_keys: List of applicable config keys
+        :type applicable_config_keys: list[str]
+        :param date_timezone: Optional IANA timezone overriding the date_timezone of
+            the config
+        :type date_timezone: str | None
+        :returns: Iterator over Transaction objects
+        :rtype: LibTransactionStream
+        :raises ValueError: No config keys or an invalid date timezone provided
+        """
+
+    @staticmethod
+    def try_config(json_str: str, py_text_items: list[dict]) -> dict:
+        """
+        Parse text items with a draft config JSON string only, without registering
+        the config, updating the StatementTyper or using the cache.
+
+        :param json_str: JSON string of the draft config
+        :type json_str: str
+        :param py_text_items: List of text item dictionaries
+        :type py_text_items: list[dict]
+        :return: Dict with the config "key", "passed", "errors", "warnings",
+            "config_warnings", "statement_datas" (a StatementData per account
+            section) and "trace" (debug summary of each result)
+        :rtype: dict
+        :raises ConfigLoadError: The draft config is invalid
+        """
+
+    @staticmethod
+    def diff_json_py_strs(old_json_str: str, new_json_str: str) -> tuple[str, str]:
+        """
+        Diff two JSON exports of parses of the same statement.
+
+        :param old_json_str: JSON export of the old parse
+        :type old_json_str: str
+        :param new_json_str: JSON export of the new parse
+        :type new_json_str: str
+        :return: The diff as a JSON string, and as human-readable text
+        :rtype: tuple[str, str]
+        :raises ValueError: A JSON export is invalid
+        """
+
+    @staticmethod
+    def verify_json_py_strs(
+        json_str: str,
+        expected_csv_str: str,
+        tolerance: float = 0.005,
+        normalise_descriptions: bool = True,
+    ) -> tuple[str, str]:
+        """
+        Compare the transactions of a JSON export of statement data with an
+        expected CSV.
+
+        :param json_str: JSON export of the statement data
+        :type json_str: str
+        :param expected_csv_str: CSV with a header naming any of the "date",
+            "description", "amount" and "balance" columns
+        :type expected_csv_str: str
+        :param tolerance: Largest difference between equal amounts or balances
+        :type tolerance: float
+        :param normalise_descriptions: Compare descriptions ignoring case and runs
+            of whitespace
+        :type normalise_descriptions: bool
+        :return: The report as a JSON string, and as human-readable text
+        :rtype: tuple[str, str]
+        :raises ValueError: The JSON export or the CSV is invalid
+        """
+
+    @staticmethod
+    def apply_csv_corrections_json_py_strs(
+        json_str: str, csv_path: str
+    ) -> tuple[str, str]:
+        """
+        Apply the corrections of a CSV file to a JSON export of statement data.
+
+        :param json_str: JSON export of the statement data
+        :type json_str: str
+        :param csv_path: Path to the corrections CSV file
+        :type csv_path: str
+        :return: The corrected JSON export, and the summary of the corrections as a
+            JSON string
+        :rtype: tuple[str, str]
+        :raises ValueError: The JSON export or the CSV is invalid
+        """
+
+    @staticmethod
+    def csv_py_str_to_py_statement_data(
+        csv_str: str,
+        columns: dict[str, str] | None = None,
+        date_formats: list[str] | None = None,
+        account_number: str | None = None,
+        opening_balance: float | None = None,
+        closing_balance: float | None = None,
+        key: str = "csv",
+    ) -> StatementData:
+        """
+        Read a bank CSV export into a StatementData, applying all fixers and
+        checkers as for a parsed statement.
+
+        :param csv_str: Text of the CSV export, with a header row
+        :type csv_str: str
+        :param columns: Headers of the "date", "description", "amount" and
+            "balance" columns, if not "Date", "Description", "Amount" and "Balance".
+            An empty "balance" header reads an export without balances.
+        :type columns: dict[str, str] | None
+        :param date_formats: Names of the date formats of the date column, if not
+            ["format13", "format4"]
+        :type date_formats: list[str] | None
+        :param account_number: Account number of the statement
+        :type account_number: str | None
+        :param opening_balance: Opening balance, required without balances
+        :type opening_balance: float | None
+        :param closing_balance: Closing balance, if not the last balance
+        :type closing_balance: float | None
+        :param key: Key recorded on the statement data
+        :type key: str
+        :return: The statement data
+        :rtype: StatementData
+        :raises ValueError: The CSV cannot be read
+        :raises NoErrorFreeStatementData: The statement data has errors
+        """
+
+    def py_text_items_to_json_py_str(
+        self,
+        py_text_items: list[dict],
+        applicable_config_keys: list[str],
+        expected_account_number: str | None = None,
+        date_timezone: str | None = None,
+        fixers: str = "all",
+        retry_text_order: bool = False,
+        include_incomplete: bool = False,
+        strict: bool = False,
+        redact_patterns: list[str] | None = None,
+    ) -> str:
+        """
+        Process a Python list of text items and return the first error-free
+        statement data as a JSON string, with ISO-8601 dates.
+
+        :param py_text_items: List of text item dictionaries
+        :type py_text_items: list[dict]
+        :param applicable_config_keys: List of applicable config keys
+        :type applicable_config_keys: list[str]
+        :param expected_account_number: See py_text_items_to_py_statement_data
+        :type expected_account_number: str | None
+        :param date_timezone: See py_text_items_to_py_statement_data
+        :type date_timezone: str | None
+        :param fixers: See py_text_items_to_py_statement_data
+        :type fixers: str
+        :param retry_text_order: See py_text_items_to_py_statement_data
+        :type retry_text_order: bool
+        :param include_incomplete: Emit incomplete transactions with nulls instead
+            of skipping them
+        :type include_incomplete: bool
+        :param redact_patterns: Redact the statement data: mask all but the last four
+            characters of the account number, and replace text matching these
+            regex patterns (possibly none) with "****". Not redacted if None.
+        :type redact_patterns: list[str] | None
+        :returns: JSON string
+        :rtype: str
         :raises NoErrorFreeStatementData: No error-free statement data could be found
         """
 
-    def py_text_items_to_debug_py_str(
-        self, py_text_items: list[dict], applicable_config_keys: list[str]
+    def py_text_items_to_py_candidates(
+        self,
+        py_text_items: list[dict],
+        applicable_config_keys: list[str],
+        expected_account_number: str | None = None,
+        date_timezone: str | None = None,
+        fixers: str = "all",
+        retry_text_order: bool = False,
+        strict: bool = False,
+    ) -> list[dict]:
+        """
+        Process a Python list of text items with each applicable config and return
+        every candidate result, whether or not it is error-free.
+
+        :param py_text_items: List of text item dictionaries
+        :type py_text_items: list[dict]
+        :param applicable_config_keys: List of applicable config keys
+        :type applicable_config_keys: list[str]
+        :param expected_account_number: See py_text_items_to_py_statement_data
+        :type expected_account_number: str | None
+        :param date_timezone: See py_text_items_to_py_statement_data
+        :type date_timezone: str | None
+        :param fixers: See py_text_items_to_py_statement_data
+        :type fixers: str
+        :param retry_text_order: See py_text_items_to_py_statement_data
+        :type retry_text_order: bool
+        :returns: List of dicts in the order of applicable_config_keys, with "key",
+            "config_status", "passed", "errors", "warnings", "account_number" and
+            the transaction column lists "date", "description", "amount" and
+            "balance" (None for values that could not be parsed), and a "metadata"
+            dict of the statement "key", "account_number", "opening_balance",
+            "closing_balance", "start_date" and "errors" (None where not found)
+        :rtype: list[dict]
+        """
+
+    def py_text_items_to_debug_file(
+        self,
+        py_text_items: list[dict],
+        applicable_config_keys: list[str],
+        output_file: str,
+        retry_text_order: bool = False,
+        timings: dict[str, float] | None = None,
+        sources: bool = False,
+        max_bytes: int | None = None,
+        max_section_lines: int = 10000,
+        redact_patterns: list[str] | None = None,
+    ) -> None:
+        """
+        Process a Python list of text items and stream debug information to a file,
+        written atomically.
+
+        :param py_text_items: List of text item dictionaries
+        :type py_text_items: list[dict]
+        :param applicable_config_keys: List of applicable config keys. If empty, the
+            account terms found and missing for every registered key are written
+            instead, with the text nearest to each missing term.
+        :type applicable_config_keys: list[str]
+        :param output_file: Path to the output debug text file
+        :type output_file: str
+        :param retry_text_order: Retry configs whose fix_text_order reordering gives
+            errors in the original text order
+        :type retry_text_order: bool
+        :param timings: Optional durations in seconds of the stages before parsing
+            (see py_text_items_to_py_statement_data)
+        :type timings: dict[str, float] | None
+        :param sources: List the text items read into each statement field and
+            transaction
+        :type sources: bool
+        :param max_bytes: Maximum size of the output in bytes, after which it is
+            truncated with a marker. Unlimited if None.
+        :type max_bytes: int | None
+        :param max_section_lines: Maximum number of lines of each section of a
+            result, after which the section is truncated with a marker
+        :type max_section_lines: int
+        :param redact_patterns: Redact the results: mask all but the last four
+            characters of the account number, and replace text matching these
+            regex patterns (possibly none) with "****". Not redacted if None.
+        :type redact_patterns: list[str] | None
+        :raises RuntimeError: The configs cannot be found or the file cannot be
+            written
+        """
+
+    def py_text_items_to_debug_json_py_str(
+        self,
+        py_text_items: list[dict],
+        applicable_config_keys: list[str],
+        retry_text_order: bool = False,
+        timings: dict[str, float] | None = None,
+        redact_patterns: list[str] | None = None,
     ) -> str:
         """
-        Process a Python list of text items and return debug information as a string.
+        Process a Python list of text items and return the debug report as a JSON
+        string: for each applicable config the key, account number, dates, balances,
+        transaction count, errors, warnings and stage timings in milliseconds.
 
         :param py_text_items: List of text item dictionaries
         :type py_text_items: list[dict]
         :param applicable_config_keys: List of applicable config keys
         :type applicable_config_keys: list[str]
-        :returns: Debug information string
+        :param retry_text_order: Retry configs whose fix_text_order reordering gives
+            errors in the original text order
+        :type retry_text_order: bool
+        :param timings: Optional durations in seconds of the stages before parsing
+            (see py_text_items_to_py_statement_data)
+        :type timings: dict[str, float] | None
+        :param redact_patterns: See py_text_items_to_debug_file
+        :type redact_patterns: list[str] | None
+        :returns: The debug report as a JSON string
+        :rtype: str
+        :raises RuntimeError: The configs cannot be found
+        """
+
+    def py_text_items_to_explain_py_strs(
+        self,
+        py_text_items: list[dict],
+        applicable_config_keys: list[str],
+        retry_text_order: bool = False,
+        timings: dict[str, float] | None = None,
+    ) -> tuple[str, str]:
+        """
+        Process a Python list of text items with every applicable config and return
+        an explanation of the parse: the account terms found and missing for each
+        registered key with any term found, then for each config a summary, the
+        transaction column bounds, dropped rows, errors, warnings, page coverage and
+        timings.
+
+        :param py_text_items: List of text item dictionaries
+        :type py_text_items: list[dict]
+        :param applicable_config_keys: List of applicable config keys (may be empty)
+        :type applicable_config_keys: list[str]
+        :param retry_text_order: Retry configs whose fix_text_order reordering gives
+            errors in the original text order
+        :type retry_text_order: bool
+        :param timings: Optional durations in seconds of the stages before parsing
+            (see py_text_items_to_py_statement_data)
+        :type timings: dict[str, float] | None
+        :returns: The explanation as a human-readable string and a JSON string
+        :rtype: tuple[str, str]
+        """
+
+    def explain_file(self, path: str) -> str:
+        """
+        Read a layout text file and report, for every registered key, the account
+        terms found and missing in it, with the text nearest to each missing term
+        (e.g., the term in a different case, or with words split or joined
+        differently), most terms found first.
+
+        :param path: Path to the layout text file
+        :type path: str
+        :returns: The report as a human-readable string
         :rtype: str
+        :raises OSError: The file cannot be read
+        :raises LayoutParseError: The layout text is invalid
         """
 
     def py_text_items_to_layout_py_str(
-        self, py_text_items: list[dict], y_bin: float, x_gap: float
+        self,
+        py_text_items: list[dict],
+        y_bin: float,
+        x_gap: float,
+        header: bool = True,
+        timestamp: bool = True,
     ) -> str:
         """
         Process a Python list of text items and return layout text as a string.
@@ -178,6 +819,11 @@ class LibParser:
         :type y_bin: float
         :param x_gap: X coordinate gap size for merging text items
         :type x_gap: float
+        :param header: Precede the layout with a "#" comment header recording the
+            crate version, fix_text_order values, line height and text item counts
+        :type header: bool
+        :param timestamp: Include the generation timestamp in the header
+        :type timestamp: bool
         :returns: Layout text string
         :rtype: str
         """
@@ -190,13 +836,52 @@ class LibParser:
         :type layout_str: str
         :returns: List of text item dictionaries
         :rtype: list[dict]
+        :raises LayoutParseError: The layout text is invalid
+        """
+
+    def py_text_item_parts_to_py_text_items(
+        self, py_text_item_parts: list[list[dict]]
+    ) -> list[dict]:
+        """
+        Merge text items from a statement split across multiple files into a single
+        list of text items. Parts are ordered by "part X of Y" or "page X of Y"
+        markers if present, otherwise kept in the supplied order. Pages are
+        renumbered so each part follows on from the previous one.
+
+        :param py_text_item_parts: List of text item dictionary lists, one per part
+        :type py_text_item_parts: list[list[dict]]
+        :returns: Merged list of text item dictionaries
+        :rtype: list[dict]
+        """
+
+class LibTransactionStream:
+    """Iterator over the transactions of a statement, parsed incrementally."""
+
+    def __iter__(self) -> LibTransactionStream: ...
+    def __next__(self) -> object:
+        """
+        Parse up to the next complete transaction.
+
+        :returns: Transaction object, with its index within the day
+        :rtype: object
+        :raises RuntimeError: The transaction could not be completed
         """
 
 class NoErrorFreeStatementData(Exception):
-    """Raised when no error-free statement data could be found."""
+    """Raised when no error-free statement data could be found.
+
+    ``args[0]`` is a message summarising the best candidate config.
+    ``breakdown`` is a list of dicts with "key", "error_count" and "errors"
+    for each attempted config, or None if not known.
+    """
+
+    breakdown: list[dict] | None
 
 class ConfigLoadError(Exception):
     """Raised when a configuration cannot be loaded."""
 
 class ConfigAccessError(Exception):
     """Raised when a configuration cannot be accessed."""
+
+class LayoutParseError(RuntimeError):
+    """Raised when layout text cannot be parsed into text items."""
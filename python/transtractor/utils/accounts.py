@@ -0,0 +1,67 @@
+"""Statement data of multi-account statements, with one StatementData per account."""
+
+from pathlib import Path
+from typing import TYPE_CHECKING
+
+if TYPE_CHECKING:
+    from ..structs.statement_data import StatementData
+
+
+def accounts_by_number(
+    statement_datas: list["StatementData"],
+) -> dict[str, "StatementData"]:
+    """Key the statement data of each account of a statement by its account number.
+    Accounts without an account number are keyed by their account section number
+    (e.g., "section_2").
+
+    :param statement_datas: Statement data of each account, in statement order
+    :return: Dict of account number to statement data, in statement order
+    """
+    accounts: dict[str, "StatementData"] = {}
+    for number, statement_data in enumerate(statement_datas, start=1):
+        section = statement_data.metadata.get("account_section") or number
+        key = statement_data.account_number or f"section_{section}"
+        if key in accounts:
+            key = f"{key}_section_{section}"
+        accounts[key] = statement_data
+    return accounts
+
+
+def account_csv_path(file_path: str, account: str) -> str:
+    """Get the CSV path of an account, suffixing the file name with the account
+    (e.g., "statement_062-000_1234_5678.csv").
+
+    :param file_path: Path to the CSV file of the statement
+    :param account: Account number or section key of the account
+    :return: Path to the CSV file of the account
+    """
+    path = Path(file_path)
+    suffix = "_".join(account.replace("/", "-").split())
+    return str(path.with_name(f"{path.stem}_{suffix}{path.suffix}"))
+
+
+def accounts_to_csv(
+    accounts: dict[str, "StatementData"],
+    file_path: str,
+    fields: tuple[str, ...] | list[str] = (
+        "date",
+        "description",
+        "amount",
+        "balance",
+    ),
+) -> list[str]:
+    """Export the statement data of each account to its own CSV file, named by
+    suffixing the file name with the account (see account_csv_path).
+
+    :param accounts: Dict of account number to statement data (see
+        Parser.parse_accounts)
+    :param file_path: Path to the CSV file of the statement
+    :param fields: Fields to include in each CSV, as for StatementData.to_csv
+    :return: Paths of the CSV files written, in account order
+    """
+    paths = []
+    for account, statement_data in accounts.items():
+        path = account_csv_path(file_path, account)
+        statement_data.to_csv(path, fields)
+        paths.append(path)
+    return paths
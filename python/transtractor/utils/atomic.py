@@ -0,0 +1,38 @@
+"""Utilities for writing output files atomically."""
+
+import os
+import uuid
+from collections.abc import Iterator
+from contextlib import contextmanager
+from typing import TextIO
+
+
+@contextmanager
+def atomic_open(file_path: str, newline: str | None = None) -> Iterator[TextIO]:
+    """Open a temporary text file in the directory of file_path for writing, and
+    rename it to file_path once written. If writing fails, the temporary file is
+    removed and any existing file at file_path is left untouched, so a partially
+    written file is never seen at file_path.
+
+    :param file_path: Path to the output file
+    :param newline: Newline mode, as for open (e.g., "" for CSV files)
+    :return: Context manager yielding the open temporary file
+    """
+    directory = os.path.dirname(os.path.abspath(file_path))
+    # Unique per call, so concurrent writes of the same path do not share it
+    tmp_path = os.path.join(
+        directory, f".{os.path.basename(file_path)}.{uuid.uuid4().hex[:12]}.tmp"
+    )
+    # Created with the permissions of open, masked by the umask
+    flags = os.O_WRONLY | os.O_CREAT | os.O_EXCL | getattr(os, "O_BINARY", 0)
+    fd = os.open(tmp_path, flags, 0o666)
+    try:
+        with os.fdopen(fd, mode="w", newline=newline, encoding="utf-8") as fh:
+            yield fh
+            fh.flush()
+            os.fsync(fh.fileno())
+        os.replace(tmp_path, file_path)
+    except BaseException:
+        if os.path.exists(tmp_path):
+            os.remove(tmp_path)
+        raise
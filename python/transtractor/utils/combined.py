@@ -0,0 +1,172 @@
+"""Combined CSV of the transactions of several statements (e.g., a year of monthly
+statements of an account), with the file and config of each transaction's statement."""
+
+import csv
+import os
+from typing import TYPE_CHECKING
+
+from .atomic import atomic_open
+
+if TYPE_CHECKING:
+    from ..structs.statement_data import StatementData
+
+# Columns of the combined CSV
+COMBINED_FIELDS = (
+    "date",
+    "description",
+    "amount",
+    "balance",
+    "source_file",
+    "statement_key",
+)
+
+
+def _cell(value) -> str:
+    """Format a value as the csv module writes it, with None as an empty value."""
+    return "" if value is None else str(value)
+
+
+def _statement_rows(statement_data: "StatementData") -> list[list[str]]:
+    """Get the combined CSV rows of the transactions of a statement, in order."""
+    source_file = _cell(statement_data.filename)
+    statement_key = _cell(statement_data.key)
+    return [
+        [
+            _cell(transaction.date),
+            _cell(transaction.description),
+            _cell(transaction.amount),
+            _cell(transaction.balance),
+            source_file,
+            statement_key,
+        ]
+        for transaction in statement_data.transactions
+    ]
+
+
+def _read_rows(file_path: str) -> list[list[str]]:
+    """Read the rows of an existing combined CSV, without the header.
+
+    :raises ValueError: The file is not a combined CSV
+    """
+    with open(file_path, newline="", encoding="utf-8") as fh:
+        reader = csv.reader(fh)
+        header = next(reader, None)
+        if header is None:
+            return []
+        if tuple(header) != COMBINED_FIELDS:
+            raise ValueError(
+                f"Cannot append to {file_path}: its header {header} is not the "
+                f"combined CSV header {list(COMBINED_FIELDS)}"
+            )
+        return [row for row in reader if row]
+
+
+def _to_float(value: str) -> float | None:
+    try:
+        return float(value)
+    except ValueError:
+        return None
+
+
+def _combine(
+    rows: list[list[str]], tolerance: float
+) -> tuple[list[list[str]], list[str]]:
+    """Order the rows by date and check the continuity of their statements.
+
+    Rows are grouped into statements by source file and statement key, keeping their
+    order within each statement. Statements are ordered by their first date, and
+    rows by date, then statement. The opening balance of a statement is taken as the
+    balance before its first transaction, and its closing balance as the balance of
+    its last transaction, so that statements read back from the CSV are checked in
+    the same way.
+
+    :return: The ordered rows, and a warning for each gap between consecutive
+        statements whose balances differ by more than tolerance
+    """
+    statements: dict[tuple[str, str], list[list[str]]] = {}
+    for row in rows:
+        statements.setdefault((row[4], row[5]), []).append(row)
+    ordered = sorted(statements.items(), key=lambda item: min(r[0] for r in item[1]))
+    rank = {source: n for n, (source, _) in enumerate(ordered)}
+
+    warnings = []
+    for (previous, previous_rows), (source, statement_rows) in zip(
+        ordered, ordered[1:]
+    ):
+        closing = _to_float(previous_rows[-1][3])
+        first_balance = _to_float(statement_rows[0][3])
+        first_amount = _to_float(statement_rows[0][2])
+        if closing is None or first_balance is None or first_amount is None:
+            continue
+        opening = round(first_balance - first_amount, 2)
+        if abs(round(opening - closing, 2)) > tolerance:
+            warnings.append(
+                f"Balance gap between {previous[0]} ({previous[1]}), closing balance "
+                f"{closing:.2f}, and {source[0]} ({source[1]}), opening balance "
+                f"{opening:.2f}: difference {opening - closing:.2f}"
+            )
+
+    indexed = [
+        (row[0], rank[source], n, row)
+        for source, statement_rows in ordered
+        for n, row in enumerate(statement_rows)
+    ]
+    indexed.sort(key=lambda item: item[:3])
+    return [row for *_, row in indexed], warnings
+
+
+def _write(file_path: str, rows: list[list[str]]) -> None:
+    with atomic_open(file_path, newline="") as csvfile:
+        writer = csv.writer(csvfile)
+        writer.writerow(COMBINED_FIELDS)
+        writer.writerows(rows)
+
+
+def statements_to_csv(
+    statement_datas: list["StatementData"], file_path: str, tolerance: float = 0.0
+) -> list[str]:
+    """Export the transactions of several statements to one CSV file, with a single
+    header row and the COMBINED_FIELDS columns: the transaction "date",
+    "description", "amount" and "balance", and the "source_file" and
+    "statement_key" of its statement. Transactions are ordered by date. The file is
+    written atomically (see StatementData.to_csv).
+
+    Consecutive statements are checked for continuity: the closing balance of each
+    statement must equal the opening balance of the next within tolerance. Gaps
+    (e.g., a missing month) are returned as warnings rather than merged silently.
+
+    :param statement_datas: Statement data of each statement, in any order
+    :param file_path: Path to the output CSV file
+    :param tolerance: Largest difference between the closing and opening balances
+        of consecutive statements that is not a gap
+    :return: Warnings of the balance gaps between consecutive statements
+    """
+    rows = [row for sd in statement_datas for row in _statement_rows(sd)]
+    ordered, warnings = _combine(rows, tolerance)
+    _write(file_path, ordered)
+    return warnings
+
+
+def append_statements_to_csv(
+    statement_datas: list["StatementData"], file_path: str, tolerance: float = 0.0
+) -> list[str]:
+    """Add the transactions of statements to a combined CSV file (see
+    statements_to_csv), creating it if it does not exist. The rows already in the
+    file are kept, except those of a statement with the same source file and
+    statement key, which are replaced so that appending a statement again does not
+    duplicate it. The file is rewritten in date order, with a single header row, and
+    all its statements are checked for continuity.
+
+    :param statement_datas: Statement data of each statement to add
+    :param file_path: Path to the combined CSV file
+    :param tolerance: See statements_to_csv
+    :return: Warnings of the balance gaps between consecutive statements
+    :raises ValueError: The existing file is not a combined CSV
+    """
+    new_rows = [row for sd in statement_datas for row in _statement_rows(sd)]
+    replaced = {(row[4], row[5]) for row in new_rows}
+    existing = _read_rows(file_path) if os.path.exists(file_path) else []
+    kept = [row for row in existing if (row[4], row[5]) not in replaced]
+    ordered, warnings = _combine(kept + new_rows, tolerance)
+    _write(file_path, ordered)
+    return warnings
@@ -0,0 +1,47 @@
+"""Round-trip editing: apply a corrected CSV export back onto parsed statement data."""
+
+import json
+from typing import TYPE_CHECKING
+
+from ..transtractor import LibParser
+from .diff import statement_data_to_export
+
+if TYPE_CHECKING:
+    from ..structs.statement_data import StatementData
+
+
+def apply_csv_corrections(statement_data: "StatementData", csv_path: str) -> dict:
+    """Apply the corrections of an edited CSV export (see StatementData.to_csv) to the
+    statement data, in place, without re-parsing the statement.
+
+    CSV rows are matched to transactions by their 'date' (YYYY-MM-DD) and
+    'date_index' columns, or by their order within each date if there is no
+    'date_index' column. Changed 'description', 'amount' and 'balance' values are
+    applied and flagged as "corrected" in the description_source, amount_source and
+    balance_source of the transaction. Empty cells are left unchanged. The safe
+    fixers and the transaction checkers are then rerun.
+
+    :param statement_data: Parsed statement data
+    :param csv_path: Path to the corrections CSV file
+    :return: Dict with 'corrections' (list of dicts with the 1-based CSV 'row', the
+        1-based transaction 'number' and its field 'changes', each with the 'field',
+        'old' and 'new' values), 'unmatched_rows' (CSV rows not matching any
+        transaction), and 'errors_added' and 'errors_removed' (balance and field
+        check errors of the corrected transactions, e.g., if a corrected amount no
+        longer agrees with the balances)
+    :raises ValueError: The CSV has no 'date' column or a value cannot be parsed
+    """
+    corrected_json, summary_json = LibParser.apply_csv_corrections_json_py_strs(
+        json.dumps(statement_data_to_export(statement_data)), csv_path
+    )
+    corrected = json.loads(corrected_json)
+    for transaction, corrected_tx in zip(
+        statement_data.transactions, corrected["transactions"], strict=True
+    ):
+        transaction.description = corrected_tx["description"]
+        transaction.amount = corrected_tx["amount"]
+        transaction.balance = corrected_tx["balance"]
+        transaction.description_source = corrected_tx["description_source"]
+        transaction.amount_source = corrected_tx["amount_source"]
+        transaction.balance_source = corrected_tx["balance_source"]
+    return json.loads(summary_json)
@@ -0,0 +1,100 @@
+"""Comparison of two parses of the same statement, e.g., before and after upgrading."""
+
+import json
+from datetime import date as Date
+from pathlib import Path
+from typing import TYPE_CHECKING, Union
+
+from ..transtractor import LibParser
+
+if TYPE_CHECKING:
+    from ..structs.statement_data import StatementData
+
+# Statement values read from the StatementData metadata, as in the JSON export
+METADATA_FIELDS = (
+    "config_status",
+    "start_date",
+    "end_date",
+    "opening_balance",
+    "closing_balance",
+    "minimum_payment",
+    "payment_due_date",
+    "currency",
+)
+
+Parse = Union["StatementData", dict, str, Path]
+
+
+def statement_data_to_export(statement_data: "StatementData") -> dict:
+    """Convert statement data to the dict of its JSON export (see Parser.to_json).
+
+    :param statement_data: Statement data to convert
+    :return: Dict with the statement values, errors, warnings and transactions
+    """
+    export = {
+        "key": statement_data.key,
+        "account_number": statement_data.account_number,
+        "errors": [],
+        "warnings": list(statement_data.metadata.get("warnings", [])),
+        "transactions": [
+            {
+                "date": tx.date.isoformat() if isinstance(tx.date, Date) else tx.date,
+                "date_index": tx.date_index,
+                "description": tx.description,
+                "amount": tx.amount,
+                "balance": tx.balance,
+                "amount_source": tx.amount_source,
+                "balance_source": tx.balance_source,
+                "description_source": tx.description_source,
+            }
+            for tx in statement_data.transactions
+        ],
+    }
+    for field in METADATA_FIELDS:
+        value = statement_data.metadata.get(field)
+        export[field] = value.isoformat() if isinstance(value, Date) else value
+    return export
+
+
+def _to_json_str(parse: Parse) -> str:
+    if isinstance(parse, (str, Path)):
+        return Path(parse).read_text(encoding="utf-8")
+    if isinstance(parse, dict):
+        return json.dumps(parse)
+    return json.dumps(statement_data_to_export(parse))
+
+
+def _diff(old: Parse, new: Parse) -> tuple[str, str]:
+    return LibParser.diff_json_py_strs(_to_json_str(old), _to_json_str(new))
+
+
+def diff(old: Parse, new: Parse) -> dict[str, list]:
+    """Compare two parses of the same statement. Each parse is StatementData, the
+    dict of a JSON export, or the path to a JSON export file (see Parser.to_json).
+    Transactions are matched in order, and numbers differing by less than half a
+    cent are equal.
+
+    :param old: The old parse
+    :param new: The new parse
+    :return: Dict with 'metadata' (list of dicts with the 'field', 'old' and 'new'
+        values of changed statement values), 'transactions_changed' (list of dicts
+        with the 'old_number' and 'new_number' of the transaction, numbered from 1,
+        and its field 'changes'), 'transactions_added' and 'transactions_removed'
+        (lists of dicts with the transaction 'number' and 'transaction'),
+        'errors_added', 'errors_removed', 'warnings_added' and 'warnings_removed'.
+        All lists are empty if the parses are the same.
+    :raises ValueError: A JSON export is invalid
+    """
+    return json.loads(_diff(old, new)[0])
+
+
+def diff_to_text(old: Parse, new: Parse) -> str:
+    """Compare two parses of the same statement (see diff) as human-readable text,
+    one line per change, or "No differences".
+
+    :param old: The old parse
+    :param new: The new parse
+    :return: Text of the differences
+    :raises ValueError: A JSON export is invalid
+    """
+    return _diff(old, new)[1]
@@ -1,26 +1,182 @@
 """Utilities for extracting text items from PDF files."""
 
+from collections.abc import Iterator
+
 import pdfplumber
+from pdfplumber.utils import extract_words
+from pdfminer.pdfdocument import PDFEncryptionError
+from pdfminer.psparser import PSException
+
+from ..exceptions import PdfEncrypted, PdfMalformed, PdfNoTextContent
+
+# Line direction of the text for each character direction of rotated text. Text
+# reading bottom to top ("btt") has its first line on the left of the page, and
+# text reading top to bottom ("ttb") on the right.
+ROTATED_LINE_DIRS = {"btt": "ltr", "ttb": "rtl"}
+
+
+def rotated_text_direction(chars: list[dict]) -> str | None:
+    """Get the character direction of the rotated text of a page, if most of its
+    characters are rotated by 90 degrees (e.g., a landscape page drawn sideways).
+
+    :param chars: Characters of the page, with their text "matrix"
+    :return: "btt" (rotated anticlockwise), "ttb" (rotated clockwise) or None
+    """
+    counts = {"ltr": 0, "btt": 0, "ttb": 0}
+    for char in chars:
+        _a, b, c, _d, _e, _f = char.get("matrix", (1, 0, 0, 1, 0, 0))
+        if b > 0 and c < 0:
+            counts["btt"] += 1
+        elif b < 0 and c > 0:
+            counts["ttb"] += 1
+        else:
+            counts["ltr"] += 1
+    direction = max(counts, key=lambda d: counts[d])
+    return None if direction == "ltr" else direction
+
+
+def flipped_text(chars: list[dict]) -> bool:
+    """Whether most characters of the page are drawn upside down in a vertically
+    flipped coordinate space (e.g., a content stream transformed so that y increases
+    down the page). The character boxes are already top-down page coordinates, but
+    pdfminer does not mark these characters as upright, so their words would be read
+    as vertical text.
+
+    :param chars: Characters of the page, with their text "matrix"
+    :return: True if most characters are flipped
+    """
+    flipped = 0
+    for char in chars:
+        a, b, c, d, _e, _f = char.get("matrix", (1, 0, 0, 1, 0, 0))
+        if a > 0 and d < 0 and b == 0 and c == 0:
+            flipped += 1
+    return flipped * 2 > len(chars)
+
+
+def to_reading_orientation(
+    word: dict, direction: str, page_width: float, page_height: float
+) -> tuple[float, float, float, float]:
+    """Map the bounding box of a word of rotated text to the coordinates it would
+    have on the page turned upright, so that lines are grouped by y as on portrait
+    pages.
+
+    :param word: Word with "x0", "x1", "top" and "bottom" page coordinates
+    :param direction: Character direction of the text ("btt" or "ttb")
+    :param page_width: Width of the page
+    :param page_height: Height of the page
+    :return: (x1, y1, x2, y2) of the word, with y1 the bottom and y2 the top
+    """
+    if direction == "btt":
+        # Turned clockwise: reading x runs up the page, lines run left to right
+        return (
+            page_height - word["bottom"],
+            word["x1"],
+            page_height - word["top"],
+            word["x0"],
+        )
+    # Turned anticlockwise: reading x runs down the page, lines run right to left
+    return (
+        word["top"],
+        page_width - word["x0"],
+        word["bottom"],
+        page_width - word["x1"],
+    )
+
+
+def pdfminer_errors(error: BaseException) -> Iterator[BaseException]:
+    """Yield the error and the errors it wraps, as pdfplumber wraps the errors of
+    pdfminer (e.g., in a PdfminerException whose argument is the original error)."""
+    seen: set[int] = set()
+    pending = [error]
+    while pending:
+        current = pending.pop()
+        if id(current) in seen:
+            continue
+        seen.add(id(current))
+        yield current
+        pending.extend(arg for arg in current.args if isinstance(arg, BaseException))
+        for wrapped in (current.__cause__, current.__context__):
+            if wrapped is not None:
+                pending.append(wrapped)
+
+
+def input_kind(file_path: str) -> str:
+    """Get the kind of a statement file from its name: a layout file (see
+    Parser.layout) if the name ends in ".txt" in any case (e.g.,
+    "statement.pdf.txt"), otherwise a PDF. Every Parser method taking a statement
+    file uses this, so that they accept the same files.
+
+    :param file_path: Path to the statement file
+    :return: "layout" or "pdf"
+    """
+    return "layout" if file_path.lower().endswith(".txt") else "pdf"
 
 
 def pdf_to_text_items(pdf_path: str) -> list[dict]:
     """Extract PDF into a list of dictionaries. These are used to create
-    TextItem objects in Rust for extraction of bank statement data.
+    TextItem objects in Rust for extraction of bank statement data. Pages whose
+    text is rotated by 90 degrees are mapped to the orientation of upright pages,
+    and pages drawn in a vertically flipped coordinate space are read as upright.
 
     :param pdf_path: Path to the PDF file to be processed
     :return: List of dictionaries representing text items extracted from the PDF
+    :raises PdfEncrypted: The PDF is password-protected
+    :raises PdfMalformed: The PDF is corrupt or not a PDF file
+    :raises PdfNoTextContent: No page has any text (e.g., a scanned statement)
     """
+    try:
+        items = _extract_text_items(pdf_path)
+    except Exception as e:
+        errors = list(pdfminer_errors(e))
+        # Encryption errors are PSExceptions too, so are checked first
+        if any(isinstance(error, PDFEncryptionError) for error in errors):
+            raise PdfEncrypted(
+                f"{pdf_path} is password-protected. Remove the password and try "
+                "again."
+            ) from e
+        if any(isinstance(error, PSException) for error in errors):
+            raise PdfMalformed(f"{pdf_path} is corrupt or not a PDF file: {e}") from e
+        raise
+    if not items:
+        raise PdfNoTextContent(
+            f"{pdf_path} has no text on any page. Scanned (image-only) statements "
+            "must be OCRed to a text PDF first."
+        )
+    return items
+
+
+def _extract_text_items(pdf_path: str) -> list[dict]:
+    """Extract the text items of every page of the PDF (see pdf_to_text_items)."""
     items: list[dict] = []
     with pdfplumber.open(pdf_path) as pdf:
         for page_index, page in enumerate(pdf.pages):
-            for word in page.extract_words(x_tolerance=2):
+            direction = rotated_text_direction(page.chars)
+            if direction is None and flipped_text(page.chars):
+                chars = [dict(char, upright=True) for char in page.chars]
+                words = extract_words(chars, x_tolerance=2)
+            elif direction is None:
+                words = page.extract_words(x_tolerance=2)
+            else:
+                words = page.extract_words(
+                    x_tolerance=2,
+                    y_tolerance=2,
+                    char_dir_rotated=direction,
+                    line_dir_rotated=ROTATED_LINE_DIRS[direction],
+                )
+            for word in words:
+                if direction is None or word.get("upright", True):
+                    x1, y1, x2, y2 = word["x0"], word["bottom"], word["x1"], word["top"]
+                else:
+                    x1, y1, x2, y2 = to_reading_orientation(
+                        word, direction, float(page.width), float(page.height)
+                    )
                 items.append(
                     {
                         "text": word.get("text", ""),
-                        "x1": int(float(word["x0"])),
-                        "y1": int(float(word["bottom"])),
-                        "x2": int(float(word["x1"])),
-                        "y2": int(float(word["top"])),
+                        "x1": int(float(x1)),
+                        "y1": int(float(y1)),
+                        "x2": int(float(x2)),
+                        "y2": int(float(y2)),
                         "page": page_index,
                     }
                 )
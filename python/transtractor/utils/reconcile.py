@@ -0,0 +1,204 @@
+"""Reconciliation of parsed statement data against an external transactions CSV."""
+
+import csv
+import io
+from datetime import date as Date
+from datetime import datetime
+from typing import TYPE_CHECKING
+
+from .atomic import atomic_open
+
+if TYPE_CHECKING:
+    from ..structs.statement_data import StatementData
+
+DEFAULT_COLUMNS = {"date": "date", "description": "description", "amount": "amount"}
+
+REPORT_CSV_HEADER = [
+    "status",
+    "statement_date",
+    "statement_description",
+    "statement_amount",
+    "external_date",
+    "external_description",
+    "external_amount",
+]
+
+
+def _parse_amount(value: str) -> float:
+    """Parse an amount from an external CSV cell, ignoring currency symbols,
+    thousands separators and surrounding whitespace."""
+    cleaned = value.strip().replace(",", "").replace("$", "")
+    if cleaned.startswith("(") and cleaned.endswith(")"):
+        cleaned = "-" + cleaned[1:-1]
+    return round(float(cleaned), 2)
+
+
+def read_external_transactions(
+    csv_path: str,
+    columns: dict[str, str] | None = None,
+    date_format: str = "%Y-%m-%d",
+) -> list[dict]:
+    """Read transactions from an external CSV file.
+
+    :param csv_path: Path to the external CSV file
+    :param columns: Mapping of 'date', 'description' and 'amount' to the column
+        names in the CSV. Defaults to columns named 'date', 'description'
+        and 'amount'. The description column is optional.
+    :param date_format: strptime format of the date column
+    :return: List of transaction dicts with 'row', 'date', 'description' and
+        'amount' keys, in file order
+    :raises ValueError: If a mapped column is missing or a value cannot be parsed
+    """
+    mapping = {**DEFAULT_COLUMNS, **(columns or {})}
+    transactions = []
+    with open(csv_path, newline="", encoding="utf-8") as csvfile:
+        reader = csv.DictReader(csvfile)
+        fieldnames = reader.fieldnames or []
+        for field in ("date", "amount"):
+            if mapping[field] not in fieldnames:
+                raise ValueError(
+                    f"Column '{mapping[field]}' for {field} not found in {csv_path}"
+                )
+        has_description = mapping["description"] in fieldnames
+        # Row numbers are 1-based and exclude the header row
+        for row_number, row in enumerate(reader, start=1):
+            try:
+                tx_date = datetime.strptime(
+                    row[mapping["date"]].strip(), date_format
+                ).date()
+                amount = _parse_amount(row[mapping["amount"]])
+            except ValueError as e:
+                raise ValueError(f"Invalid row {row_number} in {csv_path}: {e}") from e
+            transactions.append(
+                {
+                    "row": row_number,
+                    "date": tx_date,
+                    "description": row[mapping["description"]]
+                    if has_description
+                    else "",
+                    "amount": amount,
+                }
+            )
+    return transactions
+
+
+def reconcile(
+    statement_data: "StatementData",
+    csv_path: str,
+    columns: dict[str, str] | None = None,
+    date_format: str = "%Y-%m-%d",
+    date_tolerance_days: int = 1,
+) -> dict[str, list]:
+    """Reconcile parsed statement transactions against an external CSV.
+
+    Transactions are matched on exact amount with dates within
+    date_tolerance_days of each other. Statement transactions are matched in
+    order, each to the unmatched external row with the closest date, then the
+    earliest row in the file, so duplicate amounts are matched deterministically.
+
+    :param statement_data: Parsed statement data
+    :param csv_path: Path to the external CSV file
+    :param columns: Mapping of 'date', 'description' and 'amount' to the column
+        names in the CSV (see read_external_transactions)
+    :param date_format: strptime format of the date column
+    :param date_tolerance_days: Maximum difference in days between matched dates
+    :return: Dict with 'matched' (list of dicts with 'statement' and 'external'
+        transactions), 'statement_only' and 'external_only' lists
+    """
+    external = read_external_transactions(csv_path, columns, date_format)
+    unmatched = list(external)
+    matched = []
+    statement_only = []
+
+    for index, transaction in enumerate(statement_data.transactions):
+        statement_tx = {
+            "index": index,
+            "date": transaction.date,
+            "description": transaction.description,
+            "amount": transaction.amount,
+        }
+        best = None
+        best_days = None
+        for candidate in unmatched:
+            if candidate["amount"] != round(transaction.amount, 2):
+                continue
+            days = abs((candidate["date"] - transaction.date).days)
+            if days > date_tolerance_days:
+                continue
+            if best_days is None or days < best_days:
+                best = candidate
+                best_days = days
+        if best is None:
+            statement_only.append(statement_tx)
+        else:
+            unmatched.remove(best)
+            matched.append({"statement": statement_tx, "external": best})
+
+    return {
+        "matched": matched,
+        "statement_only": statement_only,
+        "external_only": unmatched,
+    }
+
+
+def _format_date(value: Date | None) -> str:
+    return value.isoformat() if value is not None else ""
+
+
+def _report_rows(report: dict[str, list]) -> list[list[str]]:
+    """Flatten a reconciliation report into CSV rows (excluding header)."""
+    rows = []
+    empty = ["", "", ""]
+
+    def cells(tx: dict) -> list[str]:
+        return [_format_date(tx["date"]), tx["description"], f"{tx['amount']:.2f}"]
+
+    for pair in report["matched"]:
+        rows.append(["matched"] + cells(pair["statement"]) + cells(pair["external"]))
+    for tx in report["statement_only"]:
+        rows.append(["statement_only"] + cells(tx) + empty)
+    for tx in report["external_only"]:
+        rows.append(["external_only"] + empty + cells(tx))
+    return rows
+
+
+def reconciliation_to_csv(report: dict[str, list], file_path: str) -> None:
+    """Write a reconciliation report to a CSV file, one row per matched pair
+    or unmatched transaction.
+
+    :param report: Report returned by reconcile
+    :param file_path: Path to the output CSV file
+    """
+    with atomic_open(file_path, newline="") as csvfile:
+        writer = csv.writer(csvfile)
+        writer.writerow(REPORT_CSV_HEADER)
+        writer.writerows(_report_rows(report))
+
+
+def reconciliation_to_text(report: dict[str, list]) -> str:
+    """Format a reconciliation report as human-readable text.
+
+    :param report: Report returned by reconcile
+    :return: Summary followed by the unmatched transactions
+    """
+    output = io.StringIO()
+    output.write(
+        f"Matched: {len(report['matched'])}, "
+        f"statement only: {len(report['statement_only'])}, "
+        f"external only: {len(report['external_only'])}\n"
+    )
+    if report["statement_only"]:
+        output.write("\nIn statement only:\n")
+        for tx in report["statement_only"]:
+            output.write(
+                f"  {_format_date(tx['date'])}  {tx['amount']:>12.2f}  "
+                f"{tx['description']}\n"
+            )
+    if report["external_only"]:
+        output.write("\nIn external CSV only:\n")
+        for tx in report["external_only"]:
+            output.write(
+                f"  {_format_date(tx['date'])}  {tx['amount']:>12.2f}  "
+                f"{tx['description']} (row {tx['row']})\n"
+            )
+    return output.getvalue()
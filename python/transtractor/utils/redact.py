@@ -0,0 +1,35 @@
+"""Redaction of statement data shared with third parties, as in the Rust library
+(see Redaction): account numbers are masked but for their last four characters,
+and text matching the redaction patterns is replaced in descriptions."""
+
+import re
+
+REDACTION_MASK = "****"
+
+
+def mask_account_number(account_number: str) -> str:
+    """Mask all but the last four characters of an account number, like "****5678"
+    for "062-000 1234 5678". An account number of four characters or fewer is masked
+    whole."""
+    account_number = account_number.strip()
+    if len(account_number) <= 4:
+        return REDACTION_MASK
+    return REDACTION_MASK + account_number[-4:]
+
+
+def redact_description(description: str, patterns: list[re.Pattern]) -> str:
+    """Replace the text of a description matching the patterns with the mask."""
+    description = description.strip()
+    for pattern in patterns:
+        description = pattern.sub(REDACTION_MASK, description)
+    return description.strip()
+
+
+def redaction_patterns(
+    redact: bool, redact_patterns: list[str] | None
+) -> list[str] | None:
+    """Redaction patterns to pass to the library, None if not redacting. Setting
+    redact_patterns also redacts."""
+    if not redact and not redact_patterns:
+        return None
+    return list(redact_patterns or [])
@@ -2,13 +2,20 @@
 
 import csv
 import logging
+import os
 import time
+import warnings
+from collections.abc import Callable, Iterator
+from concurrent.futures import ProcessPoolExecutor
+from concurrent.futures.process import BrokenProcessPool
+from dataclasses import dataclass, field
 from pathlib import Path
 from typing import TYPE_CHECKING, cast
 
-from ..exceptions import StatementNotSupported
+from ..exceptions import PdfExtractError, StatementNotSupported
 from ..structs.statement_data import StatementData
-from ..transtractor import NoErrorFreeStatementData
+from ..transtractor import ConfigAccessError, NoErrorFreeStatementData
+from .atomic import atomic_open
 from .extract import pdf_to_text_items
 
 if TYPE_CHECKING:
@@ -24,6 +31,8 @@ class TestData:
         self.num_pages: int = 0  # Number of pages in the PDF
         self.num_transactions: int = 0  # Number of transactions extracted
         self.config_keys: str = ""  # Config keys used for parsing
+        self.key: str = ""  # Key of the config giving the passing result
+        self.config_status: str = ""  # Status of the config(s) used for parsing
         self.extract_time: int = 0  # Time taken to extract text items from PDF
         self.identify_time: int = 0  # Time taken to identify config keys
         self.parse_time: int = 0  # Time taken for parsing in ms
@@ -39,6 +48,7 @@ class TestData:
             "Pages",
             "Transactions",
             "Config Keys",
+            "Config Status",
             "Extract Time (ms)",
             "Identify Time (ms)",
             "Parse Time (ms)",
@@ -64,6 +74,7 @@ class TestData:
             str(self.num_pages),
             str(self.num_transactions),
             self.config_keys,
+            self.config_status,
             str(self.extract_time),
             str(self.identify_time),
             str(self.parse_time),
@@ -81,15 +92,31 @@ class TestData:
             Path(self.pdf_file_path).as_posix(),
         ]
 
+    def __getstate__(self) -> dict:
+        """Drop the parser when pickled, e.g., to return results from a worker
+        process, as it cannot be pickled."""
+        state = self.__dict__.copy()
+        state["parser"] = None
+        return state
+
     def run(self) -> None:
         """Run the test on the PDF file using the provided parser."""
         start_total = time.time()
 
         # Extract text items
         start_extract = time.time()
-        py_text_items = pdf_to_text_items(self.pdf_file_path)
+        try:
+            py_text_items = pdf_to_text_items(self.pdf_file_path)
+        except PdfExtractError as e:
+            # Reason is the specific error, e.g., "PdfEncrypted"
+            self.status = "FAIL"
+            self.reason_failed = type(e).__name__
+            py_text_items = []
         end_extract = time.time()
         self.extract_time = int((end_extract - start_extract) * 1000)
+        if self.reason_failed:
+            self.total_time = int((end_extract - start_total) * 1000)
+            return
         if py_text_items:
             self.num_pages = py_text_items[-1]["page"] - py_text_items[0]["page"] + 1
 
@@ -119,10 +146,13 @@ class TestData:
                 ),
             )
             self.num_transactions = len(sd.transactions)
+            self.key = sd.key
+            self.config_status = sd.metadata["config_status"]
             self.status = "PASS"
-        except NoErrorFreeStatementData:
+        except NoErrorFreeStatementData as e:
             self.status = "FAIL"
             self.reason_failed = "NoErrorFreeStatementData"
+            self.config_status = ", ".join(b["config_status"] for b in e.breakdown)
         end_parse = time.time()
         self.parse_time = int((end_parse - start_parse) * 1000)
 
@@ -130,15 +160,296 @@ class TestData:
         self.total_time = int((end_total - start_total) * 1000)
 
 
+# Parser of a worker process of run_test_protocol
+_worker_parser: "Parser | None" = None
+
+
+def _init_worker(state: dict) -> None:
+    """Create the parser of a worker process from the state of the tested parser
+    (see Parser._worker_state), importing its configs and setting its categories."""
+    from ..parser import Parser
+
+    global _worker_parser
+    _worker_parser = Parser(
+        locale_filter=state["locale_filter"],
+        match_threshold=state["match_threshold"],
+    )
+    # Config warnings were already given when loaded into the tested parser
+    with warnings.catch_warnings():
+        warnings.simplefilter("ignore")
+        for json_str in state["configs"]:
+            _worker_parser._inner.import_config_from_json_str(json_str)
+    _worker_parser.set_categories(*state["categories"])
+
+
+def _run_worker_test(pdf_file: str) -> TestData:
+    """Test a PDF file with the parser of the worker process."""
+    test_data = TestData(pdf_file, cast("Parser", _worker_parser))
+    test_data.run()
+    return test_data
+
+
+# Sub-directory depth below the tested directory at which the search for PDFs stops,
+# as a safety net against deep or looping directory trees
+MAX_DIRECTORY_DEPTH = 32
+
+
+def find_pdf_files(
+    pdf_dir: str, max_depth: int = MAX_DIRECTORY_DEPTH
+) -> tuple[list[str], list[str]]:
+    """Find the PDF files in a directory and its sub-directories, following
+    symlinked directories. Each directory is searched once by its real path, so
+    symlink cycles (e.g., in synced drives) are skipped, as are directories deeper
+    than max_depth.
+
+    :param pdf_dir: Path to the directory to search
+    :param max_depth: Maximum depth of the sub-directories searched
+    :return: Sorted paths of the PDF files, and a note on each skipped directory
+    """
+    pdf_files: list[str] = []
+    skipped: list[str] = []
+    visited: set[str] = set()
+    pending = [(pdf_dir, 0)]
+    while pending:
+        directory, depth = pending.pop()
+        real_path = os.path.realpath(directory)
+        if real_path in visited:
+            skipped.append(
+                f"{Path(directory).as_posix()} (symlink cycle or repeated "
+                f"directory, already searched as {Path(real_path).as_posix()})"
+            )
+            continue
+        if depth > max_depth:
+            skipped.append(
+                f"{Path(directory).as_posix()} (deeper than {max_depth} directories)"
+            )
+            continue
+        visited.add(real_path)
+        with os.scandir(directory) as entries:
+            for entry in entries:
+                if entry.is_dir():
+                    pending.append((entry.path, depth + 1))
+                elif entry.is_file() and entry.name.endswith(".pdf"):
+                    pdf_files.append(entry.path)
+    return sorted(pdf_files), sorted(skipped)
+
+
+@dataclass
+class ProgressEvent:
+    """Progress of a directory test run (see run_test_protocol), emitted once each
+    PDF file is tested."""
+
+    # Path of the PDF file tested
+    pdf_file_path: str
+    # Number of the file in the run, from 1
+    index: int
+    # Number of PDF files found in the directory before the run
+    total: int
+    # Result of the file, with its status, transactions and reason failed
+    result: "TestData"
+    # Time in seconds since the start of the run
+    elapsed: float
+
+    @property
+    def status(self) -> str:
+        """Status of the file ("PASS" or "FAIL")."""
+        return self.result.status
+
+
+# Callback of a directory test run, called with each ProgressEvent. Returning False
+# cancels the run.
+ProgressCallback = Callable[[ProgressEvent], "bool | None"]
+
+
+def log_progress(logger: logging.Logger) -> ProgressCallback:
+    """Progress callback logging a line per tested file, the default of
+    run_test_protocol."""
+
+    def callback(event: ProgressEvent) -> None:
+        logger.info(
+            "%s/%s\t%s\t%s\t%sms\t%s",
+            event.index,
+            event.total,
+            event.result.status,
+            event.result.num_transactions,
+            event.result.total_time,
+            event.pdf_file_path,
+        )
+
+    return callback
+
+
+@dataclass
+class DirectoryReport:
+    """Summary of the test results of a directory of PDFs (see run_test_protocol).
+    Results are in file path order, so the summary does not depend on the order in
+    which the files were tested."""
+
+    results: list[TestData] = field(default_factory=list)
+    # Directories not searched for PDFs, with the reason (see find_pdf_files)
+    skipped_directories: list[str] = field(default_factory=list)
+    # The run was cancelled by its progress callback before all files were tested
+    cancelled: bool = False
+
+    @property
+    def num_files(self) -> int:
+        """Number of PDF files tested."""
+        return len(self.results)
+
+    @property
+    def num_passed(self) -> int:
+        """Number of PDF files parsed without errors."""
+        return sum(1 for result in self.results if result.status == "PASS")
+
+    @property
+    def num_failed(self) -> int:
+        """Number of PDF files that failed to parse, including unmatched files."""
+        return self.num_files - self.num_passed
+
+    @property
+    def key_counts(self) -> dict[str, dict[str, int]]:
+        """Number of "PASS" and "FAIL" results of each config key, sorted by key. A
+        pass counts for the config giving the result, and a failure for every config
+        identified as applicable."""
+        counts: dict[str, dict[str, int]] = {}
+        for result in self.results:
+            if result.status == "PASS":
+                keys = [result.key]
+            else:
+                keys = [key for key in result.config_keys.split(", ") if key]
+            for key in keys:
+                counts.setdefault(key, {"PASS": 0, "FAIL": 0})[result.status] += 1
+        return dict(sorted(counts.items()))
+
+    @property
+    def unmatched_files(self) -> list[str]:
+        """PDF files that matched no config (StatementNotSupported)."""
+        return [
+            result.pdf_file_path
+            for result in self.results
+            if result.reason_failed == "StatementNotSupported"
+        ]
+
+    @property
+    def unreadable_files(self) -> list[tuple[str, str]]:
+        """PDF files whose text could not be extracted, with the reason (e.g.,
+        "PdfEncrypted" or "PdfNoTextContent")."""
+        return [
+            (result.pdf_file_path, result.reason_failed)
+            for result in self.results
+            if result.reason_failed.startswith("Pdf")
+        ]
+
+    @property
+    def total_parse_time(self) -> int:
+        """Total parse time in ms, excluding text extraction and identification."""
+        return sum(result.parse_time for result in self.results)
+
+    @property
+    def average_parse_time(self) -> float:
+        """Average parse time in ms of the files matching a config."""
+        parsed = (
+            self.num_files - len(self.unmatched_files) - len(self.unreadable_files)
+        )
+        return self.total_parse_time / parsed if parsed else 0.0
+
+    def summary(self) -> str:
+        """Summary of the results as text lines: pass and fail totals, parse times,
+        the results of each config key, the unmatched files, the unreadable
+        files with the reason of each and the skipped directories."""
+        lines = [
+            f"Summary: {self.num_passed} passed, {self.num_failed} failed out of "
+            f"{self.num_files} files.",
+            f"Parse time: {self.total_parse_time}ms total, "
+            f"{self.average_parse_time:.0f}ms average.",
+        ]
+        if self.cancelled:
+            lines.append(f"Cancelled after {self.num_files} files.")
+        for key, counts in self.key_counts.items():
+            lines.append(f"{key}: {counts['PASS']} passed, {counts['FAIL']} failed")
+        if self.unmatched_files:
+            lines.append(f"No config matched {len(self.unmatched_files)} files:")
+            lines.extend(
+                f"  {Path(path).as_posix()}" for path in self.unmatched_files
+            )
+        if self.unreadable_files:
+            lines.append(f"Could not read {len(self.unreadable_files)} files:")
+            lines.extend(
+                f"  {Path(path).as_posix()} ({reason})"
+                for path, reason in self.unreadable_files
+            )
+        if self.skipped_directories:
+            lines.append(f"Skipped {len(self.skipped_directories)} directories:")
+            lines.extend(f"  {note}" for note in self.skipped_directories)
+        return "\n".join(lines)
+
+
+def _run_tests_serially(pdf_files: list[str], parser: "Parser") -> Iterator[TestData]:
+    """Test the PDF files one at a time with the given parser."""
+    for pdf_file in pdf_files:
+        test_data = TestData(pdf_file, parser)
+        test_data.run()
+        yield test_data
+
+
+def _run_tests_in_workers(
+    pdf_files: list[str],
+    parser: "Parser",
+    state: dict,
+    max_workers: int | None,
+    logger: logging.Logger,
+) -> Iterator[TestData]:
+    """Test the PDF files in worker processes created from the state of the given
+    parser. If the workers break, e.g., as a config cannot be imported from the
+    state in a new process, the files not yet tested are tested one at a time with
+    the given parser. Closing the iterator cancels the files not yet tested."""
+    tested = 0
+    try:
+        with ProcessPoolExecutor(
+            max_workers=max_workers,
+            initializer=_init_worker,
+            initargs=(state,),
+        ) as executor:
+            try:
+                for test_data in executor.map(_run_worker_test, pdf_files):
+                    tested += 1
+                    yield test_data
+            except GeneratorExit:
+                executor.shutdown(cancel_futures=True)
+                raise
+    except BrokenProcessPool as e:
+        logger.warning(f"Testing files one at a time, without workers: {e}")
+        yield from _run_tests_serially(pdf_files[tested:], parser)
+
+
 def run_test_protocol(
-    pdf_dir: str, parser: "Parser", output_file: str = "", log_level: str = "INFO"
-) -> list[TestData]:
+    pdf_dir: str,
+    parser: "Parser",
+    output_file: str = "",
+    log_level: str = "INFO",
+    max_workers: int | None = None,
+    progress: ProgressCallback | None = None,
+) -> DirectoryReport:
     """Run test protocol on all PDFs in a given directory and sub-directories.
+    Files are tested in parallel worker processes, each with a parser with the same
+    configs and categories as the given parser. If the state of the parser cannot be
+    sent to the workers (e.g., formats registered at runtime) or the workers break,
+    files are tested one at a time with the given parser. The
+    progress callback is called once each file is tested, in file path order, and
+    the summary is logged at the end. By default, each file is logged (see
+    log_progress).
 
     :param pdf_dir: Path to the directory containing PDF files to be tested
     :param parser: Parser instance to use for testing
     :param output_file: Optional path to output CSV file for test results
     :param log_level: Logging level (DEBUG, INFO, WARNING, ERROR, CRITICAL)
+    :param max_workers: Maximum number of worker processes. Defaults to the number
+        of CPUs. Set to 1 to test the files one at a time with the given parser,
+        without worker processes.
+    :param progress: Callback called with a ProgressEvent for each tested file, e.g.,
+        to update a progress bar. Returning False cancels the run: files not yet
+        tested are skipped, and the report has the results so far.
+    :return: Report of the results, in file path order
 
     Note: Set log_level to "WARNING" or higher to suppress terminal output.
     """
@@ -150,47 +461,59 @@ def run_test_protocol(
     logger = logging.getLogger()
 
     # Get all PDF files in the directory and sub-directories
-    pdf_files: list[str] = [str(p) for p in Path(pdf_dir).rglob("*.pdf")]
+    pdf_files, skipped_directories = find_pdf_files(pdf_dir)
     num_files = len(pdf_files)
-    num_passed = 0
-    num_failed = 0
-    test_results: list[TestData] = []
     log_header = "\t".join(["Test"] + TestData.get_header_log())
     logger.info(log_header)
+    if progress is None:
+        progress = log_progress(logger)
+    started = time.perf_counter()
 
-    file_count = 1
-    for pdf_file in pdf_files:
-        test_data = TestData(pdf_file, parser)
-        test_data.run()
-        test_results.append(test_data)
-        logger.info(
-            "%s/%s\t%s\t%s\t%sms\t%s",
-            file_count,
-            num_files,
-            test_data.status,
-            test_data.num_transactions,
-            test_data.total_time,
-            test_data.pdf_file_path,
+    def run_tests(results: Iterator[TestData]) -> tuple[list[TestData], bool]:
+        """Collect the test results in order, reporting the progress of each one.
+        Return the results and whether the run was cancelled."""
+        test_results: list[TestData] = []
+        for file_count, test_data in enumerate(results, start=1):
+            test_data.parser = parser
+            test_results.append(test_data)
+            event = ProgressEvent(
+                test_data.pdf_file_path,
+                file_count,
+                num_files,
+                test_data,
+                time.perf_counter() - started,
+            )
+            if progress(event) is False and file_count < num_files:
+                return test_results, True
+        return test_results, False
+
+    state = None
+    if max_workers != 1:
+        try:
+            state = parser._worker_state()
+        except ConfigAccessError as e:
+            logger.warning(f"Testing files one at a time, without workers: {e}")
+    if state is None:
+        results, cancelled = run_tests(_run_tests_serially(pdf_files, parser))
+    else:
+        worker_results = _run_tests_in_workers(
+            pdf_files, parser, state, max_workers, logger
         )
-        if test_data.status == "PASS":
-            num_passed += 1
-        else:
-            num_failed += 1
-        file_count += 1
+        try:
+            results, cancelled = run_tests(worker_results)
+        finally:
+            worker_results.close()
+    report = DirectoryReport(results, skipped_directories, cancelled)
 
     # Write results to output CSV file if specified
     if output_file:
-        with open(output_file, mode="w", newline="", encoding="utf-8") as csvfile:
+        with atomic_open(output_file, newline="") as csvfile:
             writer = csv.writer(csvfile)
             writer.writerow(TestData.get_header_all())
-            for result in test_results:
+            for result in report.results:
                 writer.writerow(result.get_all())
 
-    logger.info(
-        "Summary: %s passed, %s failed out of %s files.",
-        num_passed,
-        num_failed,
-        num_files,
-    )
+    for line in report.summary().splitlines():
+        logger.info(line)
 
-    return test_results
+    return report
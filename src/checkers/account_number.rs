@@ -0,0 +1,136 @@
+use crate::structs::StatementData;
+use regex::Regex;
+
+/// Normalise an account number for comparison by removing spaces and hyphens.
+fn normalise_account_number(account_number: &str) -> String {
+    account_number
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect()
+}
+
+/// Prefix of an expected account number given as a regex pattern
+const EXPECTED_ACCOUNT_PATTERN_PREFIX: &str = "re:";
+
+/// Check if an account number matches the expected value. The expected value is an
+/// account number, compared after removing spaces and hyphens, or a regex pattern
+/// prefixed with "re:" (e.g., "re:\d{12}4567") that must match the whole account
+/// number with or without them.
+pub fn account_number_matches(account_number: &str, expected: &str) -> bool {
+    let normalised = normalise_account_number(account_number);
+    let Some(pattern) = expected.strip_prefix(EXPECTED_ACCOUNT_PATTERN_PREFIX) else {
+        return normalised == normalise_account_number(expected);
+    };
+    match Regex::new(&format!("^(?:{})$", pattern)) {
+        Ok(re) => re.is_match(&normalised) || re.is_match(account_number),
+        Err(_) => false,
+    }
+}
+
+/// Check that the extracted account number matches the expected account number
+/// and log an error if it does not, or if no account number was extracted.
+pub fn check_expected_account_number(sd: &mut StatementData, expected: &str) {
+    match sd.account_number.clone() {
+        Some(account_number) => {
+            if !account_number_matches(&account_number, expected) {
+                sd.add_error(format!(
+                    "Account number mismatch: expected '{}', found '{}'",
+                    expected, account_number
+                ));
+            }
+        }
+        None => {
+            sd.add_error(format!(
+                "Account number mismatch: expected '{}', but no account number was extracted",
+                expected
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_expected_account_number_exact_match() {
+        let mut sd = StatementData::new();
+        sd.set_account_number("1234 5678 9123 4567".to_string());
+
+        check_expected_account_number(&mut sd, "1234 5678 9123 4567");
+        assert!(sd.errors.is_empty());
+    }
+
+    #[test]
+    fn test_check_expected_account_number_different_separators() {
+        let mut sd = StatementData::new();
+        sd.set_account_number("1234 5678 9123 4567".to_string());
+
+        check_expected_account_number(&mut sd, "1234-5678-9123-4567");
+        assert!(sd.errors.is_empty());
+
+        check_expected_account_number(&mut sd, "1234567891234567");
+        assert!(sd.errors.is_empty());
+    }
+
+    #[test]
+    fn test_check_expected_account_number_regex_match() {
+        let mut sd = StatementData::new();
+        sd.set_account_number("1234 5678 9123 4567".to_string());
+
+        check_expected_account_number(&mut sd, r"re:\d{12}4567");
+        assert!(sd.errors.is_empty());
+        check_expected_account_number(&mut sd, r"re:1234 5678 9123 \d{4}");
+        assert!(sd.errors.is_empty());
+    }
+
+    #[test]
+    fn test_check_expected_account_number_without_prefix_is_literal() {
+        let mut sd = StatementData::new();
+        sd.set_account_number("1234 5678 9123 4567".to_string());
+
+        check_expected_account_number(&mut sd, r"\d{12}4567");
+        check_expected_account_number(&mut sd, "1234.5678.9123.4567");
+        assert_eq!(sd.errors.len(), 2);
+    }
+
+    #[test]
+    fn test_check_expected_account_number_regex_must_match_whole() {
+        let mut sd = StatementData::new();
+        sd.set_account_number("1234 5678 9123 4567".to_string());
+
+        check_expected_account_number(&mut sd, "re:4567");
+        assert_eq!(sd.errors.len(), 1);
+    }
+
+    #[test]
+    fn test_check_expected_account_number_mismatch() {
+        let mut sd = StatementData::new();
+        sd.set_account_number("1234 5678 9123 4567".to_string());
+
+        check_expected_account_number(&mut sd, "9999 5678 9123 4567");
+        assert_eq!(sd.errors.len(), 1);
+        assert_eq!(
+            sd.errors[0],
+            "Account number mismatch: expected '9999 5678 9123 4567', found '1234 5678 9123 4567'"
+        );
+    }
+
+    #[test]
+    fn test_check_expected_account_number_missing() {
+        let mut sd = StatementData::new();
+
+        check_expected_account_number(&mut sd, "1234 5678 9123 4567");
+        assert_eq!(sd.errors.len(), 1);
+        assert!(sd.errors[0].contains("no account number was extracted"));
+    }
+
+    #[test]
+    fn test_check_expected_account_number_invalid_regex_is_mismatch() {
+        let mut sd = StatementData::new();
+        sd.set_account_number("1234".to_string());
+
+        check_expected_account_number(&mut sd, "re:(1234");
+        assert_eq!(sd.errors.len(), 1);
+    }
+}
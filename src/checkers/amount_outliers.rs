@@ -0,0 +1,107 @@
+use crate::structs::{Money, StatementConfig, StatementData};
+
+/// Fewest transaction amounts for the median amount to be meaningful
+const MIN_AMOUNTS: usize = 5;
+
+/// Check for transaction amounts above amount_outlier_multiple times the median
+/// transaction amount (ignoring sign), e.g., "45.00" misread as "4,500.00" after a
+/// dropped decimal point. Each outlier is logged as a warning naming the transaction,
+/// whether or not the balance check also catches it, so that the misread row is
+/// identified even when balances are computed rather than stated.
+pub fn check_amount_outliers(sd: &mut StatementData, config: &StatementConfig) {
+    let multiple = config.amount_outlier_multiple as i64;
+    if multiple == 0 {
+        return;
+    }
+    let mut amounts: Vec<i64> = sd
+        .proto_transactions
+        .iter()
+        .filter_map(|tx| tx.amount_money())
+        .map(|amount| amount.abs().cents())
+        .collect();
+    if amounts.len() < MIN_AMOUNTS {
+        return;
+    }
+    amounts.sort_unstable();
+    let mid = amounts.len() / 2;
+    let median = if amounts.len().is_multiple_of(2) {
+        (amounts[mid - 1] + amounts[mid]) / 2
+    } else {
+        amounts[mid]
+    };
+    if median == 0 {
+        return;
+    }
+
+    let mut warnings = Vec::new();
+    for (i, tx) in sd.proto_transactions.iter().enumerate() {
+        let Some(amount) = tx.amount_money() else {
+            continue;
+        };
+        if amount.abs().cents() <= multiple * median {
+            continue;
+        }
+        warnings.push(format!(
+            "Transaction {} ('{}') amount {} is {} times the median transaction amount {} (amount_outlier_multiple {}): possibly a misread decimal point, e.g., {}",
+            i + 1,
+            tx.description,
+            amount,
+            amount.abs().cents() / median,
+            Money::from_cents(median),
+            multiple,
+            Money::from_cents(amount.cents() / 100)
+        ));
+    }
+    for warning in warnings {
+        sd.add_warning(warning);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::ProtoTransaction;
+
+    fn statement(amounts: &[f64]) -> StatementData {
+        let mut sd = StatementData::new();
+        for (i, &amount) in amounts.iter().enumerate() {
+            sd.add_proto_transaction(ProtoTransaction {
+                description: format!("TX{}", i + 1),
+                amount: Some(amount),
+                ..Default::default()
+            });
+        }
+        sd
+    }
+
+    #[test]
+    fn test_outlier_is_reported_with_its_row() {
+        let mut sd = statement(&[-4.5, -12.8, -4500.0, -8.2, -30.0, -22.4]);
+        check_amount_outliers(&mut sd, &StatementConfig::default());
+        assert_eq!(
+            sd.warnings,
+            vec![
+                "Transaction 3 ('TX3') amount -4500.00 is 255 times the median transaction amount 17.60 (amount_outlier_multiple 200): possibly a misread decimal point, e.g., -45.00"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_outlier_few_amounts_or_disabled() {
+        let mut sd = statement(&[-4.5, -12.8, -45.0, -8.2, -30.0, 2500.0]);
+        check_amount_outliers(&mut sd, &StatementConfig::default());
+        assert!(sd.warnings.is_empty());
+
+        let mut sd = statement(&[-4.5, -4500.0, -8.2, -30.0]);
+        check_amount_outliers(&mut sd, &StatementConfig::default());
+        assert!(sd.warnings.is_empty());
+
+        let mut sd = statement(&[-4.5, -12.8, -4500.0, -8.2, -30.0, -22.4]);
+        let config = StatementConfig {
+            amount_outlier_multiple: 0,
+            ..Default::default()
+        };
+        check_amount_outliers(&mut sd, &config);
+        assert!(sd.warnings.is_empty());
+    }
+}
@@ -7,51 +7,58 @@ use crate::structs::StatementData;
 /// - Each calculated running balance matches the transaction's stated balance
 /// - The final calculated balance matches the statement's closing balance
 ///
-/// # Panics
-///
-/// Panics if required data is missing (this should not happen during runtime):
-/// - Any transaction is missing an amount or balance
+/// Balances are compared in integer cents, so a difference of a cent is found however
+/// many transactions are summed. Differences up to the balance_tolerance of the
+/// statement data are not errors.
 ///
+/// The balances are not checked if the opening balance is missing, and the final balance
+/// is not checked if the closing balance is missing, which are reported by check_fields
+/// (as an error only if the config requires it). Logs an error without checking the
+/// balances if any transaction is missing an amount or balance (normally caught by
+/// check_transaction_fields first). Transactions are named by their number and, if
+/// known, their page and position (see ProtoTransaction::label).
 pub fn check_balances(sd: &mut StatementData) {
-    // Log error and return if either balance is missing
-    if sd.opening_balance.is_none() || sd.closing_balance.is_none() {
-        sd.add_error("Cannot check balances if opening or closing balance is missing".to_string());
+    // A missing balance is reported by check_fields
+    let Some(mut running_balance) = sd.opening_balance_money() else {
+        return;
+    };
+
+    // Log error and return if any transaction is missing an amount or balance
+    if let Some((index, tx)) = sd
+        .proto_transactions
+        .iter()
+        .enumerate()
+        .find(|(_, tx)| tx.amount.is_none() || tx.balance.is_none())
+    {
+        sd.add_error(format!(
+            "Cannot check balances if {} is missing an amount or balance",
+            tx.label(index + 1).to_lowercase()
+        ));
         return;
     }
 
-    // Start with opening balance
-    let opening_balance = sd.opening_balance.unwrap();
-    let closing_balance = sd.closing_balance.unwrap();
-    let mut running_balance = opening_balance;
+    // Balances are summed and compared exactly in cents, within the tolerance
+    let tolerance = sd.balance_tolerance;
     let mut errors = Vec::new();
 
-    // Round to 2 decimal places to avoid floating point precision issues
-    running_balance = (running_balance * 100.0).round() / 100.0;
-
     // Check each transaction
     for (index, transaction) in sd.proto_transactions.iter().enumerate() {
-        // Panic if transaction data is missing
-        let transaction_amount = transaction.amount.unwrap_or_else(|| {
-            panic!("Transaction {index} must have an amount set before calling check_balances")
-        });
-
-        let transaction_balance = transaction.balance.unwrap_or_else(|| {
-            panic!("Transaction {index} must have a balance set before calling check_balances")
-        });
+        // Amount and balance are set, as checked above
+        let transaction_amount = transaction.amount_money().unwrap_or_default();
+        let transaction_balance = transaction.balance_money().unwrap_or_default();
 
         // Add transaction amount to running balance
         running_balance += transaction_amount;
 
-        // Round to 2 decimal places to avoid floating point precision issues
-        running_balance = (running_balance * 100.0).round() / 100.0;
-        let transaction_balance = (transaction_balance * 100.0).round() / 100.0;
-
         // Check if calculated balance matches transaction balance
-        if (running_balance - transaction_balance).abs() > 0.01 {
+        if !running_balance.within(transaction_balance, tolerance) {
             let difference = (running_balance - transaction_balance).abs();
             errors.push(format!(
-                "Transaction {} balance mismatch. Calculated: {:.2}, Stated: {:.2}, Difference: {:.2}",
-                index + 1, running_balance, transaction_balance, difference
+                "{} balance mismatch. Calculated: {}, Stated: {}, Difference: {}",
+                transaction.label(index + 1),
+                running_balance,
+                transaction_balance,
+                difference
             ));
         }
     }
@@ -61,12 +68,14 @@ pub fn check_balances(sd: &mut StatementData) {
         sd.add_error(error);
     }
 
-    // Check final balance against closing balance
-    let closing_balance = (closing_balance * 100.0).round() / 100.0;
-    if (running_balance - closing_balance).abs() > 0.01 {
+    // Check final balance against closing balance, if read
+    let Some(closing_balance) = sd.closing_balance_money() else {
+        return;
+    };
+    if !running_balance.within(closing_balance, tolerance) {
         let difference = (running_balance - closing_balance).abs();
         sd.add_error(format!(
-            "Final balance mismatch. Calculated: {:.2}, Stated: {:.2}, Difference: {:.2}",
+            "Final balance mismatch. Calculated: {}, Stated: {}, Difference: {}",
             running_balance, closing_balance, difference
         ));
     }
@@ -75,7 +84,7 @@ pub fn check_balances(sd: &mut StatementData) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::structs::ProtoTransaction;
+    use crate::structs::{Money, ProtoTransaction};
 
     /// Helper function to create a transaction with amount and balance
     fn create_transaction(amount: f64, balance: f64) -> ProtoTransaction {
@@ -86,46 +95,30 @@ mod tests {
     }
 
     #[test]
-    fn test_check_balances_missing_opening_balance() {
-        let mut sd = StatementData::new();
-        sd.set_closing_balance(1000.0);
-
-        check_balances(&mut sd);
-
-        assert_eq!(sd.errors.len(), 1);
-        assert!(
-            sd.errors[0].contains("Cannot check balances if opening or closing balance is missing")
-        );
-    }
-
-    #[test]
-    fn test_check_balances_missing_closing_balance() {
-        let mut sd = StatementData::new();
-        sd.set_opening_balance(1000.0);
-
-        check_balances(&mut sd);
-
-        assert_eq!(sd.errors.len(), 1);
-        assert!(
-            sd.errors[0].contains("Cannot check balances if opening or closing balance is missing")
-        );
-    }
-
-    #[test]
-    fn test_check_balances_missing_both_balances() {
-        let mut sd = StatementData::new();
-
-        check_balances(&mut sd);
-
-        assert_eq!(sd.errors.len(), 1);
-        assert!(
-            sd.errors[0].contains("Cannot check balances if opening or closing balance is missing")
-        );
+    fn test_check_balances_skipped_without_opening_or_closing_balance() {
+        // Without the closing balance, only the final balance is not checked
+        let row_mismatch = vec![
+            "Transaction 1 balance mismatch. Calculated: 1050.00, Stated: 2000.00, Difference: 950.00"
+                .to_string(),
+        ];
+        for (opening_balance, closing_balance, expected) in [
+            (None, Some(1000.0), vec![]),
+            (Some(1000.0), None, row_mismatch),
+            (None, None, vec![]),
+        ] {
+            let mut sd = StatementData::new();
+            sd.opening_balance = opening_balance;
+            sd.closing_balance = closing_balance;
+            sd.add_proto_transaction(create_transaction(50.0, 2000.0));
+
+            check_balances(&mut sd);
+
+            assert_eq!(sd.errors, expected);
+        }
     }
 
     #[test]
-    #[should_panic(expected = "Transaction 0 must have an amount set")]
-    fn test_check_balances_panic_missing_transaction_amount() {
+    fn test_check_balances_error_missing_transaction_amount() {
         let mut sd = StatementData::new();
         sd.set_opening_balance(1000.0);
         sd.set_closing_balance(900.0);
@@ -136,11 +129,15 @@ mod tests {
         sd.add_proto_transaction(tx);
 
         check_balances(&mut sd);
+
+        assert_eq!(
+            sd.errors,
+            vec!["Cannot check balances if transaction 1 is missing an amount or balance"]
+        );
     }
 
     #[test]
-    #[should_panic(expected = "Transaction 0 must have a balance set")]
-    fn test_check_balances_panic_missing_transaction_balance() {
+    fn test_check_balances_error_missing_transaction_balance() {
         let mut sd = StatementData::new();
         sd.set_opening_balance(1000.0);
         sd.set_closing_balance(900.0);
@@ -151,6 +148,11 @@ mod tests {
         sd.add_proto_transaction(tx);
 
         check_balances(&mut sd);
+
+        assert_eq!(
+            sd.errors,
+            vec!["Cannot check balances if transaction 1 is missing an amount or balance"]
+        );
     }
 
     #[test]
@@ -356,6 +358,83 @@ mod tests {
         assert_eq!(sd.errors.len(), 0);
     }
 
+    #[test]
+    fn test_check_balances_many_small_amounts_do_not_drift() {
+        let mut sd = StatementData::new();
+        sd.set_opening_balance(0.0);
+        sd.set_closing_balance(1000.0);
+
+        // Ten thousand stated balances of multiples of 0.1, as read from the statement
+        for i in 1..=10_000 {
+            sd.add_proto_transaction(create_transaction(
+                0.1,
+                format!("{}.{}", i / 10, i % 10).parse().unwrap(),
+            ));
+        }
+
+        check_balances(&mut sd);
+
+        assert_eq!(
+            sd.errors.len(),
+            0,
+            "{:?}",
+            &sd.errors[..sd.errors.len().min(3)]
+        );
+    }
+
+    #[test]
+    fn test_check_balances_tolerance_in_cents() {
+        let mut sd = StatementData::new();
+        sd.set_opening_balance(100.0);
+        sd.set_closing_balance(100.01);
+        sd.add_proto_transaction(create_transaction(-0.33, 99.68));
+
+        check_balances(&mut sd);
+        assert_eq!(
+            sd.errors,
+            vec![
+                "Transaction 1 balance mismatch. Calculated: 99.67, Stated: 99.68, Difference: 0.01",
+                "Final balance mismatch. Calculated: 99.67, Stated: 100.01, Difference: 0.34",
+            ]
+        );
+
+        sd.errors.clear();
+        sd.balance_tolerance = Money::from_cents(1);
+        check_balances(&mut sd);
+        assert_eq!(
+            sd.errors,
+            vec!["Final balance mismatch. Calculated: 99.67, Stated: 100.01, Difference: 0.34"]
+        );
+    }
+
+    #[test]
+    fn test_check_balances_reports_location() {
+        let mut sd = StatementData::new();
+        sd.set_opening_balance(1000.0);
+        sd.set_closing_balance(900.0);
+        let mut tx = create_transaction(-100.0, 950.0);
+        tx.page = Some(3);
+        tx.y1 = Some(412);
+        sd.add_proto_transaction(tx);
+
+        check_balances(&mut sd);
+
+        assert_eq!(
+            sd.errors[0],
+            "Transaction 1 (page 3, y≈412) balance mismatch. Calculated: 900.00, Stated: 950.00, Difference: 50.00"
+        );
+
+        sd.errors.clear();
+        sd.proto_transactions[0].amount = None;
+        check_balances(&mut sd);
+        assert_eq!(
+            sd.errors,
+            vec![
+                "Cannot check balances if transaction 1 (page 3, y≈412) is missing an amount or balance"
+            ]
+        );
+    }
+
     #[test]
     fn test_check_balances_large_numbers() {
         let mut sd = StatementData::new();
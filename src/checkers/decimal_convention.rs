@@ -0,0 +1,173 @@
+use crate::structs::{StatementConfig, StatementData};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Amounts below this value (ignoring sign) are suspiciously small when most amounts are
+const SMALL_AMOUNT: f64 = 10.0;
+
+/// Fraction of the amounts above which small amounts point to a misread decimal comma
+const MAX_SMALL_FRACTION: f64 = 0.5;
+
+static NUMERIC: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d").unwrap());
+static COMMA_DECIMAL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d,\d{2}-?$").unwrap());
+
+/// Fewest amounts for the fraction of small amounts to be meaningful
+const MIN_AMOUNTS: usize = 4;
+
+/// Parsers whose claimed text holds amounts
+const AMOUNT_PARSERS: [&str; 5] = [
+    "opening_balance",
+    "closing_balance",
+    "minimum_payment",
+    "transaction_amount",
+    "transaction_balance",
+];
+
+/// Check for a statement printed with a decimal comma (e.g., "1.234,56") parsed by a
+/// config reading a decimal point, which misreads the amounts by orders of magnitude.
+/// If most amounts are below 10 while most numeric tokens of the text claimed by the
+/// amount parsers end in a comma and two digits, a single error naming the config is
+/// logged and true returned, so that the balance check can be skipped rather than
+/// report a mismatch for every transaction.
+pub fn check_decimal_convention(sd: &mut StatementData, config: &StatementConfig) -> bool {
+    if config.decimal_separator == "," {
+        return false;
+    }
+
+    let amounts: Vec<f64> = [sd.opening_balance, sd.closing_balance]
+        .into_iter()
+        .chain(
+            sd.proto_transactions
+                .iter()
+                .flat_map(|tx| [tx.amount, tx.balance]),
+        )
+        .flatten()
+        .collect();
+    if amounts.len() < MIN_AMOUNTS {
+        return false;
+    }
+    let small = amounts.iter().filter(|a| a.abs() < SMALL_AMOUNT).count();
+    if (small as f64) <= MAX_SMALL_FRACTION * amounts.len() as f64 {
+        return false;
+    }
+
+    let tokens: Vec<&str> = sd
+        .item_claims
+        .iter()
+        .filter(|claim| AMOUNT_PARSERS.contains(&claim.parser.as_str()))
+        .flat_map(|claim| claim.text.split_whitespace())
+        .filter(|token| NUMERIC.is_match(token))
+        .collect();
+    let comma_tokens: Vec<&str> = tokens
+        .iter()
+        .copied()
+        .filter(|token| COMMA_DECIMAL.is_match(token))
+        .collect();
+    if (comma_tokens.len() as f64) <= 0.5 * tokens.len() as f64 {
+        return false;
+    }
+
+    sd.add_error(format!(
+        "Decimal convention mismatch: {} of {} amounts are below {} and {} of {} numeric tokens use a decimal comma (e.g., '{}'), but config {} reads a decimal point. Set decimal_separator to \",\" with a decimal comma format (format6), or use a config for this statement variant",
+        small,
+        amounts.len(),
+        SMALL_AMOUNT,
+        comma_tokens.len(),
+        tokens.len(),
+        comma_tokens[0],
+        config.key
+    ));
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::check_statement_data;
+    use crate::structs::{ItemClaim, ProtoTransaction, TextItem};
+
+    /// Statement data of a comma-decimal statement whose amounts and balances were
+    /// read three orders of magnitude too small, with the claimed text of each
+    fn misread_statement(texts: &[(&str, &str)]) -> StatementData {
+        let mut sd = StatementData::new();
+        sd.set_key("uk__bank__current__1".to_string());
+        let items: Vec<TextItem> = texts
+            .iter()
+            .map(|(_, text)| TextItem::new(text.to_string(), 0, 0, 10, 10, 0))
+            .collect();
+        for (i, (parser, _)) in texts.iter().enumerate() {
+            sd.add_item_claim(ItemClaim::new(parser, &items, i, i + 1));
+        }
+        sd.set_opening_balance(1.0);
+        for (date, amount, balance) in [(1, 1.234, 2.234), (2, -0.5, 1.734), (3, 2.0, 3.734)] {
+            let mut tx = ProtoTransaction::new();
+            tx.set_date(date);
+            tx.description = "PAYMENT".to_string();
+            tx.set_amount(amount);
+            tx.set_balance(balance);
+            sd.add_proto_transaction(tx);
+        }
+        sd.set_closing_balance(3.734);
+        sd
+    }
+
+    fn config() -> StatementConfig {
+        StatementConfig {
+            key: "uk__bank__current__1".to_string(),
+            ..StatementConfig::default()
+        }
+    }
+
+    const COMMA_TEXTS: [(&str, &str); 8] = [
+        ("opening_balance", "1.000,00"),
+        ("transaction_amount", "1.234,56"),
+        ("transaction_balance", "2.234,56"),
+        ("transaction_amount", "-500,00"),
+        ("transaction_balance", "1.734,56"),
+        ("transaction_amount", "2.000,00"),
+        ("transaction_balance", "3.734,56"),
+        ("closing_balance", "3.734,56"),
+    ];
+
+    #[test]
+    fn test_decimal_comma_statement_is_reported() {
+        let mut sd = misread_statement(&COMMA_TEXTS);
+        assert!(check_decimal_convention(&mut sd, &config()));
+        assert_eq!(sd.errors.len(), 1);
+        assert!(
+            sd.errors[0].starts_with(
+                "Decimal convention mismatch: 8 of 8 amounts are below 10 and 8 of 8 numeric tokens use a decimal comma (e.g., '1.000,00'), but config uk__bank__current__1 reads a decimal point."
+            ),
+            "{}",
+            sd.errors[0]
+        );
+    }
+
+    #[test]
+    fn test_decimal_comma_statement_skips_balance_errors() {
+        let mut sd = misread_statement(&COMMA_TEXTS);
+        sd.set_account_number("12345678".to_string());
+        sd.set_opening_balance(1.1);
+        check_statement_data(&mut sd, &config());
+        assert_eq!(sd.errors.len(), 1, "{:?}", sd.errors);
+        assert!(sd.errors[0].starts_with("Decimal convention mismatch"));
+    }
+
+    #[test]
+    fn test_small_decimal_point_amounts_are_not_reported() {
+        let texts = COMMA_TEXTS.map(|(parser, _)| (parser, "1.23"));
+        let mut sd = misread_statement(&texts);
+        assert!(!check_decimal_convention(&mut sd, &config()));
+        assert!(sd.errors.is_empty());
+    }
+
+    #[test]
+    fn test_comma_decimal_config_is_not_reported() {
+        let mut sd = misread_statement(&COMMA_TEXTS);
+        let config = StatementConfig {
+            decimal_separator: ",".to_string(),
+            ..config()
+        };
+        assert!(!check_decimal_convention(&mut sd, &config));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::structs::{ProtoTransaction, StatementData};
+
+/// Check for consecutive duplicate transactions, e.g., the last transaction of a page
+/// repeated at the top of the next page.
+///
+/// Genuinely repeated transactions (e.g., two identical purchases on the same day)
+/// have different balances, so are not duplicates. Each duplicate is logged as an
+/// error. Duplicates are dropped before the checks by fix_duplicates if the config
+/// sets drop_duplicate_transactions and fixers are applied.
+pub fn check_duplicates(sd: &mut StatementData) {
+    let mut messages: Vec<String> = Vec::new();
+    let mut prev: Option<&ProtoTransaction> = None;
+    for (i, tx) in sd.proto_transactions.iter().enumerate() {
+        if prev.is_some_and(|prev| tx.is_duplicate_of(prev)) {
+            messages.push(format!(
+                "Transaction {} ('{}') duplicates the previous transaction",
+                i + 1,
+                tx.description
+            ));
+        }
+        prev = Some(tx);
+    }
+    for message in messages {
+        sd.add_error(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_tx(date: i64, description: &str, amount: f64, balance: f64) -> ProtoTransaction {
+        ProtoTransaction {
+            date: Some(date),
+            description: description.to_string(),
+            amount: Some(amount),
+            balance: Some(balance),
+            ..Default::default()
+        }
+    }
+
+    fn make_statement(transactions: Vec<ProtoTransaction>) -> StatementData {
+        let mut sd = StatementData::new();
+        sd.proto_transactions = transactions;
+        sd
+    }
+
+    #[test]
+    fn test_repeated_row_is_error() {
+        let mut sd = make_statement(vec![
+            make_tx(1, "RENT", -120.0, 880.0),
+            make_tx(1, "RENT", -120.0, 880.0),
+            make_tx(2, "SALARY", 250.0, 1130.0),
+        ]);
+
+        check_duplicates(&mut sd);
+
+        assert_eq!(
+            sd.errors,
+            vec!["Transaction 2 ('RENT') duplicates the previous transaction"]
+        );
+        assert_eq!(sd.proto_transactions.len(), 3);
+    }
+
+    #[test]
+    fn test_identical_purchases_with_different_balances_are_kept() {
+        let mut sd = make_statement(vec![
+            make_tx(1, "CAFE COFFEE", -4.5, 95.5),
+            make_tx(1, "CAFE COFFEE", -4.5, 91.0),
+        ]);
+
+        check_duplicates(&mut sd);
+
+        assert!(sd.errors.is_empty());
+        assert!(sd.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_computed_balances_are_not_compared() {
+        // Balances computed from the amounts were not read from the statement, so do
+        // not show that a row was repeated
+        let mut transactions = vec![
+            make_tx(1, "CAFE COFFEE", -4.5, 95.5),
+            make_tx(1, "CAFE COFFEE", -4.5, 95.5),
+        ];
+        transactions[1].balance_computed = true;
+        let mut sd = make_statement(transactions);
+
+        check_duplicates(&mut sd);
+
+        assert!(sd.errors.is_empty());
+    }
+}
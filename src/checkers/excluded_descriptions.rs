@@ -0,0 +1,83 @@
+use crate::structs::StatementData;
+
+/// Fraction of transactions above which descriptions reduced to empty by the
+/// exclude patterns are an error rather than a warning
+const MAX_EXCLUDED_FRACTION: f64 = 0.5;
+
+/// Number of original descriptions quoted in the message
+const EXAMPLE_COUNT: usize = 3;
+
+/// Check for descriptions reduced to empty by transaction_description_exclude. A
+/// warning quoting examples of the original text is logged, escalated to an error
+/// if more than half of the transactions are affected, as an overly broad pattern
+/// would otherwise silently empty every description.
+pub fn check_excluded_descriptions(sd: &mut StatementData) {
+    let count = sd.excluded_descriptions.len();
+    if count == 0 {
+        return;
+    }
+    let examples = sd
+        .excluded_descriptions
+        .iter()
+        .take(EXAMPLE_COUNT)
+        .map(|desc| format!("'{}'", desc))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let message = format!(
+        "{} of {} transaction descriptions reduced to empty by transaction_description_exclude (e.g., {})",
+        count,
+        sd.proto_transactions.len(),
+        examples
+    );
+    if count as f64 > MAX_EXCLUDED_FRACTION * sd.proto_transactions.len() as f64 {
+        sd.add_error(message);
+    } else {
+        sd.add_warning(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::ProtoTransaction;
+
+    fn make_statement(transactions: usize, excluded: &[&str]) -> StatementData {
+        let mut sd = StatementData::new();
+        sd.proto_transactions = vec![ProtoTransaction::new(); transactions];
+        sd.excluded_descriptions = excluded.iter().map(|desc| desc.to_string()).collect();
+        sd
+    }
+
+    #[test]
+    fn test_no_excluded_descriptions() {
+        let mut sd = make_statement(4, &[]);
+        check_excluded_descriptions(&mut sd);
+        assert!(sd.errors.is_empty());
+        assert!(sd.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_few_excluded_descriptions_warn() {
+        let mut sd = make_statement(4, &["....", "...."]);
+        check_excluded_descriptions(&mut sd);
+        assert!(sd.errors.is_empty());
+        assert_eq!(
+            sd.warnings,
+            vec![
+                "2 of 4 transaction descriptions reduced to empty by transaction_description_exclude (e.g., '....', '....')"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_most_excluded_descriptions_error() {
+        let mut sd = make_statement(4, &["RENT", "SALARY", "GROCERIES", "FEE"]);
+        check_excluded_descriptions(&mut sd);
+        assert_eq!(
+            sd.errors,
+            vec![
+                "4 of 4 transaction descriptions reduced to empty by transaction_description_exclude (e.g., 'RENT', 'SALARY', 'GROCERIES')"
+            ]
+        );
+    }
+}
@@ -1,33 +1,71 @@
-use crate::structs::StatementData;
+use crate::structs::{StatementConfig, StatementData};
 
 /// Check if required fields are set in the statement data and log errors for missing fields.
 ///
 /// This function checks for the presence of critical statement fields and logs errors
-/// when they are missing. Unlike check_balances which panics on missing transaction data,
-/// this function gracefully logs issues for missing statement-level fields.
+/// when they are missing.
 ///
 /// Checks performed:
-/// - Opening balance is set
-/// - Closing balance is set
+/// - Account number, opening balance, closing balance, start date, minimum payment and
+///   payment due date are set, if in the required_fields of the config (the account
+///   number and balances by default)
 ///
 /// The function adds error messages to the statement data's error collection for any
-/// missing required fields.
-pub fn check_fields(sd: &mut StatementData) {
+/// missing required fields. Missing fields that are not required are added as a warning
+/// instead, except a field read from terms (e.g., the start date) if the config has
+/// none. If both the start and end dates are set, a warning is also added for each
+/// transaction dated outside the statement period, naming its page and position if
+/// known (see ProtoTransaction::label).
+pub fn check_fields(sd: &mut StatementData, config: &StatementConfig) {
     let mut missing_fields = Vec::new();
+    let mut missing_optional_fields = Vec::new();
 
-    // Check for account number
-    if sd.account_number.is_none() {
-        missing_fields.push("account number");
-    }
-
-    // Check for opening balance
-    if sd.opening_balance.is_none() {
-        missing_fields.push("opening balance");
-    }
-
-    // Check for closing balance
-    if sd.closing_balance.is_none() {
-        missing_fields.push("closing balance");
+    for (field, label, is_set, has_terms) in [
+        (
+            "account_number",
+            "account number",
+            sd.account_number.is_some(),
+            true,
+        ),
+        (
+            "opening_balance",
+            "opening balance",
+            sd.opening_balance.is_some(),
+            true,
+        ),
+        (
+            "closing_balance",
+            "closing balance",
+            sd.closing_balance.is_some(),
+            true,
+        ),
+        (
+            "start_date",
+            "start date",
+            sd.start_date.is_some(),
+            !config.start_date_terms.is_empty(),
+        ),
+        (
+            "minimum_payment",
+            "minimum payment",
+            sd.minimum_payment.is_some(),
+            !config.minimum_payment_terms.is_empty(),
+        ),
+        (
+            "payment_due_date",
+            "payment due date",
+            sd.payment_due_date.is_some(),
+            !config.payment_due_date_terms.is_empty(),
+        ),
+    ] {
+        if is_set {
+            continue;
+        }
+        if config.required_fields.iter().any(|f| f == field) {
+            missing_fields.push(label);
+        } else if has_terms {
+            missing_optional_fields.push(label);
+        }
     }
 
     // Log and add errors for missing fields
@@ -35,17 +73,88 @@ pub fn check_fields(sd: &mut StatementData) {
         let error_message = format!("Missing required fields: {}", missing_fields.join(", "));
         sd.add_error(error_message);
     }
+    if !missing_optional_fields.is_empty() {
+        sd.add_warning(format!(
+            "Missing optional fields: {}",
+            missing_optional_fields.join(", ")
+        ));
+    }
+
+    // Transactions should fall within the statement period
+    if let (Some(start_date), Some(end_date)) = (sd.start_date, sd.end_date) {
+        let format_date = |ms: i64| match sd.to_naive_date(ms) {
+            Some(date) => date.format("%d %b %Y").to_string(),
+            None => ms.to_string(),
+        };
+        let warnings: Vec<String> = sd
+            .proto_transactions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, tx)| {
+                let date = tx.date?;
+                (date < start_date || date > end_date).then(|| {
+                    format!(
+                        "{} dated {} is outside the statement period {} to {}",
+                        tx.label(index + 1),
+                        format_date(date),
+                        format_date(start_date),
+                        format_date(end_date)
+                    )
+                })
+            })
+            .collect();
+        for warning in warnings {
+            sd.add_warning(warning);
+        }
+    }
+}
+
+/// Check that every transaction has a date, description, amount and balance, and log
+/// an error for each incomplete transaction. These are normally filled in by the fixers,
+/// so are only expected to be missing if fixers are skipped.
+///
+/// Returns true if all transactions are complete.
+pub fn check_transaction_fields(sd: &mut StatementData) -> bool {
+    let mut errors = Vec::new();
+    for (index, tx) in sd.proto_transactions.iter().enumerate() {
+        let mut missing_fields = Vec::new();
+        if tx.date.is_none() {
+            missing_fields.push("date");
+        }
+        if !tx.has_description() {
+            missing_fields.push("description");
+        }
+        if tx.amount.is_none() {
+            missing_fields.push("amount");
+        }
+        if tx.balance.is_none() {
+            missing_fields.push("balance");
+        }
+        if !missing_fields.is_empty() {
+            errors.push(format!(
+                "{} is missing fields: {}",
+                tx.label(index + 1),
+                missing_fields.join(", ")
+            ));
+        }
+    }
+    let complete = errors.is_empty();
+    for error in errors {
+        sd.add_error(error);
+    }
+    complete
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::structs::ProtoTransaction;
 
     #[test]
     fn test_check_fields_all_missing() {
         let mut sd = StatementData::new();
 
-        check_fields(&mut sd);
+        check_fields(&mut sd, &StatementConfig::default());
         assert_eq!(sd.errors.len(), 1);
         assert!(
             sd.errors[0].contains(
@@ -59,7 +168,7 @@ mod tests {
         let mut sd = StatementData::new();
         sd.set_closing_balance(1000.0);
 
-        check_fields(&mut sd);
+        check_fields(&mut sd, &StatementConfig::default());
         assert_eq!(sd.errors.len(), 1);
         assert!(sd.errors[0].contains("Missing required fields: account number, opening balance"));
     }
@@ -69,7 +178,7 @@ mod tests {
         let mut sd = StatementData::new();
         sd.set_opening_balance(1000.0);
 
-        check_fields(&mut sd);
+        check_fields(&mut sd, &StatementConfig::default());
         assert_eq!(sd.errors.len(), 1);
         assert!(sd.errors[0].contains("Missing required fields: account number, closing balance"));
     }
@@ -81,7 +190,7 @@ mod tests {
         sd.set_opening_balance(1000.0);
         sd.set_closing_balance(900.0);
 
-        check_fields(&mut sd);
+        check_fields(&mut sd, &StatementConfig::default());
         assert_eq!(sd.errors.len(), 0);
     }
 
@@ -92,7 +201,7 @@ mod tests {
         sd.set_opening_balance(0.0);
         sd.set_closing_balance(0.0);
 
-        check_fields(&mut sd);
+        check_fields(&mut sd, &StatementConfig::default());
         assert_eq!(sd.errors.len(), 0);
     }
 
@@ -103,7 +212,7 @@ mod tests {
         sd.set_opening_balance(-500.0);
         sd.set_closing_balance(-200.0);
 
-        check_fields(&mut sd);
+        check_fields(&mut sd, &StatementConfig::default());
         assert_eq!(sd.errors.len(), 0);
     }
 
@@ -112,8 +221,8 @@ mod tests {
         let mut sd = StatementData::new();
 
         // Call check_fields twice
-        check_fields(&mut sd);
-        check_fields(&mut sd);
+        check_fields(&mut sd, &StatementConfig::default());
+        check_fields(&mut sd, &StatementConfig::default());
 
         // Should have 2 error entries (one from each call)
         assert_eq!(sd.errors.len(), 2);
@@ -128,4 +237,150 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_check_fields_required_fields_from_config() {
+        let mut sd = StatementData::new();
+        sd.set_opening_balance(1000.0);
+        let config = StatementConfig {
+            required_fields: vec!["opening_balance".to_string(), "start_date".to_string()],
+            start_date_terms: vec!["Statement period".to_string()],
+            ..Default::default()
+        };
+
+        check_fields(&mut sd, &config);
+        assert_eq!(
+            sd.errors,
+            vec!["Missing required fields: start date".to_string()]
+        );
+        assert_eq!(
+            sd.warnings,
+            vec!["Missing optional fields: account number, closing balance".to_string()]
+        );
+
+        // Nothing required, and no start date terms to read a start date
+        let mut sd = StatementData::new();
+        let config = StatementConfig {
+            required_fields: vec![],
+            ..Default::default()
+        };
+        check_fields(&mut sd, &config);
+        assert!(sd.errors.is_empty());
+        assert_eq!(
+            sd.warnings,
+            vec![
+                "Missing optional fields: account number, opening balance, closing balance"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_fields_credit_card_fields_optional() {
+        let mut sd = StatementData::new();
+        sd.set_account_number("1234 5678 9012".to_string());
+        sd.set_opening_balance(-500.0);
+        sd.set_closing_balance(-200.0);
+        let config = StatementConfig {
+            minimum_payment_terms: vec!["Minimum payment".to_string()],
+            payment_due_date_terms: vec!["Payment due".to_string()],
+            ..Default::default()
+        };
+
+        check_fields(&mut sd, &config);
+        assert!(sd.errors.is_empty());
+        assert_eq!(
+            sd.warnings,
+            vec!["Missing optional fields: minimum payment, payment due date".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_check_fields_credit_card_fields_required() {
+        let mut sd = StatementData::new();
+        sd.set_account_number("1234 5678 9012".to_string());
+        sd.set_opening_balance(-500.0);
+        sd.set_closing_balance(-200.0);
+        let mut config = StatementConfig {
+            minimum_payment_terms: vec!["Minimum payment".to_string()],
+            payment_due_date_terms: vec!["Payment due".to_string()],
+            ..Default::default()
+        };
+        config.required_fields.push("minimum_payment".to_string());
+        config.required_fields.push("payment_due_date".to_string());
+
+        check_fields(&mut sd, &config);
+        assert_eq!(
+            sd.errors,
+            vec!["Missing required fields: minimum payment, payment due date".to_string()]
+        );
+
+        let mut sd_set = sd.clone();
+        sd_set.errors.clear();
+        sd_set.set_minimum_payment(25.0);
+        sd_set.set_payment_due_date(0);
+        check_fields(&mut sd_set, &config);
+        assert!(sd_set.errors.is_empty());
+    }
+
+    #[test]
+    fn test_check_fields_transactions_outside_statement_period() {
+        let mut sd = StatementData::new();
+        sd.set_account_number("1234 5678 9012".to_string());
+        sd.set_opening_balance(1000.0);
+        sd.set_closing_balance(900.0);
+        sd.set_start_date(1_704_067_200_000); // 2024-01-01
+        sd.set_end_date(1_706_659_200_000); // 2024-01-31
+        for date in [1_704_067_200_000, 1_706_659_200_000, 1_706_745_600_000] {
+            let mut tx = ProtoTransaction::new();
+            tx.date = Some(date);
+            sd.add_proto_transaction(tx);
+        }
+
+        check_fields(&mut sd, &StatementConfig::default());
+        assert!(sd.errors.is_empty());
+        assert_eq!(
+            sd.warnings,
+            vec![
+                "Transaction 3 dated 01 Feb 2024 is outside the statement period 01 Jan 2024 to 31 Jan 2024"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_transaction_fields_complete() {
+        let mut sd = StatementData::new();
+        let mut tx = ProtoTransaction::new();
+        tx.date = Some(0);
+        tx.description = "Coffee".to_string();
+        tx.amount = Some(-4.5);
+        tx.balance = Some(95.5);
+        sd.add_proto_transaction(tx);
+
+        assert!(check_transaction_fields(&mut sd));
+        assert!(sd.errors.is_empty());
+    }
+
+    #[test]
+    fn test_check_transaction_fields_missing_balance() {
+        let mut sd = StatementData::new();
+        let mut tx = ProtoTransaction::new();
+        tx.date = Some(0);
+        tx.description = "Coffee".to_string();
+        tx.amount = Some(-4.5);
+        tx.page = Some(2);
+        tx.y1 = Some(301);
+        sd.add_proto_transaction(tx);
+        sd.add_proto_transaction(ProtoTransaction::new());
+
+        assert!(!check_transaction_fields(&mut sd));
+        assert_eq!(
+            sd.errors,
+            vec![
+                "Transaction 1 (page 2, y≈301) is missing fields: balance".to_string(),
+                "Transaction 2 is missing fields: date, description, amount, balance".to_string(),
+            ]
+        );
+    }
 }
@@ -0,0 +1,81 @@
+use crate::structs::StatementData;
+
+/// Check for text items claimed by both a statement-level parser and the
+/// transaction parser, and log a warning naming both parsers and the text.
+///
+/// This usually means a statement-level value (e.g. the opening balance) lies
+/// within the transaction table columns, so the same text can be read twice.
+pub fn check_item_claims(sd: &mut StatementData) {
+    let mut warnings = Vec::new();
+    for claim in sd.item_claims.iter().filter(|c| !c.is_transaction()) {
+        for other in sd.item_claims.iter().filter(|c| c.is_transaction()) {
+            if !claim.overlaps(other) {
+                continue;
+            }
+            let start = claim.start.max(other.start);
+            let end = claim.end.min(other.end);
+            warnings.push(format!(
+                "Text items {}..{} ('{}') on page {} consumed by {} parser were also claimed by {} parser",
+                start, end, claim.text, claim.page, claim.parser, other.parser
+            ));
+        }
+    }
+    for warning in warnings {
+        sd.add_warning(warning);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::{ItemClaim, TextItem};
+
+    fn make_items() -> Vec<TextItem> {
+        vec![
+            TextItem::new("Opening".to_string(), 0, 0, 10, 10, 0),
+            TextItem::new("balance".to_string(), 12, 0, 20, 10, 0),
+            TextItem::new("1,000.00".to_string(), 30, 0, 40, 10, 0),
+            TextItem::new("Payment".to_string(), 0, 20, 10, 30, 0),
+        ]
+    }
+
+    #[test]
+    fn test_check_item_claims_no_overlap() {
+        let items = make_items();
+        let mut sd = StatementData::new();
+        sd.add_item_claim(ItemClaim::new("opening_balance", &items, 0, 3));
+        sd.add_item_claim(ItemClaim::new("transaction_description", &items, 3, 4));
+
+        check_item_claims(&mut sd);
+        assert!(sd.warnings.is_empty());
+        assert!(sd.errors.is_empty());
+    }
+
+    #[test]
+    fn test_check_item_claims_overlap() {
+        let items = make_items();
+        let mut sd = StatementData::new();
+        sd.add_item_claim(ItemClaim::new("opening_balance", &items, 0, 2));
+        sd.add_item_claim(ItemClaim::new("opening_balance", &items, 2, 3));
+        sd.add_item_claim(ItemClaim::new("transaction_amount", &items, 2, 3));
+
+        check_item_claims(&mut sd);
+        assert_eq!(sd.warnings.len(), 1);
+        assert_eq!(
+            sd.warnings[0],
+            "Text items 2..3 ('1,000.00') on page 0 consumed by opening_balance parser were also claimed by transaction_amount parser"
+        );
+        assert!(sd.errors.is_empty());
+    }
+
+    #[test]
+    fn test_check_item_claims_ignores_statement_level_pairs() {
+        let items = make_items();
+        let mut sd = StatementData::new();
+        sd.add_item_claim(ItemClaim::new("opening_balance", &items, 0, 3));
+        sd.add_item_claim(ItemClaim::new("closing_balance", &items, 2, 3));
+
+        check_item_claims(&mut sd);
+        assert!(sd.warnings.is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+use crate::structs::{StatementConfig, StatementData};
+
+/// Multiple of the table line spacing above which transaction_new_line_tol can merge
+/// consecutive rows
+const MAX_TOL_TO_SPACING: f64 = 1.5;
+
+/// Fewest table lines for the number of transactions per line to be meaningful
+const MIN_TABLE_LINES: usize = 4;
+
+/// Check transaction_new_line_tol against the most common line spacing of the
+/// transaction table. A tolerance above 1.5 times the spacing reads consecutive rows
+/// as one line, merging rows into single transactions. A warning naming both values
+/// is logged, escalated to an error if at most about one transaction was parsed per
+/// two table lines.
+pub fn check_line_spacing(sd: &mut StatementData, config: &StatementConfig) {
+    if config.transaction_layout != "table" {
+        return;
+    }
+    let Some(spacing) = sd.table_lines.line_spacing else {
+        return;
+    };
+    let tol = config.transaction_new_line_tol;
+    if tol as f64 <= MAX_TOL_TO_SPACING * spacing as f64 {
+        return;
+    }
+
+    let lines = sd.table_lines.lines;
+    let count = sd.proto_transactions.len();
+    if lines >= MIN_TABLE_LINES && 2 * count <= lines + 1 {
+        sd.add_error(format!(
+            "Only {} transaction(s) parsed from {} transaction table lines: transaction_new_line_tol {} exceeds the table line spacing {}, so rows are likely merged. Set transaction_new_line_tol to approx. half the line spacing",
+            count, lines, tol, spacing
+        ));
+    } else {
+        sd.add_warning(format!(
+            "transaction_new_line_tol {} exceeds 1.5 times the transaction table line spacing {}, so consecutive rows may be read as one line",
+            tol, spacing
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::{ProtoTransaction, TableLines};
+
+    fn statement(transactions: usize, lines: usize) -> StatementData {
+        let mut sd = StatementData::new();
+        for _ in 0..transactions {
+            sd.add_proto_transaction(ProtoTransaction::new());
+        }
+        sd.table_lines = TableLines {
+            lines,
+            line_spacing: Some(11),
+        };
+        sd
+    }
+
+    fn config(tol: i32) -> StatementConfig {
+        StatementConfig {
+            transaction_new_line_tol: tol,
+            ..StatementConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_tolerance_within_line_spacing() {
+        let mut sd = statement(3, 3);
+        check_line_spacing(&mut sd, &config(5));
+        assert!(sd.warnings.is_empty());
+        assert!(sd.errors.is_empty());
+    }
+
+    #[test]
+    fn test_tolerance_above_line_spacing_is_reported() {
+        let mut sd = statement(6, 6);
+        check_line_spacing(&mut sd, &config(20));
+        assert_eq!(
+            sd.warnings,
+            vec![
+                "transaction_new_line_tol 20 exceeds 1.5 times the transaction table line spacing 11, so consecutive rows may be read as one line"
+            ]
+        );
+        assert!(sd.errors.is_empty());
+
+        let mut sd = statement(3, 6);
+        check_line_spacing(&mut sd, &config(20));
+        assert!(sd.warnings.is_empty());
+        assert_eq!(sd.errors.len(), 1);
+        assert!(
+            sd.errors[0].starts_with("Only 3 transaction(s) parsed from 6 transaction table lines: transaction_new_line_tol 20 exceeds the table line spacing 11"),
+            "{}",
+            sd.errors[0]
+        );
+    }
+}
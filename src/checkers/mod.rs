@@ -1,13 +1,58 @@
-use crate::structs::StatementData;
+use crate::structs::{StatementConfig, StatementData};
 
+pub mod account_number;
+pub mod amount_outliers;
 pub mod balances;
+pub mod decimal_convention;
+pub mod duplicates;
+pub mod excluded_descriptions;
 pub mod fields;
+pub mod item_claims;
+pub mod line_spacing;
+pub mod no_transactions;
+pub mod orphan_lines;
+pub mod page_coverage;
 
+pub use account_number::check_expected_account_number;
+pub use amount_outliers::check_amount_outliers;
 pub use balances::check_balances;
-pub use fields::check_fields;
+pub use decimal_convention::check_decimal_convention;
+pub use duplicates::check_duplicates;
+pub use excluded_descriptions::check_excluded_descriptions;
+pub use fields::{check_fields, check_transaction_fields};
+pub use item_claims::check_item_claims;
+pub use line_spacing::check_line_spacing;
+pub use no_transactions::check_no_transactions;
+pub use orphan_lines::check_orphan_lines;
+pub use page_coverage::check_page_coverage;
 
-/// Apply all checkers to the StatementData
-pub fn check_statement_data(statement: &mut StatementData) {
-    check_fields(statement);
-    check_balances(statement);
+/// Apply all checkers to the StatementData parsed with the given config
+pub fn check_statement_data(statement: &mut StatementData, config: &StatementConfig) {
+    check_fields(statement, config);
+    // Duplicates left by the fixers are flagged before they break the balance check
+    check_duplicates(statement);
+    // A decimal convention mismatch would otherwise be reported as a balance error for
+    // every transaction
+    if check_decimal_convention(statement, config) {
+        check_transaction_fields(statement);
+    } else {
+        check_transaction_values(statement);
+    }
+    // Independent of the balance check, which cannot pin a misread amount to its row
+    // when balances are computed
+    check_amount_outliers(statement, config);
+    check_item_claims(statement);
+    check_no_transactions(statement);
+    check_excluded_descriptions(statement);
+    check_page_coverage(statement);
+    check_line_spacing(statement, config);
+    check_orphan_lines(statement, config);
+}
+
+/// Apply the checkers of the transaction values: the transaction fields, then the
+/// balances, which can only be checked once every transaction is complete
+pub fn check_transaction_values(statement: &mut StatementData) {
+    if check_transaction_fields(statement) {
+        check_balances(statement);
+    }
 }
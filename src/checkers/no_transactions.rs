@@ -0,0 +1,55 @@
+use crate::structs::StatementData;
+
+/// Check the number of transactions against whether the statement declares that it
+/// has none, and log a warning if they disagree.
+pub fn check_no_transactions(sd: &mut StatementData) {
+    let count = sd.proto_transactions.len();
+    if sd.no_transactions_declared && count > 0 {
+        sd.add_warning(format!(
+            "Statement declares no transactions, but {} transaction(s) were parsed",
+            count
+        ));
+    } else if !sd.no_transactions_declared && count == 0 {
+        sd.add_warning(
+            "No transactions were parsed and the statement does not declare that there were none"
+                .to_string(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::ProtoTransaction;
+
+    #[test]
+    fn test_check_no_transactions_declared_and_none_parsed() {
+        let mut sd = StatementData::new();
+        sd.no_transactions_declared = true;
+
+        check_no_transactions(&mut sd);
+        assert!(sd.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_check_no_transactions_declared_but_parsed() {
+        let mut sd = StatementData::new();
+        sd.no_transactions_declared = true;
+        sd.add_proto_transaction(ProtoTransaction::new());
+
+        check_no_transactions(&mut sd);
+        assert_eq!(
+            sd.warnings,
+            vec!["Statement declares no transactions, but 1 transaction(s) were parsed"]
+        );
+    }
+
+    #[test]
+    fn test_check_no_transactions_undeclared_and_none_parsed() {
+        let mut sd = StatementData::new();
+
+        check_no_transactions(&mut sd);
+        assert_eq!(sd.warnings.len(), 1);
+        assert!(sd.errors.is_empty());
+    }
+}
@@ -0,0 +1,88 @@
+use crate::structs::{StatementConfig, StatementData};
+
+/// Number of orphan lines quoted in the message
+const EXAMPLE_COUNT: usize = 10;
+
+/// Check for lines of the transaction table not read by any parser, if the config is
+/// strict (transaction_strict). More than transaction_orphan_lines_max such lines is
+/// an error quoting the text and position of each, as the text may be a transaction
+/// that was silently skipped (e.g., a row misaligned with the column headers).
+pub fn check_orphan_lines(sd: &mut StatementData, config: &StatementConfig) {
+    if !config.transaction_strict {
+        return;
+    }
+    let count = sd.orphan_lines.len();
+    let max_lines = config.transaction_orphan_lines_max.max(0) as usize;
+    if count <= max_lines {
+        return;
+    }
+    let mut lines = sd
+        .orphan_lines
+        .iter()
+        .take(EXAMPLE_COUNT)
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>()
+        .join("; ");
+    if count > EXAMPLE_COUNT {
+        lines.push_str(&format!("; and {} more", count - EXAMPLE_COUNT));
+    }
+    sd.add_error(format!(
+        "{} line(s) of the transaction table not read by any parser, more than transaction_orphan_lines_max {}: {}",
+        count, max_lines, lines
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::OrphanLine;
+
+    fn make_statement(texts: &[&str]) -> StatementData {
+        let mut sd = StatementData::new();
+        sd.orphan_lines = texts
+            .iter()
+            .enumerate()
+            .map(|(n, text)| OrphanLine {
+                page: 0,
+                x1: 50,
+                y1: 100 + 12 * n as i32,
+                text: text.to_string(),
+            })
+            .collect();
+        sd
+    }
+
+    fn strict_config(max_lines: i32) -> StatementConfig {
+        StatementConfig {
+            transaction_strict: true,
+            transaction_orphan_lines_max: max_lines,
+            ..StatementConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_orphan_lines_ignored_unless_strict() {
+        let mut sd = make_statement(&["Page 2 of 3"]);
+        check_orphan_lines(&mut sd, &StatementConfig::default());
+        assert!(sd.errors.is_empty());
+    }
+
+    #[test]
+    fn test_orphan_lines_within_max() {
+        let mut sd = make_statement(&["Page 2 of 3"]);
+        check_orphan_lines(&mut sd, &strict_config(1));
+        assert!(sd.errors.is_empty());
+    }
+
+    #[test]
+    fn test_orphan_lines_above_max_error() {
+        let mut sd = make_statement(&["Page 2 of 3", "12/03 Refund 5.00"]);
+        check_orphan_lines(&mut sd, &strict_config(1));
+        assert_eq!(
+            sd.errors,
+            vec![
+                "2 line(s) of the transaction table not read by any parser, more than transaction_orphan_lines_max 1: page 0 at (50, 100): \"Page 2 of 3\"; page 0 at (50, 112): \"12/03 Refund 5.00\""
+            ]
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use crate::structs::StatementData;
+
+/// Format a sorted list of page numbers as ranges, e.g. "2-5, 7".
+fn format_page_ranges(pages: &[i32]) -> String {
+    let mut ranges: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < pages.len() {
+        let start = pages[i];
+        let mut end = start;
+        while i + 1 < pages.len() && pages[i + 1] == end + 1 {
+            i += 1;
+            end = pages[i];
+        }
+        if start == end {
+            ranges.push(start.to_string());
+        } else {
+            ranges.push(format!("{}-{}", start, end));
+        }
+        i += 1;
+    }
+    ranges.join(", ")
+}
+
+/// Check for pages within the transaction table that were silently ignored.
+///
+/// The transaction table spans from the first page with a transaction to the
+/// page with the transaction stop term (or the last page if no stop term was
+/// found). A warning is logged for any page in that span that has text items
+/// but none consumed by any parser.
+pub fn check_page_coverage(sd: &mut StatementData) {
+    let Some(first_page) = sd
+        .page_coverage
+        .iter()
+        .find(|coverage| coverage.transactions > 0)
+        .map(|coverage| coverage.page)
+    else {
+        return;
+    };
+    let Some(last_page) = sd
+        .transaction_stop_page
+        .or_else(|| sd.page_coverage.last().map(|coverage| coverage.page))
+    else {
+        return;
+    };
+
+    let ignored_pages: Vec<i32> = sd
+        .page_coverage
+        .iter()
+        .filter(|coverage| coverage.page > first_page && coverage.page <= last_page)
+        .filter(|coverage| coverage.text_items > 0 && coverage.consumed_items == 0)
+        .map(|coverage| coverage.page)
+        .collect();
+    if ignored_pages.is_empty() {
+        return;
+    }
+    let label = if ignored_pages.len() == 1 {
+        "Page"
+    } else {
+        "Pages"
+    };
+    sd.add_warning(format!(
+        "{} {} within the transaction table had no text items read by any parser",
+        label,
+        format_page_ranges(&ignored_pages)
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::PageCoverage;
+
+    fn make_coverage(page: i32, consumed_items: usize, transactions: usize) -> PageCoverage {
+        PageCoverage {
+            page,
+            text_items: 50,
+            consumed_items,
+            transactions,
+        }
+    }
+
+    #[test]
+    fn test_check_page_coverage_all_pages_read() {
+        let mut sd = StatementData::new();
+        sd.page_coverage = vec![
+            make_coverage(0, 40, 10),
+            make_coverage(1, 40, 10),
+            make_coverage(2, 40, 5),
+        ];
+
+        check_page_coverage(&mut sd);
+        assert!(sd.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_check_page_coverage_ignored_pages() {
+        let mut sd = StatementData::new();
+        sd.page_coverage = vec![
+            make_coverage(0, 40, 10),
+            make_coverage(1, 40, 10),
+            make_coverage(2, 0, 0),
+            make_coverage(3, 0, 0),
+            make_coverage(4, 0, 0),
+            make_coverage(5, 0, 0),
+        ];
+
+        check_page_coverage(&mut sd);
+        assert_eq!(
+            sd.warnings,
+            vec!["Pages 2-5 within the transaction table had no text items read by any parser"]
+        );
+        assert!(sd.errors.is_empty());
+    }
+
+    #[test]
+    fn test_check_page_coverage_ignores_pages_after_stop_term() {
+        let mut sd = StatementData::new();
+        sd.page_coverage = vec![
+            make_coverage(0, 40, 10),
+            make_coverage(1, 40, 10),
+            make_coverage(2, 0, 0),
+        ];
+        sd.transaction_stop_page = Some(1);
+
+        check_page_coverage(&mut sd);
+        assert!(sd.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_check_page_coverage_ignores_pages_before_transactions() {
+        let mut sd = StatementData::new();
+        sd.page_coverage = vec![make_coverage(0, 0, 0), make_coverage(1, 40, 10)];
+
+        check_page_coverage(&mut sd);
+        assert!(sd.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_format_page_ranges() {
+        assert_eq!(format_page_ranges(&[3]), "3");
+        assert_eq!(format_page_ranges(&[2, 3, 4, 7, 9, 10]), "2-4, 7, 9-10");
+    }
+}
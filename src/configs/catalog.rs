@@ -0,0 +1,224 @@
+use crate::configs::validate::status::EXAMPLE_STATUS;
+use crate::structs::StatementConfig;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Summary of a supported statement config for display to end users
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogEntry {
+    pub account_type: String,
+    pub key: String,
+    pub status: String,
+    pub account_examples: Vec<String>,
+}
+
+/// Supported statement configs grouped by country code (e.g., "AU") then bank name.
+/// Countries and banks are sorted alphabetically, and the entries of each bank by
+/// account type then key, so the order is deterministic.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Catalog {
+    pub countries: BTreeMap<String, BTreeMap<String, Vec<CatalogEntry>>>,
+}
+
+/// Summary of a registered config and where it was registered from, for listing the
+/// supported configs in applications
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigSummary {
+    pub key: String,
+    pub bank_name: String,
+    pub account_type: String,
+    pub account_examples: Vec<String>,
+    /// Country code from the key prefix (e.g., "AU")
+    pub country: String,
+    pub status: String,
+    /// "embedded" for a config packaged with the library, or "user" for one imported
+    /// by the user
+    pub source: String,
+}
+
+/// Get the country code of a config key from its prefix, e.g., "AU" for
+/// "au__cba__debit__1"
+fn key_country(key: &str) -> String {
+    key.split("__").next().unwrap_or_default().to_uppercase()
+}
+
+/// Summarise the embedded and user configs, sorted by key. A user config replaces the
+/// embedded config with the same key. Example configs are left out, as they are not
+/// supported statements.
+pub fn config_summaries(
+    embedded: &[StatementConfig],
+    user: &[StatementConfig],
+) -> Vec<ConfigSummary> {
+    let mut summaries: BTreeMap<&str, ConfigSummary> = BTreeMap::new();
+    for (configs, source) in [(embedded, "embedded"), (user, "user")] {
+        for cfg in configs.iter().filter(|cfg| cfg.status != EXAMPLE_STATUS) {
+            summaries.insert(
+                &cfg.key,
+                ConfigSummary {
+                    key: cfg.key.clone(),
+                    bank_name: cfg.bank_name.clone(),
+                    account_type: cfg.account_type.clone(),
+                    account_examples: cfg.account_examples.clone(),
+                    country: key_country(&cfg.key),
+                    status: cfg.status.clone(),
+                    source: source.to_string(),
+                },
+            );
+        }
+    }
+    summaries.into_values().collect()
+}
+
+/// Build the catalog of the given configs, leaving out example configs
+pub fn catalog(configs: &[StatementConfig]) -> Catalog {
+    let mut catalog = Catalog::default();
+    for cfg in configs.iter().filter(|cfg| cfg.status != EXAMPLE_STATUS) {
+        let country = key_country(&cfg.key);
+        catalog
+            .countries
+            .entry(country)
+            .or_default()
+            .entry(cfg.bank_name.clone())
+            .or_default()
+            .push(CatalogEntry {
+                account_type: cfg.account_type.clone(),
+                key: cfg.key.clone(),
+                status: cfg.status.clone(),
+                account_examples: cfg.account_examples.clone(),
+            });
+    }
+    for banks in catalog.countries.values_mut() {
+        for entries in banks.values_mut() {
+            entries.sort_by(|a, b| (&a.account_type, &a.key).cmp(&(&b.account_type, &b.key)));
+        }
+    }
+    catalog
+}
+
+impl fmt::Display for Catalog {
+    /// One line per bank listing its account types, under a line per country, e.g.
+    /// "AU" then "  Commonwealth Bank of Australia (Credit Card, Savings)"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (country, banks) in &self.countries {
+            writeln!(f, "{}", country)?;
+            for (bank_name, entries) in banks {
+                let mut account_types: Vec<&str> =
+                    entries.iter().map(|e| e.account_type.as_str()).collect();
+                account_types.dedup();
+                writeln!(f, "  {} ({})", bank_name, account_types.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_config(key: &str, bank_name: &str, account_type: &str) -> StatementConfig {
+        StatementConfig {
+            key: key.to_string(),
+            bank_name: bank_name.to_string(),
+            account_type: account_type.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_catalog_groups_and_sorts() {
+        let configs = vec![
+            make_config("au__nab__classic__1", "National Australia Bank", "Savings"),
+            make_config("au__cba__debit__1", "Commonwealth Bank", "Savings"),
+            make_config("nz__kiwi__credit__1", "Kiwi Bank", "Credit Card"),
+            make_config(
+                "au__cba__credit_card__1",
+                "Commonwealth Bank",
+                "Credit Card",
+            ),
+            make_config("au__cba__debit__2", "Commonwealth Bank", "Savings"),
+        ];
+        let result = catalog(&configs);
+
+        let countries: Vec<&String> = result.countries.keys().collect();
+        assert_eq!(countries, vec!["AU", "NZ"]);
+        let banks: Vec<&String> = result.countries["AU"].keys().collect();
+        assert_eq!(banks, vec!["Commonwealth Bank", "National Australia Bank"]);
+        let keys: Vec<&str> = result.countries["AU"]["Commonwealth Bank"]
+            .iter()
+            .map(|e| e.key.as_str())
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                "au__cba__credit_card__1",
+                "au__cba__debit__1",
+                "au__cba__debit__2"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_config_summaries_mark_user_configs() {
+        let embedded = vec![
+            make_config("au__cba__debit__1", "Commonwealth Bank", "Savings"),
+            make_config("au__anz__debit__1", "ANZ", "Savings"),
+        ];
+        let user = vec![
+            make_config("au__cba__debit__1", "Commonwealth Bank", "Everyday"),
+            make_config("zz__example__checking__1", "Example Bank", "Checking"),
+        ];
+
+        let summaries = config_summaries(&embedded, &user);
+
+        let listed: Vec<(&str, &str, &str)> = summaries
+            .iter()
+            .map(|s| (s.key.as_str(), s.account_type.as_str(), s.source.as_str()))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("au__anz__debit__1", "Savings", "embedded"),
+                ("au__cba__debit__1", "Everyday", "user"),
+                ("zz__example__checking__1", "Checking", "user"),
+            ]
+        );
+        assert_eq!(summaries[2].country, "ZZ");
+    }
+
+    #[test]
+    fn test_example_configs_are_not_listed() {
+        let mut example = make_config("zz__example__checking__1", "Example Bank", "Checking");
+        example.status = EXAMPLE_STATUS.to_string();
+        let configs = vec![
+            make_config("au__cba__debit__1", "Commonwealth Bank", "Savings"),
+            example,
+        ];
+
+        let result = catalog(&configs);
+        let countries: Vec<&String> = result.countries.keys().collect();
+        assert_eq!(countries, vec!["AU"]);
+        let keys: Vec<String> = config_summaries(&[], &configs)
+            .into_iter()
+            .map(|s| s.key)
+            .collect();
+        assert_eq!(keys, vec!["au__cba__debit__1"]);
+    }
+
+    #[test]
+    fn test_catalog_display() {
+        let configs = vec![
+            make_config("au__cba__debit__1", "Commonwealth Bank", "Savings"),
+            make_config("au__cba__debit__2", "Commonwealth Bank", "Savings"),
+            make_config(
+                "au__cba__credit_card__1",
+                "Commonwealth Bank",
+                "Credit Card",
+            ),
+        ];
+        assert_eq!(
+            catalog(&configs).to_string(),
+            "AU\n  Commonwealth Bank (Credit Card, Savings)\n"
+        );
+    }
+}
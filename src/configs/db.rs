@@ -1,3 +1,6 @@
+use crate::configs::catalog::{Catalog, catalog};
+use crate::formats::amount::{AmountFormat, register_amount_format};
+use crate::formats::date::{DateFormat, register_date_format};
 use crate::parsers::flows::config_json_file_to_config::from_json_file;
 use crate::parsers::flows::config_json_file_to_config::from_json_str;
 use crate::structs::StatementConfig;
@@ -94,12 +97,25 @@ impl ConfigDB {
         Err(format!("Config with key '{}' not found", key))
     }
 
+    /// Get the JSON of a registered config with every field set, including defaults
+    /// (see StatementConfig::to_json_str). Unlike get_config_json_str, also available
+    /// for configs registered from strings without str_caching.
+    pub fn get_config_json(&self, key: &str) -> Result<String, String> {
+        self.get_config(key)?.to_json_str()
+    }
+
     /// Get list of all account_terms from all registered configs by key.
     pub fn get_account_terms(&self, key: &str) -> Result<Vec<String>, String> {
         let cfg = self.get_config(key)?;
         Ok(cfg.account_terms)
     }
 
+    /// Get whether the terms of a registered config are matched ignoring case.
+    pub fn get_terms_case_insensitive(&self, key: &str) -> Result<bool, String> {
+        let cfg = self.get_config(key)?;
+        Ok(cfg.terms_case_insensitive)
+    }
+
     /// Get list of all registered config keys.
     pub fn get_config_keys(&self) -> Vec<String> {
         let mut keys: Vec<String> = self.config_paths.keys().cloned().collect();
@@ -120,4 +136,133 @@ impl ConfigDB {
         }
         self.config_paths.contains_key(key)
     }
+
+    /// Get all registered configs, sorted by key.
+    pub fn get_configs(&self) -> Result<Vec<StatementConfig>, String> {
+        let mut keys = self.get_config_keys();
+        keys.sort();
+        keys.iter().map(|key| self.get_config(key)).collect()
+    }
+
+    /// Get the catalog of all registered configs, grouped by country and bank.
+    pub fn catalog(&self) -> Result<Catalog, String> {
+        Ok(catalog(&self.get_configs()?))
+    }
+
+    /// Register a custom amount format under a name, for configs registered afterwards
+    /// to list alongside the built-in formats. The format registry is process-wide
+    /// (see formats::amount::register_amount_format).
+    pub fn register_amount_format(
+        &self,
+        name: &str,
+        format: Box<dyn AmountFormat + Send + Sync>,
+    ) -> Result<(), String> {
+        register_amount_format(name, format)
+    }
+
+    /// Register a custom date format under a name, for configs registered afterwards
+    /// to list alongside the built-in formats. The format registry is process-wide
+    /// (see formats::date::register_date_format).
+    pub fn register_date_format(
+        &self,
+        name: &str,
+        format: Box<dyn DateFormat + Send + Sync>,
+    ) -> Result<(), String> {
+        register_date_format(name, format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixers::FixersMode;
+    use crate::parsers::flows::layout_to_text_items::layout_to_text_items;
+    use crate::parsers::flows::text_items_to_statement_datas::text_items_to_statement_datas;
+    use crate::test_fixtures::patch;
+
+    const CONFIG: &str = include_str!("../../tests/fixtures/test3_config.json");
+    const LAYOUT: &str = include_str!("../../tests/fixtures/test3_layout.txt");
+
+    #[test]
+    fn test_get_config_json_round_trips() {
+        let mut db = ConfigDB::new(true, false);
+        db.register_from_str(CONFIG).unwrap();
+        let config = db.get_config("au__ssb__savings__1").unwrap();
+
+        let json_str = db.get_config_json("au__ssb__savings__1").unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json_str).unwrap();
+        // Defaults are included
+        assert_eq!(value["transaction_strict"], false);
+        assert!(db.get_config_json_str("au__ssb__savings__1").is_err());
+
+        let reloaded = from_json_str(&json_str).unwrap();
+        assert_eq!(format!("{:?}", reloaded), format!("{:?}", config));
+    }
+
+    /// Amounts with a "CR" or "DR" suffix and no space, like "120.00DR"
+    struct SuffixedAmount;
+
+    impl AmountFormat for SuffixedAmount {
+        fn num_items(&self) -> usize {
+            1
+        }
+
+        fn parse(&self, input: &str) -> Option<f64> {
+            let (value, sign) = match input.split_at_checked(input.len().checked_sub(2)?)? {
+                (value, "CR") => (value, 1.0),
+                (value, "DR") => (value, -1.0),
+                _ => return None,
+            };
+            Some(sign * value.parse::<f64>().ok()?)
+        }
+    }
+
+    #[test]
+    fn test_registered_amount_format_parses_statement() {
+        let config = patch(
+            CONFIG,
+            &[(
+                "\"transaction_amount_formats\": [\"format1\"]",
+                "\"transaction_amount_formats\": [\"db_test_suffixed\"]",
+            )],
+        );
+        let layout = patch(
+            LAYOUT,
+            &[
+                ("\"250.00\"", "\"250.00CR\""),
+                ("\"-120.00\"", "\"120.00DR\""),
+                ("\"-24.50\"", "\"24.50DR\""),
+            ],
+        );
+        let mut db = ConfigDB::new(true, false);
+        let error = db.register_from_str(&config).unwrap_err();
+        assert!(
+            error.starts_with(
+                "Config validation error: Invalid transaction_amount_formats. Invalid amount format: 'db_test_suffixed'"
+            ),
+            "{}",
+            error
+        );
+
+        db.register_amount_format("db_test_suffixed", Box::new(SuffixedAmount))
+            .unwrap();
+        db.register_from_str(&config).unwrap();
+        let items = layout_to_text_items(&layout).unwrap();
+        let datas = text_items_to_statement_datas(
+            &items,
+            &db.get_configs().unwrap(),
+            FixersMode::All,
+            false,
+            false,
+        )
+        .unwrap();
+
+        assert!(datas[0].errors.is_empty(), "{:?}", datas[0].errors);
+        let amounts: Vec<Option<f64>> = datas[0]
+            .proto_transactions
+            .iter()
+            .map(|tx| tx.amount)
+            .collect();
+        assert_eq!(amounts, vec![Some(250.0), Some(-120.0), Some(-24.5)]);
+    }
 }
@@ -0,0 +1,352 @@
+use crate::configs::validate::utils::normalize_term;
+use crate::parsers::transaction::utils::parse_format_field;
+use crate::structs::StatementConfig;
+use std::fmt;
+
+/// Likely mistake found by the cross-field checks of a config (see lint_config). Unlike
+/// validation errors, the config can still be registered and used.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintWarning {
+    /// Config field the warning is about (e.g., "transaction_balance_headers")
+    pub field: String,
+    pub message: String,
+}
+
+impl LintWarning {
+    fn new(field: &str, message: String) -> Self {
+        Self {
+            field: field.to_string(),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Check a config for combinations of field values that are each valid but unlikely to
+/// parse as intended (see validate_config for the checks of each field in isolation):
+/// - transaction_formats fields without headers or formats to read them
+/// - transaction formats listed for a field that no transaction format includes
+/// - alignments with an alignment tolerance of zero
+/// - transaction_start_date_required without start_date_terms
+pub fn lint_config(config: &StatementConfig) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+    lint_transaction_fields(config, &mut warnings);
+    lint_alignment_tols(config, &mut warnings);
+    if config.transaction_start_date_required && config.start_date_terms.is_empty() {
+        warnings.push(LintWarning::new(
+            "transaction_start_date_required",
+            "Set, but start_date_terms is empty, so no transactions can be read".to_string(),
+        ));
+    }
+    warnings
+}
+
+/// Check that the account terms of a config are not all account terms of another
+/// config, in which case every statement of the other config is also identified as
+/// this config
+pub fn lint_account_terms(
+    config: &StatementConfig,
+    others: &[StatementConfig],
+) -> Vec<LintWarning> {
+    if config.account_terms.is_empty() {
+        return Vec::new();
+    }
+    let terms: Vec<String> = config
+        .account_terms
+        .iter()
+        .map(|term| normalize_term(term))
+        .collect();
+    others
+        .iter()
+        .filter(|other| other.key != config.key)
+        .filter(|other| {
+            let other_terms: Vec<String> = other
+                .account_terms
+                .iter()
+                .map(|term| normalize_term(term))
+                .collect();
+            terms.iter().all(|term| other_terms.contains(term))
+        })
+        .map(|other| {
+            LintWarning::new(
+                "account_terms",
+                format!(
+                    "All terms are also account_terms of {}, so its statements are \
+                     identified as this config too. Add a term unique to this statement type",
+                    other.key
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Check the fields of the transaction formats against their headers and formats
+fn lint_transaction_fields(config: &StatementConfig, warnings: &mut Vec<LintWarning>) {
+    let used = |field: &str| {
+        config
+            .transaction_formats
+            .iter()
+            .any(|format| format.iter().any(|f| parse_format_field(f).0 == field))
+    };
+    for (field, headers, formats) in [
+        (
+            "date",
+            &config.transaction_date_headers,
+            Some(&config.transaction_date_formats),
+        ),
+        ("description", &config.transaction_description_headers, None),
+        (
+            "amount",
+            &config.transaction_amount_headers,
+            Some(&config.transaction_amount_formats),
+        ),
+        (
+            "balance",
+            &config.transaction_balance_headers,
+            Some(&config.transaction_balance_formats),
+        ),
+    ] {
+        if !used(field) {
+            if formats.is_some_and(|formats| !formats.is_empty()) {
+                warnings.push(LintWarning::new(
+                    &format!("transaction_{}_formats", field),
+                    format!(
+                        "Never used, as no transaction_formats include \"{}\"",
+                        field
+                    ),
+                ));
+            }
+            continue;
+        }
+        let positioned = config.get_transaction_column_position(field).is_some();
+        // Amounts can be read from debit and credit columns instead
+        let debit_credit = field == "amount" && !config.transaction_debit_headers.is_empty();
+        if headers.is_empty() && !positioned && !debit_credit {
+            warnings.push(LintWarning::new(
+                &format!("transaction_{}_headers", field),
+                format!(
+                    "Empty, but transaction_formats include \"{}\" and \
+                     transaction_columns_by_position has no \"{}\" column",
+                    field, field
+                ),
+            ));
+        }
+        if formats.is_some_and(|formats| formats.is_empty()) {
+            warnings.push(LintWarning::new(
+                &format!("transaction_{}_formats", field),
+                format!("Empty, but transaction_formats include \"{}\"", field),
+            ));
+        }
+    }
+}
+
+/// Check for alignments whose tolerance of zero only matches exactly aligned values
+fn lint_alignment_tols(config: &StatementConfig, warnings: &mut Vec<LintWarning>) {
+    for (field, terms, alignment, tol) in [
+        (
+            "account_number",
+            &config.account_number_terms,
+            &config.account_number_alignment,
+            config.account_number_alignment_tol,
+        ),
+        (
+            "opening_balance",
+            &config.opening_balance_terms,
+            &config.opening_balance_alignment,
+            config.opening_balance_alignment_tol,
+        ),
+        (
+            "closing_balance",
+            &config.closing_balance_terms,
+            &config.closing_balance_alignment,
+            config.closing_balance_alignment_tol,
+        ),
+        (
+            "start_date",
+            &config.start_date_terms,
+            &config.start_date_alignment,
+            config.start_date_alignment_tol,
+        ),
+        (
+            "end_date",
+            &config.end_date_terms,
+            &config.end_date_alignment,
+            config.end_date_alignment_tol,
+        ),
+        (
+            "minimum_payment",
+            &config.minimum_payment_terms,
+            &config.minimum_payment_alignment,
+            config.minimum_payment_alignment_tol,
+        ),
+        (
+            "payment_due_date",
+            &config.payment_due_date_terms,
+            &config.payment_due_date_alignment,
+            config.payment_due_date_alignment_tol,
+        ),
+    ] {
+        if !terms.is_empty() && !alignment.is_empty() && tol == 0 {
+            warnings.push(zero_tol_warning(
+                &format!("{}_alignment_tol", field),
+                alignment,
+            ));
+        }
+    }
+
+    for (field, alignment, own_tol) in [
+        (
+            "date",
+            &config.transaction_date_alignment,
+            config.transaction_date_alignment_tol,
+        ),
+        (
+            "description",
+            &config.transaction_description_alignment,
+            config.transaction_description_alignment_tol,
+        ),
+        (
+            "amount",
+            &config.transaction_amount_alignment,
+            config.transaction_amount_alignment_tol,
+        ),
+        (
+            "balance",
+            &config.transaction_balance_alignment,
+            config.transaction_balance_alignment_tol,
+        ),
+    ] {
+        let used = config
+            .transaction_formats
+            .iter()
+            .any(|format| format.iter().any(|f| parse_format_field(f).0 == field));
+        if !used
+            || alignment.is_empty()
+            || config.get_transaction_column_position(field).is_some()
+            || config.get_transaction_alignment_tol(field) != 0
+        {
+            continue;
+        }
+        let tol_field = match own_tol {
+            Some(_) => format!("transaction_{}_alignment_tol", field),
+            None => "transaction_alignment_tol".to_string(),
+        };
+        warnings.push(zero_tol_warning(&tol_field, alignment));
+    }
+}
+
+fn zero_tol_warning(field: &str, alignment: &str) -> LintWarning {
+    LintWarning::new(
+        field,
+        format!(
+            "Zero with \"{}\" alignment, so only values exactly aligned to the {} of \
+             their term or header are read",
+            alignment, alignment
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_config() -> StatementConfig {
+        StatementConfig {
+            key: "au__bank__savings__1".to_string(),
+            account_terms: vec!["Bank".to_string(), "Savings Statement".to_string()],
+            start_date_terms: vec!["Period".to_string()],
+            transaction_formats: vec![vec![
+                "date".to_string(),
+                "description".to_string(),
+                "amount".to_string(),
+            ]],
+            transaction_date_formats: vec!["format4".to_string()],
+            transaction_date_headers: vec!["Date".to_string()],
+            transaction_description_headers: vec!["Description".to_string()],
+            transaction_amount_formats: vec!["format1".to_string()],
+            transaction_amount_headers: vec!["Amount".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn fields(warnings: &[LintWarning]) -> Vec<&str> {
+        warnings.iter().map(|w| w.field.as_str()).collect()
+    }
+
+    #[test]
+    fn test_consistent_config_has_no_warnings() {
+        assert_eq!(lint_config(&make_config()), vec![]);
+    }
+
+    #[test]
+    fn test_transaction_fields_without_headers_or_formats() {
+        let mut config = make_config();
+        config.transaction_formats[0].push("balance".to_string());
+        config.transaction_amount_formats.clear();
+        assert_eq!(
+            fields(&lint_config(&config)),
+            vec![
+                "transaction_amount_formats",
+                "transaction_balance_headers",
+                "transaction_balance_formats"
+            ]
+        );
+
+        // Formats of a field in no transaction format are never used
+        let mut config = make_config();
+        config.transaction_balance_formats = vec!["format1".to_string()];
+        let warnings = lint_config(&config);
+        assert_eq!(fields(&warnings), vec!["transaction_balance_formats"]);
+        assert!(warnings[0].to_string().contains("Never used"));
+
+        // Amounts from debit and credit columns need no amount headers
+        let mut config = make_config();
+        config.transaction_amount_headers.clear();
+        config.transaction_debit_headers = vec!["Debit".to_string()];
+        config.transaction_credit_headers = vec!["Credit".to_string()];
+        assert_eq!(lint_config(&config), vec![]);
+    }
+
+    #[test]
+    fn test_zero_alignment_tol_and_start_date_required() {
+        let mut config = make_config();
+        config.opening_balance_terms = vec!["Opening balance".to_string()];
+        config.opening_balance_alignment_tol = 0;
+        config.transaction_amount_alignment_tol = Some(0);
+        config.transaction_start_date_required = true;
+        config.start_date_terms.clear();
+        assert_eq!(
+            fields(&lint_config(&config)),
+            vec![
+                "opening_balance_alignment_tol",
+                "transaction_amount_alignment_tol",
+                "transaction_start_date_required"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_account_terms_of_another_config() {
+        let config = make_config();
+        let mut other = make_config();
+        other.key = "au__bank__savings__2".to_string();
+        other.account_terms.push("Bonus Saver".to_string());
+        let unrelated = StatementConfig {
+            key: "au__other__cheque__1".to_string(),
+            account_terms: vec!["Bank".to_string()],
+            ..Default::default()
+        };
+        let others = vec![config.clone(), other.clone(), unrelated];
+
+        let warnings = lint_account_terms(&config, &others);
+
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("au__bank__savings__2"));
+        assert_eq!(lint_account_terms(&other, &others), vec![]);
+    }
+}
@@ -1,3 +1,5 @@
+pub mod catalog;
 pub mod db;
+pub mod lint;
 pub mod typer;
 pub mod validate;
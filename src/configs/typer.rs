@@ -1,19 +1,96 @@
+use crate::configs::validate::utils::normalize_term;
+use crate::parsers::base::primer::normalise_whitespace;
 use crate::structs::TextItem;
 use crate::structs::text_items::get_text_item_buffer;
 use crate::structs::text_items::tokenise_items;
 use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Account terms of a statement config key found and missing in a statement (see
+/// StatementTyper::term_evidence)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TermEvidence {
+    pub key: String,
+    pub found: Vec<String>,
+    pub missing: Vec<String>,
+}
+
+/// Diagnostic of the account terms of a statement config key in a statement (see
+/// StatementTyper::explain)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TyperDiagnostic {
+    pub key: String,
+    /// Terms found, as matched (lowercase if matched ignoring case)
+    pub matched: Vec<String>,
+    /// Terms not found, with the nearest text of the statement
+    pub missing: Vec<MissingTerm>,
+}
+
+/// An account term not found in a statement (see TyperDiagnostic)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingTerm {
+    pub term: String,
+    /// Nearest text to the term: the shortest run of words containing the term when
+    /// case and whitespace are ignored, else the run of words sharing the most words
+    /// with the term. None if no word is shared.
+    pub nearest: Option<String>,
+    /// Page of the first word of the nearest text
+    pub nearest_page: Option<i32>,
+}
+
+impl fmt::Display for TyperDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "  {}: {} of {} account term(s) found",
+            self.key,
+            self.matched.len(),
+            self.matched.len() + self.missing.len()
+        )?;
+        for term in &self.matched {
+            writeln!(f, "    Found: \"{}\"", term)?;
+        }
+        for missing in &self.missing {
+            match (&missing.nearest, missing.nearest_page) {
+                (Some(nearest), Some(page)) => writeln!(
+                    f,
+                    "    Missing: \"{}\", nearest text on page {}: \"{}\"",
+                    missing.term, page, nearest
+                )?,
+                _ => writeln!(f, "    Missing: \"{}\", no similar text", missing.term)?,
+            }
+        }
+        Ok(())
+    }
+}
 
 /// Struct to identify statement types from text items.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct StatementTyper {
-    /// Collection of all account_terms identifying statement types (case-sensitive),
-    account_terms: Vec<String>,
-    /// Maps each term to one or more statement config keys
-    keys_by_term: HashMap<String, Vec<String>>,
+    /// Collection of all account_terms identifying statement types, each flagged if
+    /// matched ignoring case (stored lowercase)
+    account_terms: Vec<(String, bool)>,
+    /// Maps each term (and case-insensitive flag) to one or more statement config keys
+    keys_by_term: HashMap<(String, bool), Vec<String>>,
     /// Maps each statement config key to the number of expected terms
     expected_terms_by_key: HashMap<String, usize>,
     /// Maximum number of space-delimited words in any account_term
     max_lookahead: usize,
+    /// Minimum score (fraction of the account terms found) of an identified key. 1.0
+    /// identifies only keys with every term found.
+    min_score: f64,
+}
+
+impl Default for StatementTyper {
+    fn default() -> Self {
+        Self {
+            account_terms: Vec::new(),
+            keys_by_term: HashMap::new(),
+            expected_terms_by_key: HashMap::new(),
+            max_lookahead: 0,
+            min_score: 1.0,
+        }
+    }
 }
 
 impl StatementTyper {
@@ -22,14 +99,28 @@ impl StatementTyper {
         Self::default()
     }
 
-    pub fn add_account_terms(&mut self, key: &str, terms: &Vec<String>) {
+    /// Add the account_terms of a statement config key, replacing any existing terms for
+    /// the key. If case_insensitive, the terms are matched ignoring case.
+    pub fn add_account_terms(&mut self, key: &str, terms: &Vec<String>, case_insensitive: bool) {
         // Remove existing terms for this key first
         self.remove_account_terms(key);
 
+        // Normalise as for validated configs, so that repeated terms are only expected once
+        let mut normalized_terms: Vec<String> = Vec::new();
+        for term in terms {
+            let mut term = normalise_whitespace(&normalize_term(term));
+            if case_insensitive {
+                term = term.to_lowercase();
+            }
+            if !term.is_empty() && !normalized_terms.contains(&term) {
+                normalized_terms.push(term);
+            }
+        }
+
         self.expected_terms_by_key
-            .insert(key.to_string(), terms.len());
+            .insert(key.to_string(), normalized_terms.len());
 
-        for term in terms {
+        for term in &normalized_terms {
             // Track max lookahead
             let word_count = term.split_whitespace().count();
             if word_count > self.max_lookahead {
@@ -38,7 +129,7 @@ impl StatementTyper {
 
             // Map term to config keys
             self.keys_by_term
-                .entry(term.clone())
+                .entry((term.clone(), case_insensitive))
                 .and_modify(|keys| {
                     if !keys.contains(&key.to_string()) {
                         keys.push(key.to_string());
@@ -51,22 +142,187 @@ impl StatementTyper {
         self.account_terms = self.keys_by_term.keys().cloned().collect();
     }
 
-    /// Return a list of config keys whose account_terms are all found in the provided text items.
+    /// Set the minimum score (fraction of the account terms found, above 0.0 and at
+    /// most 1.0) of an identified key. Below 1.0, statements with some account terms
+    /// reworded or missing are still identified, best match first.
+    pub fn set_min_score(&mut self, min_score: f64) -> Result<(), String> {
+        if !(min_score > 0.0 && min_score <= 1.0) {
+            return Err(format!(
+                "Invalid minimum identification score: {}. Must be above 0.0 and at most 1.0",
+                min_score
+            ));
+        }
+        self.min_score = min_score;
+        Ok(())
+    }
+
+    /// Return a list of config keys whose score (see scores_filtered) is at least the
+    /// minimum score, highest score first (then by key). With the default minimum
+    /// score of 1.0, these are the keys whose account_terms are all found.
     pub fn identify(&self, text_items: &Vec<TextItem>) -> Vec<String> {
-        let tokenised_items = tokenise_items(text_items);
+        self.identify_filtered(text_items, |_| true)
+    }
+
+    /// As identify, but only for the config keys accepted by the filter (e.g., keys of
+    /// countries plausible for the document, see LocaleProfile). Terms of rejected keys
+    /// are not matched, so filtering out most keys speeds up identification.
+    pub fn identify_filtered(
+        &self,
+        text_items: &Vec<TextItem>,
+        key_filter: impl Fn(&str) -> bool,
+    ) -> Vec<String> {
+        let mut scored: Vec<(String, f64)> = self
+            .scores_filtered(text_items, key_filter)
+            .into_iter()
+            .filter(|(_, score)| *score >= self.min_score)
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        scored.into_iter().map(|(key, _)| key).collect()
+    }
+
+    /// The score of each config key accepted by the filter with at least one account
+    /// term found in the provided text items: the fraction of its terms found.
+    pub fn scores_filtered(
+        &self,
+        text_items: &Vec<TextItem>,
+        key_filter: impl Fn(&str) -> bool,
+    ) -> HashMap<String, f64> {
         // Incremented for each found term found for a key
         let mut matches_by_key: HashMap<String, usize> = HashMap::new();
+        for term in self.find_terms(text_items, &key_filter) {
+            if let Some(keys) = self.keys_by_term.get(&term) {
+                for key in keys.iter().filter(|key| key_filter(key)) {
+                    matches_by_key
+                        .entry(key.clone())
+                        .and_modify(|count| *count += 1)
+                        .or_insert(1);
+                }
+            }
+        }
+
+        matches_by_key
+            .into_iter()
+            .filter_map(|(key, count)| {
+                let expected = *self.expected_terms_by_key.get(&key)?;
+                Some((key, count as f64 / expected as f64))
+            })
+            .collect()
+    }
+
+    /// The account terms found and missing in the provided text items for each config
+    /// key with at least one term found, most terms found first (then by key). A key
+    /// is identified if no terms are missing.
+    pub fn term_evidence(&self, text_items: &Vec<TextItem>) -> Vec<TermEvidence> {
+        let found_terms = self.find_terms(text_items, |_| true);
+        let mut evidence_by_key: HashMap<String, TermEvidence> = HashMap::new();
+        for (term, keys) in &self.keys_by_term {
+            let found = found_terms.contains(term);
+            for key in keys {
+                let evidence = evidence_by_key
+                    .entry(key.clone())
+                    .or_insert_with(|| TermEvidence {
+                        key: key.clone(),
+                        found: Vec::new(),
+                        missing: Vec::new(),
+                    });
+                if found {
+                    evidence.found.push(term.0.clone());
+                } else {
+                    evidence.missing.push(term.0.clone());
+                }
+            }
+        }
+        let mut evidence: Vec<TermEvidence> = evidence_by_key
+            .into_values()
+            .filter(|evidence| !evidence.found.is_empty())
+            .map(|mut evidence| {
+                evidence.found.sort();
+                evidence.missing.sort();
+                evidence
+            })
+            .collect();
+        evidence.sort_by(|a, b| b.found.len().cmp(&a.found.len()).then(a.key.cmp(&b.key)));
+        evidence
+    }
+
+    /// Diagnose the account terms of every registered config key in the provided text
+    /// items: the terms found and the terms missing, each with the nearest text of the
+    /// statement (see MissingTerm), to show why a key is not identified (e.g., a term
+    /// split or joined differently by tokenisation, or in a different case). Most terms
+    /// found first (then by key).
+    pub fn explain(&self, text_items: &Vec<TextItem>) -> Vec<TyperDiagnostic> {
+        let found_terms = self.find_terms(text_items, |_| true);
+        let tokens = tokenise_items(text_items);
+        let mut diagnostics_by_key: HashMap<&String, TyperDiagnostic> = HashMap::new();
+        let mut terms: Vec<&(String, bool)> = self.keys_by_term.keys().collect();
+        terms.sort();
+        for term in terms {
+            let found = found_terms.contains(term);
+            let missing = (!found).then(|| {
+                let (nearest, nearest_page) = match nearest_text(&term.0, &tokens) {
+                    Some((text, page)) => (Some(text), Some(page)),
+                    None => (None, None),
+                };
+                MissingTerm {
+                    term: term.0.clone(),
+                    nearest,
+                    nearest_page,
+                }
+            });
+            for key in &self.keys_by_term[term] {
+                let diagnostic = diagnostics_by_key
+                    .entry(key)
+                    .or_insert_with(|| TyperDiagnostic {
+                        key: key.clone(),
+                        matched: Vec::new(),
+                        missing: Vec::new(),
+                    });
+                match &missing {
+                    Some(missing) => diagnostic.missing.push(missing.clone()),
+                    None => diagnostic.matched.push(term.0.clone()),
+                }
+            }
+        }
+        let mut diagnostics: Vec<TyperDiagnostic> = diagnostics_by_key.into_values().collect();
+        diagnostics.sort_by(|a, b| {
+            b.matched
+                .len()
+                .cmp(&a.matched.len())
+                .then(a.key.cmp(&b.key))
+        });
+        diagnostics
+    }
+
+    /// Find the account terms of the config keys accepted by the filter in the
+    /// provided text items
+    fn find_terms(
+        &self,
+        text_items: &Vec<TextItem>,
+        key_filter: impl Fn(&str) -> bool,
+    ) -> HashSet<(String, bool)> {
+        let account_terms: Vec<&(String, bool)> = self
+            .account_terms
+            .iter()
+            .filter(|term| {
+                self.keys_by_term
+                    .get(*term)
+                    .is_some_and(|keys| keys.iter().any(|key| key_filter(key)))
+            })
+            .collect();
+        let max_lookahead = account_terms
+            .iter()
+            .map(|(term, _)| term.split_whitespace().count())
+            .max()
+            .unwrap_or(0);
+        let tokenised_items = tokenise_items(text_items);
         // Lookup set of account_terms already encountered, to prevent double counting
-        let mut found_terms: HashSet<String> = HashSet::new();
+        let mut found_terms: HashSet<(String, bool)> = HashSet::new();
 
         // Iterate through text items, attempting to match account_terms
         let len = tokenised_items.len();
-        if len == 0 {
-            return vec![];
-        }
         let mut i: usize = 0;
         while i < len {
-            let buffer_size = self.max_lookahead.min(len - i);
+            let buffer_size = max_lookahead.min(len - i);
             let buffer = get_text_item_buffer(&tokenised_items, i, buffer_size);
             if buffer.is_empty() {
                 break;
@@ -76,27 +332,23 @@ impl StatementTyper {
                 .map(|ti| ti.text.as_str())
                 .collect::<Vec<_>>()
                 .join(" ");
+            let lower_phrase = phrase.to_lowercase();
 
-            for term in &self.account_terms {
+            for &term in &account_terms {
+                let (text, case_insensitive) = term;
+                let phrase = if *case_insensitive {
+                    &lower_phrase
+                } else {
+                    &phrase
+                };
                 // Skip if term longer than phrase
-                if term.len() > phrase.len() {
+                if text.len() > phrase.len() {
                     continue;
                 }
 
-                // Check if phrase starts with term (case-sensitive)
-                if phrase.starts_with(term) {
-                    // Log term if not already found
-                    if !found_terms.contains(term) {
-                        found_terms.insert(term.clone());
-                        if let Some(keys) = self.keys_by_term.get(term) {
-                            for key in keys {
-                                matches_by_key
-                                    .entry(key.clone())
-                                    .and_modify(|count| *count += 1)
-                                    .or_insert(1);
-                            }
-                        }
-                    }
+                // Log term if phrase starts with it and not already found
+                if phrase.starts_with(text.as_str()) && !found_terms.contains(term) {
+                    found_terms.insert(term.clone());
                 }
             }
 
@@ -104,20 +356,7 @@ impl StatementTyper {
             i += 1;
         }
 
-        // Return list of keys that have all terms satisfied
-        let complete_keys: Vec<String> = matches_by_key
-            .iter()
-            .filter_map(|(key, &count)| {
-                if let Some(&expected) = self.expected_terms_by_key.get(key)
-                    && count == expected
-                {
-                    return Some(key.clone());
-                }
-                None
-            })
-            .collect();
-
-        complete_keys
+        found_terms
     }
 
     /// Remove account terms for a given config key and all other data associated with it.
@@ -133,8 +372,259 @@ impl StatementTyper {
         self.max_lookahead = self
             .keys_by_term
             .keys()
-            .map(|term| term.split_whitespace().count())
+            .map(|(term, _)| term.split_whitespace().count())
             .max()
             .unwrap_or(0);
     }
 }
+
+/// Find the text nearest to a term in the tokenised text items, with the page of its
+/// first token (see MissingTerm::nearest)
+fn nearest_text(term: &str, tokens: &[TextItem]) -> Option<(String, i32)> {
+    let word_count = term.split_whitespace().count().max(1);
+    let window = |start: usize, size: usize| {
+        let end = (start + size).min(tokens.len());
+        let text = tokens[start..end]
+            .iter()
+            .map(|token| token.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        (text, tokens[start].page)
+    };
+
+    // Shortest run of words containing the term, ignoring case and whitespace
+    let compact =
+        |text: &str| -> String { text.split_whitespace().collect::<String>().to_lowercase() };
+    let compact_term = compact(term);
+    for size in 1..=word_count + 1 {
+        for start in 0..tokens.len() {
+            let (text, page) = window(start, size);
+            if compact(&text).contains(&compact_term) {
+                return Some((text, page));
+            }
+        }
+    }
+
+    // Run of words sharing the most words with the term, ignoring case
+    let term_words: HashSet<String> = term
+        .split_whitespace()
+        .map(|word| word.to_lowercase())
+        .collect();
+    let mut best: Option<(usize, usize)> = None;
+    for start in 0..tokens.len() {
+        let end = (start + word_count).min(tokens.len());
+        let shared: HashSet<String> = tokens[start..end]
+            .iter()
+            .map(|token| token.text.to_lowercase())
+            .filter(|word| term_words.contains(word))
+            .collect();
+        if !shared.is_empty() && best.is_none_or(|(count, _)| shared.len() > count) {
+            best = Some((shared.len(), start));
+        }
+    }
+    best.map(|(_, start)| window(start, word_count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_text_item(text: &str, x1: i32, y1: i32) -> TextItem {
+        TextItem::new(text.to_string(), x1, y1, x1 + 40, y1 + 10, 0)
+    }
+
+    #[test]
+    fn test_identify_case_insensitive_terms() {
+        let mut typer = StatementTyper::new();
+        typer.add_account_terms("exact", &vec!["Gravy  Toast".to_string()], false);
+        typer.add_account_terms("any_case", &vec!["gravy toast bank".to_string()], true);
+        let items = vec![
+            make_text_item("GRAVY", 10, 10),
+            make_text_item("TOAST", 60, 10),
+            make_text_item("BANK", 110, 10),
+        ];
+
+        assert_eq!(typer.identify(&items), vec!["any_case".to_string()]);
+
+        let items = vec![
+            make_text_item("Gravy", 10, 10),
+            make_text_item("Toast", 60, 10),
+            make_text_item("Bank", 110, 10),
+        ];
+        let mut keys = typer.identify(&items);
+        keys.sort();
+        assert_eq!(keys, vec!["any_case".to_string(), "exact".to_string()]);
+    }
+
+    #[test]
+    fn test_identify_filtered_skips_rejected_keys() {
+        let mut typer = StatementTyper::new();
+        typer.add_account_terms(
+            "au__gtb__savings__1",
+            &vec!["Gravy Toast".to_string()],
+            false,
+        );
+        typer.add_account_terms(
+            "cn__gtb__savings__1",
+            &vec!["Gravy Toast".to_string()],
+            false,
+        );
+        let items = vec![
+            make_text_item("Gravy", 10, 10),
+            make_text_item("Toast", 60, 10),
+        ];
+
+        let keys = typer.identify_filtered(&items, |key| key.starts_with("au__"));
+
+        assert_eq!(keys, vec!["au__gtb__savings__1".to_string()]);
+    }
+
+    #[test]
+    fn test_term_evidence_lists_found_and_missing_terms() {
+        let mut typer = StatementTyper::new();
+        typer.add_account_terms(
+            "au__gtb__savings__1",
+            &vec!["Gravy Toast".to_string(), "Savings".to_string()],
+            false,
+        );
+        typer.add_account_terms(
+            "au__gtb__credit__1",
+            &vec!["Gravy Toast".to_string(), "Credit Card".to_string()],
+            false,
+        );
+        typer.add_account_terms("au__other__1", &vec!["Other Bank".to_string()], false);
+        let items = vec![
+            make_text_item("Gravy", 10, 10),
+            make_text_item("Toast", 60, 10),
+            make_text_item("Savings", 110, 10),
+        ];
+
+        let evidence = typer.term_evidence(&items);
+
+        assert_eq!(
+            evidence,
+            vec![
+                TermEvidence {
+                    key: "au__gtb__savings__1".to_string(),
+                    found: vec!["Gravy Toast".to_string(), "Savings".to_string()],
+                    missing: vec![],
+                },
+                TermEvidence {
+                    key: "au__gtb__credit__1".to_string(),
+                    found: vec!["Gravy Toast".to_string()],
+                    missing: vec!["Credit Card".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_identify_ranks_keys_above_min_score() {
+        let mut typer = StatementTyper::new();
+        let terms = |terms: &[&str]| terms.iter().map(|t| t.to_string()).collect();
+        typer.add_account_terms("full", &terms(&["Sample", "Bank", "Savings"]), false);
+        typer.add_account_terms(
+            "near_miss",
+            &terms(&["Sample", "Bank", "Savings", "Reworded footer", "Everyday"]),
+            false,
+        );
+        typer.add_account_terms("weak", &terms(&["Sample", "Credit", "Card"]), false);
+        let items = vec![
+            make_text_item("Sample", 0, 0),
+            make_text_item("Bank", 50, 0),
+            make_text_item("Everyday", 100, 0),
+            make_text_item("Savings", 150, 0),
+        ];
+
+        assert_eq!(typer.identify(&items), vec!["full"]);
+        let scores = typer.scores_filtered(&items, |_| true);
+        assert_eq!(scores["near_miss"], 0.8);
+        assert!((scores["weak"] - 1.0 / 3.0).abs() < 1e-9);
+
+        typer.set_min_score(0.75).unwrap();
+        assert_eq!(typer.identify(&items), vec!["full", "near_miss"]);
+        assert!(typer.set_min_score(0.0).is_err());
+        assert!(typer.set_min_score(1.5).is_err());
+    }
+
+    #[test]
+    fn test_explain_reports_nearest_text_of_missing_terms() {
+        let mut typer = StatementTyper::new();
+        typer.add_account_terms(
+            "au__gtb__savings__1",
+            &vec![
+                "Gravy Toast".to_string(),
+                "Savings Account".to_string(),
+                "Bonus Saver".to_string(),
+                "Term Deposit".to_string(),
+            ],
+            false,
+        );
+        typer.add_account_terms("au__other__1", &vec!["Other Bank".to_string()], true);
+        let items = vec![
+            make_text_item("GravyToast", 10, 10),
+            make_text_item("SAVINGS ACCOUNT", 60, 10),
+            make_text_item("Bonus", 110, 10),
+            make_text_item("Saver", 160, 10),
+        ];
+
+        let diagnostics = typer.explain(&items);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0],
+            TyperDiagnostic {
+                key: "au__gtb__savings__1".to_string(),
+                matched: vec!["Bonus Saver".to_string()],
+                missing: vec![
+                    // Joined by the statement
+                    MissingTerm {
+                        term: "Gravy Toast".to_string(),
+                        nearest: Some("GravyToast".to_string()),
+                        nearest_page: Some(0),
+                    },
+                    // In a different case
+                    MissingTerm {
+                        term: "Savings Account".to_string(),
+                        nearest: Some("SAVINGS ACCOUNT".to_string()),
+                        nearest_page: Some(0),
+                    },
+                    MissingTerm {
+                        term: "Term Deposit".to_string(),
+                        nearest: None,
+                        nearest_page: None,
+                    },
+                ],
+            }
+        );
+        assert_eq!(diagnostics[1].key, "au__other__1");
+        assert!(diagnostics[1].matched.is_empty());
+        assert_eq!(diagnostics[1].missing[0].term, "other bank");
+
+        let text = diagnostics[0].to_string();
+        assert!(text.starts_with("  au__gtb__savings__1: 1 of 4 account term(s) found\n"));
+        assert!(
+            text.contains("    Missing: \"Gravy Toast\", nearest text on page 0: \"GravyToast\"\n")
+        );
+        assert!(text.contains("    Missing: \"Term Deposit\", no similar text\n"));
+    }
+
+    #[test]
+    fn test_explain_nearest_text_by_shared_words() {
+        let mut typer = StatementTyper::new();
+        typer.add_account_terms("key", &vec!["Everyday Saver Account".to_string()], false);
+        let items = vec![
+            make_text_item("Your", 10, 10),
+            make_text_item("Everyday", 60, 10),
+            make_text_item("Account", 110, 10),
+            make_text_item("Details", 160, 10),
+        ];
+
+        let diagnostics = typer.explain(&items);
+
+        assert_eq!(
+            diagnostics[0].missing[0].nearest.as_deref(),
+            Some("Your Everyday Account")
+        );
+    }
+}
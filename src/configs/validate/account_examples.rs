@@ -1,3 +1,5 @@
+use crate::configs::validate::utils::validate_term_characters;
+
 pub fn account_examples(account_examples: &Vec<String>) -> Result<(), String> {
     // An example cannot be more then 100 chars
     for example in account_examples {
@@ -7,6 +9,9 @@ pub fn account_examples(account_examples: &Vec<String>) -> Result<(), String> {
                 example
             ));
         }
+        if let Err(e) = validate_term_characters(example) {
+            return Err(format!("Invalid account_examples. {}", e));
+        }
     }
     Ok(())
 }
@@ -1,7 +1,7 @@
-use crate::configs::validate::utils::validate_tolerance;
+use crate::configs::validate::utils::validate_alignment_tolerance;
 
-pub fn account_number_alignment_tol(tol: i32) -> Result<(), String> {
-    let result = validate_tolerance(tol);
+pub fn account_number_alignment_tol(tol: i32, alignment: &str) -> Result<(), String> {
+    let result = validate_alignment_tolerance(tol, alignment);
     if result.is_err() {
         return Err(format!(
             "Invalid account_number_alignment_tol: {}. {}",
@@ -11,3 +11,20 @@ pub fn account_number_alignment_tol(tol: i32) -> Result<(), String> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pasted_pixel_tolerance_is_rejected() {
+        let error = account_number_alignment_tol(300, "y1").unwrap_err();
+
+        assert!(
+            error.starts_with("Invalid account_number_alignment_tol: 300. Must be <= 50"),
+            "{}",
+            error
+        );
+        assert!(account_number_alignment_tol(300, "").is_ok());
+    }
+}
@@ -0,0 +1,14 @@
+use crate::configs::validate::utils::validate_terms;
+
+/// Multi-account statements must have terms to split the account sections
+pub fn account_section_terms(terms: &Vec<String>, multi_account: bool) -> Result<(), String> {
+    if let Err(e) = validate_terms(terms, true) {
+        return Err(format!("Invalid account_section_terms. {}", e));
+    }
+    if multi_account && terms.is_empty() {
+        return Err(
+            "Invalid account_section_terms. Cannot be empty if multi_account is true.".to_string(),
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,11 @@
+/// Multiple of the median transaction amount above which an amount is an outlier. Small
+/// multiples would report the ordinary spread of amounts.
+pub fn amount_outlier_multiple(multiple: i32) -> Result<(), String> {
+    if multiple != 0 && multiple < 10 {
+        return Err(format!(
+            "Invalid amount_outlier_multiple: {}. Must be 0 (disabled) or at least 10.",
+            multiple
+        ));
+    }
+    Ok(())
+}
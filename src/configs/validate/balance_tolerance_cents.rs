@@ -0,0 +1,10 @@
+/// Tolerance of the balance checks in cents. Large tolerances would hide misread amounts.
+pub fn balance_tolerance_cents(tolerance: i32) -> Result<(), String> {
+    if !(0..=100).contains(&tolerance) {
+        return Err(format!(
+            "Invalid balance_tolerance_cents: {}. Must be from 0 to 100.",
+            tolerance
+        ));
+    }
+    Ok(())
+}
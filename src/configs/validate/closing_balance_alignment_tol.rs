@@ -1,7 +1,7 @@
-use crate::configs::validate::utils::validate_tolerance;
+use crate::configs::validate::utils::validate_alignment_tolerance;
 
-pub fn closing_balance_alignment_tol(tol: i32) -> Result<(), String> {
-    let result = validate_tolerance(tol);
+pub fn closing_balance_alignment_tol(tol: i32, alignment: &str) -> Result<(), String> {
+    let result = validate_alignment_tolerance(tol, alignment);
     if result.is_err() {
         return Err(format!(
             "Invalid closing_balance_alignment_tol: {}. {}",
@@ -0,0 +1,21 @@
+use crate::configs::validate::utils::tolerance::MAX_Y_TOLERANCE;
+use crate::configs::validate::utils::{validate_tolerance, validate_tolerance_cap};
+
+/// Capped as a y tolerance, as exclude terms are usually on nearby lines
+pub fn closing_balance_exclude_distance(distance: i32) -> Result<(), String> {
+    let result = validate_tolerance(distance).and_then(|_| {
+        validate_tolerance_cap(
+            distance,
+            MAX_Y_TOLERANCE,
+            "closing_balance_exclude_distance",
+        )
+    });
+    if result.is_err() {
+        return Err(format!(
+            "Invalid closing_balance_exclude_distance: {}. {}",
+            distance,
+            result.err().unwrap()
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,10 @@
+/// Must be "first" or "last"
+pub fn closing_balance_occurrence(occurrence: &str) -> Result<(), String> {
+    if matches!(occurrence, "first" | "last") {
+        return Ok(());
+    }
+    Err(format!(
+        "Invalid closing_balance_occurrence: {}. Must be \"first\" or \"last\"",
+        occurrence
+    ))
+}
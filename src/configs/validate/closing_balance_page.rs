@@ -0,0 +1,10 @@
+/// Pages are numbered from 0, as in the layout text
+pub fn closing_balance_page(page: Option<i32>) -> Result<(), String> {
+    match page {
+        Some(page) if page < 0 => Err(format!(
+            "Invalid closing_balance_page: {}. Must be >= 0 (0 for the first page)",
+            page
+        )),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,34 @@
+/// Currency symbol must be empty (format defaults) or a short symbol without digits,
+/// whitespace or separator characters, so it cannot be confused with the amount itself
+pub fn currency_symbol(symbol: &str) -> Result<(), String> {
+    if symbol.is_empty() {
+        return Ok(());
+    }
+    let invalid = symbol.chars().count() > 3
+        || symbol
+            .chars()
+            .any(|c| c.is_ascii_digit() || c.is_whitespace() || matches!(c, '-' | ',' | '.'));
+    if invalid {
+        return Err(format!(
+            "Invalid currency_symbol: {}. Must be empty or a symbol of up to 3 characters without digits, whitespace, '-', ',' or '.' (e.g., \"£\")",
+            symbol
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_currency_symbol() {
+        assert!(currency_symbol("").is_ok());
+        assert!(currency_symbol("£").is_ok());
+        assert!(currency_symbol("NZ$").is_ok());
+        assert!(currency_symbol("1$").is_err());
+        assert!(currency_symbol("$ ").is_err());
+        assert!(currency_symbol("R.").is_err());
+        assert!(currency_symbol("EURO").is_err());
+    }
+}
@@ -0,0 +1,11 @@
+use chrono_tz::Tz;
+
+pub fn date_timezone(timezone: &str) -> Result<(), String> {
+    if timezone.is_empty() || timezone.parse::<Tz>().is_ok() {
+        return Ok(());
+    }
+    Err(format!(
+        "Invalid date_timezone: {}. Must be empty (UTC) or an IANA timezone name (e.g., \"Australia/Sydney\")",
+        timezone
+    ))
+}
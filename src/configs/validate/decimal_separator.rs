@@ -0,0 +1,9 @@
+pub fn decimal_separator(separator: &str) -> Result<(), String> {
+    if matches!(separator, "" | "." | ",") {
+        return Ok(());
+    }
+    Err(format!(
+        "Invalid decimal_separator: {}. Must be empty (any), \".\" or \",\"",
+        separator
+    ))
+}
@@ -0,0 +1,40 @@
+use crate::configs::validate::utils::validate_term_characters;
+
+/// Ditto tokens must be single text items without letters or digits, so that they
+/// cannot be mistaken for a date or the first word of a description
+pub fn ditto_tokens(tokens: &[String]) -> Result<(), String> {
+    for token in tokens {
+        if let Err(e) = validate_term_characters(token) {
+            return Err(format!("Invalid ditto_tokens. {}", e));
+        }
+        if token.is_empty()
+            || token
+                .chars()
+                .any(|c| c.is_alphanumeric() || c.is_whitespace())
+        {
+            return Err(format!(
+                "Invalid ditto_tokens: '{}'. Must be non-empty marks without letters, digits or whitespace (e.g., \"〃\")",
+                token
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ss(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_ditto_tokens() {
+        assert!(ditto_tokens(&ss(&["\"", "〃", "''"])).is_ok());
+        assert!(ditto_tokens(&[]).is_ok());
+        assert!(ditto_tokens(&ss(&[""])).is_err());
+        assert!(ditto_tokens(&ss(&["do"])).is_err());
+        assert!(ditto_tokens(&ss(&["\" \""])).is_err());
+    }
+}
@@ -0,0 +1,13 @@
+use crate::configs::validate::utils::validate_alignment;
+
+pub fn end_date_alignment(alignment: &str) -> Result<(), String> {
+    let result = validate_alignment(alignment, true, true);
+    if result.is_err() {
+        return Err(format!(
+            "Invalid end_date_alignment: {}. {}",
+            alignment,
+            result.err().unwrap()
+        ));
+    }
+    Ok(())
+}
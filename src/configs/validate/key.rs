@@ -1,12 +1,17 @@
 use crate::configs::validate::utils::iso_3166_1_alpha_2::is_valid_iso_3166_1_alpha_2;
 
+/// Country code of the synthetic example configs (see examples/data)
+pub const EXAMPLE_COUNTRY_CODE: &str = "zz";
+
 /// Validates a configuration key format.
 ///
 /// A valid key must:
 /// - Not contain any whitespace characters
 /// - Have exactly 4 components separated by "__"
 /// - Be all lowercase
-/// - Have a valid ISO 3166-1 alpha-2 country code as the first component
+/// - Have a valid ISO 3166-1 alpha-2 country code as the first component, or "zz"
+///   (user-assigned in ISO 3166-1) for the synthetic example configs, so that they
+///   never share a key with a real bank
 /// - Have an integer as the last component
 /// - Middle two components can be any lowercase text
 pub fn key(key: &str) -> Result<(), String> {
@@ -32,7 +37,7 @@ pub fn key(key: &str) -> Result<(), String> {
 
     // Validate first component is a valid ISO 3166-1 alpha-2 country code
     let country_code = components[0];
-    if !is_valid_iso_3166_1_alpha_2(country_code) {
+    if country_code != EXAMPLE_COUNTRY_CODE && !is_valid_iso_3166_1_alpha_2(country_code) {
         return Err(format!(
             "First component must be a valid ISO 3166-1 alpha-2 country code. Found: '{}'",
             country_code
@@ -70,6 +75,7 @@ mod tests {
         assert!(key("us__bank__debit__2").is_ok());
         assert!(key("gb__hsbc__loan__10").is_ok());
         assert!(key("de__deutsche__savings__999").is_ok());
+        assert!(key("zz__example__checking__1").is_ok());
     }
 
     #[test]
@@ -0,0 +1,13 @@
+use crate::configs::validate::utils::validate_alignment;
+
+pub fn minimum_payment_alignment(alignment: &str) -> Result<(), String> {
+    let result = validate_alignment(alignment, true, true);
+    if result.is_err() {
+        return Err(format!(
+            "Invalid minimum_payment_alignment: {}. {}",
+            alignment,
+            result.err().unwrap()
+        ));
+    }
+    Ok(())
+}
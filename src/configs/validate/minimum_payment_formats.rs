@@ -0,0 +1,12 @@
+use crate::configs::validate::utils::validate_amount_formats;
+
+pub fn minimum_payment_formats(formats: &[String]) -> Result<(), String> {
+    let valid_formats = validate_amount_formats(formats);
+    if valid_formats.is_err() {
+        return Err(format!(
+            "Invalid minimum_payment_formats. {}",
+            valid_formats.err().unwrap()
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,12 @@
+use crate::configs::validate::utils::validate_terms;
+
+pub fn minimum_payment_terms(terms: &Vec<String>) -> Result<(), String> {
+    let result = validate_terms(terms, true);
+    if result.is_err() {
+        return Err(format!(
+            "Invalid minimum_payment_terms. {}",
+            result.err().unwrap()
+        ));
+    }
+    Ok(())
+}
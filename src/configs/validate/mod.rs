@@ -5,43 +5,104 @@ pub mod account_number_alignment;
 pub mod account_number_alignment_tol;
 pub mod account_number_patterns;
 pub mod account_number_terms;
+pub mod account_section_terms;
 pub mod account_terms;
 pub mod account_type;
+pub mod amount_outlier_multiple;
+pub mod balance_tolerance_cents;
 pub mod bank_name;
 pub mod closing_balance_alignment;
 pub mod closing_balance_alignment_tol;
+pub mod closing_balance_exclude_distance;
+pub mod closing_balance_exclude_terms;
 pub mod closing_balance_formats;
+pub mod closing_balance_occurrence;
+pub mod closing_balance_page;
 pub mod closing_balance_terms;
+pub mod currency_symbol;
+pub mod date_timezone;
+pub mod decimal_separator;
+pub mod ditto_tokens;
+pub mod end_date_alignment;
+pub mod end_date_alignment_tol;
+pub mod end_date_formats;
+pub mod end_date_terms;
 pub mod fix_text_order;
 pub mod key;
+pub mod minimum_payment_alignment;
+pub mod minimum_payment_alignment_tol;
+pub mod minimum_payment_formats;
+pub mod minimum_payment_terms;
+pub mod month_only_default_day;
+pub mod multi_statement;
+pub mod no_transaction_terms;
 pub mod opening_balance_alignment;
 pub mod opening_balance_alignment_tol;
 pub mod opening_balance_formats;
 pub mod opening_balance_terms;
+pub mod payment_due_date_alignment;
+pub mod payment_due_date_alignment_tol;
+pub mod payment_due_date_formats;
+pub mod payment_due_date_terms;
+pub mod required_fields;
 pub mod start_date_alignment;
 pub mod start_date_alignment_tol;
 pub mod start_date_formats;
 pub mod start_date_terms;
+pub mod status;
 pub mod transaction_alignment_tol;
 pub mod transaction_amount_alignment;
+pub mod transaction_amount_alignment_tol;
 pub mod transaction_amount_formats;
 pub mod transaction_amount_headers;
 pub mod transaction_amount_invert_alignment;
 pub mod transaction_amount_invert_headers;
 pub mod transaction_balance_alignment;
+pub mod transaction_balance_alignment_tol;
 pub mod transaction_balance_formats;
 pub mod transaction_balance_headers;
+pub mod transaction_columns_by_position;
+pub mod transaction_credit_headers;
 pub mod transaction_date_alignment;
+pub mod transaction_date_alignment_tol;
 pub mod transaction_date_formats;
 pub mod transaction_date_headers;
+pub mod transaction_debit_headers;
 pub mod transaction_description_alignment;
+pub mod transaction_description_alignment_tol;
+pub mod transaction_description_continuation_max_lines;
+pub mod transaction_description_exclude;
 pub mod transaction_description_headers;
+pub mod transaction_description_placeholder;
 pub mod transaction_formats;
+pub mod transaction_layout;
 pub mod transaction_new_line_tol;
+pub mod transaction_orphan_lines_max;
+pub mod transaction_summary_exclude;
 pub mod transaction_terms;
 pub mod transaction_terms_stop;
+pub mod transaction_terms_stop_scope;
+pub mod two_digit_year_pivot;
 pub mod utils;
 
+/// Get warnings for config values that are valid but likely to be mistakes: account_terms
+/// and account_examples repeated after normalisation (see utils::normalize_term)
+pub fn config_warnings(config: &StatementConfig) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for (field, terms) in [
+        ("account_terms", &config.account_terms),
+        ("account_examples", &config.account_examples),
+    ] {
+        for term in utils::duplicate_terms(terms) {
+            warnings.push(format!(
+                "Config {} has duplicate {}: '{}'",
+                config.key, field, term
+            ));
+        }
+    }
+    warnings
+}
+
 /// Validate the entire StatementConfig
 pub fn validate_config(config: &StatementConfig) -> Result<(), String> {
     key::key(&config.key)?;
@@ -49,18 +110,43 @@ pub fn validate_config(config: &StatementConfig) -> Result<(), String> {
     account_type::account_type(&config.account_type)?;
     account_terms::account_terms(&config.account_terms)?;
     account_examples::account_examples(&config.account_examples)?;
+    status::status(&config.status)?;
     fix_text_order::fix_text_order(&config.fix_text_order)?;
+    date_timezone::date_timezone(&config.date_timezone)?;
+    two_digit_year_pivot::two_digit_year_pivot(config.two_digit_year_pivot)?;
+    currency_symbol::currency_symbol(&config.currency_symbol)?;
+    decimal_separator::decimal_separator(&config.decimal_separator)?;
+    // scale_tolerances is a bool, no validation needed
+    account_section_terms::account_section_terms(
+        &config.account_section_terms,
+        config.multi_account,
+    )?;
+    multi_statement::multi_statement(
+        config.multi_statement,
+        config.multi_account,
+        &config.account_terms,
+        &config.start_date_terms,
+    )?;
+    // terms_case_insensitive is a bool, no validation needed
+    required_fields::required_fields(
+        &config.required_fields,
+        &config.start_date_terms,
+        &config.minimum_payment_terms,
+        &config.payment_due_date_terms,
+    )?;
     account_number_terms::account_number_terms(&config.account_number_terms)?;
     account_number_patterns::account_number_patterns(&config.account_number_patterns)?;
     account_number_alignment::account_number_alignment(&config.account_number_alignment)?;
     account_number_alignment_tol::account_number_alignment_tol(
         config.account_number_alignment_tol,
+        &config.account_number_alignment,
     )?;
     opening_balance_terms::opening_balance_terms(&config.opening_balance_terms)?;
     opening_balance_formats::opening_balance_formats(&config.opening_balance_formats)?;
     opening_balance_alignment::opening_balance_alignment(&config.opening_balance_alignment)?;
     opening_balance_alignment_tol::opening_balance_alignment_tol(
         config.opening_balance_alignment_tol,
+        &config.opening_balance_alignment,
     )?;
     // opening_balance_invert is a bool, no validation needed
     closing_balance_terms::closing_balance_terms(&config.closing_balance_terms)?;
@@ -68,33 +154,107 @@ pub fn validate_config(config: &StatementConfig) -> Result<(), String> {
     closing_balance_alignment::closing_balance_alignment(&config.closing_balance_alignment)?;
     closing_balance_alignment_tol::closing_balance_alignment_tol(
         config.closing_balance_alignment_tol,
+        &config.closing_balance_alignment,
     )?;
     // closing_balance_invert is a bool, no validation needed
+    closing_balance_occurrence::closing_balance_occurrence(&config.closing_balance_occurrence)?;
+    closing_balance_page::closing_balance_page(config.closing_balance_page)?;
+    closing_balance_exclude_terms::closing_balance_exclude_terms(
+        &config.closing_balance_exclude_terms,
+    )?;
+    closing_balance_exclude_distance::closing_balance_exclude_distance(
+        config.closing_balance_exclude_distance,
+    )?;
+    balance_tolerance_cents::balance_tolerance_cents(config.balance_tolerance_cents)?;
+    amount_outlier_multiple::amount_outlier_multiple(config.amount_outlier_multiple)?;
     start_date_terms::start_date_terms(&config.start_date_terms)?;
     start_date_formats::start_date_formats(&config.start_date_formats)?;
     start_date_alignment::start_date_alignment(&config.start_date_alignment)?;
-    start_date_alignment_tol::start_date_alignment_tol(config.start_date_alignment_tol)?;
+    start_date_alignment_tol::start_date_alignment_tol(
+        config.start_date_alignment_tol,
+        &config.start_date_alignment,
+    )?;
+    end_date_terms::end_date_terms(&config.end_date_terms)?;
+    end_date_formats::end_date_formats(&config.end_date_formats)?;
+    end_date_alignment::end_date_alignment(&config.end_date_alignment)?;
+    end_date_alignment_tol::end_date_alignment_tol(
+        config.end_date_alignment_tol,
+        &config.end_date_alignment,
+    )?;
+    minimum_payment_terms::minimum_payment_terms(&config.minimum_payment_terms)?;
+    minimum_payment_formats::minimum_payment_formats(&config.minimum_payment_formats)?;
+    minimum_payment_alignment::minimum_payment_alignment(&config.minimum_payment_alignment)?;
+    minimum_payment_alignment_tol::minimum_payment_alignment_tol(
+        config.minimum_payment_alignment_tol,
+        &config.minimum_payment_alignment,
+    )?;
+    payment_due_date_terms::payment_due_date_terms(&config.payment_due_date_terms)?;
+    payment_due_date_formats::payment_due_date_formats(&config.payment_due_date_formats)?;
+    payment_due_date_alignment::payment_due_date_alignment(&config.payment_due_date_alignment)?;
+    payment_due_date_alignment_tol::payment_due_date_alignment_tol(
+        config.payment_due_date_alignment_tol,
+        &config.payment_due_date_alignment,
+    )?;
     transaction_terms::transaction_terms(&config.transaction_terms)?;
     transaction_terms_stop::transaction_terms_stop(&config.transaction_terms_stop)?;
+    transaction_terms_stop_scope::transaction_terms_stop_scope(
+        &config.transaction_terms_stop_scope,
+    )?;
+    no_transaction_terms::no_transaction_terms(&config.no_transaction_terms)?;
     transaction_formats::transaction_formats(&config.transaction_formats)?;
+    transaction_layout::transaction_layout(&config.transaction_layout)?;
     transaction_new_line_tol::transaction_new_line_tol(config.transaction_new_line_tol)?;
     // transaction_start_date_required is a bool, no validation needed
     transaction_alignment_tol::transaction_alignment_tol(config.transaction_alignment_tol)?;
+    transaction_columns_by_position::transaction_columns_by_position(
+        &config.transaction_columns_by_position,
+        &config.transaction_formats,
+    )?;
+    // drop_duplicate_transactions is a bool, no validation needed
+    transaction_summary_exclude::transaction_summary_exclude(&config.transaction_summary_exclude)?;
+    ditto_tokens::ditto_tokens(&config.ditto_tokens)?;
+    // transaction_strict is a bool, no validation needed
+    transaction_orphan_lines_max::transaction_orphan_lines_max(
+        config.transaction_orphan_lines_max,
+    )?;
     transaction_date_formats::transaction_date_formats(&config.transaction_date_formats)?;
     transaction_date_headers::transaction_date_headers(&config.transaction_date_headers)?;
     transaction_date_alignment::transaction_date_alignment(&config.transaction_date_alignment)?;
+    transaction_date_alignment_tol::transaction_date_alignment_tol(
+        config.transaction_date_alignment_tol,
+    )?;
+    month_only_default_day::month_only_default_day(&config.month_only_default_day)?;
     transaction_description_headers::transaction_description_headers(
         &config.transaction_description_headers,
     )?;
     transaction_description_alignment::transaction_description_alignment(
         &config.transaction_description_alignment,
     )?;
-    // transaction_description_exclude is not validated
+    transaction_description_alignment_tol::transaction_description_alignment_tol(
+        config.transaction_description_alignment_tol,
+    )?;
+    transaction_description_exclude::transaction_description_exclude(
+        &config.transaction_description_exclude,
+    )?;
+    // transaction_description_allows_dates is a bool, no validation needed
+    // transaction_description_allows_amounts is a bool, no validation needed
+    // transaction_description_optional is a bool, no validation needed
+    transaction_description_placeholder::transaction_description_placeholder(
+        &config.transaction_description_placeholder,
+        config.transaction_description_optional,
+    )?;
+    // transaction_description_multiline is a bool, no validation needed
+    transaction_description_continuation_max_lines::transaction_description_continuation_max_lines(
+        config.transaction_description_continuation_max_lines,
+    )?;
     transaction_amount_formats::transaction_amount_formats(&config.transaction_amount_formats)?;
     transaction_amount_headers::transaction_amount_headers(&config.transaction_amount_headers)?;
     transaction_amount_alignment::transaction_amount_alignment(
         &config.transaction_amount_alignment,
     )?;
+    transaction_amount_alignment_tol::transaction_amount_alignment_tol(
+        config.transaction_amount_alignment_tol,
+    )?;
     transaction_amount_invert_headers::transaction_amount_invert_headers(
         &config.transaction_amount_invert_headers,
     )?;
@@ -102,11 +262,19 @@ pub fn validate_config(config: &StatementConfig) -> Result<(), String> {
         &config.transaction_amount_invert_alignment,
     )?;
     // transaction_amount_invert is a bool, no validation needed
+    transaction_debit_headers::transaction_debit_headers(&config.transaction_debit_headers)?;
+    transaction_credit_headers::transaction_credit_headers(
+        &config.transaction_credit_headers,
+        &config.transaction_debit_headers,
+    )?;
     transaction_balance_formats::transaction_balance_formats(&config.transaction_balance_formats)?;
     transaction_balance_headers::transaction_balance_headers(&config.transaction_balance_headers)?;
     transaction_balance_alignment::transaction_balance_alignment(
         &config.transaction_balance_alignment,
     )?;
+    transaction_balance_alignment_tol::transaction_balance_alignment_tol(
+        config.transaction_balance_alignment_tol,
+    )?;
     // transaction_balance_invert is a bool, no validation needed
     Ok(())
 }
@@ -0,0 +1,10 @@
+/// Must be "last" or a day of the month (1-31)
+pub fn month_only_default_day(day: &str) -> Result<(), String> {
+    if day == "last" || day.parse::<u32>().is_ok_and(|d| (1..=31).contains(&d)) {
+        return Ok(());
+    }
+    Err(format!(
+        "Invalid month_only_default_day: {}. Must be a day of the month (1-31) or \"last\".",
+        day
+    ))
+}
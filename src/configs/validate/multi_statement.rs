@@ -0,0 +1,21 @@
+/// Statements are split at repeated account_terms and start_date_terms, so both must be
+/// set. Splitting into statements is not combined with account sections.
+pub fn multi_statement(
+    multi_statement: bool,
+    multi_account: bool,
+    account_terms: &[String],
+    start_date_terms: &[String],
+) -> Result<(), String> {
+    if !multi_statement {
+        return Ok(());
+    }
+    if multi_account {
+        return Err(
+            "Invalid multi_statement. Cannot be true if multi_account is true.".to_string(),
+        );
+    }
+    if account_terms.is_empty() || start_date_terms.is_empty() {
+        return Err("Invalid multi_statement. Cannot be true if account_terms or start_date_terms are empty.".to_string());
+    }
+    Ok(())
+}
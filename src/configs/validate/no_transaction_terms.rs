@@ -0,0 +1,12 @@
+use crate::configs::validate::utils::validate_terms;
+
+pub fn no_transaction_terms(terms: &Vec<String>) -> Result<(), String> {
+    let result = validate_terms(terms, false);
+    if result.is_err() {
+        return Err(format!(
+            "Invalid no_transaction_terms. {}",
+            result.err().unwrap()
+        ));
+    }
+    Ok(())
+}
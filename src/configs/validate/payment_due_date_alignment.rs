@@ -0,0 +1,13 @@
+use crate::configs::validate::utils::validate_alignment;
+
+pub fn payment_due_date_alignment(alignment: &str) -> Result<(), String> {
+    let result = validate_alignment(alignment, true, true);
+    if result.is_err() {
+        return Err(format!(
+            "Invalid payment_due_date_alignment: {}. {}",
+            alignment,
+            result.err().unwrap()
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use crate::configs::validate::utils::validate_alignment_tolerance;
+
+pub fn payment_due_date_alignment_tol(tol: i32, alignment: &str) -> Result<(), String> {
+    let result = validate_alignment_tolerance(tol, alignment);
+    if result.is_err() {
+        return Err(format!(
+            "Invalid payment_due_date_alignment_tol: {}. {}",
+            tol,
+            result.err().unwrap()
+        ));
+    }
+    Ok(())
+}
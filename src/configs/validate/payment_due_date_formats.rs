@@ -0,0 +1,12 @@
+use crate::configs::validate::utils::validate_date_formats;
+
+pub fn payment_due_date_formats(formats: &[String]) -> Result<(), String> {
+    let valid_formats = validate_date_formats(formats);
+    if valid_formats.is_err() {
+        return Err(format!(
+            "Invalid payment_due_date_formats. {}",
+            valid_formats.err().unwrap()
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,76 @@
+/// Statement fields that a config can require (see StatementConfig::required_fields)
+pub const REQUIRED_FIELDS: [&str; 6] = [
+    "account_number",
+    "opening_balance",
+    "closing_balance",
+    "start_date",
+    "minimum_payment",
+    "payment_due_date",
+];
+
+/// Required fields must be known statement fields, listed once. A required start date,
+/// minimum payment or payment due date must have terms to prime its parser.
+pub fn required_fields(
+    fields: &[String],
+    start_date_terms: &[String],
+    minimum_payment_terms: &[String],
+    payment_due_date_terms: &[String],
+) -> Result<(), String> {
+    for (i, field) in fields.iter().enumerate() {
+        if !REQUIRED_FIELDS.contains(&field.as_str()) {
+            return Err(format!(
+                "Invalid required_fields: '{}'. Must be one of: {}",
+                field,
+                REQUIRED_FIELDS.join(", ")
+            ));
+        }
+        if fields[..i].contains(field) {
+            return Err(format!(
+                "Invalid required_fields: '{}' is listed more than once",
+                field
+            ));
+        }
+    }
+    for (field, terms) in [
+        ("start_date", start_date_terms),
+        ("minimum_payment", minimum_payment_terms),
+        ("payment_due_date", payment_due_date_terms),
+    ] {
+        if fields.iter().any(|f| f == field) && terms.is_empty() {
+            return Err(format!(
+                "Invalid required_fields: {}. {}_terms cannot be empty if required.",
+                field, field
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ss(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_required_fields() {
+        let terms = ss(&["Period"]);
+        assert!(required_fields(&ss(&REQUIRED_FIELDS), &terms, &terms, &terms).is_ok());
+        assert!(required_fields(&[], &[], &[], &[]).is_ok());
+        assert!(required_fields(&ss(&["balance"]), &terms, &terms, &terms).is_err());
+        assert!(
+            required_fields(
+                &ss(&["closing_balance", "closing_balance"]),
+                &terms,
+                &terms,
+                &terms
+            )
+            .is_err()
+        );
+        assert!(required_fields(&ss(&["start_date"]), &[], &terms, &terms).is_err());
+        assert!(required_fields(&ss(&["minimum_payment"]), &terms, &[], &terms).is_err());
+        assert!(required_fields(&ss(&["payment_due_date"]), &terms, &terms, &[]).is_err());
+    }
+}
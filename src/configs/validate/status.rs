@@ -0,0 +1,15 @@
+/// Status of the synthetic example configs (see examples/data), which are not
+/// supported statements so are left out of the catalog
+pub const EXAMPLE_STATUS: &str = "Example";
+
+pub fn status(status: &str) -> Result<(), String> {
+    let valid_statuses = vec!["None", "Limited", "Full", EXAMPLE_STATUS];
+    if valid_statuses.contains(&status) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid status: {}. Valid statuses are: {:?}",
+            status, valid_statuses
+        ))
+    }
+}
@@ -1,7 +1,9 @@
-use crate::configs::validate::utils::validate_tolerance;
+use crate::configs::validate::utils::tolerance::MAX_X_TOLERANCE;
+use crate::configs::validate::utils::{validate_tolerance, validate_tolerance_cap};
 
 pub fn transaction_alignment_tol(tol: i32) -> Result<(), String> {
-    let result = validate_tolerance(tol);
+    let result = validate_tolerance(tol)
+        .and_then(|_| validate_tolerance_cap(tol, MAX_X_TOLERANCE, "header alignment"));
     if result.is_err() {
         return Err(format!(
             "Invalid transaction_alignment_tol: {}. {}",
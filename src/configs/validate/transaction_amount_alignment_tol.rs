@@ -0,0 +1,33 @@
+use crate::configs::validate::utils::tolerance::MAX_X_TOLERANCE;
+use crate::configs::validate::utils::{validate_tolerance, validate_tolerance_cap};
+
+pub fn transaction_amount_alignment_tol(tol: Option<i32>) -> Result<(), String> {
+    // Not set, transaction_alignment_tol is used instead
+    let Some(tol) = tol else {
+        return Ok(());
+    };
+    let result = validate_tolerance(tol)
+        .and_then(|_| validate_tolerance_cap(tol, MAX_X_TOLERANCE, "header alignment"));
+    if result.is_err() {
+        return Err(format!(
+            "Invalid transaction_amount_alignment_tol: {}. {}",
+            tol,
+            result.err().unwrap()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transaction_amount_alignment_tol() {
+        assert!(transaction_amount_alignment_tol(None).is_ok());
+        assert!(transaction_amount_alignment_tol(Some(40)).is_ok());
+        assert!(transaction_amount_alignment_tol(Some(-1)).is_err());
+        let error = transaction_amount_alignment_tol(Some(500)).unwrap_err();
+        assert!(error.starts_with("Invalid transaction_amount_alignment_tol: 500."));
+    }
+}
@@ -0,0 +1,111 @@
+use crate::parsers::transaction::utils::parse_format_field;
+use crate::structs::TransactionColumnPosition;
+
+/// Validate transaction_columns_by_position configuration option. Each column must be
+/// a known field with 0 <= x1 < x2, fields may not repeat, and if any columns are set,
+/// every field used in transaction_formats must have a column.
+pub fn transaction_columns_by_position(
+    columns: &Vec<TransactionColumnPosition>,
+    transaction_formats: &[Vec<String>],
+) -> Result<(), String> {
+    let allowed_fields = ["date", "description", "amount", "balance"];
+    let mut seen_fields: Vec<&str> = Vec::new();
+    for column in columns {
+        if !allowed_fields.contains(&column.field.as_str()) {
+            return Err(format!(
+                "Invalid transaction_columns_by_position. Unknown field '{}'",
+                column.field
+            ));
+        }
+        if column.x1 < 0 || column.x1 >= column.x2 {
+            return Err(format!(
+                "Invalid transaction_columns_by_position. Column '{}' must have 0 <= x1 < x2, got x1={}, x2={}",
+                column.field, column.x1, column.x2
+            ));
+        }
+        if seen_fields.contains(&column.field.as_str()) {
+            return Err(format!(
+                "Invalid transaction_columns_by_position. Duplicate field '{}'",
+                column.field
+            ));
+        }
+        seen_fields.push(column.field.as_str());
+    }
+    if columns.is_empty() {
+        return Ok(());
+    }
+    for token in transaction_formats.iter().flatten() {
+        let (field, _) = parse_format_field(token);
+        if !seen_fields.contains(&field) {
+            return Err(format!(
+                "Invalid transaction_columns_by_position. No column for field '{}' used in transaction_formats",
+                field
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(field: &str, x1: i32, x2: i32) -> TransactionColumnPosition {
+        TransactionColumnPosition {
+            field: field.to_string(),
+            x1,
+            x2,
+        }
+    }
+
+    fn formats() -> Vec<Vec<String>> {
+        vec![vec![
+            "date".to_string(),
+            "description".to_string(),
+            "amount".to_string(),
+        ]]
+    }
+
+    #[test]
+    fn test_empty_columns_are_valid() {
+        assert!(transaction_columns_by_position(&vec![], &formats()).is_ok());
+    }
+
+    #[test]
+    fn test_columns_for_all_fields_are_valid() {
+        let columns = vec![
+            column("date", 40, 95),
+            column("description", 100, 300),
+            column("amount", 310, 360),
+        ];
+        assert!(transaction_columns_by_position(&columns, &formats()).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_field() {
+        let columns = vec![column("payee", 40, 95)];
+        let err = transaction_columns_by_position(&columns, &formats()).unwrap_err();
+        assert!(err.contains("Unknown field 'payee'"));
+    }
+
+    #[test]
+    fn test_invalid_range() {
+        let columns = vec![column("date", 95, 40)];
+        let err = transaction_columns_by_position(&columns, &formats()).unwrap_err();
+        assert!(err.contains("must have 0 <= x1 < x2"));
+    }
+
+    #[test]
+    fn test_duplicate_field() {
+        let columns = vec![column("date", 40, 95), column("date", 100, 150)];
+        let err = transaction_columns_by_position(&columns, &formats()).unwrap_err();
+        assert!(err.contains("Duplicate field 'date'"));
+    }
+
+    #[test]
+    fn test_missing_format_field() {
+        let columns = vec![column("date", 40, 95), column("description", 100, 300)];
+        let err = transaction_columns_by_position(&columns, &formats()).unwrap_err();
+        assert!(err.contains("No column for field 'amount'"));
+    }
+}
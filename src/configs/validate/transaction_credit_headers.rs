@@ -0,0 +1,18 @@
+use crate::configs::validate::utils::validate_terms;
+
+/// Debit and credit columns are read together, so both or neither must have headers
+pub fn transaction_credit_headers(
+    terms: &Vec<String>,
+    debit_headers: &[String],
+) -> Result<(), String> {
+    if let Err(e) = validate_terms(terms, true) {
+        return Err(format!("Invalid transaction_credit_headers. {}", e));
+    }
+    if terms.is_empty() != debit_headers.is_empty() {
+        return Err(
+            "Invalid transaction_credit_headers. Must be set if and only if transaction_debit_headers is set."
+                .to_string(),
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use crate::configs::validate::utils::tolerance::MAX_X_TOLERANCE;
+use crate::configs::validate::utils::{validate_tolerance, validate_tolerance_cap};
+
+pub fn transaction_description_alignment_tol(tol: Option<i32>) -> Result<(), String> {
+    // Not set, transaction_alignment_tol is used instead
+    let Some(tol) = tol else {
+        return Ok(());
+    };
+    let result = validate_tolerance(tol)
+        .and_then(|_| validate_tolerance_cap(tol, MAX_X_TOLERANCE, "header alignment"));
+    if result.is_err() {
+        return Err(format!(
+            "Invalid transaction_description_alignment_tol: {}. {}",
+            tol,
+            result.err().unwrap()
+        ));
+    }
+    Ok(())
+}
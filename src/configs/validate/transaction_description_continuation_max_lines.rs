@@ -0,0 +1,11 @@
+/// Wrapped descriptions rarely span more than a few lines, so a large maximum is likely
+/// to read unrelated text below the table into the last description
+pub fn transaction_description_continuation_max_lines(max_lines: i32) -> Result<(), String> {
+    if !(1..=10).contains(&max_lines) {
+        return Err(format!(
+            "Invalid transaction_description_continuation_max_lines: {}. Must be from 1 to 10.",
+            max_lines
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use regex::Regex;
+
+/// Sample descriptions that no exclude pattern should remove entirely
+const SAMPLE_DESCRIPTIONS: [&str; 3] = [
+    "A",
+    "Payment to Store - REF123456",
+    "01/02/2024 Transfer $1,234.56 CR",
+];
+
+/// Exclude patterns must not match the empty string (e.g., "x*"), or remove every
+/// sample description entirely (e.g., ".*" or ".+"), as they would empty every
+/// description
+pub fn transaction_description_exclude(patterns: &[Regex]) -> Result<(), String> {
+    for pattern in patterns {
+        if pattern.is_match("") {
+            return Err(format!(
+                "Invalid transaction_description_exclude pattern '{}'. Must not match an empty string.",
+                pattern.as_str()
+            ));
+        }
+        if SAMPLE_DESCRIPTIONS
+            .iter()
+            .all(|desc| pattern.replace_all(desc, "").trim().is_empty())
+        {
+            return Err(format!(
+                "Invalid transaction_description_exclude pattern '{}'. Must not match any description entirely.",
+                pattern.as_str()
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compile(patterns: &[&str]) -> Vec<Regex> {
+        patterns.iter().map(|p| Regex::new(p).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_rejects_universal_patterns() {
+        assert!(transaction_description_exclude(&compile(&[" -$", r"^\.+$"])).is_ok());
+        for pattern in [".*", r"\s*", ".+", r"[\s\S]+", r"\S+"] {
+            assert!(
+                transaction_description_exclude(&compile(&[pattern])).is_err(),
+                "{}",
+                pattern
+            );
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use crate::configs::validate::utils::validate_term_characters;
+
+/// A placeholder is only used for optional descriptions
+pub fn transaction_description_placeholder(
+    placeholder: &str,
+    optional: bool,
+) -> Result<(), String> {
+    if placeholder.is_empty() {
+        return Ok(());
+    }
+    if let Err(e) = validate_term_characters(placeholder) {
+        return Err(format!(
+            "Invalid transaction_description_placeholder. {}",
+            e
+        ));
+    }
+    if !optional {
+        return Err(
+            "Invalid transaction_description_placeholder. Requires transaction_description_optional to be true."
+                .to_string(),
+        );
+    }
+    Ok(())
+}
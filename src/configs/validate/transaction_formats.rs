@@ -1,3 +1,8 @@
+use crate::parsers::transaction::utils::parse_format_field;
+
+/// Validate transaction_formats configuration option. Each format is a non-empty list of
+/// known fields, each optionally marked "?" if some rows omit it (e.g. "balance?"), with
+/// at least one field not optional.
 pub fn transaction_formats(formats: &Vec<Vec<String>>) -> Result<(), String> {
     let allowed_tokens = ["date", "description", "amount", "balance"]; // extend as needed
     for fmt in formats {
@@ -5,13 +10,41 @@ pub fn transaction_formats(formats: &Vec<Vec<String>>) -> Result<(), String> {
             return Err("Invalid transaction_formats. Cannot be empty".into());
         }
         for token in fmt {
-            if !allowed_tokens.iter().any(|a| a == token) {
+            let (field, _) = parse_format_field(token);
+            if !allowed_tokens.contains(&field) {
                 return Err(format!(
                     "Invalid transaction_formats. Unknown token '{}'",
                     token
                 ));
             }
         }
+        if fmt.iter().all(|token| parse_format_field(token).1) {
+            return Err(
+                "Invalid transaction_formats. A format must have a field not marked optional"
+                    .into(),
+            );
+        }
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn formats(tokens: &[&str]) -> Vec<Vec<String>> {
+        vec![tokens.iter().map(|t| t.to_string()).collect()]
+    }
+
+    #[test]
+    fn test_transaction_formats_optional_marker() {
+        assert!(
+            transaction_formats(&formats(&["date", "description", "amount", "balance?"])).is_ok()
+        );
+        assert_eq!(
+            transaction_formats(&formats(&["date", "balance??"])),
+            Err("Invalid transaction_formats. Unknown token 'balance??'".to_string())
+        );
+        assert!(transaction_formats(&formats(&["amount?", "balance?"])).is_err());
+    }
+}
@@ -0,0 +1,10 @@
+/// Must be "table" or "labelled_blocks"
+pub fn transaction_layout(layout: &str) -> Result<(), String> {
+    if matches!(layout, "table" | "labelled_blocks") {
+        return Ok(());
+    }
+    Err(format!(
+        "Invalid transaction_layout: {}. Must be \"table\" or \"labelled_blocks\"",
+        layout
+    ))
+}
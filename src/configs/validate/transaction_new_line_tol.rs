@@ -1,7 +1,9 @@
-use crate::configs::validate::utils::validate_tolerance;
+use crate::configs::validate::utils::tolerance::MAX_Y_TOLERANCE;
+use crate::configs::validate::utils::{validate_tolerance, validate_tolerance_cap};
 
 pub fn transaction_new_line_tol(tol: i32) -> Result<(), String> {
-    let result = validate_tolerance(tol);
+    let result = validate_tolerance(tol)
+        .and_then(|_| validate_tolerance_cap(tol, MAX_Y_TOLERANCE, "new lines"));
     if result.is_err() {
         return Err(format!(
             "Invalid transaction_new_line_tol: {}. {}",
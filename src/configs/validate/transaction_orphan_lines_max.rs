@@ -0,0 +1,9 @@
+pub fn transaction_orphan_lines_max(max_lines: i32) -> Result<(), String> {
+    if max_lines < 0 {
+        return Err(format!(
+            "Invalid transaction_orphan_lines_max: {}. Must be 0 or more.",
+            max_lines
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,15 @@
+use regex::Regex;
+
+/// Summary patterns must not match the empty string (e.g., "x*"), as they would
+/// match every description
+pub fn transaction_summary_exclude(patterns: &[Regex]) -> Result<(), String> {
+    for pattern in patterns {
+        if pattern.is_match("") {
+            return Err(format!(
+                "Invalid transaction_summary_exclude pattern '{}'. Must not match an empty string.",
+                pattern.as_str()
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,10 @@
+/// Must be "statement" or "page"
+pub fn transaction_terms_stop_scope(scope: &str) -> Result<(), String> {
+    if matches!(scope, "statement" | "page") {
+        return Ok(());
+    }
+    Err(format!(
+        "Invalid transaction_terms_stop_scope: {}. Must be \"statement\" or \"page\"",
+        scope
+    ))
+}
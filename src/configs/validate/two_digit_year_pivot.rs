@@ -0,0 +1,24 @@
+pub fn two_digit_year_pivot(pivot: Option<u32>) -> Result<(), String> {
+    // Not set, 2-digit years are read as 20xx instead
+    match pivot {
+        Some(year) if !(1970..2100).contains(&year) => Err(format!(
+            "Invalid two_digit_year_pivot: {}. Must be a year from 1970 to 2099",
+            year
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_digit_year_pivot() {
+        assert!(two_digit_year_pivot(None).is_ok());
+        assert!(two_digit_year_pivot(Some(1999)).is_ok());
+        assert!(two_digit_year_pivot(Some(1969)).is_err());
+        let error = two_digit_year_pivot(Some(2100)).unwrap_err();
+        assert!(error.starts_with("Invalid two_digit_year_pivot: 2100."));
+    }
+}
@@ -1,13 +1,19 @@
-use crate::formats::date::get_valid_formats;
+use crate::formats::date::{get_registered_formats, get_valid_formats};
 
-/// Validate date formats.
+/// Validate date formats, built-in or registered at runtime.
 pub fn validate_date_formats(date_formats: &[String]) -> Result<(), String> {
     let valid_formats = get_valid_formats();
+    let registered_formats = get_registered_formats();
     for format in date_formats {
-        if !valid_formats.contains(&format.as_str()) {
+        if !valid_formats.contains(&format.as_str()) && !registered_formats.contains(format) {
             return Err(format!(
                 "Invalid date format: '{}'. Valid formats are: {:?}",
-                format, valid_formats
+                format,
+                valid_formats
+                    .iter()
+                    .map(|name| name.to_string())
+                    .chain(registered_formats)
+                    .collect::<Vec<_>>()
             ));
         }
     }
@@ -11,5 +11,5 @@ pub use amount_formats::validate_amount_formats;
 pub use date_formats::validate_date_formats;
 pub use iso_3166_1_alpha_2::is_valid_iso_3166_1_alpha_2;
 pub use patterns::validate_patterns;
-pub use terms::validate_terms;
-pub use tolerance::validate_tolerance;
+pub use terms::{duplicate_terms, normalize_term, validate_term_characters, validate_terms};
+pub use tolerance::{validate_alignment_tolerance, validate_tolerance, validate_tolerance_cap};
@@ -5,6 +5,7 @@ pub fn validate_terms(terms: &Vec<String>, allow_empty: bool) -> Result<(), Stri
         if term.trim().is_empty() && !allow_empty {
             return Err("Terms cannot be empty.".to_string());
         }
+        validate_term_characters(term)?;
         let word_count = term.split_whitespace().count();
         if word_count > 10 {
             return Err(format!(
@@ -15,3 +16,99 @@ pub fn validate_terms(terms: &Vec<String>, allow_empty: bool) -> Result<(), Stri
     }
     Ok(())
 }
+
+/// Check that a term has no leading or trailing whitespace, zero-width characters or
+/// control characters. These are invisible in the JSON config (e.g., when copied from a
+/// PDF), but stop the term from ever matching the statement text.
+pub fn validate_term_characters(term: &str) -> Result<(), String> {
+    for (position, c) in term.chars().enumerate() {
+        let kind = if is_zero_width(c) {
+            "zero-width"
+        } else if c.is_control() {
+            "control"
+        } else {
+            continue;
+        };
+        return Err(format!(
+            "Term '{}' contains a {} character U+{:04X} at position {}.",
+            term.escape_debug(),
+            kind,
+            c as u32,
+            position
+        ));
+    }
+    if term.trim() != term {
+        return Err(format!(
+            "Term '{}' has leading or trailing whitespace.",
+            term
+        ));
+    }
+    Ok(())
+}
+
+/// Normalise a term as read from a statement: without leading or trailing whitespace
+/// and zero-width characters
+pub fn normalize_term(term: &str) -> String {
+    term.trim_matches(|c: char| c.is_whitespace() || is_zero_width(c))
+        .to_string()
+}
+
+/// Get the terms that are repeated after normalisation, in order of first repeat
+pub fn duplicate_terms(terms: &[String]) -> Vec<String> {
+    let mut seen = Vec::new();
+    let mut duplicates = Vec::new();
+    for term in terms {
+        let normalized = normalize_term(term);
+        if seen.contains(&normalized) {
+            if !duplicates.contains(&normalized) {
+                duplicates.push(normalized);
+            }
+        } else {
+            seen.push(normalized);
+        }
+    }
+    duplicates
+}
+
+fn is_zero_width(c: char) -> bool {
+    matches!(
+        c,
+        '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_term_with_zero_width_space_rejected() {
+        let terms = vec!["Smart Access\u{200B}".to_string()];
+        let err = validate_terms(&terms, false).unwrap_err();
+        assert_eq!(
+            err,
+            "Term 'Smart Access\\u{200b}' contains a zero-width character U+200B at position 12."
+        );
+    }
+
+    #[test]
+    fn test_term_with_control_or_outer_whitespace_rejected() {
+        let err = validate_term_characters("Smart\tAccess").unwrap_err();
+        assert!(err.contains("control character U+0009 at position 5"));
+        let err = validate_term_characters("Smart Access ").unwrap_err();
+        assert!(err.contains("leading or trailing whitespace"));
+        assert!(validate_term_characters("Smart Access").is_ok());
+    }
+
+    #[test]
+    fn test_normalize_and_duplicate_terms() {
+        assert_eq!(normalize_term(" Smart Access\u{200D} "), "Smart Access");
+        let terms = vec![
+            "Smart Access".to_string(),
+            "Smart Access ".to_string(),
+            "Netbank".to_string(),
+            "\u{FEFF}Smart Access".to_string(),
+        ];
+        assert_eq!(duplicate_terms(&terms), vec!["Smart Access".to_string()]);
+    }
+}
@@ -1,3 +1,15 @@
+/// Largest accepted tolerance (in points) for y alignments ("y1", "y2") and new lines,
+/// about four lines of typical statement text
+pub const MAX_Y_TOLERANCE: i32 = 50;
+
+/// Largest accepted tolerance (in points) for x alignments ("x1", "x2"), about the
+/// width of a wide column
+pub const MAX_X_TOLERANCE: i32 = 200;
+
+/// Tolerance value accepted regardless of the maximum, for configs that really need a
+/// huge tolerance
+pub const UNCAPPED_TOLERANCE: i32 = 9999;
+
 /// General validator for tolerance values.
 pub fn validate_tolerance(val: i32) -> Result<(), String> {
     if val < 0 {
@@ -5,3 +17,60 @@ pub fn validate_tolerance(val: i32) -> Result<(), String> {
     }
     Ok(())
 }
+
+/// Validator for the tolerance of an alignment ("x1", "x2", "y1", "y2" or "" for none),
+/// capped by the axis of the alignment unless set to UNCAPPED_TOLERANCE.
+pub fn validate_alignment_tolerance(val: i32, alignment: &str) -> Result<(), String> {
+    validate_tolerance(val)?;
+    if alignment.starts_with('y') {
+        validate_tolerance_cap(
+            val,
+            MAX_Y_TOLERANCE,
+            &format!("\"{}\" alignment", alignment),
+        )
+    } else if alignment.starts_with('x') {
+        validate_tolerance_cap(
+            val,
+            MAX_X_TOLERANCE,
+            &format!("\"{}\" alignment", alignment),
+        )
+    } else {
+        Ok(())
+    }
+}
+
+/// Validator for a tolerance capped at max, unless set to UNCAPPED_TOLERANCE.
+pub fn validate_tolerance_cap(val: i32, max: i32, context: &str) -> Result<(), String> {
+    if val > max && val != UNCAPPED_TOLERANCE {
+        return Err(format!(
+            "Must be <= {} for {}, as larger values effectively disable the constraint \
+             (a pixel value or coordinate may have been pasted). Set to {} if a larger \
+             tolerance is really needed",
+            max, context, UNCAPPED_TOLERANCE
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alignment_tolerance_caps() {
+        assert!(validate_alignment_tolerance(5, "y1").is_ok());
+        assert!(validate_alignment_tolerance(150, "x2").is_ok());
+        assert!(validate_alignment_tolerance(300, "").is_ok());
+        assert!(validate_alignment_tolerance(UNCAPPED_TOLERANCE, "y1").is_ok());
+        assert!(validate_alignment_tolerance(-1, "").is_err());
+
+        let error = validate_alignment_tolerance(300, "y1").unwrap_err();
+
+        assert!(
+            error.starts_with("Must be <= 50 for \"y1\" alignment"),
+            "{}",
+            error
+        );
+        assert!(validate_alignment_tolerance(300, "x1").is_err());
+    }
+}
@@ -0,0 +1,57 @@
+use std::fmt;
+
+/// Errors of the native Rust API (see Parser). The Python bindings raise the
+/// matching Python exception for each variant.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TranstractorError {
+    /// A config could not be read or is invalid
+    ConfigLoad(String),
+    /// A config key is not registered
+    ConfigAccess(String),
+    /// Layout text is malformed (see layout_to_text_items)
+    LayoutParse(String),
+    /// An argument is invalid (e.g., an unknown fixers mode or date timezone)
+    InvalidArgument(String),
+    /// No registered config is applicable to the statement
+    StatementNotSupported(String),
+    /// No config gave statement data without errors
+    NoErrorFreeStatementData(String),
+    /// Parsing failed for another reason
+    Parse(String),
+}
+
+impl fmt::Display for TranstractorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (kind, message) = match self {
+            TranstractorError::ConfigLoad(message) => ("Config load error", message),
+            TranstractorError::ConfigAccess(message) => ("Config access error", message),
+            TranstractorError::LayoutParse(message) => ("Layout parse error", message),
+            TranstractorError::InvalidArgument(message) => ("Invalid argument", message),
+            TranstractorError::StatementNotSupported(message) => {
+                ("Statement not supported", message)
+            }
+            TranstractorError::NoErrorFreeStatementData(message) => {
+                ("No error-free statement data", message)
+            }
+            TranstractorError::Parse(message) => ("Parse error", message),
+        };
+        write!(f, "{}: {}", kind, message)
+    }
+}
+
+impl std::error::Error for TranstractorError {}
+
+impl TranstractorError {
+    /// Get the message of the error, without its kind
+    pub fn message(&self) -> &str {
+        match self {
+            TranstractorError::ConfigLoad(message)
+            | TranstractorError::ConfigAccess(message)
+            | TranstractorError::LayoutParse(message)
+            | TranstractorError::InvalidArgument(message)
+            | TranstractorError::StatementNotSupported(message)
+            | TranstractorError::NoErrorFreeStatementData(message)
+            | TranstractorError::Parse(message) => message,
+        }
+    }
+}
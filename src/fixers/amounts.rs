@@ -9,20 +9,22 @@ use crate::structs::StatementData;
 /// then it reverses the sign of the transaction amount.
 pub fn fix_amounts(sd: &mut StatementData) {
     // Start with the opening balance, return early if not set
-    let mut balance = match sd.opening_balance {
+    let mut balance = match sd.opening_balance_money() {
         Some(opening_balance) => opening_balance,
         None => return, // Can't fix amounts without opening balance
     };
+    let tolerance = sd.balance_tolerance;
 
     for transaction in &mut sd.proto_transactions {
         // Skip transactions that don't have both amount and balance
-        let (amount, transaction_balance) = match (transaction.amount, transaction.balance) {
-            (Some(amt), Some(bal)) => (amt, bal),
-            _ => continue,
-        };
-
-        // Check if the balance is more consistent with the reversed amount
-        // Using a small tolerance (0.01) for floating point comparison
+        let (amount, transaction_balance) =
+            match (transaction.amount_money(), transaction.balance_money()) {
+                (Some(amt), Some(bal)) => (amt, bal),
+                _ => continue,
+            };
+
+        // Check if the balance is more consistent with the reversed amount, comparing
+        // in cents within the balance tolerance
         let expected_balance_with_current_amount = balance + amount;
         let expected_balance_with_reversed_amount = balance - amount;
 
@@ -30,8 +32,10 @@ pub fn fix_amounts(sd: &mut StatementData) {
         let diff_reversed = (transaction_balance - expected_balance_with_reversed_amount).abs();
 
         // If the reversed amount gives a better match, reverse the transaction amount
-        if diff_reversed < diff_current && diff_reversed < 0.01 {
+        if diff_reversed < diff_current && diff_reversed <= tolerance {
+            let amount = transaction.amount.unwrap_or_default();
             transaction.set_amount(-amount);
+            transaction.amount_inferred = true;
         }
 
         // Update the running balance to the actual transaction balance
@@ -76,8 +80,9 @@ mod tests {
 
         fix_amounts(&mut sd);
 
-        // The amount should now be -100
+        // The amount should now be -100, with its sign inferred
         assert_eq!(sd.proto_transactions[0].amount, Some(-100.0));
+        assert_eq!(sd.proto_transactions[0].amount_source(), "inferred");
     }
 
     #[test]
@@ -95,8 +100,9 @@ mod tests {
 
         fix_amounts(&mut sd);
 
-        // The amount should remain +100
+        // The amount should remain +100, as parsed
         assert_eq!(sd.proto_transactions[0].amount, Some(100.0));
+        assert_eq!(sd.proto_transactions[0].amount_source(), "parsed");
     }
 
     #[test]
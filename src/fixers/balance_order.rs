@@ -0,0 +1,378 @@
+use crate::structs::{Money, StatementData};
+
+/// Largest number of transactions on one day that are searched for a balance order.
+/// Larger groups are left in their order, as the search could be slow.
+const MAX_GROUP_SIZE: usize = 12;
+
+/// Largest number of partial orders tried when searching a group for a balance order,
+/// after which the group is left in its order
+const MAX_SEARCH_STEPS: usize = 100_000;
+
+/// Reorder transactions on the same day so that their stated balances are consistent.
+/// Occurs when a statement lists the transactions of a day in a different order than
+/// they were applied (e.g., credits before debits).
+///
+/// Transactions are grouped by consecutive equal dates. For each group whose stated
+/// balances do not follow from the previous balance (the balance of the previous
+/// transaction, or the opening balance for the first day), a chain is searched: each
+/// transaction must have a balance equal to the previous balance plus or minus its
+/// amount, within the balance tolerance. The sign is not fixed here, as the signs of
+/// amounts are fixed afterwards (see fix_amounts). Candidates are tried greedily in
+/// their original order, backtracking when the chain breaks. Transactions with equal
+/// amounts and balances are interchangeable, so only the first of them is tried at
+/// each position, and the search of a group stops after MAX_SEARCH_STEPS steps.
+///
+/// For the first day, if no chain follows from the opening balance (e.g., its sign
+/// is wrong), a chain starting from any transaction is accepted. If no chain is
+/// found, or the search stops, the transactions are left in their original order and
+/// a warning is logged, as the balance mismatch is reported by check_balances.
+///
+/// Groups with a transaction missing a date, amount or balance are skipped.
+pub fn fix_balance_order(sd: &mut StatementData) {
+    let tolerance = sd.balance_tolerance;
+    let mut warnings = Vec::new();
+    let mut start = 0;
+
+    while start < sd.proto_transactions.len() {
+        let date = sd.proto_transactions[start].date;
+        let end = start
+            + sd.proto_transactions[start..]
+                .iter()
+                .take_while(|tx| tx.date == date)
+                .count();
+        let group = &sd.proto_transactions[start..end];
+
+        // Amounts and balances of the group, if all are set
+        let values: Option<Vec<(Money, Money)>> = group
+            .iter()
+            .map(|tx| Some((tx.amount_money()?, tx.balance_money()?)))
+            .collect();
+        let values = match values {
+            Some(values) if date.is_some() && (2..=MAX_GROUP_SIZE).contains(&values.len()) => {
+                values
+            }
+            _ => {
+                start = end;
+                continue;
+            }
+        };
+
+        // Balance before the group
+        let previous = if start == 0 {
+            sd.opening_balance_money()
+        } else {
+            sd.proto_transactions[start - 1].balance_money()
+        };
+
+        let identity: Vec<usize> = (0..values.len()).collect();
+        if !is_chain(previous, &identity, &values, tolerance) {
+            let mut steps = MAX_SEARCH_STEPS;
+            let order = find_chain(previous, &values, tolerance, &mut steps).or_else(|| {
+                (start == 0 && previous.is_some())
+                    .then(|| find_chain(None, &values, tolerance, &mut steps))
+                    .flatten()
+            });
+            match order {
+                Some(order) => {
+                    let original = group.to_vec();
+                    for (offset, index) in order.into_iter().enumerate() {
+                        sd.proto_transactions[start + offset] = original[index].clone();
+                    }
+                }
+                None => {
+                    let format_date = |ms: i64| match sd.to_naive_date(ms) {
+                        Some(date) => date.format("%d %b %Y").to_string(),
+                        None => ms.to_string(),
+                    };
+                    let reason = if steps == 0 {
+                        "have too many possible orders to search for one matching their balances"
+                    } else {
+                        "cannot be ordered to match their balances"
+                    };
+                    warnings.push(format!(
+                        "Transactions {} to {} dated {} {}",
+                        start + 1,
+                        end,
+                        format_date(date.unwrap_or_default()),
+                        reason
+                    ));
+                }
+            }
+        }
+
+        start = end;
+    }
+
+    for warning in warnings {
+        sd.add_warning(warning);
+    }
+}
+
+/// Check if the balance follows from the previous balance and the amount, with
+/// either sign of the amount
+fn fits(previous: Money, (amount, balance): (Money, Money), tolerance: Money) -> bool {
+    balance.within(previous + amount, tolerance) || balance.within(previous - amount, tolerance)
+}
+
+/// Check if the values in the given order form a chain from the previous balance, or
+/// from the first balance if there is no previous balance
+fn is_chain(
+    previous: Option<Money>,
+    order: &[usize],
+    values: &[(Money, Money)],
+    tolerance: Money,
+) -> bool {
+    let mut balance = previous;
+    order.iter().all(|&index| {
+        let ok = balance.is_none_or(|previous| fits(previous, values[index], tolerance));
+        balance = Some(values[index].1);
+        ok
+    })
+}
+
+/// Find an order of the values that forms a chain (see is_chain), trying at most the
+/// given number of steps, which are deducted. None if no chain is found in the steps.
+fn find_chain(
+    previous: Option<Money>,
+    values: &[(Money, Money)],
+    tolerance: Money,
+    steps: &mut usize,
+) -> Option<Vec<usize>> {
+    let mut order = Vec::with_capacity(values.len());
+    let mut used = vec![false; values.len()];
+    extend_chain(previous, values, tolerance, &mut order, &mut used, steps).then_some(order)
+}
+
+fn extend_chain(
+    previous: Option<Money>,
+    values: &[(Money, Money)],
+    tolerance: Money,
+    order: &mut Vec<usize>,
+    used: &mut [bool],
+    steps: &mut usize,
+) -> bool {
+    if order.len() == values.len() {
+        return true;
+    }
+    for index in 0..values.len() {
+        if used[index] || !previous.is_none_or(|p| fits(p, values[index], tolerance)) {
+            continue;
+        }
+        // Equal values were tried at this position already
+        if (0..index).any(|other| !used[other] && values[other] == values[index]) {
+            continue;
+        }
+        if *steps == 0 {
+            return false;
+        }
+        *steps -= 1;
+        used[index] = true;
+        order.push(index);
+        if extend_chain(Some(values[index].1), values, tolerance, order, used, steps) {
+            return true;
+        }
+        order.pop();
+        used[index] = false;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::ProtoTransaction;
+
+    const DAY: i64 = 86_400_000;
+
+    fn make_statement_data(opening: f64, transactions: &[(i64, f64, f64)]) -> StatementData {
+        let mut sd = StatementData::new();
+        sd.set_opening_balance(opening);
+        for (n, &(date, amount, balance)) in transactions.iter().enumerate() {
+            let mut tx = ProtoTransaction::new();
+            tx.date = Some(date * DAY);
+            tx.set_amount(amount);
+            tx.set_balance(balance);
+            tx.description = format!("Transaction {}", n + 1);
+            sd.add_proto_transaction(tx);
+        }
+        sd
+    }
+
+    fn descriptions(sd: &StatementData) -> Vec<&str> {
+        sd.proto_transactions
+            .iter()
+            .map(|tx| tx.description.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_reorders_scrambled_same_day_transactions() {
+        // Applied order: +200 (1200), -50 (1150), -300 (850), +25.5 (875.5)
+        let mut sd = make_statement_data(
+            1000.0,
+            &[
+                (1, -300.0, 850.0),
+                (1, 25.5, 875.5),
+                (1, 200.0, 1200.0),
+                (1, -50.0, 1150.0),
+                (2, -75.5, 800.0),
+            ],
+        );
+
+        fix_balance_order(&mut sd);
+
+        assert_eq!(
+            descriptions(&sd),
+            vec![
+                "Transaction 3",
+                "Transaction 4",
+                "Transaction 1",
+                "Transaction 2",
+                "Transaction 5"
+            ]
+        );
+        assert!(sd.errors.is_empty());
+    }
+
+    #[test]
+    fn test_reorders_later_day_from_previous_balance() {
+        // Day 2 applied order: -20 (80), +40 (120), -10 (110)
+        let mut sd = make_statement_data(
+            50.0,
+            &[
+                (1, 50.0, 100.0),
+                (2, -10.0, 110.0),
+                (2, 40.0, 120.0),
+                (2, -20.0, 80.0),
+            ],
+        );
+
+        fix_balance_order(&mut sd);
+
+        assert_eq!(
+            descriptions(&sd),
+            vec![
+                "Transaction 1",
+                "Transaction 4",
+                "Transaction 3",
+                "Transaction 2"
+            ]
+        );
+        assert!(sd.errors.is_empty());
+    }
+
+    #[test]
+    fn test_reorders_unsigned_amounts() {
+        // Debits printed without a sign, fixed later by fix_amounts. Applied order:
+        // +200 (1200), -300 (900), -100 (800)
+        let mut sd = make_statement_data(
+            1000.0,
+            &[(1, 300.0, 900.0), (1, 100.0, 800.0), (1, 200.0, 1200.0)],
+        );
+
+        fix_balance_order(&mut sd);
+
+        assert_eq!(
+            descriptions(&sd),
+            vec!["Transaction 3", "Transaction 1", "Transaction 2"]
+        );
+    }
+
+    #[test]
+    fn test_consistent_order_is_unchanged() {
+        let mut sd = make_statement_data(
+            100.0,
+            &[(1, 10.0, 110.0), (1, -110.0, 0.0), (1, 110.0, 110.0)],
+        );
+
+        fix_balance_order(&mut sd);
+
+        assert_eq!(
+            descriptions(&sd),
+            vec!["Transaction 1", "Transaction 2", "Transaction 3"]
+        );
+        assert!(sd.errors.is_empty());
+    }
+
+    #[test]
+    fn test_no_chain_keeps_order_and_logs_warning() {
+        let mut sd = make_statement_data(
+            100.0,
+            &[(3, 10.0, 110.0), (3, 5.0, 999.0), (3, -20.0, 90.0)],
+        );
+
+        fix_balance_order(&mut sd);
+
+        assert_eq!(
+            descriptions(&sd),
+            vec!["Transaction 1", "Transaction 2", "Transaction 3"]
+        );
+        assert!(sd.errors.is_empty());
+        assert_eq!(
+            sd.warnings,
+            vec!["Transactions 1 to 3 dated 04 Jan 1970 cannot be ordered to match their balances"]
+        );
+    }
+
+    #[test]
+    fn test_wrong_opening_balance_sign_still_reorders() {
+        // Opening balance unsigned, first day applied order: -30 (-130), +10 (-120)
+        let mut sd = make_statement_data(100.0, &[(1, 10.0, -120.0), (1, -30.0, -130.0)]);
+
+        fix_balance_order(&mut sd);
+
+        assert_eq!(descriptions(&sd), vec!["Transaction 2", "Transaction 1"]);
+        assert!(sd.errors.is_empty());
+    }
+
+    #[test]
+    fn test_identical_transactions_are_searched_quickly() {
+        // Twelve identical amounts, which fit either sign from any balance of 10 or 0
+        // but can never reach the balance of 999
+        let mut transactions = vec![(1, 10.0, 10.0); 6];
+        transactions.extend(vec![(1, 10.0, 0.0); 5]);
+        transactions.push((1, 10.0, 999.0));
+        let mut sd = make_statement_data(0.0, &transactions);
+
+        let started = std::time::Instant::now();
+        fix_balance_order(&mut sd);
+
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+        assert_eq!(
+            sd.warnings,
+            vec![
+                "Transactions 1 to 12 dated 02 Jan 1970 cannot be ordered to match their balances"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_stops_after_max_steps() {
+        let values: Vec<(Money, Money)> = [(10.0, 110.0), (-10.0, 100.0), (5.0, 105.0)]
+            .iter()
+            .map(|&(amount, balance)| (Money::from_f64(amount), Money::from_f64(balance)))
+            .collect();
+        let tolerance = Money::from_f64(0.01);
+        let previous = Some(Money::from_f64(100.0));
+
+        let mut steps = MAX_SEARCH_STEPS;
+        assert_eq!(
+            find_chain(previous, &values, tolerance, &mut steps),
+            Some(vec![0, 1, 2])
+        );
+        let mut steps = 2;
+        assert_eq!(find_chain(previous, &values, tolerance, &mut steps), None);
+        assert_eq!(steps, 0);
+    }
+
+    #[test]
+    fn test_skips_groups_missing_balances() {
+        let mut sd = make_statement_data(0.0, &[(1, 5.0, 15.0), (1, 10.0, 10.0)]);
+        sd.proto_transactions[1].balance = None;
+
+        fix_balance_order(&mut sd);
+
+        assert_eq!(descriptions(&sd), vec!["Transaction 1", "Transaction 2"]);
+        assert!(sd.errors.is_empty());
+    }
+}
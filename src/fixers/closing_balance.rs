@@ -8,27 +8,28 @@ use crate::structs::StatementData;
 /// to the negative of this expected value, it reverses the sign of the closing balance.
 pub fn fix_closing_balance(sd: &mut StatementData) {
     // Start with the opening balance, return early if not set
-    let mut balance = match sd.opening_balance {
+    let mut balance = match sd.opening_balance_money() {
         Some(opening_balance) => opening_balance,
         None => return, // Can't fix closing balance without opening balance
     };
 
     // Sum all transaction amounts to calculate expected closing balance
     for transaction in &sd.proto_transactions {
-        if let Some(amount) = transaction.amount {
+        if let Some(amount) = transaction.amount_money() {
             balance += amount;
         }
     }
 
     // Check if the closing balance should be reversed
-    if let Some(closing_balance) = sd.closing_balance {
-        // Check if the negative of the calculated balance is closer to the actual closing balance
-        // Using a small tolerance (0.01) for floating point comparison
+    if let Some(closing_balance) = sd.closing_balance_money() {
+        // Check if the negative of the calculated balance is closer to the actual closing
+        // balance, comparing in cents within the balance tolerance
         let diff_with_negative = (-balance - closing_balance).abs();
         let diff_with_positive = (balance - closing_balance).abs();
 
         // If the negative calculated balance is much closer (within tolerance), reverse the sign
-        if diff_with_negative < 0.01 && diff_with_negative < diff_with_positive {
+        if diff_with_negative <= sd.balance_tolerance && diff_with_negative < diff_with_positive {
+            let closing_balance = sd.closing_balance.unwrap_or_default();
             sd.set_closing_balance(-closing_balance);
         }
     }
@@ -1,38 +1,105 @@
 use crate::structs::StatementData;
-use chrono::{DateTime, Datelike, Utc};
+use chrono::{DateTime, Datelike};
+
+/// Typical length of a statement period, used to judge how far a date is from the period
+/// when the statement end date is unknown
+const STATEMENT_PERIOD_DAYS: i64 = 31;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Distance in milliseconds of a date from the statement period [start_date, end_date]
+fn distance_from_period(date: i64, start_date: i64, end_date: i64) -> i64 {
+    if date < start_date {
+        start_date - date
+    } else if date > end_date {
+        date - end_date
+    } else {
+        0
+    }
+}
+
+/// Resolve the year of a transaction date before the statement start date (see
+/// fix_year_crossovers), returning the date moved to the following year or unchanged.
+fn resolve_crossover(transaction_date: i64, start_date: i64, end_date: i64) -> i64 {
+    if transaction_date >= start_date {
+        return transaction_date;
+    }
+    let Some(transaction_datetime) = DateTime::from_timestamp_millis(transaction_date) else {
+        return transaction_date;
+    };
+    if transaction_datetime.month() == 2 && transaction_datetime.day() == 29 {
+        return transaction_date;
+    }
+    let Some(moved_datetime) = transaction_datetime.with_year(transaction_datetime.year() + 1)
+    else {
+        return transaction_date;
+    };
+    let moved_date = moved_datetime.timestamp_millis();
+    if (moved_date - start_date).abs() < 365 * DAY_MS
+        && distance_from_period(moved_date, start_date, end_date)
+            < distance_from_period(transaction_date, start_date, end_date)
+    {
+        moved_date
+    } else {
+        transaction_date
+    }
+}
 
 /// Fix transactions with year crossover dates.
 ///
 /// This function handles cases where transaction dates appear to be from the previous year
-/// due to year boundaries in statements. If a transaction date is before the statement start date,
-/// it assumes the transaction actually occurred in the following year and adjusts accordingly.
+/// due to year boundaries in statements (e.g., "Jan 5" on a statement starting 15 Dec 2023 is
+/// parsed with the start date year as 5 Jan 2023). A transaction dated before the statement
+/// start date is moved to the following year if that puts it within a year of the start date
+/// and closer to the statement period. The period ends on the statement end date if read,
+/// otherwise a typical period length after the start date. Dates just before the start date
+/// (e.g., December transactions on a statement starting in January) are left in their year.
+/// Leap day (29 February) dates are never moved, as their year has already been resolved
+/// when parsed (see DateParts::to_utc_timestamp).
+///
+/// Consecutive transactions on the same calendar day (e.g., a first row with a full date
+/// "31 Dec 2024" followed by rows with a short date "31 Dec" read with the start date
+/// year) are resolved together: they take the year of the first transaction of the day,
+/// which is then adjusted for all of them.
 pub fn fix_year_crossovers(sd: &mut StatementData) {
     // Return early if no start date
     let start_date = match sd.start_date {
         Some(date) => date,
         None => return,
     };
+    let end_date = match sd.end_date {
+        Some(date) if date >= start_date => date,
+        _ => start_date + STATEMENT_PERIOD_DAYS * DAY_MS,
+    };
 
-    // Get the year from the start date
-    let start_datetime =
-        DateTime::from_timestamp_millis(start_date).unwrap_or(DateTime::<Utc>::MIN_UTC);
-    let year = start_datetime.year();
-
+    // Month, day and resolved year of the first transaction of the current day
+    let mut day: Option<(u32, u32, i32)> = None;
     for transaction in &mut sd.proto_transactions {
-        if let Some(transaction_date) = transaction.date {
-            // If transaction date is before start date, assume it's in the next year
-            if transaction_date < start_date {
-                // Convert to DateTime to manipulate the year
-                if let Some(transaction_datetime) =
-                    DateTime::from_timestamp_millis(transaction_date)
-                {
-                    // Create a new date with the next year (year + 1) to match TypeScript behavior
-                    if let Some(new_datetime) = transaction_datetime.with_year(year + 1) {
-                        transaction.set_date(new_datetime.timestamp_millis());
-                    }
-                }
+        let Some(transaction_date) = transaction.date else {
+            continue;
+        };
+        let Some(transaction_datetime) = DateTime::from_timestamp_millis(transaction_date) else {
+            continue;
+        };
+        let (month, day_of_month) = (transaction_datetime.month(), transaction_datetime.day());
+        if let Some((day_month, day_day, resolved_year)) = day
+            && (day_month, day_day) == (month, day_of_month)
+        {
+            if transaction_datetime.year() != resolved_year
+                && let Some(aligned) = transaction_datetime.with_year(resolved_year)
+            {
+                transaction.set_date(aligned.timestamp_millis());
             }
+            continue;
         }
+
+        let resolved_date = resolve_crossover(transaction_date, start_date, end_date);
+        if resolved_date != transaction_date {
+            transaction.set_date(resolved_date);
+        }
+        let resolved_year = DateTime::from_timestamp_millis(resolved_date)
+            .map_or(transaction_datetime.year(), |dt| dt.year());
+        day = Some((month, day_of_month, resolved_year));
     }
 }
 
@@ -112,7 +179,7 @@ mod tests {
             .timestamp_millis();
         sd.set_start_date(start_date);
 
-        // Transaction date: December 25, 2023 (appears to be previous year)
+        // Transaction date: December 25, 2023 (just before the statement period)
         let mut tx1 = ProtoTransaction::new();
         let tx_date = Utc
             .with_ymd_and_hms(2023, 12, 25, 0, 0, 0)
@@ -123,12 +190,8 @@ mod tests {
 
         fix_year_crossovers(&mut sd);
 
-        // Transaction should be moved to December 25, 2025 (year + 1 from start date year)
-        let expected_date = Utc
-            .with_ymd_and_hms(2025, 12, 25, 0, 0, 0)
-            .unwrap()
-            .timestamp_millis();
-        assert_eq!(sd.proto_transactions[0].date, Some(expected_date));
+        // Transaction should stay in December 2023, not move to December 2024 or 2025
+        assert_eq!(sd.proto_transactions[0].date, Some(tx_date));
     }
 
     #[test]
@@ -141,7 +204,7 @@ mod tests {
             .timestamp_millis();
         sd.set_start_date(start_date);
 
-        // Transaction 1: Before start date (December 2023) - should be moved to 2025
+        // Transaction 1: Just before start date (December 2023) - should remain unchanged
         let mut tx1 = ProtoTransaction::new();
         let tx1_date = Utc
             .with_ymd_and_hms(2023, 12, 20, 0, 0, 0)
@@ -159,10 +222,11 @@ mod tests {
         tx2.set_date(tx2_date);
         sd.add_proto_transaction(tx2);
 
-        // Transaction 3: Before start date (November 2023) - should be moved to 2025
+        // Transaction 3: Before start date (January 2023, read with the start date year)
+        // - should be moved to January 2024
         let mut tx3 = ProtoTransaction::new();
         let tx3_date = Utc
-            .with_ymd_and_hms(2023, 11, 30, 0, 0, 0)
+            .with_ymd_and_hms(2023, 1, 20, 0, 0, 0)
             .unwrap()
             .timestamp_millis();
         tx3.set_date(tx3_date);
@@ -171,18 +235,14 @@ mod tests {
         fix_year_crossovers(&mut sd);
 
         // Check results
-        let expected_tx1_date = Utc
-            .with_ymd_and_hms(2025, 12, 20, 0, 0, 0)
-            .unwrap()
-            .timestamp_millis();
         let expected_tx3_date = Utc
-            .with_ymd_and_hms(2025, 11, 30, 0, 0, 0)
+            .with_ymd_and_hms(2024, 1, 20, 0, 0, 0)
             .unwrap()
             .timestamp_millis();
 
-        assert_eq!(sd.proto_transactions[0].date, Some(expected_tx1_date)); // tx1 moved to 2025
+        assert_eq!(sd.proto_transactions[0].date, Some(tx1_date)); // tx1 unchanged
         assert_eq!(sd.proto_transactions[1].date, Some(tx2_date)); // tx2 unchanged
-        assert_eq!(sd.proto_transactions[2].date, Some(expected_tx3_date)); // tx3 moved to 2025
+        assert_eq!(sd.proto_transactions[2].date, Some(expected_tx3_date)); // tx3 moved to 2024
     }
 
     #[test]
@@ -255,13 +315,8 @@ mod tests {
 
         fix_year_crossovers(&mut sd);
 
-        // Should be moved to next year
-        let original_datetime = DateTime::from_timestamp_millis(tx_date).unwrap();
-        let expected_date = original_datetime
-            .with_year(2024 + 1)
-            .unwrap()
-            .timestamp_millis();
-        assert_eq!(sd.proto_transactions[0].date, Some(expected_date));
+        // Should remain unchanged, as it is closer to the statement period than a year later
+        assert_eq!(sd.proto_transactions[0].date, Some(tx_date));
     }
 
     #[test]
@@ -285,28 +340,113 @@ mod tests {
 
         fix_year_crossovers(&mut sd);
 
-        // Should be moved to December 31, 2025 (year + 1 from start date year)
+        // Should remain in December 2023
+        assert_eq!(sd.proto_transactions[0].date, Some(tx_date));
+    }
+
+    #[test]
+    fn test_fix_year_crossovers_preserves_time_components() {
+        let mut sd = StatementData::new();
+        // Start date: December 15, 2023 at 12:30:45
+        let start_date = Utc
+            .with_ymd_and_hms(2023, 12, 15, 12, 30, 45)
+            .unwrap()
+            .timestamp_millis();
+        sd.set_start_date(start_date);
+
+        // Transaction: January 10, 2023 at 14:25:30
+        let mut tx1 = ProtoTransaction::new();
+        let tx_date = Utc
+            .with_ymd_and_hms(2023, 1, 10, 14, 25, 30)
+            .unwrap()
+            .timestamp_millis();
+        tx1.set_date(tx_date);
+        sd.add_proto_transaction(tx1);
+
+        fix_year_crossovers(&mut sd);
+
+        // Should be moved to January 10, 2024 at 14:25:30 (time preserved)
         let expected_date = Utc
-            .with_ymd_and_hms(2025, 12, 31, 23, 59, 59)
+            .with_ymd_and_hms(2024, 1, 10, 14, 25, 30)
             .unwrap()
             .timestamp_millis();
         assert_eq!(sd.proto_transactions[0].date, Some(expected_date));
     }
 
     #[test]
-    fn test_fix_year_crossovers_preserves_time_components() {
+    fn test_fix_year_crossovers_statement_spanning_new_year() {
+        let mut sd = StatementData::new();
+        // Statement period: December 15, 2023 to January 14, 2024
+        let start_date = Utc
+            .with_ymd_and_hms(2023, 12, 15, 0, 0, 0)
+            .unwrap()
+            .timestamp_millis();
+        sd.set_start_date(start_date);
+
+        // Dates read with the start date year: December 20 and 31, January 2 and 14
+        for (month, day) in [(12, 20), (12, 31), (1, 2), (1, 14)] {
+            let mut tx = ProtoTransaction::new();
+            tx.set_date(
+                Utc.with_ymd_and_hms(2023, month, day, 0, 0, 0)
+                    .unwrap()
+                    .timestamp_millis(),
+            );
+            sd.add_proto_transaction(tx);
+        }
+
+        fix_year_crossovers(&mut sd);
+
+        let dates: Vec<(i32, u32, u32)> = sd
+            .proto_transactions
+            .iter()
+            .map(|tx| {
+                let dt = DateTime::from_timestamp_millis(tx.date.unwrap()).unwrap();
+                (dt.year(), dt.month(), dt.day())
+            })
+            .collect();
+        assert_eq!(
+            dates,
+            vec![(2023, 12, 20), (2023, 12, 31), (2024, 1, 2), (2024, 1, 14)]
+        );
+    }
+
+    #[test]
+    fn test_fix_year_crossovers_bounded_by_end_date() {
+        let ymd = |year: i32, month: u32, day: u32| {
+            Utc.with_ymd_and_hms(year, month, day, 0, 0, 0)
+                .unwrap()
+                .timestamp_millis()
+        };
+        // Annual statement: July 1, 2023 to June 30, 2024. Without the end date, June 15
+        // is closer to a month-long period starting July 1, 2023 in 2023.
+        let mut sd = StatementData::new();
+        sd.set_start_date(ymd(2023, 7, 1));
+        let mut tx = ProtoTransaction::new();
+        tx.set_date(ymd(2023, 6, 15));
+        sd.add_proto_transaction(tx);
+
+        let mut unbounded = sd.clone();
+        fix_year_crossovers(&mut unbounded);
+        assert_eq!(unbounded.proto_transactions[0].date, Some(ymd(2023, 6, 15)));
+
+        sd.set_end_date(ymd(2024, 6, 30));
+        fix_year_crossovers(&mut sd);
+        assert_eq!(sd.proto_transactions[0].date, Some(ymd(2024, 6, 15)));
+    }
+
+    #[test]
+    fn test_fix_year_crossovers_older_years_not_moved() {
         let mut sd = StatementData::new();
-        // Start date: January 15, 2024 at 12:30:45
         let start_date = Utc
-            .with_ymd_and_hms(2024, 1, 15, 12, 30, 45)
+            .with_ymd_and_hms(2024, 6, 1, 0, 0, 0)
             .unwrap()
             .timestamp_millis();
         sd.set_start_date(start_date);
 
-        // Transaction: December 10, 2023 at 14:25:30
+        // An explicit date several years before the statement is left as is
         let mut tx1 = ProtoTransaction::new();
         let tx_date = Utc
-            .with_ymd_and_hms(2023, 12, 10, 14, 25, 30)
+            .with_ymd_and_hms(2020, 5, 1, 0, 0, 0)
             .unwrap()
             .timestamp_millis();
         tx1.set_date(tx_date);
@@ -314,11 +454,126 @@ mod tests {
 
         fix_year_crossovers(&mut sd);
 
-        // Should be moved to December 10, 2025 at 14:25:30 (time preserved)
-        let expected_date = Utc
-            .with_ymd_and_hms(2025, 12, 10, 14, 25, 30)
+        assert_eq!(sd.proto_transactions[0].date, Some(tx_date));
+    }
+
+    #[test]
+    fn test_fix_year_crossovers_leap_day_not_moved() {
+        let mut sd = StatementData::new();
+        // Start date: March 1, 2024
+        let start_date = Utc
+            .with_ymd_and_hms(2024, 3, 1, 0, 0, 0)
             .unwrap()
             .timestamp_millis();
-        assert_eq!(sd.proto_transactions[0].date, Some(expected_date));
+        sd.set_start_date(start_date);
+
+        // Transaction on the leap day before the start date
+        let mut tx1 = ProtoTransaction::new();
+        let tx_date = Utc
+            .with_ymd_and_hms(2024, 2, 29, 0, 0, 0)
+            .unwrap()
+            .timestamp_millis();
+        tx1.set_date(tx_date);
+        sd.add_proto_transaction(tx1);
+
+        fix_year_crossovers(&mut sd);
+
+        assert_eq!(sd.proto_transactions[0].date, Some(tx_date));
+    }
+
+    #[test]
+    fn test_fix_year_crossovers_leap_day_not_moved_to_next_leap_year() {
+        let mut sd = StatementData::new();
+        // Start date: March 1, 2023 (year + 1 is a leap year)
+        let start_date = Utc
+            .with_ymd_and_hms(2023, 3, 1, 0, 0, 0)
+            .unwrap()
+            .timestamp_millis();
+        sd.set_start_date(start_date);
+
+        // Explicit leap day from an earlier leap year
+        let mut tx1 = ProtoTransaction::new();
+        let tx_date = Utc
+            .with_ymd_and_hms(2020, 2, 29, 0, 0, 0)
+            .unwrap()
+            .timestamp_millis();
+        tx1.set_date(tx_date);
+        sd.add_proto_transaction(tx1);
+
+        fix_year_crossovers(&mut sd);
+
+        // Should not be moved to 2024-02-29
+        assert_eq!(sd.proto_transactions[0].date, Some(tx_date));
+    }
+
+    #[test]
+    fn test_fix_year_crossovers_leap_day_across_year_end() {
+        let ymd = |year: i32, month: u32, day: u32| {
+            Utc.with_ymd_and_hms(year, month, day, 0, 0, 0)
+                .unwrap()
+                .timestamp_millis()
+        };
+        // Statement period: December 1, 2023 to February 29, 2024. "05/01" is read with
+        // the start date year, and the two-digit year leap day "29/02/24" as 2024.
+        let mut sd = StatementData::new();
+        sd.set_start_date(ymd(2023, 12, 1));
+        sd.set_end_date(ymd(2024, 2, 29));
+        for date in [ymd(2023, 12, 15), ymd(2023, 1, 5), ymd(2024, 2, 29)] {
+            let mut tx = ProtoTransaction::new();
+            tx.set_date(date);
+            sd.add_proto_transaction(tx);
+        }
+
+        fix_year_crossovers(&mut sd);
+
+        let dates: Vec<Option<i64>> = sd.proto_transactions.iter().map(|tx| tx.date).collect();
+        assert_eq!(
+            dates,
+            vec![
+                Some(ymd(2023, 12, 15)),
+                Some(ymd(2024, 1, 5)),
+                Some(ymd(2024, 2, 29))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fix_year_crossovers_same_day_rows_resolved_together() {
+        let ymd = |year: i32, month: u32, day: u32| {
+            Utc.with_ymd_and_hms(year, month, day, 0, 0, 0)
+                .unwrap()
+                .timestamp_millis()
+        };
+        // Statement period: January 1 to 31, 2025. The first row of 31 December has the
+        // full date "31 Dec 2024", and its continuation rows the short date "31 Dec",
+        // read with the start date year as 31 Dec 2025.
+        let mut sd = StatementData::new();
+        sd.set_start_date(ymd(2025, 1, 1));
+        sd.set_end_date(ymd(2025, 1, 31));
+        for date in [
+            ymd(2024, 12, 31),
+            ymd(2025, 12, 31),
+            ymd(2025, 12, 31),
+            ymd(2025, 1, 2),
+            ymd(2025, 1, 2),
+        ] {
+            let mut tx = ProtoTransaction::new();
+            tx.set_date(date);
+            sd.add_proto_transaction(tx);
+        }
+
+        fix_year_crossovers(&mut sd);
+
+        let dates: Vec<Option<i64>> = sd.proto_transactions.iter().map(|tx| tx.date).collect();
+        assert_eq!(
+            dates,
+            vec![
+                Some(ymd(2024, 12, 31)),
+                Some(ymd(2024, 12, 31)),
+                Some(ymd(2024, 12, 31)),
+                Some(ymd(2025, 1, 2)),
+                Some(ymd(2025, 1, 2)),
+            ]
+        );
     }
 }
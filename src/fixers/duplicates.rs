@@ -0,0 +1,96 @@
+use crate::structs::StatementData;
+
+/// Drop consecutive duplicate transactions, e.g., the last transaction of a page
+/// repeated at the top of the next page, recording a warning for each so that the
+/// balance check sees each transaction once.
+///
+/// A transaction is a duplicate if it repeats the previous kept transaction (see
+/// ProtoTransaction::is_duplicate_of). Genuinely repeated transactions (e.g., two
+/// identical purchases on the same day) have different balances, so are kept. Must be
+/// applied after fix_implicit_dates, so that a repeated row with an implicit date is
+/// compared by its date, and before fix_set_indices.
+pub fn fix_duplicates(sd: &mut StatementData) {
+    let mut dropped = vec![false; sd.proto_transactions.len()];
+    let mut messages: Vec<String> = Vec::new();
+    let mut prev = None;
+    for (i, tx) in sd.proto_transactions.iter().enumerate() {
+        if prev.is_some_and(|prev| tx.is_duplicate_of(prev)) {
+            messages.push(format!(
+                "Transaction {} ('{}') duplicates the previous transaction: dropped",
+                i + 1,
+                tx.description
+            ));
+            dropped[i] = true;
+            continue;
+        }
+        prev = Some(tx);
+    }
+
+    let mut dropped = dropped.into_iter();
+    sd.proto_transactions.retain(|_| !dropped.next().unwrap());
+    for message in messages {
+        sd.add_warning(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::ProtoTransaction;
+
+    fn make_tx(date: i64, description: &str, amount: f64, balance: f64) -> ProtoTransaction {
+        ProtoTransaction {
+            date: Some(date),
+            description: description.to_string(),
+            amount: Some(amount),
+            balance: Some(balance),
+            ..Default::default()
+        }
+    }
+
+    fn make_statement(transactions: Vec<ProtoTransaction>) -> StatementData {
+        let mut sd = StatementData::new();
+        sd.proto_transactions = transactions;
+        sd
+    }
+
+    #[test]
+    fn test_repeated_rows_are_dropped() {
+        let mut sd = make_statement(vec![
+            make_tx(1, "RENT", -120.0, 880.0),
+            make_tx(1, "RENT", -120.0, 880.0),
+            make_tx(1, "RENT", -120.0, 880.0),
+            make_tx(1, "FEE", -5.0, 875.0),
+        ]);
+
+        fix_duplicates(&mut sd);
+
+        assert!(sd.errors.is_empty());
+        assert_eq!(
+            sd.warnings,
+            vec![
+                "Transaction 2 ('RENT') duplicates the previous transaction: dropped",
+                "Transaction 3 ('RENT') duplicates the previous transaction: dropped",
+            ]
+        );
+        let kept: Vec<&str> = sd
+            .proto_transactions
+            .iter()
+            .map(|tx| tx.description.as_str())
+            .collect();
+        assert_eq!(kept, vec!["RENT", "FEE"]);
+    }
+
+    #[test]
+    fn test_identical_purchases_with_different_balances_are_kept() {
+        let mut sd = make_statement(vec![
+            make_tx(1, "CAFE COFFEE", -4.5, 95.5),
+            make_tx(1, "CAFE COFFEE", -4.5, 91.0),
+        ]);
+
+        fix_duplicates(&mut sd);
+
+        assert!(sd.warnings.is_empty());
+        assert_eq!(sd.proto_transactions.len(), 2);
+    }
+}
@@ -9,21 +9,22 @@ use crate::structs::StatementData;
 /// the calculation for subsequent transactions.
 pub fn fix_implicit_balances(sd: &mut StatementData) {
     // Start with the opening balance, return early if not set
-    let mut balance = match sd.opening_balance {
+    let mut balance = match sd.opening_balance_money() {
         Some(opening_balance) => opening_balance,
         None => return, // Can't fix implicit balances without opening balance
     };
 
     for transaction in &mut sd.proto_transactions {
         // Skip transactions that don't have an amount
-        if let Some(amount) = transaction.amount {
-            // If the transaction doesn't have a balance, calculate it
-            if let Some(existing_balance) = transaction.balance {
+        if let Some(amount) = transaction.amount_money() {
+            // If the transaction doesn't have a balance, calculate it in cents
+            if let Some(existing_balance) = transaction.balance_money() {
                 // If the transaction already has a balance, use it for the next calculation
                 balance = existing_balance;
             } else {
                 let new_balance = balance + amount;
-                transaction.set_balance(new_balance);
+                transaction.set_balance(new_balance.to_f64());
+                transaction.balance_computed = true;
                 balance = new_balance;
             }
         }
@@ -145,6 +146,15 @@ mod tests {
 
         // Third transaction should use second transaction's balance
         assert_eq!(sd.proto_transactions[2].balance, Some(925.0)); // 900 + 25
+
+        // Only the missing balances are computed
+        let sources: Vec<&str> = sd
+            .proto_transactions
+            .iter()
+            .map(|tx| tx.balance_source())
+            .collect();
+        assert_eq!(sources, vec!["computed", "parsed", "computed"]);
+        assert_eq!(sd.computed_balance_count(), 2);
     }
 
     #[test]
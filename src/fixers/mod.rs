@@ -1,31 +1,230 @@
-use crate::structs::StatementData;
+use crate::structs::{StatementConfig, StatementData};
+use std::str::FromStr;
 
 pub mod amounts;
+pub mod balance_order;
 pub mod closing_balance;
 pub mod date;
+pub mod duplicates;
 pub mod implicit_balance;
 pub mod implicit_date;
 pub mod opening_balance;
 pub mod set_indices;
+pub mod trailing_summary;
 pub mod transaction_order;
 
 pub use amounts::fix_amounts;
+pub use balance_order::fix_balance_order;
 pub use closing_balance::fix_closing_balance;
 pub use date::fix_year_crossovers;
+pub use duplicates::fix_duplicates;
 pub use implicit_balance::fix_implicit_balances;
 pub use implicit_date::fix_implicit_dates;
 pub use opening_balance::fix_opening_balance;
 pub use set_indices::fix_set_indices;
+pub use trailing_summary::fix_trailing_summary;
 pub use transaction_order::fix_transaction_order;
 
-/// Apply all fixers to the StatementData in a logical order
-pub fn fix_statement_data(sd: &mut StatementData) {
+/// Apply all fixers to the StatementData parsed with the given config in a logical order.
+/// Duplicate transactions are dropped if the config sets drop_duplicate_transactions,
+/// before they break the balance ordering. Transactions on the same day are reordered by
+/// their balances before the opening balance and amounts are fixed, as those follow the
+/// transaction order. Reordering and setting indices are skipped if any transaction is
+/// still missing a date after the implicit dates are filled, and indices are also skipped
+/// if the dates are not in chronological order (see fix_set_indices), leaving these for
+/// the checks to report.
+pub fn fix_statement_data(sd: &mut StatementData, config: &StatementConfig) {
+    fix_trailing_summary(sd, &config.transaction_summary_exclude);
     fix_implicit_dates(sd);
     fix_year_crossovers(sd);
+    if config.drop_duplicate_transactions {
+        fix_duplicates(sd);
+    }
     fix_transaction_order(sd);
+    if sd.proto_transactions.iter().all(|tx| tx.date.is_some()) {
+        fix_balance_order(sd);
+    }
     fix_opening_balance(sd);
     fix_amounts(sd);
     fix_implicit_balances(sd);
     fix_set_indices(sd);
     fix_closing_balance(sd);
 }
+
+/// Apply only fixers that do not change parsed values, i.e., that only reorder
+/// transactions and set their indices within each day. Skipped if any transaction
+/// is missing a date, or if the dates are not in chronological order after reordering.
+pub fn fix_statement_data_safe(sd: &mut StatementData) {
+    fix_transaction_order(sd);
+    fix_set_indices(sd);
+}
+
+/// Fixers applied to parsed statement data before the checks
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FixersMode {
+    /// Apply all fixers (see fix_statement_data)
+    #[default]
+    All,
+    /// Apply only fixers that do not change parsed values (see fix_statement_data_safe)
+    Safe,
+    /// Apply no fixers
+    None,
+}
+
+impl FromStr for FixersMode {
+    type Err = String;
+
+    /// Read a fixers mode from its name: "all", "safe" or "none"
+    fn from_str(mode: &str) -> Result<Self, Self::Err> {
+        match mode {
+            "all" => Ok(FixersMode::All),
+            "safe" => Ok(FixersMode::Safe),
+            "none" => Ok(FixersMode::None),
+            _ => Err(format!(
+                "Invalid fixers mode: {}. Must be \"all\", \"safe\" or \"none\"",
+                mode
+            )),
+        }
+    }
+}
+
+/// Apply fixers to the StatementData according to the fixers mode
+pub fn fix_statement_data_with_mode(
+    sd: &mut StatementData,
+    config: &StatementConfig,
+    mode: FixersMode,
+) {
+    match mode {
+        FixersMode::All => fix_statement_data(sd, config),
+        FixersMode::Safe => fix_statement_data_safe(sd),
+        FixersMode::None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::ProtoTransaction;
+
+    fn make_statement_data() -> StatementData {
+        let mut sd = StatementData::new();
+        sd.set_opening_balance(1000.0);
+        for (date, amount, balance) in [(2, 250.0, -750.0), (1, -120.0, -870.0)] {
+            let mut tx = ProtoTransaction::new();
+            tx.date = Some(date);
+            tx.amount = Some(amount);
+            tx.balance = Some(balance);
+            tx.description = "Transaction".to_string();
+            sd.add_proto_transaction(tx);
+        }
+        sd
+    }
+
+    #[test]
+    fn test_fix_statement_data_with_mode_all_flips_opening_balance() {
+        let mut sd = make_statement_data();
+        sd.proto_transactions[0].date = Some(1);
+
+        fix_statement_data_with_mode(&mut sd, &StatementConfig::default(), FixersMode::All);
+        assert_eq!(sd.opening_balance, Some(-1000.0));
+    }
+
+    #[test]
+    fn test_fix_statement_data_reorders_same_day_before_fixing_amounts() {
+        // Unsigned debit listed before the credit it follows on the same day
+        let mut sd = StatementData::new();
+        sd.set_opening_balance(100.0);
+        sd.set_closing_balance(120.0);
+        for (amount, balance, description) in [(30.0, 120.0, "Debit"), (50.0, 150.0, "Credit")] {
+            let mut tx = ProtoTransaction::new();
+            tx.date = Some(0);
+            tx.amount = Some(amount);
+            tx.balance = Some(balance);
+            tx.description = description.to_string();
+            sd.add_proto_transaction(tx);
+        }
+
+        fix_statement_data(&mut sd, &StatementConfig::default());
+        crate::checkers::check_balances(&mut sd);
+
+        assert_eq!(sd.proto_transactions[0].description, "Credit");
+        assert_eq!(sd.proto_transactions[1].amount, Some(-30.0));
+        assert!(sd.errors.is_empty(), "{:?}", sd.errors);
+    }
+
+    #[test]
+    fn test_unorderable_same_day_transactions_reported_once() {
+        // The second balance follows from neither the opening balance nor the first
+        let mut sd = StatementData::new();
+        sd.set_opening_balance(100.0);
+        sd.set_closing_balance(90.0);
+        for (amount, balance) in [(10.0, 110.0), (-20.0, 999.0)] {
+            let mut tx = ProtoTransaction::new();
+            tx.date = Some(0);
+            tx.amount = Some(amount);
+            tx.balance = Some(balance);
+            tx.description = "Transaction".to_string();
+            sd.add_proto_transaction(tx);
+        }
+
+        fix_statement_data(&mut sd, &StatementConfig::default());
+        crate::checkers::check_balances(&mut sd);
+
+        assert_eq!(sd.errors.len(), 1, "{:?}", sd.errors);
+        assert_eq!(sd.warnings.len(), 1, "{:?}", sd.warnings);
+        assert!(sd.warnings[0].contains("cannot be ordered to match their balances"));
+    }
+
+    #[test]
+    fn test_fix_statement_data_with_mode_none_leaves_data_unchanged() {
+        let mut sd = make_statement_data();
+
+        fix_statement_data_with_mode(&mut sd, &StatementConfig::default(), FixersMode::None);
+        assert_eq!(sd.opening_balance, Some(1000.0));
+        assert_eq!(sd.proto_transactions[0].date, Some(2));
+    }
+
+    #[test]
+    fn test_duplicates_only_dropped_with_all_fixers() {
+        let config = StatementConfig {
+            drop_duplicate_transactions: true,
+            ..Default::default()
+        };
+        for (mode, expected) in [
+            (FixersMode::All, 2),
+            (FixersMode::Safe, 3),
+            (FixersMode::None, 3),
+        ] {
+            let mut sd = make_statement_data();
+            let repeated = sd.proto_transactions[0].clone();
+            sd.proto_transactions.insert(1, repeated);
+
+            fix_statement_data_with_mode(&mut sd, &config, mode);
+            crate::checkers::check_duplicates(&mut sd);
+
+            assert_eq!(sd.proto_transactions.len(), expected, "{:?}", mode);
+            assert_eq!(sd.errors.len(), expected - 2, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn test_fix_statement_data_with_mode_safe_skips_out_of_order_dates() {
+        let mut sd = make_statement_data();
+
+        // Dates out of order with balances set: not reordered, indices not set
+        fix_statement_data_with_mode(&mut sd, &StatementConfig::default(), FixersMode::Safe);
+        assert_eq!(sd.opening_balance, Some(1000.0));
+        assert_eq!(sd.proto_transactions[0].date, Some(2));
+    }
+
+    #[test]
+    fn test_fixers_mode_from_str() {
+        assert_eq!("safe".parse::<FixersMode>(), Ok(FixersMode::Safe));
+        assert!(
+            "some"
+                .parse::<FixersMode>()
+                .unwrap_err()
+                .contains("Invalid fixers mode: some")
+        );
+    }
+}
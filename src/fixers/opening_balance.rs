@@ -16,37 +16,41 @@ pub fn fix_opening_balance(sd: &mut StatementData) {
 
     // Return early if first transaction is missing amount or balance
     let first_transaction = &sd.proto_transactions[0];
-    let first_amount = match first_transaction.amount {
+    let first_amount = match first_transaction.amount_money() {
         Some(amount) => amount,
         None => return,
     };
-    let first_balance = match first_transaction.balance {
+    let first_balance = match first_transaction.balance_money() {
         Some(balance) => balance,
         None => return,
     };
 
     // Return early if no opening balance
-    let opening_balance = match sd.opening_balance {
+    let opening_balance = match sd.opening_balance_money() {
         Some(balance) => balance,
         None => return,
     };
 
-    const TOLERANCE: f64 = 0.01;
+    // Balances are compared in cents, within the balance tolerance
+    let tolerance = sd.balance_tolerance;
 
     // Opening and first balance agree, no issue
-    if (first_balance - (opening_balance + first_amount)).abs() < TOLERANCE {
+    if first_balance.within(opening_balance + first_amount, tolerance) {
         return;
     }
 
     // Try reversing sign of opening balance
-    if (first_balance - (-opening_balance + first_amount)).abs() < TOLERANCE {
+    if first_balance.within(-opening_balance + first_amount, tolerance) {
+        let opening_balance = sd.opening_balance.unwrap_or_default();
         sd.set_opening_balance(-opening_balance);
         return;
     }
 
     // First amount is a debit, reverse sign of first amount
-    if (first_balance - (opening_balance - first_amount)).abs() < TOLERANCE {
+    if first_balance.within(opening_balance - first_amount, tolerance) {
+        let first_amount = sd.proto_transactions[0].amount.unwrap_or_default();
         sd.proto_transactions[0].set_amount(-first_amount);
+        sd.proto_transactions[0].amount_inferred = true;
     }
 }
 
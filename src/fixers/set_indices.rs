@@ -4,41 +4,25 @@ use crate::structs::StatementData;
 /// This fixer should be applied after implicit_balance to lock the order
 /// so that transactions can be reordered safely without breaking the running balance.
 ///
-/// # Panics
-/// Panics if dates are found out of order - the transtractor isn't set up to deal with this.
+/// Indices are left unchanged if any transaction is missing a date or the dates are
+/// not in chronological order, as the transtractor isn't set up to deal with this.
+/// These are left for the checks to report.
 pub fn fix_set_indices(sd: &mut StatementData) {
-    if sd.proto_transactions.is_empty() {
+    let dates: Option<Vec<i64>> = sd.proto_transactions.iter().map(|tx| tx.date).collect();
+    let Some(dates) = dates else {
+        return;
+    };
+    if dates.windows(2).any(|pair| pair[1] < pair[0]) {
         return;
     }
 
-    let mut prev_date: Option<i64> = None;
     let mut current_day: Option<i64> = None;
     let mut day_index = 0;
 
-    for (i, proto_transaction) in sd.proto_transactions.iter_mut().enumerate() {
-        // Validate that transaction has a date (should be guaranteed by earlier fixers)
-        let current_date = match proto_transaction.date {
-            Some(date) => date,
-            None => panic!(
-                "Transaction at position {} does not have a date. This should not happen after date fixers.",
-                i
-            ),
-        };
-
-        // Check that dates are in chronological order (panic if not)
-        if let Some(prev) = prev_date
-            && current_date < prev
-        {
-            panic!(
-                "Transaction dates are out of order at position {}: current date {} < previous date {}. The transtractor isn't set up to deal with this.",
-                i, current_date, prev
-            );
-        }
-        prev_date = Some(current_date);
-
+    for (proto_transaction, date) in sd.proto_transactions.iter_mut().zip(dates) {
         // Check if we've moved to a new day and reset indices
-        if current_day != Some(current_date) {
-            current_day = Some(current_date);
+        if current_day != Some(date) {
+            current_day = Some(date);
             day_index = 0; // Reset index for new day
         }
 
@@ -51,7 +35,8 @@ pub fn fix_set_indices(sd: &mut StatementData) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::structs::{ProtoTransaction, StatementData};
+    use crate::structs::{Money, ParseTimings, ProtoTransaction, StatementData, TableLines};
+    use std::collections::BTreeMap;
 
     fn create_proto_transaction(date: i64, index: usize) -> ProtoTransaction {
         ProtoTransaction {
@@ -60,6 +45,14 @@ mod tests {
             description: format!("Transaction {}", index),
             amount: Some(100.0),
             balance: None,
+            page: None,
+            y1: None,
+            description_optional: false,
+            amount_inferred: false,
+            balance_computed: false,
+            corrected_fields: vec![],
+            category: None,
+            source_items: vec![],
         }
     }
 
@@ -70,10 +63,35 @@ mod tests {
             account_number: None,
             opening_balance: None,
             closing_balance: None,
+            minimum_payment: None,
+            payment_due_date: None,
             start_date: None,
             start_date_year: None,
+            end_date: None,
             key: None,
+            config_status: None,
             errors: Vec::new(),
+            warnings: Vec::new(),
+            item_claims: Vec::new(),
+            field_sources: BTreeMap::new(),
+            page_coverage: Vec::new(),
+            excluded_descriptions: Vec::new(),
+            dropped_rows: Vec::new(),
+            closing_balance_candidates: Vec::new(),
+            column_bounds: BTreeMap::new(),
+            transaction_stop_page: None,
+            table_lines: TableLines::default(),
+            orphan_lines: Vec::new(),
+            date_timezone: None,
+            currency: None,
+            balance_tolerance: Money::ZERO,
+            identification_score: None,
+            fix_text_order_applied: false,
+            no_transactions_declared: false,
+            coordinate_scale: 1.0,
+            account_section: None,
+            statement_section: None,
+            timings: ParseTimings::new(),
         };
 
         fix_set_indices(&mut sd);
@@ -87,10 +105,35 @@ mod tests {
             account_number: None,
             opening_balance: None,
             closing_balance: None,
+            minimum_payment: None,
+            payment_due_date: None,
             start_date: None,
             start_date_year: None,
+            end_date: None,
             key: None,
+            config_status: None,
             errors: Vec::new(),
+            warnings: Vec::new(),
+            item_claims: Vec::new(),
+            field_sources: BTreeMap::new(),
+            page_coverage: Vec::new(),
+            excluded_descriptions: Vec::new(),
+            dropped_rows: Vec::new(),
+            closing_balance_candidates: Vec::new(),
+            column_bounds: BTreeMap::new(),
+            transaction_stop_page: None,
+            table_lines: TableLines::default(),
+            orphan_lines: Vec::new(),
+            date_timezone: None,
+            currency: None,
+            balance_tolerance: Money::ZERO,
+            identification_score: None,
+            fix_text_order_applied: false,
+            no_transactions_declared: false,
+            coordinate_scale: 1.0,
+            account_section: None,
+            statement_section: None,
+            timings: ParseTimings::new(),
         };
 
         fix_set_indices(&mut sd);
@@ -108,10 +151,35 @@ mod tests {
             account_number: None,
             opening_balance: None,
             closing_balance: None,
+            minimum_payment: None,
+            payment_due_date: None,
             start_date: None,
             start_date_year: None,
+            end_date: None,
             key: None,
+            config_status: None,
             errors: Vec::new(),
+            warnings: Vec::new(),
+            item_claims: Vec::new(),
+            field_sources: BTreeMap::new(),
+            page_coverage: Vec::new(),
+            excluded_descriptions: Vec::new(),
+            dropped_rows: Vec::new(),
+            closing_balance_candidates: Vec::new(),
+            column_bounds: BTreeMap::new(),
+            transaction_stop_page: None,
+            table_lines: TableLines::default(),
+            orphan_lines: Vec::new(),
+            date_timezone: None,
+            currency: None,
+            balance_tolerance: Money::ZERO,
+            identification_score: None,
+            fix_text_order_applied: false,
+            no_transactions_declared: false,
+            coordinate_scale: 1.0,
+            account_section: None,
+            statement_section: None,
+            timings: ParseTimings::new(),
         };
 
         fix_set_indices(&mut sd);
@@ -135,10 +203,35 @@ mod tests {
             account_number: None,
             opening_balance: None,
             closing_balance: None,
+            minimum_payment: None,
+            payment_due_date: None,
             start_date: None,
             start_date_year: None,
+            end_date: None,
             key: None,
+            config_status: None,
             errors: Vec::new(),
+            warnings: Vec::new(),
+            item_claims: Vec::new(),
+            field_sources: BTreeMap::new(),
+            page_coverage: Vec::new(),
+            excluded_descriptions: Vec::new(),
+            dropped_rows: Vec::new(),
+            closing_balance_candidates: Vec::new(),
+            column_bounds: BTreeMap::new(),
+            transaction_stop_page: None,
+            table_lines: TableLines::default(),
+            orphan_lines: Vec::new(),
+            date_timezone: None,
+            currency: None,
+            balance_tolerance: Money::ZERO,
+            identification_score: None,
+            fix_text_order_applied: false,
+            no_transactions_declared: false,
+            coordinate_scale: 1.0,
+            account_section: None,
+            statement_section: None,
+            timings: ParseTimings::new(),
         };
 
         fix_set_indices(&mut sd);
@@ -156,48 +249,107 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Transaction at position 1 does not have a date")]
-    fn test_fix_set_indices_panics_on_missing_date() {
+    fn test_fix_set_indices_skips_missing_date() {
         let mut sd = StatementData {
             proto_transactions: vec![
-                create_proto_transaction(1000, 0),
+                create_proto_transaction(1000, 5),
                 ProtoTransaction {
-                    date: None, // Missing date should cause panic
+                    date: None, // Missing date leaves the indices unchanged
                     index: 1,
                     description: "No date transaction".to_string(),
                     amount: Some(100.0),
                     balance: None,
+                    page: None,
+                    y1: None,
+                    description_optional: false,
+                    amount_inferred: false,
+                    balance_computed: false,
+                    corrected_fields: vec![],
+                    category: None,
+                    source_items: vec![],
                 },
             ],
             account_number: None,
             opening_balance: None,
             closing_balance: None,
+            minimum_payment: None,
+            payment_due_date: None,
             start_date: None,
             start_date_year: None,
+            end_date: None,
             key: None,
+            config_status: None,
             errors: Vec::new(),
+            warnings: Vec::new(),
+            item_claims: Vec::new(),
+            field_sources: BTreeMap::new(),
+            page_coverage: Vec::new(),
+            excluded_descriptions: Vec::new(),
+            dropped_rows: Vec::new(),
+            closing_balance_candidates: Vec::new(),
+            column_bounds: BTreeMap::new(),
+            transaction_stop_page: None,
+            table_lines: TableLines::default(),
+            orphan_lines: Vec::new(),
+            date_timezone: None,
+            currency: None,
+            balance_tolerance: Money::ZERO,
+            identification_score: None,
+            fix_text_order_applied: false,
+            no_transactions_declared: false,
+            coordinate_scale: 1.0,
+            account_section: None,
+            statement_section: None,
+            timings: ParseTimings::new(),
         };
 
         fix_set_indices(&mut sd);
+        assert_eq!(sd.proto_transactions[0].index, 5);
     }
 
     #[test]
-    #[should_panic(expected = "Transaction dates are out of order at position 1")]
-    fn test_fix_set_indices_panics_on_out_of_order_dates() {
+    fn test_fix_set_indices_skips_out_of_order_dates() {
         let mut sd = StatementData {
             proto_transactions: vec![
                 create_proto_transaction(2000, 0), // Later date first
-                create_proto_transaction(1000, 1), // Earlier date second - should panic
+                create_proto_transaction(1000, 1), // Earlier date second
             ],
             account_number: None,
             opening_balance: None,
             closing_balance: None,
+            minimum_payment: None,
+            payment_due_date: None,
             start_date: None,
             start_date_year: None,
+            end_date: None,
             key: None,
+            config_status: None,
             errors: Vec::new(),
+            warnings: Vec::new(),
+            item_claims: Vec::new(),
+            field_sources: BTreeMap::new(),
+            page_coverage: Vec::new(),
+            excluded_descriptions: Vec::new(),
+            dropped_rows: Vec::new(),
+            closing_balance_candidates: Vec::new(),
+            column_bounds: BTreeMap::new(),
+            transaction_stop_page: None,
+            table_lines: TableLines::default(),
+            orphan_lines: Vec::new(),
+            date_timezone: None,
+            currency: None,
+            balance_tolerance: Money::ZERO,
+            identification_score: None,
+            fix_text_order_applied: false,
+            no_transactions_declared: false,
+            coordinate_scale: 1.0,
+            account_section: None,
+            statement_section: None,
+            timings: ParseTimings::new(),
         };
 
         fix_set_indices(&mut sd);
+        let indices: Vec<usize> = sd.proto_transactions.iter().map(|tx| tx.index).collect();
+        assert_eq!(indices, vec![0, 1]);
     }
 }
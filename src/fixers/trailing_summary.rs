@@ -0,0 +1,147 @@
+use crate::structs::{Money, ProtoTransaction, StatementData};
+use regex::Regex;
+
+/// Maximum number of summary rows after the transaction table (e.g., total debits,
+/// total credits and net total)
+const MAX_SUMMARY_ROWS: usize = 3;
+
+/// Returns true if the amount equals (ignoring sign) the total debits, total credits
+/// or net total of the transactions, each summing at least two amounts. Totals are
+/// summed and compared exactly in cents.
+fn is_total(amount: Money, transactions: &[ProtoTransaction]) -> bool {
+    let amounts: Vec<Money> = transactions
+        .iter()
+        .filter_map(|tx| tx.amount_money())
+        .collect();
+    let debits: Vec<Money> = amounts
+        .iter()
+        .copied()
+        .filter(|a| *a < Money::ZERO)
+        .collect();
+    let credits: Vec<Money> = amounts
+        .iter()
+        .copied()
+        .filter(|a| *a > Money::ZERO)
+        .collect();
+    [amounts, debits, credits]
+        .iter()
+        .filter(|sums| sums.len() >= 2)
+        .any(|sums| sums.iter().copied().sum::<Money>().abs() == amount.abs())
+}
+
+/// Returns true if the transaction is a summary row of the preceding transactions: its
+/// description matches one of the summary patterns, or it has no date and its amount
+/// is a total of the preceding transactions (see is_total).
+fn is_summary(tx: &ProtoTransaction, preceding: &[ProtoTransaction], patterns: &[Regex]) -> bool {
+    if patterns
+        .iter()
+        .any(|pattern| pattern.is_match(&tx.description))
+    {
+        return true;
+    }
+    match (tx.date, tx.amount_money()) {
+        (None, Some(amount)) => is_total(amount, preceding),
+        _ => false,
+    }
+}
+
+/// Remove summary rows (e.g., "Total debits" and "Total credits") read as transactions
+/// from below the transaction table.
+///
+/// Up to MAX_SUMMARY_ROWS transactions at the end of the list are removed if each is a
+/// summary of the transactions before them: matching one of the transaction_summary_exclude
+/// patterns, or with no date and an amount equal to the total debits, total credits or
+/// net total of those transactions. Dated transactions are only removed by pattern, so a
+/// final payment equal to the preceding purchases is kept. Must be applied in statement
+/// order, before fix_implicit_dates and fix_implicit_balances.
+pub fn fix_trailing_summary(sd: &mut StatementData, patterns: &[Regex]) {
+    let len = sd.proto_transactions.len();
+    for start in len.saturating_sub(MAX_SUMMARY_ROWS)..len {
+        let (preceding, trailing) = sd.proto_transactions.split_at(start);
+        if trailing
+            .iter()
+            .all(|tx| is_summary(tx, preceding, patterns))
+        {
+            sd.proto_transactions.truncate(start);
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_tx(date: Option<i64>, description: &str, amount: f64) -> ProtoTransaction {
+        ProtoTransaction {
+            date,
+            description: description.to_string(),
+            amount: Some(amount),
+            ..Default::default()
+        }
+    }
+
+    fn make_statement(trailing: Vec<ProtoTransaction>) -> StatementData {
+        let mut sd = StatementData::new();
+        sd.proto_transactions = vec![
+            make_tx(Some(1), "SALARY", 250.0),
+            make_tx(Some(2), "RENT", -120.0),
+            make_tx(None, "GROCERIES", -24.5),
+            make_tx(Some(3), "REFUND", 10.0),
+        ];
+        sd.proto_transactions.extend(trailing);
+        sd
+    }
+
+    fn descriptions(sd: &StatementData) -> Vec<&str> {
+        sd.proto_transactions
+            .iter()
+            .map(|tx| tx.description.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_removes_debit_and_credit_totals() {
+        let mut sd = make_statement(vec![
+            make_tx(None, "Total debits", -144.5),
+            make_tx(None, "Total credits", 260.0),
+        ]);
+
+        fix_trailing_summary(&mut sd, &[]);
+
+        assert_eq!(
+            descriptions(&sd),
+            vec!["SALARY", "RENT", "GROCERIES", "REFUND"]
+        );
+    }
+
+    #[test]
+    fn test_removes_unsigned_debit_total() {
+        let mut sd = make_statement(vec![make_tx(None, "Total debits", 144.5)]);
+
+        fix_trailing_summary(&mut sd, &[]);
+
+        assert_eq!(sd.proto_transactions.len(), 4);
+    }
+
+    #[test]
+    fn test_removes_row_matching_pattern() {
+        let mut sd = make_statement(vec![make_tx(None, "Closing totals", 999.0)]);
+
+        fix_trailing_summary(&mut sd, &[Regex::new(r"^Closing totals").unwrap()]);
+
+        assert_eq!(sd.proto_transactions.len(), 4);
+    }
+
+    #[test]
+    fn test_keeps_dated_and_non_total_transactions() {
+        let mut sd = make_statement(vec![
+            make_tx(Some(4), "PAYMENT", 144.5),
+            make_tx(None, "COFFEE", -4.5),
+        ]);
+
+        fix_trailing_summary(&mut sd, &[]);
+
+        assert_eq!(sd.proto_transactions.len(), 6);
+    }
+}
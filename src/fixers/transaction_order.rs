@@ -14,10 +14,8 @@ use crate::structs::StatementData;
 /// 1. Primary: by date (oldest first)
 /// 2. Secondary: by index (lowest first) for transactions with the same date
 ///
-/// # Panics
-///
-/// Panics if any transaction does not have a date set. All transactions should
-/// have dates before this fixer is called.
+/// Transactions are left in their order if any transaction does not have a date
+/// set, for the checks to report.
 pub fn fix_transaction_order(sd: &mut StatementData) {
     // Check if any transaction has a balance set
     // If so, we should not reorder as it might break balance consistency
@@ -27,14 +25,9 @@ pub fn fix_transaction_order(sd: &mut StatementData) {
         return; // Don't reorder if any transaction has a balance
     }
 
-    // Verify all transactions have dates - panic if not
-    for (i, tx) in sd.proto_transactions.iter().enumerate() {
-        if tx.date.is_none() {
-            panic!(
-                "Transaction at index {} does not have a date set. All transactions must have dates before reordering.",
-                i
-            );
-        }
+    // Dates cannot be ordered if any are missing
+    if sd.proto_transactions.iter().any(|tx| tx.date.is_none()) {
+        return;
     }
 
     // Sort by date first, then by index
@@ -97,8 +90,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Transaction at index 1 does not have a date set")]
-    fn test_fix_transaction_order_panics_on_none_dates() {
+    fn test_fix_transaction_order_skips_none_dates() {
         let mut sd = StatementData::new();
 
         let mut tx1 = ProtoTransaction::new();
@@ -107,13 +99,17 @@ mod tests {
         tx1.description = "Transaction with date".to_string();
 
         let mut tx2 = ProtoTransaction::new();
-        tx2.date = None; // This should cause a panic
-        tx2.index = 2;
+        tx2.date = None; // This leaves the order unchanged
+        tx2.index = 0;
         tx2.description = "Transaction without date".to_string();
 
         sd.proto_transactions = vec![tx1, tx2];
 
-        fix_transaction_order(&mut sd); // Should panic here
+        fix_transaction_order(&mut sd);
+        assert_eq!(
+            sd.proto_transactions[0].description,
+            "Transaction with date"
+        );
     }
 
     #[test]
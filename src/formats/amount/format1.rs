@@ -1,5 +1,9 @@
 use crate::formats::amount::AmountFormat;
 use regex::Regex;
+use std::sync::LazyLock;
+
+static PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^-?\d{1,3}(,\d{3})*\.\d{2}(-|\s)?$").unwrap());
 
 /// Format1: parses amounts like "1,234.56", "-1,234.56", "1,234.56-"
 pub struct Format1;
@@ -10,8 +14,7 @@ impl AmountFormat for Format1 {
     }
 
     fn parse(&self, amount_str: &str) -> Option<f64> {
-        let re = Regex::new(r"^-?\d{1,3}(,\d{3})*\.\d{2}(-|\s)?$").unwrap();
-        if !re.is_match(amount_str) {
+        if !PATTERN.is_match(amount_str) {
             return None;
         }
         // Remove commas
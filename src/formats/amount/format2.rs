@@ -1,8 +1,33 @@
 use crate::formats::amount::AmountFormat;
 use regex::Regex;
 
-/// Format2: parses amounts like "-$1,234.56", "$1,234.56", "$1,234.56-"
-pub struct Format2;
+/// Format2: parses amounts like "-$1,234.56", "$1,234.56", "$1,234.56-", with "$" or
+/// another currency symbol
+pub struct Format2 {
+    symbol: String,
+    /// Pattern of the amounts, compiled once for the symbol
+    pattern: Regex,
+}
+
+impl Format2 {
+    pub fn new(symbol: &str) -> Self {
+        let pattern = Regex::new(&format!(
+            r"^-?{}\d{{1,3}}(,\d{{3}})*\.\d{{2}}(-|\s)?$",
+            regex::escape(symbol)
+        ))
+        .unwrap();
+        Format2 {
+            symbol: symbol.to_string(),
+            pattern,
+        }
+    }
+}
+
+impl Default for Format2 {
+    fn default() -> Self {
+        Format2::new("$")
+    }
+}
 
 impl AmountFormat for Format2 {
     fn num_items(&self) -> usize {
@@ -10,8 +35,7 @@ impl AmountFormat for Format2 {
     }
 
     fn parse(&self, currency_str: &str) -> Option<f64> {
-        let re = Regex::new(r"^-?\$\d{1,3}(,\d{3})*\.\d{2}(-|\s)?$").unwrap();
-        if !re.is_match(currency_str) {
+        if !self.pattern.is_match(currency_str) {
             return None;
         }
         // Remove commas
@@ -22,8 +46,8 @@ impl AmountFormat for Format2 {
             sign = -1.0;
             cleaned = cleaned.replace('-', "");
         }
-        // Remove dollar sign
-        cleaned = cleaned.replace('$', "");
+        // Remove currency symbol
+        cleaned = cleaned.replace(self.symbol.as_str(), "");
         // Parse float
         match cleaned.parse::<f64>() {
             Ok(val) => Some(sign * val),
@@ -38,7 +62,7 @@ mod tests {
 
     #[test]
     fn test_format2() {
-        let fmt = Format2;
+        let fmt = Format2::default();
         assert_eq!(fmt.parse("$1,234.56"), Some(1234.56));
         assert_eq!(fmt.parse("-$1,234.56"), Some(-1234.56));
         assert_eq!(fmt.parse("$1,234.56-"), Some(-1234.56));
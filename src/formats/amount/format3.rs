@@ -1,8 +1,34 @@
 use crate::formats::amount::AmountFormat;
 use regex::Regex;
+use std::sync::LazyLock;
 
-/// Format3: parses amounts like "-$1,234.56 DR", "$1,234.56 DR", "$1,234.56 CR"
-pub struct Format3;
+static SIGN_INDICATOR: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(cr|dr)$").unwrap());
+
+/// Format3: parses amounts like "-$1,234.56 DR", "$1,234.56 DR", "$1,234.56 CR", with
+/// "$" or another currency symbol
+pub struct Format3 {
+    symbol: String,
+    /// Pattern of the amounts, compiled once for the symbol
+    pattern: Regex,
+}
+
+impl Format3 {
+    pub fn new(symbol: &str) -> Self {
+        let symbol = symbol.to_lowercase();
+        let pattern = Regex::new(&format!(
+            r"^-?{}\d{{1,3}}(,\d{{3}})*\.\d{{2}} (cr|dr)$",
+            regex::escape(&symbol)
+        ))
+        .unwrap();
+        Format3 { symbol, pattern }
+    }
+}
+
+impl Default for Format3 {
+    fn default() -> Self {
+        Format3::new("$")
+    }
+}
 
 impl AmountFormat for Format3 {
     fn num_items(&self) -> usize {
@@ -11,8 +37,7 @@ impl AmountFormat for Format3 {
 
     fn parse(&self, currency_str: &str) -> Option<f64> {
         let currency_str = currency_str.to_lowercase();
-        let re = Regex::new(r"^-?\$\d{1,3}(,\d{3})*\.\d{2} (cr|dr)$").unwrap();
-        if !re.is_match(&currency_str) {
+        if !self.pattern.is_match(&currency_str) {
             return None;
         }
         let mut sign = 1.0;
@@ -20,15 +45,14 @@ impl AmountFormat for Format3 {
             sign = -1.0;
         }
         // Remove "cr" or "dr"
-        let mut cleaned = Regex::new(r"(cr|dr)")
-            .unwrap()
+        let mut cleaned = SIGN_INDICATOR
             .replace(&currency_str, "")
-            .to_string();
+            .replace(self.symbol.as_str(), "");
         if cleaned.contains('-') {
             sign *= -1.0;
             cleaned = cleaned.replace('-', "");
         }
-        cleaned = cleaned.replace(['$', ','], "").trim().to_string();
+        cleaned = cleaned.replace(',', "").trim().to_string();
         match cleaned.parse::<f64>() {
             Ok(val) => Some(sign * val),
             Err(_) => None,
@@ -42,7 +66,7 @@ mod tests {
 
     #[test]
     fn test_format3() {
-        let fmt = Format3;
+        let fmt = Format3::default();
         assert_eq!(fmt.parse("$1,234.56 DR"), Some(-1234.56));
         assert_eq!(fmt.parse("-$1,234.56 DR"), Some(1234.56));
         assert_eq!(fmt.parse("$1,234.56 CR"), Some(1234.56));
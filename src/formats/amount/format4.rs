@@ -1,5 +1,10 @@
 use crate::formats::amount::AmountFormat;
 use regex::Regex;
+use std::sync::LazyLock;
+
+static PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^-?\d{1,3}(,\d{3})*\.\d{2} (cr|dr)$").unwrap());
+static SIGN_INDICATOR: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(cr|dr)").unwrap());
 
 /// Format4: parses amounts like "-1,234.56 DR", "1,234.56 DR", "1,234.56 CR"
 pub struct Format4;
@@ -11,8 +16,7 @@ impl AmountFormat for Format4 {
 
     fn parse(&self, currency_str: &str) -> Option<f64> {
         let currency_str = currency_str.to_lowercase();
-        let re = Regex::new(r"^-?\d{1,3}(,\d{3})*\.\d{2} (cr|dr)$").unwrap();
-        if !re.is_match(&currency_str) {
+        if !PATTERN.is_match(&currency_str) {
             return None;
         }
         let mut sign = 1.0;
@@ -20,10 +24,7 @@ impl AmountFormat for Format4 {
             sign = -1.0;
         }
         // Remove "cr" or "dr"
-        let mut cleaned = Regex::new(r"(cr|dr)")
-            .unwrap()
-            .replace(&currency_str, "")
-            .to_string();
+        let mut cleaned = SIGN_INDICATOR.replace(&currency_str, "").to_string();
         if cleaned.contains('-') {
             sign *= -1.0;
             cleaned = cleaned.replace('-', "");
@@ -15,6 +15,10 @@ impl AmountFormat for Format5 {
             None
         }
     }
+
+    fn decimal_separator(&self) -> Option<&'static str> {
+        None
+    }
 }
 
 #[cfg(test)]
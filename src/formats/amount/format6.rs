@@ -0,0 +1,101 @@
+use crate::formats::amount::AmountFormat;
+use regex::Regex;
+
+/// Format6: parses European amounts with a decimal comma, like "1.234,56", "-1.234,56",
+/// "1.234,56-", "1 234,56 €" and "1.234" (thousands only). Thousands are separated by "."
+/// or a (thin) space, which splits the amount into several text items. A "." followed by
+/// three digits is always a thousands separator, so "1.234" is 1234.0. Space-separated
+/// amounts require the decimal comma, so that adjacent numbers are not joined. The
+/// trailing currency symbol is "€" unless another is given.
+pub struct Format6 {
+    /// Pattern of the amounts, compiled once for the symbol
+    pattern: Regex,
+}
+
+impl Format6 {
+    pub fn new(symbol: &str) -> Self {
+        let pattern = Regex::new(&format!(
+            r"^(-)?(\d{{1,3}}(?:\.\d{{3}})+(?:,\d{{2}})?|\d{{1,3}}(?:[ \u{{2009}}\u{{202F}}\u{{00A0}}]\d{{3}})+,\d{{2}}|\d+,\d{{2}})(-)?(?: ?{})?$",
+            regex::escape(symbol)
+        ))
+        .unwrap();
+        Format6 { pattern }
+    }
+}
+
+impl Default for Format6 {
+    fn default() -> Self {
+        Format6::new("€")
+    }
+}
+
+impl AmountFormat for Format6 {
+    fn num_items(&self) -> usize {
+        // e.g., "1 234 567,89 €"
+        4
+    }
+
+    fn parse(&self, currency_str: &str) -> Option<f64> {
+        let caps = self.pattern.captures(currency_str.trim())?;
+        let leading_minus = caps.get(1).is_some();
+        let trailing_minus = caps.get(3).is_some();
+        if leading_minus && trailing_minus {
+            return None;
+        }
+        let sign = if leading_minus || trailing_minus {
+            -1.0
+        } else {
+            1.0
+        };
+        let cleaned: String = caps[2]
+            .chars()
+            .filter(|c| c.is_ascii_digit() || *c == ',')
+            .map(|c| if c == ',' { '.' } else { c })
+            .collect();
+        match cleaned.parse::<f64>() {
+            Ok(val) => Some(sign * val),
+            Err(_) => None,
+        }
+    }
+
+    fn decimal_separator(&self) -> Option<&'static str> {
+        Some(",")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format6() {
+        let fmt = Format6::default();
+        assert_eq!(fmt.parse("1.234,56"), Some(1234.56));
+        assert_eq!(fmt.parse("-1.234,56"), Some(-1234.56));
+        assert_eq!(fmt.parse("1.234,56-"), Some(-1234.56));
+        assert_eq!(fmt.parse("1.000.234,56"), Some(1000234.56));
+        assert_eq!(fmt.parse("234,56"), Some(234.56));
+        assert_eq!(fmt.parse("1234,56"), Some(1234.56));
+        assert_eq!(fmt.parse("1 234,56 €"), Some(1234.56));
+        assert_eq!(fmt.parse("1 234 567,89 €"), Some(1234567.89));
+        assert_eq!(fmt.parse("1\u{2009}234,56 €"), Some(1234.56));
+        assert_eq!(fmt.parse("1\u{202F}234,56€"), Some(1234.56));
+        assert_eq!(fmt.parse("1.234,56- €"), Some(-1234.56));
+        assert_eq!(fmt.parse("bad input"), None);
+        assert_eq!(fmt.parse("1,234.56"), None);
+        assert_eq!(fmt.parse("1.234,5"), None);
+        assert_eq!(fmt.parse("-1.234,56-"), None);
+        assert_eq!(fmt.parse("2024"), None);
+    }
+
+    #[test]
+    fn test_format6_thousands_only() {
+        let fmt = Format6::default();
+        // European interpretation: "." is a thousands separator
+        assert_eq!(fmt.parse("1.234"), Some(1234.0));
+        assert_eq!(fmt.parse("12.345.678"), Some(12345678.0));
+        assert_eq!(fmt.parse("1 234"), None);
+        assert_eq!(fmt.parse("1.23"), None);
+        assert_eq!(fmt.parse("1.2345"), None);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::formats::amount::AmountFormat;
+use regex::Regex;
+use std::sync::LazyLock;
+
+static PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(-)?(\d{1,3}(?:[ \u{2009}\u{202F}\u{00A0}]\d{3})+\.\d{2})(-)?$").unwrap()
+});
+
+/// Format7: parses amounts with a decimal point and thousands separated by a (thin)
+/// space, like "1 234.56", "-1 234.56" and "1 234 567.89-". The spaces split the amount
+/// into several text items. At least one thousands group is required, so amounts below
+/// 1,000 are left to format1.
+pub struct Format7;
+
+impl AmountFormat for Format7 {
+    fn num_items(&self) -> usize {
+        // e.g., "1 234 567.89"
+        3
+    }
+
+    fn parse(&self, currency_str: &str) -> Option<f64> {
+        let caps = PATTERN.captures(currency_str.trim())?;
+        let leading_minus = caps.get(1).is_some();
+        let trailing_minus = caps.get(3).is_some();
+        if leading_minus && trailing_minus {
+            return None;
+        }
+        let sign = if leading_minus || trailing_minus {
+            -1.0
+        } else {
+            1.0
+        };
+        let cleaned: String = caps[2]
+            .chars()
+            .filter(|c| c.is_ascii_digit() || *c == '.')
+            .collect();
+        match cleaned.parse::<f64>() {
+            Ok(val) => Some(sign * val),
+            Err(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format7() {
+        let fmt = Format7;
+        assert_eq!(fmt.parse("1 234.56"), Some(1234.56));
+        assert_eq!(fmt.parse("-1 234.56"), Some(-1234.56));
+        assert_eq!(fmt.parse("1 234.56-"), Some(-1234.56));
+        assert_eq!(fmt.parse("1 234 567.89"), Some(1234567.89));
+        assert_eq!(fmt.parse("12\u{2009}345.00"), Some(12345.0));
+        assert_eq!(fmt.parse("-1 234.56-"), None);
+        assert_eq!(fmt.parse("234.56"), None);
+        assert_eq!(fmt.parse("1 23.56"), None);
+        assert_eq!(fmt.parse("1 234,56"), None);
+        assert_eq!(fmt.parse("1,234.56"), None);
+    }
+}
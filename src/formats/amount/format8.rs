@@ -0,0 +1,105 @@
+use crate::formats::amount::AmountFormat;
+use regex::Regex;
+
+/// Format8: parses negative amounts in parentheses like "(1,234.56)", "($1,234.56)" and
+/// "$(1,234.56)", with "$" or another currency symbol, and unsigned amounts like
+/// "1,234.56". An opening parenthesis in its own text item is read as "( 1,234.56)". A
+/// "CR" or "DR" marker may follow, as in format3: "DR" marks the amount as negative, so
+/// "(1,234.56) DR" is read as a single negative rather than the two cancelling out.
+pub struct Format8 {
+    symbol: String,
+    /// Pattern of the amounts, compiled once for the symbol
+    pattern: Regex,
+}
+
+impl Format8 {
+    pub fn new(symbol: &str) -> Self {
+        let symbol = symbol.to_lowercase();
+        let symbol_pattern = regex::escape(&symbol);
+        let pattern = Regex::new(&format!(
+            r"^(?:(?:{s})?(\( ?)(?:{s})?|(?:{s})?)(\d{{1,3}}(?:,\d{{3}})*\.\d{{2}})( ?\))? ?(cr|dr)?$",
+            s = symbol_pattern
+        ))
+        .unwrap();
+        Format8 { symbol, pattern }
+    }
+}
+
+impl Default for Format8 {
+    fn default() -> Self {
+        Format8::new("$")
+    }
+}
+
+impl AmountFormat for Format8 {
+    fn num_items(&self) -> usize {
+        // e.g., "(" "1,234.56)" "CR"
+        3
+    }
+
+    fn parse(&self, currency_str: &str) -> Option<f64> {
+        let currency_str = currency_str.trim().to_lowercase();
+        let caps = self.pattern.captures(&currency_str)?;
+        let open = caps.get(1).is_some();
+        let close = caps.get(3).is_some();
+        // Parentheses must be balanced, and a symbol is only read once
+        if open != close || currency_str.matches(self.symbol.as_str()).count() > 1 {
+            return None;
+        }
+        let debit = caps.get(4).is_some_and(|m| m.as_str() == "dr");
+        let sign = if open || debit { -1.0 } else { 1.0 };
+        let val = caps[2].replace(',', "").parse::<f64>().ok()?;
+        // Avoid "-0.00" for "(0.00)"
+        if val == 0.0 {
+            return Some(0.0);
+        }
+        Some(sign * val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format8() {
+        let fmt = Format8::default();
+        assert_eq!(fmt.parse("(123.45)"), Some(-123.45));
+        assert_eq!(fmt.parse("($1,234.56)"), Some(-1234.56));
+        assert_eq!(fmt.parse("$(1,234.56)"), Some(-1234.56));
+        assert_eq!(fmt.parse("( 123.45)"), Some(-123.45)); // "(" in its own item
+        assert_eq!(fmt.parse("( $1,000,234.56 )"), Some(-1000234.56));
+        assert_eq!(fmt.parse("1,234.56"), Some(1234.56));
+        assert_eq!(fmt.parse("$1,234.56"), Some(1234.56));
+        assert_eq!(fmt.parse("(0.00)"), Some(0.0));
+        assert!(fmt.parse("(0.00)").unwrap().is_sign_positive());
+        assert_eq!(fmt.parse("bad input"), None);
+        assert_eq!(fmt.parse("(123.45"), None);
+        assert_eq!(fmt.parse("123.45)"), None);
+        assert_eq!(fmt.parse("-123.45"), None);
+        assert_eq!(fmt.parse("($$123.45)"), None);
+        assert_eq!(fmt.parse("$($123.45)"), None);
+        assert_eq!(fmt.parse("(1234.56)"), None);
+        assert_eq!(fmt.parse("(1,234.5)"), None);
+    }
+
+    #[test]
+    fn test_format8_sign_indicator() {
+        let fmt = Format8::default();
+        assert_eq!(fmt.parse("(123.45) CR"), Some(-123.45));
+        assert_eq!(fmt.parse("(123.45)CR"), Some(-123.45));
+        assert_eq!(fmt.parse("( 123.45) cr"), Some(-123.45));
+        assert_eq!(fmt.parse("(123.45) DR"), Some(-123.45));
+        assert_eq!(fmt.parse("( 123.45)DR"), Some(-123.45));
+        assert_eq!(fmt.parse("123.45 DR"), Some(-123.45));
+        assert_eq!(fmt.parse("(0.00) CR"), Some(0.0));
+        assert_eq!(fmt.parse("(123.45) XR"), None);
+    }
+
+    #[test]
+    fn test_format8_symbol() {
+        let fmt = Format8::new("£");
+        assert_eq!(fmt.parse("(£1,234.56)"), Some(-1234.56));
+        assert_eq!(fmt.parse("($1,234.56)"), None);
+    }
+}
@@ -3,12 +3,20 @@ pub mod format2;
 pub mod format3;
 pub mod format4;
 pub mod format5;
+pub mod format6;
+pub mod format7;
+pub mod format8;
 
 use format1::Format1;
 use format2::Format2;
 use format3::Format3;
 use format4::Format4;
 use format5::Format5;
+use format6::Format6;
+use format7::Format7;
+use format8::Format8;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
 
 /// Trait for amount formats.
 pub trait AmountFormat {
@@ -17,32 +25,96 @@ pub trait AmountFormat {
 
     /// Parse the input string and return a float if valid.
     fn parse(&self, input: &str) -> Option<f64>;
+
+    /// Decimal separator of the amounts read, if any.
+    fn decimal_separator(&self) -> Option<&'static str> {
+        Some(".")
+    }
+}
+
+/// Currency and separator hints of a statement's amounts (see the currency_symbol and
+/// decimal_separator config fields). Empty hints leave the formats unchanged.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AmountHints {
+    /// Currency symbol read by the formats with a symbol in place of their default
+    /// (e.g., "£" for format2 in place of "$")
+    pub currency_symbol: String,
+    /// Decimal separator ("." or ","): formats with a different separator are skipped
+    pub decimal_separator: String,
 }
 
 /// Get a list of valid formats.
 pub fn get_valid_formats() -> Vec<&'static str> {
-    vec!["format1", "format2", "format3", "format4", "format5"]
+    vec![
+        "format1", "format2", "format3", "format4", "format5", "format6", "format7", "format8",
+    ]
+}
+
+/// Amount formats registered at runtime, by name (see register_amount_format)
+static REGISTERED_FORMATS: LazyLock<RwLock<HashMap<String, Arc<dyn AmountFormat + Send + Sync>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Register a custom amount format under a name, for configs to list alongside the
+/// built-in formats. The registry is process-wide. Fails if the name is empty, a
+/// built-in format or already registered.
+pub fn register_amount_format(
+    name: &str,
+    format: Box<dyn AmountFormat + Send + Sync>,
+) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Amount format name must not be empty".to_string());
+    }
+    if get_valid_formats().contains(&name) {
+        return Err(format!(
+            "Amount format '{}' is a built-in format and cannot be registered",
+            name
+        ));
+    }
+    let mut registered = REGISTERED_FORMATS.write().unwrap();
+    if registered.contains_key(name) {
+        return Err(format!("Amount format '{}' is already registered", name));
+    }
+    registered.insert(name.to_string(), Arc::from(format));
+    Ok(())
+}
+
+/// Get the sorted names of the amount formats registered at runtime.
+pub fn get_registered_formats() -> Vec<String> {
+    let mut names: Vec<String> = REGISTERED_FORMATS.read().unwrap().keys().cloned().collect();
+    names.sort();
+    names
+}
+
+/// Get the amount format registered at runtime under a name, if any.
+fn registered_format(name: &str) -> Option<Arc<dyn AmountFormat + Send + Sync>> {
+    REGISTERED_FORMATS.read().unwrap().get(name).cloned()
 }
 
 /// Dispatcher for multiple amount formats.
+#[derive(Clone)]
 pub struct MultiAmountFormatParser {
-    parsers: Vec<Box<dyn AmountFormat>>,
+    parsers: Vec<Arc<dyn AmountFormat + Send + Sync>>,
 }
 
 impl MultiAmountFormatParser {
-    /// Create a new dispatcher from a list of format names.
-    pub fn new(format_names: &[&str]) -> Self {
+    /// Create a new dispatcher from a list of format names, built-in or registered at
+    /// runtime (see register_amount_format), with the currency symbol and decimal
+    /// separator hints applied (see AmountHints).
+    pub fn new(format_names: &[&str], hints: &AmountHints) -> Self {
         // Collect (name, NUM_TERMS) pairs
         let mut formats: Vec<(&str, usize)> = format_names
             .iter()
             .map(|&name| {
                 let num_items = match name {
                     "format1" => Format1.num_items(),
-                    "format2" => Format2.num_items(),
-                    "format3" => Format3.num_items(),
+                    "format2" => Format2::default().num_items(),
+                    "format3" => Format3::default().num_items(),
                     "format4" => Format4.num_items(),
                     "format5" => Format5.num_items(),
-                    _ => 0,
+                    "format6" => Format6::default().num_items(),
+                    "format7" => Format7.num_items(),
+                    "format8" => Format8::default().num_items(),
+                    _ => registered_format(name).map_or(0, |format| format.num_items()),
                 };
                 (name, num_items)
             })
@@ -51,18 +123,40 @@ impl MultiAmountFormatParser {
         // Sort by num items descending
         formats.sort_by_key(|a| std::cmp::Reverse(a.1));
 
-        // Instantiate parsers in sorted order
-        let mut parsers: Vec<Box<dyn AmountFormat>> = Vec::new();
+        // Instantiate parsers in sorted order, with the hinted currency symbol if any
+        let symbol = |default: &str| {
+            if hints.currency_symbol.is_empty() {
+                default.to_string()
+            } else {
+                hints.currency_symbol.clone()
+            }
+        };
+        let mut parsers: Vec<Arc<dyn AmountFormat + Send + Sync>> = Vec::new();
         for &(name, _) in &formats {
             match name {
-                "format1" => parsers.push(Box::new(Format1)),
-                "format2" => parsers.push(Box::new(Format2)),
-                "format3" => parsers.push(Box::new(Format3)),
-                "format4" => parsers.push(Box::new(Format4)),
-                "format5" => parsers.push(Box::new(Format5)),
-                _ => {}
+                "format1" => parsers.push(Arc::new(Format1)),
+                "format2" => parsers.push(Arc::new(Format2::new(&symbol("$")))),
+                "format3" => parsers.push(Arc::new(Format3::new(&symbol("$")))),
+                "format4" => parsers.push(Arc::new(Format4)),
+                "format5" => parsers.push(Arc::new(Format5)),
+                "format6" => parsers.push(Arc::new(Format6::new(&symbol("€")))),
+                "format7" => parsers.push(Arc::new(Format7)),
+                "format8" => parsers.push(Arc::new(Format8::new(&symbol("$")))),
+                _ => {
+                    if let Some(format) = registered_format(name) {
+                        parsers.push(format);
+                    }
+                }
             }
         }
+        // Skip formats with a decimal separator other than the hinted one
+        if !hints.decimal_separator.is_empty() {
+            parsers.retain(|parser| {
+                parser
+                    .decimal_separator()
+                    .is_none_or(|separator| separator == hints.decimal_separator)
+            });
+        }
         MultiAmountFormatParser { parsers }
     }
 
@@ -92,7 +186,8 @@ mod tests {
     use super::*;
     #[test]
     fn test_multi_amount_format_parser() {
-        let multi_fmt1 = MultiAmountFormatParser::new(&["format1", "format2"]);
+        let multi_fmt1 =
+            MultiAmountFormatParser::new(&["format1", "format2"], &AmountHints::default());
         assert_eq!(multi_fmt1.parse("1,234.56"), Some(1234.56));
         assert_eq!(multi_fmt1.parse("-$1,234.56"), Some(-1234.56)); // format2
         assert_eq!(multi_fmt1.parse("$1,234.56 DR"), None); // format3 not included
@@ -100,14 +195,90 @@ mod tests {
 
     #[test]
     fn test_max_items() {
-        let multi_fmt = MultiAmountFormatParser::new(&["format1", "format3", "format5"]);
+        let multi_fmt = MultiAmountFormatParser::new(
+            &["format1", "format3", "format5"],
+            &AmountHints::default(),
+        );
         // format1: 1 item, format3: 2 items, format5: 1 item
         assert_eq!(multi_fmt.max_items(), 2);
 
-        let multi_fmt2 = MultiAmountFormatParser::new(&["format1", "format5"]);
+        let multi_fmt2 =
+            MultiAmountFormatParser::new(&["format1", "format5"], &AmountHints::default());
         assert_eq!(multi_fmt2.max_items(), 1);
 
-        let multi_fmt3 = MultiAmountFormatParser::new(&[]);
+        let multi_fmt3 = MultiAmountFormatParser::new(&[], &AmountHints::default());
         assert_eq!(multi_fmt3.max_items(), 0);
     }
+
+    #[test]
+    fn test_multi_amount_format_parser_european() {
+        let multi_fmt =
+            MultiAmountFormatParser::new(&["format1", "format6"], &AmountHints::default());
+        assert_eq!(multi_fmt.parse("1,234.56"), Some(1234.56)); // format1
+        assert_eq!(multi_fmt.parse("1.234,56"), Some(1234.56)); // format6
+        assert_eq!(multi_fmt.parse("1.234"), Some(1234.0)); // format6, not 1.234
+
+        let multi_fmt1 = MultiAmountFormatParser::new(&["format1"], &AmountHints::default());
+        assert_eq!(multi_fmt1.parse("1.234"), None);
+    }
+
+    /// Amounts in thousands, like "1.5k"
+    struct Thousands;
+
+    impl AmountFormat for Thousands {
+        fn num_items(&self) -> usize {
+            1
+        }
+
+        fn parse(&self, input: &str) -> Option<f64> {
+            let value = input.strip_suffix('k')?.parse::<f64>().ok()?;
+            Some(value * 1000.0)
+        }
+    }
+
+    #[test]
+    fn test_registered_amount_format() {
+        register_amount_format("amount_test_thousands", Box::new(Thousands)).unwrap();
+        assert!(get_registered_formats().contains(&"amount_test_thousands".to_string()));
+
+        let multi_fmt = MultiAmountFormatParser::new(
+            &["format1", "amount_test_thousands"],
+            &AmountHints::default(),
+        );
+        assert_eq!(multi_fmt.parse("1,234.56"), Some(1234.56));
+        assert_eq!(multi_fmt.parse("1.5k"), Some(1500.0));
+
+        assert_eq!(
+            register_amount_format("amount_test_thousands", Box::new(Thousands)),
+            Err("Amount format 'amount_test_thousands' is already registered".to_string())
+        );
+        assert_eq!(
+            register_amount_format("format1", Box::new(Thousands)),
+            Err(
+                "Amount format 'format1' is a built-in format and cannot be registered".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_multi_amount_format_parser_hints() {
+        let hints = AmountHints {
+            currency_symbol: "£".to_string(),
+            decimal_separator: ".".to_string(),
+        };
+        let multi_fmt = MultiAmountFormatParser::new(&["format2", "format5", "format6"], &hints);
+        assert_eq!(multi_fmt.parse("£1,234.56"), Some(1234.56));
+        assert_eq!(multi_fmt.parse("$1,234.56"), None);
+        assert_eq!(multi_fmt.parse("nil"), Some(0.0));
+        assert_eq!(multi_fmt.parse("1.234"), None); // format6 skipped
+        assert_eq!(multi_fmt.max_items(), 1);
+
+        let hints = AmountHints {
+            currency_symbol: String::new(),
+            decimal_separator: ",".to_string(),
+        };
+        let multi_fmt = MultiAmountFormatParser::new(&["format1", "format6"], &hints);
+        assert_eq!(multi_fmt.parse("1,234.56"), None); // format1 skipped
+        assert_eq!(multi_fmt.parse("1.234,56 €"), Some(1234.56));
+    }
 }
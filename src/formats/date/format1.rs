@@ -1,5 +1,9 @@
-use crate::formats::date::DateFormat;
 use crate::formats::date::DateParts;
+use crate::formats::date::{DateFormat, DateParseError};
+use regex::Regex;
+use std::sync::LazyLock;
+
+static PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\d{1,2} \w+$").unwrap());
 
 /// Format1: parses dates like "24 mar", "1 mar", "01 mar"
 pub struct Format1;
@@ -11,14 +15,13 @@ impl DateFormat for Format1 {
 
     /// Parses a date string and returns the UTC timestamp if valid.
     /// Requires a year_str argument.
-    fn parse(&self, date_str: &str, year_str: &str) -> Option<i64> {
-        let re = regex::Regex::new(r"^\d{1,2} \w+$").unwrap();
-        if !re.is_match(date_str) {
-            return None;
+    fn parse(&self, date_str: &str, year_str: &str) -> Result<i64, DateParseError> {
+        if !PATTERN.is_match(date_str) {
+            return Err(DateParseError::Invalid);
         }
         let parts: Vec<&str> = date_str.split(' ').collect();
         if parts.len() != 2 {
-            return None;
+            return Err(DateParseError::Invalid);
         }
         let date_parts = DateParts {
             day_str: parts[0].to_string(),
@@ -38,19 +41,19 @@ mod tests {
         let fmt = Format1;
         // 24 Mar 2023
         let ts = fmt.parse("24 mar", "2023");
-        assert!(ts.is_some());
+        assert!(ts.is_ok());
         // 01 Mar 2023
         let ts2 = fmt.parse("01 mar", "2023");
-        assert!(ts2.is_some());
+        assert!(ts2.is_ok());
         // 9 Mar 2023
         let ts3 = fmt.parse("9 mar", "2023");
-        assert!(ts3.is_some());
+        assert!(ts3.is_ok());
         // 30 Feb 2023 (invalid date)
         let ts4 = fmt.parse("30 feb", "2023");
-        assert!(ts4.is_none());
+        assert!(ts4.is_err());
         // Invalid
-        assert_eq!(fmt.parse("mar 24", "2023"), None);
-        assert_eq!(fmt.parse("", "2023"), None);
-        assert_eq!(fmt.parse("24", "2023"), None);
+        assert_eq!(fmt.parse("mar 24", "2023"), Err(DateParseError::Invalid));
+        assert_eq!(fmt.parse("", "2023"), Err(DateParseError::Invalid));
+        assert_eq!(fmt.parse("24", "2023"), Err(DateParseError::Invalid));
     }
 }
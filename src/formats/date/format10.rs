@@ -1,5 +1,9 @@
-use crate::formats::date::DateFormat;
 use crate::formats::date::DateParts;
+use crate::formats::date::{DateFormat, DateParseError};
+use regex::Regex;
+use std::sync::LazyLock;
+
+static PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\w+ \d{1,2}$").unwrap());
 
 /// Format10: parses MMM DD dates like "Mar 24", "Mar 4", "March 4"
 pub struct Format10;
@@ -10,14 +14,13 @@ impl DateFormat for Format10 {
     }
 
     /// Parses a date string and returns the UTC timestamp if valid.
-    fn parse(&self, date_str: &str, year_str: &str) -> Option<i64> {
-        let re = regex::Regex::new(r"^\w+ \d{1,2}$").unwrap();
-        if !re.is_match(date_str) {
-            return None;
+    fn parse(&self, date_str: &str, year_str: &str) -> Result<i64, DateParseError> {
+        if !PATTERN.is_match(date_str) {
+            return Err(DateParseError::Invalid);
         }
         let parts: Vec<&str> = date_str.split(' ').collect();
         if parts.len() != 2 {
-            return None;
+            return Err(DateParseError::Invalid);
         }
         let date_parts = DateParts {
             day_str: parts[1].to_string(),
@@ -37,34 +40,37 @@ mod tests {
         let fmt = Format10;
         // "Mar 24" (abbreviated month)
         let ts = fmt.parse("Mar 24", "2023");
-        assert!(ts.is_some());
+        assert!(ts.is_ok());
         // "Mar 4" (single-digit day)
         let ts2 = fmt.parse("Mar 4", "2023");
-        assert!(ts2.is_some());
+        assert!(ts2.is_ok());
         // "March 4" (full month name)
         let ts3 = fmt.parse("March 4", "2023");
-        assert!(ts3.is_some());
+        assert!(ts3.is_ok());
         // "Jan 1" (January 1)
         let ts4 = fmt.parse("Jan 1", "2023");
-        assert!(ts4.is_some());
+        assert!(ts4.is_ok());
         // "Dec 31" (December 31)
         let ts5 = fmt.parse("Dec 31", "2023");
-        assert!(ts5.is_some());
+        assert!(ts5.is_ok());
         // "January 15"
         let ts6 = fmt.parse("January 15", "2023");
-        assert!(ts6.is_some());
+        assert!(ts6.is_ok());
         // Invalid date (February 30)
         let ts7 = fmt.parse("Feb 30", "2023");
-        assert!(ts7.is_none());
+        assert!(ts7.is_err());
         // Invalid format - wrong order (DD MMM)
         // Note: "24 Mar" will match regex but fail parsing
         let ts8 = fmt.parse("24 Mar", "2023");
-        assert!(ts8.is_none());
+        assert!(ts8.is_err());
         // Invalid format - empty string
-        assert_eq!(fmt.parse("", "2023"), None);
+        assert_eq!(fmt.parse("", "2023"), Err(DateParseError::Invalid));
         // Invalid format - missing parts
-        assert_eq!(fmt.parse("Mar", "2023"), None);
+        assert_eq!(fmt.parse("Mar", "2023"), Err(DateParseError::Invalid));
         // Invalid format - includes year
-        assert_eq!(fmt.parse("Mar 24 2023", "2023"), None);
+        assert_eq!(
+            fmt.parse("Mar 24 2023", "2023"),
+            Err(DateParseError::Invalid)
+        );
     }
 }
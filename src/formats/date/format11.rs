@@ -1,5 +1,9 @@
-use crate::formats::date::DateFormat;
 use crate::formats::date::DateParts;
+use crate::formats::date::{DateFormat, DateParseError};
+use regex::Regex;
+use std::sync::LazyLock;
+
+static PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\w+ \d{1,2}, \d{4}-\w+").unwrap());
 
 /// Format11: parses dates like "Mar 24, 2023-Apr 24, 2023", "March 4, 2023-April 4, 2023"
 /// Example: CapitalOne credit card statements
@@ -12,18 +16,17 @@ impl DateFormat for Format11 {
 
     /// Parses a date string and returns the UTC timestamp if valid.
     /// Requires a year_str argument.
-    fn parse(&self, date_str: &str, _year_str: &str) -> Option<i64> {
+    fn parse(&self, date_str: &str, _year_str: &str) -> Result<i64, DateParseError> {
         // Only capture the "Mar 24, 2023-Apr" part
-        let re = regex::Regex::new(r"^\w+ \d{1,2}, \d{4}-\w+").unwrap();
-        if !re.is_match(date_str) {
-            return None;
+        if !PATTERN.is_match(date_str) {
+            return Err(DateParseError::Invalid);
         }
         let parts: Vec<&str> = date_str
             .split(&[' ', ',', '-'][..])
             .filter(|s| !s.is_empty())
             .collect();
         if parts.len() < 4 {
-            return None;
+            return Err(DateParseError::Invalid);
         }
         let date_parts = DateParts {
             day_str: parts[1].to_string(),
@@ -43,33 +46,42 @@ mod tests {
         let fmt = Format11;
         // "Mar 24, 2023-Apr" (abbreviated months)
         let ts = fmt.parse("Mar 24, 2023-Apr", "");
-        assert!(ts.is_some());
+        assert!(ts.is_ok());
         // "March 4, 2023-April" (full month names, single-digit day)
         let ts2 = fmt.parse("March 4, 2023-April", "");
-        assert!(ts2.is_some());
+        assert!(ts2.is_ok());
         // "Jan 1, 2023-Feb" (January 1)
         let ts3 = fmt.parse("Jan 1, 2023-Feb", "");
-        assert!(ts3.is_some());
+        assert!(ts3.is_ok());
         // "Dec 31, 2023-Jan" (December 31)
         let ts4 = fmt.parse("Dec 31, 2023-Jan", "");
-        assert!(ts4.is_some());
+        assert!(ts4.is_ok());
         // "January 15, 2023-February" (full month name with two-digit day)
         let ts5 = fmt.parse("January 15, 2023-February", "");
-        assert!(ts5.is_some());
+        assert!(ts5.is_ok());
         // Invalid date (February 30)
         let ts6 = fmt.parse("Feb 30, 2023-Mar", "");
-        assert!(ts6.is_none());
+        assert!(ts6.is_err());
         // Invalid format - missing comma
-        assert_eq!(fmt.parse("Mar 24 2023-Apr", ""), None);
+        assert_eq!(
+            fmt.parse("Mar 24 2023-Apr", ""),
+            Err(DateParseError::Invalid)
+        );
         // Invalid format - missing dash and second month
-        assert_eq!(fmt.parse("Mar 24, 2023", ""), None);
+        assert_eq!(fmt.parse("Mar 24, 2023", ""), Err(DateParseError::Invalid));
         // Invalid format - empty string
-        assert_eq!(fmt.parse("", ""), None);
+        assert_eq!(fmt.parse("", ""), Err(DateParseError::Invalid));
         // Invalid format - missing day
-        assert_eq!(fmt.parse("Mar 2023-Apr", ""), None);
+        assert_eq!(fmt.parse("Mar 2023-Apr", ""), Err(DateParseError::Invalid));
         // Invalid format - wrong separator
-        assert_eq!(fmt.parse("Mar 24-2023-Apr", ""), None);
+        assert_eq!(
+            fmt.parse("Mar 24-2023-Apr", ""),
+            Err(DateParseError::Invalid)
+        );
         // Invalid format - two-digit year
-        assert_eq!(fmt.parse("Mar 24, 23-Apr", ""), None);
+        assert_eq!(
+            fmt.parse("Mar 24, 23-Apr", ""),
+            Err(DateParseError::Invalid)
+        );
     }
 }
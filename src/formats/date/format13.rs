@@ -1,5 +1,9 @@
-use crate::formats::date::DateFormat;
 use crate::formats::date::DateParts;
+use crate::formats::date::{DateFormat, DateParseError};
+use regex::Regex;
+use std::sync::LazyLock;
+
+static PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\d{4}-\d{1,2}-\d{1,2}$").unwrap());
 
 /// Format13: parses YYYY-MM-DD dates like "2023-03-24", "2023-3-24"
 pub struct Format13;
@@ -10,14 +14,13 @@ impl DateFormat for Format13 {
     }
 
     /// Parses a date string and returns the UTC timestamp if valid.
-    fn parse(&self, date_str: &str, _year_str: &str) -> Option<i64> {
-        let re = regex::Regex::new(r"^\d{4}-\d{1,2}-\d{1,2}$").unwrap();
-        if !re.is_match(date_str) {
-            return None;
+    fn parse(&self, date_str: &str, _year_str: &str) -> Result<i64, DateParseError> {
+        if !PATTERN.is_match(date_str) {
+            return Err(DateParseError::Invalid);
         }
         let parts: Vec<&str> = date_str.split('-').collect();
         if parts.len() != 3 {
-            return None;
+            return Err(DateParseError::Invalid);
         }
         let date_parts = DateParts {
             day_str: parts[2].to_string(),
@@ -37,36 +40,36 @@ mod tests {
         let fmt = Format13;
         // "2023-03-24" (YYYY-MM-DD)
         let ts = fmt.parse("2023-03-24", "");
-        assert!(ts.is_some());
+        assert!(ts.is_ok());
         // "2023-3-24" (YYYY-M-DD, single-digit month)
         let ts2 = fmt.parse("2023-3-24", "");
-        assert!(ts2.is_some());
+        assert!(ts2.is_ok());
         // "2023-03-4" (YYYY-MM-D, single-digit day)
         let ts3 = fmt.parse("2023-03-4", "");
-        assert!(ts3.is_some());
+        assert!(ts3.is_ok());
         // "2023-3-4" (YYYY-M-D, both single-digit)
         let ts4 = fmt.parse("2023-3-4", "");
-        assert!(ts4.is_some());
+        assert!(ts4.is_ok());
         // "2023-01-01" (January 1)
         let ts5 = fmt.parse("2023-01-01", "");
-        assert!(ts5.is_some());
+        assert!(ts5.is_ok());
         // "2023-12-31" (December 31)
         let ts6 = fmt.parse("2023-12-31", "");
-        assert!(ts6.is_some());
+        assert!(ts6.is_ok());
         // Invalid date (February 30)
         let ts7 = fmt.parse("2023-02-30", "");
-        assert!(ts7.is_none());
+        assert!(ts7.is_err());
         // Invalid format - wrong separator (slash)
-        assert_eq!(fmt.parse("2023/03/24", ""), None);
+        assert_eq!(fmt.parse("2023/03/24", ""), Err(DateParseError::Invalid));
         // Invalid format - empty string
-        assert_eq!(fmt.parse("", ""), None);
+        assert_eq!(fmt.parse("", ""), Err(DateParseError::Invalid));
         // Invalid format - missing parts
-        assert_eq!(fmt.parse("2023-03", ""), None);
+        assert_eq!(fmt.parse("2023-03", ""), Err(DateParseError::Invalid));
         // Invalid format - two-digit year
-        assert_eq!(fmt.parse("23-03-24", ""), None);
+        assert_eq!(fmt.parse("23-03-24", ""), Err(DateParseError::Invalid));
         // Invalid format - three-digit year
-        assert_eq!(fmt.parse("202-03-24", ""), None);
+        assert_eq!(fmt.parse("202-03-24", ""), Err(DateParseError::Invalid));
         // Invalid format - too many digits in month
-        assert_eq!(fmt.parse("2023-003-24", ""), None);
+        assert_eq!(fmt.parse("2023-003-24", ""), Err(DateParseError::Invalid));
     }
 }
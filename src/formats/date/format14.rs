@@ -0,0 +1,104 @@
+use crate::formats::date::generate::{parse_month, parse_year};
+use crate::formats::date::{DateFormat, DateParseError};
+use regex::Regex;
+use std::sync::LazyLock;
+
+static PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[A-Za-z]+$").unwrap());
+
+/// Format14: parses month-only dates like "Mar", "March" (e.g., monthly recurring entries).
+/// The day is set by the month_only_default_day config field: a day of the month (clamped
+/// to the last day of short months) or "last" for the last day of the month.
+pub struct Format14 {
+    /// Day of the month, or None for the last day of the month
+    pub default_day: Option<u32>,
+}
+
+impl Format14 {
+    /// Create a new Format14 from a month_only_default_day value ("last" or a day of
+    /// the month). Invalid values default to the first day of the month.
+    pub fn new(default_day: &str) -> Self {
+        let default_day = match default_day.trim() {
+            "last" => None,
+            day => Some(day.parse::<u32>().unwrap_or(1)),
+        };
+        Self { default_day }
+    }
+}
+
+impl Default for Format14 {
+    fn default() -> Self {
+        Self {
+            default_day: Some(1),
+        }
+    }
+}
+
+impl DateFormat for Format14 {
+    fn num_items(&self) -> usize {
+        1
+    }
+
+    /// Parses a month name and returns the UTC timestamp of the default day in the
+    /// month of the year_str, if valid. Requires a year_str.
+    fn parse(&self, date_str: &str, year_str: &str) -> Result<i64, DateParseError> {
+        if !PATTERN.is_match(date_str) {
+            return Err(DateParseError::Invalid);
+        }
+        let month = parse_month(date_str).ok_or(DateParseError::Invalid)?;
+        if year_str.trim().is_empty() {
+            return Err(DateParseError::MissingYear);
+        }
+        let year = parse_year(year_str).ok_or(DateParseError::Invalid)? as i32;
+
+        let first =
+            chrono::NaiveDate::from_ymd_opt(year, month, 1).ok_or(DateParseError::Invalid)?;
+        let last = first
+            .checked_add_months(chrono::Months::new(1))
+            .and_then(|d| d.pred_opt())
+            .ok_or(DateParseError::Invalid)?;
+        // Days past the end of the month fall on the last day
+        let date = match self.default_day {
+            Some(day) => chrono::NaiveDate::from_ymd_opt(year, month, day.max(1)).unwrap_or(last),
+            None => last,
+        };
+        let datetime = date.and_hms_opt(0, 0, 0).ok_or(DateParseError::Invalid)?;
+        Ok(datetime.and_utc().timestamp_millis())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ymd(year: i32, month: u32, day: u32) -> Result<i64, DateParseError> {
+        chrono::NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .map(|dt| dt.and_utc().timestamp_millis())
+            .ok_or(DateParseError::Invalid)
+    }
+
+    #[test]
+    fn test_format14_parse() {
+        let fmt = Format14::default();
+        // "Mar" (abbreviated month), first day by default
+        assert_eq!(fmt.parse("Mar", "2024"), ymd(2024, 3, 1));
+        // "March" (full month)
+        assert_eq!(fmt.parse("March", "2024"), ymd(2024, 3, 1));
+        // Configured day, clamped to the end of short months
+        assert_eq!(Format14::new("15").parse("Feb", "2023"), ymd(2023, 2, 15));
+        assert_eq!(Format14::new("31").parse("Feb", "2024"), ymd(2024, 2, 29));
+        assert_eq!(Format14::new("31").parse("Apr", "2024"), ymd(2024, 4, 30));
+        // Last day of the month
+        assert_eq!(Format14::new("last").parse("Feb", "2023"), ymd(2023, 2, 28));
+        assert_eq!(
+            Format14::new("last").parse("Dec", "2023"),
+            ymd(2023, 12, 31)
+        );
+        // No year context
+        assert_eq!(fmt.parse("Mar", ""), Err(DateParseError::MissingYear));
+        // Invalid: numeric month, day and month, or not a month
+        assert_eq!(fmt.parse("03", "2024"), Err(DateParseError::Invalid));
+        assert_eq!(fmt.parse("24 Mar", "2024"), Err(DateParseError::Invalid));
+        assert_eq!(fmt.parse("Rent", "2024"), Err(DateParseError::Invalid));
+    }
+}
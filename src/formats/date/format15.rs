@@ -0,0 +1,99 @@
+use crate::formats::date::generate::parse_weekday;
+use crate::formats::date::{DateFormat, DateParseError, format1::Format1, format2::Format2};
+
+/// Format15: parses day-month dates with a weekday name before or after them, like
+/// "Mon 24 Mar", "24 Mar Mon", "Weds 24 March 2025" or "Tu, 24 Mar". Weekday names may be
+/// full, abbreviated ("Mon", "Tues", "Weds", "Thurs") or two letters ("Mo", "Tu"), in
+/// any case. The date without the weekday is read as format1 or format2, and the weekday
+/// is not checked against the date.
+pub struct Format15;
+
+impl DateFormat for Format15 {
+    fn num_items(&self) -> usize {
+        // e.g., "Mon" "24" "Mar" "2025"
+        4
+    }
+
+    /// Parses a date string and returns the UTC timestamp if valid. Requires a year_str
+    /// argument for dates without a year.
+    fn parse(&self, date_str: &str, year_str: &str) -> Result<i64, DateParseError> {
+        let parts: Vec<&str> = date_str.split(' ').collect();
+        if parts.len() < 3 {
+            return Err(DateParseError::Invalid);
+        }
+        let date_str = if parse_weekday(parts[0]).is_some() {
+            parts[1..].join(" ")
+        } else if parse_weekday(parts[parts.len() - 1]).is_some() {
+            parts[..parts.len() - 1].join(" ")
+        } else {
+            return Err(DateParseError::Invalid);
+        };
+        match parts.len() {
+            3 => Format1.parse(&date_str, year_str),
+            _ => Format2.parse(&date_str, year_str),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::date::DateParts;
+
+    fn ts(day: &str, month: &str, year: &str) -> Result<i64, DateParseError> {
+        DateParts::new(day.to_string(), month.to_string(), year.to_string()).to_utc_timestamp("")
+    }
+
+    #[test]
+    fn test_format15_parse() {
+        let fmt = Format15;
+        let expected = ts("24", "03", "2025");
+        assert_eq!(fmt.parse("Mon 24 Mar", "2025"), expected);
+        assert_eq!(fmt.parse("24 Mar Mon", "2025"), expected);
+        assert_eq!(fmt.parse("MON 24 MAR", "2025"), expected);
+        assert_eq!(fmt.parse("Monday 24 March", "2025"), expected);
+        assert_eq!(fmt.parse("Mon, 24 Mar", "2025"), expected);
+        assert_eq!(fmt.parse("Mo 24 Mar", "2025"), expected);
+        assert_eq!(fmt.parse("Mon 24 Mar 2025", ""), expected);
+        assert_eq!(fmt.parse("24 Mar 2025 Mon", ""), expected);
+        // Without a year to fall back on
+        assert_eq!(
+            fmt.parse("Mon 24 Mar", ""),
+            Err(DateParseError::MissingYear)
+        );
+        // No weekday, or not a weekday
+        assert_eq!(fmt.parse("24 Mar", "2025"), Err(DateParseError::Invalid));
+        assert_eq!(fmt.parse("24 Mar 2025", ""), Err(DateParseError::Invalid));
+        assert_eq!(
+            fmt.parse("Sunny 24 Mar", "2025"),
+            Err(DateParseError::Invalid)
+        );
+        assert_eq!(
+            fmt.parse("Mon 32 Mar", "2025"),
+            Err(DateParseError::Invalid)
+        );
+        assert_eq!(
+            fmt.parse("Mon 24 Mar Tue", "2025"),
+            Err(DateParseError::Invalid)
+        );
+    }
+
+    #[test]
+    fn test_format15_abbreviations() {
+        let fmt = Format15;
+        let expected = ts("26", "03", "2025");
+        for weekday in ["We", "Wed", "Weds", "Wednesday", "WEDS", "Wed."] {
+            assert_eq!(
+                fmt.parse(&format!("{} 26 Mar", weekday), "2025"),
+                expected,
+                "{}",
+                weekday
+            );
+        }
+        for weekday in [
+            "Tu", "Tue", "Tues", "Th", "Thu", "Thur", "Thurs", "Sa", "Sat", "Su",
+        ] {
+            assert!(fmt.parse(&format!("26 Mar {}", weekday), "2025").is_ok());
+        }
+    }
+}
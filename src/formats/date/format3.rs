@@ -1,5 +1,9 @@
-use crate::formats::date::DateFormat;
 use crate::formats::date::DateParts;
+use crate::formats::date::{DateFormat, DateParseError};
+use regex::Regex;
+use std::sync::LazyLock;
+
+static PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\w+ \d{1,2}, \d{4}$").unwrap());
 
 /// Format3: parses dates like "march 24, 2020", "mar 1, 2020"
 pub struct Format3;
@@ -10,16 +14,15 @@ impl DateFormat for Format3 {
     }
 
     /// Parses a date string and returns the UTC timestamp if valid.
-    fn parse(&self, date_str: &str, _year_str: &str) -> Option<i64> {
-        let re = regex::Regex::new(r"^\w+ \d{1,2}, \d{4}$").unwrap();
-        if !re.is_match(date_str) {
-            return None;
+    fn parse(&self, date_str: &str, _year_str: &str) -> Result<i64, DateParseError> {
+        if !PATTERN.is_match(date_str) {
+            return Err(DateParseError::Invalid);
         }
         // Remove comma and split
         let cleaned = date_str.replace(",", "");
         let parts: Vec<&str> = cleaned.split(' ').collect();
         if parts.len() != 3 {
-            return None;
+            return Err(DateParseError::Invalid);
         }
         let date_parts = DateParts {
             day_str: parts[1].to_string(),
@@ -39,14 +42,14 @@ mod tests {
         let fmt = Format3;
         // "march 24, 2020"
         let ts = fmt.parse("march 24, 2020", "");
-        assert!(ts.is_some());
+        assert!(ts.is_ok());
         // "mar 1, 2020"
         let ts2 = fmt.parse("mar 1, 2020", "");
-        assert!(ts2.is_some());
+        assert!(ts2.is_ok());
         // Invalid
-        assert_eq!(fmt.parse("24 march 2020", ""), None);
-        assert_eq!(fmt.parse("march 24 2020", ""), None);
-        assert_eq!(fmt.parse("march", ""), None);
-        assert_eq!(fmt.parse("", ""), None);
+        assert_eq!(fmt.parse("24 march 2020", ""), Err(DateParseError::Invalid));
+        assert_eq!(fmt.parse("march 24 2020", ""), Err(DateParseError::Invalid));
+        assert_eq!(fmt.parse("march", ""), Err(DateParseError::Invalid));
+        assert_eq!(fmt.parse("", ""), Err(DateParseError::Invalid));
     }
 }
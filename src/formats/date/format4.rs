@@ -1,5 +1,9 @@
-use crate::formats::date::DateFormat;
 use crate::formats::date::DateParts;
+use crate::formats::date::{DateFormat, DateParseError};
+use regex::Regex;
+use std::sync::LazyLock;
+
+static PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\d{1,2}/\d{1,2}/\d{4}$").unwrap());
 
 /// Format4: parses dates like "24/3/2020", "01/03/2020", "24/03/2020"
 pub struct Format4;
@@ -10,14 +14,13 @@ impl DateFormat for Format4 {
     }
 
     /// Parses a date string and returns the UTC timestamp if valid.
-    fn parse(&self, date_str: &str, _year_str: &str) -> Option<i64> {
-        let re = regex::Regex::new(r"^\d{1,2}/\d{1,2}/\d{4}$").unwrap();
-        if !re.is_match(date_str) {
-            return None;
+    fn parse(&self, date_str: &str, _year_str: &str) -> Result<i64, DateParseError> {
+        if !PATTERN.is_match(date_str) {
+            return Err(DateParseError::Invalid);
         }
         let parts: Vec<&str> = date_str.split('/').collect();
         if parts.len() != 3 {
-            return None;
+            return Err(DateParseError::Invalid);
         }
         let date_parts = DateParts {
             day_str: parts[0].to_string(),
@@ -37,16 +40,16 @@ mod tests {
         let fmt = Format4;
         // "24/3/2020"
         let ts = fmt.parse("24/3/2020", "");
-        assert!(ts.is_some());
+        assert!(ts.is_ok());
         // "01/03/2020"
         let ts2 = fmt.parse("01/03/2020", "");
-        assert!(ts2.is_some());
+        assert!(ts2.is_ok());
         // "24/03/2020"
         let ts3 = fmt.parse("24/03/2020", "");
-        assert!(ts3.is_some());
+        assert!(ts3.is_ok());
         // Invalid
-        assert_eq!(fmt.parse("24-03-2020", ""), None);
-        assert_eq!(fmt.parse("24/03", ""), None);
-        assert_eq!(fmt.parse("", ""), None);
+        assert_eq!(fmt.parse("24-03-2020", ""), Err(DateParseError::Invalid));
+        assert_eq!(fmt.parse("24/03", ""), Err(DateParseError::Invalid));
+        assert_eq!(fmt.parse("", ""), Err(DateParseError::Invalid));
     }
 }
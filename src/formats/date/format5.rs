@@ -1,5 +1,9 @@
-use crate::formats::date::DateFormat;
 use crate::formats::date::DateParts;
+use crate::formats::date::{DateFormat, DateParseError};
+use regex::Regex;
+use std::sync::LazyLock;
+
+static PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\d{1,2}/\d{1,2}/\d{2}$").unwrap());
 
 /// Format5: parses dates like "24/3/20", "01/03/20", "24/03/20"
 pub struct Format5;
@@ -10,23 +14,31 @@ impl DateFormat for Format5 {
     }
 
     /// Parses a date string and returns the UTC timestamp if valid.
-    fn parse(&self, date_str: &str, _year_str: &str) -> Option<i64> {
-        let re = regex::Regex::new(r"^\d{1,2}/\d{1,2}/\d{2}$").unwrap();
-        if !re.is_match(date_str) {
-            return None;
+    fn parse(&self, date_str: &str, year_str: &str) -> Result<i64, DateParseError> {
+        self.parse_with_pivot(date_str, year_str, None)
+    }
+
+    /// Parses a date string, expanding its 2-digit year with year_str or the pivot year.
+    fn parse_with_pivot(
+        &self,
+        date_str: &str,
+        year_str: &str,
+        pivot: Option<u32>,
+    ) -> Result<i64, DateParseError> {
+        if !PATTERN.is_match(date_str) {
+            return Err(DateParseError::Invalid);
         }
         let parts: Vec<&str> = date_str.split('/').collect();
         if parts.len() != 3 {
-            return None;
+            return Err(DateParseError::Invalid);
         }
-        // Convert 2-digit year to 4-digit year using parse_year
-        let year = crate::formats::date::generate::parse_year(parts[2])?;
+        // 2-digit year is expanded relative to year_str (if given) in to_utc_timestamp
         let date_parts = DateParts {
             day_str: parts[0].to_string(),
             month_str: parts[1].to_string(),
-            year_str: year.to_string(),
+            year_str: parts[2].to_string(),
         };
-        date_parts.to_utc_timestamp("")
+        date_parts.to_utc_timestamp_with_pivot(year_str, pivot)
     }
 }
 
@@ -39,16 +51,75 @@ mod tests {
         let fmt = Format5;
         // "24/3/20"
         let ts = fmt.parse("24/3/20", "");
-        assert!(ts.is_some());
+        assert!(ts.is_ok());
         // "01/03/20"
         let ts2 = fmt.parse("01/03/20", "");
-        assert!(ts2.is_some());
+        assert!(ts2.is_ok());
         // "24/03/20"
         let ts3 = fmt.parse("24/03/20", "");
-        assert!(ts3.is_some());
+        assert!(ts3.is_ok());
         // Invalid
-        assert_eq!(fmt.parse("24-03-20", ""), None);
-        assert_eq!(fmt.parse("24/03", ""), None);
-        assert_eq!(fmt.parse("", ""), None);
+        assert_eq!(fmt.parse("24-03-20", ""), Err(DateParseError::Invalid));
+        assert_eq!(fmt.parse("24/03", ""), Err(DateParseError::Invalid));
+        assert_eq!(fmt.parse("", ""), Err(DateParseError::Invalid));
+    }
+
+    #[test]
+    fn test_format5_leap_day() {
+        let fmt = Format5;
+        let expected_2024_feb_29 = chrono::NaiveDate::from_ymd_opt(2024, 2, 29)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp_millis();
+        assert_eq!(fmt.parse("29/02/24", ""), Ok(expected_2024_feb_29));
+        assert_eq!(fmt.parse("29/02/24", "2024"), Ok(expected_2024_feb_29));
+        // On a statement starting in December 2023
+        assert_eq!(fmt.parse("29/02/24", "2023"), Ok(expected_2024_feb_29));
+        // Not a leap year, must not be moved to 2024
+        assert_eq!(fmt.parse("29/02/23", "2023"), Err(DateParseError::Invalid));
+    }
+
+    #[test]
+    fn test_format5_century_from_year_str() {
+        let fmt = Format5;
+        let expected_1999_dec_31 = chrono::NaiveDate::from_ymd_opt(1999, 12, 31)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp_millis();
+        assert_eq!(fmt.parse("31/12/99", "2000"), Ok(expected_1999_dec_31));
+    }
+
+    #[test]
+    fn test_format5_year_window() {
+        let fmt = Format5;
+        let timestamp = |year, month, day| {
+            chrono::NaiveDate::from_ymd_opt(year, month, day)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_utc()
+                .timestamp_millis()
+        };
+        // The statement's own decade
+        assert_eq!(fmt.parse("24/3/20", "2020"), Ok(timestamp(2020, 3, 24)));
+        // An archived 1999 statement, with or without its start year
+        assert_eq!(fmt.parse("24/3/99", "1999"), Ok(timestamp(1999, 3, 24)));
+        assert_eq!(
+            fmt.parse_with_pivot("24/3/99", "", Some(2025)),
+            Ok(timestamp(1999, 3, 24))
+        );
+        // The start year takes precedence over the pivot
+        assert_eq!(
+            fmt.parse_with_pivot("24/3/95", "2000", Some(2090)),
+            Ok(timestamp(1995, 3, 24))
+        );
+        // Up to 10 years after the start year, and not before 1970
+        assert_eq!(fmt.parse("1/1/49", "2039"), Ok(timestamp(2049, 1, 1)));
+        assert_eq!(fmt.parse("1/1/50", "2039"), Err(DateParseError::Invalid));
+        assert_eq!(fmt.parse("1/1/70", "2039"), Ok(timestamp(1970, 1, 1)));
     }
 }
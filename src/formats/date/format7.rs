@@ -1,5 +1,10 @@
-use crate::formats::date::DateFormat;
 use crate::formats::date::DateParts;
+use crate::formats::date::{DateFormat, DateParseError};
+use regex::Regex;
+use std::sync::LazyLock;
+
+static PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\d{1,2}-\d{1,2}-\d{2,4}$").unwrap());
 
 /// Format7: parses DD-MM-YYYY or DD-MM-YY dates like "24-03-2023", 24-3-2023", "24-03-23", "24-3-23"
 pub struct Format7;
@@ -10,21 +15,30 @@ impl DateFormat for Format7 {
     }
 
     /// Parses a date string and returns the UTC timestamp if valid.
-    fn parse(&self, date_str: &str, _year_str: &str) -> Option<i64> {
-        let re = regex::Regex::new(r"^\d{1,2}-\d{1,2}-\d{2,4}$").unwrap();
-        if !re.is_match(date_str) {
-            return None;
+    fn parse(&self, date_str: &str, year_str: &str) -> Result<i64, DateParseError> {
+        self.parse_with_pivot(date_str, year_str, None)
+    }
+
+    /// Parses a date string, expanding its 2-digit year with year_str or the pivot year.
+    fn parse_with_pivot(
+        &self,
+        date_str: &str,
+        year_str: &str,
+        pivot: Option<u32>,
+    ) -> Result<i64, DateParseError> {
+        if !PATTERN.is_match(date_str) {
+            return Err(DateParseError::Invalid);
         }
         let parts: Vec<&str> = date_str.split('-').collect();
         if parts.len() != 3 {
-            return None;
+            return Err(DateParseError::Invalid);
         }
         let date_parts = DateParts {
             day_str: parts[0].to_string(),
             month_str: parts[1].to_string(),
             year_str: parts[2].to_string(),
         };
-        date_parts.to_utc_timestamp("")
+        date_parts.to_utc_timestamp_with_pivot(year_str, pivot)
     }
 }
 
@@ -37,36 +51,36 @@ mod tests {
         let fmt = Format7;
         // "24-03-2023" (DD-MM-YYYY)
         let ts = fmt.parse("24-03-2023", "");
-        assert!(ts.is_some());
+        assert!(ts.is_ok());
         // "24-3-2023" (DD-M-YYYY, single-digit month)
         let ts2 = fmt.parse("24-3-2023", "");
-        assert!(ts2.is_some());
+        assert!(ts2.is_ok());
         // "24-03-23" (DD-MM-YY)
         let ts3 = fmt.parse("24-03-23", "");
-        assert!(ts3.is_some());
+        assert!(ts3.is_ok());
         // "24-3-23" (DD-M-YY)
         let ts4 = fmt.parse("24-3-23", "");
-        assert!(ts4.is_some());
+        assert!(ts4.is_ok());
         // "1-1-2023" (D-M-YYYY, both single-digit)
         let ts5 = fmt.parse("1-1-2023", "");
-        assert!(ts5.is_some());
+        assert!(ts5.is_ok());
         // "31-12-2023" (December 31)
         let ts6 = fmt.parse("31-12-2023", "");
-        assert!(ts6.is_some());
+        assert!(ts6.is_ok());
         // Invalid date (February 30)
         let ts7 = fmt.parse("30-02-2023", "");
-        assert!(ts7.is_none());
+        assert!(ts7.is_err());
         // Invalid format - wrong separator (slash)
-        assert_eq!(fmt.parse("24/03/2023", ""), None);
+        assert_eq!(fmt.parse("24/03/2023", ""), Err(DateParseError::Invalid));
         // Invalid format - wrong order (MM-DD-YYYY)
         // Note: This will parse but may give unexpected results
         // Invalid format - empty string
-        assert_eq!(fmt.parse("", ""), None);
+        assert_eq!(fmt.parse("", ""), Err(DateParseError::Invalid));
         // Invalid format - missing parts
-        assert_eq!(fmt.parse("24-03", ""), None);
+        assert_eq!(fmt.parse("24-03", ""), Err(DateParseError::Invalid));
         // Invalid format - single digit year
-        assert_eq!(fmt.parse("24-03-3", ""), None);
+        assert_eq!(fmt.parse("24-03-3", ""), Err(DateParseError::Invalid));
         // Invalid format - too many digits in day
-        assert_eq!(fmt.parse("240-03-2023", ""), None);
+        assert_eq!(fmt.parse("240-03-2023", ""), Err(DateParseError::Invalid));
     }
 }
@@ -1,5 +1,10 @@
-use crate::formats::date::DateFormat;
 use crate::formats::date::DateParts;
+use crate::formats::date::{DateFormat, DateParseError};
+use regex::Regex;
+use std::sync::LazyLock;
+
+static PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\d{1,2}-\d{1,2}-\d{2,4}$").unwrap());
 
 /// Format8: parses MM-DD-YYYY or MM-DD-YY dates like "03-24-2023", "3-24-2023", "03-24-23", "3-24-23"
 pub struct Format8;
@@ -10,21 +15,30 @@ impl DateFormat for Format8 {
     }
 
     /// Parses a date string and returns the UTC timestamp if valid.
-    fn parse(&self, date_str: &str, _year_str: &str) -> Option<i64> {
-        let re = regex::Regex::new(r"^\d{1,2}-\d{1,2}-\d{2,4}$").unwrap();
-        if !re.is_match(date_str) {
-            return None;
+    fn parse(&self, date_str: &str, year_str: &str) -> Result<i64, DateParseError> {
+        self.parse_with_pivot(date_str, year_str, None)
+    }
+
+    /// Parses a date string, expanding its 2-digit year with year_str or the pivot year.
+    fn parse_with_pivot(
+        &self,
+        date_str: &str,
+        year_str: &str,
+        pivot: Option<u32>,
+    ) -> Result<i64, DateParseError> {
+        if !PATTERN.is_match(date_str) {
+            return Err(DateParseError::Invalid);
         }
         let parts: Vec<&str> = date_str.split('-').collect();
         if parts.len() != 3 {
-            return None;
+            return Err(DateParseError::Invalid);
         }
         let date_parts = DateParts {
             day_str: parts[1].to_string(),
             month_str: parts[0].to_string(),
             year_str: parts[2].to_string(),
         };
-        date_parts.to_utc_timestamp("")
+        date_parts.to_utc_timestamp_with_pivot(year_str, pivot)
     }
 }
 
@@ -37,34 +51,34 @@ mod tests {
         let fmt = Format8;
         // "03-24-2023" (MM-DD-YYYY)
         let ts = fmt.parse("03-24-2023", "");
-        assert!(ts.is_some());
+        assert!(ts.is_ok());
         // "3-24-2023" (M-DD-YYYY, single-digit month)
         let ts2 = fmt.parse("3-24-2023", "");
-        assert!(ts2.is_some());
+        assert!(ts2.is_ok());
         // "03-24-23" (MM-DD-YY)
         let ts3 = fmt.parse("03-24-23", "");
-        assert!(ts3.is_some());
+        assert!(ts3.is_ok());
         // "3-24-23" (M-DD-YY)
         let ts4 = fmt.parse("3-24-23", "");
-        assert!(ts4.is_some());
+        assert!(ts4.is_ok());
         // "1-1-2023" (M-D-YYYY, both single-digit)
         let ts5 = fmt.parse("1-1-2023", "");
-        assert!(ts5.is_some());
+        assert!(ts5.is_ok());
         // "12-31-2023" (December 31)
         let ts6 = fmt.parse("12-31-2023", "");
-        assert!(ts6.is_some());
+        assert!(ts6.is_ok());
         // Invalid date (February 30)
         let ts7 = fmt.parse("02-30-2023", "");
-        assert!(ts7.is_none());
+        assert!(ts7.is_err());
         // Invalid format - wrong separator (slash)
-        assert_eq!(fmt.parse("03/24/2023", ""), None);
+        assert_eq!(fmt.parse("03/24/2023", ""), Err(DateParseError::Invalid));
         // Invalid format - empty string
-        assert_eq!(fmt.parse("", ""), None);
+        assert_eq!(fmt.parse("", ""), Err(DateParseError::Invalid));
         // Invalid format - missing parts
-        assert_eq!(fmt.parse("03-24", ""), None);
+        assert_eq!(fmt.parse("03-24", ""), Err(DateParseError::Invalid));
         // Invalid format - single digit year
-        assert_eq!(fmt.parse("03-24-3", ""), None);
+        assert_eq!(fmt.parse("03-24-3", ""), Err(DateParseError::Invalid));
         // Invalid format - too many digits in month
-        assert_eq!(fmt.parse("003-24-2023", ""), None);
+        assert_eq!(fmt.parse("003-24-2023", ""), Err(DateParseError::Invalid));
     }
 }
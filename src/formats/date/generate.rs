@@ -34,18 +34,72 @@ pub fn parse_month(month_str: &str) -> Option<u32> {
     }
 }
 
+/// Parses a weekday name (e.g. "Mon", "Weds", "Thursday", "Tu") and returns the day number
+/// from Monday (0) to Sunday (6). A trailing "." or "," is ignored. Returns None if the input
+/// is not a weekday name.
+pub fn parse_weekday(weekday_str: &str) -> Option<u32> {
+    let weekday = weekday_str.trim().trim_end_matches(['.', ',']);
+    match weekday.to_ascii_lowercase().as_str() {
+        "mo" | "mon" | "monday" => Some(0),
+        "tu" | "tue" | "tues" | "tuesday" => Some(1),
+        "we" | "wed" | "weds" | "wednesday" => Some(2),
+        "th" | "thu" | "thur" | "thurs" | "thursday" => Some(3),
+        "fr" | "fri" | "friday" => Some(4),
+        "sa" | "sat" | "saturday" => Some(5),
+        "su" | "sun" | "sunday" => Some(6),
+        _ => None,
+    }
+}
+
+/// Years after the pivot year into which a 2-digit year can be expanded (see
+/// expand_two_digit_year). The other 89 years of the window are before the pivot.
+pub const TWO_DIGIT_YEAR_WINDOW_AHEAD: u32 = 10;
+
+/// Expands a 2-digit year (0-99) to the year within the window from 89 years before to 10
+/// years after the pivot year (e.g. the statement start year).
+/// - "99" with pivot 2001 is 1999, "11" with pivot 2001 is 2011 but "12" is 1912.
+/// - "49" with pivot 2039 is 2049, the end of the window, and "50" is 1950.
+pub fn expand_two_digit_year(short_year: u32, pivot: u32) -> u32 {
+    let latest = pivot + TWO_DIGIT_YEAR_WINDOW_AHEAD;
+    let year = latest / 100 * 100 + short_year;
+    if year > latest { year - 100 } else { year }
+}
+
 /// Parses a year string and returns the year as u32 if valid, or None if invalid.
-/// - 2-digit years are interpreted as 2000+year.
-/// - Years in [1970, 2100) are accepted.
+/// - 2-digit years are read as 20xx without a pivot year (see parse_year_with_pivot).
+/// - Years in [1970, 2100) are accepted, whether given with 2 or 4 digits.
 /// - Otherwise returns None.
 pub fn parse_year(year_str: &str) -> Option<u32> {
+    parse_year_with_pivot(year_str, None)
+}
+
+/// Parses a year string like parse_year, but expands 2-digit years with the given pivot
+/// year (see expand_two_digit_year). Without a pivot year, a 2-digit year is read as
+/// 20xx, so that the result never depends on when it is parsed.
+pub fn parse_year_with_pivot(year_str: &str, pivot: Option<u32>) -> Option<u32> {
     let year = year_str.trim().parse::<u32>().ok()?;
-    if year < 100 {
-        Some(year + 2000)
-    } else if (1970..2100).contains(&year) {
-        Some(year)
-    } else {
-        None
+    let year = match pivot {
+        Some(pivot) if is_two_digit_year(year_str) => expand_two_digit_year(year, pivot),
+        None if is_two_digit_year(year_str) => year + 2000,
+        _ => year,
+    };
+    (1970..2100).contains(&year).then_some(year)
+}
+
+/// Returns true if the year string is a 2-digit year (e.g. "24").
+pub fn is_two_digit_year(year_str: &str) -> bool {
+    let year_str = year_str.trim();
+    year_str.len() <= 2 && !year_str.is_empty() && year_str.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Parses a year string like parse_year, but expands 2-digit years with the anchor year
+/// (e.g. the statement start year) as the pivot (see expand_two_digit_year).
+/// - "99" with anchor "2000" is 1999, "01" with anchor "1999" is 2001.
+/// - Falls back to the given pivot year, if any, if the anchor is empty or invalid.
+pub fn parse_year_anchored(year_str: &str, anchor_str: &str, pivot: Option<u32>) -> Option<u32> {
+    match parse_year_with_pivot(anchor_str, pivot) {
+        Some(anchor) => parse_year_with_pivot(year_str, Some(anchor)),
+        None => parse_year_with_pivot(year_str, pivot),
     }
 }
 
@@ -88,12 +142,55 @@ mod tests {
         assert_eq!(parse_month("foo"), None);
     }
 
+    // Weekday tests
+    #[test]
+    fn test_parse_weekday() {
+        assert_eq!(parse_weekday("Mon"), Some(0));
+        assert_eq!(parse_weekday("tues"), Some(1));
+        assert_eq!(parse_weekday("Weds"), Some(2));
+        assert_eq!(parse_weekday("THURSDAY"), Some(3));
+        assert_eq!(parse_weekday("Fr"), Some(4));
+        assert_eq!(parse_weekday("Sat,"), Some(5));
+        assert_eq!(parse_weekday("Sun."), Some(6));
+        assert_eq!(parse_weekday("Mar"), None);
+        assert_eq!(parse_weekday("M"), None);
+        assert_eq!(parse_weekday(""), None);
+    }
+
     // Year tests
     #[test]
     fn test_parse_year_two_digit() {
-        assert_eq!(parse_year("23"), Some(2023));
+        assert_eq!(parse_year_with_pivot("23", Some(2025)), Some(2023));
+        assert_eq!(parse_year_with_pivot("99", Some(2025)), Some(1999));
+        assert_eq!(parse_year_with_pivot("00", Some(2025)), Some(2000));
+        // 20xx without a pivot, whatever the current year
         assert_eq!(parse_year("99"), Some(2099));
-        assert_eq!(parse_year("00"), Some(2000));
+        assert_eq!(parse_year("25"), Some(2025));
+    }
+
+    #[test]
+    fn test_parse_year_two_digit_before_1970() {
+        // Years before 1970 are rejected, whether given with 2 or 4 digits
+        assert_eq!(expand_two_digit_year(12, 2001), 1912);
+        assert_eq!(parse_year_with_pivot("12", Some(2001)), None);
+        assert_eq!(parse_year_with_pivot("1912", Some(2001)), None);
+        assert_eq!(parse_year_with_pivot("70", Some(2001)), Some(1970));
+        assert_eq!(parse_year_with_pivot("69", Some(2001)), None);
+    }
+
+    #[test]
+    fn test_expand_two_digit_year_window() {
+        // A 1999 statement
+        assert_eq!(expand_two_digit_year(99, 1999), 1999);
+        assert_eq!(expand_two_digit_year(0, 1999), 2000);
+        assert_eq!(expand_two_digit_year(9, 1999), 2009);
+        assert_eq!(expand_two_digit_year(10, 1999), 1910);
+        // The window ends 10 years after the pivot
+        assert_eq!(expand_two_digit_year(49, 2039), 2049);
+        assert_eq!(expand_two_digit_year(50, 2039), 1950);
+        assert_eq!(expand_two_digit_year(49, 2038), 1949);
+        assert_eq!(parse_year_with_pivot("49", Some(2039)), Some(2049));
+        assert_eq!(parse_year_with_pivot("2049", Some(1990)), Some(2049));
     }
 
     #[test]
@@ -111,4 +208,43 @@ mod tests {
         assert_eq!(parse_year(""), None);
         assert_eq!(parse_year("3000"), None);
     }
+
+    #[test]
+    fn test_is_two_digit_year() {
+        assert!(is_two_digit_year("24"));
+        assert!(is_two_digit_year(" 05 "));
+        assert!(!is_two_digit_year("2024"));
+        assert!(!is_two_digit_year(""));
+        assert!(!is_two_digit_year("ab"));
+    }
+
+    #[test]
+    fn test_parse_year_anchored_same_century() {
+        assert_eq!(parse_year_anchored("24", "2024", None), Some(2024));
+        assert_eq!(parse_year_anchored("25", "2024", None), Some(2025));
+        assert_eq!(parse_year_anchored("23", "2024", None), Some(2023));
+    }
+
+    #[test]
+    fn test_parse_year_anchored_across_century() {
+        assert_eq!(parse_year_anchored("99", "2000", None), Some(1999));
+        assert_eq!(parse_year_anchored("00", "1999", None), Some(2000));
+        assert_eq!(parse_year_anchored("98", "1998", None), Some(1998));
+    }
+
+    #[test]
+    fn test_parse_year_anchored_without_anchor() {
+        // The pivot is used without an anchor, and ignored with one
+        assert_eq!(parse_year_anchored("99", "", Some(2025)), Some(1999));
+        assert_eq!(parse_year_anchored("24", "abc", Some(2025)), Some(2024));
+        assert_eq!(parse_year_anchored("45", "", Some(2040)), Some(2045));
+        assert_eq!(parse_year_anchored("95", "", Some(2090)), Some(2095));
+        assert_eq!(parse_year_anchored("95", "2000", Some(2090)), Some(1995));
+    }
+
+    #[test]
+    fn test_parse_year_anchored_four_digit_ignores_anchor() {
+        assert_eq!(parse_year_anchored("2024", "1999", None), Some(2024));
+        assert_eq!(parse_year_anchored("1969", "2024", None), None);
+    }
 }
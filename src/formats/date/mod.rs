@@ -3,6 +3,8 @@ pub mod format10;
 pub mod format11;
 pub mod format12;
 pub mod format13;
+pub mod format14;
+pub mod format15;
 pub mod format2;
 pub mod format3;
 pub mod format4;
@@ -13,30 +15,112 @@ pub mod format8;
 pub mod format9;
 pub mod generate;
 
-use crate::formats::date::generate::{parse_day, parse_month, parse_year};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, LazyLock, RwLock};
+
+use chrono::{DateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::formats::date::generate::{
+    is_two_digit_year, parse_day, parse_month, parse_year_anchored, parse_year_with_pivot,
+};
 use crate::formats::date::{
     format1::Format1, format2::Format2, format3::Format3, format4::Format4, format5::Format5,
     format6::Format6, format7::Format7, format8::Format8, format9::Format9, format10::Format10,
-    format11::Format11, format12::Format12, format13::Format13,
+    format11::Format11, format12::Format12, format13::Format13, format14::Format14,
+    format15::Format15,
 };
 
+/// Reason a date string could not be converted to a timestamp.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateParseError {
+    /// Not a date in the format, or not a valid calendar date
+    Invalid,
+    /// A date without a year, with no year to fall back on (e.g., the statement start
+    /// date has not been read yet)
+    MissingYear,
+}
+
+impl fmt::Display for DateParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateParseError::Invalid => write!(f, "Invalid date"),
+            DateParseError::MissingYear => write!(f, "Date has no year and no year was provided"),
+        }
+    }
+}
+
 /// Trait for date formats.
 pub trait DateFormat {
     /// Number of space-delimited items in the input string.
     fn num_items(&self) -> usize;
 
     /// Parse the input string and return a UTC timestamp (milliseconds since epoch) if valid.
-    fn parse(&self, input: &str, year_str: &str) -> Option<i64>;
+    fn parse(&self, input: &str, year_str: &str) -> Result<i64, DateParseError>;
+
+    /// Parse like parse, expanding 2-digit years with the pivot year, if given, when
+    /// year_str is empty, rather than as 20xx (see expand_two_digit_year).
+    /// Only formats with 2-digit years need to implement it.
+    fn parse_with_pivot(
+        &self,
+        input: &str,
+        year_str: &str,
+        pivot: Option<u32>,
+    ) -> Result<i64, DateParseError> {
+        let _ = pivot;
+        self.parse(input, year_str)
+    }
 }
 
 /// Get a list of valid formats.
 pub fn get_valid_formats() -> Vec<&'static str> {
     vec![
         "format1", "format2", "format3", "format4", "format5", "format6", "format7", "format8",
-        "format9", "format10", "format11", "format12", "format13",
+        "format9", "format10", "format11", "format12", "format13", "format14", "format15",
     ]
 }
 
+/// Date formats registered at runtime, by name (see register_date_format)
+static REGISTERED_FORMATS: LazyLock<RwLock<HashMap<String, Arc<dyn DateFormat + Send + Sync>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Register a custom date format under a name, for configs to list alongside the
+/// built-in formats. The registry is process-wide. Fails if the name is empty, a
+/// built-in format or already registered.
+pub fn register_date_format(
+    name: &str,
+    format: Box<dyn DateFormat + Send + Sync>,
+) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Date format name must not be empty".to_string());
+    }
+    if get_valid_formats().contains(&name) {
+        return Err(format!(
+            "Date format '{}' is a built-in format and cannot be registered",
+            name
+        ));
+    }
+    let mut registered = REGISTERED_FORMATS.write().unwrap();
+    if registered.contains_key(name) {
+        return Err(format!("Date format '{}' is already registered", name));
+    }
+    registered.insert(name.to_string(), Arc::from(format));
+    Ok(())
+}
+
+/// Get the sorted names of the date formats registered at runtime.
+pub fn get_registered_formats() -> Vec<String> {
+    let mut names: Vec<String> = REGISTERED_FORMATS.read().unwrap().keys().cloned().collect();
+    names.sort();
+    names
+}
+
+/// Get the date format registered at runtime under a name, if any.
+fn registered_format(name: &str) -> Option<Arc<dyn DateFormat + Send + Sync>> {
+    REGISTERED_FORMATS.read().unwrap().get(name).cloned()
+}
+
 /// Stores day, month, and year strings and can convert to a UTC timestamp.
 #[derive(Debug, Clone)]
 pub struct DateParts {
@@ -54,24 +138,37 @@ impl DateParts {
         }
     }
 
+    /// Attempts to convert the stored strings to a UTC timestamp (milliseconds since epoch),
+    /// as for to_utc_timestamp_with_pivot without a pivot year.
+    pub fn to_utc_timestamp(&self, year_str: &str) -> Result<i64, DateParseError> {
+        self.to_utc_timestamp_with_pivot(year_str, None)
+    }
+
     /// Attempts to convert the stored strings to a UTC timestamp (milliseconds since epoch).
     /// If self.year_str is empty, uses the input arg year_str.
-    /// If both are empty, panics.
-    /// If self.year_str is not empty, uses it even if the input arg is not empty.
-    pub fn to_utc_timestamp(&self, year_str: &str) -> Option<i64> {
-        let day = parse_day(&self.day_str)?;
-        let month = parse_month(&self.month_str)?;
+    /// If both are empty, returns DateParseError::MissingYear.
+    /// If self.year_str is not empty, uses it even if the input arg is not empty. A 2-digit
+    /// self.year_str is expanded to the window from 89 years before to 10 years after the
+    /// input arg year_str as the pivot year, if given, else the pivot arg (see
+    /// expand_two_digit_year). Without either, a 2-digit self.year_str is read as 20xx.
+    /// An invalid 29 February is moved to the next year, unless an explicit 2-digit year was given.
+    pub fn to_utc_timestamp_with_pivot(
+        &self,
+        year_str: &str,
+        pivot: Option<u32>,
+    ) -> Result<i64, DateParseError> {
+        let day = parse_day(&self.day_str).ok_or(DateParseError::Invalid)?;
+        let month = parse_month(&self.month_str).ok_or(DateParseError::Invalid)?;
 
         // Determine which year string to use
-        let year_source = if !self.year_str.trim().is_empty() {
-            &self.year_str
+        let explicit_two_digit_year = is_two_digit_year(&self.year_str);
+        let year = if !self.year_str.trim().is_empty() {
+            parse_year_anchored(&self.year_str, year_str, pivot).ok_or(DateParseError::Invalid)?
         } else if !year_str.trim().is_empty() {
-            year_str
+            parse_year_with_pivot(year_str, pivot).ok_or(DateParseError::Invalid)?
         } else {
-            panic!("No year string provided to to_utc_timestamp");
-        };
-
-        let year = parse_year(year_source)? as i32;
+            return Err(DateParseError::MissingYear);
+        } as i32;
 
         // Try to create the date
         let date = chrono::NaiveDate::from_ymd_opt(year, month, day);
@@ -79,27 +176,54 @@ impl DateParts {
         // If parsing failed and we have February 29, try adding 1 year (leap year fix)
         let date = match date {
             Some(d) => d,
-            None if day == 29 && month == 2 => {
-                // Feb 29 failed, likely because current year is not a leap year
+            None if day == 29 && month == 2 && !explicit_two_digit_year => {
+                // Feb 29 failed, likely because the year is not a leap year
                 // Try adding 1 year to handle year crossover issue with leap years
-                chrono::NaiveDate::from_ymd_opt(year + 1, month, day)?
+                chrono::NaiveDate::from_ymd_opt(year + 1, month, day)
+                    .ok_or(DateParseError::Invalid)?
             }
-            None => return None,
+            None => return Err(DateParseError::Invalid),
         };
 
-        let datetime = date.and_hms_opt(0, 0, 0)?;
-        Some(datetime.and_utc().timestamp_millis())
+        let datetime = date.and_hms_opt(0, 0, 0).ok_or(DateParseError::Invalid)?;
+        Ok(datetime.and_utc().timestamp_millis())
     }
 }
 
+/// Convert a UTC midnight timestamp (see DateParts::to_utc_timestamp) to the timestamp of
+/// local midnight on the same calendar date in the given timezone. If midnight does not
+/// exist on that date (DST transition), the first valid local time of the day is used.
+pub fn to_local_midnight_timestamp(utc_midnight_ms: i64, tz: Tz) -> Option<i64> {
+    let date = DateTime::<Utc>::from_timestamp_millis(utc_midnight_ms)?.date_naive();
+    (0..24).find_map(|hour| {
+        tz.from_local_datetime(&date.and_hms_opt(hour, 0, 0)?)
+            .earliest()
+            .map(|dt| dt.timestamp_millis())
+    })
+}
+
 /// Dispatcher for multiple date formats.
+#[derive(Clone)]
 pub struct MultiDateFormatParser {
-    parsers: Vec<Box<dyn DateFormat>>,
+    parsers: Vec<Arc<dyn DateFormat + Send + Sync>>,
+    /// Pivot year of 2-digit years when no year is given (see DateFormat::parse_with_pivot)
+    pivot: Option<u32>,
 }
 
 impl MultiDateFormatParser {
-    /// Create a new dispatcher from a list of format names.
+    /// Create a new dispatcher from a list of format names. Month-only dates (format14)
+    /// fall on the first day of the month.
     pub fn new(format_names: &[&str]) -> Self {
+        Self::with_month_only_default_day(format_names, "1")
+    }
+
+    /// Create a new dispatcher from a list of format names, built-in or registered at
+    /// runtime (see register_date_format), with month-only dates (format14) on the given
+    /// month_only_default_day ("last" or a day of the month).
+    pub fn with_month_only_default_day(
+        format_names: &[&str],
+        month_only_default_day: &str,
+    ) -> Self {
         // Collect (name, num_items) pairs
         let mut formats: Vec<(&str, usize)> = format_names
             .iter()
@@ -118,7 +242,9 @@ impl MultiDateFormatParser {
                     "format11" => Format11.num_items(),
                     "format12" => Format12.num_items(),
                     "format13" => Format13.num_items(),
-                    _ => 0,
+                    "format14" => Format14::default().num_items(),
+                    "format15" => Format15.num_items(),
+                    _ => registered_format(name).map_or(0, |format| format.num_items()),
                 };
                 (name, num_items)
             })
@@ -128,36 +254,58 @@ impl MultiDateFormatParser {
         formats.sort_by_key(|a| std::cmp::Reverse(a.1));
 
         // Instantiate parsers in sorted order
-        let mut parsers: Vec<Box<dyn DateFormat>> = Vec::new();
+        let mut parsers: Vec<Arc<dyn DateFormat + Send + Sync>> = Vec::new();
         for &(name, _) in &formats {
             match name {
-                "format1" => parsers.push(Box::new(Format1)),
-                "format2" => parsers.push(Box::new(Format2)),
-                "format3" => parsers.push(Box::new(Format3)),
-                "format4" => parsers.push(Box::new(Format4)),
-                "format5" => parsers.push(Box::new(Format5)),
-                "format6" => parsers.push(Box::new(Format6)),
-                "format7" => parsers.push(Box::new(Format7)),
-                "format8" => parsers.push(Box::new(Format8)),
-                "format9" => parsers.push(Box::new(Format9)),
-                "format10" => parsers.push(Box::new(Format10)),
-                "format11" => parsers.push(Box::new(Format11)),
-                "format12" => parsers.push(Box::new(Format12)),
-                "format13" => parsers.push(Box::new(Format13)),
-                _ => {}
+                "format1" => parsers.push(Arc::new(Format1)),
+                "format2" => parsers.push(Arc::new(Format2)),
+                "format3" => parsers.push(Arc::new(Format3)),
+                "format4" => parsers.push(Arc::new(Format4)),
+                "format5" => parsers.push(Arc::new(Format5)),
+                "format6" => parsers.push(Arc::new(Format6)),
+                "format7" => parsers.push(Arc::new(Format7)),
+                "format8" => parsers.push(Arc::new(Format8)),
+                "format9" => parsers.push(Arc::new(Format9)),
+                "format10" => parsers.push(Arc::new(Format10)),
+                "format11" => parsers.push(Arc::new(Format11)),
+                "format12" => parsers.push(Arc::new(Format12)),
+                "format13" => parsers.push(Arc::new(Format13)),
+                "format14" => parsers.push(Arc::new(Format14::new(month_only_default_day))),
+                "format15" => parsers.push(Arc::new(Format15)),
+                _ => {
+                    if let Some(format) = registered_format(name) {
+                        parsers.push(format);
+                    }
+                }
             }
         }
-        MultiDateFormatParser { parsers }
+        MultiDateFormatParser {
+            parsers,
+            pivot: None,
+        }
+    }
+
+    /// Set the pivot year of 2-digit years when no year is given. Without a pivot year a
+    /// 2-digit year is read as 20xx, and with one it is expanded to the year from 89
+    /// years before to 10 years after the pivot (see expand_two_digit_year)
+    pub fn with_two_digit_year_pivot(mut self, pivot: Option<u32>) -> Self {
+        self.pivot = pivot;
+        self
     }
 
     /// Try parsing with each format in order, returning the first successful result.
-    pub fn parse(&self, input: &str, year_str: &str) -> Option<i64> {
+    /// If none succeeds, the error is DateParseError::MissingYear if any format matched
+    /// a date without a year, else DateParseError::Invalid.
+    pub fn parse(&self, input: &str, year_str: &str) -> Result<i64, DateParseError> {
+        let mut error = DateParseError::Invalid;
         for parser in &self.parsers {
-            if let Some(val) = parser.parse(input, year_str) {
-                return Some(val);
+            match parser.parse_with_pivot(input, year_str, self.pivot) {
+                Ok(val) => return Ok(val),
+                Err(DateParseError::MissingYear) => error = DateParseError::MissingYear,
+                Err(DateParseError::Invalid) => {}
             }
         }
-        None
+        Err(error)
     }
 
     /// Returns the maximum number of items among all formats.
@@ -181,7 +329,7 @@ mod tests {
             month_str: "Feb".to_string(),
             year_str: "2023".to_string(),
         };
-        assert_eq!(dp.to_utc_timestamp(""), Some(1676419200000)); // 2023-02-15T00:00:00Z
+        assert_eq!(dp.to_utc_timestamp(""), Ok(1676419200000)); // 2023-02-15T00:00:00Z
     }
 
     #[test]
@@ -191,7 +339,7 @@ mod tests {
             month_str: "Feb".to_string(),
             year_str: "2023".to_string(),
         };
-        assert_eq!(dp.to_utc_timestamp(""), None);
+        assert_eq!(dp.to_utc_timestamp(""), Err(DateParseError::Invalid));
     }
 
     #[test]
@@ -201,7 +349,7 @@ mod tests {
             month_str: "Foo".to_string(),
             year_str: "2023".to_string(),
         };
-        assert_eq!(dp.to_utc_timestamp(""), None);
+        assert_eq!(dp.to_utc_timestamp(""), Err(DateParseError::Invalid));
     }
 
     #[test]
@@ -211,7 +359,7 @@ mod tests {
             month_str: "Feb".to_string(),
             year_str: "abcd".to_string(),
         };
-        assert_eq!(dp.to_utc_timestamp(""), None);
+        assert_eq!(dp.to_utc_timestamp(""), Err(DateParseError::Invalid));
     }
 
     #[test]
@@ -221,66 +369,71 @@ mod tests {
             month_str: "Feb".to_string(),
             year_str: "".to_string(),
         };
-        assert_eq!(dp.to_utc_timestamp("2023"), Some(1676419200000)); // 2023-02-15T00:00:00Z
+        assert_eq!(dp.to_utc_timestamp("2023"), Ok(1676419200000)); // 2023-02-15T00:00:00Z
     }
 
     #[test]
-    #[should_panic(expected = "No year string provided to to_utc_timestamp")]
-    fn test_to_utc_timestamp_panics_if_no_year_provided() {
+    fn test_to_utc_timestamp_errors_if_no_year_provided() {
         let dp = DateParts {
             day_str: "15".to_string(),
             month_str: "Feb".to_string(),
             year_str: "".to_string(),
         };
-        dp.to_utc_timestamp("");
+        assert_eq!(dp.to_utc_timestamp(""), Err(DateParseError::MissingYear));
     }
 
     #[test]
     fn test_multi_date_format_parser() {
         let multi_fmt = MultiDateFormatParser::new(&[
             "format1", "format2", "format3", "format4", "format5", "format6", "format7", "format8",
-            "format9", "format10", "format11", "format12", "format13",
+            "format9", "format10", "format11", "format12", "format13", "format14", "format15",
         ]);
         // Should parse using format1
-        assert!(multi_fmt.parse("24 mar", "2023").is_some());
+        assert!(multi_fmt.parse("24 mar", "2023").is_ok());
         // Should parse using format2
-        assert!(multi_fmt.parse("24 march 2020", "").is_some());
+        assert!(multi_fmt.parse("24 march 2020", "").is_ok());
         // Should parse using format3
-        assert!(multi_fmt.parse("march 24, 2020", "").is_some());
+        assert!(multi_fmt.parse("march 24, 2020", "").is_ok());
         // Should parse using format4
-        assert!(multi_fmt.parse("24/3/2020", "").is_some());
+        assert!(multi_fmt.parse("24/3/2020", "").is_ok());
         // Should parse using format5
-        assert!(multi_fmt.parse("24/3/20", "").is_some());
+        assert!(multi_fmt.parse("24/3/20", "").is_ok());
         // Should parse using format6
-        assert!(multi_fmt.parse("3/24", "2020").is_some());
+        assert!(multi_fmt.parse("3/24", "2020").is_ok());
         // Should parse using format7
-        assert!(multi_fmt.parse("24-03-2020", "").is_some());
-        assert!(multi_fmt.parse("24-3-20", "").is_some());
+        assert!(multi_fmt.parse("24-03-2020", "").is_ok());
+        assert!(multi_fmt.parse("24-3-20", "").is_ok());
         // Should parse using format8
-        assert!(multi_fmt.parse("03-24-2020", "").is_some());
-        assert!(multi_fmt.parse("3-24-20", "").is_some());
+        assert!(multi_fmt.parse("03-24-2020", "").is_ok());
+        assert!(multi_fmt.parse("3-24-20", "").is_ok());
         // Should parse using format9
-        assert!(multi_fmt.parse("03/24/2020", "").is_some());
-        assert!(multi_fmt.parse("3/24/20", "").is_some());
+        assert!(multi_fmt.parse("03/24/2020", "").is_ok());
+        assert!(multi_fmt.parse("3/24/20", "").is_ok());
         // Should parse using format10
-        assert!(multi_fmt.parse("Mar 24", "2023").is_some());
-        assert!(multi_fmt.parse("March 24", "2023").is_some());
-        assert!(multi_fmt.parse("March 4", "2023").is_some());
+        assert!(multi_fmt.parse("Mar 24", "2023").is_ok());
+        assert!(multi_fmt.parse("March 24", "2023").is_ok());
+        assert!(multi_fmt.parse("March 4", "2023").is_ok());
         // Should parse using format11
-        assert!(multi_fmt.parse("Mar 24, 2023-Apr 24, 2023", "").is_some());
-        assert!(
-            multi_fmt
-                .parse("March 1, 2020-March 31, 2020", "")
-                .is_some()
-        );
+        assert!(multi_fmt.parse("Mar 24, 2023-Apr 24, 2023", "").is_ok());
+        assert!(multi_fmt.parse("March 1, 2020-March 31, 2020", "").is_ok());
         // Should parse using format12
-        assert!(multi_fmt.parse("2023/03/24", "").is_some());
-        assert!(multi_fmt.parse("2023/3/24", "").is_some());
+        assert!(multi_fmt.parse("2023/03/24", "").is_ok());
+        assert!(multi_fmt.parse("2023/3/24", "").is_ok());
         // Should parse using format13
-        assert!(multi_fmt.parse("2023-03-24", "").is_some());
-        assert!(multi_fmt.parse("2023-3-24", "").is_some());
+        assert!(multi_fmt.parse("2023-03-24", "").is_ok());
+        assert!(multi_fmt.parse("2023-3-24", "").is_ok());
+        // Should parse using format14
+        assert!(multi_fmt.parse("Mar", "2023").is_ok());
+        // Should parse using format15
+        assert!(multi_fmt.parse("Weds 24 Mar", "2023").is_ok());
+        assert!(multi_fmt.parse("24 Mar 2023 Fri", "").is_ok());
         // Should not parse invalid
-        assert_eq!(multi_fmt.parse("foo", "2023"), None);
+        assert_eq!(multi_fmt.parse("foo", "2023"), Err(DateParseError::Invalid));
+        // Dates without a year need a year to fall back on
+        assert_eq!(
+            multi_fmt.parse("24 mar", ""),
+            Err(DateParseError::MissingYear)
+        );
     }
 
     #[test]
@@ -294,7 +447,7 @@ mod tests {
 
         // Should automatically try 2024 (which is a leap year) when 2023 fails
         let result = dp.to_utc_timestamp("");
-        assert!(result.is_some());
+        assert!(result.is_ok());
 
         // Verify it's actually 2024-02-29
         let expected_2024_feb_29 = chrono::NaiveDate::from_ymd_opt(2024, 2, 29)
@@ -316,7 +469,7 @@ mod tests {
         };
 
         let result = dp.to_utc_timestamp("");
-        assert!(result.is_some());
+        assert!(result.is_ok());
 
         // Should be exactly 2024-02-29
         let expected_2024_feb_29 = chrono::NaiveDate::from_ymd_opt(2024, 2, 29)
@@ -328,6 +481,75 @@ mod tests {
         assert_eq!(result.unwrap(), expected_2024_feb_29);
     }
 
+    #[test]
+    fn test_february_29_explicit_two_digit_year_not_bumped() {
+        // 2023 is not a leap year, and the year was given explicitly
+        let dp = DateParts::new("29".to_string(), "02".to_string(), "23".to_string());
+        assert_eq!(dp.to_utc_timestamp(""), Err(DateParseError::Invalid));
+        assert_eq!(dp.to_utc_timestamp("2023"), Err(DateParseError::Invalid));
+    }
+
+    #[test]
+    fn test_february_29_two_digit_year_anchored_to_start_year() {
+        let dp = DateParts::new("29".to_string(), "02".to_string(), "24".to_string());
+        let expected_2024_feb_29 = chrono::NaiveDate::from_ymd_opt(2024, 2, 29)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp_millis();
+        assert_eq!(dp.to_utc_timestamp(""), Ok(expected_2024_feb_29));
+        assert_eq!(dp.to_utc_timestamp("2024"), Ok(expected_2024_feb_29));
+        assert_eq!(dp.to_utc_timestamp("2023"), Ok(expected_2024_feb_29));
+    }
+
+    #[test]
+    fn test_two_digit_year_anchored_across_century() {
+        let dp = DateParts::new("31".to_string(), "12".to_string(), "99".to_string());
+        let expected_1999_dec_31 = chrono::NaiveDate::from_ymd_opt(1999, 12, 31)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp_millis();
+        assert_eq!(dp.to_utc_timestamp("2000"), Ok(expected_1999_dec_31));
+    }
+
+    #[test]
+    fn test_two_digit_year_pivot() {
+        use chrono::Datelike;
+        let year_of = |pivot| {
+            let parser = MultiDateFormatParser::new(&["format5"]).with_two_digit_year_pivot(pivot);
+            let timestamp = parser.parse("1/1/95", "").unwrap();
+            DateTime::from_timestamp_millis(timestamp).unwrap().year()
+        };
+        assert_eq!(year_of(Some(2090)), 2095);
+        assert_eq!(year_of(Some(2000)), 1995);
+    }
+
+    #[test]
+    fn test_to_local_midnight_timestamp() {
+        let hour_ms = 3_600_000;
+        let jan = DateParts::new("15".to_string(), "01".to_string(), "2024".to_string())
+            .to_utc_timestamp("")
+            .unwrap();
+        let jul = DateParts::new("15".to_string(), "07".to_string(), "2024".to_string())
+            .to_utc_timestamp("")
+            .unwrap();
+
+        // Sydney is UTC+11 in January (daylight saving) and UTC+10 in July
+        let sydney: Tz = "Australia/Sydney".parse().unwrap();
+        assert_eq!(
+            to_local_midnight_timestamp(jan, sydney),
+            Some(jan - 11 * hour_ms)
+        );
+        assert_eq!(
+            to_local_midnight_timestamp(jul, sydney),
+            Some(jul - 10 * hour_ms)
+        );
+        assert_eq!(to_local_midnight_timestamp(jan, chrono_tz::UTC), Some(jan));
+    }
+
     #[test]
     fn test_max_items() {
         let multi_fmt = MultiDateFormatParser::new(&["format1", "format3", "format5"]);
@@ -336,7 +558,51 @@ mod tests {
         let multi_fmt2 = MultiDateFormatParser::new(&["format1"]);
         assert_eq!(multi_fmt2.max_items(), 2);
 
+        // The weekday is an extra item
+        let multi_fmt4 = MultiDateFormatParser::new(&["format1", "format15"]);
+        assert_eq!(multi_fmt4.max_items(), 4);
+
         let multi_fmt3 = MultiDateFormatParser::new(&[]);
         assert_eq!(multi_fmt3.max_items(), 0);
     }
+
+    /// Compact dates without separators, like "20240415"
+    struct Compact;
+
+    impl DateFormat for Compact {
+        fn num_items(&self) -> usize {
+            1
+        }
+
+        fn parse(&self, input: &str, _year_str: &str) -> Result<i64, DateParseError> {
+            if input.len() != 8 || !input.chars().all(|c| c.is_ascii_digit()) {
+                return Err(DateParseError::Invalid);
+            }
+            DateParts::new(
+                input[6..8].to_string(),
+                input[4..6].to_string(),
+                input[0..4].to_string(),
+            )
+            .to_utc_timestamp("")
+        }
+    }
+
+    #[test]
+    fn test_registered_date_format() {
+        register_date_format("date_test_compact", Box::new(Compact)).unwrap();
+        assert!(get_registered_formats().contains(&"date_test_compact".to_string()));
+
+        let multi_fmt = MultiDateFormatParser::new(&["format1", "date_test_compact"]);
+        assert_eq!(multi_fmt.parse("20240415", ""), Ok(1713139200000));
+        assert_eq!(multi_fmt.max_items(), 2);
+
+        assert_eq!(
+            register_date_format("date_test_compact", Box::new(Compact)),
+            Err("Date format 'date_test_compact' is already registered".to_string())
+        );
+        assert_eq!(
+            register_date_format("format14", Box::new(Compact)),
+            Err("Date format 'format14' is a built-in format and cannot be registered".to_string())
+        );
+    }
 }
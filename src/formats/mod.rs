@@ -1,5 +1,5 @@
 pub mod amount;
 pub mod date;
 
-pub use amount::MultiAmountFormatParser;
+pub use amount::{AmountHints, MultiAmountFormatParser};
 pub use date::MultiDateFormatParser;
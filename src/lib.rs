@@ -1,25 +1,46 @@
 pub mod checkers;
 pub mod configs;
+pub mod error;
 pub mod fixers;
 pub mod formats;
+pub mod parser;
 pub mod parsers;
+#[cfg(feature = "python")]
 pub mod python;
 pub mod structs;
+#[cfg(test)]
+mod test_fixtures;
 
-use crate::python::exceptions::{ConfigLoadError, NoErrorFreeStatementData};
+pub use error::TranstractorError;
+pub use fixers::FixersMode;
+pub use parser::{ParseOptions, Parser};
+
+#[cfg(feature = "python")]
+use crate::python::exceptions::{
+    ConfigAccessError, ConfigLoadError, LayoutParseError, NoErrorFreeStatementData,
+};
+#[cfg(feature = "python")]
 use crate::python::lib_config_db::LibConfigDB;
+#[cfg(feature = "python")]
 use crate::python::lib_parser::LibParser;
+#[cfg(feature = "python")]
+use crate::python::lib_transaction_stream::LibTransactionStream;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
 /// Python module definition
+#[cfg(feature = "python")]
 #[pymodule]
 fn transtractor(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<LibParser>()?;
     m.add_class::<LibConfigDB>()?;
-    m.add(
-        "NoErrorFreeStatementData",
-        m.py().get_type::<NoErrorFreeStatementData>(),
-    )?;
+    m.add_class::<LibTransactionStream>()?;
+    let no_error_free = m.py().get_type::<NoErrorFreeStatementData>();
+    // Set on the raised exception when the results of each config are known
+    no_error_free.setattr("breakdown", m.py().None())?;
+    m.add("NoErrorFreeStatementData", no_error_free)?;
     m.add("ConfigLoadError", m.py().get_type::<ConfigLoadError>())?;
+    m.add("ConfigAccessError", m.py().get_type::<ConfigAccessError>())?;
+    m.add("LayoutParseError", m.py().get_type::<LayoutParseError>())?;
     Ok(())
 }
@@ -0,0 +1,675 @@
+use crate::checkers::check_expected_account_number;
+use crate::configs::db::ConfigDB;
+use crate::configs::typer::StatementTyper;
+use crate::configs::validate::account_terms::account_terms as validate_account_terms;
+use crate::configs::validate::date_timezone::date_timezone as validate_date_timezone;
+use crate::configs::validate::utils::normalize_term;
+use crate::error::TranstractorError;
+use crate::fixers::FixersMode;
+use crate::parsers::flows::config_json_file_to_config;
+use crate::parsers::flows::layout_to_text_items::layout_to_text_items;
+use crate::parsers::flows::statement_data_verification::{
+    VerificationReport, VerifyOptions, verify_statement_data,
+};
+use crate::parsers::flows::text_items_to_debug::{DebugReport, text_items_to_debug_report};
+use crate::parsers::flows::text_items_to_statement_datas::{
+    PreparedConfigs, text_items_to_statement_datas_prepared,
+};
+use crate::structs::{
+    Categorizer, LocaleProfile, ParseTimings, Redaction, StatementConfig, StatementData, TextItem,
+    Transaction,
+};
+
+/// Options of a parse (see Parser::parse_statement_datas)
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    /// Account number, or regex pattern prefixed with "re:", that results must have to
+    /// be error-free (see account_number_matches)
+    pub expected_account_number: Option<String>,
+    /// Timezone overriding the date_timezone of every config
+    pub date_timezone: Option<String>,
+    /// Fixers applied before the checks
+    pub fixers: FixersMode,
+    /// Retry configs whose fix_text_order reordering gives errors in the original
+    /// text order
+    pub retry_text_order: bool,
+    /// Report an error if more lines of the transaction table than allowed are not
+    /// read by any parser, as if every config set transaction_strict
+    pub strict: bool,
+    /// Redaction of the results, applied after the checks (see Redaction)
+    pub redaction: Option<Redaction>,
+    /// Categorizer of the transactions, applied after the checks and before any
+    /// redaction (see Categorizer)
+    pub categorizer: Option<Categorizer>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            expected_account_number: None,
+            date_timezone: None,
+            fixers: FixersMode::All,
+            retry_text_order: false,
+            strict: false,
+            redaction: None,
+            categorizer: None,
+        }
+    }
+}
+
+/// Native parser of bank statements, without Python: a database of configs, the
+/// StatementTyper identifying the configs applicable to a statement, and the configs
+/// prepared for parsing, reused across documents. Statements are parsed from text
+/// items (or layout text), as text extraction from PDFs is done in Python.
+pub struct Parser {
+    typer: StatementTyper,
+    db: ConfigDB,
+    /// Skip configs of countries implausible for the document (see LocaleProfile)
+    locale_filter: bool,
+    /// Configs prepared for parsing, reused across documents (see PreparedConfigs)
+    prepared_configs: PreparedConfigs,
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Self {
+            typer: StatementTyper::new(),
+            db: ConfigDB::new(true, false),
+            locale_filter: false,
+            prepared_configs: PreparedConfigs::new(),
+        }
+    }
+}
+
+impl Parser {
+    /// Create a parser with no configs
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Skip the configs of countries whose statements are in a different script from
+    /// the document when identifying configs (see LocaleProfile::is_plausible_key)
+    pub fn set_locale_filter(&mut self, locale_filter: bool) {
+        self.locale_filter = locale_filter;
+    }
+
+    /// Identify configs if at least match_threshold of their account terms are found
+    /// (see StatementTyper::set_min_score). Must be above 0.0 and at most 1.0.
+    pub fn set_match_threshold(&mut self, match_threshold: f64) -> Result<(), TranstractorError> {
+        self.typer
+            .set_min_score(match_threshold)
+            .map_err(TranstractorError::InvalidArgument)
+    }
+
+    /// Get the StatementTyper identifying the configs applicable to a statement
+    pub fn typer(&self) -> &StatementTyper {
+        &self.typer
+    }
+
+    /// Import a JSON config string into the database and update the StatementTyper.
+    /// Returns the config.
+    pub fn import_config_from_json_str(
+        &mut self,
+        json_str: &str,
+    ) -> Result<StatementConfig, TranstractorError> {
+        self.db
+            .register_from_str(json_str)
+            .map_err(TranstractorError::ConfigLoad)?;
+        let cfg = config_json_file_to_config::from_json_str(json_str)
+            .map_err(TranstractorError::ConfigLoad)?;
+        self.typer
+            .add_account_terms(&cfg.key, &cfg.account_terms, cfg.terms_case_insensitive);
+        Ok(cfg)
+    }
+
+    /// Import a JSON config file into the database and update the StatementTyper.
+    /// Returns the config.
+    pub fn import_config_from_file(
+        &mut self,
+        file_path: &str,
+    ) -> Result<StatementConfig, TranstractorError> {
+        self.db
+            .register_from_file(file_path)
+            .map_err(TranstractorError::ConfigLoad)?;
+        let cfg = config_json_file_to_config::from_json_file(file_path)
+            .map_err(TranstractorError::ConfigLoad)?;
+        self.typer
+            .add_account_terms(&cfg.key, &cfg.account_terms, cfg.terms_case_insensitive);
+        Ok(cfg)
+    }
+
+    /// Register a JSON config string into the database without updating the
+    /// StatementTyper
+    pub fn register_config_from_json_str(
+        &mut self,
+        json_str: &str,
+    ) -> Result<(), TranstractorError> {
+        self.db
+            .register_from_str(json_str)
+            .map_err(TranstractorError::ConfigLoad)
+    }
+
+    /// Add account terms to the StatementTyper for a config key. Terms are normalised
+    /// as for configs loaded from JSON, then validated.
+    pub fn add_account_terms(
+        &mut self,
+        key: &str,
+        terms: &[String],
+        terms_case_insensitive: bool,
+    ) -> Result<(), TranstractorError> {
+        let terms: Vec<String> = terms.iter().map(|term| normalize_term(term)).collect();
+        validate_account_terms(&terms).map_err(TranstractorError::ConfigLoad)?;
+        self.typer
+            .add_account_terms(key, &terms, terms_case_insensitive);
+        Ok(())
+    }
+
+    /// Check if a config is registered
+    pub fn has_config(&self, key: &str) -> bool {
+        self.db.has_config(key)
+    }
+
+    /// Get the registered configs
+    pub fn configs(&self) -> Result<Vec<StatementConfig>, TranstractorError> {
+        self.db
+            .get_configs()
+            .map_err(TranstractorError::ConfigAccess)
+    }
+
+    /// Get the configs of the provided keys, in order
+    pub fn configs_from_keys(
+        &self,
+        keys: &[String],
+    ) -> Result<Vec<StatementConfig>, TranstractorError> {
+        let mut configs: Vec<StatementConfig> = Vec::new();
+        for key in keys {
+            if !self.db.has_config(key) {
+                return Err(TranstractorError::ConfigAccess(format!(
+                    "Failed to get configs: Config with key '{}' is not registered",
+                    key
+                )));
+            }
+            let cfg = self.db.get_config(key).map_err(|e| {
+                TranstractorError::ConfigAccess(format!("Failed to get configs: {}", e))
+            })?;
+            configs.push(cfg);
+        }
+        Ok(configs)
+    }
+
+    /// Remove the configs prepared for parsing
+    pub fn clear_prepared_configs(&self) {
+        self.prepared_configs.clear();
+    }
+
+    /// Get the keys of the configs applicable to the text items, best match first
+    pub fn identify(&self, text_items: &Vec<TextItem>) -> Vec<String> {
+        if self.locale_filter {
+            let profile = LocaleProfile::from_text_items(text_items);
+            self.typer
+                .identify_filtered(text_items, |key| profile.is_plausible_key(key))
+        } else {
+            self.typer.identify(text_items)
+        }
+    }
+
+    /// Record the identification score of the config of each StatementData, if any of
+    /// its account terms are found in the text items
+    pub fn set_identification_scores(
+        &self,
+        text_items: &Vec<TextItem>,
+        statement_datas: &mut [StatementData],
+    ) {
+        let keys: Vec<&str> = statement_datas
+            .iter()
+            .filter_map(|data| data.key.as_deref())
+            .collect();
+        let scores = self
+            .typer
+            .scores_filtered(text_items, |key| keys.contains(&key));
+        for data in statement_datas {
+            data.identification_score = data.key.as_ref().and_then(|key| scores.get(key).copied());
+        }
+    }
+
+    /// Parse the text items with each config of the provided keys, in order, and
+    /// return all StatementData results, including those with errors. Results with a
+    /// different account number from the expected account number are not error-free.
+    pub fn parse_statement_datas(
+        &self,
+        text_items: &Vec<TextItem>,
+        keys: &[String],
+        options: &ParseOptions,
+    ) -> Result<Vec<StatementData>, TranstractorError> {
+        let mut configs = self.configs_from_keys(keys)?;
+        if let Some(tz) = &options.date_timezone {
+            validate_date_timezone(tz).map_err(TranstractorError::InvalidArgument)?;
+            for config in &mut configs {
+                config.date_timezone = tz.clone();
+            }
+        }
+        if options.strict {
+            for config in &mut configs {
+                config.transaction_strict = true;
+            }
+        }
+
+        let mut statement_data_results = text_items_to_statement_datas_prepared(
+            text_items,
+            &configs,
+            options.fixers,
+            options.retry_text_order,
+            false,
+            &self.prepared_configs,
+        )
+        .map_err(TranstractorError::Parse)?;
+        self.set_identification_scores(text_items, &mut statement_data_results);
+        // Sections of a multi-account statement each have their own account number
+        if let Some(expected) = &options.expected_account_number {
+            for data in statement_data_results
+                .iter_mut()
+                .filter(|data| data.account_section.is_none())
+            {
+                check_expected_account_number(data, expected);
+            }
+        }
+        if let Some(categorizer) = &options.categorizer {
+            for data in &mut statement_data_results {
+                categorizer.apply(data);
+            }
+        }
+        if let Some(redaction) = &options.redaction {
+            for data in &mut statement_data_results {
+                redaction.apply(data, options.expected_account_number.as_deref());
+            }
+        }
+        Ok(statement_data_results)
+    }
+
+    /// Return the first error-free StatementData. For a multi-account config, this is
+    /// the first account section, and for a document of several statements, the
+    /// earliest statement.
+    pub fn first_error_free<'a>(
+        statement_data_results: &'a [StatementData],
+        keys: &[String],
+    ) -> Result<&'a StatementData, TranstractorError> {
+        Ok(&Self::first_error_free_sections(statement_data_results, keys)?[0])
+    }
+
+    /// Return the results of the first config whose account sections or statements are
+    /// all error-free (a single result unless the config is multi_account or
+    /// multi_statement). The error summarises the best candidate (fewest errors).
+    pub fn first_error_free_sections<'a>(
+        statement_data_results: &'a [StatementData],
+        keys: &[String],
+    ) -> Result<&'a [StatementData], TranstractorError> {
+        // Results of a config are consecutive, with later sections numbered from 2
+        if let Some(sections) = statement_data_results
+            .chunk_by(|_, next| {
+                next.account_section.is_some_and(|n| n > 1)
+                    || next.statement_section.is_some_and(|n| n > 1)
+            })
+            .find(|sections| sections.iter().all(|data| data.errors.is_empty()))
+        {
+            return Ok(sections);
+        }
+
+        let best_summary = match statement_data_results
+            .iter()
+            .min_by_key(|data| data.errors.len())
+        {
+            Some(best) => format!(
+                " Best candidate: {} with {} error(s), first error: {}.",
+                best.key.clone().unwrap_or_default(),
+                best.errors.len(),
+                best.errors.first().cloned().unwrap_or_default()
+            ),
+            None => "".to_string(),
+        };
+        Err(TranstractorError::NoErrorFreeStatementData(format!(
+            "No error-free StatementData found. This indicates an error in one of the configurations: {}.{} See further details using the \"debug\" method. Please contact a developer if this config is part of the default set provided by the package.",
+            keys.join(", "),
+            best_summary
+        )))
+    }
+
+    /// Identify the configs applicable to the text items and return the first
+    /// error-free StatementData (see first_error_free)
+    pub fn parse(
+        &self,
+        text_items: &Vec<TextItem>,
+        options: &ParseOptions,
+    ) -> Result<StatementData, TranstractorError> {
+        let keys = self.identify(text_items);
+        if keys.is_empty() {
+            return Err(TranstractorError::StatementNotSupported(
+                "No registered config is applicable to the statement".to_string(),
+            ));
+        }
+        let results = self.parse_statement_datas(text_items, &keys, options)?;
+        Self::first_error_free(&results, &keys).cloned()
+    }
+
+    /// Parse layout text (see layout_to_text_items) as for parse
+    pub fn parse_layout(
+        &self,
+        layout_text: &str,
+        options: &ParseOptions,
+    ) -> Result<StatementData, TranstractorError> {
+        let text_items =
+            layout_to_text_items(layout_text).map_err(TranstractorError::LayoutParse)?;
+        self.parse(&text_items, options)
+    }
+
+    /// Identify the configs applicable to the text items and parse with each, applying
+    /// all fixers, into a debug report (see text_items_to_debug_report). If no config
+    /// is identified, the report has the account terms found and missing for every
+    /// registered key instead (see StatementTyper::explain). The results are redacted
+    /// with the redaction, if any.
+    pub fn debug(
+        &self,
+        text_items: &Vec<TextItem>,
+        retry_text_order: bool,
+        sources: bool,
+        redaction: Option<&Redaction>,
+    ) -> Result<DebugReport, TranstractorError> {
+        let keys = self.identify(text_items);
+        if keys.is_empty() {
+            let mut report = DebugReport {
+                error: None,
+                statement_datas: Vec::new(),
+                diagnostics: self.typer.explain(text_items),
+            };
+            if let Some(redaction) = redaction {
+                report.redact(redaction);
+            }
+            return Ok(report);
+        }
+        let configs = self.configs_from_keys(&keys)?;
+        let mut report = text_items_to_debug_report(
+            text_items,
+            &configs,
+            retry_text_order,
+            sources,
+            &ParseTimings::new(),
+        );
+        self.set_identification_scores(text_items, &mut report.statement_datas);
+        if let Some(redaction) = redaction {
+            report.redact(redaction);
+        }
+        Ok(report)
+    }
+
+    /// Parse the text items as for parse, including incomplete transactions, and
+    /// compare the transactions with an expected CSV (see verify_statement_data)
+    pub fn verify(
+        &self,
+        text_items: &Vec<TextItem>,
+        expected_csv: &str,
+        options: &ParseOptions,
+        verify_options: &VerifyOptions,
+    ) -> Result<VerificationReport, TranstractorError> {
+        let data = self.parse(text_items, options)?;
+        verify_statement_data(&data, expected_csv, verify_options)
+            .map_err(TranstractorError::InvalidArgument)
+    }
+
+    /// Parse the text items as for parse and return the transactions of the
+    /// statement
+    pub fn parse_transactions(
+        &self,
+        text_items: &Vec<TextItem>,
+        options: &ParseOptions,
+    ) -> Result<Vec<Transaction>, TranstractorError> {
+        self.parse(text_items, options)?
+            .proto_transactions
+            .iter()
+            .map(|tx| tx.to_transaction().map_err(TranstractorError::Parse))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::patch;
+
+    const CONFIG: &str = include_str!("../examples/data/zz__example__checking__1.json");
+    const LAYOUT: &str = include_str!("../examples/data/zz__example__checking__1_layout.txt");
+
+    #[test]
+    fn test_parse_layout_with_imported_config() {
+        let mut parser = Parser::new();
+        parser.import_config_from_json_str(CONFIG).unwrap();
+
+        let data = parser
+            .parse_layout(LAYOUT, &ParseOptions::default())
+            .unwrap();
+        let text_items = layout_to_text_items(LAYOUT).unwrap();
+        let transactions = parser
+            .parse_transactions(&text_items, &ParseOptions::default())
+            .unwrap();
+
+        assert_eq!(data.key.as_deref(), Some("zz__example__checking__1"));
+        assert_eq!(transactions.len(), 4);
+        assert_eq!(transactions[3].balance, 1553.0);
+    }
+
+    #[test]
+    fn test_errors_are_typed() {
+        let mut parser = Parser::new();
+
+        assert!(matches!(
+            parser.import_config_from_json_str("{"),
+            Err(TranstractorError::ConfigLoad(_))
+        ));
+        assert!(matches!(
+            parser.parse_layout("[\"Alpha\",1,5,7,3]", &ParseOptions::default()),
+            Err(TranstractorError::LayoutParse(_))
+        ));
+        assert!(matches!(
+            parser.parse_layout(LAYOUT, &ParseOptions::default()),
+            Err(TranstractorError::StatementNotSupported(_))
+        ));
+
+        parser.import_config_from_json_str(CONFIG).unwrap();
+        let options = ParseOptions {
+            expected_account_number: Some("999".to_string()),
+            ..ParseOptions::default()
+        };
+        assert!(matches!(
+            parser.parse_layout(LAYOUT, &options),
+            Err(TranstractorError::NoErrorFreeStatementData(_))
+        ));
+    }
+
+    #[test]
+    fn test_strict_rejects_unread_table_lines() {
+        let mut parser = Parser::new();
+        parser.import_config_from_json_str(CONFIG).unwrap();
+        let layout = patch(
+            LAYOUT,
+            &[(
+                "[\"End\",",
+                "[\"Page\",560,590,298,290][\"1\",593,599,298,290]\n[\"End\",",
+            )],
+        );
+
+        let data = parser
+            .parse_layout(&layout, &ParseOptions::default())
+            .unwrap();
+        assert_eq!(data.orphan_lines.len(), 1);
+        assert_eq!(
+            data.orphan_lines[0].to_string(),
+            "page 0 at (560, 298): \"Page 1\""
+        );
+
+        let options = ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
+        };
+        assert!(matches!(
+            parser.parse_layout(&layout, &options),
+            Err(TranstractorError::NoErrorFreeStatementData(_))
+        ));
+        assert!(parser.parse_layout(LAYOUT, &options).is_ok());
+    }
+
+    #[test]
+    fn test_debug_explains_unidentified_statement() {
+        let mut parser = Parser::new();
+        parser.import_config_from_json_str(CONFIG).unwrap();
+        let text_items = layout_to_text_items(LAYOUT).unwrap();
+
+        let report = parser.debug(&text_items, false, false, None).unwrap();
+        assert!(report.diagnostics.is_empty());
+        assert_eq!(report.statement_datas.len(), 1);
+
+        // A term in a different case in the statement is missing, with the nearest text
+        let layout = patch(
+            LAYOUT,
+            &[(
+                "[\"Example\",72,114,49,37][\"Everyday\",120,168,49,37]",
+                "[\"EXAMPLE\",72,114,49,37][\"EVERYDAY\",120,168,49,37]",
+            )],
+        );
+        let text_items = layout_to_text_items(&layout).unwrap();
+
+        let report = parser.debug(&text_items, false, false, None).unwrap();
+        assert!(report.statement_datas.is_empty());
+        let diagnostic = &report.diagnostics[0];
+        assert_eq!(diagnostic.key, "zz__example__checking__1");
+        assert_eq!(diagnostic.missing.len(), 1);
+        assert_eq!(diagnostic.missing[0].term, "Example Everyday Bank");
+        assert_eq!(
+            diagnostic.missing[0].nearest.as_deref(),
+            Some("EXAMPLE EVERYDAY Bank")
+        );
+        let mut text = String::new();
+        report.write_text(&mut text, &Default::default()).unwrap();
+        assert!(text.contains("No config identified"), "{}", text);
+    }
+
+    #[test]
+    fn test_redaction_after_checks() {
+        let mut parser = Parser::new();
+        parser.import_config_from_json_str(CONFIG).unwrap();
+        let text_items = layout_to_text_items(LAYOUT).unwrap();
+        let redaction = Redaction::new(&["(?i)salary".to_string()]).unwrap();
+
+        let options = ParseOptions {
+            redaction: Some(redaction.clone()),
+            ..ParseOptions::default()
+        };
+        let data = parser.parse(&text_items, &options).unwrap();
+        assert_eq!(data.account_number.as_deref(), Some("****2345"));
+
+        // The expected account number of a mismatch is masked too
+        let options = ParseOptions {
+            expected_account_number: Some("062-000 98765432".to_string()),
+            ..options
+        };
+        let datas = parser
+            .parse_statement_datas(&text_items, &parser.identify(&text_items), &options)
+            .unwrap();
+        assert_eq!(
+            datas[0].errors,
+            vec!["Account number mismatch: expected '****5432', found '****2345'"]
+        );
+
+        let report = parser
+            .debug(&text_items, false, true, Some(&redaction))
+            .unwrap();
+        let mut text = String::new();
+        report.write_text(&mut text, &Default::default()).unwrap();
+        assert!(text.contains("Account Number: ****2345"), "{}", text);
+        assert!(!text.contains("00012345"), "{}", text);
+    }
+
+    #[test]
+    fn test_verify_against_expected_csv() {
+        let mut parser = Parser::new();
+        parser.import_config_from_json_str(CONFIG).unwrap();
+        let text_items = layout_to_text_items(LAYOUT).unwrap();
+        let data = parser.parse(&text_items, &ParseOptions::default()).unwrap();
+        let transactions = &data.proto_transactions;
+        let mut csv = "Date,Description,Amount,Balance\n".to_string();
+        for tx in transactions {
+            csv += &format!(
+                "{},\"{}\",{},{}\n",
+                data.to_naive_date(tx.date.unwrap()).unwrap(),
+                tx.description.to_lowercase(),
+                tx.amount.unwrap(),
+                tx.balance.unwrap()
+            );
+        }
+
+        let verify = |csv: &str| {
+            parser.verify(
+                &text_items,
+                csv,
+                &ParseOptions::default(),
+                &VerifyOptions::default(),
+            )
+        };
+        assert!(verify(&csv).unwrap().passed());
+
+        let truncated: Vec<&str> = csv.lines().take(transactions.len()).collect();
+        let report = verify(&truncated.join("\n")).unwrap();
+        assert_eq!(report.extra.len(), 1);
+        assert_eq!(report.extra[0].0, transactions.len());
+        assert!(matches!(
+            verify("Notes\n"),
+            Err(TranstractorError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_categories_set_before_redaction() {
+        let mut parser = Parser::new();
+        parser.import_config_from_json_str(CONFIG).unwrap();
+        let text_items = layout_to_text_items(LAYOUT).unwrap();
+        let options = ParseOptions {
+            categorizer: Some(
+                Categorizer::new(&[("income".to_string(), "salary".to_string())], true).unwrap(),
+            ),
+            redaction: Some(Redaction::new(&["(?i)salary".to_string()]).unwrap()),
+            ..ParseOptions::default()
+        };
+
+        let transactions = parser.parse_transactions(&text_items, &options).unwrap();
+
+        assert!(transactions[0].description.contains("****"));
+        assert_eq!(transactions[0].category.as_deref(), Some("income"));
+        assert!(transactions[1..].iter().all(|tx| tx.category.is_none()));
+    }
+
+    #[test]
+    fn test_transactions_without_dates_do_not_panic() {
+        let mut parser = Parser::new();
+        let config = patch(
+            CONFIG,
+            &[(
+                "[\"date\", \"description\", \"amount\", \"balance\"]",
+                "[\"date\", \"description\", \"amount\", \"balance\"], [\"description\", \"amount\", \"balance\"]",
+            )],
+        );
+        parser.import_config_from_json_str(&config).unwrap();
+        // The second transaction has no date, and the statement period is not read,
+        // so its date cannot be filled
+        let layout = patch(
+            LAYOUT,
+            &[
+                ("[\"03/05/2024\",72,132,252,240]", ""),
+                ("\"period:\"", "\"dates:\""),
+            ],
+        );
+
+        let result = parser.parse_layout(&layout, &ParseOptions::default());
+
+        assert!(matches!(
+            result,
+            Err(TranstractorError::NoErrorFreeStatementData(_))
+        ));
+    }
+}
@@ -1,6 +1,7 @@
-use crate::formats::MultiAmountFormatParser;
+use crate::formats::{AmountHints, MultiAmountFormatParser};
 use crate::structs::TextItem;
 
+#[derive(Clone)]
 pub struct AmountParser {
     /// The current amount that has been successfully parsed
     pub value: Option<f64>,
@@ -13,9 +14,10 @@ pub struct AmountParser {
 }
 
 impl AmountParser {
-    /// Create a new AmountParser with specified format names
-    pub fn new(format_names: &[&str]) -> Self {
-        let parser = MultiAmountFormatParser::new(format_names);
+    /// Create a new AmountParser with specified format names and currency and separator
+    /// hints (see AmountHints)
+    pub fn new(format_names: &[&str], hints: &AmountHints) -> Self {
+        let parser = MultiAmountFormatParser::new(format_names, hints);
         let max_lookahead = parser.max_items();
         AmountParser {
             value: None,
@@ -56,6 +58,37 @@ impl AmountParser {
         0
     }
 
+    /// Read a standalone sign indicator ("CR", "DR", "CR." or "DR.", any case) in the
+    /// item after the consumed items of a parsed amount, if on the same page and line
+    /// (y1 within y_tol). A debit makes the amount negative, also if it was already read
+    /// as negative (e.g., "(12.00)" then "DR"). Returns 1 if an indicator was read (to be
+    /// consumed with the amount), else 0.
+    pub fn parse_sign_indicator(
+        &mut self,
+        items: &[TextItem],
+        consumed: usize,
+        y_tol: i32,
+    ) -> usize {
+        let (Some(value), Some(amount_item), Some(next)) =
+            (self.value, self.text_item.as_mut(), items.get(consumed))
+        else {
+            return 0;
+        };
+        if next.page != amount_item.page || (next.y1 - amount_item.y1).abs() > y_tol {
+            return 0;
+        }
+        let debit = match next.text.trim().to_uppercase().as_str() {
+            "DR" | "DR." => true,
+            "CR" | "CR." => false,
+            _ => return 0,
+        };
+        if debit {
+            self.value = Some(-value.abs());
+        }
+        amount_item.text = format!("{} {}", amount_item.text, next.text.trim());
+        1
+    }
+
     /// Invert the sign of the parsed amount
     pub fn invert(&mut self) {
         if let Some(val) = self.value {
@@ -84,7 +117,7 @@ mod tests {
 
     #[test]
     fn test_parse_single_item() {
-        let mut parser = AmountParser::new(&["format1"]);
+        let mut parser = AmountParser::new(&["format1"], &AmountHints::default());
         let items = vec![make_text_item("1,234.56")];
         let consumed = parser.parse_items(&items);
         assert_eq!(consumed, 1);
@@ -94,7 +127,7 @@ mod tests {
 
     #[test]
     fn test_parse_longest_first() {
-        let mut parser = AmountParser::new(&["format1"]);
+        let mut parser = AmountParser::new(&["format1"], &AmountHints::default());
         let items = vec![make_text_item("1,234"), make_text_item(".56")];
         // "1,234 .56" is not a valid format1, so should fail
         let consumed = parser.parse_items(&items);
@@ -105,7 +138,7 @@ mod tests {
 
     #[test]
     fn test_parse_longest_first_successfully() {
-        let mut parser = AmountParser::new(&["format4"]);
+        let mut parser = AmountParser::new(&["format4"], &AmountHints::default());
         let items = vec![make_text_item("1,234.56"), make_text_item("DR")];
         // "1,234.56 DR" is a valid format4, so should succeed
         let consumed = parser.parse_items(&items);
@@ -116,7 +149,7 @@ mod tests {
 
     #[test]
     fn test_parse_multiple_formats() {
-        let mut parser = AmountParser::new(&["format2", "format1"]);
+        let mut parser = AmountParser::new(&["format2", "format1"], &AmountHints::default());
         let items = vec![make_text_item("-$1,234.56")];
         let consumed = parser.parse_items(&items);
         assert_eq!(consumed, 1);
@@ -124,9 +157,45 @@ mod tests {
         assert_eq!(parser.text_item.as_ref().unwrap().text, "-$1,234.56");
     }
 
+    #[test]
+    fn test_parse_split_parentheses() {
+        let mut parser = AmountParser::new(&["format1", "format8"], &AmountHints::default());
+        // Opening parenthesis in its own item, followed by a sign indicator
+        let items = vec![
+            make_text_item("("),
+            make_text_item("$1,234.56)"),
+            make_text_item("CR"),
+        ];
+        let consumed = parser.parse_items(&items);
+        assert_eq!(consumed, 3);
+        assert_eq!(parser.value, Some(-1234.56));
+        assert_eq!(parser.text_item.as_ref().unwrap().text, "( $1,234.56) CR");
+
+        let items = vec![make_text_item("("), make_text_item("0.00)")];
+        assert_eq!(parser.parse_items(&items), 2);
+        assert_eq!(parser.value, Some(0.0));
+
+        // A debit marker after parentheses is a single negative
+        let items = vec![
+            make_text_item("("),
+            make_text_item("12.00)"),
+            make_text_item("DR"),
+        ];
+        assert_eq!(parser.parse_items(&items), 3);
+        assert_eq!(parser.value, Some(-12.0));
+        let items = vec![make_text_item("(12.00)"), make_text_item("DR")];
+        assert_eq!(parser.parse_items(&items[..1]), 1);
+        assert_eq!(parser.parse_sign_indicator(&items, 1, 0), 1);
+        assert_eq!(parser.value, Some(-12.0));
+
+        // An unbalanced parenthesis is not read as an amount
+        let items = vec![make_text_item("("), make_text_item("12.00")];
+        assert_eq!(parser.parse_items(&items), 0);
+    }
+
     #[test]
     fn test_reset() {
-        let mut parser = AmountParser::new(&["format1"]);
+        let mut parser = AmountParser::new(&["format1"], &AmountHints::default());
         let items = vec![make_text_item("1,234.56")];
         parser.parse_items(&items);
         parser.reset();
@@ -136,7 +205,7 @@ mod tests {
 
     #[test]
     fn test_invert() {
-        let mut parser = AmountParser::new(&["format1"]);
+        let mut parser = AmountParser::new(&["format1"], &AmountHints::default());
         let items = vec![make_text_item("1,234.56")];
         parser.parse_items(&items);
         parser.invert();
@@ -145,7 +214,7 @@ mod tests {
 
     #[test]
     fn test_empty_items() {
-        let mut parser = AmountParser::new(&["format1"]);
+        let mut parser = AmountParser::new(&["format1"], &AmountHints::default());
         let items: Vec<TextItem> = vec![];
         let consumed = parser.parse_items(&items);
         assert_eq!(consumed, 0);
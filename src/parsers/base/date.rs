@@ -1,7 +1,8 @@
-use crate::formats::date::MultiDateFormatParser;
+use crate::formats::date::{DateParseError, MultiDateFormatParser};
 use crate::structs::TextItem;
 
 /// DateParser: parses date strings using multiple date formats.
+#[derive(Clone)]
 pub struct DateParser {
     /// The current parsed UTC timestamp (milliseconds since epoch)
     pub value: Option<i64>,
@@ -16,7 +17,19 @@ pub struct DateParser {
 impl DateParser {
     /// Create a new DateParser with specified format names
     pub fn new(format_names: &[&str]) -> Self {
-        let parser = MultiDateFormatParser::new(format_names);
+        Self::with_month_only_default_day(format_names, "1")
+    }
+
+    /// Create a new DateParser with specified format names, with month-only dates
+    /// (format14) on the given month_only_default_day
+    pub fn with_month_only_default_day(
+        format_names: &[&str],
+        month_only_default_day: &str,
+    ) -> Self {
+        let parser = MultiDateFormatParser::with_month_only_default_day(
+            format_names,
+            month_only_default_day,
+        );
         let max_lookahead = parser.max_items();
         DateParser {
             value: None,
@@ -26,6 +39,13 @@ impl DateParser {
         }
     }
 
+    /// Set the pivot year of 2-digit years when no year is given (see
+    /// MultiDateFormatParser::with_two_digit_year_pivot)
+    pub fn with_two_digit_year_pivot(mut self, pivot: Option<u32>) -> Self {
+        self.parser = self.parser.with_two_digit_year_pivot(pivot);
+        self
+    }
+
     /// Reset the parser state
     pub fn reset(&mut self) {
         self.value = None;
@@ -33,29 +53,43 @@ impl DateParser {
     }
 
     /// Iteratively join text items and attempt to parse dates
-    /// Returns number of items consumed if successful, else 0
-    pub fn parse_items(&mut self, items: &[TextItem], year_str: &str) -> usize {
+    /// Returns number of items consumed if successful, else 0. Returns
+    /// DateParseError::MissingYear if no date was parsed but the items start with a
+    /// date without a year and year_str is empty; text_item is then set to that date
+    /// (and value left unset) so that the caller can locate it.
+    pub fn parse_items(
+        &mut self,
+        items: &[TextItem],
+        year_str: &str,
+    ) -> Result<usize, DateParseError> {
         if items.is_empty() {
-            return 0;
+            return Ok(0);
         }
         // Try longest first, then shorter
         let max = usize::min(self.max_lookahead, items.len());
+        let mut missing_year = None;
         for i in (1..=max).rev() {
-            let merged = items[0..i]
-                .iter()
-                .map(|t| t.text.as_str())
-                .collect::<Vec<_>>()
-                .join(" ");
-            if let Some(val) = self.parser.parse(&merged, year_str) {
-                self.value = Some(val);
-                self.text_item = Some(TextItem {
-                    text: merged,
-                    ..items[0].clone()
-                });
-                return i;
+            // Items on different pages are not joined
+            let Some(merged) = TextItem::from_items(&items[0..i]) else {
+                continue;
+            };
+            match self.parser.parse(&merged.text, year_str) {
+                Ok(val) => {
+                    self.value = Some(val);
+                    self.text_item = Some(merged);
+                    return Ok(i);
+                }
+                Err(DateParseError::MissingYear) if missing_year.is_none() => {
+                    missing_year = Some(merged);
+                }
+                Err(_) => {}
             }
         }
-        0
+        if let Some(merged) = missing_year {
+            self.text_item = Some(merged);
+            return Err(DateParseError::MissingYear);
+        }
+        Ok(0)
     }
 }
 
@@ -77,7 +111,7 @@ mod tests {
     fn test_parse_single_item_format1() {
         let mut parser = DateParser::new(&["format1"]);
         let items = vec![make_text_item("24 mar")];
-        let consumed = parser.parse_items(&items, "2023");
+        let consumed = parser.parse_items(&items, "2023").unwrap();
         assert_eq!(consumed, 1);
         assert!(parser.value.is_some());
         assert_eq!(parser.text_item.as_ref().unwrap().text, "24 mar");
@@ -91,17 +125,52 @@ mod tests {
             make_text_item("march"),
             make_text_item("2020"),
         ];
-        let consumed = parser.parse_items(&items, "");
+        let consumed = parser.parse_items(&items, "").unwrap();
         assert_eq!(consumed, 3);
         assert!(parser.value.is_some());
         assert_eq!(parser.text_item.as_ref().unwrap().text, "24 march 2020");
     }
 
+    #[test]
+    fn test_parse_items_weekday_prefix() {
+        let mut parser = DateParser::new(&["format1", "format15"]);
+        assert_eq!(parser.max_lookahead, 4);
+        let items = vec![
+            make_text_item("Weds"),
+            make_text_item("26"),
+            make_text_item("Mar"),
+            make_text_item("COFFEE"),
+        ];
+        let consumed = parser.parse_items(&items, "2025").unwrap();
+        assert_eq!(consumed, 3);
+        assert_eq!(parser.text_item.as_ref().unwrap().text, "Weds 26 Mar");
+
+        // The weekday after the date is consumed with it
+        let items = vec![
+            make_text_item("26"),
+            make_text_item("Mar"),
+            make_text_item("Wed"),
+            make_text_item("COFFEE"),
+        ];
+        assert_eq!(parser.parse_items(&items, "2025").unwrap(), 3);
+    }
+
+    #[test]
+    fn test_date_straddling_page_break_not_joined() {
+        let mut parser = DateParser::new(&["format1"]);
+        let mut next_page_item = make_text_item("mar");
+        next_page_item.page = 2;
+        let items = vec![make_text_item("24"), next_page_item];
+        let consumed = parser.parse_items(&items, "2023").unwrap();
+        assert_eq!(consumed, 0);
+        assert!(parser.value.is_none());
+    }
+
     #[test]
     fn test_no_match() {
         let mut parser = DateParser::new(&["format1"]);
         let items = vec![make_text_item("foo")];
-        let consumed = parser.parse_items(&items, "2023");
+        let consumed = parser.parse_items(&items, "2023").unwrap();
         assert_eq!(consumed, 0);
         assert!(parser.value.is_none());
         assert!(parser.text_item.is_none());
@@ -111,10 +180,20 @@ mod tests {
     fn test_reset() {
         let mut parser = DateParser::new(&["format1"]);
         let items = vec![make_text_item("24 mar")];
-        parser.parse_items(&items, "2023");
+        parser.parse_items(&items, "2023").unwrap();
         assert!(parser.value.is_some());
         parser.reset();
         assert!(parser.value.is_none());
         assert!(parser.text_item.is_none());
     }
+
+    #[test]
+    fn test_missing_year() {
+        let mut parser = DateParser::new(&["format1"]);
+        let items = vec![make_text_item("24 mar")];
+        let result = parser.parse_items(&items, "");
+        assert_eq!(result, Err(DateParseError::MissingYear));
+        assert!(parser.value.is_none());
+        assert_eq!(parser.text_item.as_ref().unwrap().text, "24 mar");
+    }
 }
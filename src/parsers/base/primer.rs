@@ -1,21 +1,26 @@
 use crate::structs::TextItem;
 
 /// A parser that is primed by matching terms from text items.
+#[derive(Clone)]
 pub struct ParserPrimer {
     /// Parser is ready to scan for terms
     pub primed: bool,
     /// The last successfully parsed text item
     pub text_item: Option<TextItem>,
-    /// The set of terms to match against (lowercase)
+    /// The set of terms to match against, with whitespace normalised (lowercase if
+    /// case-insensitive)
     pub terms: Vec<String>,
     /// Number of space-delimited items in the longest term
     pub max_lookahead: usize,
+    /// Match terms ignoring case
+    pub case_insensitive: bool,
 }
 
 impl ParserPrimer {
-    /// Create a new ParserPrimer with specified terms
+    /// Create a new ParserPrimer with specified terms. Runs of whitespace in terms are
+    /// matched as a single space, as text items are tokenised before matching.
     pub fn new(terms: &[&str]) -> Self {
-        let terms_vec: Vec<String> = terms.iter().map(|t| t.to_string()).collect();
+        let terms_vec: Vec<String> = terms.iter().map(|t| normalise_whitespace(t)).collect();
         let max_lookahead = terms_vec
             .iter()
             .map(|t| t.split(' ').count())
@@ -26,15 +31,26 @@ impl ParserPrimer {
             text_item: None,
             terms: terms_vec,
             max_lookahead,
+            case_insensitive: false,
         }
     }
 
+    /// Set whether terms are matched ignoring case (see terms_case_insensitive)
+    pub fn with_case_insensitive(mut self, case_insensitive: bool) -> Self {
+        if case_insensitive {
+            self.terms = self.terms.iter().map(|t| t.to_lowercase()).collect();
+        }
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
     /// Get text item, raise error if none
     pub fn text_item(&self) -> &TextItem {
         self.text_item.as_ref().expect("No text item available")
     }
 
-    /// Iteratively join text items and attempt to match terms (case sensitive)
+    /// Iteratively join text items and attempt to match terms (case sensitive unless
+    /// case_insensitive is set)
     /// Returns number of items consumed if successful, else 0
     pub fn parse_items(&mut self, items: &[TextItem]) -> usize {
         if items.is_empty() {
@@ -44,8 +60,12 @@ impl ParserPrimer {
         let max = usize::min(self.max_lookahead, items.len());
         for i in (1..=max).rev() {
             if let Some(curr_item) = TextItem::from_items(&items[0..i]) {
-                let curr_text = &curr_item.text;
-                if self.terms.iter().any(|t| t == curr_text) {
+                let curr_text = if self.case_insensitive {
+                    curr_item.text.to_lowercase()
+                } else {
+                    curr_item.text.clone()
+                };
+                if self.terms.contains(&curr_text) {
                     self.text_item = Some(curr_item);
                     self.primed = true;
                     return i;
@@ -62,6 +82,12 @@ impl ParserPrimer {
     }
 }
 
+/// Collapse runs of whitespace in a term to single spaces and trim it, matching text
+/// joined from tokenised items
+pub fn normalise_whitespace(term: &str) -> String {
+    term.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,4 +155,17 @@ mod tests {
         assert!(!parser.primed);
         assert!(parser.text_item.is_none());
     }
+
+    #[test]
+    fn test_case_insensitive_and_whitespace_match() {
+        let items = vec![make_text_item("OPENING"), make_text_item("BALANCE")];
+
+        let mut parser = ParserPrimer::new(&["Opening  Balance"]);
+        assert_eq!(parser.max_lookahead, 2);
+        assert_eq!(parser.parse_items(&items), 0);
+
+        let mut parser = ParserPrimer::new(&["Opening  Balance"]).with_case_insensitive(true);
+        assert_eq!(parser.parse_items(&items), 2);
+        assert_eq!(parser.text_item().text, "OPENING BALANCE");
+    }
 }
@@ -2,6 +2,7 @@ use crate::structs::TextItem;
 use regex::Regex;
 
 /// A parser for reading values based on regex patterns.
+#[derive(Clone)]
 pub struct ValueParser {
     /// The current account number that has been successfully parsed
     pub value: Option<String>,
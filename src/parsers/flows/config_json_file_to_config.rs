@@ -1,5 +1,5 @@
 use crate::configs::validate::validate_config;
-use crate::structs::statement_config::StatementConfig;
+use crate::structs::statement_config::{StatementConfig, TransactionColumnPosition};
 use regex::Regex;
 use serde::Deserialize;
 use std::fs;
@@ -25,7 +25,18 @@ struct StatementConfigPartial {
     account_type: Option<String>,
     account_terms: Option<Vec<String>>,
     account_examples: Option<Vec<String>>,
+    status: Option<String>,
     fix_text_order: Option<Vec<f32>>,
+    date_timezone: Option<String>,
+    two_digit_year_pivot: Option<u32>,
+    currency_symbol: Option<String>,
+    decimal_separator: Option<String>,
+    scale_tolerances: Option<bool>,
+    multi_account: Option<bool>,
+    account_section_terms: Option<Vec<String>>,
+    multi_statement: Option<bool>,
+    terms_case_insensitive: Option<bool>,
+    required_fields: Option<Vec<String>>,
 
     account_number_terms: Option<Vec<String>>,
     account_number_patterns: Option<Vec<String>>,
@@ -43,37 +54,79 @@ struct StatementConfigPartial {
     closing_balance_alignment: Option<String>,
     closing_balance_alignment_tol: Option<i32>,
     closing_balance_invert: Option<bool>,
+    closing_balance_occurrence: Option<String>,
+    closing_balance_page: Option<i32>,
+    closing_balance_exclude_terms: Option<Vec<String>>,
+    closing_balance_exclude_distance: Option<i32>,
+    balance_tolerance_cents: Option<i32>,
+    amount_outlier_multiple: Option<i32>,
 
     start_date_terms: Option<Vec<String>>,
     start_date_formats: Option<Vec<String>>,
     start_date_alignment: Option<String>,
     start_date_alignment_tol: Option<i32>,
+    end_date_terms: Option<Vec<String>>,
+    end_date_formats: Option<Vec<String>>,
+    end_date_alignment: Option<String>,
+    end_date_alignment_tol: Option<i32>,
+
+    minimum_payment_terms: Option<Vec<String>>,
+    minimum_payment_formats: Option<Vec<String>>,
+    minimum_payment_alignment: Option<String>,
+    minimum_payment_alignment_tol: Option<i32>,
+
+    payment_due_date_terms: Option<Vec<String>>,
+    payment_due_date_formats: Option<Vec<String>>,
+    payment_due_date_alignment: Option<String>,
+    payment_due_date_alignment_tol: Option<i32>,
 
     transaction_terms: Option<Vec<String>>,
     transaction_terms_stop: Option<Vec<String>>,
+    transaction_terms_stop_scope: Option<String>,
+    no_transaction_terms: Option<Vec<String>>,
     transaction_formats: Option<Vec<Vec<String>>>,
+    transaction_layout: Option<String>,
     transaction_new_line_tol: Option<i32>,
     transaction_start_date_required: Option<bool>,
     transaction_alignment_tol: Option<i32>,
+    transaction_columns_by_position: Option<Vec<TransactionColumnPosition>>,
+    drop_duplicate_transactions: Option<bool>,
+    transaction_summary_exclude: Option<Vec<String>>,
+    ditto_tokens: Option<Vec<String>>,
+    transaction_strict: Option<bool>,
+    transaction_orphan_lines_max: Option<i32>,
 
     transaction_date_formats: Option<Vec<String>>,
     transaction_date_headers: Option<Vec<String>>,
     transaction_date_alignment: Option<String>,
+    transaction_date_alignment_tol: Option<i32>,
+    month_only_default_day: Option<serde_json::Value>,
 
     transaction_description_headers: Option<Vec<String>>,
     transaction_description_alignment: Option<String>,
+    transaction_description_alignment_tol: Option<i32>,
     transaction_description_exclude: Option<Vec<String>>,
+    transaction_description_allows_dates: Option<bool>,
+    transaction_description_allows_amounts: Option<bool>,
+    transaction_description_optional: Option<bool>,
+    transaction_description_placeholder: Option<String>,
+    transaction_description_multiline: Option<bool>,
+    transaction_description_continuation_max_lines: Option<i32>,
 
     transaction_amount_formats: Option<Vec<String>>,
     transaction_amount_headers: Option<Vec<String>>,
     transaction_amount_alignment: Option<String>,
+    transaction_amount_alignment_tol: Option<i32>,
     transaction_amount_invert_headers: Option<Vec<String>>,
     transaction_amount_invert_alignment: Option<String>,
     transaction_amount_invert: Option<bool>,
+    transaction_debit_headers: Option<Vec<String>>,
+    transaction_credit_headers: Option<Vec<String>>,
 
     transaction_balance_formats: Option<Vec<String>>,
     transaction_balance_headers: Option<Vec<String>>,
     transaction_balance_alignment: Option<String>,
+    transaction_balance_alignment_tol: Option<i32>,
     transaction_balance_invert: Option<bool>,
 }
 
@@ -103,7 +156,18 @@ pub fn from_json_str(src: &str) -> Result<StatementConfig, String> {
     overlay!(account_type);
     overlay!(account_terms);
     overlay!(account_examples);
+    overlay!(status);
     overlay!(fix_text_order);
+    overlay!(date_timezone);
+    cfg.two_digit_year_pivot = partial.two_digit_year_pivot;
+    overlay!(currency_symbol);
+    overlay!(decimal_separator);
+    overlay!(scale_tolerances);
+    overlay!(multi_account);
+    overlay!(account_section_terms);
+    overlay!(multi_statement);
+    overlay!(terms_case_insensitive);
+    overlay!(required_fields);
 
     overlay!(account_number_terms);
     if let Some(patterns) = partial.account_number_patterns {
@@ -123,42 +187,99 @@ pub fn from_json_str(src: &str) -> Result<StatementConfig, String> {
     overlay!(closing_balance_alignment);
     overlay!(closing_balance_alignment_tol);
     overlay!(closing_balance_invert);
+    overlay!(closing_balance_occurrence);
+    cfg.closing_balance_page = partial.closing_balance_page;
+    overlay!(closing_balance_exclude_terms);
+    overlay!(closing_balance_exclude_distance);
+    overlay!(balance_tolerance_cents);
+    overlay!(amount_outlier_multiple);
 
     overlay!(start_date_terms);
     overlay!(start_date_formats);
     overlay!(start_date_alignment);
     overlay!(start_date_alignment_tol);
+    overlay!(end_date_terms);
+    overlay!(end_date_formats);
+    overlay!(end_date_alignment);
+    overlay!(end_date_alignment_tol);
+
+    overlay!(minimum_payment_terms);
+    overlay!(minimum_payment_formats);
+    overlay!(minimum_payment_alignment);
+    overlay!(minimum_payment_alignment_tol);
+
+    overlay!(payment_due_date_terms);
+    overlay!(payment_due_date_formats);
+    overlay!(payment_due_date_alignment);
+    overlay!(payment_due_date_alignment_tol);
 
     overlay!(transaction_terms);
     overlay!(transaction_terms_stop);
+    overlay!(transaction_terms_stop_scope);
+    overlay!(no_transaction_terms);
     overlay!(transaction_formats);
+    overlay!(transaction_layout);
     overlay!(transaction_new_line_tol);
     overlay!(transaction_start_date_required);
     overlay!(transaction_alignment_tol);
+    overlay!(transaction_columns_by_position);
+    overlay!(drop_duplicate_transactions);
+    if let Some(summary_patterns) = partial.transaction_summary_exclude {
+        cfg.transaction_summary_exclude = compile_regex_vec(summary_patterns)?;
+    }
+    overlay!(ditto_tokens);
+    overlay!(transaction_strict);
+    overlay!(transaction_orphan_lines_max);
 
     overlay!(transaction_date_formats);
     overlay!(transaction_date_headers);
     overlay!(transaction_date_alignment);
+    cfg.transaction_date_alignment_tol = partial.transaction_date_alignment_tol;
+    // Accept a day number (e.g., 1) or a string (e.g., "last")
+    if let Some(day) = partial.month_only_default_day {
+        cfg.month_only_default_day = match day {
+            serde_json::Value::Number(n) => n.to_string(),
+            serde_json::Value::String(s) => s,
+            other => {
+                return Err(format!(
+                    "Config validation error: Invalid month_only_default_day: {}. Must be a day of the month (1-31) or \"last\".",
+                    other
+                ));
+            }
+        };
+    }
 
     overlay!(transaction_description_headers);
     overlay!(transaction_description_alignment);
+    cfg.transaction_description_alignment_tol = partial.transaction_description_alignment_tol;
 
     if let Some(ex_patterns) = partial.transaction_description_exclude {
         cfg.transaction_description_exclude = compile_regex_vec(ex_patterns)?;
     }
+    overlay!(transaction_description_allows_dates);
+    overlay!(transaction_description_allows_amounts);
+    overlay!(transaction_description_optional);
+    overlay!(transaction_description_placeholder);
+    overlay!(transaction_description_multiline);
+    overlay!(transaction_description_continuation_max_lines);
 
     overlay!(transaction_amount_formats);
     overlay!(transaction_amount_headers);
     overlay!(transaction_amount_alignment);
+    cfg.transaction_amount_alignment_tol = partial.transaction_amount_alignment_tol;
     overlay!(transaction_amount_invert_headers);
     overlay!(transaction_amount_invert_alignment);
     overlay!(transaction_amount_invert);
+    overlay!(transaction_debit_headers);
+    overlay!(transaction_credit_headers);
 
     overlay!(transaction_balance_formats);
     overlay!(transaction_balance_headers);
     overlay!(transaction_balance_alignment);
+    cfg.transaction_balance_alignment_tol = partial.transaction_balance_alignment_tol;
     overlay!(transaction_balance_invert);
 
     validate_config(&cfg).map_err(|e| format!("Config validation error: {}", e))?;
+    cfg.config_hash = Some(cfg.fields_hash());
     Ok(cfg)
 }
@@ -0,0 +1,248 @@
+use crate::checkers::check_statement_data;
+use crate::configs::validate::utils::validate_date_formats;
+use crate::fixers::fix_statement_data;
+use crate::formats::MultiDateFormatParser;
+use crate::parsers::flows::statement_data_corrections::read_csv_records;
+use crate::structs::{ProtoTransaction, StatementConfig, StatementData};
+use std::fs;
+
+/// Column mapping and statement values of a bank CSV export (see
+/// csv_str_to_statement_data)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvImport {
+    /// Key recorded on the statement data
+    pub key: String,
+    /// Header of the transaction date column
+    pub date_column: String,
+    /// Header of the transaction description column
+    pub description_column: String,
+    /// Header of the transaction amount column
+    pub amount_column: String,
+    /// Header of the balance column, or empty if the export has no balances
+    pub balance_column: String,
+    /// Names of the date formats (see formats::date) of the date column
+    pub date_formats: Vec<String>,
+    pub account_number: Option<String>,
+    /// Opening balance, required if the export has no balances. Otherwise inferred
+    /// from the first balance and amount if not given.
+    pub opening_balance: Option<f64>,
+    /// Closing balance. The balance after the last transaction if not given.
+    pub closing_balance: Option<f64>,
+}
+
+impl Default for CsvImport {
+    fn default() -> Self {
+        Self {
+            key: "csv".to_string(),
+            date_column: "Date".to_string(),
+            description_column: "Description".to_string(),
+            amount_column: "Amount".to_string(),
+            balance_column: "Balance".to_string(),
+            date_formats: vec!["format13".to_string(), "format4".to_string()],
+            account_number: None,
+            opening_balance: None,
+            closing_balance: None,
+        }
+    }
+}
+
+/// Read a bank CSV export into statement data (see csv_str_to_statement_data)
+pub fn csv_to_statement_data(csv_path: &str, import: &CsvImport) -> Result<StatementData, String> {
+    let csv_str = fs::read_to_string(csv_path)
+        .map_err(|e| format!("Failed reading CSV {}: {}", csv_path, e))?;
+    csv_str_to_statement_data(&csv_str, import)
+}
+
+/// Read a bank CSV export into statement data, then apply all fixers and checkers as
+/// for a parsed statement. Each row is a transaction, with the columns and date
+/// formats of the import. Amounts and balances are decimal numbers, optionally with
+/// thousands commas; empty balances are computed from the opening balance (see
+/// fix_implicit_balances). Exports listing the newest transaction first are reversed.
+/// The statement period runs from the first to the last transaction date. Fails if a
+/// column is missing or a value cannot be read.
+pub fn csv_str_to_statement_data(
+    csv_str: &str,
+    import: &CsvImport,
+) -> Result<StatementData, String> {
+    validate_date_formats(&import.date_formats)?;
+    let records = read_csv_records(csv_str).map_err(|e| format!("Invalid CSV: {}", e))?;
+    let Some((header, rows)) = records.split_first() else {
+        return Err("CSV is empty".to_string());
+    };
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h.trim() == name)
+            .ok_or(format!("Column '{}' not found in CSV", name))
+    };
+    let date_column = column(&import.date_column)?;
+    let description_column = column(&import.description_column)?;
+    let amount_column = column(&import.amount_column)?;
+    let balance_column = match import.balance_column.as_str() {
+        "" => None,
+        name => Some(column(name)?),
+    };
+
+    let format_names: Vec<&str> = import.date_formats.iter().map(String::as_str).collect();
+    let date_parser = MultiDateFormatParser::new(&format_names);
+    let mut transactions = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        // Rows are numbered as in a spreadsheet, after the header
+        let row_number = i + 2;
+        let cell = |col: usize| row.get(col).map(|value| value.trim()).unwrap_or("");
+        let mut tx = ProtoTransaction::new();
+        let date = date_parser
+            .parse(cell(date_column), "")
+            .map_err(|e| format!("Row {}: {} '{}'", row_number, e, cell(date_column)))?;
+        tx.set_date(date);
+        tx.description = cell(description_column).to_string();
+        tx.set_amount(parse_csv_amount(cell(amount_column), row_number)?);
+        if let Some(col) = balance_column
+            && !cell(col).is_empty()
+        {
+            tx.set_balance(parse_csv_amount(cell(col), row_number)?);
+        }
+        transactions.push(tx);
+    }
+    if let (Some(first), Some(last)) = (transactions.first(), transactions.last())
+        && first.date > last.date
+    {
+        transactions.reverse();
+    }
+
+    let mut data = StatementData::new();
+    data.set_key(import.key.clone());
+    if let Some(account_number) = &import.account_number {
+        data.set_account_number(account_number.clone());
+    }
+    if let (Some(first), Some(last)) = (transactions.first(), transactions.last()) {
+        data.set_start_date(first.date.unwrap_or_default());
+        data.set_end_date(last.date.unwrap_or_default());
+    }
+    let opening_balance = import.opening_balance.or_else(|| {
+        let first = transactions.first()?;
+        Some(first.balance? - first.amount?)
+    });
+    if let Some(balance) = opening_balance {
+        data.set_opening_balance(balance);
+    }
+    for tx in transactions {
+        data.add_proto_transaction(tx);
+    }
+
+    let config = StatementConfig {
+        key: import.key.clone(),
+        ..StatementConfig::default()
+    };
+    fix_statement_data(&mut data, &config);
+    let closing_balance = import.closing_balance.or_else(|| {
+        data.proto_transactions
+            .last()
+            .map_or(opening_balance, |tx| tx.balance)
+    });
+    if let Some(balance) = closing_balance {
+        data.set_closing_balance(balance);
+    }
+    check_statement_data(&mut data, &config);
+    Ok(data)
+}
+
+/// Parse a CSV amount or balance: a decimal number, optionally with thousands commas
+pub(crate) fn parse_csv_amount(value: &str, row_number: usize) -> Result<f64, String> {
+    value
+        .replace(',', "")
+        .parse::<f64>()
+        .map_err(|_| format!("Row {}: invalid amount '{}'", row_number, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::patch;
+
+    const CSV: &str = "\
+Date,Description,Amount,Balance
+2024-04-02,SALARY ACME PTY LTD,250.00,\"1,250.00\"
+2024-04-03,\"RENT, APRIL\",-120.00,\"1,130.00\"
+2024-04-05,GROCERIES,-24.50,\"1,105.50\"
+";
+
+    fn import() -> CsvImport {
+        CsvImport {
+            account_number: Some("062-000 1234 5678".to_string()),
+            ..CsvImport::default()
+        }
+    }
+
+    #[test]
+    fn test_csv_with_balances() {
+        let data = csv_str_to_statement_data(CSV, &import()).unwrap();
+
+        assert!(data.errors.is_empty(), "{:?}", data.errors);
+        assert_eq!(data.key.as_deref(), Some("csv"));
+        assert_eq!(data.opening_balance, Some(1000.0));
+        assert_eq!(data.closing_balance, Some(1105.5));
+        assert_eq!(data.start_date, Some(1712016000000)); // 2024-04-02
+        assert_eq!(data.proto_transactions.len(), 3);
+        assert_eq!(data.proto_transactions[1].description, "RENT, APRIL");
+        assert_eq!(data.proto_transactions[1].amount, Some(-120.0));
+    }
+
+    #[test]
+    fn test_csv_without_balances_uses_opening_balance() {
+        let csv = "\
+Posted,Details,Value
+05/04/2024,GROCERIES,-24.50
+03/04/2024,RENT,-120.00
+02/04/2024,SALARY,250.00
+";
+        let import = CsvImport {
+            date_column: "Posted".to_string(),
+            description_column: "Details".to_string(),
+            amount_column: "Value".to_string(),
+            balance_column: String::new(),
+            opening_balance: Some(1000.0),
+            ..import()
+        };
+
+        let data = csv_str_to_statement_data(csv, &import).unwrap();
+
+        assert!(data.errors.is_empty(), "{:?}", data.errors);
+        let balances: Vec<Option<f64>> = data
+            .proto_transactions
+            .iter()
+            .map(|tx| tx.balance)
+            .collect();
+        assert_eq!(balances, vec![Some(1250.0), Some(1130.0), Some(1105.5)]);
+        assert!(data.proto_transactions[0].balance_computed);
+        assert_eq!(data.closing_balance, Some(1105.5));
+    }
+
+    #[test]
+    fn test_csv_balance_mismatch_is_checked() {
+        let import = CsvImport {
+            closing_balance: Some(1100.0),
+            ..import()
+        };
+        let data = csv_str_to_statement_data(CSV, &import).unwrap();
+        assert_eq!(data.errors.len(), 1, "{:?}", data.errors);
+    }
+
+    #[test]
+    fn test_csv_errors() {
+        let import = import();
+        assert_eq!(
+            csv_str_to_statement_data("Date,Description,Amount\n", &import).unwrap_err(),
+            "Column 'Balance' not found in CSV"
+        );
+        assert_eq!(
+            csv_str_to_statement_data(&patch(CSV, &[("-120.00", "-120.OO")]), &import).unwrap_err(),
+            "Row 3: invalid amount '-120.OO'"
+        );
+        assert_eq!(
+            csv_str_to_statement_data(&patch(CSV, &[("2024-04-03", "3 Apr")]), &import)
+                .unwrap_err(),
+            "Row 3: Invalid date '3 Apr'"
+        );
+    }
+}
@@ -1,33 +1,23 @@
 use crate::structs::TextItem;
 
 fn parse_quoted_text(input: &str) -> Option<(String, usize)> {
-    let bytes = input.as_bytes();
-    if bytes.first() != Some(&b'"') {
+    if !input.starts_with('"') {
         return None;
     }
 
-    let mut cursor = 1;
     let mut escaped = false;
     let mut text = String::new();
 
-    while cursor < bytes.len() {
-        match bytes[cursor] {
-            b'\\' if !escaped => escaped = true,
-            b'"' if !escaped => return Some((text, cursor + 1)),
-            b'\\' => {
-                text.push('\\');
-                escaped = false;
-            }
+    // Read by character, so that multi-byte text (e.g., "〃" or "£") is kept whole
+    for (cursor, c) in input.char_indices().skip(1) {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some((text, cursor + 1)),
             other => {
-                if escaped {
-                    text.push(other as char);
-                    escaped = false;
-                } else {
-                    text.push(other as char);
-                }
+                text.push(other);
+                escaped = false;
             }
         }
-        cursor += 1;
     }
 
     None
@@ -35,14 +25,15 @@ fn parse_quoted_text(input: &str) -> Option<(String, usize)> {
 
 fn parse_layout_item(input: &str) -> Option<(String, i32, i32, i32, i32, usize)> {
     let input = input.trim_start();
-    let end = input.find(']')?;
-    let block = &input[..end + 1];
-    let contents = &block[1..block.len() - 1];
-
-    let trimmed_contents = contents.trim_start();
-    let (text, text_len) = parse_quoted_text(trimmed_contents)?;
-    let remainder = &trimmed_contents[text_len..].trim_start();
-    let remainder = remainder.trim_start_matches(',').trim_start();
+    let contents = input.strip_prefix('[')?;
+    let text_start = contents.len() - contents.trim_start().len();
+    // The text is read before looking for the closing bracket, so that quotes and
+    // brackets within it do not end the item
+    let (text, text_len) = parse_quoted_text(&contents[text_start..])?;
+    let remainder_start = 1 + text_start + text_len;
+    let end = remainder_start + input[remainder_start..].find(']')?;
+    let remainder = input[remainder_start..end].trim_start();
+    let remainder = remainder.strip_prefix(',')?;
 
     let values: Vec<i32> = remainder
         .split(',')
@@ -58,10 +49,33 @@ fn parse_layout_item(input: &str) -> Option<(String, i32, i32, i32, i32, usize)>
     Some((text, values[0], values[2], values[1], values[3], end + 1))
 }
 
-/// Converts layout text format to a collection of TextItems
+/// Describe the first line of a text item for an error message
+fn item_excerpt(input: &str) -> String {
+    input
+        .lines()
+        .next()
+        .unwrap_or("")
+        .chars()
+        .take(40)
+        .collect()
+}
+
+/// Converts layout text format to a collection of TextItems. Comment lines starting
+/// with "#" (e.g., the layout header) are skipped wherever they appear. Text is
+/// quoted, with quotes and backslashes within it escaped by a backslash (as written
+/// by text_items_to_layout). Returns an error for a malformed text item (e.g., a
+/// truncated item or missing coordinate), for an item whose x2 is less than its x1
+/// or whose top (y2) is below its bottom (y1), for an invalid page header and for
+/// an item before the first "[Page N]" header.
 pub fn layout_to_text_items(layout_text: &str) -> Result<Vec<TextItem>, String> {
+    let layout_text = layout_text
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let layout_text = layout_text.as_str();
     let mut text_items: Vec<TextItem> = Vec::new();
-    let mut current_page = 0;
+    let mut current_page = None;
     let mut cursor = 0;
 
     while cursor < layout_text.len() {
@@ -72,18 +86,51 @@ pub fn layout_to_text_items(layout_text: &str) -> Result<Vec<TextItem>, String>
         if trimmed.starts_with("[Page") {
             let page_end = trimmed.find(']').unwrap_or(trimmed.len());
             let page_text = trimmed["[Page".len()..page_end].trim();
-            if let Ok(page) = page_text.parse::<i32>() {
-                current_page = page;
+            match page_text.parse::<i32>() {
+                Ok(page) => current_page = Some(page),
+                Err(_) => {
+                    return Err(format!("Invalid page header: {}", item_excerpt(trimmed)));
+                }
             }
             cursor = trimmed_start + page_end + 1;
             continue;
         }
 
         if let Some((text, x1, y1, x2, y2, consumed)) = parse_layout_item(trimmed) {
-            text_items.push(TextItem::new(text, x1, y1, x2, y2, current_page));
+            let Some(page) = current_page else {
+                return Err(format!(
+                    "Text item before the first [Page N] header: {}",
+                    item_excerpt(trimmed)
+                ));
+            };
+            if x2 < x1 {
+                return Err(format!(
+                    "Invalid text item on page {}: {} (x2 {} is less than x1 {})",
+                    page,
+                    item_excerpt(trimmed),
+                    x2,
+                    x1
+                ));
+            }
+            if y2 > y1 {
+                return Err(format!(
+                    "Invalid text item on page {}: {} (top y2 {} is below bottom y1 {})",
+                    page,
+                    item_excerpt(trimmed),
+                    y2,
+                    y1
+                ));
+            }
+            text_items.push(TextItem::new(text, x1, y1, x2, y2, page));
             cursor = trimmed_start + consumed;
+        } else if trimmed.starts_with('[') {
+            return Err(format!(
+                "Invalid text item on page {}: {}",
+                current_page.unwrap_or_default(),
+                item_excerpt(trimmed)
+            ));
         } else {
-            cursor += 1;
+            cursor = trimmed_start + trimmed.chars().next().map_or(1, char::len_utf8);
         }
     }
 
@@ -93,13 +140,15 @@ pub fn layout_to_text_items(layout_text: &str) -> Result<Vec<TextItem>, String>
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parsers::flows::text_items_to_layout::text_items_to_layout;
+    use crate::parsers::flows::text_items_to_layout::{
+        text_items_to_layout, text_items_to_layout_with_header,
+    };
 
     #[test]
     fn round_trips_layout_text_back_to_text_items() {
         let items = vec![
-            TextItem::new("Alpha".to_string(), 1, 3, 5, 7, 0),
-            TextItem::new("Beta".to_string(), 8, 10, 12, 14, 1),
+            TextItem::new("Alpha".to_string(), 1, 7, 5, 3, 0),
+            TextItem::new("Beta".to_string(), 8, 14, 12, 10, 1),
         ];
 
         let layout = text_items_to_layout(&items, 10.0, 1.0).unwrap();
@@ -107,4 +156,139 @@ mod tests {
 
         assert_eq!(parsed, items);
     }
+
+    #[test]
+    fn skips_comment_lines_anywhere() {
+        let layout = "# header\n[Page 0]\n[\"Alpha\",1,5,7,3]\n  # [\"Hidden\",1,5,7,3]\n[Page 1]\n#\n[\"Beta\",8,12,14,10]";
+        let parsed = layout_to_text_items(layout).unwrap();
+
+        assert_eq!(
+            parsed,
+            vec![
+                TextItem::new("Alpha".to_string(), 1, 7, 5, 3, 0),
+                TextItem::new("Beta".to_string(), 8, 14, 12, 10, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trips_layout_text_with_header() {
+        let items = vec![
+            TextItem::new("Alpha".to_string(), 1, 7, 5, 3, 0),
+            TextItem::new("Beta".to_string(), 8, 14, 12, 10, 1),
+        ];
+
+        let layout = text_items_to_layout_with_header(&items, 0.0, 0.0, true).unwrap();
+        let parsed = layout_to_text_items(&layout).unwrap();
+
+        assert!(layout.starts_with("# transtractor layout text\n"));
+        assert_eq!(parsed, items);
+    }
+
+    #[test]
+    fn errors_on_malformed_item() {
+        let layout = "[Page 0]\n[\"Alpha\",1,5,7,3][\"Beta\",8,12,10]";
+
+        let error = layout_to_text_items(layout).unwrap_err();
+
+        assert_eq!(error, "Invalid text item on page 0: [\"Beta\",8,12,10]");
+    }
+
+    #[test]
+    fn reads_quotes_and_brackets_within_text() {
+        let layout = r#"[Page 0]
+["Say \"hi\"",1,5,7,3]["a][\"b\"],[c",8,12,7,3]["back\\slash",13,20,7,3]"#;
+
+        let parsed = layout_to_text_items(layout).unwrap();
+
+        assert_eq!(
+            parsed
+                .iter()
+                .map(|item| item.text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Say \"hi\"", "a][\"b\"],[c", "back\\slash"]
+        );
+        assert_eq!(parsed[1].x1, 8);
+    }
+
+    #[test]
+    fn errors_on_reversed_coordinates() {
+        let x_error = layout_to_text_items("[Page 2]\n[\"Alpha\",5,1,7,3]").unwrap_err();
+        let y_error = layout_to_text_items("[Page 2]\n[\"Alpha\",1,5,3,7]").unwrap_err();
+
+        assert_eq!(
+            x_error,
+            "Invalid text item on page 2: [\"Alpha\",5,1,7,3] (x2 1 is less than x1 5)"
+        );
+        assert_eq!(
+            y_error,
+            "Invalid text item on page 2: [\"Alpha\",1,5,3,7] (top y2 7 is below bottom y1 3)"
+        );
+    }
+
+    #[test]
+    fn errors_on_missing_or_invalid_page_header() {
+        let missing = layout_to_text_items("[\"Alpha\",1,5,7,3]\n[Page 0]").unwrap_err();
+        let invalid = layout_to_text_items("[Page one]\n[\"Alpha\",1,5,7,3]").unwrap_err();
+
+        assert_eq!(
+            missing,
+            "Text item before the first [Page N] header: [\"Alpha\",1,5,7,3]"
+        );
+        assert_eq!(invalid, "Invalid page header: [Page one]");
+        assert_eq!(layout_to_text_items("# header only\n").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn round_trips_randomised_text_items() {
+        // Characters that the layout format must escape or keep within the text
+        const CHARS: &[char] = &[
+            'a', 'Z', '0', ' ', '"', '\\', '[', ']', ',', '#', '$', '-', '.', '£', '〃', 'é',
+        ];
+        // xorshift64, seeded for reproducible inputs
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut next = |bound: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % bound
+        };
+
+        for _ in 0..200 {
+            let mut items: Vec<TextItem> = (0..1 + next(12))
+                .map(|_| {
+                    let text: String = (0..1 + next(10))
+                        .map(|_| CHARS[next(CHARS.len() as u64) as usize])
+                        .collect();
+                    let x1 = next(1200) as i32 - 100;
+                    let y2 = next(1200) as i32 - 100;
+                    TextItem::new(
+                        text,
+                        x1,
+                        y2 + next(40) as i32,
+                        x1 + next(300) as i32,
+                        y2,
+                        next(4) as i32,
+                    )
+                })
+                .collect();
+
+            let layout = text_items_to_layout(&items, 0.0, 0.0).unwrap();
+            let mut parsed = layout_to_text_items(&layout).unwrap();
+
+            let key = |item: &TextItem| {
+                (
+                    item.page,
+                    item.y1,
+                    item.y2,
+                    item.x1,
+                    item.x2,
+                    item.text.clone(),
+                )
+            };
+            items.sort_by_key(key);
+            parsed.sort_by_key(key);
+            assert_eq!(parsed, items, "{}", layout);
+        }
+    }
 }
@@ -1,6 +1,14 @@
 pub mod config_json_file_to_config;
+pub mod csv_to_statement_data;
 pub mod layout_to_text_items;
+pub mod statement_data_corrections;
+pub mod statement_data_diff;
+pub mod statement_data_to_json;
+pub mod statement_data_verification;
 pub mod text_items_to_debug;
+pub mod text_items_to_explanation;
 pub mod text_items_to_layout;
 pub mod text_items_to_statement_data;
 pub mod text_items_to_statement_datas;
+pub mod text_items_to_transactions;
+pub mod try_config;
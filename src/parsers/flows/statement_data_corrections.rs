@@ -0,0 +1,437 @@
+use crate::checkers::check_transaction_values;
+use crate::fixers::fix_statement_data_safe;
+use crate::parsers::flows::statement_data_diff::{FieldChange, multiset_difference};
+use crate::parsers::flows::statement_data_to_json::{
+    statement_data_from_json_value, statement_data_to_json,
+};
+use crate::structs::{Money, StatementData};
+use chrono::NaiveDate;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+
+/// Transaction values that can be corrected, by CSV column
+const CORRECTED_FIELDS: [&str; 3] = ["description", "amount", "balance"];
+
+/// Corrections applied to a transaction from a CSV row
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionCorrection {
+    /// 1-based row of the CSV, excluding the header row
+    pub row: usize,
+    /// 1-based number of the transaction in the statement data
+    pub number: usize,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Summary of the corrections applied from a CSV (see apply_csv_corrections)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CorrectionSummary {
+    pub corrections: Vec<TransactionCorrection>,
+    /// 1-based rows of the CSV not matching any transaction by date and date index
+    pub unmatched_rows: Vec<usize>,
+    /// Errors of the transaction checkers after the corrections, not before
+    pub errors_added: Vec<String>,
+    /// Errors of the transaction checkers before the corrections, not after
+    pub errors_removed: Vec<String>,
+}
+
+impl CorrectionSummary {
+    /// Get the summary as a JSON value, e.g., for conversion to a Python dict
+    pub fn to_value(&self) -> Value {
+        json!({
+            "corrections": self
+                .corrections
+                .iter()
+                .map(|c| json!({
+                    "row": c.row,
+                    "number": c.number,
+                    "changes": c
+                        .changes
+                        .iter()
+                        .map(|change| json!({
+                            "field": change.field,
+                            "old": change.old,
+                            "new": change.new,
+                        }))
+                        .collect::<Vec<Value>>(),
+                }))
+                .collect::<Vec<Value>>(),
+            "unmatched_rows": self.unmatched_rows,
+            "errors_added": self.errors_added,
+            "errors_removed": self.errors_removed,
+        })
+    }
+}
+
+impl fmt::Display for CorrectionSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.corrections.is_empty() {
+            writeln!(f, "No corrections applied")?;
+        }
+        for correction in &self.corrections {
+            let changes: Vec<String> = correction
+                .changes
+                .iter()
+                .map(|c| format!("{}: {} -> {}", c.field, c.old, c.new))
+                .collect();
+            writeln!(
+                f,
+                "Row {}: transaction {} corrected: {}",
+                correction.row,
+                correction.number,
+                changes.join(", ")
+            )?;
+        }
+        for row in &self.unmatched_rows {
+            writeln!(f, "Row {}: no matching transaction", row)?;
+        }
+        for (label, messages) in [
+            ("Error removed", &self.errors_removed),
+            ("Error added", &self.errors_added),
+        ] {
+            for message in messages {
+                writeln!(f, "{}: {}", label, message)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Apply the corrections of a CSV file to statement data (see
+/// apply_csv_str_corrections)
+pub fn apply_csv_corrections(
+    data: &mut StatementData,
+    csv_path: &str,
+) -> Result<CorrectionSummary, String> {
+    let csv_str = fs::read_to_string(csv_path)
+        .map_err(|e| format!("Failed reading corrections CSV {}: {}", csv_path, e))?;
+    apply_csv_str_corrections(data, &csv_str)
+}
+
+/// Apply the corrections of a CSV (e.g., exported with StatementData.to_csv, then
+/// edited) to statement data. Rows are matched to transactions by their "date"
+/// (YYYY-MM-DD) and "date_index" columns, or by their order within each date if there
+/// is no "date_index" column. Changed "description", "amount" and "balance" values
+/// are applied and flagged as corrected; empty cells are left unchanged. The safe
+/// fixers are then rerun, and the errors of the transaction checkers are replaced by
+/// those of the corrected transactions.
+pub fn apply_csv_str_corrections(
+    data: &mut StatementData,
+    csv_str: &str,
+) -> Result<CorrectionSummary, String> {
+    let records =
+        read_csv_records(csv_str).map_err(|e| format!("Invalid corrections CSV: {}", e))?;
+    let Some((header, rows)) = records.split_first() else {
+        return Err("Corrections CSV is empty".to_string());
+    };
+    let column = |name: &str| header.iter().position(|h| h.trim() == name);
+    let date_column = column("date").ok_or("Column 'date' not found in corrections CSV")?;
+    let index_column = column("date_index");
+    let value_columns: Vec<(&str, usize)> = CORRECTED_FIELDS
+        .iter()
+        .filter_map(|field| column(field).map(|col| (*field, col)))
+        .collect();
+    if value_columns.is_empty() {
+        return Err(
+            "Corrections CSV has none of the columns 'description', 'amount' or 'balance'"
+                .to_string(),
+        );
+    }
+
+    let mut positions: HashMap<(NaiveDate, usize), usize> = HashMap::new();
+    for (position, tx) in data.proto_transactions.iter().enumerate() {
+        if let Some(date) = tx.date.and_then(|ms| data.to_naive_date(ms)) {
+            positions.insert((date, tx.index), position);
+        }
+    }
+    // Errors of the transaction checkers before the corrections, to be replaced
+    let mut checked = data.clone();
+    checked.errors.clear();
+    check_transaction_values(&mut checked);
+    let stale_errors = checked.errors;
+
+    let mut summary = CorrectionSummary::default();
+    let mut date_counts: HashMap<NaiveDate, usize> = HashMap::new();
+    for (n, record) in rows.iter().enumerate() {
+        let row = n + 1;
+        let cell = |col: usize| record.get(col).map(|s| s.trim()).unwrap_or_default();
+        let date = NaiveDate::parse_from_str(cell(date_column), "%Y-%m-%d")
+            .map_err(|e| format!("Invalid row {} date '{}': {}", row, cell(date_column), e))?;
+        let index = match index_column {
+            Some(col) => cell(col)
+                .parse::<usize>()
+                .map_err(|e| format!("Invalid row {} date_index '{}': {}", row, cell(col), e))?,
+            None => {
+                let count = date_counts.entry(date).or_insert(0);
+                *count += 1;
+                *count - 1
+            }
+        };
+        let Some(&position) = positions.get(&(date, index)) else {
+            summary.unmatched_rows.push(row);
+            continue;
+        };
+
+        let tx = &mut data.proto_transactions[position];
+        let mut changes = Vec::new();
+        for &(field, col) in &value_columns {
+            let text = cell(col);
+            if text.is_empty() {
+                continue;
+            }
+            let change = if field == "description" {
+                (text != tx.description).then(|| {
+                    let old = json!(tx.description);
+                    tx.description = text.to_string();
+                    (old, json!(text))
+                })
+            } else {
+                let value: f64 = text
+                    .replace(',', "")
+                    .parse()
+                    .map_err(|e| format!("Invalid row {} {} '{}': {}", row, field, text, e))?;
+                let current = if field == "amount" {
+                    &mut tx.amount
+                } else {
+                    &mut tx.balance
+                };
+                // Numbers are equal to the cent, as written by the CSV export
+                current
+                    .is_none_or(|c| Money::from_f64(c) != Money::from_f64(value))
+                    .then(|| (json!(current.replace(value)), json!(value)))
+            };
+            if let Some((old, new)) = change {
+                if !tx.is_corrected(field) {
+                    tx.corrected_fields.push(field.to_string());
+                }
+                changes.push(FieldChange {
+                    field: field.to_string(),
+                    old,
+                    new,
+                });
+            }
+        }
+        if !changes.is_empty() {
+            summary.corrections.push(TransactionCorrection {
+                row,
+                number: position + 1,
+                changes,
+            });
+        }
+    }
+
+    fix_statement_data_safe(data);
+    let errors_before = data.errors.clone();
+    data.errors = multiset_difference(&data.errors, &stale_errors);
+    check_transaction_values(data);
+    summary.errors_added = multiset_difference(&data.errors, &errors_before);
+    summary.errors_removed = multiset_difference(&errors_before, &data.errors);
+    Ok(summary)
+}
+
+/// Apply the corrections of a CSV file to a JSON export of statement data (see
+/// statement_data_to_json and apply_csv_corrections). Returns the corrected JSON
+/// export and the summary of the corrections.
+pub fn statement_data_json_str_apply_csv_corrections(
+    json_str: &str,
+    csv_path: &str,
+) -> Result<(String, CorrectionSummary), String> {
+    let value: Value =
+        serde_json::from_str(json_str).map_err(|e| format!("JSON parse error: {}", e))?;
+    let mut data = statement_data_from_json_value(&value)?;
+    let summary = apply_csv_corrections(&mut data, csv_path)?;
+    Ok((statement_data_to_json(&data, true)?, summary))
+}
+
+/// Read the records of a CSV: comma-separated fields, optionally double-quoted with
+/// doubled quotes for a quote, and with line breaks within quoted fields
+pub(crate) fn read_csv_records(csv_str: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = csv_str.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => quoted = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted field".to_string());
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    // Skip blank lines
+    records.retain(|record| !(record.len() == 1 && record[0].is_empty()));
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::ProtoTransaction;
+    use std::process;
+
+    const DAY_MS: i64 = 86_400_000;
+    const JAN_2_2024: i64 = 1_704_153_600_000;
+
+    fn make_statement_data() -> StatementData {
+        let mut data = StatementData::new();
+        data.set_key("au__test__1".to_string());
+        data.set_account_number("123456".to_string());
+        data.set_opening_balance(100.0);
+        data.set_closing_balance(128.0);
+        for (day, index, description, amount, balance) in [
+            (0, 0, "Deposit", 50.0, 150.0),
+            (0, 1, "Coffe", -10.0, 140.0),
+            (1, 0, "Fee", -12.0, 128.0),
+        ] {
+            data.add_proto_transaction(ProtoTransaction {
+                date: Some(JAN_2_2024 + day * DAY_MS),
+                index,
+                description: description.to_string(),
+                amount: Some(amount),
+                balance: Some(balance),
+                ..ProtoTransaction::default()
+            });
+        }
+        data
+    }
+
+    #[test]
+    fn test_apply_csv_corrections_applies_edited_cells() {
+        let mut data = make_statement_data();
+        // The fee amount was misread, breaking the balances
+        data.proto_transactions[2].amount = Some(-21.0);
+        data.add_error("Parse error kept".to_string());
+        let mut checked = data.clone();
+        check_transaction_values(&mut checked);
+        data.errors = checked.errors;
+        let csv = "date,date_index,description,amount,balance\n\
+                   2024-01-02,0,Deposit,50.0,150.0\n\
+                   2024-01-02,1,\"Coffee, large\",-10.0,140.0\n\
+                   2024-01-03,0,Fee,-12.0,128.0\n";
+
+        let summary = apply_csv_str_corrections(&mut data, csv).unwrap();
+
+        assert_eq!(summary.corrections.len(), 2);
+        assert_eq!(summary.corrections[0].row, 2);
+        assert_eq!(
+            summary.corrections[0].changes,
+            vec![FieldChange {
+                field: "description".to_string(),
+                old: json!("Coffe"),
+                new: json!("Coffee, large"),
+            }]
+        );
+        assert_eq!(summary.corrections[1].number, 3);
+        assert_eq!(summary.corrections[1].changes[0].old, json!(-21.0));
+        assert!(summary.unmatched_rows.is_empty());
+        assert!(summary.errors_added.is_empty());
+        assert_eq!(summary.errors_removed.len(), 2, "{:?}", summary);
+        assert_eq!(data.errors, vec!["Parse error kept".to_string()]);
+
+        let value: Value =
+            serde_json::from_str(&statement_data_to_json(&data, true).unwrap()).unwrap();
+        let transactions = value["transactions"].as_array().unwrap();
+        assert_eq!(transactions[0]["description_source"], "parsed");
+        assert_eq!(transactions[0]["amount_source"], "parsed");
+        assert_eq!(transactions[1]["description"], "Coffee, large");
+        assert_eq!(transactions[1]["description_source"], "corrected");
+        assert_eq!(transactions[1]["amount_source"], "parsed");
+        assert_eq!(transactions[2]["amount"], -12.0);
+        assert_eq!(transactions[2]["amount_source"], "corrected");
+        assert_eq!(transactions[2]["balance_source"], "parsed");
+    }
+
+    #[test]
+    fn test_apply_csv_corrections_reports_unmatched_rows_and_new_errors() {
+        let mut data = make_statement_data();
+        // Matched by order within each date without a date_index column
+        let csv = "date,amount\n\
+                   2024-01-02,50.00\n\
+                   2024-01-02,-11.00\n\
+                   2024-01-03,\n\
+                   2024-01-04,5.00\n";
+
+        let summary = apply_csv_str_corrections(&mut data, csv).unwrap();
+
+        assert_eq!(summary.corrections.len(), 1);
+        assert_eq!(summary.corrections[0].number, 2);
+        assert_eq!(summary.unmatched_rows, vec![4]);
+        assert_eq!(summary.errors_added.len(), 3, "{:?}", summary.errors_added);
+        assert!(summary.errors_added[0].starts_with("Transaction 2 balance mismatch"));
+        assert_eq!(data.errors, summary.errors_added);
+        let text = summary.to_string();
+        assert!(text.contains("Row 2: transaction 2 corrected: amount: -10.0 -> -11.0\n"));
+        assert!(text.contains("Row 4: no matching transaction\n"));
+    }
+
+    #[test]
+    fn test_apply_csv_corrections_rejects_invalid_csv() {
+        let mut data = make_statement_data();
+        for (csv, error) in [
+            ("", "empty"),
+            ("description\nDeposit\n", "Column 'date'"),
+            ("date,date_index\n2024-01-02,0\n", "none of the columns"),
+            ("date,amount\n02/01/2024,1.00\n", "Invalid row 1 date"),
+            ("date,amount\n2024-01-02,one\n", "Invalid row 1 amount"),
+            ("date,description\n2024-01-02,\"Deposit\n", "unterminated"),
+        ] {
+            let result = apply_csv_str_corrections(&mut data, csv);
+            assert!(
+                result.as_ref().is_err_and(|e| e.contains(error)),
+                "{:?}: {:?}",
+                csv,
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_json_str_apply_csv_corrections_round_trips() {
+        let json_str = statement_data_to_json(&make_statement_data(), false).unwrap();
+        let path =
+            std::env::temp_dir().join(format!("transtractor_corrections_{}.csv", process::id()));
+        fs::write(
+            &path,
+            "date,date_index,description\n2024-01-03,0,Account fee\n",
+        )
+        .unwrap();
+
+        let (corrected, summary) =
+            statement_data_json_str_apply_csv_corrections(&json_str, path.to_str().unwrap())
+                .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(summary.corrections.len(), 1);
+        let value: Value = serde_json::from_str(&corrected).unwrap();
+        assert_eq!(value["key"], "au__test__1");
+        assert_eq!(value["opening_balance"], 100.0);
+        assert_eq!(value["transactions"][2]["date"], "2024-01-03");
+        assert_eq!(value["transactions"][2]["description"], "Account fee");
+        assert_eq!(value["transactions"][2]["description_source"], "corrected");
+        assert_eq!(value["errors"], json!([]));
+    }
+}
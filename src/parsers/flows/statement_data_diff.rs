@@ -0,0 +1,438 @@
+use crate::parsers::flows::statement_data_to_json::statement_data_to_json;
+use crate::structs::StatementData;
+use serde_json::{Map, Value, json};
+use std::fmt;
+
+/// Tolerance of the balance checks (see checkers::balances). Numbers differ if they
+/// are more than half of it apart, so a change of a cent is reported but float
+/// rounding is not.
+const BALANCE_TOLERANCE: f64 = 0.01;
+
+/// Change of a single field between two parses
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+/// Field changes of a transaction, by its 1-based number in each parse
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionChange {
+    pub old_number: usize,
+    pub new_number: usize,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Structured differences between two parses of the same statement, as JSON exports
+/// (see statement_data_to_json). Transactions are matched in order, so a transaction
+/// inserted or removed is reported once rather than as changes to all that follow.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatementDataDiff {
+    /// Changes of the statement-level fields (key, balances, dates, etc.)
+    pub metadata: Vec<FieldChange>,
+    /// Transactions only in the new parse, by their 1-based number in it
+    pub transactions_added: Vec<(usize, Value)>,
+    /// Transactions only in the old parse, by their 1-based number in it
+    pub transactions_removed: Vec<(usize, Value)>,
+    pub transactions_changed: Vec<TransactionChange>,
+    pub errors_added: Vec<String>,
+    pub errors_removed: Vec<String>,
+    pub warnings_added: Vec<String>,
+    pub warnings_removed: Vec<String>,
+}
+
+/// Diff two parses of the same statement (incomplete transactions included)
+pub fn statement_data_diff(
+    old: &StatementData,
+    new: &StatementData,
+) -> Result<StatementDataDiff, String> {
+    let old_value = export_value(old)?;
+    let new_value = export_value(new)?;
+    Ok(statement_data_json_diff(&old_value, &new_value))
+}
+
+/// Diff two JSON exports of parses of the same statement
+pub fn statement_data_json_str_diff(
+    old_json: &str,
+    new_json: &str,
+) -> Result<StatementDataDiff, String> {
+    let parse = |json_str: &str| {
+        serde_json::from_str::<Value>(json_str).map_err(|e| format!("JSON parse error: {}", e))
+    };
+    Ok(statement_data_json_diff(
+        &parse(old_json)?,
+        &parse(new_json)?,
+    ))
+}
+
+/// Diff two JSON export values of parses of the same statement
+pub fn statement_data_json_diff(old: &Value, new: &Value) -> StatementDataDiff {
+    let empty = Map::new();
+    let old_fields = old.as_object().unwrap_or(&empty);
+    let new_fields = new.as_object().unwrap_or(&empty);
+    let mut diff = StatementDataDiff::default();
+
+    let mut metadata_fields: Vec<&String> = old_fields.keys().chain(new_fields.keys()).collect();
+    metadata_fields.sort();
+    metadata_fields.dedup();
+    for field in metadata_fields {
+        if matches!(field.as_str(), "errors" | "warnings" | "transactions") {
+            continue;
+        }
+        let old_value = old_fields.get(field).unwrap_or(&Value::Null);
+        let new_value = new_fields.get(field).unwrap_or(&Value::Null);
+        if !values_equal(old_value, new_value) {
+            diff.metadata.push(FieldChange {
+                field: field.clone(),
+                old: old_value.clone(),
+                new: new_value.clone(),
+            });
+        }
+    }
+
+    let strings = |fields: &Map<String, Value>, name: &str| -> Vec<String> {
+        fields
+            .get(name)
+            .and_then(Value::as_array)
+            .map(|values| {
+                values
+                    .iter()
+                    .map(|v| v.as_str().map(str::to_string).unwrap_or(v.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let (old_errors, new_errors) = (strings(old_fields, "errors"), strings(new_fields, "errors"));
+    diff.errors_added = multiset_difference(&new_errors, &old_errors);
+    diff.errors_removed = multiset_difference(&old_errors, &new_errors);
+    let (old_warnings, new_warnings) = (
+        strings(old_fields, "warnings"),
+        strings(new_fields, "warnings"),
+    );
+    diff.warnings_added = multiset_difference(&new_warnings, &old_warnings);
+    diff.warnings_removed = multiset_difference(&old_warnings, &new_warnings);
+
+    let transactions = |fields: &Map<String, Value>| -> Vec<Value> {
+        fields
+            .get("transactions")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default()
+    };
+    diff_transactions(
+        &transactions(old_fields),
+        &transactions(new_fields),
+        &mut diff,
+    );
+    diff
+}
+
+impl StatementDataDiff {
+    /// True if the parses are the same
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Get the diff as a JSON value, e.g., for conversion to a Python dict
+    pub fn to_value(&self) -> Value {
+        let field_changes = |changes: &[FieldChange]| -> Vec<Value> {
+            changes
+                .iter()
+                .map(|c| json!({"field": c.field, "old": c.old, "new": c.new}))
+                .collect()
+        };
+        let numbered = |transactions: &[(usize, Value)]| -> Vec<Value> {
+            transactions
+                .iter()
+                .map(|(number, tx)| json!({"number": number, "transaction": tx}))
+                .collect()
+        };
+        json!({
+            "metadata": field_changes(&self.metadata),
+            "transactions_added": numbered(&self.transactions_added),
+            "transactions_removed": numbered(&self.transactions_removed),
+            "transactions_changed": self
+                .transactions_changed
+                .iter()
+                .map(|c| json!({
+                    "old_number": c.old_number,
+                    "new_number": c.new_number,
+                    "changes": field_changes(&c.changes),
+                }))
+                .collect::<Vec<Value>>(),
+            "errors_added": self.errors_added,
+            "errors_removed": self.errors_removed,
+            "warnings_added": self.warnings_added,
+            "warnings_removed": self.warnings_removed,
+        })
+    }
+}
+
+impl fmt::Display for StatementDataDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No differences");
+        }
+        let write_changes = |f: &mut fmt::Formatter<'_>, changes: &[FieldChange]| {
+            let changes: Vec<String> = changes
+                .iter()
+                .map(|c| format!("{}: {} -> {}", c.field, c.old, c.new))
+                .collect();
+            writeln!(f, "{}", changes.join(", "))
+        };
+        if !self.metadata.is_empty() {
+            write!(f, "Metadata changed: ")?;
+            write_changes(f, &self.metadata)?;
+        }
+        for change in &self.transactions_changed {
+            write!(
+                f,
+                "Transaction {} (now {}) changed: ",
+                change.old_number, change.new_number
+            )?;
+            write_changes(f, &change.changes)?;
+        }
+        for (number, tx) in &self.transactions_removed {
+            writeln!(f, "Transaction {} removed: {}", number, tx)?;
+        }
+        for (number, tx) in &self.transactions_added {
+            writeln!(f, "Transaction {} added: {}", number, tx)?;
+        }
+        for (label, messages) in [
+            ("Error removed", &self.errors_removed),
+            ("Error added", &self.errors_added),
+            ("Warning removed", &self.warnings_removed),
+            ("Warning added", &self.warnings_added),
+        ] {
+            for message in messages {
+                writeln!(f, "{}: {}", label, message)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn export_value(data: &StatementData) -> Result<Value, String> {
+    let json_str = statement_data_to_json(data, true)?;
+    serde_json::from_str(&json_str).map_err(|e| format!("JSON parse error: {}", e))
+}
+
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(x), Some(y)) => (x - y).abs() <= BALANCE_TOLERANCE / 2.0,
+        _ => a == b,
+    }
+}
+
+fn field_changes(old: &Value, new: &Value) -> Vec<FieldChange> {
+    let empty = Map::new();
+    let old_fields = old.as_object().unwrap_or(&empty);
+    let new_fields = new.as_object().unwrap_or(&empty);
+    let mut fields: Vec<&String> = old_fields.keys().chain(new_fields.keys()).collect();
+    fields.sort();
+    fields.dedup();
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let old_value = old_fields.get(field).unwrap_or(&Value::Null);
+            let new_value = new_fields.get(field).unwrap_or(&Value::Null);
+            (!values_equal(old_value, new_value)).then(|| FieldChange {
+                field: field.clone(),
+                old: old_value.clone(),
+                new: new_value.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Items of a not in b, counting repeats
+pub(crate) fn multiset_difference(a: &[String], b: &[String]) -> Vec<String> {
+    let mut remaining: Vec<&String> = b.iter().collect();
+    a.iter()
+        .filter(|item| match remaining.iter().position(|r| r == item) {
+            Some(position) => {
+                remaining.remove(position);
+                false
+            }
+            None => true,
+        })
+        .cloned()
+        .collect()
+}
+
+/// Match unchanged transactions in order, then pair the unmatched transactions
+/// between each match as changed, and report the rest as added or removed
+fn diff_transactions(old: &[Value], new: &[Value], diff: &mut StatementDataDiff) {
+    let unchanged = |i: usize, j: usize| field_changes(&old[i], &new[j]).is_empty();
+    for (old_pending, new_pending) in align_in_order(old.len(), new.len(), unchanged) {
+        pair_pending(old, new, &old_pending, &new_pending, diff);
+    }
+}
+
+/// Match equal items of two sequences in order (longest common subsequence), and
+/// return the indices of the unmatched items of each sequence between consecutive
+/// matches (and before the first and after the last), in order. Groups with no
+/// unmatched items are skipped.
+pub(crate) fn align_in_order(
+    old_len: usize,
+    new_len: usize,
+    equal: impl Fn(usize, usize) -> bool,
+) -> Vec<(Vec<usize>, Vec<usize>)> {
+    // lengths[i][j]: longest common subsequence of old[i..] and new[j..]
+    let mut lengths = vec![vec![0usize; new_len + 1]; old_len + 1];
+    for i in (0..old_len).rev() {
+        for j in (0..new_len).rev() {
+            lengths[i][j] = if equal(i, j) {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut groups = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    let mut old_pending: Vec<usize> = Vec::new();
+    let mut new_pending: Vec<usize> = Vec::new();
+    loop {
+        let matched = i < old_len && j < new_len && equal(i, j);
+        if matched || (i == old_len && j == new_len) {
+            if !old_pending.is_empty() || !new_pending.is_empty() {
+                groups.push((
+                    std::mem::take(&mut old_pending),
+                    std::mem::take(&mut new_pending),
+                ));
+            }
+            if !matched {
+                break;
+            }
+            i += 1;
+            j += 1;
+        } else if j == new_len || (i < old_len && lengths[i + 1][j] >= lengths[i][j + 1]) {
+            old_pending.push(i);
+            i += 1;
+        } else {
+            new_pending.push(j);
+            j += 1;
+        }
+    }
+    groups
+}
+
+fn pair_pending(
+    old: &[Value],
+    new: &[Value],
+    old_pending: &[usize],
+    new_pending: &[usize],
+    diff: &mut StatementDataDiff,
+) {
+    for (&i, &j) in old_pending.iter().zip(new_pending) {
+        diff.transactions_changed.push(TransactionChange {
+            old_number: i + 1,
+            new_number: j + 1,
+            changes: field_changes(&old[i], &new[j]),
+        });
+    }
+    for &i in old_pending.iter().skip(new_pending.len()) {
+        diff.transactions_removed.push((i + 1, old[i].clone()));
+    }
+    for &j in new_pending.iter().skip(old_pending.len()) {
+        diff.transactions_added.push((j + 1, new[j].clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::ProtoTransaction;
+
+    fn make_statement_data() -> StatementData {
+        let mut data = StatementData::new();
+        data.set_key("au__test__1".to_string());
+        data.set_opening_balance(100.0);
+        data.set_closing_balance(160.0);
+        for (i, amount) in [10.0, 20.0, 30.0].into_iter().enumerate() {
+            data.add_proto_transaction(ProtoTransaction {
+                date: Some(1_704_153_600_000 + i as i64 * 86_400_000),
+                index: 0,
+                description: format!("Transaction {}", i + 1),
+                amount: Some(amount),
+                balance: Some(100.0 + (1..=i + 1).map(|n| n as f64 * 10.0).sum::<f64>()),
+                page: Some(1),
+                y1: None,
+                description_optional: false,
+                amount_inferred: false,
+                balance_computed: false,
+                corrected_fields: vec![],
+                category: None,
+                source_items: vec![],
+            });
+        }
+        data
+    }
+
+    #[test]
+    fn test_identical_parses_have_empty_diff() {
+        let data = make_statement_data();
+        let diff = statement_data_diff(&data, &data.clone()).unwrap();
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "No differences\n");
+    }
+
+    #[test]
+    fn test_one_cent_change_reported_at_transaction_and_field() {
+        let old = make_statement_data();
+        let mut new = old.clone();
+        new.proto_transactions[1].amount = Some(20.01);
+        new.add_warning("Balance mismatch".to_string());
+
+        let diff = statement_data_diff(&old, &new).unwrap();
+        assert_eq!(
+            diff.transactions_changed,
+            vec![TransactionChange {
+                old_number: 2,
+                new_number: 2,
+                changes: vec![FieldChange {
+                    field: "amount".to_string(),
+                    old: json!(20.0),
+                    new: json!(20.01),
+                }],
+            }]
+        );
+        assert!(diff.metadata.is_empty());
+        assert_eq!(diff.warnings_added, vec!["Balance mismatch".to_string()]);
+        assert_eq!(
+            diff.to_string(),
+            "Transaction 2 (now 2) changed: amount: 20.0 -> 20.01\nWarning added: Balance mismatch\n"
+        );
+    }
+
+    #[test]
+    fn test_inserted_transaction_and_metadata_change() {
+        let old = make_statement_data();
+        let mut new = old.clone();
+        let mut inserted = new.proto_transactions[0].clone();
+        inserted.description = "Inserted".to_string();
+        new.proto_transactions.insert(1, inserted);
+        new.set_closing_balance(170.0);
+
+        let diff = statement_data_diff(&old, &new).unwrap();
+        assert!(diff.transactions_changed.is_empty());
+        assert!(diff.transactions_removed.is_empty());
+        assert_eq!(diff.transactions_added.len(), 1);
+        assert_eq!(diff.transactions_added[0].0, 2);
+        assert_eq!(diff.transactions_added[0].1["description"], "Inserted");
+        assert_eq!(
+            diff.metadata,
+            vec![FieldChange {
+                field: "closing_balance".to_string(),
+                old: json!(160.0),
+                new: json!(170.0),
+            }]
+        );
+        let value = diff.to_value();
+        assert_eq!(value["transactions_added"][0]["number"], 2);
+        assert_eq!(value["metadata"][0]["field"], "closing_balance");
+    }
+}
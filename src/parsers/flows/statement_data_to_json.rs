@@ -0,0 +1,332 @@
+use crate::structs::{ProtoTransaction, StatementData};
+use chrono::NaiveDate;
+use serde_json::{Value, json};
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Count of temporary files created by write_atomic_with, to name each uniquely
+static TMP_FILE_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Serialise a StatementData to a pretty-printed JSON string, with dates as ISO-8601
+/// calendar dates in the statement's date timezone. Incomplete transactions (missing a
+/// date, amount or balance) are emitted with nulls if include_incomplete is set,
+/// otherwise skipped. The source of each description ("parsed" or "corrected"), amount
+/// ("parsed", "inferred" or "corrected") and balance ("parsed", "computed" or
+/// "corrected") is given with the number of computed balances and inferred amounts.
+pub fn statement_data_to_json(
+    data: &StatementData,
+    include_incomplete: bool,
+) -> Result<String, String> {
+    let value = statement_data_to_json_value(data, include_incomplete);
+    serde_json::to_string_pretty(&value).map_err(|e| format!("JSON write error: {}", e))
+}
+
+/// Serialise a StatementData to a JSON value (see statement_data_to_json)
+pub fn statement_data_to_json_value(data: &StatementData, include_incomplete: bool) -> Value {
+    let iso_date = |timestamp: Option<i64>| -> Value {
+        match timestamp.and_then(|t| data.to_naive_date(t)) {
+            Some(date) => json!(date.format("%Y-%m-%d").to_string()),
+            None => Value::Null,
+        }
+    };
+
+    // Rounded to cents as for the Python Transaction
+    let round_cents = |value: Option<f64>| value.map(|v| (v * 100.0).round() / 100.0);
+
+    let transactions: Vec<Value> = data
+        .proto_transactions
+        .iter()
+        .filter(|proto_tx| include_incomplete || proto_tx.is_ready())
+        .map(|proto_tx| {
+            json!({
+                "date": iso_date(proto_tx.date),
+                "date_index": proto_tx.index,
+                "description": proto_tx.description,
+                "description_source": proto_tx.description_source(),
+                "amount": round_cents(proto_tx.amount),
+                "balance": round_cents(proto_tx.balance),
+                "amount_source": proto_tx.amount.map(|_| proto_tx.amount_source()),
+                "balance_source": proto_tx.balance.map(|_| proto_tx.balance_source()),
+            })
+        })
+        .collect();
+
+    json!({
+        "key": data.key,
+        "config_status": data.config_status,
+        "account_number": data.account_number,
+        "start_date": iso_date(data.start_date),
+        "end_date": iso_date(data.end_date),
+        "opening_balance": data.opening_balance,
+        "closing_balance": data.closing_balance,
+        "minimum_payment": data.minimum_payment,
+        "payment_due_date": iso_date(data.payment_due_date),
+        "currency": data.currency,
+        "errors": data.errors,
+        "warnings": data.warnings,
+        "balances_computed": data.computed_balance_count(),
+        "amounts_inferred": data.inferred_amount_count(),
+        "transactions": transactions,
+    })
+}
+
+/// Read a StatementData back from a JSON export value (see statement_data_to_json).
+/// Dates are read as UTC midnight, and the sources of the transaction values are
+/// restored. Timings, page coverage and other parse details are not exported, so
+/// are left unset.
+pub fn statement_data_from_json_value(value: &Value) -> Result<StatementData, String> {
+    let fields = value
+        .as_object()
+        .ok_or("Invalid statement data JSON: not an object")?;
+    let string = |value: &Value| value.as_str().map(str::to_string);
+    let timestamp = |value: &Value, field: &str| -> Result<Option<i64>, String> {
+        let Some(date_str) = value.as_str() else {
+            return Ok(None);
+        };
+        let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+            .map_err(|e| format!("Invalid {} '{}': {}", field, date_str, e))?;
+        Ok(Some(
+            date.and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_utc()
+                .timestamp_millis(),
+        ))
+    };
+    let field = |name: &str| fields.get(name).unwrap_or(&Value::Null);
+    let strings = |name: &str| -> Vec<String> {
+        field(name)
+            .as_array()
+            .map(|values| values.iter().filter_map(string).collect())
+            .unwrap_or_default()
+    };
+
+    let mut data = StatementData::new();
+    data.key = string(field("key"));
+    data.config_status = string(field("config_status"));
+    data.account_number = string(field("account_number"));
+    if let Some(date) = timestamp(field("start_date"), "start_date")? {
+        data.set_start_date(date);
+    }
+    data.end_date = timestamp(field("end_date"), "end_date")?;
+    data.opening_balance = field("opening_balance").as_f64();
+    data.closing_balance = field("closing_balance").as_f64();
+    data.minimum_payment = field("minimum_payment").as_f64();
+    data.payment_due_date = timestamp(field("payment_due_date"), "payment_due_date")?;
+    data.currency = string(field("currency"));
+    data.errors = strings("errors");
+    data.warnings = strings("warnings");
+
+    for (number, tx) in field("transactions")
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+    {
+        let tx_field = |name: &str| tx.get(name).unwrap_or(&Value::Null);
+        let mut proto_tx = ProtoTransaction::new();
+        proto_tx.date = timestamp(
+            tx_field("date"),
+            &format!("transaction {} date", number + 1),
+        )?;
+        proto_tx.index = tx_field("date_index").as_u64().unwrap_or_default() as usize;
+        proto_tx.description = string(tx_field("description")).unwrap_or_default();
+        proto_tx.description_optional = proto_tx.description.is_empty();
+        proto_tx.amount = tx_field("amount").as_f64();
+        proto_tx.balance = tx_field("balance").as_f64();
+        proto_tx.amount_inferred = tx_field("amount_source") == "inferred";
+        proto_tx.balance_computed = tx_field("balance_source") == "computed";
+        for (name, source) in [
+            ("description", "description_source"),
+            ("amount", "amount_source"),
+            ("balance", "balance_source"),
+        ] {
+            if tx_field(source) == "corrected" {
+                proto_tx.corrected_fields.push(name.to_string());
+            }
+        }
+        data.add_proto_transaction(proto_tx);
+    }
+    Ok(data)
+}
+
+/// Serialise a StatementData to a JSON file (see statement_data_to_json). The file is
+/// written atomically: on failure, any existing file at the path is left untouched.
+pub fn statement_data_to_json_file<P: AsRef<Path>>(
+    data: &StatementData,
+    path: P,
+    include_incomplete: bool,
+) -> Result<(), String> {
+    let json_str = statement_data_to_json(data, include_incomplete)?;
+    write_atomic(path.as_ref(), &json_str)
+        .map_err(|e| format!("Failed writing JSON {:?}: {}", path.as_ref(), e))
+}
+
+/// Write to a temporary file in the same directory, synced to disk, then rename it to
+/// the path. The temporary file is removed on failure.
+fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    write_atomic_with(path, |file| file.write_all(contents.as_bytes()))
+}
+
+/// Stream to a temporary file in the same directory with a buffered writer, then sync
+/// it to disk and rename it to the path, as for write_atomic. The temporary file is
+/// named uniquely per call, so concurrent writes of the same path do not clobber it.
+pub(crate) fn write_atomic_with<F>(path: &Path, write: F) -> io::Result<()>
+where
+    F: FnOnce(&mut BufWriter<fs::File>) -> io::Result<()>,
+{
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let count = TMP_FILE_COUNT.fetch_add(1, Ordering::Relaxed);
+    let tmp_path = path.with_file_name(format!(".{}.{}.{}.tmp", file_name, process::id(), count));
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&tmp_path)?;
+    let result = (|| {
+        let mut writer = BufWriter::new(file);
+        write(&mut writer)?;
+        let file = writer.into_inner().map_err(|e| e.into_error())?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::ProtoTransaction;
+
+    fn make_statement_data() -> StatementData {
+        let mut data = StatementData::new();
+        data.set_key("au__test__1".to_string());
+        data.set_account_number("123456".to_string());
+        data.set_opening_balance(100.0);
+        data.set_closing_balance(150.0);
+        data.set_start_date(1_704_067_200_000); // 2024-01-01
+        data.add_proto_transaction(ProtoTransaction {
+            date: Some(1_704_153_600_000), // 2024-01-02
+            index: 0,
+            description: "Deposit".to_string(),
+            amount: Some(50.0),
+            balance: Some(150.0),
+            page: Some(1),
+            y1: None,
+            description_optional: false,
+            amount_inferred: false,
+            balance_computed: true,
+            corrected_fields: vec![],
+            category: None,
+            source_items: vec![],
+        });
+        data.add_proto_transaction(ProtoTransaction {
+            date: Some(1_704_153_600_000),
+            index: 1,
+            description: "Unfinished".to_string(),
+            amount: Some(-10.0),
+            balance: None,
+            page: Some(1),
+            y1: None,
+            description_optional: false,
+            amount_inferred: false,
+            balance_computed: false,
+            corrected_fields: vec![],
+            category: None,
+            source_items: vec![],
+        });
+        data.add_error("Transaction missing balance".to_string());
+        data
+    }
+
+    #[test]
+    fn test_statement_data_to_json_skips_incomplete() {
+        let json_str = statement_data_to_json(&make_statement_data(), false).unwrap();
+        let value: Value = serde_json::from_str(&json_str).unwrap();
+
+        assert_eq!(value["key"], "au__test__1");
+        assert_eq!(value["account_number"], "123456");
+        assert_eq!(value["opening_balance"], 100.0);
+        assert_eq!(value["closing_balance"], 150.0);
+        assert_eq!(value["start_date"], "2024-01-01");
+        assert_eq!(value["end_date"], Value::Null);
+        assert_eq!(value["payment_due_date"], Value::Null);
+        assert_eq!(value["errors"][0], "Transaction missing balance");
+        let transactions = value["transactions"].as_array().unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0]["date"], "2024-01-02");
+        assert_eq!(transactions[0]["description"], "Deposit");
+        assert_eq!(transactions[0]["amount"], 50.0);
+    }
+
+    #[test]
+    fn test_statement_data_to_json_includes_incomplete_with_nulls() {
+        let json_str = statement_data_to_json(&make_statement_data(), true).unwrap();
+        let value: Value = serde_json::from_str(&json_str).unwrap();
+
+        let transactions = value["transactions"].as_array().unwrap();
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[1]["description"], "Unfinished");
+        assert_eq!(transactions[1]["date_index"], 1);
+        assert_eq!(transactions[1]["balance"], Value::Null);
+        assert_eq!(transactions[0]["amount_source"], "parsed");
+        assert_eq!(transactions[0]["balance_source"], "computed");
+        assert_eq!(transactions[1]["amount_source"], "parsed");
+        assert_eq!(transactions[1]["balance_source"], Value::Null);
+        assert_eq!(value["balances_computed"], 1);
+        assert_eq!(value["amounts_inferred"], 0);
+    }
+
+    #[test]
+    fn test_statement_data_to_json_file_is_atomic() {
+        let dir = std::env::temp_dir().join(format!("transtractor_json_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("statement.json");
+        fs::write(&path, "previous").unwrap();
+
+        // Failure (the path is a directory) leaves no partial or temporary file
+        let dir_path = dir.join("existing_dir");
+        fs::create_dir_all(&dir_path).unwrap();
+        assert!(statement_data_to_json_file(&make_statement_data(), &dir_path, false).is_err());
+        assert!(dir_path.is_dir());
+
+        statement_data_to_json_file(&make_statement_data(), &path, false).unwrap();
+        let value: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(value["key"], "au__test__1");
+
+        let mut names: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["existing_dir", "statement.json"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_concurrent_atomic_writes_of_the_same_path() {
+        let dir = std::env::temp_dir().join(format!("transtractor_atomic_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("out.txt");
+        let contents: Vec<String> = (0..8)
+            .map(|i| format!("writer {}\n", i).repeat(1000))
+            .collect();
+
+        std::thread::scope(|scope| {
+            for text in &contents {
+                let path = &path;
+                scope.spawn(move || write_atomic(path, text).unwrap());
+            }
+        });
+
+        // The file is whole from one of the writers, and no temporary file is left
+        assert!(contents.contains(&fs::read_to_string(&path).unwrap()));
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
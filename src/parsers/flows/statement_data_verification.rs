@@ -0,0 +1,419 @@
+use crate::parsers::flows::csv_to_statement_data::parse_csv_amount;
+use crate::parsers::flows::statement_data_corrections::read_csv_records;
+use crate::parsers::flows::statement_data_diff::{align_in_order, export_value};
+use crate::structs::StatementData;
+use chrono::NaiveDate;
+use serde_json::{Map, Value, json};
+use std::fmt;
+
+/// Transaction fields compared with an expected CSV, in column order
+const FIELDS: [&str; 4] = ["date", "description", "amount", "balance"];
+
+/// Options of the comparison of parsed transactions with an expected CSV (see
+/// verify_statement_data)
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifyOptions {
+    /// Largest difference between equal amounts or balances
+    pub tolerance: f64,
+    /// Compare descriptions ignoring case and runs of whitespace
+    pub normalise_descriptions: bool,
+}
+
+impl Default for VerifyOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.005,
+            normalise_descriptions: true,
+        }
+    }
+}
+
+/// Field of a transaction differing from the expected CSV
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldMismatch {
+    pub field: String,
+    pub expected: Value,
+    pub actual: Value,
+}
+
+/// Field mismatches of a parsed transaction, by its 1-based number in the expected
+/// CSV (data rows, after the header) and in the parse
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowMismatch {
+    pub expected_number: usize,
+    pub actual_number: usize,
+    pub mismatches: Vec<FieldMismatch>,
+}
+
+/// Comparison of the transactions of a parse with an expected CSV (see
+/// verify_statement_data). Transactions are matched in order, so a row missing from
+/// the parse is reported once rather than as mismatches of all that follow.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VerificationReport {
+    pub expected_count: usize,
+    pub actual_count: usize,
+    /// Expected rows not parsed, by their 1-based number in the CSV
+    pub missing: Vec<(usize, Value)>,
+    /// Parsed transactions not expected, by their 1-based number in the parse
+    pub extra: Vec<(usize, Value)>,
+    pub mismatched: Vec<RowMismatch>,
+}
+
+impl VerificationReport {
+    /// True if the parsed transactions are those expected
+    pub fn passed(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty() && self.mismatched.is_empty()
+    }
+
+    /// Get the report as a JSON value, e.g., for conversion to a Python dict
+    pub fn to_value(&self) -> Value {
+        let numbered = |rows: &[(usize, Value)]| -> Vec<Value> {
+            rows.iter()
+                .map(|(number, row)| json!({"number": number, "transaction": row}))
+                .collect()
+        };
+        json!({
+            "passed": self.passed(),
+            "expected_count": self.expected_count,
+            "actual_count": self.actual_count,
+            "missing": numbered(&self.missing),
+            "extra": numbered(&self.extra),
+            "mismatched": self
+                .mismatched
+                .iter()
+                .map(|m| json!({
+                    "expected_number": m.expected_number,
+                    "actual_number": m.actual_number,
+                    "mismatches": m
+                        .mismatches
+                        .iter()
+                        .map(|f| json!({"field": f.field, "expected": f.expected, "actual": f.actual}))
+                        .collect::<Vec<Value>>(),
+                }))
+                .collect::<Vec<Value>>(),
+        })
+    }
+}
+
+impl fmt::Display for VerificationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.passed() {
+            return writeln!(f, "Verified {} transactions", self.expected_count);
+        }
+        writeln!(
+            f,
+            "Expected {} transactions, parsed {}",
+            self.expected_count, self.actual_count
+        )?;
+        for mismatch in &self.mismatched {
+            let fields: Vec<String> = mismatch
+                .mismatches
+                .iter()
+                .map(|m| format!("{}: expected {}, got {}", m.field, m.expected, m.actual))
+                .collect();
+            writeln!(
+                f,
+                "Row {} (transaction {}) mismatched: {}",
+                mismatch.expected_number,
+                mismatch.actual_number,
+                fields.join(", ")
+            )?;
+        }
+        for (number, row) in &self.missing {
+            writeln!(f, "Row {} missing: {}", number, row)?;
+        }
+        for (number, row) in &self.extra {
+            writeln!(f, "Transaction {} extra: {}", number, row)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare the transactions of statement data (incomplete transactions included)
+/// with an expected CSV, as written by StatementData.to_csv in Python: a header
+/// naming any of the "date" (YYYY-MM-DD), "description", "amount" and "balance"
+/// columns, in any case and order, and a row per transaction. Only the fields with a
+/// column are compared, and other columns are ignored. Fails if the CSV has none of
+/// the columns or a value cannot be read.
+pub fn verify_statement_data(
+    data: &StatementData,
+    expected_csv: &str,
+    options: &VerifyOptions,
+) -> Result<VerificationReport, String> {
+    verify_json_value(&export_value(data)?, expected_csv, options)
+}
+
+/// Compare the transactions of a JSON export of statement data (see
+/// statement_data_to_json) with an expected CSV, as for verify_statement_data
+pub fn verify_json_str(
+    json_str: &str,
+    expected_csv: &str,
+    options: &VerifyOptions,
+) -> Result<VerificationReport, String> {
+    let export =
+        serde_json::from_str::<Value>(json_str).map_err(|e| format!("JSON parse error: {}", e))?;
+    verify_json_value(&export, expected_csv, options)
+}
+
+fn verify_json_value(
+    export: &Value,
+    expected_csv: &str,
+    options: &VerifyOptions,
+) -> Result<VerificationReport, String> {
+    let (fields, expected) = read_expected_rows(expected_csv)?;
+    let actual: Vec<Value> = export
+        .get("transactions")
+        .and_then(Value::as_array)
+        .map(|transactions| {
+            transactions
+                .iter()
+                .map(|tx| {
+                    let row: Map<String, Value> = fields
+                        .iter()
+                        .map(|&field| {
+                            (
+                                field.to_string(),
+                                tx.get(field).cloned().unwrap_or(Value::Null),
+                            )
+                        })
+                        .collect();
+                    Value::Object(row)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut report = VerificationReport {
+        expected_count: expected.len(),
+        actual_count: actual.len(),
+        ..Default::default()
+    };
+    let mismatches = |i: usize, j: usize| field_mismatches(&expected[i], &actual[j], options);
+    let equal = |i: usize, j: usize| mismatches(i, j).is_empty();
+    for (expected_pending, actual_pending) in align_in_order(expected.len(), actual.len(), equal) {
+        for (&i, &j) in expected_pending.iter().zip(&actual_pending) {
+            report.mismatched.push(RowMismatch {
+                expected_number: i + 1,
+                actual_number: j + 1,
+                mismatches: mismatches(i, j),
+            });
+        }
+        for &i in expected_pending.iter().skip(actual_pending.len()) {
+            report.missing.push((i + 1, expected[i].clone()));
+        }
+        for &j in actual_pending.iter().skip(expected_pending.len()) {
+            report.extra.push((j + 1, actual[j].clone()));
+        }
+    }
+    Ok(report)
+}
+
+/// Read the compared fields of the expected CSV and its rows, as JSON objects with
+/// the values of the export (see statement_data_to_json)
+fn read_expected_rows(expected_csv: &str) -> Result<(Vec<&'static str>, Vec<Value>), String> {
+    let records = read_csv_records(expected_csv).map_err(|e| format!("Invalid CSV: {}", e))?;
+    let Some((header, rows)) = records.split_first() else {
+        return Err("Expected CSV is empty".to_string());
+    };
+    let columns: Vec<(&'static str, usize)> = FIELDS
+        .iter()
+        .filter_map(|&field| {
+            header
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(field))
+                .map(|col| (field, col))
+        })
+        .collect();
+    if columns.is_empty() {
+        return Err(format!(
+            "Expected CSV has none of the columns {}",
+            FIELDS.join(", ")
+        ));
+    }
+
+    let mut expected = Vec::new();
+    for (i, record) in rows.iter().enumerate() {
+        // Rows are numbered as in a spreadsheet, after the header
+        let row_number = i + 2;
+        let mut row = Map::new();
+        for &(field, col) in &columns {
+            let cell = record.get(col).map(|value| value.trim()).unwrap_or("");
+            let value = match field {
+                _ if cell.is_empty() => Value::Null,
+                "date" => {
+                    NaiveDate::parse_from_str(cell, "%Y-%m-%d")
+                        .map_err(|_| format!("Row {}: invalid date '{}'", row_number, cell))?;
+                    json!(cell)
+                }
+                "description" => json!(cell),
+                _ => json!(parse_csv_amount(cell, row_number)?),
+            };
+            row.insert(field.to_string(), value);
+        }
+        expected.push(Value::Object(row));
+    }
+    let fields = columns.into_iter().map(|(field, _)| field).collect();
+    Ok((fields, expected))
+}
+
+fn field_mismatches(
+    expected: &Value,
+    actual: &Value,
+    options: &VerifyOptions,
+) -> Vec<FieldMismatch> {
+    let empty = Map::new();
+    let expected = expected.as_object().unwrap_or(&empty);
+    expected
+        .iter()
+        .filter_map(|(field, expected_value)| {
+            let actual_value = actual.get(field).unwrap_or(&Value::Null);
+            (!values_match(field, expected_value, actual_value, options)).then(|| FieldMismatch {
+                field: field.clone(),
+                expected: expected_value.clone(),
+                actual: actual_value.clone(),
+            })
+        })
+        .collect()
+}
+
+fn values_match(field: &str, expected: &Value, actual: &Value, options: &VerifyOptions) -> bool {
+    match (expected, actual) {
+        (Value::String(a), Value::String(b))
+            if field == "description" && options.normalise_descriptions =>
+        {
+            normalise_description(a) == normalise_description(b)
+        }
+        _ => match (expected.as_f64(), actual.as_f64()) {
+            (Some(a), Some(b)) => (a - b).abs() <= options.tolerance,
+            _ => expected == actual,
+        },
+    }
+}
+
+/// Uppercase a description with runs of whitespace collapsed to a space
+fn normalise_description(description: &str) -> String {
+    description
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::ProtoTransaction;
+
+    fn make_statement_data() -> StatementData {
+        let mut data = StatementData::new();
+        for (i, (description, amount)) in
+            [("SALARY", 250.0), ("RENT  payment", -120.0), ("FEE", -5.0)]
+                .into_iter()
+                .enumerate()
+        {
+            let mut tx = ProtoTransaction::new();
+            tx.set_date(1_712_016_000_000 + i as i64 * 86_400_000); // from 2024-04-02
+            tx.description = description.to_string();
+            tx.set_amount(amount);
+            tx.set_balance(1000.0 + [250.0, 130.0, 125.0][i]);
+            data.add_proto_transaction(tx);
+        }
+        data
+    }
+
+    const EXPECTED: &str = "\
+date,description,amount,balance
+2024-04-02,SALARY,250.0,1250.0
+2024-04-03,Rent Payment,-120.0,1130.0
+2024-04-04,FEE,-5.0,1125.0
+";
+
+    #[test]
+    fn test_verify_matching_transactions() {
+        let report =
+            verify_statement_data(&make_statement_data(), EXPECTED, &VerifyOptions::default())
+                .unwrap();
+        assert!(report.passed(), "{}", report);
+        assert_eq!(report.to_string(), "Verified 3 transactions\n");
+
+        // Descriptions compared exactly
+        let options = VerifyOptions {
+            normalise_descriptions: false,
+            ..Default::default()
+        };
+        let report = verify_statement_data(&make_statement_data(), EXPECTED, &options).unwrap();
+        assert_eq!(report.mismatched.len(), 1);
+        assert_eq!(report.mismatched[0].expected_number, 2);
+        assert_eq!(report.mismatched[0].mismatches[0].field, "description");
+    }
+
+    #[test]
+    fn test_verify_reports_missing_extra_and_mismatched_rows() {
+        let mut data = make_statement_data();
+        data.proto_transactions.remove(0);
+        let mut extra = data.proto_transactions[1].clone();
+        extra.description = "INTEREST".to_string();
+        extra.set_amount(0.5);
+        data.add_proto_transaction(extra);
+        data.proto_transactions[1].set_amount(-5.01);
+
+        let report = verify_statement_data(&data, EXPECTED, &VerifyOptions::default()).unwrap();
+
+        assert!(!report.passed());
+        assert_eq!((report.expected_count, report.actual_count), (3, 3));
+        assert_eq!(report.missing.len(), 1);
+        assert_eq!(report.missing[0].0, 1);
+        assert_eq!(report.extra.len(), 1);
+        assert_eq!(report.extra[0].0, 3);
+        assert_eq!(
+            report.mismatched,
+            vec![RowMismatch {
+                expected_number: 3,
+                actual_number: 2,
+                mismatches: vec![FieldMismatch {
+                    field: "amount".to_string(),
+                    expected: json!(-5.0),
+                    actual: json!(-5.01),
+                }],
+            }]
+        );
+        assert!(
+            report
+                .to_string()
+                .contains("Row 3 (transaction 2) mismatched: amount: expected -5.0, got -5.01")
+        );
+
+        // Within the tolerance
+        let options = VerifyOptions {
+            tolerance: 0.02,
+            ..Default::default()
+        };
+        let report = verify_statement_data(&data, EXPECTED, &options).unwrap();
+        assert!(report.mismatched.is_empty());
+    }
+
+    #[test]
+    fn test_verify_compares_only_csv_columns() {
+        let csv = "Amount,Notes\n250.00,pay\n-120.00,\n\"-5.00\",\n";
+        let report =
+            verify_statement_data(&make_statement_data(), csv, &VerifyOptions::default()).unwrap();
+        assert!(report.passed(), "{}", report);
+    }
+
+    #[test]
+    fn test_verify_invalid_csv() {
+        let data = make_statement_data();
+        let options = VerifyOptions::default();
+        assert_eq!(
+            verify_statement_data(&data, "a,b\n1,2\n", &options),
+            Err(
+                "Expected CSV has none of the columns date, description, amount, balance"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            verify_statement_data(&data, "date\n02/04/2024\n", &options),
+            Err("Row 2: invalid date '02/04/2024'".to_string())
+        );
+    }
+}
@@ -1,34 +1,489 @@
+use crate::configs::typer::TyperDiagnostic;
+use crate::fixers::FixersMode;
+use crate::parsers::flows::statement_data_to_json::write_atomic_with;
 use crate::parsers::flows::text_items_to_statement_datas::text_items_to_statement_datas;
+use crate::structs::ParseTimings;
+use crate::structs::Redaction;
 use crate::structs::StatementConfig;
+use crate::structs::StatementData;
 use crate::structs::TextItem;
+use crate::structs::redaction::mask_account_number;
+use serde_json::{Map, Value, json};
+use std::fmt;
+use std::io;
+use std::path::Path;
 
-/// Parse non-tokenised text items into debug information string,
-/// using provided statement configurations.
-pub fn text_items_to_debug(
+/// Size limits of the debug output, so that a pathological statement (e.g., hundreds
+/// of pages matched by several configs) cannot produce an unbounded output
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DebugLimits {
+    /// Maximum number of lines of each section of a result (transactions, errors,
+    /// page coverage, sources and warnings), after which a truncation marker is written
+    pub max_section_lines: usize,
+    /// Maximum size of the whole output in bytes, after which a truncation marker is
+    /// written and the rest left out. Unlimited if None.
+    pub max_bytes: Option<usize>,
+}
+
+impl Default for DebugLimits {
+    fn default() -> Self {
+        Self {
+            max_section_lines: 10000,
+            max_bytes: None,
+        }
+    }
+}
+
+/// Debug report of the parse of a statement with the applicable configs (see
+/// text_items_to_debug_report). The text output and the JSON report are both rendered
+/// from it.
+#[derive(Debug, Clone)]
+pub struct DebugReport {
+    /// Error if the text items could not be parsed with the configs
+    pub error: Option<String>,
+    /// Statement data of each config applied (one per account section of a
+    /// multi_account config), whether or not error-free
+    pub statement_datas: Vec<StatementData>,
+    /// Account terms found and missing for each registered config key, set if no
+    /// config was identified (see StatementTyper::explain)
+    pub diagnostics: Vec<TyperDiagnostic>,
+}
+
+impl DebugReport {
+    /// Redact the statement data of each result (see Redaction), and the error and
+    /// the nearest text of the diagnostics as document text, with the account numbers
+    /// read also masked
+    pub fn redact(&mut self, redaction: &Redaction) {
+        let account_numbers: Vec<String> = self
+            .statement_datas
+            .iter()
+            .filter_map(|data| data.account_number.clone())
+            .filter(|number| !number.is_empty())
+            .collect();
+        for data in &mut self.statement_datas {
+            redaction.apply(data, None);
+        }
+        let redact_text = |text: &str| {
+            let mut text = text.to_string();
+            for number in &account_numbers {
+                text = text.replace(number.as_str(), &mask_account_number(number));
+            }
+            redaction.redact_document_text(&text)
+        };
+        if let Some(error) = &mut self.error {
+            *error = redact_text(error);
+        }
+        for diagnostic in &mut self.diagnostics {
+            for missing in &mut diagnostic.missing {
+                if let Some(nearest) = &mut missing.nearest {
+                    *nearest = redact_text(nearest);
+                }
+            }
+        }
+    }
+
+    /// Write the human-readable report: the identification diagnostics if no config
+    /// was identified, then for each result the statement data, errors, page coverage,
+    /// warnings and timings, with each section truncated at the limits
+    pub fn write_text<W: fmt::Write>(&self, out: &mut W, limits: &DebugLimits) -> fmt::Result {
+        out.write_str("Debug output\n")?;
+        if !self.diagnostics.is_empty() {
+            out.write_str("No config identified. Account terms of each config:\n")?;
+            for diagnostic in self.diagnostics.iter().take(limits.max_section_lines) {
+                write!(out, "{}", diagnostic)?;
+            }
+            if self.diagnostics.len() > limits.max_section_lines {
+                writeln!(
+                    out,
+                    "  ... {} more config(s) truncated",
+                    self.diagnostics.len() - limits.max_section_lines
+                )?;
+            }
+            out.write_char('\n')?;
+        }
+        if let Some(error) = &self.error {
+            out.write_str("Error: Failed to identify statement type or parse text items\n")?;
+            write!(out, "Error details: {}\n\n", error)?;
+            return Ok(());
+        }
+        write!(
+            out,
+            "Found {} StatementData result(s)\n\n",
+            self.statement_datas.len()
+        )?;
+        for (i, data) in self.statement_datas.iter().enumerate() {
+            writeln!(out, "=== StatementData Result {} ===", i + 1)?;
+            data.write_report(out, limits.max_section_lines)?;
+            out.write_char('\n')?;
+        }
+        Ok(())
+    }
+
+    /// Stream the text report (see write_text) to a writer, without building the output
+    /// in memory, truncated at the limits
+    pub fn write_to<W: io::Write>(&self, limits: &DebugLimits, out: W) -> io::Result<()> {
+        let mut writer = DebugWriter {
+            inner: out,
+            written: 0,
+            max_bytes: limits.max_bytes,
+            truncated: false,
+            error: None,
+        };
+        let result = self.write_text(&mut writer, limits);
+        if let Some(e) = writer.error {
+            return Err(e);
+        }
+        if result.is_err() && writer.truncated {
+            write!(
+                writer.inner,
+                "\n... debug output truncated at {} bytes\n",
+                writer.written
+            )?;
+        }
+        writer.inner.flush()
+    }
+
+    /// Write the text report to a file (see write_to). The file is written atomically:
+    /// on failure, any existing file at the path is left untouched.
+    pub fn write_file<P: AsRef<Path>>(&self, limits: &DebugLimits, path: P) -> Result<(), String> {
+        write_atomic_with(path.as_ref(), |file| self.write_to(limits, file))
+            .map_err(|e| format!("Failed writing debug output {:?}: {}", path.as_ref(), e))
+    }
+
+    /// JSON report with, for each result, the key, identification score, account
+    /// number, dates (YYYY-MM-DD), balances, transaction count, errors, warnings,
+    /// orphan lines and stage timings in milliseconds, and the identification
+    /// diagnostics
+    pub fn to_json_value(&self) -> Value {
+        let diagnostics: Vec<Value> = self
+            .diagnostics
+            .iter()
+            .map(|diagnostic| {
+                let missing: Vec<Value> = diagnostic
+                    .missing
+                    .iter()
+                    .map(|missing| {
+                        json!({
+                            "term": missing.term,
+                            "nearest": missing.nearest,
+                            "nearest_page": missing.nearest_page,
+                        })
+                    })
+                    .collect();
+                json!({
+                    "key": diagnostic.key,
+                    "matched": diagnostic.matched,
+                    "missing": missing,
+                })
+            })
+            .collect();
+        let results: Vec<Value> = self
+            .statement_datas
+            .iter()
+            .map(|data| {
+                let date = |ms: Option<i64>| {
+                    ms.and_then(|ms| data.to_naive_date(ms))
+                        .map(|date| date.format("%Y-%m-%d").to_string())
+                };
+                let orphan_lines: Vec<Value> = data
+                    .orphan_lines
+                    .iter()
+                    .map(|line| {
+                        json!({
+                            "page": line.page,
+                            "x1": line.x1,
+                            "y1": line.y1,
+                            "text": line.text,
+                        })
+                    })
+                    .collect();
+                let timings: Map<String, Value> = data
+                    .timings
+                    .stages()
+                    .iter()
+                    .map(|(stage, duration)| {
+                        (stage.to_string(), json!(duration.as_secs_f64() * 1000.0))
+                    })
+                    .collect();
+                json!({
+                    "key": data.key,
+                    "identification_score": data.identification_score,
+                    "account_number": data.account_number,
+                    "start_date": date(data.start_date),
+                    "end_date": date(data.end_date),
+                    "opening_balance": data.opening_balance,
+                    "closing_balance": data.closing_balance,
+                    "transaction_count": data.proto_transactions.len(),
+                    "errors": data.errors,
+                    "warnings": data.warnings,
+                    "orphan_lines": orphan_lines,
+                    "timings_ms": timings,
+                })
+            })
+            .collect();
+        json!({
+            "error": self.error,
+            "results": results,
+            "diagnostics": diagnostics,
+        })
+    }
+
+    /// JSON report as a string (see to_json_value)
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(&self.to_json_value())
+            .map_err(|e| format!("JSON write error: {}", e))
+    }
+}
+
+/// Parse non-tokenised text items with the configs, applying all fixers, and collect
+/// the results in a debug report. See text_items_to_statement_datas for
+/// retry_text_order. The pre-parse timings (text extraction and statement typing) are
+/// added to the timings of each result. If sources is set, the text items read into
+/// each statement field and transaction are recorded.
+pub fn text_items_to_debug_report(
     items: &Vec<TextItem>,
     configs: &Vec<StatementConfig>,
-) -> Result<String, String> {
-    // Write debug information to the output file
-    let mut output = String::new();
-    output.push_str("Debug output\n");
-
-    match text_items_to_statement_datas(items, configs) {
-        Ok(statement_data_results) => {
-            output.push_str(&format!(
-                "Found {} StatementData result(s)\n\n",
-                statement_data_results.len()
-            ));
-
-            for (i, data) in statement_data_results.iter().enumerate() {
-                output.push_str(&format!("=== StatementData Result {} ===\n", i + 1));
-                output.push_str(&data.to_string());
-                output.push('\n');
+    retry_text_order: bool,
+    sources: bool,
+    pre_parse_timings: &ParseTimings,
+) -> DebugReport {
+    match text_items_to_statement_datas(items, configs, FixersMode::All, retry_text_order, sources)
+    {
+        Ok(mut statement_datas) => {
+            for data in &mut statement_datas {
+                data.timings.add(pre_parse_timings);
+            }
+            DebugReport {
+                error: None,
+                statement_datas,
+                diagnostics: Vec::new(),
             }
         }
-        Err(error) => {
-            output.push_str("Error: Failed to identify statement type or parse text items\n");
-            output.push_str(&format!("Error details: {}\n\n", error));
+        Err(error) => DebugReport {
+            error: Some(error),
+            statement_datas: Vec::new(),
+            diagnostics: Vec::new(),
+        },
+    }
+}
+
+/// Writer of the debug output to an io::Write, stopping at the size limit
+struct DebugWriter<W: io::Write> {
+    inner: W,
+    written: usize,
+    max_bytes: Option<usize>,
+    truncated: bool,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for DebugWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.truncated {
+            return Err(fmt::Error);
+        }
+        let mut s = s;
+        if let Some(max_bytes) = self.max_bytes
+            && self.written + s.len() > max_bytes
+        {
+            let mut end = max_bytes - self.written;
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            s = &s[..end];
+            self.truncated = true;
+        }
+        if let Err(e) = self.inner.write_all(s.as_bytes()) {
+            self.error = Some(e);
+            return Err(fmt::Error);
         }
+        self.written += s.len();
+        if self.truncated {
+            return Err(fmt::Error);
+        }
+        Ok(())
+    }
+}
+
+/// Parse non-tokenised text items and stream the debug information to a writer,
+/// using provided statement configurations (see text_items_to_debug_report and
+/// DebugReport::write_to). If sources is set, the text items read into each statement
+/// field and transaction are listed.
+pub fn text_items_to_debug<W: io::Write>(
+    items: &Vec<TextItem>,
+    configs: &Vec<StatementConfig>,
+    retry_text_order: bool,
+    sources: bool,
+    pre_parse_timings: &ParseTimings,
+    limits: &DebugLimits,
+    out: W,
+) -> io::Result<()> {
+    text_items_to_debug_report(items, configs, retry_text_order, sources, pre_parse_timings)
+        .write_to(limits, out)
+}
+
+/// Parse non-tokenised text items and write the debug information to a file (see
+/// text_items_to_debug and DebugReport::write_file).
+pub fn text_items_to_debug_file<P: AsRef<Path>>(
+    items: &Vec<TextItem>,
+    configs: &Vec<StatementConfig>,
+    retry_text_order: bool,
+    sources: bool,
+    pre_parse_timings: &ParseTimings,
+    limits: &DebugLimits,
+    path: P,
+) -> Result<(), String> {
+    text_items_to_debug_report(items, configs, retry_text_order, sources, pre_parse_timings)
+        .write_file(limits, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::typer::MissingTerm;
+    use crate::parsers::flows::config_json_file_to_config::from_json_str;
+    use crate::parsers::flows::layout_to_text_items::layout_to_text_items;
+    use crate::test_fixtures::patch;
+
+    const CONFIG: &str = include_str!("../../../tests/fixtures/test3_config.json");
+    const LAYOUT: &str = include_str!("../../../tests/fixtures/test3_layout.txt");
+
+    /// Debug output of the test3 statement page repeated 200 times, parsed by two
+    /// configs with the same account terms
+    fn pathological_debug(limits: &DebugLimits) -> String {
+        let layout: String = (0..200)
+            .map(|page| patch(LAYOUT, &[("[Page 0]", &format!("[Page {}]", page))]))
+            .collect();
+        let items = layout_to_text_items(&layout).unwrap();
+        let cfg = from_json_str(CONFIG).unwrap();
+        let mut other = cfg.clone();
+        other.key = "au__ssb__savings__2".to_string();
+        let mut output = Vec::new();
+        text_items_to_debug(
+            &items,
+            &vec![cfg, other],
+            false,
+            true,
+            &ParseTimings::new(),
+            limits,
+            &mut output,
+        )
+        .unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_debug_sections_are_truncated() {
+        let limits = DebugLimits {
+            max_section_lines: 50,
+            max_bytes: None,
+        };
+        let output = pathological_debug(&limits);
+
+        assert!(output.starts_with("Debug output\nFound 2 StatementData result(s)\n\n"));
+        assert!(output.contains("=== StatementData Result 2 ==="));
+        assert!(output.contains("    Page 49: 50 text items"));
+        assert!(!output.contains("    Page 50: 50 text items"));
+        assert_eq!(
+            output
+                .matches("    ... 150 more line(s) truncated\n")
+                .count(),
+            2
+        );
+        assert!(output.contains("  Transaction Sources:\n    1: page 0"));
+    }
+
+    #[test]
+    fn test_debug_output_is_truncated_at_max_bytes() {
+        let limits = DebugLimits {
+            max_section_lines: 10000,
+            max_bytes: Some(5_000),
+        };
+        let output = pathological_debug(&limits);
+
+        let (written, marker) = output
+            .rsplit_once("\n... debug output truncated at ")
+            .unwrap();
+        assert_eq!(written.len(), 5_000);
+        assert_eq!(marker, "5000 bytes\n");
+        assert!(!output.contains("=== StatementData Result 2 ==="));
+    }
+
+    #[test]
+    fn test_debug_report_json_matches_text() {
+        let items = layout_to_text_items(LAYOUT).unwrap();
+        let cfg = from_json_str(CONFIG).unwrap();
+        let mut report =
+            text_items_to_debug_report(&items, &vec![cfg], false, false, &ParseTimings::new());
+        report.statement_datas[0].identification_score = Some(0.8);
+
+        let mut text = String::new();
+        report
+            .write_text(&mut text, &DebugLimits::default())
+            .unwrap();
+        assert!(text.contains("  Identification Score: 0.80\n"), "{}", text);
+        assert!(text.contains("  Opening Balance: 1000.00\n"), "{}", text);
+        assert!(text.contains("  Start Date: 01 Apr 2024\n"), "{}", text);
+
+        let value: Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(value["error"], Value::Null);
+        let result = &value["results"][0];
+        assert_eq!(result["key"], json!("au__ssb__savings__1"));
+        assert_eq!(result["identification_score"], json!(0.8));
+        assert_eq!(result["start_date"], json!("2024-04-01"));
+        assert_eq!(result["opening_balance"], json!(1000.0));
+        assert_eq!(result["closing_balance"], json!(1105.5));
+        assert_eq!(result["transaction_count"], json!(3));
+        assert_eq!(result["errors"], json!([]));
+        assert!(result["timings_ms"]["transactions"].is_number());
+    }
+
+    #[test]
+    fn test_debug_report_renders_parse_error() {
+        let report = DebugReport {
+            error: Some("Invalid fixers mode".to_string()),
+            statement_datas: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+
+        let mut text = String::new();
+        report
+            .write_text(&mut text, &DebugLimits::default())
+            .unwrap();
+        assert!(text.ends_with("Error details: Invalid fixers mode\n\n"));
+        assert_eq!(
+            report.to_json_value()["error"],
+            json!("Invalid fixers mode")
+        );
+        assert_eq!(report.to_json_value()["results"], json!([]));
+    }
+
+    #[test]
+    fn test_redact_masks_diagnostics_and_error() {
+        let mut report = DebugReport {
+            error: Some("Account 062-000 1234 5678 of JANE CITIZEN".to_string()),
+            statement_datas: Vec::new(),
+            diagnostics: vec![TyperDiagnostic {
+                key: "au__bank__savings__1".to_string(),
+                matched: vec!["bank".to_string()],
+                missing: vec![MissingTerm {
+                    term: "Savings Account".to_string(),
+                    nearest: Some("Savings 062-000 1234 5678".to_string()),
+                    nearest_page: Some(1),
+                }],
+            }],
+        };
+
+        report.redact(&Redaction::new(&["JANE CITIZEN".to_string()]).unwrap());
+
+        assert_eq!(report.error.as_deref(), Some("Account ****5678 of ****"));
+        assert_eq!(
+            report.diagnostics[0].missing[0].nearest.as_deref(),
+            Some("Savings ****5678")
+        );
+        let mut text = String::new();
+        report
+            .write_text(&mut text, &DebugLimits::default())
+            .unwrap();
+        assert!(!text.contains("1234 5678"));
     }
-    Ok(output)
 }
@@ -0,0 +1,308 @@
+use crate::configs::typer::TermEvidence;
+use crate::fixers::FixersMode;
+use crate::parsers::flows::statement_data_to_json::statement_data_to_json_value;
+use crate::parsers::flows::text_items_to_statement_datas::text_items_to_statement_datas;
+use crate::structs::ParseTimings;
+use crate::structs::StatementConfig;
+use crate::structs::StatementData;
+use crate::structs::TextItem;
+use serde_json::{Map, Value, json};
+
+/// Explanation of the parse of a statement with every candidate config (see
+/// text_items_to_explanation)
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    /// Account terms found and missing for each config key with any term found
+    pub evidence: Vec<TermEvidence>,
+    /// Statement data of each candidate config (one per account section of a
+    /// multi_account config), whether or not error-free
+    pub statement_datas: Vec<StatementData>,
+}
+
+impl Explanation {
+    /// Human-readable report: the identification evidence, then for each result a
+    /// summary, the transaction column bounds, the dropped rows, the closing balance
+    /// candidates, the excluded descriptions and the debug output
+    /// (errors, page coverage, warnings and timings)
+    pub fn to_text(&self) -> String {
+        let mut output = String::new();
+        output.push_str("Explain output\n");
+        output.push_str("Identification:\n");
+        if self.evidence.is_empty() {
+            output.push_str("  No account terms found\n");
+        }
+        for evidence in &self.evidence {
+            if evidence.missing.is_empty() {
+                output.push_str(&format!("  {}: identified\n", evidence.key));
+            } else {
+                output.push_str(&format!("  {}: not identified\n", evidence.key));
+            }
+            output.push_str(&format!("    Found: {}\n", quote_terms(&evidence.found)));
+            if !evidence.missing.is_empty() {
+                output.push_str(&format!(
+                    "    Missing: {}\n",
+                    quote_terms(&evidence.missing)
+                ));
+            }
+        }
+        output.push_str(&format!(
+            "\nFound {} StatementData result(s)\n\n",
+            self.statement_datas.len()
+        ));
+
+        for (i, data) in self.statement_datas.iter().enumerate() {
+            output.push_str(&format!("=== StatementData Result {} ===\n", i + 1));
+            output.push_str(&format!(
+                "Summary: {}, {} error(s), {} transaction(s), {} computed balance(s), {} inferred amount(s)\n",
+                data.key.as_deref().unwrap_or("Not set"),
+                data.errors.len(),
+                data.proto_transactions.len(),
+                data.computed_balance_count(),
+                data.inferred_amount_count()
+            ));
+            output.push_str("Column Bounds:\n");
+            if data.column_bounds.is_empty() {
+                output.push_str("  No column headers read\n");
+            }
+            for (field, (x_lower, x_upper)) in &data.column_bounds {
+                output.push_str(&format!("  {}: {} to {}\n", field, x_lower, x_upper));
+            }
+            if !data.dropped_rows.is_empty() {
+                output.push_str("Dropped Rows:\n");
+                for row in &data.dropped_rows {
+                    output.push_str(&format!("  - {}\n", row));
+                }
+            }
+            if !data.closing_balance_candidates.is_empty() {
+                output.push_str("Closing Balance Candidates:\n");
+                for candidate in &data.closing_balance_candidates {
+                    output.push_str(&format!("  - {}\n", candidate));
+                }
+            }
+            if !data.excluded_descriptions.is_empty() {
+                output.push_str("Excluded Descriptions:\n");
+                for description in &data.excluded_descriptions {
+                    output.push_str(&format!("  - \"{}\"\n", description));
+                }
+            }
+            output.push_str(&data.to_string());
+            output.push('\n');
+        }
+        output
+    }
+
+    /// JSON report with the same content as to_text, with the statement data of each
+    /// result as in statement_data_to_json (including incomplete transactions)
+    pub fn to_json(&self) -> Result<String, String> {
+        let identification: Vec<Value> = self
+            .evidence
+            .iter()
+            .map(|evidence| {
+                json!({
+                    "key": evidence.key,
+                    "identified": evidence.missing.is_empty(),
+                    "found": evidence.found,
+                    "missing": evidence.missing,
+                })
+            })
+            .collect();
+        let results: Vec<Value> = self
+            .statement_datas
+            .iter()
+            .map(|data| {
+                let column_bounds: Map<String, Value> = data
+                    .column_bounds
+                    .iter()
+                    .map(|(field, (x_lower, x_upper))| (field.clone(), json!([x_lower, x_upper])))
+                    .collect();
+                let page_coverage: Vec<Value> = data
+                    .page_coverage
+                    .iter()
+                    .map(|coverage| {
+                        json!({
+                            "page": coverage.page,
+                            "text_items": coverage.text_items,
+                            "consumed_items": coverage.consumed_items,
+                            "transactions": coverage.transactions,
+                        })
+                    })
+                    .collect();
+                let timings: Map<String, Value> = data
+                    .timings
+                    .stages()
+                    .iter()
+                    .map(|(stage, duration)| {
+                        (stage.to_string(), json!(duration.as_secs_f64() * 1000.0))
+                    })
+                    .collect();
+                json!({
+                    "statement_data": statement_data_to_json_value(data, true),
+                    "column_bounds": column_bounds,
+                    "dropped_rows": data.dropped_rows,
+                    "closing_balance_candidates": data.closing_balance_candidates,
+                    "excluded_descriptions": data.excluded_descriptions,
+                    "page_coverage": page_coverage,
+                    "timings_ms": timings,
+                })
+            })
+            .collect();
+        let value = json!({
+            "identification": identification,
+            "results": results,
+        });
+        serde_json::to_string_pretty(&value).map_err(|e| format!("JSON write error: {}", e))
+    }
+}
+
+/// Quote and comma-separate account terms
+fn quote_terms(terms: &[String]) -> String {
+    terms
+        .iter()
+        .map(|term| format!("\"{}\"", term))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parse non-tokenised text items with every candidate config, applying all fixers,
+/// and combine the results with the identification evidence of the statement typer.
+/// See text_items_to_statement_datas for retry_text_order. The pre-parse timings (text
+/// extraction and statement typing) are added to the timings of each result.
+pub fn text_items_to_explanation(
+    items: &Vec<TextItem>,
+    evidence: Vec<TermEvidence>,
+    configs: &Vec<StatementConfig>,
+    retry_text_order: bool,
+    pre_parse_timings: &ParseTimings,
+) -> Result<Explanation, String> {
+    let mut statement_datas =
+        text_items_to_statement_datas(items, configs, FixersMode::All, retry_text_order, false)?;
+    for data in &mut statement_datas {
+        data.timings.add(pre_parse_timings);
+    }
+    Ok(Explanation {
+        evidence,
+        statement_datas,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::typer::StatementTyper;
+    use crate::parsers::flows::config_json_file_to_config::from_json_str;
+    use crate::parsers::flows::layout_to_text_items::layout_to_text_items;
+    use crate::test_fixtures::patch;
+
+    const CONFIG: &str = include_str!("../../../tests/fixtures/test3_config.json");
+    const LAYOUT: &str = include_str!("../../../tests/fixtures/test3_layout.txt");
+
+    fn explain(layout: &str) -> Explanation {
+        let items = layout_to_text_items(layout).unwrap();
+        let cfg = from_json_str(CONFIG).unwrap();
+        let mut typer = StatementTyper::new();
+        typer.add_account_terms(&cfg.key, &cfg.account_terms, false);
+        let evidence = typer.term_evidence(&items);
+        text_items_to_explanation(&items, evidence, &vec![cfg], false, &ParseTimings::new())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_explanation_reports_evidence_and_geometry() {
+        let explanation = explain(LAYOUT);
+
+        let text = explanation.to_text();
+        assert!(
+            text.contains("  au__ssb__savings__1: identified\n"),
+            "{}",
+            text
+        );
+        assert!(text.contains("Summary: au__ssb__savings__1, 0 error(s), 3 transaction(s)"));
+        assert!(text.contains("  amount: 440 to 460\n"), "{}", text);
+        assert!(!text.contains("Dropped Rows:"));
+
+        let value: Value = serde_json::from_str(&explanation.to_json().unwrap()).unwrap();
+        assert_eq!(value["identification"][0]["identified"], json!(true));
+        let result = &value["results"][0];
+        assert_eq!(result["column_bounds"]["balance"], json!([520, 540]));
+        assert_eq!(
+            result["statement_data"]["transactions"]
+                .as_array()
+                .unwrap()
+                .len(),
+            3
+        );
+        assert_eq!(result["page_coverage"][0]["transactions"], json!(3));
+    }
+
+    #[test]
+    fn test_explanation_reports_dropped_rows() {
+        let layout = patch(LAYOUT, &[("[\"-120.00\",408,450,252,240]", "")]);
+
+        let explanation = explain(&layout);
+
+        let dropped = &explanation.statement_datas[0].dropped_rows;
+        assert_eq!(dropped.len(), 1, "{:?}", dropped);
+        assert!(
+            dropped[0].starts_with("Page 0: date 03 Apr 2024, description \"RENT PAYMENT"),
+            "{}",
+            dropped[0]
+        );
+        assert!(
+            dropped[0].ends_with("(missing amount, balance)"),
+            "{}",
+            dropped[0]
+        );
+        assert!(explanation.to_text().contains("Dropped Rows:\n  - Page 0:"));
+    }
+
+    #[test]
+    fn test_explanation_reports_skipped_closing_balance() {
+        let layout = patch(
+            LAYOUT,
+            &[(
+                "[Page 0]\n",
+                "[Page 0]\n[\"Projected\",380,434,20,8][\"balance\",437,479,20,8]\n[\"Closing\",380,422,34,22][\"balance:\",425,473,34,22][\"$1,050.00\",480,534,34,22]\n",
+            )],
+        );
+        let mut cfg = from_json_str(CONFIG).unwrap();
+        let items = layout_to_text_items(&layout).unwrap();
+        let evidence = Vec::new();
+
+        // The projected balance of the footnote comes first in scan order
+        let explanation = text_items_to_explanation(
+            &items,
+            evidence.clone(),
+            &vec![cfg.clone()],
+            false,
+            &ParseTimings::new(),
+        )
+        .unwrap();
+        assert_eq!(explanation.statement_datas[0].closing_balance, Some(1050.0));
+        assert!(
+            !explanation
+                .to_text()
+                .contains("Closing Balance Candidates:")
+        );
+
+        cfg.closing_balance_exclude_terms = vec!["Projected balance".to_string()];
+        let explanation =
+            text_items_to_explanation(&items, evidence, &vec![cfg], false, &ParseTimings::new())
+                .unwrap();
+
+        let data = &explanation.statement_datas[0];
+        assert_eq!(data.closing_balance, Some(1105.5));
+        assert!(data.errors.is_empty(), "{:?}", data.errors);
+        assert_eq!(
+            data.closing_balance_candidates,
+            vec![
+                "Page 0: 1050 (skipped: near exclude term 'Projected balance')",
+                "Page 0: 1105.5 (used)",
+            ]
+        );
+        assert!(
+            explanation
+                .to_text()
+                .contains("Closing Balance Candidates:\n  - Page 0: 1050 (skipped")
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use crate::structs::TextItem;
 use crate::structs::text_items::sort_items;
+use chrono::Utc;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
 
 /// Converts a collection of TextItems into a structured layout text format
 pub fn text_items_to_layout(
@@ -12,7 +15,73 @@ pub fn text_items_to_layout(
     }
 
     let sorted_items = sort_items(items, x_gap, y_bin);
+    Ok(write_layout(&sorted_items))
+}
+
+/// Converts a collection of TextItems into a structured layout text format, preceded by
+/// a comment header recording how it was generated (see layout_header). The generation
+/// timestamp can be omitted for byte-stable output.
+pub fn text_items_to_layout_with_header(
+    items: &Vec<TextItem>,
+    y_bin: f32,
+    x_gap: f32,
+    timestamp: bool,
+) -> Result<String, String> {
+    let sorted_items = sort_items(items, x_gap, y_bin);
+    let generated = if timestamp {
+        Some(Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string())
+    } else {
+        None
+    };
+    let mut output = layout_header(&sorted_items, y_bin, x_gap, generated.as_deref());
+    if !sorted_items.is_empty() {
+        output.push_str(&write_layout(&sorted_items));
+    }
+    Ok(output)
+}
+
+/// Get the comment header of a layout text file: the crate version, generation timestamp
+/// (if given), fix_text_order values applied, most common line height and the number of
+/// text items in total and per page. Header lines start with "#" and are ignored when
+/// the layout text is read.
+pub fn layout_header(
+    items: &[TextItem],
+    y_bin: f32,
+    x_gap: f32,
+    generated: Option<&str>,
+) -> String {
+    let mut header = String::from("# transtractor layout text\n");
+    header.push_str(&format!("# version: {}\n", env!("CARGO_PKG_VERSION")));
+    if let Some(generated) = generated {
+        header.push_str(&format!("# generated: {}\n", generated));
+    }
+    header.push_str(&format!("# fix_text_order: {:?}\n", vec![y_bin, x_gap]));
 
+    // Most common line height, preferring the smaller height on ties
+    let mut heights: HashMap<i32, usize> = HashMap::new();
+    for item in items {
+        *heights.entry((item.y2 - item.y1).abs()).or_insert(0) += 1;
+    }
+    if let Some((height, _)) = heights
+        .iter()
+        .max_by_key(|(height, count)| (**count, std::cmp::Reverse(**height)))
+    {
+        header.push_str(&format!("# line height: {}\n", height));
+    }
+
+    header.push_str(&format!("# text items: {}\n", items.len()));
+    let mut page_counts: BTreeMap<i32, usize> = BTreeMap::new();
+    for item in items {
+        *page_counts.entry(item.page).or_insert(0) += 1;
+    }
+    for (page, count) in page_counts {
+        header.push_str(&format!("# page {}: {} text items\n", page, count));
+    }
+    header
+}
+
+/// Write sorted TextItems in the layout text format
+fn write_layout(sorted_items: &[TextItem]) -> String {
     let mut output = String::new();
     let mut current_page = sorted_items[0].page;
     let mut last_y1 = sorted_items[0].y1;
@@ -21,7 +90,7 @@ pub fn text_items_to_layout(
     // Start with the first page marker
     output.push_str(&format!("[Page {}]", current_page));
 
-    for item in &sorted_items {
+    for item in sorted_items {
         // Check if we're on a new page
         if item.page != current_page {
             current_page = item.page;
@@ -42,11 +111,32 @@ pub fn text_items_to_layout(
         }
 
         // Print the item in the format [text, x1, x2, y1, y2]
-        output.push_str(&format!(
-            "[\"{}\",{},{},{},{}]",
-            item.text, item.x1, item.x2, item.y1, item.y2
-        ));
+        output.push_str(&item.to_layout_block());
     }
 
-    Ok(output)
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_header() {
+        let items = vec![
+            TextItem::new("Alpha".to_string(), 1, 3, 5, 15, 0),
+            TextItem::new("Beta".to_string(), 8, 10, 12, 22, 0),
+            TextItem::new("Gamma".to_string(), 8, 30, 12, 42, 2),
+        ];
+
+        let header = layout_header(&items, 5.0, 2.0, None);
+
+        assert_eq!(
+            header,
+            format!(
+                "# transtractor layout text\n# version: {}\n# fix_text_order: [5.0, 2.0]\n# line height: 12\n# text items: 3\n# page 0: 2 text items\n# page 2: 1 text items\n",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+    }
 }
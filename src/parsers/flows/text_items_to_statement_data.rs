@@ -1,70 +1,866 @@
+use crate::parsers::base::ParserPrimer;
 use crate::parsers::statement::{
-    AccountNumberParser, ClosingBalanceParser, OpeningBalanceParser, StartDateParser,
+    AccountNumberParser, ClosingBalanceParser, EndDateParser, MinimumPaymentParser,
+    NoTransactionsParser, OpeningBalanceParser, PaymentDueDateParser, StartDateParser,
     TransactionParser,
 };
+use crate::structs::ItemClaim;
+use crate::structs::ParseTimings;
 use crate::structs::StatementConfig;
 use crate::structs::StatementData;
 use crate::structs::TextItem;
+use crate::structs::page_coverage::get_page_coverage;
+use crate::structs::table_lines::get_table_lines;
 use crate::structs::text_items::get_text_item_buffer;
+use std::time::Instant;
 
-/// Converts a list of TextItems into structured StatementData
+/// Record of the account number parser over a parse: the lookahead used, and the
+/// number of items it consumed at each position visited by the parse loop.
+#[derive(Debug, Clone, Default)]
+pub struct AccountNumberTrace {
+    max_lookahead: usize,
+    steps: Vec<(usize, usize)>,
+}
+
+/// Converts a list of TextItems into structured StatementData, recording the time
+/// spent in each parser
 pub fn text_items_to_statement_data(
     config: &StatementConfig,
     text_items: &[TextItem],
 ) -> StatementData {
-    let mut statement_data = StatementData::new();
-
-    // Initialize parsers
-    let mut account_number_parser = AccountNumberParser::new(config);
-    let mut opening_balance_parser = OpeningBalanceParser::new(config);
-    let mut closing_balance_parser = ClosingBalanceParser::new(config);
-    let mut start_date_parser = StartDateParser::new(config);
-    let mut transaction_parser = TransactionParser::new(config);
-
-    // Other settings based on parsers
-    // Compute max lookahead across all parsers generically to keep this scalable
-    let lookaheads = [
-        account_number_parser.get_max_lookahead(),
-        opening_balance_parser.get_max_lookahead(),
-        closing_balance_parser.get_max_lookahead(),
-        start_date_parser.get_max_lookahead(),
-        transaction_parser.get_max_lookahead(),
-    ];
-    let max_lookahead = *lookaheads.iter().max().unwrap_or(&0);
-
-    // Iterate through text items, attempting to match account_terms
-    let len = text_items.len();
-    if len == 0 {
-        return statement_data;
-    }
-    let mut i: usize = 0;
-    while i < len {
-        let buffer_size = max_lookahead.min(len - i);
+    text_items_to_statement_data_traced(config, text_items, false).0
+}
+
+/// Config with its statement parser built ahead of parsing. The parser holds the state
+/// derived from the config alone (compiled patterns, format parsers, primer terms and
+/// lookaheads), and a copy is parsed with for each document, so that this state is
+/// built once per config rather than once per document.
+#[derive(Clone)]
+pub struct PreparedConfig {
+    parser: StatementDataParser,
+}
+
+impl PreparedConfig {
+    pub fn new(config: &StatementConfig) -> Self {
+        Self {
+            parser: StatementDataParser::new(config),
+        }
+    }
+
+    /// Get an unused parser of the config
+    pub fn parser(&self) -> StatementDataParser {
+        self.parser.clone()
+    }
+}
+
+/// Reuse StatementData parsed from the same text items with a config that differs
+/// from this one only in account number fields. The account number parser is replayed
+/// at each position visited by the original parse loop. Returns None unless it
+/// consumes exactly the same items as in the original parse, since the other
+/// parsers would then see different items.
+pub fn reuse_statement_data(
+    config: &StatementConfig,
+    text_items: &[TextItem],
+    parsed: &StatementData,
+    trace: &AccountNumberTrace,
+) -> Option<StatementData> {
+    reuse_prepared_statement_data(&PreparedConfig::new(config), text_items, parsed, trace)
+}
+
+/// Reuse StatementData parsed from the same text items with a prepared config (see
+/// reuse_statement_data)
+pub fn reuse_prepared_statement_data(
+    prepared: &PreparedConfig,
+    text_items: &[TextItem],
+    parsed: &StatementData,
+    trace: &AccountNumberTrace,
+) -> Option<StatementData> {
+    let started = Instant::now();
+    let mut account_number_parser = prepared.parser.account_number_parser.clone();
+    let max_lookahead = prepared.parser.max_lookahead;
+    if max_lookahead != trace.max_lookahead {
+        return None;
+    }
+
+    // The account number parser only reads the account number from the data
+    let mut account_data = StatementData::new();
+    for &(i, consumed) in &trace.steps {
+        let buffer_size = max_lookahead.min(text_items.len() - i);
+        let buffer = get_text_item_buffer(text_items, i, buffer_size);
+        if account_number_parser.parse_items(&buffer, &mut account_data) != consumed {
+            return None;
+        }
+    }
+
+    let mut statement_data = parsed.clone();
+    statement_data.account_number = account_data.account_number;
+    statement_data.timings = ParseTimings::new();
+    statement_data.timings.account_number = started.elapsed();
+    Some(statement_data)
+}
+
+/// Converts a list of TextItems into structured StatementData, also returning the
+/// account number trace needed to reuse the result (see reuse_statement_data).
+/// If record_sources is set, the text items consumed by each parser are recorded
+/// (see StatementDataParser::with_source_items).
+pub fn text_items_to_statement_data_traced(
+    config: &StatementConfig,
+    text_items: &[TextItem],
+    record_sources: bool,
+) -> (StatementData, AccountNumberTrace) {
+    text_items_to_statement_data_prepared(&PreparedConfig::new(config), text_items, record_sources)
+}
+
+/// Converts a list of TextItems into structured StatementData with a prepared config
+/// (see text_items_to_statement_data_traced)
+pub fn text_items_to_statement_data_prepared(
+    prepared: &PreparedConfig,
+    text_items: &[TextItem],
+    record_sources: bool,
+) -> (StatementData, AccountNumberTrace) {
+    let mut parser = prepared.parser().with_source_items(record_sources);
+    while parser.step(text_items) {}
+    parser.finish(text_items)
+}
+
+/// The statement parsers run over a list of tokenised TextItems, one position at
+/// a time, recording the parsed values in statement_data. Transactions are added
+/// to statement_data once complete, so they can be taken as parsing proceeds.
+#[derive(Clone)]
+pub struct StatementDataParser {
+    account_number_parser: AccountNumberParser,
+    opening_balance_parser: OpeningBalanceParser,
+    closing_balance_parser: ClosingBalanceParser,
+    start_date_parser: StartDateParser,
+    end_date_parser: EndDateParser,
+    minimum_payment_parser: MinimumPaymentParser,
+    payment_due_date_parser: PaymentDueDateParser,
+    no_transactions_parser: NoTransactionsParser,
+    transaction_parser: TransactionParser,
+    max_lookahead: usize,
+    position: usize,
+    table_found: bool,
+    /// Position of the first item after the transaction table, once the stop term is
+    /// found
+    table_end: Option<usize>,
+    trace: AccountNumberTrace,
+    record_sources: bool,
+    pub statement_data: StatementData,
+}
+
+impl StatementDataParser {
+    pub fn new(config: &StatementConfig) -> Self {
+        // Initialize parsers
+        let account_number_parser = AccountNumberParser::new(config);
+        let opening_balance_parser = OpeningBalanceParser::new(config);
+        let closing_balance_parser = ClosingBalanceParser::new(config);
+        let start_date_parser = StartDateParser::new(config);
+        let end_date_parser = EndDateParser::new(config);
+        let minimum_payment_parser = MinimumPaymentParser::new(config);
+        let payment_due_date_parser = PaymentDueDateParser::new(config);
+        let no_transactions_parser = NoTransactionsParser::new(config);
+        let transaction_parser = TransactionParser::new(config);
+
+        // Other settings based on parsers
+        // Compute max lookahead across all parsers generically to keep this scalable
+        let lookaheads = [
+            account_number_parser.get_max_lookahead(),
+            opening_balance_parser.get_max_lookahead(),
+            closing_balance_parser.get_max_lookahead(),
+            start_date_parser.get_max_lookahead(),
+            end_date_parser.get_max_lookahead(),
+            minimum_payment_parser.get_max_lookahead(),
+            payment_due_date_parser.get_max_lookahead(),
+            no_transactions_parser.get_max_lookahead(),
+            transaction_parser.get_max_lookahead(),
+        ];
+        let max_lookahead = *lookaheads.iter().max().unwrap_or(&0);
+
+        StatementDataParser {
+            account_number_parser,
+            opening_balance_parser,
+            closing_balance_parser,
+            start_date_parser,
+            end_date_parser,
+            minimum_payment_parser,
+            payment_due_date_parser,
+            no_transactions_parser,
+            transaction_parser,
+            max_lookahead,
+            position: 0,
+            table_found: false,
+            table_end: None,
+            trace: AccountNumberTrace {
+                max_lookahead,
+                steps: Vec::new(),
+            },
+            record_sources: false,
+            statement_data: StatementData::new(),
+        }
+    }
+
+    /// Record the text items consumed by each statement-level parser in the field
+    /// sources of the statement data, and those of each transaction in its source
+    /// items. Off by default to avoid copying the items.
+    pub fn with_source_items(mut self, record_sources: bool) -> Self {
+        self.record_sources = record_sources;
+        self.transaction_parser = self.transaction_parser.with_source_items(record_sources);
+        self
+    }
+
+    /// Parse the text items at the current position, moving past any items consumed.
+    /// Returns false once all text items have been parsed.
+    pub fn step(&mut self, text_items: &[TextItem]) -> bool {
+        let len = text_items.len();
+        let i = self.position;
+        if i >= len {
+            return false;
+        }
+        let statement_data = &mut self.statement_data;
+        let buffer_size = self.max_lookahead.min(len - i);
         let buffer = get_text_item_buffer(text_items, i, buffer_size);
         let mut consumed = 0usize;
-        // Try parsers in a stable order: account number -> start date -> opening balance -> closing balance
+        let mut consumer = "";
+        // Try parsers in a stable order: account number -> start date -> end date -> opening balance -> closing balance
+        if consumed == 0 {
+            let started = Instant::now();
+            consumed = self
+                .account_number_parser
+                .parse_items(&buffer, statement_data);
+            statement_data.timings.account_number += started.elapsed();
+            self.trace.steps.push((i, consumed));
+            consumer = "account_number";
+        }
+        if consumed == 0 {
+            let started = Instant::now();
+            consumed = self.start_date_parser.parse_items(&buffer, statement_data);
+            statement_data.timings.start_date += started.elapsed();
+            consumer = "start_date";
+        }
         if consumed == 0 {
-            consumed = account_number_parser.parse_items(&buffer, &mut statement_data);
+            let started = Instant::now();
+            consumed = self.end_date_parser.parse_items(&buffer, statement_data);
+            statement_data.timings.end_date += started.elapsed();
+            consumer = "end_date";
         }
         if consumed == 0 {
-            consumed = start_date_parser.parse_items(&buffer, &mut statement_data);
+            let started = Instant::now();
+            consumed = self
+                .opening_balance_parser
+                .parse_items(&buffer, statement_data);
+            statement_data.timings.opening_balance += started.elapsed();
+            consumer = "opening_balance";
         }
         if consumed == 0 {
-            consumed = opening_balance_parser.parse_items(&buffer, &mut statement_data);
+            let started = Instant::now();
+            consumed = self
+                .closing_balance_parser
+                .parse_items(&buffer, statement_data);
+            statement_data.timings.closing_balance += started.elapsed();
+            consumer = "closing_balance";
         }
         if consumed == 0 {
-            consumed = closing_balance_parser.parse_items(&buffer, &mut statement_data);
+            let started = Instant::now();
+            consumed = self
+                .minimum_payment_parser
+                .parse_items(&buffer, statement_data);
+            statement_data.timings.minimum_payment += started.elapsed();
+            consumer = "minimum_payment";
         }
         if consumed == 0 {
-            consumed = transaction_parser.parse_items(&buffer, &mut statement_data);
+            let started = Instant::now();
+            consumed = self
+                .payment_due_date_parser
+                .parse_items(&buffer, statement_data);
+            statement_data.timings.payment_due_date += started.elapsed();
+            consumer = "payment_due_date";
         }
+        if consumed == 0 {
+            let in_table = self.transaction_parser.in_table();
+            self.table_found |= in_table;
+            consumed = self.no_transactions_parser.parse_items(&buffer, in_table);
+            consumer = "no_transactions";
+        }
+        if consumed == 0 {
+            let started = Instant::now();
+            let stopped = self.transaction_parser.stop_page().is_some();
+            consumed = self.transaction_parser.parse_items(&buffer, statement_data);
+            statement_data.timings.transactions += started.elapsed();
+            // The table ends at the last stop term, if page-scoped stop terms repeat
+            if !stopped && self.transaction_parser.stop_page().is_some() {
+                self.table_end = Some(i);
+            }
+            if consumed > 0 {
+                let field = self.transaction_parser.last_field();
+                statement_data.add_item_claim(ItemClaim::new(
+                    &format!("transaction_{}", field),
+                    text_items,
+                    i,
+                    i + consumed,
+                ));
+            }
+        } else {
+            // Record statement-level claims, and any transaction value the
+            // transaction parser would also have read from the same items
+            statement_data.add_item_claim(ItemClaim::new(consumer, text_items, i, i + consumed));
+            if self.record_sources {
+                statement_data
+                    .field_sources
+                    .entry(consumer.to_string())
+                    .or_default()
+                    .extend_from_slice(&text_items[i..i + consumed]);
+            }
+            let started = Instant::now();
+            let peeked = self
+                .transaction_parser
+                .peek_value_field(&buffer, statement_data);
+            statement_data.timings.transactions += started.elapsed();
+            if let Some((field, n)) = peeked {
+                statement_data.add_item_claim(ItemClaim::new(
+                    &format!("transaction_{}", field),
+                    text_items,
+                    i,
+                    i + n,
+                ));
+            }
+        }
+
+        // Move past the consumed items, or to the next item if no parser matched
+        self.position += consumed.max(1);
+        true
+    }
+
+    /// Finish parsing, setting the values that depend on the whole statement, and
+    /// return the statement data with the account number trace
+    pub fn finish(mut self, text_items: &[TextItem]) -> (StatementData, AccountNumberTrace) {
+        let mut statement_data = self.statement_data;
+        self.table_found |= self.transaction_parser.in_table();
+        statement_data.no_transactions_declared =
+            self.no_transactions_parser.is_declared(self.table_found);
+        statement_data.transaction_stop_page = self.transaction_parser.stop_page();
+        statement_data.column_bounds = self.transaction_parser.column_bounds();
+        statement_data.orphan_lines = self.transaction_parser.orphan_lines();
+        self.transaction_parser
+            .record_unfinished_row(&mut statement_data);
+        self.closing_balance_parser
+            .finish(text_items, &mut statement_data);
+        statement_data.page_coverage = get_page_coverage(
+            text_items,
+            &statement_data.item_claims,
+            &statement_data.proto_transactions,
+        );
+        statement_data.table_lines =
+            get_table_lines(text_items, &statement_data.item_claims, self.table_end);
+        (statement_data, self.trace)
+    }
+}
+
+/// Split tokenised text items of a multi-account statement into one section per
+/// account, each starting at one of the config's account_section_terms. The items
+/// before the first section (e.g., the statement period in the header) are included
+/// at the start of every section. If no section term is found, all items are
+/// returned as a single section.
+pub fn split_account_sections(
+    config: &StatementConfig,
+    text_items: &[TextItem],
+) -> Vec<Vec<TextItem>> {
+    let starts = find_term_starts(config, &config.account_section_terms, text_items);
+    if starts.is_empty() {
+        return vec![text_items.to_vec()];
+    }
+
+    let header = &text_items[..starts[0]];
+    starts
+        .iter()
+        .enumerate()
+        .map(|(n, &start)| {
+            let end = starts.get(n + 1).copied().unwrap_or(text_items.len());
+            let mut section = header.to_vec();
+            section.extend_from_slice(&text_items[start..end]);
+            section
+        })
+        .collect()
+}
+
+/// Split tokenised text items of a document of several consecutive statements
+/// (multi_statement config) into one segment per statement. To be conservative, a new
+/// statement only starts at a complete header block: every account term found again
+/// after the previous start date term, then a start date term, with an opening balance
+/// term (if the config has any) before the next statement. The segment starts at the
+/// first item of the header block. If no second header block is found, all items are
+/// returned as a single segment.
+pub fn split_statements(config: &StatementConfig, text_items: &[TextItem]) -> Vec<Vec<TextItem>> {
+    let date_starts = find_term_starts(config, &config.start_date_terms, text_items);
+    let term_starts: Vec<Vec<usize>> = config
+        .account_terms
+        .iter()
+        .map(|term| find_term_starts(config, std::slice::from_ref(term), text_items))
+        .collect();
+    let mut boundaries = vec![0];
+    for pair in date_starts.windows(2) {
+        let (previous, next) = (pair[0], pair[1]);
+        // Latest occurrence of each account term between the two start date terms
+        let block_start = term_starts
+            .iter()
+            .map(|starts| {
+                starts
+                    .iter()
+                    .rev()
+                    .find(|&&i| i > previous && i < next)
+                    .copied()
+            })
+            .collect::<Option<Vec<usize>>>()
+            .and_then(|starts| starts.into_iter().min());
+        if let Some(start) = block_start {
+            boundaries.push(start);
+        }
+    }
+    if !config.opening_balance_terms.is_empty() {
+        let balance_starts = find_term_starts(config, &config.opening_balance_terms, text_items);
+        let ends: Vec<usize> = boundaries
+            .iter()
+            .skip(1)
+            .copied()
+            .chain([text_items.len()])
+            .collect();
+        boundaries = boundaries
+            .iter()
+            .zip(ends)
+            .enumerate()
+            .filter(|(n, (start, end))| {
+                *n == 0 || balance_starts.iter().any(|i| i >= start && i < end)
+            })
+            .map(|(_, (start, _))| *start)
+            .collect();
+    }
+    boundaries
+        .iter()
+        .enumerate()
+        .map(|(n, &start)| {
+            let end = boundaries.get(n + 1).copied().unwrap_or(text_items.len());
+            text_items[start..end].to_vec()
+        })
+        .collect()
+}
+
+/// Get the indices of the text items starting each occurrence of the terms
+fn find_term_starts(
+    config: &StatementConfig,
+    terms: &[String],
+    text_items: &[TextItem],
+) -> Vec<usize> {
+    let terms: Vec<&str> = terms.iter().map(|t| t.as_str()).collect();
+    let mut primer = ParserPrimer::new(&terms).with_case_insensitive(config.terms_case_insensitive);
+    let mut starts = Vec::new();
+    let mut i = 0;
+    while i < text_items.len() {
+        let buffer_size = primer.max_lookahead.min(text_items.len() - i);
+        let consumed = primer.parse_items(&get_text_item_buffer(text_items, i, buffer_size));
         if consumed > 0 {
+            starts.push(i);
             i += consumed;
-            continue;
+        } else {
+            i += 1;
+        }
+    }
+    starts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::check_item_claims;
+    use crate::structs::TransactionColumnPosition;
+
+    fn make_text_item(text: &str, x1: i32, x2: i32, y1: i32) -> TextItem {
+        TextItem::new(text.to_string(), x1, y1, x2, y1 + 10, 0)
+    }
+
+    fn make_config() -> StatementConfig {
+        StatementConfig {
+            opening_balance_terms: vec!["Opening balance".to_string()],
+            opening_balance_formats: vec!["format1".to_string()],
+            transaction_terms: vec!["Transactions".to_string()],
+            transaction_formats: vec![vec![
+                "description".to_string(),
+                "amount".to_string(),
+                "balance".to_string(),
+            ]],
+            transaction_description_headers: vec!["Description".to_string()],
+            transaction_amount_formats: vec!["format1".to_string()],
+            transaction_amount_headers: vec!["Amount".to_string()],
+            transaction_amount_alignment: "x2".to_string(),
+            transaction_balance_formats: vec!["format1".to_string()],
+            transaction_balance_headers: vec!["Balance".to_string()],
+            transaction_balance_alignment: "x2".to_string(),
+            ..Default::default()
         }
+    }
+
+    #[test]
+    fn test_opening_balance_in_transaction_table_is_claimed_twice() {
+        let items = vec![
+            make_text_item("Transactions", 100, 180, 100),
+            make_text_item("Description", 100, 160, 120),
+            make_text_item("Amount", 260, 300, 120),
+            make_text_item("Balance", 360, 400, 120),
+            make_text_item("Opening", 100, 140, 140),
+            make_text_item("balance", 142, 180, 140),
+            make_text_item("1,000.00", 260, 300, 140),
+            make_text_item("Payment", 100, 140, 160),
+            make_text_item("50.00", 270, 300, 160),
+            make_text_item("1,050.00", 355, 400, 160),
+        ];
+
+        let mut data = text_items_to_statement_data(&make_config(), &items);
+        check_item_claims(&mut data);
+
+        assert_eq!(data.opening_balance, Some(1000.0));
+        assert_eq!(data.proto_transactions.len(), 1);
+        assert_eq!(data.warnings.len(), 1);
+        assert_eq!(
+            data.warnings[0],
+            "Text items 6..7 ('1,000.00') on page 0 consumed by opening_balance parser were also claimed by transaction_amount parser"
+        );
+    }
+
+    #[test]
+    fn test_separate_opening_balance_is_not_claimed_twice() {
+        let items = vec![
+            make_text_item("Opening", 100, 140, 60),
+            make_text_item("balance", 142, 180, 60),
+            make_text_item("1,000.00", 260, 300, 60),
+            make_text_item("Transactions", 100, 180, 100),
+            make_text_item("Description", 100, 160, 120),
+            make_text_item("Amount", 260, 300, 120),
+            make_text_item("Balance", 360, 400, 120),
+            make_text_item("Payment", 100, 140, 160),
+            make_text_item("50.00", 270, 300, 160),
+            make_text_item("1,050.00", 355, 400, 160),
+        ];
+
+        let mut data = text_items_to_statement_data(&make_config(), &items);
+        check_item_claims(&mut data);
+
+        assert_eq!(data.opening_balance, Some(1000.0));
+        assert!(data.warnings.is_empty());
+        assert!(
+            data.item_claims
+                .iter()
+                .any(|claim| claim.parser == "transaction_balance" && claim.text == "1,050.00")
+        );
+    }
+
+    #[test]
+    fn test_transaction_columns_by_position_without_headers() {
+        let config = StatementConfig {
+            transaction_columns_by_position: vec![
+                TransactionColumnPosition {
+                    field: "description".to_string(),
+                    x1: 100,
+                    x2: 240,
+                },
+                TransactionColumnPosition {
+                    field: "amount".to_string(),
+                    x1: 250,
+                    x2: 305,
+                },
+                TransactionColumnPosition {
+                    field: "balance".to_string(),
+                    x1: 350,
+                    x2: 405,
+                },
+            ],
+            transaction_description_headers: vec![],
+            transaction_description_allows_amounts: true,
+            transaction_amount_headers: vec![],
+            transaction_balance_headers: vec![],
+            ..make_config()
+        };
+        let items = vec![
+            make_text_item("Transactions", 10, 90, 100),
+            make_text_item("Split", 100, 130, 120),
+            make_text_item("30.00", 133, 160, 120),
+            make_text_item("-30.00", 265, 300, 120),
+            make_text_item("970.00", 365, 400, 120),
+            make_text_item("Payment", 100, 140, 140),
+            make_text_item("50.00", 270, 300, 140),
+            make_text_item("1,020.00", 355, 400, 140),
+        ];
+
+        let data = text_items_to_statement_data(&config, &items);
+
+        assert_eq!(data.proto_transactions.len(), 2);
+        assert_eq!(data.proto_transactions[0].description, "Split 30.00");
+        assert_eq!(data.proto_transactions[0].amount, Some(-30.0));
+        assert_eq!(data.proto_transactions[0].balance, Some(970.0));
+        assert_eq!(data.proto_transactions[1].description, "Payment");
+        assert_eq!(data.proto_transactions[1].balance, Some(1020.0));
+    }
+
+    #[test]
+    fn test_start_and_end_date_from_statement_period() {
+        let config = StatementConfig {
+            start_date_terms: vec!["Statement Period:".to_string()],
+            start_date_formats: vec!["format2".to_string()],
+            end_date_terms: vec!["-".to_string()],
+            end_date_formats: vec!["format2".to_string()],
+            ..make_config()
+        };
+        let items = vec![
+            make_text_item("Statement", 77, 131, 40),
+            make_text_item("Period:", 134, 176, 40),
+            make_text_item("1", 268, 274, 40),
+            make_text_item("April", 277, 307, 40),
+            make_text_item("2024", 310, 334, 40),
+            make_text_item("-", 337, 343, 40),
+            make_text_item("30", 346, 358, 40),
+            make_text_item("April", 361, 391, 40),
+            make_text_item("2024", 394, 418, 40),
+        ];
+
+        let data = text_items_to_statement_data(&config, &items);
+
+        assert_eq!(data.start_date, Some(1_711_929_600_000)); // 2024-04-01
+        assert_eq!(data.end_date, Some(1_714_435_200_000)); // 2024-04-30
+    }
+
+    #[test]
+    fn test_transaction_date_without_year_is_an_error() {
+        let config = StatementConfig {
+            transaction_formats: vec![vec![
+                "date".to_string(),
+                "description".to_string(),
+                "amount".to_string(),
+                "balance".to_string(),
+            ]],
+            transaction_date_formats: vec!["format1".to_string()],
+            transaction_date_headers: vec!["Date".to_string()],
+            ..make_config()
+        };
+        let items = vec![
+            make_text_item("Transactions", 100, 180, 100),
+            make_text_item("Date", 40, 70, 120),
+            make_text_item("Description", 100, 160, 120),
+            make_text_item("Amount", 260, 300, 120),
+            make_text_item("Balance", 360, 400, 120),
+            make_text_item("24", 40, 52, 140),
+            make_text_item("Mar", 55, 70, 140),
+            make_text_item("Payment", 100, 140, 140),
+            make_text_item("50.00", 270, 300, 140),
+            make_text_item("1,050.00", 355, 400, 140),
+            make_text_item("25", 40, 52, 160),
+            make_text_item("Mar", 55, 70, 160),
+        ];
+
+        let data = text_items_to_statement_data(&config, &items);
+
+        assert_eq!(
+            data.errors,
+            vec![
+                "Invalid transaction date '24 Mar' on page 0. Date has no year and no year \
+                 was provided: set transaction_start_date_required so that the year is taken \
+                 from the start date"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_row_omitting_optional_balance_ends_with_its_line() {
+        // Fee rows without a balance, the last one at the end of the table
+        let items = vec![
+            make_text_item("Transactions", 100, 180, 100),
+            make_text_item("Description", 100, 160, 120),
+            make_text_item("Amount", 260, 300, 120),
+            make_text_item("Balance", 360, 400, 120),
+            make_text_item("Fee", 100, 140, 140),
+            make_text_item("-2.00", 270, 300, 140),
+            make_text_item("Payment", 100, 140, 160),
+            make_text_item("50.00", 270, 300, 160),
+            make_text_item("1,048.00", 355, 400, 160),
+            make_text_item("Card fee", 100, 140, 180),
+            make_text_item("-1.00", 270, 300, 180),
+        ];
+
+        let mut config = make_config();
+        config.transaction_formats[0][2] = "balance?".to_string();
+        let data = text_items_to_statement_data(&config, &items);
+
+        assert!(!data.dropped_rows.iter().any(|row| row.contains("Card fee")));
+        assert_eq!(data.proto_transactions.len(), 3);
+        assert_eq!(data.proto_transactions[0].description, "Fee");
+        assert_eq!(data.proto_transactions[0].balance, None);
+        assert_eq!(data.proto_transactions[1].description, "Payment");
+        assert_eq!(data.proto_transactions[1].balance, Some(1048.0));
+        assert_eq!(data.proto_transactions[2].description, "Card fee");
+        assert_eq!(data.proto_transactions[2].balance, None);
+    }
+
+    #[test]
+    fn test_optional_description_keeps_filler_rows() {
+        let items = vec![
+            make_text_item("Transactions", 100, 180, 100),
+            make_text_item("Description", 100, 160, 120),
+            make_text_item("Amount", 260, 300, 120),
+            make_text_item("Balance", 360, 400, 120),
+            make_text_item("Payment", 100, 140, 140),
+            make_text_item("50.00", 270, 300, 140),
+            make_text_item("1,050.00", 355, 400, 140),
+            make_text_item("........", 100, 140, 160),
+            make_text_item("-2.00", 270, 300, 160),
+            make_text_item("1,048.00", 355, 400, 160),
+            make_text_item("-3.00", 270, 300, 180),
+            make_text_item("1,045.00", 355, 400, 180),
+        ];
+        let mut config = make_config();
+        config.transaction_description_exclude = vec![regex::Regex::new(r"^\.+$").unwrap()];
+
+        // Filler removed after the row is read, row without description dropped
+        let data = text_items_to_statement_data(&config, &items);
+        assert_eq!(data.proto_transactions.len(), 2);
+        assert!(!data.proto_transactions[1].has_description());
+
+        config.transaction_description_optional = true;
+        let data = text_items_to_statement_data(&config, &items);
+        assert_eq!(data.proto_transactions.len(), 3);
+        assert_eq!(data.proto_transactions[1].description, "");
+        assert_eq!(data.proto_transactions[2].description, "");
+        assert_eq!(data.proto_transactions[2].balance, Some(1045.0));
+        assert!(data.proto_transactions[2].has_description());
+
+        config.transaction_description_placeholder = "(no description)".to_string();
+        let data = text_items_to_statement_data(&config, &items);
+        assert_eq!(data.proto_transactions[1].description, "(no description)");
+    }
+
+    #[test]
+    fn test_split_account_sections_repeats_header() {
+        let config = StatementConfig {
+            account_section_terms: vec!["Account summary".to_string()],
+            ..make_config()
+        };
+        let items = vec![
+            make_text_item("Period", 100, 140, 20),
+            make_text_item("Account", 100, 150, 40),
+            make_text_item("summary", 152, 200, 40),
+            make_text_item("Savings", 100, 150, 60),
+            make_text_item("Account", 100, 150, 80),
+            make_text_item("summary", 152, 200, 80),
+            make_text_item("Cheque", 100, 150, 100),
+        ];
+
+        let sections = split_account_sections(&config, &items);
+
+        let texts: Vec<Vec<&str>> = sections
+            .iter()
+            .map(|s| s.iter().map(|item| item.text.as_str()).collect())
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                vec!["Period", "Account", "summary", "Savings"],
+                vec!["Period", "Account", "summary", "Cheque"],
+            ]
+        );
+        assert_eq!(split_account_sections(&make_config(), &items).len(), 1);
+    }
+
+    #[test]
+    fn test_split_statements_needs_complete_header() {
+        let config = StatementConfig {
+            account_terms: vec!["Bank".to_string()],
+            start_date_terms: vec!["Period".to_string()],
+            ..make_config()
+        };
+        let words = [
+            "Bank", "Period", "Opening", "balance", "First", "Bank", "Period", "Second", "Period",
+            "Third", "Bank", "Period", "Opening", "balance", "Fourth",
+        ];
+        let items: Vec<TextItem> = words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| make_text_item(word, 100, 150, 20 * i as i32))
+            .collect();
+
+        let segments = split_statements(&config, &items);
+
+        // The repeated page header without an opening balance and the start date term
+        // without the account terms do not start a statement
+        let texts: Vec<Vec<&str>> = segments
+            .iter()
+            .map(|s| s.iter().map(|item| item.text.as_str()).collect())
+            .collect();
+        assert_eq!(texts, vec![words[..10].to_vec(), words[10..].to_vec(),]);
+    }
+
+    #[test]
+    fn test_source_items_recorded_on_request() {
+        let items = vec![
+            make_text_item("Opening balance", 100, 180, 60),
+            make_text_item("1,000.00", 260, 300, 60),
+            make_text_item("Transactions", 100, 180, 100),
+            make_text_item("Description", 100, 160, 120),
+            make_text_item("Amount", 260, 300, 120),
+            make_text_item("Balance", 360, 400, 120),
+            make_text_item("Payment", 100, 140, 140),
+            make_text_item("50.00", 270, 300, 140),
+            make_text_item("1,050.00", 355, 400, 140),
+            make_text_item("Fee", 100, 140, 160),
+            make_text_item("-2.00", 270, 300, 160),
+            make_text_item("1,048.00", 355, 400, 160),
+        ];
+
+        let (data, _) = text_items_to_statement_data_traced(&make_config(), &items, true);
+
+        assert_eq!(data.field_sources["opening_balance"], items[0..2].to_vec());
+        assert_eq!(data.proto_transactions.len(), 2);
+        assert_eq!(
+            data.proto_transactions[0].source_items,
+            items[6..9].to_vec()
+        );
+        assert_eq!(
+            data.proto_transactions[1].source_items,
+            items[9..12].to_vec()
+        );
+        let output = data.to_string();
+        assert!(output.contains("    opening_balance: page 0 [\"Opening balance\",100,180,60,70]"));
+        assert!(output.contains("    2: page 0 [\"Fee\",100,140,160,170]"));
+
+        // Not recorded by default
+        let data = text_items_to_statement_data(&make_config(), &items);
+        assert!(data.field_sources.is_empty());
+        assert!(data.proto_transactions[0].source_items.is_empty());
+        assert!(!data.to_string().contains("Sources"));
+    }
+
+    #[test]
+    fn test_transaction_location_is_recorded() {
+        let items = vec![
+            make_text_item("Transactions", 100, 180, 100),
+            make_text_item("Description", 100, 160, 120),
+            make_text_item("Amount", 260, 300, 120),
+            make_text_item("Balance", 360, 400, 120),
+            make_text_item("Payment", 100, 140, 140),
+            make_text_item("50.00", 270, 300, 140),
+            make_text_item("1,050.00", 355, 400, 140),
+            make_text_item("Fee", 100, 140, 160),
+            make_text_item("-2.00", 270, 300, 160),
+            make_text_item("1,048.00", 355, 400, 160),
+        ];
+
+        let data = text_items_to_statement_data(&make_config(), &items);
 
-        // No parser matched, move to next item
-        i += 1;
+        let locations: Vec<Option<String>> = data
+            .proto_transactions
+            .iter()
+            .map(|tx| tx.location())
+            .collect();
+        assert_eq!(
+            locations,
+            vec![
+                Some("page 0, y≈140".to_string()),
+                Some("page 0, y≈160".to_string())
+            ]
+        );
     }
-    statement_data
 }
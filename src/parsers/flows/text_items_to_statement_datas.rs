@@ -1,32 +1,1245 @@
 use crate::checkers::check_statement_data;
-use crate::fixers::fix_statement_data;
-use crate::parsers::flows::text_items_to_statement_data::text_items_to_statement_data;
+use crate::fixers::{FixersMode, fix_statement_data_with_mode};
+use crate::parsers::flows::text_items_to_statement_data::{
+    AccountNumberTrace, PreparedConfig, reuse_prepared_statement_data, split_account_sections,
+    split_statements, text_items_to_statement_data_prepared,
+};
+use crate::structs::LocaleProfile;
+use crate::structs::Money;
 use crate::structs::StatementConfig;
 use crate::structs::StatementData;
 use crate::structs::TextItem;
+use crate::structs::text_items::get_coordinate_scale;
 use crate::structs::text_items::sort_items;
 use crate::structs::text_items::tokenise_items;
+use chrono_tz::Tz;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Parsed statement data and account number trace, keyed by transaction cache key
+type ParseCache = HashMap<String, (StatementData, AccountNumberTrace)>;
+
+/// Most configs kept prepared at once. Each coordinate scale of a config with
+/// scale_tolerances set is prepared separately, so the cache is cleared once full.
+const MAX_PREPARED_CONFIGS: usize = 256;
+
+/// Key of a prepared config: the config key and hash of its fields (see
+/// StatementConfig::config_hash), with the fields changed for a parse after the hash
+/// is computed (timezone and strict overrides, text order retry and coordinate scale)
+#[derive(PartialEq, Eq, Hash)]
+struct PreparedKey {
+    key: String,
+    config_hash: u64,
+    date_timezone: String,
+    transaction_strict: bool,
+    fix_text_order: Vec<u32>,
+    coordinate_scale: u32,
+}
+
+impl PreparedKey {
+    fn new(cfg: &StatementConfig, coordinate_scale: f32) -> Self {
+        Self {
+            key: cfg.key.clone(),
+            config_hash: cfg.fields_hash(),
+            date_timezone: cfg.date_timezone.clone(),
+            transaction_strict: cfg.transaction_strict,
+            fix_text_order: cfg
+                .fix_text_order
+                .iter()
+                .map(|value| value.to_bits())
+                .collect(),
+            coordinate_scale: coordinate_scale.to_bits(),
+        }
+    }
+}
+
+/// Configs prepared for parsing (see PreparedConfig), kept across parses of
+/// different documents and shared between threads. Configs are keyed by their key
+/// and field hash (see PreparedKey), so a changed or re-registered config is prepared
+/// again. The lock is only held to get or add a prepared config, not while parsing.
+#[derive(Default)]
+pub struct PreparedConfigs {
+    prepared: Mutex<HashMap<PreparedKey, Arc<PreparedConfig>>>,
+}
+
+impl PreparedConfigs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the prepared config scaled by coordinate_scale (1.0 if not scaled),
+    /// preparing it if not yet prepared
+    pub fn get(&self, cfg: &StatementConfig, coordinate_scale: f32) -> Arc<PreparedConfig> {
+        let key = PreparedKey::new(cfg, coordinate_scale);
+        if let Some(prepared) = self.lock().get(&key) {
+            return Arc::clone(prepared);
+        }
+        // Prepared without the lock, so that parses with other configs are not held up
+        let prepared = Arc::new(PreparedConfig::new(cfg));
+        let mut configs = self.lock();
+        if configs.len() >= MAX_PREPARED_CONFIGS {
+            configs.clear();
+        }
+        Arc::clone(configs.entry(key).or_insert(prepared))
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Lock the prepared configs. These are only a cache, so they are used even if
+    /// another thread panicked while holding the lock.
+    fn lock(&self) -> MutexGuard<'_, HashMap<PreparedKey, Arc<PreparedConfig>>> {
+        self.prepared
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
 
 /// Parse non-tokenised text items into list of statement data results,
-/// using provided statement configurations.
+/// using provided statement configurations and fixers mode.
+/// A multi_account config gives one result per account section, in order, sharing
+/// the config's key, and a multi_statement config one result per statement, in start
+/// date order; other configs give a single result.
+/// If retry_text_order is set, a config with fix_text_order reordering enabled that
+/// produces errors is retried in the original text order, keeping the better result.
+/// Configs that differ only in identification and account number fields reuse the
+/// first such config's parse instead of parsing the transactions again.
+/// If record_sources is set, the text items read into each field are recorded
+/// (see StatementDataParser::with_source_items).
 pub fn text_items_to_statement_datas(
     items: &Vec<TextItem>,
     configs: &Vec<StatementConfig>,
+    fixers: FixersMode,
+    retry_text_order: bool,
+    record_sources: bool,
+) -> Result<Vec<StatementData>, String> {
+    text_items_to_statement_datas_prepared(
+        items,
+        configs,
+        fixers,
+        retry_text_order,
+        record_sources,
+        &PreparedConfigs::new(),
+    )
+}
+
+/// Parse non-tokenised text items into list of statement data results, as
+/// text_items_to_statement_datas, with configs prepared for parsing kept in prepared
+/// for the parses of other documents
+pub fn text_items_to_statement_datas_prepared(
+    items: &Vec<TextItem>,
+    configs: &Vec<StatementConfig>,
+    fixers: FixersMode,
+    retry_text_order: bool,
+    record_sources: bool,
+    prepared: &PreparedConfigs,
 ) -> Result<Vec<StatementData>, String> {
     let mut results = Vec::new();
+    let mut cache = ParseCache::new();
     for cfg in configs {
-        // Sort will just return a clone if y_bin is 0.0
-        let sorted_items = sort_items(items, cfg.fix_text_order[1], cfg.fix_text_order[0]);
-        let tokenised_sorted_items = tokenise_items(&sorted_items);
-        let mut data = text_items_to_statement_data(cfg, &tokenised_sorted_items);
-        data.set_key(cfg.key.clone());
+        let mut datas =
+            parse_with_config(items, cfg, fixers, record_sources, &mut cache, prepared)?;
 
-        // Apply fixers to clean up the data
-        fix_statement_data(&mut data);
-        check_statement_data(&mut data);
+        let reordered_errors = error_count(&datas);
+        if retry_text_order && datas[0].fix_text_order_applied && reordered_errors > 0 {
+            let mut original_order_cfg = cfg.clone();
+            original_order_cfg.fix_text_order = vec![0.0, 0.0];
+            let mut retry_datas = parse_with_config(
+                items,
+                &original_order_cfg,
+                fixers,
+                record_sources,
+                &mut cache,
+                prepared,
+            )?;
+            let original_errors = error_count(&retry_datas);
+            // Timings are cumulative over both attempts, whichever is kept
+            let applied = original_errors >= reordered_errors;
+            if !applied {
+                retry_datas[0].timings.add(&datas[0].timings);
+                datas = retry_datas;
+            } else {
+                datas[0].timings.add(&retry_datas[0].timings);
+            }
+            for data in &mut datas {
+                data.add_warning(format!(
+                    "fix_text_order {:?} {}: reordering text gave {} error(s), the original text order gave {}",
+                    cfg.fix_text_order,
+                    if applied { "applied" } else { "not applied" },
+                    reordered_errors,
+                    original_errors
+                ));
+            }
+        }
 
-        results.push(data);
+        results.extend(datas);
     }
 
     Ok(results)
 }
+
+/// Total number of errors over the results of a config
+fn error_count(datas: &[StatementData]) -> usize {
+    datas.iter().map(|data| data.errors.len()).sum()
+}
+
+/// Get the key of the config fields that can affect parsing any part of the statement
+/// other than the account number. The fields are formatted rather than hashed, so that
+/// any difference gives a different key.
+fn transaction_cache_key(cfg: &StatementConfig) -> String {
+    let cfg = StatementConfig {
+        key: String::new(),
+        status: String::new(),
+        bank_name: String::new(),
+        account_type: String::new(),
+        account_terms: vec![],
+        account_examples: vec![],
+        date_timezone: String::new(),
+        account_number_terms: vec![],
+        account_number_patterns: vec![],
+        account_number_alignment: String::new(),
+        account_number_alignment_tol: 0,
+        ..cfg.clone()
+    };
+    format!("{:?}", cfg)
+}
+
+/// Parse non-tokenised text items into statement data using a single configuration,
+/// then apply fixers and checkers. Parses are cached by transaction cache key, and
+/// reused if the account number parser consumes the same items for this config.
+/// Each account section of a multi_account config, and each statement of a document
+/// of several statements with a multi_statement config, is parsed separately, without
+/// the cache.
+fn parse_with_config(
+    items: &Vec<TextItem>,
+    cfg: &StatementConfig,
+    fixers: FixersMode,
+    record_sources: bool,
+    cache: &mut ParseCache,
+    prepared: &PreparedConfigs,
+) -> Result<Vec<StatementData>, String> {
+    let coordinate_scale = get_coordinate_scale(items);
+    let currency = if cfg.currency_symbol.is_empty() {
+        LocaleProfile::from_text_items(items)
+            .currency_symbol()
+            .map(|symbol| symbol.to_string())
+    } else {
+        Some(cfg.currency_symbol.clone())
+    };
+    let config_scale = if cfg.scale_tolerances {
+        coordinate_scale
+    } else {
+        1.0
+    };
+    let scaled_cfg;
+    let cfg = if config_scale != 1.0 {
+        scaled_cfg = cfg.scaled(config_scale);
+        &scaled_cfg
+    } else {
+        cfg
+    };
+    // Sort will just return a clone if y_bin is 0.0
+    let started = Instant::now();
+    let sorted_items = sort_items(items, cfg.fix_text_order[1], cfg.fix_text_order[0]);
+    let tokenised_sorted_items = tokenise_items(&sorted_items);
+    let text_ordering = started.elapsed();
+    let prepared = &prepared.get(cfg, config_scale);
+    if cfg.multi_account {
+        let sections = split_account_sections(cfg, &tokenised_sorted_items);
+        let mut results = Vec::with_capacity(sections.len());
+        for (n, section) in sections.iter().enumerate() {
+            let (mut data, _) =
+                text_items_to_statement_data_prepared(prepared, section, record_sources);
+            data.account_section = Some(n + 1);
+            data.currency = currency.clone();
+            finish_statement_data(&mut data, cfg, fixers, coordinate_scale, text_ordering)?;
+            results.push(data);
+        }
+        return Ok(results);
+    }
+    if cfg.multi_statement {
+        let segments = split_statements(cfg, &tokenised_sorted_items);
+        if segments.len() > 1 {
+            let mut results = Vec::with_capacity(segments.len());
+            for segment in &segments {
+                let (mut data, _) =
+                    text_items_to_statement_data_prepared(prepared, segment, record_sources);
+                data.currency = currency.clone();
+                finish_statement_data(&mut data, cfg, fixers, coordinate_scale, text_ordering)?;
+                results.push(data);
+            }
+            // Statements without a start date keep their document order, last
+            results.sort_by_key(|data| data.start_date.unwrap_or(i64::MAX));
+            for (n, data) in results.iter_mut().enumerate() {
+                data.statement_section = Some(n + 1);
+            }
+            return Ok(results);
+        }
+    }
+
+    let cache_key = transaction_cache_key(cfg);
+    let reused = cache.get(&cache_key).and_then(|(parsed, trace)| {
+        reuse_prepared_statement_data(prepared, &tokenised_sorted_items, parsed, trace)
+    });
+    let mut data = match reused {
+        Some(data) => data,
+        None => {
+            let (data, trace) = text_items_to_statement_data_prepared(
+                prepared,
+                &tokenised_sorted_items,
+                record_sources,
+            );
+            cache
+                .entry(cache_key)
+                .or_insert_with(|| (data.clone(), trace));
+            data
+        }
+    };
+    data.currency = currency;
+    finish_statement_data(&mut data, cfg, fixers, coordinate_scale, text_ordering)?;
+    Ok(vec![data])
+}
+
+/// Set the config details of parsed statement data, then apply fixers and checkers
+/// and localise its dates
+fn finish_statement_data(
+    data: &mut StatementData,
+    cfg: &StatementConfig,
+    fixers: FixersMode,
+    coordinate_scale: f32,
+    text_ordering: Duration,
+) -> Result<(), String> {
+    data.timings.text_ordering = text_ordering;
+    data.set_key(cfg.key.clone());
+    data.set_config_status(cfg.status.clone());
+    data.fix_text_order_applied = cfg.fix_text_order[0] != 0.0;
+    data.coordinate_scale = coordinate_scale;
+    data.balance_tolerance = Money::from_cents(cfg.balance_tolerance_cents.into());
+    if coordinate_scale != 1.0 {
+        if cfg.scale_tolerances {
+            data.add_warning(format!(
+                "Coordinate scale {} detected: config tolerances scaled by {}",
+                coordinate_scale, coordinate_scale
+            ));
+        } else {
+            data.add_warning(format!(
+                "Coordinate scale {} detected: config tolerances not scaled as scale_tolerances is not set",
+                coordinate_scale
+            ));
+        }
+    }
+
+    // Apply fixers to clean up the data
+    let started = Instant::now();
+    fix_statement_data_with_mode(data, cfg, fixers);
+    data.timings.fixers = started.elapsed();
+    let started = Instant::now();
+    check_statement_data(data, cfg);
+    data.timings.checkers = started.elapsed();
+
+    // Dates are parsed, fixed and checked as UTC midnight, then localised if required
+    if !cfg.date_timezone.is_empty() {
+        let tz = cfg.date_timezone.parse::<Tz>().map_err(|e| {
+            format!(
+                "Invalid date_timezone '{}' in config {}: {}",
+                cfg.date_timezone, cfg.key, e
+            )
+        })?;
+        data.localise_dates(tz);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::flows::config_json_file_to_config::from_json_str;
+    use crate::parsers::flows::layout_to_text_items::layout_to_text_items;
+    use crate::structs::ParseTimings;
+    use crate::test_fixtures::{Test3Layout, ditto_layout, multiline_layout, patch};
+    use regex::Regex;
+
+    fn make_text_item(text: &str, x1: i32, x2: i32, y1: i32) -> TextItem {
+        TextItem::new(text.to_string(), x1, y1, x2, y1 + 10, 0)
+    }
+
+    fn make_items() -> Vec<TextItem> {
+        vec![
+            make_text_item("Account number", 100, 180, 60),
+            make_text_item("1234 5678", 200, 260, 60),
+            make_text_item("Opening balance", 100, 180, 80),
+            make_text_item("1,000.00", 260, 300, 80),
+            make_text_item("Transactions", 100, 180, 100),
+            make_text_item("Description", 100, 160, 120),
+            make_text_item("Amount", 260, 300, 120),
+            make_text_item("Balance", 360, 400, 120),
+            make_text_item("Payment", 100, 140, 140),
+            make_text_item("50.00", 270, 300, 140),
+            make_text_item("1,050.00", 355, 400, 140),
+            make_text_item("Fee", 100, 140, 160),
+            make_text_item("-2.00", 270, 300, 160),
+            make_text_item("1,048.00", 355, 400, 160),
+        ]
+    }
+
+    fn make_config(key: &str, account_number_pattern: &str) -> StatementConfig {
+        StatementConfig {
+            key: key.to_string(),
+            account_terms: vec![key.to_string()],
+            account_number_terms: vec!["Account number".to_string()],
+            account_number_patterns: vec![Regex::new(account_number_pattern).unwrap()],
+            opening_balance_terms: vec!["Opening balance".to_string()],
+            opening_balance_formats: vec!["format1".to_string()],
+            transaction_terms: vec!["Transactions".to_string()],
+            transaction_formats: vec![vec![
+                "description".to_string(),
+                "amount".to_string(),
+                "balance".to_string(),
+            ]],
+            transaction_description_headers: vec!["Description".to_string()],
+            transaction_amount_formats: vec!["format1".to_string()],
+            transaction_amount_headers: vec!["Amount".to_string()],
+            transaction_amount_alignment: "x2".to_string(),
+            transaction_balance_formats: vec!["format1".to_string()],
+            transaction_balance_headers: vec!["Balance".to_string()],
+            transaction_balance_alignment: "x2".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn without_timings(data: &StatementData) -> String {
+        let mut data = data.clone();
+        data.timings = ParseTimings::new();
+        data.to_string()
+    }
+
+    #[test]
+    fn test_sibling_configs_reuse_transaction_parse() {
+        let items = make_items();
+        let configs = vec![
+            make_config("sibling_1", r"\d{4} \d{4}"),
+            make_config("sibling_2", r"\d{4}\s\d{4}"),
+            make_config("sibling_3", r"\d+ \d+"),
+            make_config("sibling_4", r"[0-9]{4} [0-9]{4}"),
+        ];
+
+        let results =
+            text_items_to_statement_datas(&items, &configs, FixersMode::None, false, false)
+                .unwrap();
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].proto_transactions.len(), 2);
+        assert!(results[0].timings.transactions > Duration::ZERO);
+        for (cfg, data) in configs.iter().zip(&results) {
+            let separate = text_items_to_statement_datas(
+                &items,
+                &vec![cfg.clone()],
+                FixersMode::None,
+                false,
+                false,
+            )
+            .unwrap();
+            assert_eq!(without_timings(data), without_timings(&separate[0]));
+            assert_eq!(data.account_number, Some("1234 5678".to_string()));
+        }
+        for data in &results[1..] {
+            assert_eq!(data.timings.transactions, Duration::ZERO);
+        }
+    }
+
+    #[test]
+    fn test_prepared_configs_are_reused_across_documents() {
+        let items = make_items();
+        let configs = vec![
+            make_config("sibling_1", r"\d{4} \d{4}"),
+            make_config("sibling_2", r"\d{12}"),
+        ];
+        let unprepared =
+            text_items_to_statement_datas(&items, &configs, FixersMode::None, false, false)
+                .unwrap();
+
+        let prepared = PreparedConfigs::new();
+        for _ in 0..2 {
+            let results = text_items_to_statement_datas_prepared(
+                &items,
+                &configs,
+                FixersMode::None,
+                false,
+                false,
+                &prepared,
+            )
+            .unwrap();
+            for (data, expected) in results.iter().zip(&unprepared) {
+                assert_eq!(without_timings(data), without_timings(expected));
+            }
+        }
+        assert_eq!(prepared.len(), 2);
+
+        // A changed config is prepared again
+        let mut changed = configs[0].clone();
+        changed.transaction_amount_alignment = "x1".to_string();
+        changed.config_hash = None;
+        text_items_to_statement_datas_prepared(
+            &items,
+            &vec![changed],
+            FixersMode::None,
+            false,
+            false,
+            &prepared,
+        )
+        .unwrap();
+        assert_eq!(prepared.len(), 3);
+    }
+
+    #[test]
+    fn test_prepared_configs_keyed_by_config_hash() {
+        let json = include_str!("../../../tests/fixtures/test3_config.json");
+        let cfg = from_json_str(json).unwrap();
+        assert!(cfg.config_hash.is_some());
+        let prepared = PreparedConfigs::new();
+        let first = prepared.get(&cfg, 1.0);
+
+        // Registering the same JSON again reuses the prepared config
+        let again = prepared.get(&from_json_str(json).unwrap(), 1.0);
+        assert!(Arc::ptr_eq(&first, &again));
+
+        // A re-registered config with changed fields, a timezone override and a scaled
+        // config are each prepared again
+        let changed = from_json_str(&patch(
+            json,
+            &[(
+                "\"transaction_amount_alignment\": \"x2\"",
+                "\"transaction_amount_alignment\": \"x1\"",
+            )],
+        ))
+        .unwrap();
+        assert_eq!(changed.key, cfg.key);
+        assert!(!Arc::ptr_eq(&first, &prepared.get(&changed, 1.0)));
+        let mut overridden = cfg.clone();
+        overridden.date_timezone = "Australia/Sydney".to_string();
+        prepared.get(&overridden, 1.0);
+        prepared.get(&cfg.scaled(2.0), 2.0);
+        assert_eq!(prepared.len(), 4);
+    }
+
+    #[test]
+    fn test_sibling_config_with_different_account_number_consumption_is_parsed() {
+        let items = make_items();
+        let configs = vec![
+            make_config("sibling_1", r"\d{4} \d{4}"),
+            make_config("sibling_2", r"\d{12}"),
+        ];
+
+        let results =
+            text_items_to_statement_datas(&items, &configs, FixersMode::None, false, false)
+                .unwrap();
+
+        assert_eq!(results[1].account_number, None);
+        assert!(results[1].timings.transactions > Duration::ZERO);
+        assert_eq!(results[1].proto_transactions.len(), 2);
+    }
+
+    #[test]
+    fn test_different_transaction_fields_bypass_cache() {
+        let items = make_items();
+        let mut inverted = make_config("sibling_2", r"\d{4} \d{4}");
+        inverted.transaction_amount_invert = true;
+        let configs = vec![make_config("sibling_1", r"\d{4} \d{4}"), inverted];
+
+        let results =
+            text_items_to_statement_datas(&items, &configs, FixersMode::None, false, false)
+                .unwrap();
+
+        assert!(results[1].timings.transactions > Duration::ZERO);
+        assert_eq!(results[0].proto_transactions[0].amount, Some(50.0));
+        assert_eq!(results[1].proto_transactions[0].amount, Some(-50.0));
+    }
+
+    #[test]
+    fn test_multi_account_config_gives_result_per_section() {
+        let mut items = make_items();
+        let second_account: Vec<TextItem> = make_items()
+            .into_iter()
+            .map(|mut item| {
+                item.y1 += 200;
+                item.y2 += 200;
+                if item.text == "1234 5678" {
+                    item.text = "8765 4321".to_string();
+                }
+                item
+            })
+            .collect();
+        items.extend(second_account);
+        let mut cfg = make_config("multi", r"\d{4} \d{4}");
+        cfg.multi_account = true;
+        cfg.account_section_terms = vec!["Account number".to_string()];
+
+        let results =
+            text_items_to_statement_datas(&items, &vec![cfg], FixersMode::None, false, false)
+                .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].account_number, Some("1234 5678".to_string()));
+        assert_eq!(results[1].account_number, Some("8765 4321".to_string()));
+        for (n, data) in results.iter().enumerate() {
+            assert_eq!(data.key, Some("multi".to_string()));
+            assert_eq!(data.account_section, Some(n + 1));
+            assert_eq!(data.opening_balance, Some(1000.0));
+            assert_eq!(data.proto_transactions.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_amount_sign_indicators_in_separate_items() {
+        // Unsigned amounts followed by a CR or DR item on the same line
+        let json = include_str!("../../../tests/fixtures/test3_config.json");
+        let layout = patch(
+            include_str!("../../../tests/fixtures/test3_layout.txt"),
+            &[
+                (
+                    "[\"250.00\",414,450,235,223]",
+                    "[\"250.00\",414,450,235,223][\"CR\",453,467,235,223]",
+                ),
+                (
+                    "[\"-120.00\",408,450,252,240]",
+                    "[\"120.00\",414,450,252,240][\"DR\",453,467,252,240]",
+                ),
+                (
+                    "[\"-24.50\",414,450,269,257]",
+                    "[\"24.50\",420,450,269,257][\"dr.\",453,470,269,257]",
+                ),
+            ],
+        );
+        let items = layout_to_text_items(&layout).unwrap();
+
+        let cfg = from_json_str(json).unwrap();
+        let results =
+            text_items_to_statement_datas(&items, &vec![cfg], FixersMode::All, false, false)
+                .unwrap();
+
+        assert!(results[0].errors.is_empty(), "{:?}", results[0].errors);
+        let amounts: Vec<Option<f64>> = results[0]
+            .proto_transactions
+            .iter()
+            .map(|tx| tx.amount)
+            .collect();
+        assert_eq!(amounts, vec![Some(250.0), Some(-120.0), Some(-24.5)]);
+        assert_eq!(results[0].proto_transactions[1].description, "RENT PAYMENT");
+    }
+
+    #[test]
+    fn test_amounts_in_separate_debit_and_credit_columns() {
+        // Unsigned amounts in alternating debit and credit columns, and a line with
+        // neither between them
+        let json = patch(
+            include_str!("../../../tests/fixtures/test3_config.json"),
+            &[(
+                "\"transaction_amount_headers\": [\"Amount\"],",
+                "\"transaction_amount_headers\": [],\n\
+             \"transaction_debit_headers\": [\"Debit\"],\n\
+             \"transaction_credit_headers\": [\"Credit\"],",
+            )],
+        );
+        let layout = patch(
+            include_str!("../../../tests/fixtures/test3_layout.txt"),
+            &[
+                (
+                    "[\"Amount\",414,450,218,206]",
+                    "[\"Debit\",350,380,218,206][\"Credit\",414,450,218,206]",
+                ),
+                (
+                    "[\"-120.00\",408,450,252,240]",
+                    "[\"120.00\",344,380,252,240]",
+                ),
+                (
+                    "[\"-24.50\",414,450,269,257]",
+                    "[\"24.50\",350,380,269,257]",
+                ),
+                (
+                    "[\"03/04/2024\",72,132,252,240]",
+                    "[\"02/04/2024\",72,132,244,232][\"RATE\",140,164,244,232]\
+                 [\"CHANGE\",167,203,244,232]\n[\"03/04/2024\",72,132,252,240]",
+                ),
+            ],
+        );
+        let items = layout_to_text_items(&layout).unwrap();
+
+        let cfg = from_json_str(&json).unwrap();
+        let results =
+            text_items_to_statement_datas(&items, &vec![cfg], FixersMode::All, false, false)
+                .unwrap();
+
+        assert!(results[0].errors.is_empty(), "{:?}", results[0].errors);
+        let amounts: Vec<Option<f64>> = results[0]
+            .proto_transactions
+            .iter()
+            .map(|tx| tx.amount)
+            .collect();
+        assert_eq!(amounts, vec![Some(250.0), Some(-120.0), Some(-24.5)]);
+        assert_eq!(
+            results[0].proto_transactions[0].description,
+            "SALARY ACME PTY LTD"
+        );
+        assert_eq!(results[0].proto_transactions[1].description, "RENT PAYMENT");
+    }
+
+    #[test]
+    fn test_misread_decimal_point_is_pinned_to_its_row() {
+        // "45.00" read as "4,500.00" on the third row
+        let json = include_str!("../../../tests/fixtures/test3_config.json");
+        let layout = Test3Layout::new("$877.10")
+            .table_header()
+            .row("02/04/2024", "COFFEE", "-4.50", "995.50")
+            .row("03/04/2024", "LUNCH", "-12.80", "982.70")
+            .row("04/04/2024", "BOOKS", "-4,500.00", "937.70")
+            .row("05/04/2024", "TRANSPORT", "-8.20", "929.50")
+            .row("06/04/2024", "GROCERIES", "-30.00", "899.50")
+            .row("07/04/2024", "PHARMACY", "-22.40", "877.10")
+            .build();
+        let items = layout_to_text_items(&layout).unwrap();
+
+        let cfg = from_json_str(json).unwrap();
+        let results =
+            text_items_to_statement_datas(&items, &vec![cfg], FixersMode::All, false, false)
+                .unwrap();
+
+        assert!(!results[0].errors.is_empty());
+        let outliers: Vec<&String> = results[0]
+            .warnings
+            .iter()
+            .filter(|w| w.contains("amount_outlier_multiple"))
+            .collect();
+        assert_eq!(outliers.len(), 1, "{:?}", results[0].warnings);
+        assert!(
+            outliers[0].starts_with("Transaction 3 ('BOOKS') amount -4500.00"),
+            "{}",
+            outliers[0]
+        );
+    }
+
+    #[test]
+    fn test_example_data_parses_without_errors() {
+        let examples = [
+            (
+                include_str!("../../../examples/data/zz__example__checking__1.json"),
+                include_str!("../../../examples/data/zz__example__checking__1_layout.txt"),
+                4,
+                1553.0,
+            ),
+            (
+                include_str!("../../../examples/data/zz__example__credit_card__1.json"),
+                include_str!("../../../examples/data/zz__example__credit_card__1_layout.txt"),
+                4,
+                -98.0,
+            ),
+        ];
+        for (json, layout, transactions, closing_balance) in examples {
+            let cfg = from_json_str(json).unwrap();
+            let items = layout_to_text_items(layout).unwrap();
+            let results =
+                text_items_to_statement_datas(&items, &vec![cfg], FixersMode::All, false, false)
+                    .unwrap();
+
+            assert_eq!(results.len(), 1);
+            assert!(results[0].errors.is_empty(), "{:?}", results[0].errors);
+            assert_eq!(results[0].proto_transactions.len(), transactions);
+            assert_eq!(results[0].closing_balance, Some(closing_balance));
+        }
+    }
+
+    #[test]
+    fn test_space_thousands_balances_are_merged_and_parsed() {
+        // Balances with a space thousands separator, split by the extraction into two
+        // items listed out of order
+        let json = patch(
+            include_str!("../../../tests/fixtures/test3_config.json"),
+            &[
+                (
+                    "\"fix_text_order\": [0.0, 0.0]",
+                    "\"fix_text_order\": [3.0, 1.0]",
+                ),
+                (
+                    "\"transaction_balance_formats\": [\"format1\"]",
+                    "\"transaction_balance_formats\": [\"format1\", \"format7\"]",
+                ),
+            ],
+        );
+        let layout = patch(
+            include_str!("../../../tests/fixtures/test3_layout.txt"),
+            &[
+                (
+                    "[\"1,250.00\",482,530,235,223]",
+                    "[\"250.00\",491,530,235,223][\"1\",482,488,235,223]",
+                ),
+                (
+                    "[\"1,130.00\",482,530,252,240]",
+                    "[\"130.00\",491,530,252,240][\"1\",482,488,252,240]",
+                ),
+                (
+                    "[\"1,105.50\",482,530,269,257]",
+                    "[\"105.50\",491,530,269,257][\"1\",482,488,269,257]",
+                ),
+            ],
+        );
+        let items = layout_to_text_items(&layout).unwrap();
+        let cfg = from_json_str(&json).unwrap();
+
+        let results =
+            text_items_to_statement_datas(&items, &vec![cfg], FixersMode::All, false, false)
+                .unwrap();
+
+        assert!(results[0].errors.is_empty(), "{:?}", results[0].errors);
+        let balances: Vec<Option<f64>> = results[0]
+            .proto_transactions
+            .iter()
+            .map(|tx| tx.balance)
+            .collect();
+        assert_eq!(balances, vec![Some(1250.0), Some(1130.0), Some(1105.5)]);
+    }
+
+    #[test]
+    fn test_account_number_not_required() {
+        // A statement without an account number in its body
+        let json = include_str!("../../../tests/fixtures/test3_config.json");
+        let layout = patch(
+            include_str!("../../../tests/fixtures/test3_layout.txt"),
+            &[(
+                "[\"Account\",77,119,131,119][\"number:\",122,164,131,119]",
+                "",
+            )],
+        );
+        let items = layout_to_text_items(&layout).unwrap();
+        let mut cfg = from_json_str(json).unwrap();
+
+        let results = text_items_to_statement_datas(
+            &items,
+            &vec![cfg.clone()],
+            FixersMode::All,
+            false,
+            false,
+        )
+        .unwrap();
+        assert!(results[0].errors[0].contains("Missing required fields: account number"));
+
+        cfg.required_fields = vec!["opening_balance".to_string(), "closing_balance".to_string()];
+        let results =
+            text_items_to_statement_datas(&items, &vec![cfg], FixersMode::All, false, false)
+                .unwrap();
+        assert!(results[0].errors.is_empty(), "{:?}", results[0].errors);
+        assert!(
+            results[0]
+                .warnings
+                .contains(&"Missing optional fields: account number".to_string())
+        );
+    }
+
+    #[test]
+    fn test_closing_balance_not_required() {
+        // A statement without a closing balance, whose rows are still checked against
+        // the opening balance
+        let json = include_str!("../../../tests/fixtures/test3_config.json");
+        let layout = patch(
+            include_str!("../../../tests/fixtures/test3_layout.txt"),
+            &[(
+                "[\"Closing\",77,119,161,149][\"balance:\",122,170,161,149][\"$1,105.50\",268,322,161,149]",
+                "",
+            )],
+        );
+        let items = layout_to_text_items(&layout).unwrap();
+        let mut cfg = from_json_str(json).unwrap();
+
+        let results = text_items_to_statement_datas(
+            &items,
+            &vec![cfg.clone()],
+            FixersMode::All,
+            false,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            results[0].errors,
+            vec!["Missing required fields: closing balance".to_string()]
+        );
+
+        cfg.required_fields = vec!["account_number".to_string(), "opening_balance".to_string()];
+        let results =
+            text_items_to_statement_datas(&items, &vec![cfg], FixersMode::All, false, false)
+                .unwrap();
+        assert!(results[0].errors.is_empty(), "{:?}", results[0].errors);
+        assert_eq!(
+            results[0].warnings,
+            vec!["Missing optional fields: closing balance".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_page_scoped_stop_terms_resume_on_next_page() {
+        // Three pages with the column headers repeated and a "Continued on next page"
+        // footer on the first two
+        let json = patch(
+            include_str!("../../../tests/fixtures/test3_config.json"),
+            &[(
+                "\"transaction_terms_stop\": [\"End of transactions\"],",
+                "\"transaction_terms_stop\": [\"Continued on next page\", \"End of transactions\"],\n\"transaction_terms_stop_scope\": \"page\",",
+            )],
+        );
+        let layout = Test3Layout::new("$1,029.75")
+            .table_header()
+            .row("02/04/2024", "SALARY ACME PTY LTD", "250.00", "1,250.00")
+            .row("03/04/2024", "RENT PAYMENT", "-120.00", "1,130.00")
+            .footer("Continued on next page")
+            .page()
+            .table_header()
+            .row("05/04/2024", "GROCERIES", "-24.50", "1,105.50")
+            .row("09/04/2024", "FUEL", "-60.00", "1,045.50")
+            .footer("Continued on next page")
+            .page()
+            .table_header()
+            .row("15/04/2024", "REFUND", "14.50", "1,060.00")
+            .row("22/04/2024", "PHARMACY", "-30.25", "1,029.75")
+            .build();
+        let items = layout_to_text_items(&layout).unwrap();
+
+        let cfg = from_json_str(&json).unwrap();
+        let results = text_items_to_statement_datas(
+            &items,
+            &vec![cfg.clone()],
+            FixersMode::All,
+            false,
+            false,
+        )
+        .unwrap();
+
+        assert!(results[0].errors.is_empty(), "{:?}", results[0].errors);
+        let descriptions: Vec<&str> = results[0]
+            .proto_transactions
+            .iter()
+            .map(|tx| tx.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                "SALARY ACME PTY LTD",
+                "RENT PAYMENT",
+                "GROCERIES",
+                "FUEL",
+                "REFUND",
+                "PHARMACY"
+            ]
+        );
+        assert_eq!(results[0].transaction_stop_page, Some(2));
+
+        // With the default statement scope, the first footer ends the transactions
+        let mut statement_scoped = cfg;
+        statement_scoped.transaction_terms_stop_scope = "statement".to_string();
+        let results = text_items_to_statement_datas(
+            &items,
+            &vec![statement_scoped],
+            FixersMode::All,
+            false,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(results[0].proto_transactions.len(), 2);
+        assert!(!results[0].errors.is_empty());
+    }
+
+    #[test]
+    fn test_ditto_marks_copy_previous_fields() {
+        let json = include_str!("../../../tests/fixtures/test3_config.json");
+        let items = layout_to_text_items(&ditto_layout()).unwrap();
+
+        let cfg = from_json_str(json).unwrap();
+        let results = text_items_to_statement_datas(
+            &items,
+            &vec![cfg.clone()],
+            FixersMode::None,
+            false,
+            false,
+        )
+        .unwrap();
+
+        let data = &results[0];
+        assert!(data.errors.is_empty(), "{:?}", data.errors);
+        let rows: Vec<(Option<i64>, &str)> = data
+            .proto_transactions
+            .iter()
+            .map(|tx| (tx.date, tx.description.as_str()))
+            .collect();
+        let (first, third) = (rows[0].0, rows[2].0);
+        assert_eq!(
+            rows,
+            vec![
+                (first, "SALARY ACME PTY LTD"),
+                (first, "RENT PAYMENT"),
+                (third, "GROCERIES"),
+                (third, "GROCERIES"),
+                (third, "GROCERIES"),
+            ]
+        );
+
+        // Without ditto tokens, the rows without a date are not read
+        let mut plain = cfg;
+        plain.ditto_tokens = vec![];
+        let results =
+            text_items_to_statement_datas(&items, &vec![plain], FixersMode::None, false, false)
+                .unwrap();
+        assert_eq!(results[0].proto_transactions.len(), 2);
+    }
+
+    #[test]
+    fn test_multiline_descriptions() {
+        let json = include_str!("../../../tests/fixtures/test3_config.json");
+        let items = layout_to_text_items(&multiline_layout()).unwrap();
+        let descriptions = |cfg: StatementConfig| -> Vec<String> {
+            let results =
+                text_items_to_statement_datas(&items, &vec![cfg], FixersMode::All, false, false)
+                    .unwrap();
+            assert!(results[0].errors.is_empty(), "{:?}", results[0].errors);
+            results[0]
+                .proto_transactions
+                .iter()
+                .map(|tx| tx.description.clone())
+                .collect()
+        };
+
+        let mut cfg = from_json_str(json).unwrap();
+        assert_eq!(
+            descriptions(cfg.clone()),
+            vec!["SALARY ACME PTY LTD", "RENT PAYMENT", "GROCERIES"]
+        );
+
+        cfg.transaction_description_multiline = true;
+        assert_eq!(
+            descriptions(cfg.clone()),
+            vec![
+                "SALARY ACME PTY LTD SYDNEY BRANCH",
+                "RENT PAYMENT REF 1042 FLAT 3",
+                "GROCERIES"
+            ]
+        );
+
+        cfg.transaction_description_continuation_max_lines = 1;
+        assert_eq!(
+            descriptions(cfg),
+            vec![
+                "SALARY ACME PTY LTD SYDNEY BRANCH",
+                "RENT PAYMENT REF 1042",
+                "GROCERIES"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_labelled_block_transactions() {
+        // Each transaction is a block of "Label: value" lines. The second block starts
+        // at the repeated "Date:" label, the third after a blank line and with the
+        // description first.
+        let json = include_str!("../../../tests/fixtures/test3_config.json");
+        let layout = Test3Layout::new("$1,105.50")
+            .with_line_spacing(15)
+            .line(&[("Date:", 77), ("02 Apr 2024", 180)])
+            .line(&[("Description:", 77), ("SALARY ACME PTY LTD", 180)])
+            .line(&[("Amount:", 77), ("250.00", 180)])
+            .line(&[("Balance:", 77), ("1,250.00", 180)])
+            .line(&[("Date:", 77), ("03 Apr 2024", 180)])
+            .line(&[("Description:", 77), ("RENT PAYMENT", 180)])
+            .line(&[("Amount:", 77), ("-120.00", 180)])
+            .line(&[("Balance:", 77), ("1,130.00", 180)])
+            .blank_line()
+            .line(&[("Description:", 77), ("GROCERIES", 180)])
+            .line(&[("Date:", 77), ("05 Apr 2024", 180)])
+            .line(&[("Amount:", 77), ("-24.50", 180)])
+            .line(&[("Balance:", 77), ("1,105.50", 180)])
+            .build();
+        let items = layout_to_text_items(&layout).unwrap();
+
+        let mut cfg = from_json_str(json).unwrap();
+        cfg.transaction_layout = "labelled_blocks".to_string();
+        cfg.transaction_date_formats = vec!["format2".to_string()];
+        cfg.transaction_date_headers = vec!["Date:".to_string()];
+        cfg.transaction_description_headers = vec!["Description:".to_string()];
+        cfg.transaction_amount_headers = vec!["Amount:".to_string()];
+        cfg.transaction_balance_headers = vec!["Balance:".to_string()];
+        let results = text_items_to_statement_datas(
+            &items,
+            &vec![cfg.clone()],
+            FixersMode::All,
+            false,
+            false,
+        )
+        .unwrap();
+
+        let data = &results[0];
+        assert!(data.errors.is_empty(), "{:?}", data.errors);
+        assert!(data.dropped_rows.is_empty(), "{:?}", data.dropped_rows);
+        let rows: Vec<(&str, Option<f64>, Option<f64>)> = data
+            .proto_transactions
+            .iter()
+            .map(|tx| (tx.description.as_str(), tx.amount, tx.balance))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("SALARY ACME PTY LTD", Some(250.0), Some(1250.0)),
+                ("RENT PAYMENT", Some(-120.0), Some(1130.0)),
+                ("GROCERIES", Some(-24.5), Some(1105.5)),
+            ]
+        );
+        assert_eq!(data.proto_transactions[2].date, Some(1712275200000)); // 2024-04-05
+
+        // Read as a table, the labelled lines give no transactions
+        cfg.transaction_layout = "table".to_string();
+        let results =
+            text_items_to_statement_datas(&items, &vec![cfg], FixersMode::All, false, false)
+                .unwrap();
+        assert!(results[0].proto_transactions.is_empty());
+    }
+
+    #[test]
+    fn test_new_line_tol_above_line_spacing_is_an_error() {
+        // Six rows 11 points apart
+        let json = include_str!("../../../tests/fixtures/test3_config.json");
+        let layout = Test3Layout::new("$1,065.00")
+            .table_header()
+            .with_line_spacing(11)
+            .blank_line()
+            .blank_line()
+            .row("02/04/2024", "SALARY", "250.00", "1,250.00")
+            .row("03/04/2024", "RENT", "-120.00", "1,130.00")
+            .row("05/04/2024", "GROCERIES", "-24.50", "1,105.50")
+            .row("08/04/2024", "FUEL", "-40.00", "1,065.50")
+            .row("10/04/2024", "REFUND", "15.50", "1,081.00")
+            .row("12/04/2024", "PHARMACY", "-16.00", "1,065.00")
+            .build();
+        let items = layout_to_text_items(&layout).unwrap();
+
+        let mut cfg = from_json_str(json).unwrap();
+        let results = text_items_to_statement_datas(
+            &items,
+            &vec![cfg.clone()],
+            FixersMode::All,
+            false,
+            false,
+        )
+        .unwrap();
+        assert!(results[0].errors.is_empty(), "{:?}", results[0].errors);
+        assert_eq!(results[0].proto_transactions.len(), 6);
+
+        // A tolerance above the spacing merges the rows
+        cfg.transaction_new_line_tol = 20;
+        let results =
+            text_items_to_statement_datas(&items, &vec![cfg], FixersMode::All, false, false)
+                .unwrap();
+        let data = &results[0];
+        assert_eq!(data.table_lines.line_spacing, Some(11));
+        assert!(
+            data.errors.iter().any(|e| e.starts_with(
+                "Only 1 transaction(s) parsed from 6 transaction table lines: transaction_new_line_tol 20 exceeds the table line spacing 11"
+            )),
+            "{:?}",
+            data.errors
+        );
+    }
+
+    #[test]
+    fn test_currency_symbol_hint() {
+        let items: Vec<TextItem> = make_items()
+            .into_iter()
+            .map(|mut item| {
+                if item.text.ends_with(".00") {
+                    item.text = match item.text.strip_prefix('-') {
+                        Some(amount) => format!("-£{}", amount),
+                        None => format!("£{}", item.text),
+                    };
+                }
+                item
+            })
+            .collect();
+        let mut cfg = make_config("pounds", r"\d{4} \d{4}");
+        cfg.opening_balance_formats = vec!["format2".to_string()];
+        cfg.transaction_amount_formats = vec!["format2".to_string()];
+        cfg.transaction_balance_formats = vec!["format2".to_string()];
+        let mut hinted = cfg.clone();
+        hinted.currency_symbol = "£".to_string();
+
+        let results = text_items_to_statement_datas(
+            &items,
+            &vec![cfg, hinted],
+            FixersMode::None,
+            false,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(results[0].opening_balance, None);
+        assert_eq!(results[0].currency, Some("£".to_string()));
+        assert_eq!(results[1].opening_balance, Some(1000.0));
+        assert_eq!(results[1].proto_transactions[1].amount, Some(-2.0));
+        assert_eq!(results[1].currency, Some("£".to_string()));
+    }
+
+    #[test]
+    fn test_multi_statement_document_gives_result_per_statement() {
+        // April, May and June statements, with the April statement moved to the end
+        let json = include_str!("../../../tests/fixtures/test3_config.json");
+        let layout = patch(
+            include_str!("../../../tests/fixtures/test3_layout_three_statements.txt"),
+            &[("[Page 0]", "[Page 3]")],
+        );
+        let items = layout_to_text_items(&layout).unwrap();
+        let mut cfg = from_json_str(json).unwrap();
+        cfg.multi_statement = true;
+
+        let results = text_items_to_statement_datas(
+            &items,
+            &vec![cfg.clone()],
+            FixersMode::All,
+            false,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(results.len(), 3);
+        let closing_balances: Vec<Option<f64>> =
+            results.iter().map(|data| data.closing_balance).collect();
+        assert_eq!(
+            closing_balances,
+            vec![Some(1105.5), Some(1211.0), Some(1316.5)]
+        );
+        for (n, data) in results.iter().enumerate() {
+            assert!(data.errors.is_empty(), "{:?}", data.errors);
+            assert_eq!(data.statement_section, Some(n + 1));
+            assert_eq!(data.proto_transactions.len(), 3);
+        }
+        // Merged, the balances of the statements chain
+        let mut balance = results[0].opening_balance.unwrap();
+        for data in &results {
+            assert_eq!(data.opening_balance, Some(balance));
+            for tx in &data.proto_transactions {
+                balance = ((balance + tx.amount.unwrap()) * 100.0).round() / 100.0;
+                assert_eq!(tx.balance, Some(balance));
+            }
+        }
+
+        // Without multi_statement, the document is a single statement
+        cfg.multi_statement = false;
+        let results =
+            text_items_to_statement_datas(&items, &vec![cfg], FixersMode::All, false, false)
+                .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].statement_section, None);
+    }
+}
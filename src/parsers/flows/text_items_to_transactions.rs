@@ -0,0 +1,280 @@
+use crate::formats::date::to_local_midnight_timestamp;
+use crate::parsers::flows::text_items_to_statement_data::StatementDataParser;
+use crate::structs::ProtoTransaction;
+use crate::structs::StatementConfig;
+use crate::structs::TextItem;
+use crate::structs::Transaction;
+use crate::structs::text_items::get_coordinate_scale;
+use crate::structs::text_items::sort_items;
+use crate::structs::text_items::tokenise_items;
+use chrono_tz::Tz;
+
+/// Iterator over the transactions of a statement, parsed incrementally from its
+/// text items. Each transaction is yielded once complete, with implicit dates and
+/// balances filled from the previous transaction (or the start date and opening
+/// balance). Only the transaction being parsed and the one before it are held in
+/// memory, as ditto marks and wrapped description lines read or change the previous
+/// transaction until the next one is complete. Fixers that
+/// need the whole statement (e.g., year crossovers and reordering) and the quality
+/// checks are not applied. A transaction that cannot be completed is yielded as
+/// an error.
+pub struct TransactionStream {
+    text_items: Vec<TextItem>,
+    parser: StatementDataParser,
+    date_timezone: Option<Tz>,
+    date: Option<i64>,
+    balance: Option<f64>,
+    count: usize,
+    parsed: bool,
+}
+
+impl TransactionStream {
+    /// Fill the implicit date and balance of a parsed transaction and convert it
+    fn complete(&mut self, mut tx: ProtoTransaction) -> Result<Transaction, String> {
+        self.count += 1;
+        let data = &self.parser.statement_data;
+
+        // As fix_implicit_dates, continuing from the start date
+        match tx.date {
+            Some(date) => self.date = Some(date),
+            None => {
+                if let Some(date) = self.date.or(data.start_date) {
+                    tx.set_date(date);
+                }
+            }
+        }
+        // As fix_implicit_balances, continuing from the opening balance
+        match (tx.balance, tx.amount) {
+            (Some(balance), _) => self.balance = Some(balance),
+            (None, Some(amount)) => {
+                if let Some(balance) = self.balance.or(data.opening_balance) {
+                    tx.set_balance(balance + amount);
+                    self.balance = tx.balance;
+                }
+            }
+            (None, None) => {}
+        }
+
+        let mut transaction = tx
+            .to_transaction()
+            .map_err(|e| format!("Transaction {} ('{}'): {}", self.count, tx.description, e))?;
+        if let Some(tz) = self.date_timezone {
+            transaction.date =
+                to_local_midnight_timestamp(transaction.date, tz).unwrap_or(transaction.date);
+        }
+        Ok(transaction)
+    }
+}
+
+impl Iterator for TransactionStream {
+    type Item = Result<Transaction, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let data = &mut self.parser.statement_data;
+            // The last transaction is held back until the next one is parsed
+            if data.proto_transactions.len() > 1
+                || (self.parsed && !data.proto_transactions.is_empty())
+            {
+                let tx = data.proto_transactions.remove(0);
+                return Some(self.complete(tx));
+            }
+            // Item claims are only needed for the checks of a full parse
+            data.item_claims.clear();
+            if self.parsed {
+                return None;
+            }
+            self.parsed = !self.parser.step(&self.text_items);
+        }
+    }
+}
+
+/// Parse non-tokenised text items into a stream of transactions using the provided
+/// statement configuration (see TransactionStream). Text items are ordered and
+/// tolerances scaled as for text_items_to_statement_datas.
+pub fn text_items_to_transactions(
+    items: &Vec<TextItem>,
+    cfg: &StatementConfig,
+) -> Result<TransactionStream, String> {
+    let coordinate_scale = get_coordinate_scale(items);
+    let scaled_cfg;
+    let cfg = if cfg.scale_tolerances && coordinate_scale != 1.0 {
+        scaled_cfg = cfg.scaled(coordinate_scale);
+        &scaled_cfg
+    } else {
+        cfg
+    };
+    let date_timezone = if cfg.date_timezone.is_empty() {
+        None
+    } else {
+        Some(cfg.date_timezone.parse::<Tz>().map_err(|e| {
+            format!(
+                "Invalid date_timezone '{}' in config {}: {}",
+                cfg.date_timezone, cfg.key, e
+            )
+        })?)
+    };
+    let sorted_items = sort_items(items, cfg.fix_text_order[1], cfg.fix_text_order[0]);
+
+    Ok(TransactionStream {
+        text_items: tokenise_items(&sorted_items),
+        parser: StatementDataParser::new(cfg),
+        date_timezone,
+        date: None,
+        balance: None,
+        count: 0,
+        parsed: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixers::FixersMode;
+    use crate::parsers::flows::config_json_file_to_config::from_json_str;
+    use crate::parsers::flows::layout_to_text_items::layout_to_text_items;
+    use crate::parsers::flows::text_items_to_statement_datas::text_items_to_statement_datas;
+    use crate::test_fixtures::{ditto_layout, multiline_layout};
+
+    fn make_text_item(text: &str, x1: i32, x2: i32, y1: i32) -> TextItem {
+        TextItem::new(text.to_string(), x1, y1, x2, y1 + 10, 0)
+    }
+
+    fn make_config() -> StatementConfig {
+        StatementConfig {
+            key: "stream".to_string(),
+            start_date_terms: vec!["Statement Period:".to_string()],
+            start_date_formats: vec!["format2".to_string()],
+            opening_balance_terms: vec!["Opening balance".to_string()],
+            opening_balance_formats: vec!["format1".to_string()],
+            transaction_terms: vec!["Transactions".to_string()],
+            transaction_formats: vec![
+                vec![
+                    "date".to_string(),
+                    "description".to_string(),
+                    "amount".to_string(),
+                ],
+                vec!["description".to_string(), "amount".to_string()],
+            ],
+            transaction_date_formats: vec!["format4".to_string()],
+            transaction_date_headers: vec!["Date".to_string()],
+            transaction_description_headers: vec!["Description".to_string()],
+            transaction_amount_formats: vec!["format1".to_string()],
+            transaction_amount_headers: vec!["Amount".to_string()],
+            transaction_amount_alignment: "x2".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn make_items() -> Vec<TextItem> {
+        vec![
+            make_text_item("Statement", 77, 131, 40),
+            make_text_item("Period:", 134, 176, 40),
+            make_text_item("1", 268, 274, 40),
+            make_text_item("April", 277, 307, 40),
+            make_text_item("2024", 310, 334, 40),
+            make_text_item("Opening", 100, 140, 60),
+            make_text_item("balance", 142, 180, 60),
+            make_text_item("1,000.00", 260, 300, 60),
+            make_text_item("Transactions", 100, 180, 100),
+            make_text_item("Date", 40, 70, 120),
+            make_text_item("Description", 100, 160, 120),
+            make_text_item("Amount", 260, 300, 120),
+            make_text_item("02/04/2024", 40, 90, 140),
+            make_text_item("Payment", 100, 140, 140),
+            make_text_item("50.00", 270, 300, 140),
+            make_text_item("Fee", 100, 140, 160),
+            make_text_item("-2.00", 270, 300, 160),
+            make_text_item("05/04/2024", 40, 90, 180),
+            make_text_item("Refund", 100, 140, 180),
+            make_text_item("10.00", 270, 300, 180),
+        ]
+    }
+
+    #[test]
+    fn test_stream_fills_implicit_dates_and_balances() {
+        let transactions: Vec<Transaction> =
+            text_items_to_transactions(&make_items(), &make_config())
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap();
+
+        let values: Vec<(i64, &str, f64, f64)> = transactions
+            .iter()
+            .map(|tx| (tx.date, tx.description.as_str(), tx.amount, tx.balance))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                (1_712_016_000_000, "Payment", 50.0, 1050.0), // 2024-04-02
+                (1_712_016_000_000, "Fee", -2.0, 1048.0),
+                (1_712_275_200_000, "Refund", 10.0, 1058.0), // 2024-04-05
+            ]
+        );
+    }
+
+    fn assert_stream_matches_full_parse(items: &Vec<TextItem>, cfg: StatementConfig) {
+        let streamed: Vec<Transaction> = text_items_to_transactions(items, &cfg)
+            .unwrap()
+            .map(|tx| tx.unwrap())
+            .collect();
+        let parsed =
+            text_items_to_statement_datas(items, &vec![cfg], FixersMode::All, false, false)
+                .unwrap()
+                .remove(0);
+
+        assert_eq!(streamed.len(), parsed.proto_transactions.len());
+        for (tx, proto_tx) in streamed.iter().zip(&parsed.proto_transactions) {
+            let expected = proto_tx.to_transaction().unwrap();
+            assert_eq!(
+                (tx.date, &tx.description, tx.amount, tx.balance),
+                (
+                    expected.date,
+                    &expected.description,
+                    expected.amount,
+                    expected.balance
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_matches_full_parse() {
+        assert_stream_matches_full_parse(&make_items(), make_config());
+    }
+
+    #[test]
+    fn test_stream_matches_full_parse_with_ditto_marks() {
+        let items = layout_to_text_items(&ditto_layout()).unwrap();
+        let cfg = from_json_str(include_str!("../../../tests/fixtures/test3_config.json")).unwrap();
+        assert_stream_matches_full_parse(&items, cfg);
+    }
+
+    #[test]
+    fn test_stream_matches_full_parse_with_wrapped_descriptions() {
+        let items = layout_to_text_items(&multiline_layout()).unwrap();
+        let mut cfg =
+            from_json_str(include_str!("../../../tests/fixtures/test3_config.json")).unwrap();
+        cfg.transaction_description_multiline = true;
+        assert_stream_matches_full_parse(&items, cfg);
+    }
+
+    #[test]
+    fn test_stream_yields_incomplete_transaction_as_error() {
+        let mut cfg = make_config();
+        cfg.opening_balance_terms = vec![];
+
+        let results: Vec<Result<Transaction, String>> =
+            text_items_to_transactions(&make_items(), &cfg)
+                .unwrap()
+                .collect();
+
+        assert_eq!(results.len(), 3);
+        assert!(
+            results[0]
+                .as_ref()
+                .unwrap_err()
+                .starts_with("Transaction 1 ('Payment')")
+        );
+    }
+}
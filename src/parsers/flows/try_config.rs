@@ -0,0 +1,112 @@
+use crate::configs::validate::config_warnings;
+use crate::fixers::FixersMode;
+use crate::parsers::flows::config_json_file_to_config::from_json_str;
+use crate::parsers::flows::text_items_to_statement_datas::text_items_to_statement_datas;
+use crate::structs::StatementData;
+use crate::structs::TextItem;
+
+/// Result of parsing a statement with a draft config (see try_config)
+#[derive(Debug, Clone)]
+pub struct ConfigTrial {
+    /// Key of the draft config
+    pub key: String,
+    /// Likely mistakes in the draft config (see config_warnings)
+    pub config_warnings: Vec<String>,
+    /// Statement data of each account section (a single result unless the config
+    /// is multi_account), whether or not error-free
+    pub statement_datas: Vec<StatementData>,
+}
+
+impl ConfigTrial {
+    /// True if the statement data of every account section is error-free
+    pub fn passed(&self) -> bool {
+        self.statement_datas
+            .iter()
+            .all(|data| data.errors.is_empty())
+    }
+
+    /// Errors of every account section, in statement order
+    pub fn errors(&self) -> Vec<String> {
+        self.statement_datas
+            .iter()
+            .flat_map(|data| data.errors.iter().cloned())
+            .collect()
+    }
+
+    /// Warnings of every account section, in statement order
+    pub fn warnings(&self) -> Vec<String> {
+        self.statement_datas
+            .iter()
+            .flat_map(|data| data.warnings.iter().cloned())
+            .collect()
+    }
+
+    /// Summary of the statement data of each account section, as in the debug output
+    pub fn trace(&self) -> String {
+        let mut output = String::new();
+        for (i, data) in self.statement_datas.iter().enumerate() {
+            output.push_str(&format!("=== StatementData Result {} ===\n", i + 1));
+            output.push_str(&data.to_string());
+            output.push('\n');
+        }
+        output
+    }
+}
+
+/// Parse non-tokenised text items with a draft config JSON string only, without
+/// registering the config or identifying the statement type (the config is assumed
+/// to apply). The config is validated as when loaded. All fixers are applied.
+pub fn try_config(json_str: &str, items: &Vec<TextItem>) -> Result<ConfigTrial, String> {
+    let cfg = from_json_str(json_str)?;
+    let statement_datas =
+        text_items_to_statement_datas(items, &vec![cfg.clone()], FixersMode::All, false, false)?;
+    Ok(ConfigTrial {
+        key: cfg.key.clone(),
+        config_warnings: config_warnings(&cfg),
+        statement_datas,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::flows::layout_to_text_items::layout_to_text_items;
+    use crate::test_fixtures::patch;
+
+    const CONFIG: &str = include_str!("../../../tests/fixtures/test3_config.json");
+    const LAYOUT: &str = include_str!("../../../tests/fixtures/test3_layout.txt");
+
+    #[test]
+    fn test_try_config_parses_with_draft() {
+        let items = layout_to_text_items(LAYOUT).unwrap();
+
+        let trial = try_config(CONFIG, &items).unwrap();
+
+        assert_eq!(trial.key, "au__ssb__savings__1");
+        assert!(trial.passed(), "{:?}", trial.errors());
+        assert_eq!(trial.statement_datas.len(), 1);
+        assert_eq!(trial.statement_datas[0].proto_transactions.len(), 3);
+        assert!(trial.trace().starts_with("=== StatementData Result 1 ==="));
+    }
+
+    #[test]
+    fn test_try_config_reports_errors_of_draft() {
+        let items = layout_to_text_items(LAYOUT).unwrap();
+        let draft = patch(CONFIG, &[("Closing balance:", "Final balance:")]);
+
+        let trial = try_config(&draft, &items).unwrap();
+
+        assert!(!trial.passed());
+        assert!(!trial.errors().is_empty());
+    }
+
+    #[test]
+    fn test_try_config_validates_draft() {
+        let items = layout_to_text_items(LAYOUT).unwrap();
+        let draft = patch(CONFIG, &[("\"Savings\"", "\"Piggy Bank\"")]);
+
+        let error = try_config(&draft, &items).unwrap_err();
+
+        assert!(error.starts_with("Config validation error"), "{}", error);
+    }
+}
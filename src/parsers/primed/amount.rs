@@ -1,7 +1,9 @@
+use crate::formats::AmountHints;
 use crate::parsers::base::AmountParser;
 use crate::parsers::base::ParserPrimer;
 use crate::structs::TextItem;
 
+#[derive(Clone)]
 pub struct PrimedAmountParser {
     primer_parser: ParserPrimer,
     amount_parser: AmountParser,
@@ -14,13 +16,14 @@ impl PrimedAmountParser {
     pub fn new(
         primer_terms: &[&str],
         amount_formats: &[&str],
+        hints: &AmountHints,
         alignment: &str,
         alignment_tol: i32,
         invert: bool,
     ) -> Self {
         Self {
             primer_parser: ParserPrimer::new(primer_terms),
-            amount_parser: AmountParser::new(amount_formats),
+            amount_parser: AmountParser::new(amount_formats, hints),
             alignment: alignment.to_string(),
             alignment_tol,
             invert,
@@ -76,7 +79,11 @@ impl PrimedAmountParser {
             return 0;
         }
 
-        // All conditions met, finalize
+        // All conditions met, finalize with any CR/DR indicator in the next item
+        let consumed = consumed
+            + self
+                .amount_parser
+                .parse_sign_indicator(items, consumed, self.alignment_tol);
         if self.invert {
             self.amount_parser.invert();
         }
@@ -92,11 +99,23 @@ impl PrimedAmountParser {
         self.primer_parser.primed
     }
 
+    /// Text item of the last primer term matched, if primed
+    pub fn primer_item(&self) -> Option<&TextItem> {
+        self.primer_parser.text_item.as_ref()
+    }
+
     /// Get the highest lookahead between primer and amount parsers
+    /// Set whether primer terms are matched ignoring case
+    pub fn with_case_insensitive_terms(mut self, case_insensitive: bool) -> Self {
+        self.primer_parser = self.primer_parser.with_case_insensitive(case_insensitive);
+        self
+    }
+
     pub fn get_max_lookahead(&self) -> usize {
+        // Allow for a CR/DR indicator after the amount
         self.primer_parser
             .max_lookahead
-            .max(self.amount_parser.max_lookahead)
+            .max(self.amount_parser.max_lookahead + 1)
     }
 }
 
@@ -118,7 +137,14 @@ mod tests {
 
     #[test]
     fn test_primer_and_amount_success() {
-        let mut parser = PrimedAmountParser::new(&["PRIME"], &["format1"], "x1", 5, false);
+        let mut parser = PrimedAmountParser::new(
+            &["PRIME"],
+            &["format1"],
+            &AmountHints::default(),
+            "x1",
+            5,
+            false,
+        );
         let items = vec![
             make_text_item("PRIME", 100, 200, 1),
             make_text_item("1,234.56", 102, 202, 1),
@@ -137,7 +163,14 @@ mod tests {
 
     #[test]
     fn test_primer_and_amount_invert() {
-        let mut parser = PrimedAmountParser::new(&["PRIME"], &["format1"], "x1", 5, true);
+        let mut parser = PrimedAmountParser::new(
+            &["PRIME"],
+            &["format1"],
+            &AmountHints::default(),
+            "x1",
+            5,
+            true,
+        );
         let items = vec![
             make_text_item("PRIME", 100, 200, 1),
             make_text_item("1,234.56", 100, 200, 1),
@@ -148,9 +181,72 @@ mod tests {
         assert_eq!(parser.amount_parser.value, Some(-1234.56));
     }
 
+    #[test]
+    fn test_primer_and_amount_sign_indicators() {
+        let mut parser = PrimedAmountParser::new(
+            &["PRIME"],
+            &["format1"],
+            &AmountHints::default(),
+            "y1",
+            5,
+            false,
+        );
+        let items = vec![
+            make_text_item("PRIME", 100, 200, 1),
+            make_text_item("1,234.56", 200, 200, 1),
+            make_text_item("DR", 240, 201, 1),
+        ];
+        parser.parse_items(&items);
+        let consumed = parser.parse_items(&items[1..]);
+        assert_eq!(consumed, 2);
+        assert_eq!(parser.amount_parser.value, Some(-1234.56));
+
+        // Credit indicator keeps the sign, one on the next line is not read
+        let mut parser = PrimedAmountParser::new(
+            &["PRIME"],
+            &["format1"],
+            &AmountHints::default(),
+            "y1",
+            5,
+            false,
+        );
+        let items = vec![
+            make_text_item("PRIME", 100, 200, 1),
+            make_text_item("1,234.56", 200, 200, 1),
+            make_text_item("CR.", 240, 200, 1),
+        ];
+        parser.parse_items(&items);
+        assert_eq!(parser.parse_items(&items[1..]), 2);
+        assert_eq!(parser.amount_parser.value, Some(1234.56));
+
+        let mut parser = PrimedAmountParser::new(
+            &["PRIME"],
+            &["format1"],
+            &AmountHints::default(),
+            "y1",
+            5,
+            false,
+        );
+        let items = vec![
+            make_text_item("PRIME", 100, 200, 1),
+            make_text_item("1,234.56", 200, 200, 1),
+            make_text_item("DR", 100, 220, 1),
+        ];
+        parser.parse_items(&items);
+        assert_eq!(parser.parse_items(&items[1..]), 1);
+        assert_eq!(parser.amount_parser.value, Some(1234.56));
+    }
+
     #[test]
     fn test_primer_x1_fail() {
-        let mut parser = PrimedAmountParser::new(&["PRIME"], &["format1"], "x1", 1, false);
+        let mut parser = PrimedAmountParser::new(
+            &["PRIME"],
+            &["format1"],
+            &AmountHints::default(),
+            "x1",
+            1,
+            false,
+        );
         let items = vec![
             make_text_item("PRIME", 100, 200, 1),
             make_text_item("1,234.56", 105, 200, 1),
@@ -163,7 +259,14 @@ mod tests {
 
     #[test]
     fn test_primer_y1_fail() {
-        let mut parser = PrimedAmountParser::new(&["PRIME"], &["format1"], "y1", 1, false);
+        let mut parser = PrimedAmountParser::new(
+            &["PRIME"],
+            &["format1"],
+            &AmountHints::default(),
+            "y1",
+            1,
+            false,
+        );
         let items = vec![
             make_text_item("PRIME", 100, 200, 1),
             make_text_item("1,234.56", 100, 205, 1),
@@ -176,7 +279,14 @@ mod tests {
 
     #[test]
     fn test_primer_page_fail() {
-        let mut parser = PrimedAmountParser::new(&["PRIME"], &["format1"], "", 0, false);
+        let mut parser = PrimedAmountParser::new(
+            &["PRIME"],
+            &["format1"],
+            &AmountHints::default(),
+            "",
+            0,
+            false,
+        );
         let items = vec![
             make_text_item("PRIME", 100, 200, 1),
             make_text_item("1,234.56", 100, 200, 2),
@@ -189,7 +299,14 @@ mod tests {
 
     #[test]
     fn test_no_items() {
-        let mut parser = PrimedAmountParser::new(&["PRIME"], &["format1"], "x1", 5, false);
+        let mut parser = PrimedAmountParser::new(
+            &["PRIME"],
+            &["format1"],
+            &AmountHints::default(),
+            "x1",
+            5,
+            false,
+        );
         let items: Vec<TextItem> = vec![];
         let consumed = parser.parse_items(&items);
         assert_eq!(consumed, 0);
@@ -199,7 +316,14 @@ mod tests {
 
     #[test]
     fn test_amount_already_set() {
-        let mut parser = PrimedAmountParser::new(&["PRIME"], &["format1"], "x1", 5, false);
+        let mut parser = PrimedAmountParser::new(
+            &["PRIME"],
+            &["format1"],
+            &AmountHints::default(),
+            "x1",
+            5,
+            false,
+        );
         let items = vec![
             make_text_item("PRIME", 100, 200, 1),
             make_text_item("1,234.56", 100, 200, 1),
@@ -2,11 +2,14 @@ use crate::parsers::base::DateParser;
 use crate::parsers::base::ParserPrimer;
 use crate::structs::TextItem;
 
+#[derive(Clone)]
 pub struct PrimedDateParser {
     primer_parser: ParserPrimer,
     date_parser: DateParser,
     alignment: String,
     alignment_tol: i32,
+    missing_year: Option<String>,
+    missing_year_found: bool,
 }
 
 impl PrimedDateParser {
@@ -21,6 +24,8 @@ impl PrimedDateParser {
             date_parser: DateParser::new(date_formats),
             alignment: alignment.to_string(),
             alignment_tol,
+            missing_year: None,
+            missing_year_found: false,
         }
     }
 
@@ -39,9 +44,11 @@ impl PrimedDateParser {
             return self.primer_parser.parse_items(items);
         }
 
-        // Primer is primed, look for date
-        let consumed = self.date_parser.parse_items(items, "");
-        if consumed == 0 {
+        // Primer is primed, look for date. Dates without a year cannot be read, but are
+        // recorded if aligned so that the mistake can be reported
+        let result = self.date_parser.parse_items(items, "");
+        let consumed = result.clone().unwrap_or(0);
+        if consumed == 0 && result.is_ok() {
             return 0; // No date found
         }
 
@@ -65,6 +72,14 @@ impl PrimedDateParser {
             self.date_parser.reset();
             return 0;
         }
+        if result.is_err() {
+            if !self.missing_year_found {
+                self.missing_year = Some(date_item.text.clone());
+                self.missing_year_found = true;
+            }
+            self.date_parser.reset();
+            return 0;
+        }
 
         // All conditions met
         consumed
@@ -74,12 +89,30 @@ impl PrimedDateParser {
         self.date_parser.value
     }
 
+    /// Take the text of the first aligned date without a year found after the primer
+    /// term, which cannot be read as no year is given (e.g., "24 Mar")
+    pub fn take_missing_year(&mut self) -> Option<String> {
+        self.missing_year.take()
+    }
+
     /// Whether the primer term has been matched
     pub fn is_primed(&self) -> bool {
         self.primer_parser.primed
     }
 
     /// Get the highest lookahead between primer and date parsers
+    /// Set whether primer terms are matched ignoring case
+    pub fn with_case_insensitive_terms(mut self, case_insensitive: bool) -> Self {
+        self.primer_parser = self.primer_parser.with_case_insensitive(case_insensitive);
+        self
+    }
+
+    /// Set the pivot year of 2-digit years (see DateParser::with_two_digit_year_pivot)
+    pub fn with_two_digit_year_pivot(mut self, pivot: Option<u32>) -> Self {
+        self.date_parser = self.date_parser.with_two_digit_year_pivot(pivot);
+        self
+    }
+
     pub fn get_max_lookahead(&self) -> usize {
         self.primer_parser
             .max_lookahead
@@ -184,4 +217,19 @@ mod tests {
         let consumed = parser.parse_items(&items[1..]);
         assert_eq!(consumed, 0);
     }
+
+    #[test]
+    fn test_date_without_year_is_recorded() {
+        let mut parser = PrimedDateParser::new(&["DATE"], &["format1"], "y1", 1);
+        let items = vec![
+            make_text_item("DATE", 100, 200, 1),
+            make_text_item("24 mar", 150, 200, 1),
+        ];
+        parser.parse_items(&items);
+        let consumed = parser.parse_items(&items[1..]);
+        assert_eq!(consumed, 0);
+        assert!(parser.value().is_none());
+        assert_eq!(parser.take_missing_year(), Some("24 mar".to_string()));
+        assert_eq!(parser.take_missing_year(), None);
+    }
 }
@@ -3,6 +3,7 @@ use crate::parsers::base::ValueParser;
 use crate::structs::TextItem;
 use regex::Regex;
 
+#[derive(Clone)]
 pub struct PrimedValueParser {
     primer_parser: ParserPrimer,
     value_parser: ValueParser,
@@ -84,6 +85,12 @@ impl PrimedValueParser {
     }
 
     /// Get the highest lookahead between primer and date parsers
+    /// Set whether primer terms are matched ignoring case
+    pub fn with_case_insensitive_terms(mut self, case_insensitive: bool) -> Self {
+        self.primer_parser = self.primer_parser.with_case_insensitive(case_insensitive);
+        self
+    }
+
     pub fn get_max_lookahead(&self) -> usize {
         self.primer_parser
             .max_lookahead
@@ -177,6 +184,33 @@ mod tests {
         assert_eq!(parser.value(), Some("1234 5678 9012"));
     }
 
+    #[test]
+    fn test_value_straddling_page_break_not_joined() {
+        let patterns = vec![Regex::new(r"\b\d{4}\s\d{4}\b").unwrap()];
+        let mut parser = PrimedValueParser::new(&["Account"], &patterns, "", 0);
+        parser.parse_items(&[create_text_item("Account", 100, 700)]);
+
+        // Tokens of the value at the bottom of page 1 and the top of page 2
+        let mut next_page_item = create_text_item("5678", 150, 50);
+        next_page_item.page = 2;
+        let items = vec![create_text_item("1234", 100, 710), next_page_item];
+        assert_eq!(parser.parse_items(&items), 0);
+        assert!(parser.value().is_none());
+
+        // Recovers on the next page
+        let mut items = vec![
+            create_text_item("Account", 100, 100),
+            create_text_item("1234", 100, 110),
+            create_text_item("5678", 150, 110),
+        ];
+        for item in &mut items {
+            item.page = 2;
+        }
+        assert_eq!(parser.parse_items(&items[..1]), 1);
+        assert_eq!(parser.parse_items(&items[1..]), 2);
+        assert_eq!(parser.value(), Some("1234 5678"));
+    }
+
     #[test]
     fn test_same_x1_constraint_pass() {
         let patterns = vec![Regex::new(r"\b\d{4}\b").unwrap()];
@@ -1,6 +1,7 @@
 use crate::parsers::primed::PrimedValueParser;
 use crate::structs::{StatementConfig, StatementData, TextItem};
 
+#[derive(Clone)]
 pub struct AccountNumberParser {
     pub(crate) parser: PrimedValueParser,
 }
@@ -12,18 +13,14 @@ impl AccountNumberParser {
             .iter()
             .map(|s| s.as_str())
             .collect();
-        let value_patterns: Vec<regex::Regex> = config
-            .account_number_patterns
-            .iter()
-            .filter_map(|p| regex::Regex::new(p.as_str()).ok())
-            .collect();
         Self {
             parser: PrimedValueParser::new(
                 primer_terms.as_slice(),
-                value_patterns.as_slice(),
+                config.account_number_patterns.as_slice(),
                 config.account_number_alignment.as_str(),
                 config.account_number_alignment_tol,
-            ),
+            )
+            .with_case_insensitive_terms(config.terms_case_insensitive),
         }
     }
 
@@ -1,8 +1,27 @@
+use crate::parsers::base::ParserPrimer;
 use crate::parsers::primed::PrimedAmountParser;
+use crate::structs::text_items::get_text_item_buffer;
 use crate::structs::{StatementConfig, StatementData, TextItem};
 
+/// Closing balance read at an occurrence of the closing balance terms
+#[derive(Clone)]
+struct Candidate {
+    value: f64,
+    /// Closing balance term of the occurrence
+    term: TextItem,
+}
+
+#[derive(Clone)]
 pub struct ClosingBalanceParser {
     pub(crate) parser: PrimedAmountParser,
+    /// Unused parser, copied after each candidate when every occurrence is read
+    template: PrimedAmountParser,
+    occurrence: String,
+    page: Option<i32>,
+    exclude_terms: Vec<String>,
+    exclude_distance: i32,
+    case_insensitive: bool,
+    candidates: Vec<Candidate>,
 }
 
 impl ClosingBalanceParser {
@@ -17,28 +36,133 @@ impl ClosingBalanceParser {
             .iter()
             .map(|s| s.as_str())
             .collect();
+        let parser = PrimedAmountParser::new(
+            primer_terms.as_slice(),
+            amount_formats.as_slice(),
+            &config.amount_hints(),
+            &config.closing_balance_alignment,
+            config.closing_balance_alignment_tol,
+            config.closing_balance_invert,
+        )
+        .with_case_insensitive_terms(config.terms_case_insensitive);
         Self {
-            parser: PrimedAmountParser::new(
-                primer_terms.as_slice(),
-                amount_formats.as_slice(),
-                &config.closing_balance_alignment,
-                config.closing_balance_alignment_tol,
-                config.closing_balance_invert,
-            ),
+            template: parser.clone(),
+            parser,
+            occurrence: config.closing_balance_occurrence.clone(),
+            page: config.closing_balance_page,
+            exclude_terms: config.closing_balance_exclude_terms.clone(),
+            exclude_distance: config.closing_balance_exclude_distance,
+            case_insensitive: config.terms_case_insensitive,
+            candidates: Vec::new(),
         }
     }
 
+    /// Whether every occurrence is read, to choose one once parsing finishes (see
+    /// finish). Otherwise the first closing balance read is set, as it is found.
+    fn reads_all(&self) -> bool {
+        self.occurrence == "last" || self.page.is_some() || !self.exclude_terms.is_empty()
+    }
+
     pub fn parse_items(&mut self, items: &[TextItem], data: &mut StatementData) -> usize {
         let consumed = self.parser.parse_items(items);
         if consumed > 0
             && let Some(value) = self.parser.value()
-            && data.closing_balance().is_none()
         {
-            data.set_closing_balance(value);
+            if self.reads_all() {
+                if let Some(term) = self.parser.primer_item().cloned() {
+                    self.candidates.push(Candidate { value, term });
+                }
+                self.parser = self.template.clone();
+            } else if data.closing_balance().is_none() {
+                data.set_closing_balance(value);
+            }
         }
         consumed
     }
 
+    /// Set the closing balance from the occurrences read, if every occurrence is read:
+    /// the first or last (closing_balance_occurrence) of those on closing_balance_page
+    /// without an exclude term nearby. Each occurrence is recorded in the closing
+    /// balance candidates, with the reason it was skipped.
+    pub fn finish(&mut self, text_items: &[TextItem], data: &mut StatementData) {
+        if !self.reads_all() || self.candidates.is_empty() {
+            return;
+        }
+        let exclude_items = self.find_exclude_items(text_items);
+        let skip_reasons: Vec<Option<String>> = self
+            .candidates
+            .iter()
+            .map(|candidate| {
+                if let Some(page) = self.page
+                    && candidate.term.page != page
+                {
+                    return Some(format!("not on closing_balance_page {}", page));
+                }
+                exclude_items
+                    .iter()
+                    .find(|item| self.is_near(item, &candidate.term))
+                    .map(|item| format!("near exclude term '{}'", item.text))
+            })
+            .collect();
+        let mut kept = (0..self.candidates.len()).filter(|&n| skip_reasons[n].is_none());
+        let used = if self.occurrence == "last" {
+            kept.next_back()
+        } else {
+            kept.next()
+        };
+        for (n, candidate) in self.candidates.iter().enumerate() {
+            let status = match &skip_reasons[n] {
+                Some(reason) => format!("skipped: {}", reason),
+                None if Some(n) == used => "used".to_string(),
+                None => format!(
+                    "not used: closing_balance_occurrence is \"{}\"",
+                    self.occurrence
+                ),
+            };
+            data.closing_balance_candidates.push(format!(
+                "Page {}: {} ({})",
+                candidate.term.page, candidate.value, status
+            ));
+        }
+        if let Some(n) = used
+            && data.closing_balance().is_none()
+        {
+            data.set_closing_balance(self.candidates[n].value);
+        }
+    }
+
+    /// Find each occurrence of the exclude terms in the text items
+    fn find_exclude_items(&self, text_items: &[TextItem]) -> Vec<TextItem> {
+        let terms: Vec<&str> = self.exclude_terms.iter().map(|t| t.as_str()).collect();
+        let mut primer = ParserPrimer::new(&terms).with_case_insensitive(self.case_insensitive);
+        let mut found = Vec::new();
+        let mut i = 0;
+        while i < text_items.len() {
+            let buffer_size = primer.max_lookahead.min(text_items.len() - i);
+            let consumed = primer.parse_items(&get_text_item_buffer(text_items, i, buffer_size));
+            if consumed > 0 {
+                found.push(primer.text_item().clone());
+                i += consumed;
+            } else {
+                i += 1;
+            }
+        }
+        found
+    }
+
+    /// Whether the gap between the boxes of two items on the same page is at most the
+    /// exclude distance, both horizontally and vertically
+    fn is_near(&self, item: &TextItem, term: &TextItem) -> bool {
+        let gap = |a1: i32, a2: i32, b1: i32, b2: i32| {
+            let (a_lower, a_upper) = (a1.min(a2), a1.max(a2));
+            let (b_lower, b_upper) = (b1.min(b2), b1.max(b2));
+            (b_lower - a_upper).max(a_lower - b_upper).max(0)
+        };
+        item.page == term.page
+            && gap(item.x1, item.x2, term.x1, term.x2) <= self.exclude_distance
+            && gap(item.y1, item.y2, term.y1, term.y2) <= self.exclude_distance
+    }
+
     pub fn get_max_lookahead(&self) -> usize {
         self.parser.get_max_lookahead()
     }
@@ -144,4 +268,44 @@ mod tests {
         assert_eq!(consumed, 0);
         assert!(data.closing_balance().is_none());
     }
+
+    #[test]
+    fn test_closing_balance_occurrence_and_page() {
+        let items = vec![
+            make_text_item("CLOSING BALANCE", 100, 200, 0),
+            make_text_item("100.00", 100, 220, 0),
+            make_text_item("CLOSING BALANCE", 100, 200, 1),
+            make_text_item("250.00", 100, 220, 1),
+        ];
+        let parse = |config: &StatementConfig| {
+            let mut data = StatementData::new();
+            let mut parser = ClosingBalanceParser::new(config);
+            let mut i = 0;
+            while i < items.len() {
+                i += parser.parse_items(&items[i..], &mut data).max(1);
+            }
+            parser.finish(&items, &mut data);
+            data
+        };
+
+        let mut config = default_config();
+        config.closing_balance_occurrence = "last".to_string();
+        let data = parse(&config);
+        assert_eq!(data.closing_balance(), Some(250.0));
+        assert_eq!(
+            data.closing_balance_candidates,
+            vec![
+                "Page 0: 100 (not used: closing_balance_occurrence is \"last\")",
+                "Page 1: 250 (used)",
+            ]
+        );
+
+        config.closing_balance_page = Some(0);
+        let data = parse(&config);
+        assert_eq!(data.closing_balance(), Some(100.0));
+        assert_eq!(
+            data.closing_balance_candidates[1],
+            "Page 1: 250 (skipped: not on closing_balance_page 0)"
+        );
+    }
 }
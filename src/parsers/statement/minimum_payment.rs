@@ -0,0 +1,108 @@
+use crate::parsers::primed::PrimedAmountParser;
+use crate::structs::{StatementConfig, StatementData, TextItem};
+
+/// Parser for the minimum payment of a credit card statement.
+#[derive(Clone)]
+pub struct MinimumPaymentParser {
+    pub(crate) parser: PrimedAmountParser,
+}
+
+impl MinimumPaymentParser {
+    pub fn new(config: &StatementConfig) -> Self {
+        let primer_terms: Vec<&str> = config
+            .minimum_payment_terms
+            .iter()
+            .map(|s| s.as_str())
+            .collect();
+        let amount_formats: Vec<&str> = config
+            .minimum_payment_formats
+            .iter()
+            .map(|s| s.as_str())
+            .collect();
+        Self {
+            parser: PrimedAmountParser::new(
+                primer_terms.as_slice(),
+                amount_formats.as_slice(),
+                &config.amount_hints(),
+                &config.minimum_payment_alignment,
+                config.minimum_payment_alignment_tol,
+                false,
+            )
+            .with_case_insensitive_terms(config.terms_case_insensitive),
+        }
+    }
+
+    pub fn parse_items(&mut self, items: &[TextItem], data: &mut StatementData) -> usize {
+        let consumed = self.parser.parse_items(items);
+        if consumed > 0
+            && let Some(value) = self.parser.value()
+            && data.minimum_payment().is_none()
+        {
+            data.set_minimum_payment(value);
+        }
+        consumed
+    }
+
+    pub fn get_max_lookahead(&self) -> usize {
+        self.parser.get_max_lookahead()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::{StatementConfig, StatementData, TextItem};
+
+    fn make_text_item(text: &str, x1: i32, y1: i32, page: i32) -> TextItem {
+        TextItem {
+            text: text.to_string(),
+            x1,
+            y1,
+            x2: x1 + 10,
+            y2: y1 + 10,
+            page,
+        }
+    }
+
+    fn default_config() -> StatementConfig {
+        StatementConfig {
+            minimum_payment_terms: vec!["MINIMUM PAYMENT".to_string()],
+            minimum_payment_formats: vec!["format2".to_string()],
+            minimum_payment_alignment: "y1".to_string(),
+            minimum_payment_alignment_tol: 5,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_minimum_payment_success() {
+        let config = default_config();
+        let mut data = StatementData::new();
+        let mut parser = MinimumPaymentParser::new(&config);
+
+        let items = vec![
+            make_text_item("MINIMUM PAYMENT", 100, 200, 1),
+            make_text_item("$25.00", 300, 202, 1),
+        ];
+
+        assert_eq!(parser.parse_items(&items, &mut data), 1);
+        assert_eq!(parser.parse_items(&items[1..], &mut data), 1);
+        assert_eq!(data.minimum_payment(), Some(25.0));
+    }
+
+    #[test]
+    fn test_minimum_payment_misaligned() {
+        let config = default_config();
+        let mut data = StatementData::new();
+        let mut parser = MinimumPaymentParser::new(&config);
+
+        let items = vec![
+            make_text_item("MINIMUM PAYMENT", 100, 200, 1),
+            make_text_item("$25.00", 300, 230, 1),
+        ];
+
+        parser.parse_items(&items, &mut data);
+        assert_eq!(parser.parse_items(&items[1..], &mut data), 0);
+        assert!(data.minimum_payment().is_none());
+    }
+}
@@ -1,11 +1,19 @@
 pub mod account_number;
 pub mod closing_balance;
+pub mod end_date;
+pub mod minimum_payment;
+pub mod no_transactions;
 pub mod opening_balance;
+pub mod payment_due_date;
 pub mod start_date;
 pub mod transaction;
 
 pub use account_number::AccountNumberParser;
 pub use closing_balance::ClosingBalanceParser;
+pub use end_date::EndDateParser;
+pub use minimum_payment::MinimumPaymentParser;
+pub use no_transactions::NoTransactionsParser;
 pub use opening_balance::OpeningBalanceParser;
+pub use payment_due_date::PaymentDueDateParser;
 pub use start_date::StartDateParser;
 pub use transaction::TransactionParser;
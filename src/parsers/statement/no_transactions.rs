@@ -0,0 +1,108 @@
+use crate::parsers::base::ParserPrimer;
+use crate::structs::{StatementConfig, TextItem};
+
+/// Parser for terms declaring that a statement has no transactions
+/// (e.g., "There were no transactions during this period").
+#[derive(Clone)]
+pub struct NoTransactionsParser {
+    primer: ParserPrimer,
+    /// A term was found within the transaction table
+    found_in_table: bool,
+    /// A term was found outside the transaction table
+    found_outside_table: bool,
+}
+
+impl NoTransactionsParser {
+    pub fn new(config: &StatementConfig) -> Self {
+        let terms: Vec<&str> = config
+            .no_transaction_terms
+            .iter()
+            .map(|s| s.as_str())
+            .collect();
+        Self {
+            primer: ParserPrimer::new(&terms).with_case_insensitive(config.terms_case_insensitive),
+            found_in_table: false,
+            found_outside_table: false,
+        }
+    }
+
+    /// Consume a no transactions term, recording whether it was found within the
+    /// transaction table (after the column headers, before the stop term).
+    pub fn parse_items(&mut self, items: &[TextItem], in_table: bool) -> usize {
+        let consumed = self.primer.parse_items(items);
+        if consumed > 0 {
+            if in_table {
+                self.found_in_table = true;
+            } else {
+                self.found_outside_table = true;
+            }
+        }
+        consumed
+    }
+
+    /// Check if the statement declares that it has no transactions. If the transaction
+    /// table was found, only terms within the table count, so that similar phrases
+    /// elsewhere (e.g., marketing text) are ignored.
+    pub fn is_declared(&self, table_found: bool) -> bool {
+        if table_found {
+            self.found_in_table
+        } else {
+            self.found_in_table || self.found_outside_table
+        }
+    }
+
+    pub fn get_max_lookahead(&self) -> usize {
+        self.primer.max_lookahead
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_text_item(text: &str) -> TextItem {
+        TextItem::new(text.to_string(), 100, 100, 200, 110, 0)
+    }
+
+    fn make_items() -> Vec<TextItem> {
+        vec![
+            make_text_item("No"),
+            make_text_item("transactions"),
+            make_text_item("this"),
+            make_text_item("period"),
+        ]
+    }
+
+    fn make_config() -> StatementConfig {
+        StatementConfig {
+            no_transaction_terms: vec!["No transactions this period".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_no_transactions_in_table() {
+        let mut parser = NoTransactionsParser::new(&make_config());
+
+        assert_eq!(parser.parse_items(&make_items(), true), 4);
+        assert!(parser.is_declared(true));
+        assert!(parser.is_declared(false));
+    }
+
+    #[test]
+    fn test_no_transactions_outside_table_ignored_if_table_found() {
+        let mut parser = NoTransactionsParser::new(&make_config());
+
+        assert_eq!(parser.parse_items(&make_items(), false), 4);
+        assert!(!parser.is_declared(true));
+        assert!(parser.is_declared(false));
+    }
+
+    #[test]
+    fn test_no_transactions_not_found() {
+        let mut parser = NoTransactionsParser::new(&make_config());
+
+        assert_eq!(parser.parse_items(&make_items()[1..], true), 0);
+        assert!(!parser.is_declared(false));
+    }
+}
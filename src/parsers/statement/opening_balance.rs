@@ -1,6 +1,7 @@
 use crate::parsers::primed::PrimedAmountParser;
 use crate::structs::{StatementConfig, StatementData, TextItem};
 
+#[derive(Clone)]
 pub struct OpeningBalanceParser {
     parser: PrimedAmountParser,
 }
@@ -22,10 +23,12 @@ impl OpeningBalanceParser {
             parser: PrimedAmountParser::new(
                 primer_terms.as_slice(),
                 amount_formats.as_slice(),
+                &config.amount_hints(),
                 &config.opening_balance_alignment,
                 config.opening_balance_alignment_tol,
                 config.opening_balance_invert,
-            ),
+            )
+            .with_case_insensitive_terms(config.terms_case_insensitive),
         }
     }
 
@@ -146,4 +149,22 @@ mod tests {
         assert_eq!(consumed, 0);
         assert!(data.opening_balance().is_none());
     }
+
+    #[test]
+    fn test_opening_balance_case_insensitive() {
+        let mut config = default_config();
+        config.opening_balance_terms = vec!["Opening  balance".to_string()];
+        config.terms_case_insensitive = true;
+        let mut data = StatementData::new();
+        let mut parser = OpeningBalanceParser::new(&config);
+
+        let items = vec![
+            make_text_item("OPENING BALANCE", 100, 200, 1),
+            make_text_item("1,234.56", 102, 202, 1),
+        ];
+
+        parser.parse_items(&items, &mut data);
+        parser.parse_items(&items[1..], &mut data);
+        assert_eq!(data.opening_balance(), Some(1234.56));
+    }
 }
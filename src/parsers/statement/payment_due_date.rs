@@ -0,0 +1,120 @@
+use crate::formats::date::DateParseError;
+use crate::parsers::primed::PrimedDateParser;
+use crate::structs::{StatementConfig, StatementData, TextItem};
+
+/// Parser for the payment due date of a credit card statement.
+#[derive(Clone)]
+pub struct PaymentDueDateParser {
+    pub(crate) parser: PrimedDateParser,
+}
+
+impl PaymentDueDateParser {
+    pub fn new(config: &StatementConfig) -> Self {
+        let primer_terms: Vec<&str> = config
+            .payment_due_date_terms
+            .iter()
+            .map(|s| s.as_str())
+            .collect();
+        let date_formats: Vec<&str> = config
+            .payment_due_date_formats
+            .iter()
+            .map(|s| s.as_str())
+            .collect();
+        Self {
+            parser: PrimedDateParser::new(
+                primer_terms.as_slice(),
+                date_formats.as_slice(),
+                &config.payment_due_date_alignment,
+                config.payment_due_date_alignment_tol,
+            )
+            .with_case_insensitive_terms(config.terms_case_insensitive)
+            .with_two_digit_year_pivot(config.two_digit_year_pivot),
+        }
+    }
+
+    pub fn parse_items(&mut self, items: &[TextItem], data: &mut StatementData) -> usize {
+        let consumed = self.parser.parse_items(items);
+        if consumed > 0
+            && let Some(value) = self.parser.value()
+            && data.payment_due_date().is_none()
+        {
+            data.set_payment_due_date(value);
+        }
+        if let Some(text) = self.parser.take_missing_year() {
+            data.add_error(format!(
+                "Invalid payment due date '{}'. {}",
+                text,
+                DateParseError::MissingYear
+            ));
+        }
+        consumed
+    }
+
+    pub fn get_max_lookahead(&self) -> usize {
+        self.parser.get_max_lookahead()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::{StatementConfig, StatementData, TextItem};
+
+    fn make_text_item(text: &str, x1: i32, y1: i32, page: i32) -> TextItem {
+        TextItem {
+            text: text.to_string(),
+            x1,
+            y1,
+            x2: x1 + 10,
+            y2: y1 + 10,
+            page,
+        }
+    }
+
+    fn default_config() -> StatementConfig {
+        StatementConfig {
+            payment_due_date_terms: vec!["PAYMENT DUE DATE".to_string()],
+            payment_due_date_formats: vec!["format2".to_string()],
+            payment_due_date_alignment: "y1".to_string(),
+            payment_due_date_alignment_tol: 5,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_payment_due_date_success() {
+        let config = default_config();
+        let mut data = StatementData::new();
+        let mut parser = PaymentDueDateParser::new(&config);
+
+        let items = vec![
+            make_text_item("PAYMENT DUE DATE", 100, 200, 1),
+            make_text_item("21", 300, 200, 1),
+            make_text_item("May", 320, 200, 1),
+            make_text_item("2024", 340, 200, 1),
+        ];
+
+        assert_eq!(parser.parse_items(&items, &mut data), 1);
+        assert_eq!(parser.parse_items(&items[1..], &mut data), 3);
+        // 21 May 2024 00:00 UTC
+        assert_eq!(data.payment_due_date(), Some(1716249600000));
+    }
+
+    #[test]
+    fn test_payment_due_date_page_mismatch() {
+        let config = default_config();
+        let mut data = StatementData::new();
+        let mut parser = PaymentDueDateParser::new(&config);
+
+        let items = vec![
+            make_text_item("PAYMENT DUE DATE", 100, 200, 1),
+            make_text_item("21", 300, 200, 2),
+            make_text_item("May", 320, 200, 2),
+            make_text_item("2024", 340, 200, 2),
+        ];
+
+        parser.parse_items(&items, &mut data);
+        assert_eq!(parser.parse_items(&items[1..], &mut data), 0);
+        assert!(data.payment_due_date().is_none());
+    }
+}
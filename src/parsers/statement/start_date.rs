@@ -1,6 +1,8 @@
+use crate::formats::date::DateParseError;
 use crate::parsers::primed::PrimedDateParser;
 use crate::structs::{StatementConfig, StatementData, TextItem};
 
+#[derive(Clone)]
 pub struct StartDateParser {
     pub(crate) parser: PrimedDateParser,
 }
@@ -19,7 +21,9 @@ impl StartDateParser {
                 date_formats.as_slice(),
                 &config.start_date_alignment,
                 config.start_date_alignment_tol,
-            ),
+            )
+            .with_case_insensitive_terms(config.terms_case_insensitive)
+            .with_two_digit_year_pivot(config.two_digit_year_pivot),
         }
     }
 
@@ -31,6 +35,13 @@ impl StartDateParser {
         {
             data.set_start_date(value);
         }
+        if let Some(text) = self.parser.take_missing_year() {
+            data.add_error(format!(
+                "Invalid start date '{}'. {}",
+                text,
+                DateParseError::MissingYear
+            ));
+        }
         consumed
     }
 
@@ -1,16 +1,20 @@
+use crate::formats::date::DateParseError;
 use crate::parsers::base::ParserPrimer;
 use crate::parsers::transaction;
+use crate::parsers::transaction::description::append_description_text;
 use crate::parsers::transaction::{
-    TransactionAmountParser, TransactionBalanceParser, TransactionDateParser,
-    TransactionDescriptionParser,
+    TransactionAmountParser, TransactionBalanceParser, TransactionBlockParser,
+    TransactionDateParser, TransactionDescriptionParser,
 };
 use crate::structs::ProtoTransaction;
 use crate::structs::StatementConfig;
 use crate::structs::StatementData;
 use crate::structs::TextItem;
+use crate::structs::orphan_line::{OrphanLine, get_orphan_lines};
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
+#[derive(Clone)]
 pub struct TransactionParser {
     date_parser: TransactionDateParser,
     date_parser_newline: TransactionDateParser,
@@ -20,10 +24,19 @@ pub struct TransactionParser {
     amount_parser_newline: TransactionAmountParser,
     balance_parser: TransactionBalanceParser,
     balance_parser_newline: TransactionBalanceParser,
+    /// Parser of labelled blocks, used instead of the column parsers if
+    /// transaction_layout is "labelled_blocks"
+    block_parser: Option<TransactionBlockParser>,
     start_primer: ParserPrimer,
     stop_primer: ParserPrimer,
+    /// A stop term only ends the transactions on its page (transaction_terms_stop_scope
+    /// is "page")
+    stop_page_scoped: bool,
     current_transaction: ProtoTransaction,
     compulsory_fields: Vec<String>,
+    /// Some fields of the transaction formats are optional (e.g. "balance?"), so a row
+    /// with the compulsory fields set may end without its last field
+    has_optional_fields: bool,
     all_fields: Vec<String>,
     new_line_fields: Vec<String>,
     end_line_fields: Vec<String>,
@@ -32,6 +45,25 @@ pub struct TransactionParser {
     new_line_tol: i32,
     description_x_bounds_adjusted: bool,
     description_exclude_patterns: Vec<Regex>,
+    description_allows_dates: bool,
+    description_allows_amounts: bool,
+    description_optional: bool,
+    description_placeholder: String,
+    description_x_tol: i32,
+    ditto_tokens: Vec<String>,
+    description_multiline: bool,
+    description_continuation_max_lines: usize,
+    /// Wrapped lines appended to the description of the last appended transaction, if
+    /// more may follow (None once another line is read)
+    description_continuation_lines: Option<usize>,
+    in_description_continuation: bool,
+    last_field: &'static str,
+    current_page: i32,
+    missing_year_reported: bool,
+    record_sources: bool,
+    /// Text items within the transaction table not read by any field (see
+    /// orphan_lines)
+    orphan_items: Vec<TextItem>,
 }
 
 impl TransactionParser {
@@ -42,7 +74,10 @@ impl TransactionParser {
         let next_fields = transaction::utils::get_next_fields(transaction_formats.clone());
         let compulsory_fields =
             transaction::utils::get_compulsory_fields(transaction_formats.clone());
+        let has_optional_fields = transaction::utils::has_optional_fields(&transaction_formats);
         let all_fields = transaction::utils::get_all_fields(transaction_formats);
+        let block_parser = (config.transaction_layout == "labelled_blocks")
+            .then(|| TransactionBlockParser::new(config, &all_fields));
         let start_terms: Vec<&str> = config
             .transaction_terms
             .iter()
@@ -63,10 +98,15 @@ impl TransactionParser {
             amount_parser_newline: TransactionAmountParser::new(config),
             balance_parser: TransactionBalanceParser::new(config),
             balance_parser_newline: TransactionBalanceParser::new(config),
-            start_primer: ParserPrimer::new(&start_terms),
-            stop_primer: ParserPrimer::new(&stop_terms),
+            block_parser,
+            start_primer: ParserPrimer::new(&start_terms)
+                .with_case_insensitive(config.terms_case_insensitive),
+            stop_primer: ParserPrimer::new(&stop_terms)
+                .with_case_insensitive(config.terms_case_insensitive),
+            stop_page_scoped: config.transaction_terms_stop_scope == "page",
             current_transaction: ProtoTransaction::new(),
             compulsory_fields,
+            has_optional_fields,
             all_fields,
             new_line_fields,
             end_line_fields,
@@ -75,71 +115,170 @@ impl TransactionParser {
             new_line_tol: config.transaction_new_line_tol,
             description_x_bounds_adjusted: false,
             description_exclude_patterns: config.transaction_description_exclude.clone(),
+            description_allows_dates: config.transaction_description_allows_dates,
+            description_allows_amounts: config.transaction_description_allows_amounts,
+            description_optional: config.transaction_description_optional,
+            description_placeholder: config.transaction_description_placeholder.clone(),
+            description_x_tol: config.get_transaction_alignment_tol("description"),
+            ditto_tokens: config.ditto_tokens.clone(),
+            description_multiline: config.transaction_description_multiline,
+            description_continuation_max_lines: config
+                .transaction_description_continuation_max_lines
+                .max(0) as usize,
+            description_continuation_lines: None,
+            in_description_continuation: false,
+            last_field: "",
+            current_page: 0,
+            missing_year_reported: false,
+            record_sources: false,
+            orphan_items: Vec::new(),
         }
     }
 
+    /// Record the text items read into the fields of each transaction (see
+    /// ProtoTransaction::source_items). Off by default to avoid copying the items.
+    pub fn with_source_items(mut self, record_sources: bool) -> Self {
+        self.record_sources = record_sources;
+        self
+    }
+
     pub fn parse_items(&mut self, items: &[TextItem], data: &mut StatementData) -> usize {
+        let consumed = self.parse_transaction_items(items, data);
+        // Record the page and position at which the current transaction starts
+        if consumed > 0
+            && self.current_transaction.page.is_none()
+            && self.current_transaction.has_any_field_set()
+        {
+            self.current_transaction.page = Some(items[0].page);
+            self.current_transaction.y1 = Some(items[0].y1);
+        }
+        // Start and stop terms are not consumed, but are outside the table
+        if consumed == 0 && self.in_table() {
+            self.orphan_items.push(items[0].clone());
+        }
+        consumed
+    }
+
+    fn parse_transaction_items(&mut self, items: &[TextItem], data: &mut StatementData) -> usize {
+        // A page-scoped stop term no longer applies once the next page starts
+        if self.stop_primer.primed && !self.is_stopped_on_page(items[0].page) {
+            self.stop_primer.reset();
+        }
+
         // Handle/check for start/stop primers - these are not consumed
         let start_consumed = self.start_primer.parse_items(items);
         if start_consumed > 0 {
             if self.start_date_required && data.start_date().is_none() {
-                panic!(
+                data.add_error(
                     "Statement config requires a start date is set prior to parsing transactions."
+                        .to_string(),
                 );
             }
             self.date_parser.set_start_date_year(data);
             self.date_parser_newline.set_start_date_year(data);
         }
 
-        self.stop_primer.parse_items(items);
+        let stop_consumed = self.stop_primer.parse_items(items);
+        if stop_consumed > 0 && self.block_parser.is_some() {
+            self.end_block(data);
+        }
         if !self.start_primer.primed || self.stop_primer.primed {
             return 0;
         }
+        if self.block_parser.is_some() {
+            self.current_page = items[0].page;
+            return self.parse_block_items(items, data);
+        }
 
         // Adjust description parser x_bounds if needed
         self.adjust_description_x_bounds();
+        self.current_page = items[0].page;
 
         // Handle new line, if one
         let is_new_line = self.is_new_line(items);
         self.current_line_y1 = items[0].y1;
         if is_new_line {
+            self.in_description_continuation = false;
             let consumed = self.handle_new_line(items, data);
             if consumed > 0 {
+                self.description_continuation_lines = None;
                 return consumed;
             }
+            if self.is_description_continuation(items) {
+                self.in_description_continuation = true;
+                self.description_continuation_lines =
+                    self.description_continuation_lines.map(|lines| lines + 1);
+            } else {
+                self.description_continuation_lines = None;
+            }
         }
 
-        // Try parsing date
-        let date_consumed = self
-            .date_parser
-            .parse_items(items, &mut self.current_transaction);
-        if date_consumed > 0 {
-            self.date_parser.reset();
-            self.post_parse_append("date".to_string(), data);
-            self.post_parse_prime("date".to_string());
-            return date_consumed;
+        // Wrapped description text is appended to the transaction above
+        if self.in_description_continuation {
+            if self.in_description_column(&items[0])
+                && let Some(previous) = data.proto_transactions.last_mut()
+            {
+                append_description_text(&mut previous.description, &items[0].text, is_new_line);
+                if self.record_sources {
+                    previous.source_items.push(items[0].clone());
+                }
+                self.last_field = "description";
+                return 1;
+            }
+            self.in_description_continuation = false;
         }
 
-        // Try parsing amount
-        let amount_consumed = self
-            .amount_parser
-            .parse_items(items, &mut self.current_transaction);
-        if amount_consumed > 0 {
-            self.amount_parser.reset();
-            self.post_parse_append("amount".to_string(), data);
-            self.post_parse_prime("amount".to_string());
-            return amount_consumed;
+        // Ditto marks copy the date or description of the previous transaction
+        let ditto_consumed = self.parse_ditto(items, data);
+        if ditto_consumed > 0 {
+            return ditto_consumed;
         }
 
-        // Try parsing balance
-        let balance_consumed = self
-            .balance_parser
-            .parse_items(items, &mut self.current_transaction);
-        if balance_consumed > 0 {
-            self.balance_parser.reset();
-            self.post_parse_append("balance".to_string(), data);
-            self.post_parse_prime("balance".to_string());
-            return balance_consumed;
+        // Description text may be allowed to contain date or amount-like values
+        let in_description = self.is_in_description_column(items);
+
+        // Try parsing date
+        if !(in_description && self.description_allows_dates) {
+            let date_consumed = self
+                .date_parser
+                .parse_items(items, &mut self.current_transaction);
+            self.report_missing_year(data);
+            if date_consumed > 0 {
+                self.date_parser.reset();
+                self.record_source_items(&items[..date_consumed]);
+                self.post_parse_append("date".to_string(), data);
+                self.post_parse_prime("date".to_string());
+                self.last_field = "date";
+                return date_consumed;
+            }
+        }
+
+        if !(in_description && self.description_allows_amounts) {
+            // Try parsing amount
+            let amount_consumed = self
+                .amount_parser
+                .parse_items(items, &mut self.current_transaction);
+            if amount_consumed > 0 {
+                self.amount_parser.reset();
+                self.record_source_items(&items[..amount_consumed]);
+                self.post_parse_append("amount".to_string(), data);
+                self.post_parse_prime("amount".to_string());
+                self.last_field = "amount";
+                return amount_consumed;
+            }
+
+            // Try parsing balance
+            let balance_consumed = self
+                .balance_parser
+                .parse_items(items, &mut self.current_transaction);
+            if balance_consumed > 0 {
+                self.balance_parser.reset();
+                self.record_source_items(&items[..balance_consumed]);
+                self.post_parse_append("balance".to_string(), data);
+                self.post_parse_prime("balance".to_string());
+                self.last_field = "balance";
+                return balance_consumed;
+            }
         }
 
         // Try parsing description
@@ -147,11 +286,141 @@ impl TransactionParser {
             .description_parser
             .parse_items(items, &mut self.current_transaction);
         if description_consumed > 0 {
+            self.record_source_items(&items[..description_consumed]);
+            self.last_field = "description";
             return description_consumed;
         }
         0
     }
 
+    /// Check if a stop term found so far applies to items on the page
+    fn is_stopped_on_page(&self, page: i32) -> bool {
+        if !self.stop_primer.primed {
+            return false;
+        }
+        !self.stop_page_scoped || self.stop_page() == Some(page)
+    }
+
+    /// Get the page on which the transaction table ended, if a stop term was found
+    pub fn stop_page(&self) -> Option<i32> {
+        if !self.stop_primer.primed {
+            return None;
+        }
+        self.stop_primer.text_item.as_ref().map(|item| item.page)
+    }
+
+    /// Check if the parser is within the transaction table, i.e., after the start term
+    /// and column headers, and before any stop term
+    pub fn in_table(&self) -> bool {
+        self.start_primer.primed
+            && !self.stop_primer.primed
+            && (self.block_parser.is_some() || self.table_headers_read())
+    }
+
+    /// Get the field ("date", "description", "amount" or "balance") read by
+    /// the last successful call to parse_items
+    pub fn last_field(&self) -> &'static str {
+        self.last_field
+    }
+
+    /// Check if the items would be read as a transaction date, amount or
+    /// balance, without changing the parser state. Returns the field and the
+    /// number of items it would consume.
+    pub fn peek_value_field(
+        &self,
+        items: &[TextItem],
+        data: &StatementData,
+    ) -> Option<(&'static str, usize)> {
+        if items.is_empty() || !self.start_primer.primed || self.is_stopped_on_page(items[0].page) {
+            return None;
+        }
+        // Re-priming the start primer may require a start date
+        let mut start_primer = self.start_primer.clone();
+        if start_primer.parse_items(items) > 0
+            && self.start_date_required
+            && data.start_date().is_none()
+        {
+            return None;
+        }
+        // Parse into scratch data with only what the transaction parser reads, rather
+        // than a copy of every transaction read so far
+        let mut scratch = StatementData {
+            start_date: data.start_date,
+            start_date_year: data.start_date_year,
+            date_timezone: data.date_timezone,
+            proto_transactions: data
+                .proto_transactions
+                .last()
+                .cloned()
+                .into_iter()
+                .collect(),
+            ..StatementData::new()
+        };
+        let mut parser = self.clone();
+        let consumed = parser.parse_items(items, &mut scratch);
+        match parser.last_field {
+            "date" | "amount" | "balance" if consumed > 0 => Some((parser.last_field, consumed)),
+            _ => None,
+        }
+    }
+
+    /// Get the effective x bounds of each transaction field whose column header has
+    /// been read (or whose column position is configured)
+    pub fn column_bounds(&self) -> BTreeMap<String, (i32, i32)> {
+        let mut bounds = BTreeMap::new();
+        for field in &self.all_fields {
+            let field_bounds = match field.as_str() {
+                "date" if self.date_parser.is_header_set() => self.date_parser.get_x_bounds(),
+                "date" if self.date_parser_newline.is_header_set() => {
+                    self.date_parser_newline.get_x_bounds()
+                }
+                "description" if self.description_parser.is_header_set() => {
+                    self.description_parser.get_x_bounds()
+                }
+                "amount" if self.amount_parser.is_header_set() => self.amount_parser.get_x_bounds(),
+                "amount" if self.amount_parser_newline.is_header_set() => {
+                    self.amount_parser_newline.get_x_bounds()
+                }
+                "balance" if self.balance_parser.is_header_set() => {
+                    self.balance_parser.get_x_bounds()
+                }
+                "balance" if self.balance_parser_newline.is_header_set() => {
+                    self.balance_parser_newline.get_x_bounds()
+                }
+                _ => continue,
+            };
+            bounds.insert(field.clone(), field_bounds);
+        }
+        bounds
+    }
+
+    /// Get the lines of the transaction table whose text items were not read by any
+    /// field, as only the transaction parser is left to read them
+    pub fn orphan_lines(&self) -> Vec<OrphanLine> {
+        get_orphan_lines(&self.orphan_items)
+    }
+
+    /// Record the transaction still being read at the end of the statement as dropped,
+    /// if it has any field set (it is never appended as no new line follows it). The
+    /// transaction of the last labelled block, or of a last row omitting optional
+    /// fields, is added instead, if complete.
+    pub fn record_unfinished_row(&mut self, data: &mut StatementData) {
+        // The last block has no following label or blank line to end it
+        if self.block_parser.is_some() {
+            self.end_block(data);
+            return;
+        }
+        if self.is_row_complete() {
+            self.append_current_transaction(data);
+            self.current_transaction = ProtoTransaction::new();
+            return;
+        }
+        if self.current_transaction.has_any_field_set() {
+            let row = self.describe_dropped_row(data);
+            data.dropped_rows.push(row);
+        }
+    }
+
     /// Get the maximum lookahead for the parser
     pub fn get_max_lookahead(&self) -> usize {
         let mut max_lookahead = 0;
@@ -161,6 +430,9 @@ impl TransactionParser {
         max_lookahead = max_lookahead.max(self.amount_parser.get_max_lookahead());
         max_lookahead = max_lookahead.max(self.balance_parser.get_max_lookahead());
         max_lookahead = max_lookahead.max(self.description_parser.get_max_lookahead());
+        if let Some(block_parser) = &self.block_parser {
+            max_lookahead = max_lookahead.max(block_parser.get_max_lookahead());
+        }
         max_lookahead
     }
 
@@ -178,6 +450,22 @@ impl TransactionParser {
         true
     }
 
+    /// Check if the column header of every field has been read, by either the same
+    /// line or the new line parser of the field
+    fn table_headers_read(&self) -> bool {
+        self.all_fields.iter().all(|field| match field.as_str() {
+            "date" => self.date_parser.is_header_set() || self.date_parser_newline.is_header_set(),
+            "description" => self.description_parser.is_header_set(),
+            "amount" => {
+                self.amount_parser.is_header_set() || self.amount_parser_newline.is_header_set()
+            }
+            "balance" => {
+                self.balance_parser.is_header_set() || self.balance_parser_newline.is_header_set()
+            }
+            _ => true,
+        })
+    }
+
     /// Get effective x_bounds for a specified parser
     fn get_parser_x_bounds(&self, field: &str) -> (i32, i32) {
         match field {
@@ -188,6 +476,73 @@ impl TransactionParser {
         }
     }
 
+    /// Get left edge of the header for a specified parser, if read
+    fn get_parser_header_x1(&self, field: &str) -> Option<i32> {
+        match field {
+            "date" => self.date_parser.get_header_x1(),
+            "amount" => self.amount_parser.get_header_x1(),
+            "balance" => self.balance_parser.get_header_x1(),
+            _ => None,
+        }
+    }
+
+    /// Get the x position where the description column ends, i.e. the left
+    /// edge of the nearest column header to the right of the description header
+    fn get_description_column_end(&self, description_x1: i32) -> Option<i32> {
+        self.all_fields
+            .iter()
+            .filter(|field| *field != "description")
+            .filter_map(|field| self.get_parser_header_x1(field))
+            .filter(|x1| *x1 > description_x1)
+            .min()
+    }
+
+    /// Check if the first item is within the description column of a primed
+    /// description. Requires the description header and a header to its right.
+    fn is_in_description_column(&self, items: &[TextItem]) -> bool {
+        if items.is_empty() || !self.description_parser.primed {
+            return false;
+        }
+        self.in_description_column(&items[0])
+    }
+
+    /// Check if the item is within the description column, from the description header
+    /// to the nearest header to its right
+    fn in_description_column(&self, item: &TextItem) -> bool {
+        let Some(description_x1) = self.description_parser.get_header_x1() else {
+            return false;
+        };
+        let Some(column_end) = self.get_description_column_end(description_x1) else {
+            return false;
+        };
+        item.x1 >= description_x1 - self.description_x_tol && item.x1 < column_end
+    }
+
+    /// Check if a new line that no field parser reads is a wrapped line of the
+    /// description of the transaction above: multi-line descriptions are enabled, no
+    /// transaction is being read, fewer than the maximum wrapped lines have been
+    /// appended, and every item of the line (within the lookahead) is in the
+    /// description column
+    fn is_description_continuation(&self, items: &[TextItem]) -> bool {
+        if !self.description_multiline
+            || self.description_parser.primed
+            || self.current_transaction.has_any_field_set()
+        {
+            return false;
+        }
+        let Some(lines) = self.description_continuation_lines else {
+            return false;
+        };
+        if lines >= self.description_continuation_max_lines {
+            return false;
+        }
+        let (page, y1) = (items[0].page, items[0].y1);
+        items
+            .iter()
+            .take_while(|item| item.page == page && (item.y1 - y1).abs() <= self.new_line_tol)
+            .all(|item| self.in_description_column(item))
+    }
+
     /// Readjust description parser x_bounds if not already done and
     /// headers are all set
     fn adjust_description_x_bounds(&mut self) {
@@ -251,18 +606,109 @@ impl TransactionParser {
         }
     }
 
-    /// Append current transaction to statement data if all compulsory fields are set
+    /// Append current transaction to statement data if all compulsory fields are set.
+    /// Empty descriptions are given the placeholder if descriptions are optional.
+    /// Descriptions reduced to empty by the exclude patterns are recorded, as are
+    /// transactions dropped with only some compulsory fields set.
     fn append_current_transaction(&mut self, data: &mut StatementData) {
+        self.current_transaction.description_optional = self.description_optional;
         if !self
             .current_transaction
             .has_required_fields_set(&self.compulsory_fields)
         {
+            if self.current_transaction.has_any_field_set() {
+                let row = self.describe_dropped_row(data);
+                data.dropped_rows.push(row);
+            }
             return;
         }
+        let original = self.current_transaction.description.trim().to_string();
         self.current_transaction
             .clean_description(&self.description_exclude_patterns);
+        if !original.is_empty() && self.current_transaction.description.is_empty() {
+            data.excluded_descriptions.push(original);
+        }
+        if self.description_optional && self.current_transaction.description.is_empty() {
+            self.current_transaction.description = self.description_placeholder.clone();
+        }
+        if self.current_transaction.page.is_none() {
+            self.current_transaction.page = Some(self.current_page);
+        }
         data.proto_transactions
             .push(self.current_transaction.clone());
+        self.description_continuation_lines = Some(0);
+    }
+
+    /// Describe the current transaction as a dropped row: its page, the fields read
+    /// and the compulsory fields missing
+    fn describe_dropped_row(&self, data: &StatementData) -> String {
+        let tx = &self.current_transaction;
+        let mut read = Vec::new();
+        if let Some(date) = tx.date.and_then(|ms| data.to_naive_date(ms)) {
+            read.push(format!("date {}", date.format("%d %b %Y")));
+        }
+        if !tx.description.trim().is_empty() {
+            read.push(format!("description \"{}\"", tx.description.trim()));
+        }
+        if let Some(amount) = tx.amount {
+            read.push(format!("amount {:.2}", amount));
+        }
+        if let Some(balance) = tx.balance {
+            read.push(format!("balance {:.2}", balance));
+        }
+        let missing: Vec<&str> = self
+            .compulsory_fields
+            .iter()
+            .filter(|field| !tx.has_required_fields_set(std::slice::from_ref(*field)))
+            .map(|field| field.as_str())
+            .collect();
+        format!(
+            "Page {}: {} (missing {})",
+            tx.page.unwrap_or(self.current_page),
+            read.join(", "),
+            missing.join(", ")
+        )
+    }
+
+    /// Parse the labels and values of a transaction printed as a block of labelled
+    /// lines, adding the transaction of the previous block once a new block starts
+    fn parse_block_items(&mut self, items: &[TextItem], data: &mut StatementData) -> usize {
+        if self
+            .block_parser
+            .as_ref()
+            .is_some_and(|parser| parser.starts_new_block(items))
+        {
+            self.end_block(data);
+        }
+        let Some(block_parser) = self.block_parser.as_mut() else {
+            return 0;
+        };
+        let (consumed, field) = block_parser.parse_items(items, &mut self.current_transaction);
+        if consumed > 0 {
+            self.record_source_items(&items[..consumed]);
+            self.last_field = field;
+        }
+        consumed
+    }
+
+    /// Add the transaction of the current block, or record it as a dropped row if
+    /// incomplete, and start a new block
+    fn end_block(&mut self, data: &mut StatementData) {
+        self.append_current_transaction(data);
+        self.current_transaction = ProtoTransaction::new();
+        if let Some(block_parser) = self.block_parser.as_mut() {
+            block_parser.reset();
+        }
+    }
+
+    /// Add text items read into a field of the current transaction to its source items,
+    /// if recorded. Column headers, read before any field is set, are not recorded.
+    fn record_source_items(&mut self, items: &[TextItem]) {
+        if self.record_sources && self.current_transaction.has_any_field_set() {
+            self.current_transaction
+                .source_items
+                .extend_from_slice(items);
+        }
     }
 
     /// Handle post-parse actions after a field is successfully parsed
@@ -289,8 +735,92 @@ impl TransactionParser {
         }
     }
 
+    /// Record an error for the first transaction date without a year, which cannot be
+    /// read as the start date year is not set (i.e., before the start date is read)
+    fn report_missing_year(&mut self, data: &mut StatementData) {
+        let missing_year = [
+            self.date_parser.take_missing_year(),
+            self.date_parser_newline.take_missing_year(),
+        ];
+        if self.missing_year_reported {
+            return;
+        }
+        if let Some(item) = missing_year.into_iter().flatten().next() {
+            data.add_error(format!(
+                "Invalid transaction date '{}' on page {}. {}: set \
+                 transaction_start_date_required so that the year is taken from the start date",
+                item.text,
+                item.page,
+                DateParseError::MissingYear
+            ));
+            self.missing_year_reported = true;
+        }
+    }
+
+    /// Check if the first item is a ditto mark
+    fn is_ditto(&self, items: &[TextItem]) -> bool {
+        !items.is_empty() && self.ditto_tokens.contains(&items[0].text)
+    }
+
+    /// Get the date of the previous transaction, copied by a ditto mark
+    fn previous_date(data: &StatementData) -> Option<i64> {
+        data.proto_transactions.last().and_then(|tx| tx.date)
+    }
+
+    /// Read a ditto mark in the date or description column of a primed field as the
+    /// field of the previous transaction. The mark is consumed even without a previous
+    /// transaction, so that it never ends up in a description.
+    fn parse_ditto(&mut self, items: &[TextItem], data: &mut StatementData) -> usize {
+        if !self.is_ditto(items) {
+            return 0;
+        }
+        if self.date_parser.primed
+            && self.date_parser.is_header_set()
+            && self.date_parser.in_column(&items[0])
+        {
+            self.current_transaction.date = Self::previous_date(data);
+            self.record_source_items(&items[..1]);
+            self.post_parse_append("date".to_string(), data);
+            self.post_parse_prime("date".to_string());
+            self.last_field = "date";
+            return 1;
+        }
+        if self.description_parser.primed
+            && self.description_parser.is_header_set()
+            && self.description_parser.in_column(&items[0])
+        {
+            if let Some(previous) = data.proto_transactions.last() {
+                append_description_text(
+                    &mut self.current_transaction.description,
+                    previous.description.trim(),
+                    false,
+                );
+            }
+            self.record_source_items(&items[..1]);
+            self.last_field = "description";
+            return 1;
+        }
+        0
+    }
+
+    /// Check if the current transaction is a row omitting optional fields, with all
+    /// compulsory fields set, which ends at the next line rather than at an end line field
+    fn is_row_complete(&self) -> bool {
+        self.has_optional_fields
+            && self.current_transaction.has_any_field_set()
+            && self
+                .current_transaction
+                .has_required_fields_set(&self.compulsory_fields)
+    }
+
     /// Handle new line parsing for specified fields
     fn handle_new_line(&mut self, items: &[TextItem], data: &mut StatementData) -> usize {
+        // A row omitting optional fields ends with its line
+        if self.is_row_complete() {
+            self.append_current_transaction(data);
+            self.current_transaction = ProtoTransaction::new();
+            self.prime_new_line_fields();
+        }
         if !self.description_parser.primed {
             self.prime_new_line_fields();
         }
@@ -300,15 +830,32 @@ impl TransactionParser {
             self.date_parser.reset();
             self.date_parser_newline.reset();
             self.date_parser_newline.prime();
+            // A ditto mark starts a transaction on the date of the previous one
+            if self.is_ditto(items)
+                && self.date_parser_newline.is_header_set()
+                && self.date_parser_newline.in_column(&items[0])
+            {
+                self.append_current_transaction(data);
+                self.current_transaction = ProtoTransaction::new();
+                self.current_transaction.date = Self::previous_date(data);
+                self.record_source_items(&items[..1]);
+                self.description_parser.reset();
+                self.post_parse_prime("date".to_string());
+                self.last_field = "date";
+                return 1;
+            }
             let mut next_transaction: ProtoTransaction = ProtoTransaction::new();
             let date_consumed = self
                 .date_parser_newline
                 .parse_items(items, &mut next_transaction);
+            self.report_missing_year(data);
             if date_consumed > 0 {
                 self.append_current_transaction(data);
                 self.current_transaction = next_transaction;
+                self.record_source_items(&items[..date_consumed]);
                 self.description_parser.reset();
                 self.post_parse_prime("date".to_string());
+                self.last_field = "date";
                 return date_consumed;
             }
         }
@@ -325,8 +872,10 @@ impl TransactionParser {
             if amount_consumed > 0 {
                 self.append_current_transaction(data);
                 self.current_transaction = next_transaction;
+                self.record_source_items(&items[..amount_consumed]);
                 self.description_parser.reset();
                 self.post_parse_prime("amount".to_string());
+                self.last_field = "amount";
                 return amount_consumed;
             }
         }
@@ -343,8 +892,10 @@ impl TransactionParser {
             if balance_consumed > 0 {
                 self.append_current_transaction(data);
                 self.current_transaction = next_transaction;
+                self.record_source_items(&items[..balance_consumed]);
                 self.description_parser.reset();
                 self.post_parse_prime("balance".to_string());
+                self.last_field = "balance";
                 return balance_consumed;
             }
         }
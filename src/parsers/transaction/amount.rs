@@ -1,6 +1,8 @@
 use crate::parsers::base::{AmountParser, ParserPrimer};
+use crate::parsers::transaction::utils::overlaps_column;
 use crate::structs::{ProtoTransaction, StatementConfig, TextItem};
 
+#[derive(Clone)]
 pub struct TransactionAmountParser {
     pub primed: bool,
     amount_parser: AmountParser,
@@ -13,7 +15,15 @@ pub struct TransactionAmountParser {
     invert_x1_range: Vec<i32>,
     invert_x2_range: Vec<i32>,
     has_inverted_column: bool,
+    debit_header_primer: ParserPrimer,
+    credit_header_primer: ParserPrimer,
+    /// Ranges of the aligned edge of amounts in the debit and credit columns, once
+    /// their headers are read
+    debit_range: Option<(i32, i32)>,
+    credit_range: Option<(i32, i32)>,
+    has_debit_credit_columns: bool,
     x_tol: i32,
+    column: Option<(i32, i32)>,
     invert: bool,
 }
 
@@ -29,6 +39,16 @@ impl TransactionAmountParser {
             .iter()
             .map(|s| s.as_str())
             .collect();
+        let debit_primer_terms: Vec<&str> = config
+            .transaction_debit_headers
+            .iter()
+            .map(|s| s.as_str())
+            .collect();
+        let credit_primer_terms: Vec<&str> = config
+            .transaction_credit_headers
+            .iter()
+            .map(|s| s.as_str())
+            .collect();
         let amount_formats: Vec<&str> = config
             .transaction_amount_formats
             .iter()
@@ -36,12 +56,14 @@ impl TransactionAmountParser {
             .collect();
         let alignment = config.transaction_amount_alignment.clone();
         let invert_alignment = config.transaction_amount_invert_alignment.clone();
-        let x_tol = config.transaction_alignment_tol;
+        let x_tol = config.get_transaction_alignment_tol("amount");
         Self {
             primed: false,
-            amount_parser: AmountParser::new(amount_formats.as_slice()),
-            header_primer: ParserPrimer::new(primer_terms.as_slice()),
-            invert_header_primer: ParserPrimer::new(invert_primer_terms.as_slice()),
+            amount_parser: AmountParser::new(amount_formats.as_slice(), &config.amount_hints()),
+            header_primer: ParserPrimer::new(primer_terms.as_slice())
+                .with_case_insensitive(config.terms_case_insensitive),
+            invert_header_primer: ParserPrimer::new(invert_primer_terms.as_slice())
+                .with_case_insensitive(config.terms_case_insensitive),
             alignment,
             invert_alignment,
             x_tol,
@@ -50,6 +72,14 @@ impl TransactionAmountParser {
             invert_x1_range: vec![0, 10000],
             invert_x2_range: vec![0, 10000],
             has_inverted_column: !invert_primer_terms.is_empty(),
+            debit_header_primer: ParserPrimer::new(debit_primer_terms.as_slice())
+                .with_case_insensitive(config.terms_case_insensitive),
+            credit_header_primer: ParserPrimer::new(credit_primer_terms.as_slice())
+                .with_case_insensitive(config.terms_case_insensitive),
+            debit_range: None,
+            credit_range: None,
+            has_debit_credit_columns: !debit_primer_terms.is_empty(),
+            column: config.get_transaction_column_position("amount"),
             invert: config.transaction_amount_invert,
         }
     }
@@ -67,14 +97,23 @@ impl TransactionAmountParser {
             return invert_header_consumed;
         }
 
+        // Try reading and setting bounds from debit or credit header
+        let debit_credit_header_consumed = self.try_parse_debit_credit_header(items);
+        if debit_credit_header_consumed > 0 {
+            return debit_credit_header_consumed;
+        }
+
         // Parser must be primed before parsing amounts
         if !self.primed {
             return 0;
         }
 
-        // Try parsing amount
-        let amount_consumed = self.try_parse_amount(items);
+        // Try parsing amount, with any CR/DR indicator in the next item
+        let mut amount_consumed = self.try_parse_amount(items);
         if amount_consumed > 0 {
+            amount_consumed +=
+                self.amount_parser
+                    .parse_sign_indicator(items, amount_consumed, self.x_tol);
             let mut value = self.amount_parser.value.unwrap();
             if self.invert {
                 value = -value;
@@ -100,23 +139,51 @@ impl TransactionAmountParser {
     pub fn get_max_lookahead(&self) -> usize {
         let mut max_lookahead = 0;
         max_lookahead = max_lookahead.max(self.header_primer.max_lookahead);
-        max_lookahead = max_lookahead.max(self.amount_parser.max_lookahead);
+        max_lookahead = max_lookahead.max(self.debit_header_primer.max_lookahead);
+        max_lookahead = max_lookahead.max(self.credit_header_primer.max_lookahead);
+        // Allow for a CR/DR indicator after the amount
+        max_lookahead = max_lookahead.max(self.amount_parser.max_lookahead + 1);
         max_lookahead
     }
 
-    /// Check if header is set
+    /// Check if header is set (always set if the column position is configured). With
+    /// debit and credit columns, both of their headers must be read instead.
     pub fn is_header_set(&self) -> bool {
+        if self.has_debit_credit_columns {
+            return self.debit_range.is_some() && self.credit_range.is_some();
+        }
+        let header_set = self.column.is_some() || self.header_primer.primed;
         if self.has_inverted_column {
-            return self.header_primer.primed && self.invert_header_primer.primed;
+            return header_set && self.invert_header_primer.primed;
         }
-        self.header_primer.primed
+        header_set
+    }
+
+    /// Get left edge of the leftmost column header (amount, invert, debit or credit),
+    /// if read, using the column position in place of the amount header if configured
+    pub fn get_header_x1(&self) -> Option<i32> {
+        let header_x1 = match self.column {
+            Some((x1, _)) => Some(x1),
+            None => self.header_primer.text_item.as_ref().map(|item| item.x1),
+        };
+        [
+            &self.invert_header_primer,
+            &self.debit_header_primer,
+            &self.credit_header_primer,
+        ]
+        .iter()
+        .filter_map(|primer| primer.text_item.as_ref().map(|item| item.x1))
+        .chain(header_x1)
+        .min()
     }
 
     /// Get effective x_bounds
     pub fn get_x_bounds(&self) -> (i32, i32) {
         let mut x_lower = 0;
         let mut x_upper = 10000;
-        if self.alignment == "x1" {
+        if let Some(column) = self.column {
+            (x_lower, x_upper) = column;
+        } else if self.alignment == "x1" {
             x_lower = self.x1_range[0];
             x_upper = self.x1_range[1];
         } else if self.alignment == "x2" {
@@ -132,13 +199,24 @@ impl TransactionAmountParser {
             x_lower = x_lower.min(inv_lower);
             x_upper = x_upper.max(inv_upper);
         }
+        if self.has_debit_credit_columns {
+            let ranges = [self.debit_range, self.credit_range];
+            let ranges = ranges.iter().flatten();
+            if self.column.is_none() && !self.header_primer.primed {
+                (x_lower, x_upper) = (10000, 0);
+            }
+            for &(lower, upper) in ranges {
+                x_lower = x_lower.min(lower);
+                x_upper = x_upper.max(upper);
+            }
+        }
         (x_lower, x_upper)
     }
 
     /// Try reading header and set x_ranges accordingly
     fn try_parse_header(&mut self, items: &[TextItem]) -> usize {
-        // Return if header already read
-        if self.header_primer.primed {
+        // Return if header already read or column position configured
+        if self.column.is_some() || self.header_primer.primed {
             return 0;
         }
         let header_consumed = self.header_primer.parse_items(items);
@@ -171,17 +249,86 @@ impl TransactionAmountParser {
         header_consumed
     }
 
-    /// Try parsing amount, invert if within invert bounds
+    /// Try reading a debit or credit header and set its range accordingly
+    fn try_parse_debit_credit_header(&mut self, items: &[TextItem]) -> usize {
+        if !self.has_debit_credit_columns {
+            return 0;
+        }
+        for (primer, range) in [
+            (&mut self.debit_header_primer, &mut self.debit_range),
+            (&mut self.credit_header_primer, &mut self.credit_range),
+        ] {
+            if range.is_some() {
+                continue;
+            }
+            let header_consumed = primer.parse_items(items);
+            if header_consumed > 0 {
+                let item = primer.text_item();
+                let edge = if self.alignment == "x2" {
+                    item.x2
+                } else {
+                    item.x1
+                };
+                *range = Some((edge - self.x_tol, edge + self.x_tol));
+                return header_consumed;
+            }
+        }
+        0
+    }
+
+    /// Whether the amount is in the debit column rather than the credit column, if in
+    /// either: the column whose header edge is nearest the aligned edge of the amount
+    fn debit_credit_column(&self, item: &TextItem) -> Option<bool> {
+        let edge = if self.alignment == "x2" {
+            item.x2
+        } else {
+            item.x1
+        };
+        let distance = |range: Option<(i32, i32)>| {
+            range
+                .filter(|(lower, upper)| edge >= *lower && edge <= *upper)
+                .map(|(lower, upper)| (edge - (lower + upper) / 2).abs())
+        };
+        match (distance(self.debit_range), distance(self.credit_range)) {
+            (Some(debit), Some(credit)) => Some(debit <= credit),
+            (Some(_), None) => Some(true),
+            (None, Some(_)) => Some(false),
+            (None, None) => None,
+        }
+    }
+
+    /// Try parsing amount, invert if within invert bounds, or sign it by its debit or
+    /// credit column
     fn try_parse_amount(&mut self, items: &[TextItem]) -> usize {
         let consumed = self.amount_parser.parse_items(items);
         if consumed == 0 {
             return 0; // No amount found
         }
         let item = self.amount_parser.text_item();
-        // Must be within x1 and x2 ranges or within invert ranges
-        let x1_ok = item.x1 >= self.x1_range[0] && item.x1 <= self.x1_range[1];
-        let x2_ok = item.x2 >= self.x2_range[0] && item.x2 <= self.x2_range[1];
-        if x1_ok && x2_ok {
+        if self.has_debit_credit_columns {
+            if let Some(debit) = self.debit_credit_column(item) {
+                let value = self.amount_parser.value.unwrap_or_default().abs();
+                self.amount_parser.value = Some(if debit { -value } else { value });
+                return consumed;
+            }
+            // Only read in the amount column if it has a header of its own
+            if self.column.is_none() && !self.header_primer.primed {
+                self.amount_parser.reset();
+                return 0;
+            }
+        }
+        // Must be within x1 and x2 ranges (or overlap the column position) or within
+        // invert ranges
+        let in_column = match self.column {
+            Some(column) => overlaps_column(item, column),
+            None => {
+                item.x1 >= self.x1_range[0]
+                    && item.x1 <= self.x1_range[1]
+                    && item.x2 >= self.x2_range[0]
+                    && item.x2 <= self.x2_range[1]
+            }
+        };
+        if in_column {
             return consumed;
         }
         // Check invert ranges if configured
@@ -198,3 +345,35 @@ impl TransactionAmountParser {
         0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_amount(config: &StatementConfig) -> Option<f64> {
+        // Amounts are right-aligned 40 points right of their header
+        let header = TextItem::new("Amount".to_string(), 780, 206, 816, 218, 0);
+        let amount = TextItem::new("2,500.00".to_string(), 808, 223, 856, 235, 0);
+        let mut parser = TransactionAmountParser::new(config);
+        let mut transaction = ProtoTransaction::new();
+        assert_eq!(parser.parse_items(&[header], &mut transaction), 1);
+        parser.prime();
+        parser.parse_items(&[amount], &mut transaction);
+        transaction.amount
+    }
+
+    #[test]
+    fn test_amount_alignment_tol_overrides_shared_tol() {
+        let mut config = StatementConfig {
+            transaction_amount_formats: vec!["format1".to_string()],
+            transaction_amount_headers: vec!["Amount".to_string()],
+            transaction_amount_alignment: "x2".to_string(),
+            transaction_alignment_tol: 10,
+            ..Default::default()
+        };
+        assert_eq!(parse_amount(&config), None);
+
+        config.transaction_amount_alignment_tol = Some(50);
+        assert_eq!(parse_amount(&config), Some(2500.0));
+    }
+}
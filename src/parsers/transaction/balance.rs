@@ -1,6 +1,8 @@
 use crate::parsers::base::{AmountParser, ParserPrimer};
+use crate::parsers::transaction::utils::overlaps_column;
 use crate::structs::{ProtoTransaction, StatementConfig, TextItem};
 
+#[derive(Clone)]
 pub struct TransactionBalanceParser {
     pub primed: bool,
     balance_parser: AmountParser,
@@ -9,6 +11,7 @@ pub struct TransactionBalanceParser {
     x1_range: Vec<i32>,
     x2_range: Vec<i32>,
     x_tol: i32,
+    column: Option<(i32, i32)>,
     invert: bool,
 }
 
@@ -25,16 +28,18 @@ impl TransactionBalanceParser {
             .map(|s| s.as_str())
             .collect();
         let alignment = config.transaction_balance_alignment.clone();
-        let x_tol = config.transaction_alignment_tol;
+        let x_tol = config.get_transaction_alignment_tol("balance");
         let invert = config.transaction_balance_invert;
         Self {
             primed: false,
-            balance_parser: AmountParser::new(balance_formats.as_slice()),
-            header_primer: ParserPrimer::new(primer_terms.as_slice()),
+            balance_parser: AmountParser::new(balance_formats.as_slice(), &config.amount_hints()),
+            header_primer: ParserPrimer::new(primer_terms.as_slice())
+                .with_case_insensitive(config.terms_case_insensitive),
             alignment,
             x_tol,
             x1_range: vec![0, 10000],
             x2_range: vec![0, 10000],
+            column: config.get_transaction_column_position("balance"),
             invert,
         }
     }
@@ -83,13 +88,24 @@ impl TransactionBalanceParser {
         max_lookahead
     }
 
-    /// Check if header is set
+    /// Get left edge of the column header, if read, or of the column position
+    pub fn get_header_x1(&self) -> Option<i32> {
+        if let Some((x1, _)) = self.column {
+            return Some(x1);
+        }
+        self.header_primer.text_item.as_ref().map(|item| item.x1)
+    }
+
+    /// Check if header is set (always set if the column position is configured)
     pub fn is_header_set(&self) -> bool {
-        self.header_primer.primed
+        self.column.is_some() || self.header_primer.primed
     }
 
     /// Get effective x_bounds
     pub fn get_x_bounds(&self) -> (i32, i32) {
+        if let Some(column) = self.column {
+            return column;
+        }
         let mut x_lower = 0;
         let mut x_upper = 10000;
         if self.alignment == "x1" {
@@ -104,8 +120,8 @@ impl TransactionBalanceParser {
 
     /// Try reading header and set x_ranges accordingly
     fn try_parse_header(&mut self, items: &[TextItem]) -> usize {
-        // Return if header already read
-        if self.header_primer.primed {
+        // Return if header already read or column position configured
+        if self.column.is_some() || self.header_primer.primed {
             return 0;
         }
         let header_consumed = self.header_primer.parse_items(items);
@@ -127,10 +143,17 @@ impl TransactionBalanceParser {
             return 0; // No balance found
         }
         let item = self.balance_parser.text_item();
-        // Check x1 and x2 ranges
-        let x1_ok = item.x1 >= self.x1_range[0] && item.x1 <= self.x1_range[1];
-        let x2_ok = item.x2 >= self.x2_range[0] && item.x2 <= self.x2_range[1];
-        if !x1_ok || !x2_ok {
+        // Check x1 and x2 ranges, or overlap with the column position
+        let in_column = match self.column {
+            Some(column) => overlaps_column(item, column),
+            None => {
+                item.x1 >= self.x1_range[0]
+                    && item.x1 <= self.x1_range[1]
+                    && item.x2 >= self.x2_range[0]
+                    && item.x2 <= self.x2_range[1]
+            }
+        };
+        if !in_column {
             // Reset balance parser state
             self.balance_parser.reset();
             return 0;
@@ -0,0 +1,248 @@
+use crate::parsers::base::ParserPrimer;
+use crate::parsers::primed::{PrimedAmountParser, PrimedDateParser, PrimedValueParser};
+use crate::parsers::transaction::description::append_description_text;
+use crate::structs::{ProtoTransaction, StatementConfig, TextItem};
+use regex::Regex;
+
+/// Primed parser of one labelled field of a block
+#[derive(Clone)]
+enum LabelledField {
+    Date(PrimedDateParser),
+    Description(PrimedValueParser),
+    Amount(PrimedAmountParser),
+    Balance(PrimedAmountParser),
+}
+
+impl LabelledField {
+    fn parse_items(&mut self, items: &[TextItem]) -> usize {
+        match self {
+            LabelledField::Date(parser) => parser.parse_items(items),
+            LabelledField::Description(parser) => parser.parse_items(items),
+            LabelledField::Amount(parser) | LabelledField::Balance(parser) => {
+                parser.parse_items(items)
+            }
+        }
+    }
+
+    fn is_set(&self) -> bool {
+        match self {
+            LabelledField::Date(parser) => parser.value().is_some(),
+            LabelledField::Description(parser) => parser.value().is_some(),
+            LabelledField::Amount(parser) | LabelledField::Balance(parser) => {
+                parser.value().is_some()
+            }
+        }
+    }
+
+    fn get_max_lookahead(&self) -> usize {
+        match self {
+            LabelledField::Date(parser) => parser.get_max_lookahead(),
+            LabelledField::Description(parser) => parser.get_max_lookahead(),
+            LabelledField::Amount(parser) | LabelledField::Balance(parser) => {
+                parser.get_max_lookahead()
+            }
+        }
+    }
+
+    /// Set the value read into the transaction field
+    fn set_value(&self, tx: &mut ProtoTransaction) {
+        match self {
+            LabelledField::Date(parser) => {
+                if let Some(date) = parser.value() {
+                    tx.set_date(date);
+                }
+            }
+            LabelledField::Description(parser) => {
+                if let Some(text) = parser.value() {
+                    tx.description = text.to_string();
+                }
+            }
+            LabelledField::Amount(parser) => {
+                if let Some(amount) = parser.value() {
+                    tx.set_amount(amount);
+                }
+            }
+            LabelledField::Balance(parser) => {
+                if let Some(balance) = parser.value() {
+                    tx.set_balance(balance);
+                }
+            }
+        }
+    }
+}
+
+/// Reads a transaction printed as a block of labelled lines (transaction_layout
+/// "labelled_blocks"), e.g., "Date: 03 Mar 2024" then "Amount: -4.50". Each field is
+/// read by a primed parser whose terms are the field headers, with the value on the
+/// line of its label. The parsers are replaced with fresh ones for each block.
+#[derive(Clone)]
+pub struct TransactionBlockParser {
+    /// Unused parsers of each field, copied at the start of each block
+    templates: Vec<(&'static str, LabelledField)>,
+    fields: Vec<(&'static str, LabelledField)>,
+    labels: Vec<ParserPrimer>,
+    new_line_tol: i32,
+    /// Last item read into the block
+    last_item: Option<TextItem>,
+    /// Last item of the description, whose line may hold more description words
+    description_item: Option<TextItem>,
+}
+
+impl TransactionBlockParser {
+    /// Create a parser of the given transaction fields (see get_all_fields)
+    pub fn new(config: &StatementConfig, all_fields: &[String]) -> Self {
+        let tol = config.transaction_new_line_tol;
+        let hints = config.amount_hints();
+        let case_insensitive = config.terms_case_insensitive;
+        let description_patterns = [Regex::new(r"\S").unwrap()];
+        let mut templates = Vec::new();
+        let mut labels = Vec::new();
+        for field in all_fields {
+            let headers = match field.as_str() {
+                "date" => &config.transaction_date_headers,
+                "description" => &config.transaction_description_headers,
+                "amount" => &config.transaction_amount_headers,
+                "balance" => &config.transaction_balance_headers,
+                _ => continue,
+            };
+            let terms = as_strs(headers);
+            let parser = match field.as_str() {
+                "date" => {
+                    let formats = as_strs(&config.transaction_date_formats);
+                    let parser = PrimedDateParser::new(&terms, &formats, "y1", tol);
+                    (
+                        "date",
+                        LabelledField::Date(
+                            parser
+                                .with_case_insensitive_terms(case_insensitive)
+                                .with_two_digit_year_pivot(config.two_digit_year_pivot),
+                        ),
+                    )
+                }
+                "description" => {
+                    let parser = PrimedValueParser::new(&terms, &description_patterns, "y1", tol);
+                    (
+                        "description",
+                        LabelledField::Description(
+                            parser.with_case_insensitive_terms(case_insensitive),
+                        ),
+                    )
+                }
+                "amount" => {
+                    let formats = as_strs(&config.transaction_amount_formats);
+                    let invert = config.transaction_amount_invert;
+                    let parser =
+                        PrimedAmountParser::new(&terms, &formats, &hints, "y1", tol, invert);
+                    (
+                        "amount",
+                        LabelledField::Amount(parser.with_case_insensitive_terms(case_insensitive)),
+                    )
+                }
+                _ => {
+                    let formats = as_strs(&config.transaction_balance_formats);
+                    let invert = config.transaction_balance_invert;
+                    let parser =
+                        PrimedAmountParser::new(&terms, &formats, &hints, "y1", tol, invert);
+                    (
+                        "balance",
+                        LabelledField::Balance(
+                            parser.with_case_insensitive_terms(case_insensitive),
+                        ),
+                    )
+                }
+            };
+            labels.push(ParserPrimer::new(&terms).with_case_insensitive(case_insensitive));
+            templates.push(parser);
+        }
+        Self {
+            fields: templates.clone(),
+            templates,
+            labels,
+            new_line_tol: tol,
+            last_item: None,
+            description_item: None,
+        }
+    }
+
+    /// Start a new block, discarding the values read
+    pub fn reset(&mut self) {
+        self.fields = self.templates.clone();
+        self.last_item = None;
+        self.description_item = None;
+    }
+
+    /// Check if the items start a new block: a label of a field already read in the
+    /// current block (e.g., the next "Date:"), or a line below a blank line
+    pub fn starts_new_block(&self, items: &[TextItem]) -> bool {
+        let Some(last) = &self.last_item else {
+            return false;
+        };
+        if let Some(index) = self.label_at(items)
+            && self.fields[index].1.is_set()
+        {
+            return true;
+        }
+        let height = (last.y1 - last.y2).abs();
+        items[0].page == last.page && (items[0].y1 - last.y1).abs() > 2 * height
+    }
+
+    /// Parse a label or a field value of the current block into the transaction.
+    /// Returns the number of items consumed and the field of the label or value.
+    pub fn parse_items(
+        &mut self,
+        items: &[TextItem],
+        tx: &mut ProtoTransaction,
+    ) -> (usize, &'static str) {
+        if items.is_empty() {
+            return (0, "");
+        }
+        // Labels only prime their own field, so that they are never read as a value
+        if let Some(index) = self.label_at(items) {
+            let (name, field) = &mut self.fields[index];
+            return (field.parse_items(items), name);
+        }
+        for (name, field) in self.fields.iter_mut() {
+            let was_set = field.is_set();
+            let consumed = field.parse_items(items);
+            if consumed > 0 && !was_set && field.is_set() {
+                field.set_value(tx);
+                let item = TextItem::from_items(&items[..consumed]);
+                if *name == "description" {
+                    self.description_item = item.clone();
+                }
+                self.last_item = item;
+                return (consumed, name);
+            }
+        }
+        // Further words on the line of the description
+        if let Some(description_item) = &self.description_item
+            && items[0].page == description_item.page
+            && (items[0].y1 - description_item.y1).abs() <= self.new_line_tol
+        {
+            append_description_text(&mut tx.description, &items[0].text, false);
+            self.description_item = Some(items[0].clone());
+            self.last_item = Some(items[0].clone());
+            return (1, "description");
+        }
+        (0, "")
+    }
+
+    pub fn get_max_lookahead(&self) -> usize {
+        self.templates
+            .iter()
+            .map(|(_, field)| field.get_max_lookahead())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Get the index of the field whose label starts the items
+    fn label_at(&self, items: &[TextItem]) -> Option<usize> {
+        self.labels
+            .iter()
+            .position(|label| label.clone().parse_items(items) > 0)
+    }
+}
+
+fn as_strs(values: &[String]) -> Vec<&str> {
+    values.iter().map(|s| s.as_str()).collect()
+}
@@ -1,6 +1,8 @@
 use crate::parsers::base::{DateParser, ParserPrimer};
+use crate::parsers::transaction::utils::overlaps_column;
 use crate::structs::{ProtoTransaction, StatementConfig, StatementData, TextItem};
 
+#[derive(Clone)]
 pub struct TransactionDateParser {
     pub primed: bool,
     date_parser: DateParser,
@@ -9,7 +11,9 @@ pub struct TransactionDateParser {
     x1_range: Vec<i32>,
     x2_range: Vec<i32>,
     x_tol: i32,
+    column: Option<(i32, i32)>,
     start_date_year_str: String,
+    missing_year: Option<TextItem>,
 }
 
 impl TransactionDateParser {
@@ -25,16 +29,23 @@ impl TransactionDateParser {
             .map(|s| s.as_str())
             .collect();
         let alignment = config.transaction_date_alignment.clone();
-        let x_tol = config.transaction_alignment_tol;
+        let x_tol = config.get_transaction_alignment_tol("date");
         Self {
             primed: false,
-            date_parser: DateParser::new(date_formats.as_slice()),
-            header_primer: ParserPrimer::new(primer_terms.as_slice()),
+            date_parser: DateParser::with_month_only_default_day(
+                date_formats.as_slice(),
+                &config.month_only_default_day,
+            )
+            .with_two_digit_year_pivot(config.two_digit_year_pivot),
+            header_primer: ParserPrimer::new(primer_terms.as_slice())
+                .with_case_insensitive(config.terms_case_insensitive),
             alignment,
             x_tol,
             x1_range: vec![0, 10000],
             x2_range: vec![0, 10000],
+            column: config.get_transaction_column_position("date"),
             start_date_year_str: "".to_string(),
+            missing_year: None,
         }
     }
 
@@ -88,13 +99,24 @@ impl TransactionDateParser {
         max_lookahead
     }
 
-    /// Check if header is set
+    /// Get left edge of the column header, if read, or of the column position
+    pub fn get_header_x1(&self) -> Option<i32> {
+        if let Some((x1, _)) = self.column {
+            return Some(x1);
+        }
+        self.header_primer.text_item.as_ref().map(|item| item.x1)
+    }
+
+    /// Check if header is set (always set if the column position is configured)
     pub fn is_header_set(&self) -> bool {
-        self.header_primer.primed
+        self.column.is_some() || self.header_primer.primed
     }
 
     /// Get effective x_bounds
     pub fn get_x_bounds(&self) -> (i32, i32) {
+        if let Some(column) = self.column {
+            return column;
+        }
         let mut x_lower = 0;
         let mut x_upper = 10000;
         if self.alignment == "x1" {
@@ -109,8 +131,8 @@ impl TransactionDateParser {
 
     /// Try reading header and set x_ranges accordingly
     fn try_parse_header(&mut self, items: &[TextItem]) -> usize {
-        // Return if header already read
-        if self.header_primer.primed {
+        // Return if header already read or column position configured
+        if self.column.is_some() || self.header_primer.primed {
             return 0;
         }
         let header_consumed = self.header_primer.parse_items(items);
@@ -125,23 +147,46 @@ impl TransactionDateParser {
         header_consumed
     }
 
+    /// Take the last date without a year found in the date column, which cannot be
+    /// read as the start date year is not set (e.g., "24 Mar")
+    pub fn take_missing_year(&mut self) -> Option<TextItem> {
+        self.missing_year.take()
+    }
+
+    /// Check if the item falls within the x_ranges, or overlaps the column position
+    pub fn in_column(&self, item: &TextItem) -> bool {
+        match self.column {
+            Some(column) => overlaps_column(item, column),
+            None => {
+                item.x1 >= self.x1_range[0]
+                    && item.x1 <= self.x1_range[1]
+                    && item.x2 >= self.x2_range[0]
+                    && item.x2 <= self.x2_range[1]
+            }
+        }
+    }
+
     /// Try parsing date and check if in x_ranges
     fn try_parse_date(&mut self, items: &[TextItem]) -> usize {
-        let consumed = self
+        let result = self
             .date_parser
             .parse_items(items, self.start_date_year_str.as_ref());
-        if consumed == 0 {
+        let consumed = result.clone().unwrap_or(0);
+        if consumed == 0 && result.is_ok() {
             return 0;
         }
-        // Check if date falls within x_ranges
+        // Check if date falls within x_ranges, or overlaps the column position
         let item = self.date_parser.text_item.as_ref().unwrap();
-        let x1_ok = item.x1 >= self.x1_range[0] && item.x1 <= self.x1_range[1];
-        let x2_ok = item.x2 >= self.x2_range[0] && item.x2 <= self.x2_range[1];
-        if !x1_ok || !x2_ok {
+        if !self.in_column(item) {
             // Reset date parser state
             self.date_parser.reset();
             return 0;
         }
+        if result.is_err() {
+            self.missing_year = Some(item.clone());
+            self.date_parser.reset();
+            return 0;
+        }
         consumed
     }
 }
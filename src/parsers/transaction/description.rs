@@ -1,6 +1,8 @@
 use crate::parsers::base::ParserPrimer;
+use crate::parsers::transaction::utils::overlaps_column;
 use crate::structs::{ProtoTransaction, StatementConfig, TextItem};
 
+#[derive(Clone)]
 pub struct TransactionDescriptionParser {
     pub primed: bool,
     header_primer: ParserPrimer,
@@ -8,6 +10,30 @@ pub struct TransactionDescriptionParser {
     x1_range: Vec<i32>,
     x2_range: Vec<i32>,
     x_tol: i32,
+    column: Option<(i32, i32)>,
+    new_line_tol: i32,
+    /// Page and y1 of the last description item read, to tell wrapped lines apart
+    last_item_line: Option<(i32, i32)>,
+}
+
+/// Append text to a description, separated by a space. A word wrapped onto the next
+/// line with a trailing hyphen (e.g., "CORPORA-" then "TION") is joined without the
+/// hyphen and space. Only merged when the text starts a new line, the hyphen follows a
+/// letter and the text starts with a letter, so standalone "-" separators and
+/// hyphenated words within a line (e.g., "ACME-" then "SYDNEY") are kept.
+pub(crate) fn append_description_text(description: &mut String, text: &str, new_line: bool) {
+    let wrapped = new_line
+        && description
+            .strip_suffix('-')
+            .and_then(|rest| rest.chars().last())
+            .is_some_and(|c| c.is_alphabetic())
+        && text.chars().next().is_some_and(|c| c.is_alphabetic());
+    if wrapped {
+        description.pop();
+    } else if !description.is_empty() {
+        description.push(' ');
+    }
+    description.push_str(text);
 }
 
 impl TransactionDescriptionParser {
@@ -18,14 +44,18 @@ impl TransactionDescriptionParser {
             .map(|s| s.as_str())
             .collect();
         let alignment = config.transaction_description_alignment.clone();
-        let x_tol = config.transaction_alignment_tol;
+        let x_tol = config.get_transaction_alignment_tol("description");
         Self {
             primed: false,
-            header_primer: ParserPrimer::new(primer_terms.as_slice()),
+            header_primer: ParserPrimer::new(primer_terms.as_slice())
+                .with_case_insensitive(config.terms_case_insensitive),
             alignment,
             x_tol,
             x1_range: vec![0, 10000],
             x2_range: vec![0, 10000],
+            column: config.get_transaction_column_position("description"),
+            new_line_tol: config.transaction_new_line_tol,
+            last_item_line: None,
         }
     }
 
@@ -45,12 +75,12 @@ impl TransactionDescriptionParser {
         let description_consumed = self.try_parse_description(items);
         if description_consumed > 0 {
             // Append text of first item to description
-            let mut description = transaction.description.clone();
-            if !description.is_empty() {
-                description.push(' ');
-            }
-            description.push_str(&items[0].text);
-            transaction.description = description;
+            let item = &items[0];
+            let new_line = self.last_item_line.is_none_or(|(page, y1)| {
+                page != item.page || (item.y1 - y1).abs() > self.new_line_tol
+            });
+            append_description_text(&mut transaction.description, &item.text, new_line);
+            self.last_item_line = Some((item.page, item.y1));
             return description_consumed;
         }
         0
@@ -59,6 +89,7 @@ impl TransactionDescriptionParser {
     /// Reset the parser state
     pub fn reset(&mut self) {
         self.primed = false;
+        self.last_item_line = None;
     }
 
     /// Set parser as primed
@@ -66,9 +97,17 @@ impl TransactionDescriptionParser {
         self.primed = true;
     }
 
-    /// Check if header is set
+    /// Get left edge of the column header, if read, or of the column position
+    pub fn get_header_x1(&self) -> Option<i32> {
+        if let Some((x1, _)) = self.column {
+            return Some(x1);
+        }
+        self.header_primer.text_item.as_ref().map(|item| item.x1)
+    }
+
+    /// Check if header is set (always set if the column position is configured)
     pub fn is_header_set(&self) -> bool {
-        self.header_primer.primed
+        self.column.is_some() || self.header_primer.primed
     }
 
     /// Get the maximum lookahead for the parser
@@ -78,8 +117,26 @@ impl TransactionDescriptionParser {
         max_lookahead
     }
 
+    /// Get effective x_bounds
+    pub fn get_x_bounds(&self) -> (i32, i32) {
+        if let Some(column) = self.column {
+            return column;
+        }
+        if self.alignment == "x1" {
+            (self.x1_range[0], self.x1_range[1])
+        } else if self.alignment == "x2" {
+            (self.x2_range[0], self.x2_range[1])
+        } else {
+            (0, 10000)
+        }
+    }
+
     /// Adjust x1 or x2 bounds based on lowest/highest x positions
     pub fn adjust_bounds(&mut self, x_lowest: i32, x_highest: i32) {
+        // Bounds are fixed if the column position is configured
+        if self.column.is_some() {
+            return;
+        }
         // Adjust upper bound if x_lowest is greater than first
         // bound and less than current upper bound
         if self.alignment == "x1" {
@@ -98,8 +155,8 @@ impl TransactionDescriptionParser {
 
     /// Try reading header and define x1 of x2 bounds
     fn try_parse_header(&mut self, items: &[TextItem]) -> usize {
-        // Return if header already read
-        if self.header_primer.primed {
+        // Return if header already read or column position configured
+        if self.column.is_some() || self.header_primer.primed {
             return 0;
         }
         let header_consumed = self.header_primer.parse_items(items);
@@ -114,19 +171,111 @@ impl TransactionDescriptionParser {
         header_consumed
     }
 
-    /// Try parsing description - x1 and x2 of first item must be within ranges
+    /// Try parsing description - x1 and x2 of first item must be within ranges,
+    /// or overlap the column position if configured
     fn try_parse_description(&mut self, items: &[TextItem]) -> usize {
         if items.is_empty() {
             return 0;
         }
-        let item = &items[0];
-        if item.x1 >= self.x1_range[0]
+        if self.in_column(&items[0]) {
+            return 1; // Consumed 1 item
+        }
+        0
+    }
+
+    /// Check if the item falls within the x ranges, or overlaps the column position
+    pub fn in_column(&self, item: &TextItem) -> bool {
+        if let Some(column) = self.column {
+            return overlaps_column(item, column);
+        }
+        item.x1 >= self.x1_range[0]
             && item.x1 <= self.x1_range[1]
             && item.x2 >= self.x2_range[0]
             && item.x2 <= self.x2_range[1]
-        {
-            return 1; // Consumed 1 item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_text_item(text: &str, y1: i32) -> TextItem {
+        TextItem::new(text.to_string(), 10, y1, 50, y1 + 10, 0)
+    }
+
+    fn parse_description(texts: &[(&str, i32)]) -> String {
+        let mut parser = TransactionDescriptionParser::new(&StatementConfig::default());
+        parser.prime();
+        let mut transaction = ProtoTransaction::new();
+        for (text, y1) in texts {
+            let items = vec![make_text_item(text, *y1)];
+            assert_eq!(parser.parse_items(&items, &mut transaction), 1);
         }
-        0
+        transaction.description
+    }
+
+    #[test]
+    fn test_hyphen_wrapped_description_is_joined() {
+        let description = parse_description(&[
+            ("INTERNATIONAL", 0),
+            ("TRANSFER", 0),
+            ("FROM", 0),
+            ("ACME", 0),
+            ("CORPORA-", 0),
+            ("TION", 12),
+            ("PTY", 12),
+            ("LTD", 12),
+        ]);
+        assert_eq!(
+            description,
+            "INTERNATIONAL TRANSFER FROM ACME CORPORATION PTY LTD"
+        );
+    }
+
+    #[test]
+    fn test_hyphen_within_line_is_kept() {
+        let description = parse_description(&[("ACME-", 0), ("SYDNEY", 0), ("NSW", 12)]);
+        assert_eq!(description, "ACME- SYDNEY NSW");
+
+        // Items of a line may be a few points apart
+        let description = parse_description(&[("ACME-", 0), ("SYDNEY", 3)]);
+        assert_eq!(description, "ACME- SYDNEY");
+    }
+
+    #[test]
+    fn test_hyphen_separator_is_kept() {
+        let description = parse_description(&[("ACME", 0), ("-", 0), ("SYDNEY", 12)]);
+        assert_eq!(description, "ACME - SYDNEY");
+    }
+
+    #[test]
+    fn test_trailing_hyphen_before_non_letter_is_kept() {
+        let description = parse_description(&[("SHOP", 0), ("7-", 0), ("11", 12), ("PTY-", 12)]);
+        assert_eq!(description, "SHOP 7- 11 PTY-");
+    }
+
+    #[test]
+    fn test_description_alignment_tol_overrides_shared_tol() {
+        // A value date column ends 6 points left of the description header
+        let header = TextItem::new("Description".to_string(), 200, 206, 266, 218, 0);
+        let value_date = TextItem::new("03/04/24".to_string(), 150, 223, 194, 235, 0);
+        let parse_header = |config: &StatementConfig| {
+            let mut parser = TransactionDescriptionParser::new(config);
+            assert_eq!(
+                parser.parse_items(std::slice::from_ref(&header), &mut ProtoTransaction::new()),
+                1
+            );
+            parser
+        };
+
+        let mut config = StatementConfig {
+            transaction_description_headers: vec!["Description".to_string()],
+            transaction_alignment_tol: 50,
+            ..Default::default()
+        };
+        assert!(parse_header(&config).in_column(&value_date));
+
+        config.transaction_description_alignment_tol = Some(5);
+        assert!(!parse_header(&config).in_column(&value_date));
     }
 }
@@ -1,10 +1,12 @@
 pub mod amount;
 pub mod balance;
+pub mod block;
 pub mod date;
 pub mod description;
 pub mod utils;
 
 pub use amount::TransactionAmountParser;
 pub use balance::TransactionBalanceParser;
+pub use block::TransactionBlockParser;
 pub use date::TransactionDateParser;
 pub use description::TransactionDescriptionParser;
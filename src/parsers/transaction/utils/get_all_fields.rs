@@ -1,9 +1,11 @@
+use super::parse_format_field;
 use std::collections::HashSet;
 
 /// Returns all unique fields that appear in any of the transaction formats.
 ///
 /// Given multiple transaction formats, this function collects all field names
-/// that appear in any format and returns them as a sorted vector of unique strings.
+/// that appear in any format, without the optional marker, and returns them as a sorted
+/// vector of unique strings.
 ///
 /// Example:
 ///   [["date","description"], ["amount","balance"], ["date","amount"]] -> ["amount","balance","date","description"]
@@ -12,8 +14,8 @@ pub fn get_all_fields(transaction_formats: Vec<Vec<String>>) -> Vec<String> {
 
     // Collect all unique fields from all formats
     for format in transaction_formats {
-        for field in format {
-            all_fields.insert(field);
+        for field in &format {
+            all_fields.insert(parse_format_field(field).0.to_string());
         }
     }
 
@@ -126,6 +128,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_all_fields_strips_optional_marker() {
+        let formats = vec![
+            ss(&["date", "amount", "balance?"]),
+            ss(&["date", "balance"]),
+        ];
+        let got = get_all_fields(formats);
+        assert_eq!(got, ss(&["amount", "balance", "date"]));
+    }
+
     #[test]
     fn test_get_all_fields_output_is_sorted() {
         let formats = vec![ss(&["zebra", "alpha", "beta"]), ss(&["gamma", "delta"])];
@@ -1,12 +1,15 @@
+use super::parse_format_field;
 use std::collections::HashSet;
 
 /// Returns the list of compulsory fields across multiple transaction formats.
 ///
-/// Given multiple transaction formats, a field is compulsory if it appears in all formats.
+/// Given multiple transaction formats, a field is compulsory if it appears in all formats,
+/// and is not marked optional (e.g. "balance?") in any of them.
 /// Uses a fixed universe of fields: ["date", "description", "amount", "balance"].
 ///
 /// Example:
 ///   [["date","description","amount"], ["description","amount"]] -> ["description","amount"]
+///   [["date","description","amount","balance?"]] -> ["date","description","amount"]
 pub fn get_compulsory_fields(transaction_formats: Vec<Vec<String>>) -> Vec<String> {
     // Fixed list of all possible fields
     let all_fields: [&str; 4] = ["date", "description", "amount", "balance"];
@@ -15,8 +18,11 @@ pub fn get_compulsory_fields(transaction_formats: Vec<Vec<String>>) -> Vec<Strin
     let mut optional_fields: HashSet<&str> = HashSet::new();
     for field in &all_fields {
         for format in &transaction_formats {
-            // If a particular format does not include the field, it's optional
-            let present = format.iter().any(|f| f == *field);
+            // If a particular format does not include the field, or marks it
+            // optional, it's optional
+            let present = format
+                .iter()
+                .any(|f| parse_format_field(f) == (*field, false));
             if !present {
                 optional_fields.insert(*field);
             }
@@ -68,6 +74,16 @@ mod tests {
         assert_eq!(got, ss(&["date", "description", "amount", "balance"]));
     }
 
+    #[test]
+    fn compulsory_fields_excludes_optional_marker() {
+        let formats = vec![
+            ss(&["date", "description", "amount", "balance?"]),
+            ss(&["date", "description", "amount", "balance"]),
+        ];
+        let got = get_compulsory_fields(formats);
+        assert_eq!(got, ss(&["date", "description", "amount"]));
+    }
+
     #[test]
     fn compulsory_fields_disjoint_formats_yield_empty() {
         let formats = vec![ss(&["date"]), ss(&["amount"]), ss(&["balance"])];
@@ -1,18 +1,22 @@
+use super::parse_format_field;
 use std::collections::HashSet;
 
-/// Returns the list of fields ending multiple transaction formats.
+/// Returns the list of fields ending multiple transaction formats, without the optional
+/// marker. A row omitting an optional last field ends at the next line instead.
 ///
 /// Example:
 ///   [["date","description","amount"], ["description","amount", "balance"]] -> ["amount", "balance"]
+///   [["date","description","amount","balance?"]] -> ["balance"]
 pub fn get_end_line_fields(transaction_formats: Vec<Vec<String>>) -> Vec<String> {
     let mut seen: HashSet<String> = HashSet::new();
     let mut new_line_fields: Vec<String> = Vec::new();
 
     for format in transaction_formats {
-        if let Some(last) = format.last()
-            && seen.insert(last.clone())
-        {
-            new_line_fields.push(last.clone());
+        if let Some(last) = format.last() {
+            let (last, _) = parse_format_field(last);
+            if seen.insert(last.to_string()) {
+                new_line_fields.push(last.to_string());
+            }
         }
     }
 
@@ -48,6 +52,16 @@ mod tests {
         assert_eq!(got, ss(&["amount"]));
     }
 
+    #[test]
+    fn end_line_fields_strip_optional_marker() {
+        let formats = vec![
+            ss(&["date", "description", "amount", "balance?"]),
+            ss(&["description", "amount", "balance"]),
+        ];
+        let got = get_end_line_fields(formats);
+        assert_eq!(got, ss(&["balance"]));
+    }
+
     #[test]
     fn end_line_fields_empty_input_is_empty() {
         let formats: Vec<Vec<String>> = vec![];
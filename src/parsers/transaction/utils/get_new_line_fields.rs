@@ -1,20 +1,26 @@
+use super::parse_format_field;
 use std::collections::HashSet;
 
 /// Returns the list of fields expected at the start of a transaction line across formats.
+/// The fields after an optional first field (e.g. "date?") may also start a line.
 ///
 /// Example:
 ///   [["date","description","amount"], ["description","amount"]] -> ["date", "description"]
+///   [["date?","description","amount"]] -> ["date", "description"]
 pub fn get_new_line_fields(transaction_formats: Vec<Vec<String>>) -> Vec<String> {
     // Preserve insertion order like JS Set
     let mut seen: HashSet<String> = HashSet::new();
     let mut start_line_fields: Vec<String> = Vec::new();
 
     for format in transaction_formats {
-        if let Some(first) = format.first()
-            && !seen.contains(first)
-        {
-            seen.insert(first.clone());
-            start_line_fields.push(first.clone());
+        for token in &format {
+            let (field, optional) = parse_format_field(token);
+            if seen.insert(field.to_string()) {
+                start_line_fields.push(field.to_string());
+            }
+            if !optional {
+                break;
+            }
         }
     }
 
@@ -50,6 +56,13 @@ mod tests {
         assert_eq!(got, ss(&["date"]));
     }
 
+    #[test]
+    fn new_line_fields_after_optional_first_field() {
+        let formats = vec![ss(&["date?", "description", "amount"])];
+        let got = get_new_line_fields(formats);
+        assert_eq!(got, ss(&["date", "description"]));
+    }
+
     #[test]
     fn new_line_fields_empty_input_is_empty() {
         let formats: Vec<Vec<String>> = vec![];
@@ -1,6 +1,9 @@
+use super::parse_format_field;
 use std::collections::HashMap;
 
-/// Return a mapping from each field to its immediate next fields across formats.
+/// Return a mapping from each field to its immediate next fields across formats, without
+/// the optional marker. The field after an optional field (e.g. "balance?") is also a
+/// next field of the field before it.
 ///
 /// Given tokenized formats like [["date","description","amount"], ["description","amount"]],
 /// returns a map such as:
@@ -12,15 +15,17 @@ pub fn get_next_fields(transaction_formats: Vec<Vec<String>>) -> HashMap<String,
 
     for format in transaction_formats {
         for i in 0..format.len() {
-            let current = &format[i];
-            let entry = related.entry(current.clone()).or_default();
-            // If no next field, continue after ensuring key exists
-            if i + 1 > format.len().saturating_sub(1) {
-                continue;
-            }
-            let next = &format[i + 1];
-            if !entry.iter().any(|s| s == next) {
-                entry.push(next.clone());
+            let (current, _) = parse_format_field(&format[i]);
+            let entry = related.entry(current.to_string()).or_default();
+            // Skip over optional next fields, if no next field the key still exists
+            for token in &format[i + 1..] {
+                let (next, optional) = parse_format_field(token);
+                if !entry.iter().any(|s| s == next) {
+                    entry.push(next.to_string());
+                }
+                if !optional {
+                    break;
+                }
             }
         }
     }
@@ -66,6 +71,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn next_fields_skip_optional_fields() {
+        let formats = vec![ss(&["date", "description?", "amount", "balance?"])];
+        let got = get_next_fields(formats);
+        assert_eq!(
+            got.get("date").cloned(),
+            Some(ss(&["description", "amount"]))
+        );
+        assert_eq!(got.get("description").cloned(), Some(ss(&["amount"])));
+        assert_eq!(got.get("amount").cloned(), Some(ss(&["balance"])));
+        assert_eq!(got.get("balance").cloned(), Some(vec![]));
+    }
+
     #[test]
     fn next_fields_empty_input() {
         let formats: Vec<Vec<String>> = vec![];
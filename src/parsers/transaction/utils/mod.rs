@@ -3,9 +3,13 @@ pub mod get_compulsory_fields;
 pub mod get_end_line_fields;
 pub mod get_new_line_fields;
 pub mod get_next_fields;
+pub mod optional_field;
+pub mod overlaps_column;
 
 pub use get_all_fields::get_all_fields;
 pub use get_compulsory_fields::get_compulsory_fields;
 pub use get_end_line_fields::get_end_line_fields;
 pub use get_new_line_fields::get_new_line_fields;
 pub use get_next_fields::get_next_fields;
+pub use optional_field::{OPTIONAL_FIELD_MARKER, has_optional_fields, parse_format_field};
+pub use overlaps_column::overlaps_column;
@@ -0,0 +1,43 @@
+/// Marker ending a field of a transaction format that some rows omit, e.g. "balance?"
+pub const OPTIONAL_FIELD_MARKER: char = '?';
+
+/// Returns the field name of a transaction format token, without the optional marker,
+/// and whether the field is optional.
+///
+/// Example:
+///   "balance?" -> ("balance", true), "amount" -> ("amount", false)
+pub fn parse_format_field(token: &str) -> (&str, bool) {
+    match token.strip_suffix(OPTIONAL_FIELD_MARKER) {
+        Some(field) => (field, true),
+        None => (token, false),
+    }
+}
+
+/// Returns true if any field of the transaction formats is marked optional.
+pub fn has_optional_fields(transaction_formats: &[Vec<String>]) -> bool {
+    transaction_formats
+        .iter()
+        .flatten()
+        .any(|token| parse_format_field(token).1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_format_field_strips_marker() {
+        assert_eq!(parse_format_field("balance?"), ("balance", true));
+        assert_eq!(parse_format_field("amount"), ("amount", false));
+    }
+
+    #[test]
+    fn has_optional_fields_in_any_format() {
+        let formats = vec![
+            vec!["date".to_string(), "amount".to_string()],
+            vec!["amount".to_string(), "balance?".to_string()],
+        ];
+        assert!(has_optional_fields(&formats));
+        assert!(!has_optional_fields(&formats[..1]));
+    }
+}
@@ -0,0 +1,29 @@
+use crate::structs::TextItem;
+
+/// Check if the x-range of a text item overlaps a fixed (x1, x2) column position.
+pub fn overlaps_column(item: &TextItem, column: (i32, i32)) -> bool {
+    item.x1 <= column.1 && item.x2 >= column.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_text_item(x1: i32, x2: i32) -> TextItem {
+        TextItem::new("1.00".to_string(), x1, 0, x2, 10, 0)
+    }
+
+    #[test]
+    fn test_overlaps_column() {
+        assert!(overlaps_column(&make_text_item(40, 60), (50, 100)));
+        assert!(overlaps_column(&make_text_item(60, 80), (50, 100)));
+        assert!(overlaps_column(&make_text_item(90, 120), (50, 100)));
+        assert!(overlaps_column(&make_text_item(20, 50), (50, 100)));
+    }
+
+    #[test]
+    fn test_does_not_overlap_column() {
+        assert!(!overlaps_column(&make_text_item(10, 49), (50, 100)));
+        assert!(!overlaps_column(&make_text_item(101, 140), (50, 100)));
+    }
+}
@@ -1,7 +1,28 @@
+use crate::error::TranstractorError;
 use pyo3::create_exception;
-use pyo3::exceptions::PyException;
+use pyo3::exceptions::{PyException, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
 
 // Define custom exceptions
 create_exception!(transtractor, NoErrorFreeStatementData, PyException);
 create_exception!(transtractor, ConfigLoadError, PyException);
 create_exception!(transtractor, ConfigAccessError, PyException);
+// Subclass of RuntimeError, previously raised for invalid layout text
+create_exception!(transtractor, LayoutParseError, PyRuntimeError);
+
+impl From<TranstractorError> for PyErr {
+    /// Raise the Python exception matching the error of the native API
+    fn from(error: TranstractorError) -> Self {
+        match error {
+            TranstractorError::ConfigLoad(message) => ConfigLoadError::new_err(message),
+            TranstractorError::ConfigAccess(message) => ConfigAccessError::new_err(message),
+            TranstractorError::LayoutParse(message) => LayoutParseError::new_err(message),
+            TranstractorError::InvalidArgument(message)
+            | TranstractorError::StatementNotSupported(message) => PyValueError::new_err(message),
+            TranstractorError::NoErrorFreeStatementData(message) => {
+                NoErrorFreeStatementData::new_err(message)
+            }
+            TranstractorError::Parse(message) => PyRuntimeError::new_err(message),
+        }
+    }
+}
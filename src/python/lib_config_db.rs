@@ -1,10 +1,13 @@
+use crate::configs::catalog::config_summaries;
 use crate::configs::db::ConfigDB;
+use crate::configs::lint::{lint_account_terms, lint_config};
 use crate::python::exceptions::{ConfigAccessError, ConfigLoadError};
+use crate::python::utils;
 use pyo3::prelude::*;
 
 #[pyclass]
 pub struct LibConfigDB {
-    db: ConfigDB,
+    pub(crate) db: ConfigDB,
 }
 
 #[pymethods]
@@ -41,6 +44,14 @@ impl LibConfigDB {
         }
     }
 
+    /// Get the JSON of a registered config with every field set, including defaults
+    /// (see ConfigDB::get_config_json).
+    pub fn get_config_json(&self, key: &str) -> PyResult<String> {
+        self.db
+            .get_config_json(key)
+            .map_err(ConfigAccessError::new_err)
+    }
+
     /// Get list of all account_terms from all registered configs by key.
     pub fn get_account_terms(&self, key: &str) -> PyResult<Vec<String>> {
         match self.db.get_account_terms(key) {
@@ -49,6 +60,14 @@ impl LibConfigDB {
         }
     }
 
+    /// Get whether the terms of a registered config are matched ignoring case.
+    pub fn get_terms_case_insensitive(&self, key: &str) -> PyResult<bool> {
+        match self.db.get_terms_case_insensitive(key) {
+            Ok(case_insensitive) => Ok(case_insensitive),
+            Err(e) => Err(ConfigAccessError::new_err(e)),
+        }
+    }
+
     /// Get list of all registered config keys.
     pub fn get_all_config_keys(&self) -> PyResult<Vec<String>> {
         Ok(self.db.get_config_keys())
@@ -58,4 +77,41 @@ impl LibConfigDB {
     pub fn has_config(&self, key: &str) -> PyResult<bool> {
         Ok(self.db.has_config(key))
     }
+
+    /// Get a list of the registered configs, sorted by key, as dictionaries with the
+    /// source "embedded" (see utils::rust_config_summaries_to_py_list).
+    pub fn list_configs(&self) -> PyResult<Py<PyAny>> {
+        let configs = self.db.get_configs().map_err(ConfigAccessError::new_err)?;
+        utils::rust_config_summaries_to_py_list(&config_summaries(&configs, &[]))
+    }
+
+    /// Get the catalog of registered configs as a dictionary of country code to bank
+    /// name to a list of config summaries (see utils::rust_catalog_to_py_catalog).
+    pub fn catalog(&self) -> PyResult<Py<PyAny>> {
+        let catalog = self.db.catalog().map_err(ConfigAccessError::new_err)?;
+        utils::rust_catalog_to_py_catalog(&catalog)
+    }
+
+    /// Get the catalog of registered configs as text, with a line per country and
+    /// a line per bank listing its account types.
+    pub fn catalog_str(&self) -> PyResult<String> {
+        let catalog = self.db.catalog().map_err(ConfigAccessError::new_err)?;
+        Ok(catalog.to_string())
+    }
+
+    /// Get the warnings of the cross-field checks of a registered config (see
+    /// lint_config), and of its account terms against the other registered configs
+    /// (see lint_account_terms), as "field: message" strings.
+    pub fn lint_config(&self, key: &str) -> PyResult<Vec<String>> {
+        let config = self
+            .db
+            .get_config(key)
+            .map_err(ConfigAccessError::new_err)?;
+        let configs = self.db.get_configs().map_err(ConfigAccessError::new_err)?;
+        Ok(lint_config(&config)
+            .into_iter()
+            .chain(lint_account_terms(&config, &configs))
+            .map(|warning| warning.to_string())
+            .collect())
+    }
 }
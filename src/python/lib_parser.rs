@@ -1,193 +1,949 @@
-use crate::configs::db::ConfigDB;
-use crate::configs::typer::StatementTyper;
-use crate::parsers::flows::config_json_file_to_config;
+use crate::configs::catalog::{Catalog, catalog, config_summaries};
+use crate::configs::lint::{lint_account_terms, lint_config};
+use crate::configs::validate::config_warnings;
+use crate::configs::validate::date_timezone::date_timezone as validate_date_timezone;
+use crate::error::TranstractorError;
+use crate::parser::{ParseOptions, Parser};
+use crate::parsers::flows::csv_to_statement_data::{CsvImport, csv_str_to_statement_data};
 use crate::parsers::flows::layout_to_text_items::layout_to_text_items;
-use crate::parsers::flows::text_items_to_debug::text_items_to_debug;
-use crate::parsers::flows::text_items_to_layout::text_items_to_layout;
-use crate::parsers::flows::text_items_to_statement_datas::text_items_to_statement_datas;
-use crate::python::exceptions::{ConfigLoadError, NoErrorFreeStatementData};
+use crate::parsers::flows::statement_data_corrections::statement_data_json_str_apply_csv_corrections;
+use crate::parsers::flows::statement_data_diff::statement_data_json_str_diff;
+use crate::parsers::flows::statement_data_to_json::statement_data_to_json;
+use crate::parsers::flows::statement_data_verification::{VerifyOptions, verify_json_str};
+use crate::parsers::flows::text_items_to_debug::{DebugLimits, text_items_to_debug_report};
+use crate::parsers::flows::text_items_to_explanation::text_items_to_explanation;
+use crate::parsers::flows::text_items_to_layout::{
+    text_items_to_layout, text_items_to_layout_with_header,
+};
+use crate::parsers::flows::text_items_to_transactions::text_items_to_transactions;
+use crate::parsers::flows::try_config::try_config;
+use crate::python::exceptions::{
+    ConfigAccessError, ConfigLoadError, LayoutParseError, NoErrorFreeStatementData,
+};
+use crate::python::lib_config_db::LibConfigDB;
+use crate::python::lib_transaction_stream::LibTransactionStream;
 use crate::python::utils;
-use crate::structs::StatementConfig;
+use crate::structs::text_items::merge_text_item_parts;
+use crate::structs::{
+    Categorizer, LocaleProfile, ParseTimings, Redaction, StatementConfig, StatementData,
+    TextItemCache,
+};
 use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::ffi::CString;
 
 #[pyclass]
 pub struct LibParser {
-    typer: StatementTyper,
-    db: ConfigDB,
+    /// Native parser, of which this is a thin wrapper
+    parser: Parser,
+    cache: TextItemCache,
+    /// Categorizer applied to the transactions of every parse, if set
+    categorizer: Option<Categorizer>,
 }
 
 impl Default for LibParser {
     fn default() -> Self {
         Self {
-            typer: StatementTyper::new(),
-            db: ConfigDB::new(true, false),
+            parser: Parser::new(),
+            cache: TextItemCache::default(),
+            categorizer: None,
         }
     }
 }
 
 impl LibParser {
-    /// Get list of configs from provided keys
-    fn get_configs_from_keys(&self, keys: &Vec<String>) -> Result<Vec<StatementConfig>, String> {
-        let mut configs: Vec<StatementConfig> = Vec::new();
-        for key in keys {
-            if !self.db.has_config(key) {
-                return Err(format!("Config with key '{}' is not registered", key));
+    /// Get the catalog of the configs in the parser database and, if provided, the base
+    /// config database (see get_all_configs)
+    fn get_catalog(&self, base_config_db: Option<PyRef<'_, LibConfigDB>>) -> PyResult<Catalog> {
+        Ok(catalog(&self.get_all_configs(base_config_db)?))
+    }
+
+    /// Get the configs in the parser database and, if provided, the base config
+    /// database. Configs in the parser database replace those with the same key.
+    fn get_all_configs(
+        &self,
+        base_config_db: Option<PyRef<'_, LibConfigDB>>,
+    ) -> PyResult<Vec<StatementConfig>> {
+        let mut configs: HashMap<String, StatementConfig> = HashMap::new();
+        if let Some(base) = base_config_db {
+            for cfg in base.db.get_configs().map_err(ConfigAccessError::new_err)? {
+                configs.insert(cfg.key.clone(), cfg);
+            }
+        }
+        for cfg in self.parser.configs()? {
+            configs.insert(cfg.key.clone(), cfg);
+        }
+        Ok(configs.into_values().collect())
+    }
+
+    /// Parse the Python list of text items with each config of the provided keys, in
+    /// order, and return all StatementData results (see Parser::parse_statement_datas
+    /// and py_text_items_to_py_statement_data for the arguments).
+    #[allow(clippy::too_many_arguments)]
+    fn parse_statement_datas(
+        &self,
+        py_text_items: &Bound<'_, pyo3::types::PyAny>,
+        applicable_config_keys: &[String],
+        expected_account_number: Option<String>,
+        date_timezone: Option<String>,
+        fixers: &str,
+        retry_text_order: bool,
+        strict: bool,
+        timings: Option<HashMap<String, f64>>,
+    ) -> PyResult<Vec<StatementData>> {
+        let text_items = utils::py_text_items_to_rust_text_items(py_text_items)?;
+        let pre_parse_timings = match &timings {
+            Some(t) => utils::py_timings_to_rust_parse_timings(t)?,
+            None => ParseTimings::new(),
+        };
+        let options = ParseOptions {
+            expected_account_number,
+            date_timezone,
+            fixers: fixers
+                .parse()
+                .map_err(pyo3::exceptions::PyValueError::new_err)?,
+            retry_text_order,
+            strict,
+            redaction: None,
+            categorizer: self.categorizer.clone(),
+        };
+        let mut statement_data_results =
+            self.parser
+                .parse_statement_datas(&text_items, applicable_config_keys, &options)?;
+        for data in &mut statement_data_results {
+            data.timings.add(&pre_parse_timings);
+        }
+        Ok(statement_data_results)
+    }
+
+    /// Return the first error-free StatementData (see Parser::first_error_free), or a
+    /// NoErrorFreeStatementData error with the per-config error breakdown as its
+    /// breakdown attribute.
+    fn first_error_free<'a>(
+        statement_data_results: &'a [StatementData],
+        applicable_config_keys: &[String],
+    ) -> PyResult<&'a StatementData> {
+        Ok(&Self::first_error_free_sections(statement_data_results, applicable_config_keys)?[0])
+    }
+
+    /// Return the results of the first config whose account sections or statements are
+    /// all error-free (see Parser::first_error_free_sections), or a
+    /// NoErrorFreeStatementData error as for first_error_free.
+    fn first_error_free_sections<'a>(
+        statement_data_results: &'a [StatementData],
+        applicable_config_keys: &[String],
+    ) -> PyResult<&'a [StatementData]> {
+        match Parser::first_error_free_sections(statement_data_results, applicable_config_keys) {
+            Ok(sections) => Ok(sections),
+            Err(TranstractorError::NoErrorFreeStatementData(message)) => {
+                let breakdown =
+                    utils::rust_statement_datas_to_py_error_breakdown(statement_data_results)?;
+                let err = NoErrorFreeStatementData::new_err(message);
+                Python::attach(|py| err.value(py).setattr("breakdown", breakdown))?;
+                Err(err)
             }
-            let cfg = self.db.get_config(key)?;
-            configs.push(cfg);
+            Err(e) => Err(e.into()),
         }
-        Ok(configs)
     }
 }
 
+/// Emit a Python UserWarning for each likely mistake in a loaded config (see
+/// config_warnings)
+fn warn_config(py: Python<'_>, cfg: &StatementConfig) -> PyResult<()> {
+    let category = py.get_type::<pyo3::exceptions::PyUserWarning>();
+    for warning in config_warnings(cfg) {
+        let message = CString::new(warning).unwrap_or_default();
+        PyErr::warn(py, &category, &message, 1)?;
+    }
+    Ok(())
+}
+
+/// Redaction with the patterns, if set (see Redaction). An invalid pattern raises a
+/// ValueError.
+fn py_redaction(redact_patterns: Option<Vec<String>>) -> PyResult<Option<Redaction>> {
+    redact_patterns
+        .map(|patterns| Redaction::new(&patterns))
+        .transpose()
+        .map_err(pyo3::exceptions::PyValueError::new_err)
+}
+
 #[pymethods]
 impl LibParser {
-    /// Create a new Parser instance
+    /// Create a new Parser instance, caching the text items and applicable config
+    /// keys of up to cache_capacity documents (0 disables the cache). If locale_filter
+    /// is set, identification skips the configs of countries whose statements are in a
+    /// different script from the document (see LocaleProfile::is_plausible_key).
+    /// Configs are identified if at least match_threshold of their account terms are
+    /// found, best match first (see StatementTyper::set_min_score). Raises ValueError
+    /// if match_threshold is not above 0.0 and at most 1.0.
     #[new]
-    pub fn new() -> Self {
-        Self {
-            typer: StatementTyper::new(),
-            db: ConfigDB::new(true, false),
+    #[pyo3(signature = (cache_capacity=0, locale_filter=false, match_threshold=1.0))]
+    pub fn new(cache_capacity: usize, locale_filter: bool, match_threshold: f64) -> PyResult<Self> {
+        let mut parser = Parser::new();
+        parser.set_match_threshold(match_threshold)?;
+        parser.set_locale_filter(locale_filter);
+        Ok(Self {
+            parser,
+            cache: TextItemCache::new(cache_capacity),
+            categorizer: None,
+        })
+    }
+
+    /// Set the maximum number of documents cached, evicting the least recently used
+    /// documents if there are more. Set to 0 to disable the cache.
+    pub fn set_cache_capacity(&mut self, capacity: usize) -> PyResult<()> {
+        self.cache.set_capacity(capacity);
+        Ok(())
+    }
+
+    /// Return the cache capacity, number of cached documents and the number of
+    /// lookups that hit and missed the cache.
+    pub fn cache_stats(&self) -> PyResult<HashMap<String, usize>> {
+        Ok(HashMap::from([
+            ("capacity".to_string(), self.cache.capacity()),
+            ("size".to_string(), self.cache.len()),
+            ("hits".to_string(), self.cache.hits),
+            ("misses".to_string(), self.cache.misses),
+        ]))
+    }
+
+    /// Remove all cached documents and prepared configs.
+    pub fn clear_cache(&mut self) -> PyResult<()> {
+        self.cache.clear();
+        self.parser.clear_prepared_configs();
+        Ok(())
+    }
+
+    /// Return the cached Python list of text items extracted from the document with
+    /// the given content, or None if not cached.
+    pub fn get_cached_py_text_items(&mut self, content: &[u8]) -> PyResult<Option<Py<PyAny>>> {
+        let key = TextItemCache::content_key(content);
+        match self.cache.get_text_items(key) {
+            Some(text_items) => Ok(Some(utils::rust_text_items_to_py_text_items(&text_items)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Cache the Python list of text items extracted from the document with the
+    /// given content. Ignored if the cache is disabled.
+    pub fn cache_py_text_items(
+        &mut self,
+        content: &[u8],
+        py_text_items: &Bound<'_, pyo3::types::PyAny>,
+    ) -> PyResult<()> {
+        if self.cache.capacity() == 0 {
+            return Ok(());
         }
+        let text_items = utils::py_text_items_to_rust_text_items(py_text_items)?;
+        self.cache
+            .put_text_items(TextItemCache::content_key(content), text_items);
+        Ok(())
+    }
+
+    /// Set the categorizer applied to the transactions of every parse from a JSON
+    /// array of [category, pattern] pairs (see Categorizer::from_json_str), or remove
+    /// it if None. Raises ValueError if the JSON or a pattern is invalid.
+    #[pyo3(signature = (json_str, case_insensitive=true))]
+    pub fn set_categorizer_from_json_str(
+        &mut self,
+        json_str: Option<&str>,
+        case_insensitive: bool,
+    ) -> PyResult<()> {
+        self.categorizer = json_str
+            .map(|json_str| Categorizer::from_json_str(json_str, case_insensitive))
+            .transpose()
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        Ok(())
     }
 
     /// Import JSON configuration str into the parser database and update the StatementTyper.
-    pub fn import_config_from_json_str(&mut self, py_json_str: &str) -> PyResult<()> {
-        match self.db.register_from_str(py_json_str) {
-            Ok(_) => {}
-            Err(e) => return Err(ConfigLoadError::new_err(e)),
-        }
-        let cfg = match config_json_file_to_config::from_json_str(py_json_str) {
-            Ok(c) => c,
-            Err(e) => return Err(ConfigLoadError::new_err(e)),
-        };
-        self.typer.add_account_terms(&cfg.key, &cfg.account_terms);
+    pub fn import_config_from_json_str(
+        &mut self,
+        py: Python<'_>,
+        py_json_str: &str,
+    ) -> PyResult<()> {
+        let cfg = self.parser.import_config_from_json_str(py_json_str)?;
+        warn_config(py, &cfg)?;
+        self.cache.clear_config_keys();
         Ok(())
     }
 
     /// Register JSON configuration string into the parser database without updating the StatementTyper.
+    /// The cache is kept, as the applicable config keys of cached documents are unchanged.
     pub fn register_config_from_json_str(&mut self, py_json_str: &str) -> PyResult<()> {
-        match self.db.register_from_str(py_json_str) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(ConfigLoadError::new_err(e)),
-        }
+        Ok(self.parser.register_config_from_json_str(py_json_str)?)
     }
 
     /// Import JSON configuration file into the parser database and update the StatementTyper.
-    pub fn import_config_from_file(&mut self, py_file_path: &str) -> PyResult<()> {
-        match self.db.register_from_file(py_file_path) {
-            Ok(_) => {}
-            Err(e) => return Err(ConfigLoadError::new_err(e)),
-        }
-        let cfg = match config_json_file_to_config::from_json_file(py_file_path) {
-            Ok(c) => c,
-            Err(e) => return Err(ConfigLoadError::new_err(e)),
-        };
-        self.typer.add_account_terms(&cfg.key, &cfg.account_terms);
+    pub fn import_config_from_file(&mut self, py: Python<'_>, py_file_path: &str) -> PyResult<()> {
+        let cfg = self.parser.import_config_from_file(py_file_path)?;
+        warn_config(py, &cfg)?;
+        self.cache.clear_config_keys();
         Ok(())
     }
 
     /// Add account terms (list of strings) to the StatementTyper for a given config key.
-    pub fn add_account_terms(&mut self, key: &str, terms: Vec<String>) -> PyResult<()> {
-        self.typer.add_account_terms(key, &terms);
+    /// Terms are normalised as for configs loaded from JSON (leading and trailing
+    /// whitespace and zero-width characters removed), then validated. If
+    /// terms_case_insensitive, the terms are matched ignoring case.
+    #[pyo3(signature = (key, terms, terms_case_insensitive=false))]
+    pub fn add_account_terms(
+        &mut self,
+        key: &str,
+        terms: Vec<String>,
+        terms_case_insensitive: bool,
+    ) -> PyResult<()> {
+        self.parser
+            .add_account_terms(key, &terms, terms_case_insensitive)?;
+        self.cache.clear_config_keys();
         Ok(())
     }
 
-    /// Return a list of keys applicable to the provided text items.
+    /// Return a list of keys applicable to the provided text items. If the content of
+    /// the document the text items were extracted from is provided, the keys are
+    /// cached with the text items of the document.
+    #[pyo3(signature = (py_text_items, content=None))]
     pub fn get_applicable_config_keys(
-        &self,
+        &mut self,
         py_text_items: &Bound<'_, pyo3::types::PyAny>,
+        content: Option<&[u8]>,
     ) -> PyResult<Vec<String>> {
+        let cache_key = match content {
+            Some(c) if self.cache.capacity() > 0 => Some(TextItemCache::content_key(c)),
+            _ => None,
+        };
+        if let Some(cache_key) = cache_key
+            && let Some(keys) = self.cache.get_config_keys(cache_key)
+        {
+            return Ok(keys);
+        }
         let text_items = utils::py_text_items_to_rust_text_items(py_text_items)?;
-        let keys = self.typer.identify(&text_items);
+        let keys = self.parser.identify(&text_items);
+        if let Some(cache_key) = cache_key {
+            self.cache.put_config_keys(cache_key, keys.clone());
+        }
         Ok(keys)
     }
 
+    /// Return the locale profile of a Python list of text items as a dictionary with
+    /// the letter counts of each script ("scripts"), the document "script" (None if
+    /// uncertain), the counts of locale-indicative "currency_symbols", and the numbers
+    /// of amounts with "decimal_commas" and "decimal_points".
+    pub fn py_text_items_to_py_locale_profile(
+        &self,
+        py: Python<'_>,
+        py_text_items: &Bound<'_, pyo3::types::PyAny>,
+    ) -> PyResult<Py<PyAny>> {
+        let text_items = utils::py_text_items_to_rust_text_items(py_text_items)?;
+        let profile = LocaleProfile::from_text_items(&text_items);
+        let scripts: HashMap<&str, usize> = profile
+            .script_counts
+            .iter()
+            .map(|(script, count)| (script.name(), *count))
+            .collect();
+        let currency_symbols: HashMap<String, usize> = profile
+            .currency_symbols
+            .iter()
+            .map(|(symbol, count)| (symbol.to_string(), *count))
+            .collect();
+        let dict = pyo3::types::PyDict::new(py);
+        dict.set_item("scripts", scripts)?;
+        dict.set_item("script", profile.script().map(|script| script.name()))?;
+        dict.set_item("currency_symbols", currency_symbols)?;
+        dict.set_item("decimal_commas", profile.decimal_commas)?;
+        dict.set_item("decimal_points", profile.decimal_points)?;
+        Ok(dict.into_any().unbind())
+    }
+
+    /// Return the catalog of supported configs, from the parser database and optionally
+    /// a base config database, as a dictionary of country code to bank name to a list
+    /// of config summaries (see utils::rust_catalog_to_py_catalog).
+    #[pyo3(signature = (base_config_db=None))]
+    pub fn catalog(&self, base_config_db: Option<PyRef<'_, LibConfigDB>>) -> PyResult<Py<PyAny>> {
+        utils::rust_catalog_to_py_catalog(&self.get_catalog(base_config_db)?)
+    }
+
+    /// Return a list of the supported configs, sorted by key, as dictionaries (see
+    /// utils::rust_config_summaries_to_py_list). Configs of the base config database,
+    /// if provided, have the source "embedded", and configs imported into the parser
+    /// database the source "user", replacing embedded configs with the same key.
+    #[pyo3(signature = (base_config_db=None))]
+    pub fn list_configs(
+        &self,
+        base_config_db: Option<PyRef<'_, LibConfigDB>>,
+    ) -> PyResult<Py<PyAny>> {
+        let embedded = match base_config_db {
+            Some(base) => base.db.get_configs().map_err(ConfigAccessError::new_err)?,
+            None => Vec::new(),
+        };
+        let user = self.parser.configs()?;
+        utils::rust_config_summaries_to_py_list(&config_summaries(&embedded, &user))
+    }
+
+    /// Return the JSON of a config of the parser database or, if not imported and
+    /// provided, the base config database, with every field set (see
+    /// StatementConfig::to_json_str). Raises ConfigAccessError if the config is in
+    /// neither.
+    #[pyo3(signature = (key, base_config_db=None))]
+    pub fn get_config_json(
+        &self,
+        key: &str,
+        base_config_db: Option<PyRef<'_, LibConfigDB>>,
+    ) -> PyResult<String> {
+        let keys = [key.to_string()];
+        let config = match base_config_db {
+            Some(base) if !self.parser.has_config(key) => base
+                .db
+                .get_config(key)
+                .map_err(ConfigAccessError::new_err)?,
+            _ => self.parser.configs_from_keys(&keys)?.remove(0),
+        };
+        config.to_json_str().map_err(ConfigAccessError::new_err)
+    }
+
+    /// Return the names of the amount and date formats registered at runtime (see
+    /// register_amount_format and register_date_format). The registries are
+    /// process-wide, so these formats are missing from new processes.
+    #[staticmethod]
+    pub fn registered_formats() -> Vec<String> {
+        let mut names = crate::formats::amount::get_registered_formats();
+        names.extend(crate::formats::date::get_registered_formats());
+        names
+    }
+
+    /// Return the catalog of supported configs (see catalog) as text, with a line per
+    /// country and a line per bank listing its account types.
+    #[pyo3(signature = (base_config_db=None))]
+    pub fn catalog_str(&self, base_config_db: Option<PyRef<'_, LibConfigDB>>) -> PyResult<String> {
+        Ok(self.get_catalog(base_config_db)?.to_string())
+    }
+
+    /// Return the warnings of the cross-field checks of a config (see lint_config), and
+    /// of its account terms against the other configs of the parser database and, if
+    /// provided, the base config database (see lint_account_terms), as
+    /// "field: message" strings. Raises ConfigAccessError if the config is in neither.
+    #[pyo3(signature = (key, base_config_db=None))]
+    pub fn lint_config(
+        &self,
+        key: &str,
+        base_config_db: Option<PyRef<'_, LibConfigDB>>,
+    ) -> PyResult<Vec<String>> {
+        let configs = self.get_all_configs(base_config_db)?;
+        let config = configs.iter().find(|cfg| cfg.key == key).ok_or_else(|| {
+            ConfigAccessError::new_err(format!("Config with key '{}' is not registered", key))
+        })?;
+        Ok(lint_config(config)
+            .into_iter()
+            .chain(lint_account_terms(config, &configs))
+            .map(|warning| warning.to_string())
+            .collect())
+    }
+
     /// Return a list of keys not yet registered in the parser database. Input
     /// a list of keys to check against.
     pub fn get_unregistered_config_keys(&self, keys: Vec<String>) -> PyResult<Vec<String>> {
         let unregistered_keys: Vec<String> = keys
             .into_iter()
-            .filter(|key| !self.db.has_config(key))
+            .filter(|key| !self.parser.has_config(key))
             .collect();
         Ok(unregistered_keys)
     }
 
     /// Process a Python list of text items and return statement data as a
-    /// Python object of type StatementData.
+    /// Python object of type StatementData. If an expected account number (or
+    /// regex pattern prefixed with "re:") is provided, results with a different
+    /// account number are not error-free. If a date timezone is provided, it overrides the
+    /// date_timezone of every applicable config. The fixers mode ("all", "safe" or
+    /// "none") sets which fixers are applied before the checks. If retry_text_order
+    /// is set, configs whose fix_text_order reordering gives errors are retried in
+    /// the original text order. If strict is set, configs are parsed as with
+    /// transaction_strict set. Timings of the stages before parsing ("text_extraction"
+    /// and "statement_typing", in seconds) are added to the timings of each result.
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (py_text_items, applicable_config_keys, expected_account_number=None, date_timezone=None, fixers="all", retry_text_order=false, timings=None, strict=false))]
     pub fn py_text_items_to_py_statement_data(
         &self,
         py_text_items: &Bound<'_, pyo3::types::PyAny>,
         applicable_config_keys: Vec<String>,
+        expected_account_number: Option<String>,
+        date_timezone: Option<String>,
+        fixers: &str,
+        retry_text_order: bool,
+        timings: Option<HashMap<String, f64>>,
+        strict: bool,
     ) -> PyResult<Py<PyAny>> {
-        let text_items = utils::py_text_items_to_rust_text_items(py_text_items)?;
-        let configs = self
-            .get_configs_from_keys(&applicable_config_keys)
-            .map_err(|e| {
-                pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to get configs: {}", e))
-            })?;
+        let statement_data_results = self.parse_statement_datas(
+            py_text_items,
+            &applicable_config_keys,
+            expected_account_number,
+            date_timezone,
+            fixers,
+            retry_text_order,
+            strict,
+            timings,
+        )?;
+
+        let data = Self::first_error_free(&statement_data_results, &applicable_config_keys)?;
+        utils::rust_statement_data_to_py_statement_data(data)
+    }
+
+    /// Process a Python list of text items and return a Python list of StatementData,
+    /// one per account section or statement of the first config whose sections are all
+    /// error-free. Configs that are neither multi_account nor multi_statement give a
+    /// single StatementData.
+    /// The expected account number is not checked for account sections. Other
+    /// arguments and errors are as for py_text_items_to_py_statement_data.
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (py_text_items, applicable_config_keys, expected_account_number=None, date_timezone=None, fixers="all", retry_text_order=false, timings=None, strict=false))]
+    pub fn py_text_items_to_py_statement_datas(
+        &self,
+        py_text_items: &Bound<'_, pyo3::types::PyAny>,
+        applicable_config_keys: Vec<String>,
+        expected_account_number: Option<String>,
+        date_timezone: Option<String>,
+        fixers: &str,
+        retry_text_order: bool,
+        timings: Option<HashMap<String, f64>>,
+        strict: bool,
+    ) -> PyResult<Vec<Py<PyAny>>> {
+        let statement_data_results = self.parse_statement_datas(
+            py_text_items,
+            &applicable_config_keys,
+            expected_account_number,
+            date_timezone,
+            fixers,
+            retry_text_order,
+            strict,
+            timings,
+        )?;
+
+        Self::first_error_free_sections(&statement_data_results, &applicable_config_keys)?
+            .iter()
+            .map(utils::rust_statement_data_to_py_statement_data)
+            .collect()
+    }
 
-        let statement_data_results = text_items_to_statement_datas(&text_items, &configs)
+    /// Process a Python list of text items and return an iterator over its
+    /// transactions, parsed incrementally with the first applicable config (see
+    /// TransactionStream). Implicit dates and balances are filled as transactions
+    /// are parsed, but other fixers and the quality checks are not applied. If a
+    /// date timezone is provided, it overrides the date_timezone of the config.
+    #[pyo3(signature = (py_text_items, applicable_config_keys, date_timezone=None))]
+    pub fn py_text_items_to_transaction_stream(
+        &self,
+        py_text_items: &Bound<'_, pyo3::types::PyAny>,
+        applicable_config_keys: Vec<String>,
+        date_timezone: Option<String>,
+    ) -> PyResult<LibTransactionStream> {
+        let text_items = utils::py_text_items_to_rust_text_items(py_text_items)?;
+        let mut config = self
+            .parser
+            .configs_from_keys(&applicable_config_keys)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err("No config keys provided"))?;
+        if let Some(tz) = date_timezone {
+            validate_date_timezone(&tz).map_err(pyo3::exceptions::PyValueError::new_err)?;
+            config.date_timezone = tz;
+        }
+        let stream = text_items_to_transactions(&text_items, &config)
             .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        let tz = config.date_timezone.parse().ok();
+        Ok(LibTransactionStream::new(stream, tz))
+    }
+
+    /// Process a Python list of text items and return the first error-free statement
+    /// data as a JSON string (see statement_data_to_json). Incomplete transactions are
+    /// emitted with nulls if include_incomplete is set, otherwise skipped. Other
+    /// arguments and errors are as for py_text_items_to_py_statement_data. If
+    /// redact_patterns is set, the statement data is redacted with the patterns (see
+    /// Redaction).
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (py_text_items, applicable_config_keys, expected_account_number=None, date_timezone=None, fixers="all", retry_text_order=false, include_incomplete=false, strict=false, redact_patterns=None))]
+    pub fn py_text_items_to_json_py_str(
+        &self,
+        py_text_items: &Bound<'_, pyo3::types::PyAny>,
+        applicable_config_keys: Vec<String>,
+        expected_account_number: Option<String>,
+        date_timezone: Option<String>,
+        fixers: &str,
+        retry_text_order: bool,
+        include_incomplete: bool,
+        strict: bool,
+        redact_patterns: Option<Vec<String>>,
+    ) -> PyResult<String> {
+        let redaction = py_redaction(redact_patterns)?;
+        let statement_data_results = self.parse_statement_datas(
+            py_text_items,
+            &applicable_config_keys,
+            expected_account_number.clone(),
+            date_timezone,
+            fixers,
+            retry_text_order,
+            strict,
+            None,
+        )?;
+        let mut data =
+            Self::first_error_free(&statement_data_results, &applicable_config_keys)?.clone();
+        if let Some(redaction) = &redaction {
+            redaction.apply(&mut data, expected_account_number.as_deref());
+        }
+        statement_data_to_json(&data, include_incomplete)
+            .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+    }
+
+    /// Diff two JSON exports of parses of the same statement (see
+    /// statement_data_json_diff). Returns the diff as a JSON string, and as text.
+    #[staticmethod]
+    pub fn diff_json_py_strs(old_json_str: &str, new_json_str: &str) -> PyResult<(String, String)> {
+        let diff = statement_data_json_str_diff(old_json_str, new_json_str)
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        Ok((diff.to_value().to_string(), diff.to_string()))
+    }
+
+    /// Compare the transactions of a JSON export of statement data with an expected
+    /// CSV (see verify_json_str). Returns the report as a JSON string, and as text.
+    #[staticmethod]
+    #[pyo3(signature = (json_str, expected_csv_str, tolerance=0.005, normalise_descriptions=true))]
+    pub fn verify_json_py_strs(
+        json_str: &str,
+        expected_csv_str: &str,
+        tolerance: f64,
+        normalise_descriptions: bool,
+    ) -> PyResult<(String, String)> {
+        let options = VerifyOptions {
+            tolerance,
+            normalise_descriptions,
+        };
+        let report = verify_json_str(json_str, expected_csv_str, &options)
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        Ok((report.to_value().to_string(), report.to_string()))
+    }
+
+    /// Apply the corrections of a CSV file to a JSON export of statement data (see
+    /// statement_data_json_str_apply_csv_corrections). Returns the corrected JSON
+    /// export, and the summary of the corrections as a JSON string.
+    #[staticmethod]
+    pub fn apply_csv_corrections_json_py_strs(
+        json_str: &str,
+        csv_path: &str,
+    ) -> PyResult<(String, String)> {
+        let (corrected, summary) =
+            statement_data_json_str_apply_csv_corrections(json_str, csv_path)
+                .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        Ok((corrected, summary.to_value().to_string()))
+    }
 
-        // Find the first error-free StatementData
-        for data in statement_data_results {
-            if data.errors.is_empty() {
-                return utils::rust_statement_data_to_py_statement_data(&data);
+    /// Read a bank CSV export into a Python StatementData, applying all fixers and
+    /// checkers (see csv_str_to_statement_data). The columns map "date",
+    /// "description", "amount" and "balance" to the CSV headers, in place of the
+    /// defaults of CsvImport; an empty "balance" header reads an export without
+    /// balances. Raises ValueError if the CSV cannot be read, or
+    /// NoErrorFreeStatementData if the statement data has errors.
+    #[staticmethod]
+    #[pyo3(signature = (csv_str, columns=None, date_formats=None, account_number=None, opening_balance=None, closing_balance=None, key="csv"))]
+    pub fn csv_py_str_to_py_statement_data(
+        csv_str: &str,
+        columns: Option<HashMap<String, String>>,
+        date_formats: Option<Vec<String>>,
+        account_number: Option<String>,
+        opening_balance: Option<f64>,
+        closing_balance: Option<f64>,
+        key: &str,
+    ) -> PyResult<Py<PyAny>> {
+        let mut import = CsvImport {
+            key: key.to_string(),
+            account_number,
+            opening_balance,
+            closing_balance,
+            ..CsvImport::default()
+        };
+        if let Some(date_formats) = date_formats {
+            import.date_formats = date_formats;
+        }
+        for (field, header) in columns.unwrap_or_default() {
+            match field.as_str() {
+                "date" => import.date_column = header,
+                "description" => import.description_column = header,
+                "amount" => import.amount_column = header,
+                "balance" => import.balance_column = header,
+                _ => {
+                    return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                        "Invalid CSV column field: {}. Must be \"date\", \"description\", \"amount\" or \"balance\"",
+                        field
+                    )));
+                }
             }
         }
+        let data = csv_str_to_statement_data(csv_str, &import)
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        let data = Self::first_error_free(std::slice::from_ref(&data), &[import.key])?;
+        utils::rust_statement_data_to_py_statement_data(data)
+    }
 
-        Err(NoErrorFreeStatementData::new_err(format!(
-            "No error-free StatementData found. This indicates an error in one of the configurations: {}. See further details using the \"debug\" method. Please contact a developer if this config is part of the default set provided by the package.",
-            applicable_config_keys.join(", ")
-        )))
+    /// Parse a Python list of text items with a draft config JSON string only (see
+    /// try_config), without registering the config, updating the StatementTyper or
+    /// using the cache. Returns a dict with the config "key", "passed" (True if every
+    /// account section is error-free), "errors", "warnings", "config_warnings",
+    /// "statement_datas" (a StatementData per account section) and "trace" (the
+    /// debug summary of each result). Raises ConfigLoadError if the config is
+    /// invalid.
+    #[staticmethod]
+    pub fn try_config(
+        py: Python<'_>,
+        json_str: &str,
+        py_text_items: &Bound<'_, pyo3::types::PyAny>,
+    ) -> PyResult<Py<PyAny>> {
+        let text_items = utils::py_text_items_to_rust_text_items(py_text_items)?;
+        let trial = try_config(json_str, &text_items).map_err(ConfigLoadError::new_err)?;
+        let statement_datas = trial
+            .statement_datas
+            .iter()
+            .map(utils::rust_statement_data_to_py_statement_data)
+            .collect::<PyResult<Vec<_>>>()?;
+        let dict = pyo3::types::PyDict::new(py);
+        dict.set_item("key", &trial.key)?;
+        dict.set_item("passed", trial.passed())?;
+        dict.set_item("errors", trial.errors())?;
+        dict.set_item("warnings", trial.warnings())?;
+        dict.set_item("config_warnings", &trial.config_warnings)?;
+        dict.set_item("statement_datas", statement_datas)?;
+        dict.set_item("trace", trial.trace())?;
+        Ok(dict.into_any().unbind())
     }
 
-    /// Process a Python list of text items and return debug information as a string.
-    pub fn py_text_items_to_debug_py_str(
+    /// Process a Python list of text items and return a Python list of candidate
+    /// dictionaries, one per applicable config in the given order, whether or not
+    /// the result is error-free (see utils::rust_statement_datas_to_py_candidates).
+    /// Arguments are as for py_text_items_to_py_statement_data.
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (py_text_items, applicable_config_keys, expected_account_number=None, date_timezone=None, fixers="all", retry_text_order=false, strict=false))]
+    pub fn py_text_items_to_py_candidates(
         &self,
         py_text_items: &Bound<'_, pyo3::types::PyAny>,
         applicable_config_keys: Vec<String>,
+        expected_account_number: Option<String>,
+        date_timezone: Option<String>,
+        fixers: &str,
+        retry_text_order: bool,
+        strict: bool,
+    ) -> PyResult<Py<PyAny>> {
+        let statement_data_results = self.parse_statement_datas(
+            py_text_items,
+            &applicable_config_keys,
+            expected_account_number,
+            date_timezone,
+            fixers,
+            retry_text_order,
+            strict,
+            None,
+        )?;
+        utils::rust_statement_datas_to_py_candidates(&statement_data_results)
+    }
+
+    /// Process a Python list of text items and stream debug information to a file,
+    /// written atomically and truncated at the size limits (see text_items_to_debug).
+    /// Timings of the stages before parsing are added as for py_text_items_to_py_statement_data.
+    /// If sources is set, the text items read into each field are listed. Without
+    /// applicable config keys, the account terms found and missing for every
+    /// registered key are written instead (see StatementTyper::explain). If
+    /// redact_patterns is set, the results are redacted with the patterns (see
+    /// Redaction).
+    #[pyo3(signature = (py_text_items, applicable_config_keys, output_file, retry_text_order=false, timings=None, sources=false, max_bytes=None, max_section_lines=10000, redact_patterns=None))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn py_text_items_to_debug_file(
+        &self,
+        py_text_items: &Bound<'_, pyo3::types::PyAny>,
+        applicable_config_keys: Vec<String>,
+        output_file: &str,
+        retry_text_order: bool,
+        timings: Option<HashMap<String, f64>>,
+        sources: bool,
+        max_bytes: Option<usize>,
+        max_section_lines: usize,
+        redact_patterns: Option<Vec<String>>,
+    ) -> PyResult<()> {
+        let redaction = py_redaction(redact_patterns)?;
+        let text_items = utils::py_text_items_to_rust_text_items(py_text_items)?;
+        let pre_parse_timings = match &timings {
+            Some(t) => utils::py_timings_to_rust_parse_timings(t)?,
+            None => ParseTimings::new(),
+        };
+        let configs = self.parser.configs_from_keys(&applicable_config_keys)?;
+        let limits = DebugLimits {
+            max_section_lines,
+            max_bytes,
+        };
+
+        let mut report = text_items_to_debug_report(
+            &text_items,
+            &configs,
+            retry_text_order,
+            sources,
+            &pre_parse_timings,
+        );
+        self.parser
+            .set_identification_scores(&text_items, &mut report.statement_datas);
+        if applicable_config_keys.is_empty() {
+            report.diagnostics = self.parser.typer().explain(&text_items);
+        }
+        if let Some(redaction) = &redaction {
+            report.redact(redaction);
+        }
+        report
+            .write_file(&limits, output_file)
+            .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+    }
+
+    /// Process a Python list of text items and return the debug report as a JSON string
+    /// (see DebugReport::to_json): for each applicable config the key, account number,
+    /// dates, balances, transaction count, errors, warnings and stage timings. Timings
+    /// of the stages before parsing are added as for py_text_items_to_py_statement_data.
+    /// If redact_patterns is set, the results are redacted as for
+    /// py_text_items_to_debug_file.
+    #[pyo3(signature = (py_text_items, applicable_config_keys, retry_text_order=false, timings=None, redact_patterns=None))]
+    pub fn py_text_items_to_debug_json_py_str(
+        &self,
+        py_text_items: &Bound<'_, pyo3::types::PyAny>,
+        applicable_config_keys: Vec<String>,
+        retry_text_order: bool,
+        timings: Option<HashMap<String, f64>>,
+        redact_patterns: Option<Vec<String>>,
     ) -> PyResult<String> {
+        let redaction = py_redaction(redact_patterns)?;
         let text_items = utils::py_text_items_to_rust_text_items(py_text_items)?;
-        let configs = self
-            .get_configs_from_keys(&applicable_config_keys)
-            .map_err(|e| {
-                pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to get configs: {}", e))
-            })?;
-
-        match text_items_to_debug(&text_items, &configs) {
-            Ok(debug_str) => Ok(debug_str),
-            Err(e) => Err(pyo3::exceptions::PyRuntimeError::new_err(e)),
+        let pre_parse_timings = match &timings {
+            Some(t) => utils::py_timings_to_rust_parse_timings(t)?,
+            None => ParseTimings::new(),
+        };
+        let configs = self.parser.configs_from_keys(&applicable_config_keys)?;
+        let mut report = text_items_to_debug_report(
+            &text_items,
+            &configs,
+            retry_text_order,
+            false,
+            &pre_parse_timings,
+        );
+        self.parser
+            .set_identification_scores(&text_items, &mut report.statement_datas);
+        if let Some(redaction) = &redaction {
+            report.redact(redaction);
         }
+        report
+            .to_json()
+            .map_err(pyo3::exceptions::PyRuntimeError::new_err)
     }
 
-    /// Process a Python list of text items and return layout text as a string.
+    /// Process a Python list of text items with every applicable config (none if the
+    /// statement was not identified) and return an explanation of the parse as a
+    /// human-readable string and a JSON string (see text_items_to_explanation). The
+    /// identification evidence covers the account terms of every registered key.
+    /// Timings of the stages before parsing are added as for py_text_items_to_py_statement_data.
+    #[pyo3(signature = (py_text_items, applicable_config_keys, retry_text_order=false, timings=None))]
+    pub fn py_text_items_to_explain_py_strs(
+        &self,
+        py_text_items: &Bound<'_, pyo3::types::PyAny>,
+        applicable_config_keys: Vec<String>,
+        retry_text_order: bool,
+        timings: Option<HashMap<String, f64>>,
+    ) -> PyResult<(String, String)> {
+        let text_items = utils::py_text_items_to_rust_text_items(py_text_items)?;
+        let pre_parse_timings = match &timings {
+            Some(t) => utils::py_timings_to_rust_parse_timings(t)?,
+            None => ParseTimings::new(),
+        };
+        let configs = self.parser.configs_from_keys(&applicable_config_keys)?;
+        let evidence = self.parser.typer().term_evidence(&text_items);
+        let explanation = text_items_to_explanation(
+            &text_items,
+            evidence,
+            &configs,
+            retry_text_order,
+            &pre_parse_timings,
+        )
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        let json_str = explanation
+            .to_json()
+            .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        Ok((explanation.to_text(), json_str))
+    }
+
+    /// Read a layout text file and return, for every registered key, the account terms
+    /// found and missing in it, with the text nearest to each missing term (see
+    /// StatementTyper::explain), most terms found first. Raises OSError if the file
+    /// cannot be read, or LayoutParseError if the layout text is invalid.
+    pub fn explain_file(&self, path: &str) -> PyResult<String> {
+        let layout = std::fs::read_to_string(path).map_err(|e| {
+            pyo3::exceptions::PyOSError::new_err(format!(
+                "Failed reading layout file {}: {}",
+                path, e
+            ))
+        })?;
+        let text_items = layout_to_text_items(&layout).map_err(|e| {
+            LayoutParseError::new_err(format!("Failed reading layout file {}: {}", path, e))
+        })?;
+        let diagnostics = self.parser.typer().explain(&text_items);
+        let mut output = String::from("Account terms of each config:\n");
+        if diagnostics.is_empty() {
+            output.push_str("  No configs registered\n");
+        }
+        for diagnostic in &diagnostics {
+            output.push_str(&diagnostic.to_string());
+        }
+        Ok(output)
+    }
+
+    /// Process a Python list of text items and return layout text as a string, optionally
+    /// preceded by a comment header recording how it was generated.
+    #[pyo3(signature = (py_text_items, y_bin, x_gap, header=true, timestamp=true))]
     pub fn py_text_items_to_layout_py_str(
         &self,
         py_text_items: &Bound<'_, pyo3::types::PyAny>,
         y_bin: f32,
         x_gap: f32,
+        header: bool,
+        timestamp: bool,
     ) -> PyResult<String> {
         let text_items = utils::py_text_items_to_rust_text_items(py_text_items)?;
-        match text_items_to_layout(&text_items, y_bin, x_gap) {
+        let result = if header {
+            text_items_to_layout_with_header(&text_items, y_bin, x_gap, timestamp)
+        } else {
+            text_items_to_layout(&text_items, y_bin, x_gap)
+        };
+        match result {
             Ok(layout_str) => Ok(layout_str),
             Err(e) => Err(pyo3::exceptions::PyRuntimeError::new_err(e)),
         }
     }
 
-    /// Process a Python layout text string and return a Python list of text item dictionaries.
+    /// Process a Python layout text string and return a Python list of text item
+    /// dictionaries. Raises LayoutParseError if the layout text is invalid.
     pub fn py_layout_py_str_to_py_text_items(
         &self,
         py_layout_str: &Bound<'_, PyAny>,
     ) -> PyResult<Py<PyAny>> {
         let rust_layout_str = py_layout_str.extract::<String>()?;
         let text_items = layout_to_text_items(&rust_layout_str).map_err(|e| {
-            pyo3::exceptions::PyRuntimeError::new_err(format!(
+            LayoutParseError::new_err(format!(
                 "Failed to convert layout string to text items: {}",
                 e
             ))
         })?;
         utils::rust_text_items_to_py_text_items(&text_items)
     }
+
+    /// Merge Python lists of text items from a statement split across multiple
+    /// files into a single Python list of text items, ordered by part and with
+    /// pages renumbered.
+    pub fn py_text_item_parts_to_py_text_items(
+        &self,
+        py_text_item_parts: Vec<Bound<'_, PyAny>>,
+    ) -> PyResult<Py<PyAny>> {
+        let mut parts = Vec::new();
+        for py_text_items in &py_text_item_parts {
+            parts.push(utils::py_text_items_to_rust_text_items(py_text_items)?);
+        }
+        let text_items = merge_text_item_parts(&parts);
+        utils::rust_text_items_to_py_text_items(&text_items)
+    }
 }
@@ -0,0 +1,75 @@
+use crate::parsers::flows::text_items_to_transactions::TransactionStream;
+use chrono::{DateTime, Datelike, Utc};
+use chrono_tz::Tz;
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+
+/// Python iterator over the transactions of a statement (see TransactionStream),
+/// yielding Python Transaction objects. Unsendable, as the parsers hold formats that
+/// are not thread-safe.
+#[pyclass(unsendable)]
+pub struct LibTransactionStream {
+    stream: TransactionStream,
+    date_timezone: Option<Tz>,
+    last_date: Option<i64>,
+    date_index: usize,
+}
+
+impl LibTransactionStream {
+    pub fn new(stream: TransactionStream, date_timezone: Option<Tz>) -> Self {
+        Self {
+            stream,
+            date_timezone,
+            last_date: None,
+            date_index: 0,
+        }
+    }
+}
+
+#[pymethods]
+impl LibTransactionStream {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Parse up to the next complete transaction and return it as a Python
+    /// Transaction, with its index within the day set as it is streamed.
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<Py<PyAny>>> {
+        let tx = match self.stream.next() {
+            Some(result) => result.map_err(PyRuntimeError::new_err)?,
+            None => return Ok(None),
+        };
+        if self.last_date == Some(tx.date) {
+            self.date_index += 1;
+        } else {
+            self.last_date = Some(tx.date);
+            self.date_index = 0;
+        }
+
+        // Take the date in the statement's date timezone, as for StatementData
+        let dt = DateTime::<Utc>::from_timestamp_millis(tx.date).ok_or_else(|| {
+            PyRuntimeError::new_err(format!("Invalid transaction timestamp: {}", tx.date))
+        })?;
+        let date = match self.date_timezone {
+            Some(tz) => dt.with_timezone(&tz).date_naive(),
+            None => dt.date_naive(),
+        };
+        let py_date = py.import("datetime")?.getattr("date")?.call1((
+            date.year(),
+            date.month(),
+            date.day(),
+        ))?;
+        let transaction_class = py
+            .import("transtractor.structs.transaction")?
+            .getattr("Transaction")?;
+        let py_transaction = transaction_class.call1((
+            py_date,
+            self.date_index,
+            tx.description,
+            tx.amount,
+            tx.balance,
+            tx.date,
+        ))?;
+        Ok(Some(py_transaction.unbind()))
+    }
+}
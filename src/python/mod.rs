@@ -1,4 +1,5 @@
 pub mod exceptions;
 pub mod lib_config_db;
 pub mod lib_parser;
+pub mod lib_transaction_stream;
 pub mod utils;
@@ -1,7 +1,11 @@
-use crate::structs::TextItem;
-use pyo3::exceptions::PyRuntimeError;
+use crate::configs::catalog::{Catalog, ConfigSummary};
+use crate::structs::{ParseTimings, TextItem};
+use chrono::Datelike;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::{PyAny, PyList};
+use std::collections::HashMap;
+use std::time::Duration;
 
 /// Converts a Python list of text item dictionaries to a Rust TextItems struct
 pub fn py_text_items_to_rust_text_items(
@@ -59,6 +63,210 @@ pub fn rust_text_items_to_py_text_items(rust_text_items: &[TextItem]) -> PyResul
     })
 }
 
+/// Convert a Rust Catalog to a Python dictionary of country code to a dictionary of bank
+/// name to a list of dictionaries with keys "account_type", "key", "status" and
+/// "account_examples". Dictionaries are in the sorted order of the catalog.
+pub fn rust_catalog_to_py_catalog(catalog: &Catalog) -> PyResult<Py<PyAny>> {
+    Python::attach(|py| {
+        let py_countries = pyo3::types::PyDict::new(py);
+        for (country, banks) in &catalog.countries {
+            let py_banks = pyo3::types::PyDict::new(py);
+            for (bank_name, entries) in banks {
+                let py_entries = PyList::empty(py);
+                for entry in entries {
+                    let dict = pyo3::types::PyDict::new(py);
+                    dict.set_item("account_type", &entry.account_type)?;
+                    dict.set_item("key", &entry.key)?;
+                    dict.set_item("status", &entry.status)?;
+                    dict.set_item("account_examples", &entry.account_examples)?;
+                    py_entries.append(dict)?;
+                }
+                py_banks.set_item(bank_name, py_entries)?;
+            }
+            py_countries.set_item(country, py_banks)?;
+        }
+        Ok(py_countries.into())
+    })
+}
+
+/// Convert config summaries into a Python list of dictionaries with the "key",
+/// "bank_name", "account_type", "account_examples", "country", "status" and "source"
+/// ("embedded" or "user") of each config
+pub fn rust_config_summaries_to_py_list(summaries: &[ConfigSummary]) -> PyResult<Py<PyAny>> {
+    Python::attach(|py| {
+        let py_summaries = PyList::empty(py);
+        for summary in summaries {
+            let dict = pyo3::types::PyDict::new(py);
+            dict.set_item("key", &summary.key)?;
+            dict.set_item("bank_name", &summary.bank_name)?;
+            dict.set_item("account_type", &summary.account_type)?;
+            dict.set_item("account_examples", &summary.account_examples)?;
+            dict.set_item("country", &summary.country)?;
+            dict.set_item("status", &summary.status)?;
+            dict.set_item("source", &summary.source)?;
+            py_summaries.append(dict)?;
+        }
+        Ok(py_summaries.into())
+    })
+}
+
+/// Convert a Python dictionary of stage name to duration in seconds into Rust
+/// ParseTimings. Stages not in the dictionary are zero.
+pub fn py_timings_to_rust_parse_timings(
+    py_timings: &HashMap<String, f64>,
+) -> PyResult<ParseTimings> {
+    let mut timings = ParseTimings::new();
+    for (stage, seconds) in py_timings {
+        let duration = Duration::try_from_secs_f64(*seconds).map_err(|_| {
+            PyValueError::new_err(format!("Invalid duration for stage {}: {}", stage, seconds))
+        })?;
+        timings
+            .set_stage(stage, duration)
+            .map_err(PyValueError::new_err)?;
+    }
+    Ok(timings)
+}
+
+/// Convert Rust ParseTimings to a Python dictionary of stage name to duration
+/// in seconds, including the "total".
+pub fn rust_parse_timings_to_py_timings<'py>(
+    py: Python<'py>,
+    timings: &ParseTimings,
+) -> PyResult<Bound<'py, pyo3::types::PyDict>> {
+    let dict = pyo3::types::PyDict::new(py);
+    for (stage, duration) in timings.stages() {
+        dict.set_item(stage, duration.as_secs_f64())?;
+    }
+    dict.set_item("total", timings.total().as_secs_f64())?;
+    Ok(dict)
+}
+
+/// Maximum number of error strings included per config in an error breakdown
+pub const MAX_BREAKDOWN_ERRORS: usize = 3;
+
+/// Convert a list of failed Rust StatementData results to a Python list of
+/// dictionaries, one per attempted config, with keys "key", "config_status",
+/// "error_count", "errors" (the first few error strings) and "timings" (see
+/// rust_parse_timings_to_py_timings).
+pub fn rust_statement_datas_to_py_error_breakdown(
+    rust_statement_datas: &[crate::structs::StatementData],
+) -> PyResult<Py<PyAny>> {
+    Python::attach(|py| {
+        let py_list = PyList::empty(py);
+        for data in rust_statement_datas {
+            let dict = pyo3::types::PyDict::new(py);
+            dict.set_item("key", data.key.clone().unwrap_or_default())?;
+            dict.set_item(
+                "config_status",
+                data.config_status.clone().unwrap_or_default(),
+            )?;
+            dict.set_item("error_count", data.errors.len())?;
+            let errors: Vec<&String> = data.errors.iter().take(MAX_BREAKDOWN_ERRORS).collect();
+            dict.set_item("errors", errors)?;
+            dict.set_item(
+                "timings",
+                rust_parse_timings_to_py_timings(py, &data.timings)?,
+            )?;
+            py_list.append(dict)?;
+        }
+        Ok(py_list.into())
+    })
+}
+
+/// Convert Rust StatementData results to a Python list of candidate dictionaries,
+/// one per attempted config in the given order, including those with errors. Each
+/// has keys "key", "config_status", "passed", "errors" (all error strings),
+/// "warnings", "account_number" and the column lists "date", "description",
+/// "amount", "balance", "amount_source", "balance_source" and "category" of every
+/// transaction, with None for missing values ("" for untagged categories). The statement values are also under "metadata", a
+/// dict with keys "key", "account_number", "opening_balance", "closing_balance",
+/// "start_date" and "errors" (None where not found), so that the column lists can be
+/// passed to a DataFrame on their own.
+pub fn rust_statement_datas_to_py_candidates(
+    rust_statement_datas: &[crate::structs::StatementData],
+) -> PyResult<Py<PyAny>> {
+    Python::attach(|py| {
+        let date_class = py.import("datetime")?.getattr("date")?;
+        let py_list = PyList::empty(py);
+        for data in rust_statement_datas {
+            let dict = pyo3::types::PyDict::new(py);
+            dict.set_item("key", data.key.clone().unwrap_or_default())?;
+            dict.set_item(
+                "config_status",
+                data.config_status.clone().unwrap_or_default(),
+            )?;
+            dict.set_item("passed", data.errors.is_empty())?;
+            dict.set_item("errors", &data.errors)?;
+            dict.set_item("warnings", &data.warnings)?;
+            dict.set_item("account_number", data.account_number.clone())?;
+            let metadata = pyo3::types::PyDict::new(py);
+            metadata.set_item("key", data.key.clone())?;
+            metadata.set_item("account_number", data.account_number.clone())?;
+            metadata.set_item("opening_balance", data.opening_balance)?;
+            metadata.set_item("closing_balance", data.closing_balance)?;
+            match data.start_date.and_then(|t| data.to_naive_date(t)) {
+                Some(date) => metadata.set_item(
+                    "start_date",
+                    date_class.call1((date.year(), date.month(), date.day()))?,
+                )?,
+                None => metadata.set_item("start_date", py.None())?,
+            }
+            metadata.set_item("errors", &data.errors)?;
+            dict.set_item("metadata", metadata)?;
+            let dates = PyList::empty(py);
+            for proto_tx in &data.proto_transactions {
+                match proto_tx.date.and_then(|t| data.to_naive_date(t)) {
+                    Some(date) => dates.append(date_class.call1((
+                        date.year(),
+                        date.month(),
+                        date.day(),
+                    ))?)?,
+                    None => dates.append(py.None())?,
+                }
+            }
+            dict.set_item("date", dates)?;
+            let descriptions: Vec<&String> = data
+                .proto_transactions
+                .iter()
+                .map(|proto_tx| &proto_tx.description)
+                .collect();
+            dict.set_item("description", descriptions)?;
+            let amounts: Vec<Option<f64>> = data
+                .proto_transactions
+                .iter()
+                .map(|proto_tx| proto_tx.amount)
+                .collect();
+            dict.set_item("amount", amounts)?;
+            let balances: Vec<Option<f64>> = data
+                .proto_transactions
+                .iter()
+                .map(|proto_tx| proto_tx.balance)
+                .collect();
+            dict.set_item("balance", balances)?;
+            let amount_sources: Vec<Option<&str>> = data
+                .proto_transactions
+                .iter()
+                .map(|proto_tx| proto_tx.amount.map(|_| proto_tx.amount_source()))
+                .collect();
+            dict.set_item("amount_source", amount_sources)?;
+            let balance_sources: Vec<Option<&str>> = data
+                .proto_transactions
+                .iter()
+                .map(|proto_tx| proto_tx.balance.map(|_| proto_tx.balance_source()))
+                .collect();
+            dict.set_item("balance_source", balance_sources)?;
+            let categories: Vec<&str> = data
+                .proto_transactions
+                .iter()
+                .map(|proto_tx| proto_tx.category.as_deref().unwrap_or_default())
+                .collect();
+            dict.set_item("category", categories)?;
+            py_list.append(dict)?;
+        }
+        Ok(py_list.into())
+    })
+}
+
 /// Convert a Rust StatementData to a Python StatementData object
 pub fn rust_statement_data_to_py_statement_data(
     rust_statement_data: &crate::structs::StatementData,
@@ -70,6 +278,7 @@ pub fn rust_statement_data_to_py_statement_data(
 
         let transaction_module = py.import("transtractor.structs.transaction")?;
         let transaction_class = transaction_module.getattr("Transaction")?;
+        let date_class = py.import("datetime")?.getattr("date")?;
 
         // Get key (required field)
         let key = rust_statement_data.key.as_ref().ok_or_else(|| {
@@ -96,14 +305,30 @@ pub fn rust_statement_data_to_py_statement_data(
                 )));
             }
 
-            // Create Python Transaction object
-            // Transaction.__init__(date: int, description: str, amount: float, balance: float)
+            // Create Python Transaction object, with the date taken in the statement's
+            // date timezone so that it is the same calendar date for any timezone
+            // Transaction.__init__(date: date, date_index: int, description: str,
+            //                      amount: float, balance: float, timestamp: int,
+            //                      amount_source: str, balance_source: str,
+            //                      description_source: str, category: str)
+            let timestamp = proto_tx.date.unwrap();
+            let date = rust_statement_data
+                .to_naive_date(timestamp)
+                .ok_or_else(|| {
+                    PyRuntimeError::new_err(format!("Invalid transaction timestamp: {}", timestamp))
+                })?;
+            let py_date = date_class.call1((date.year(), date.month(), date.day()))?;
             let py_transaction = transaction_class.call1((
-                proto_tx.date.unwrap(),
+                py_date,
                 proto_tx.index,
                 proto_tx.description.clone(),
                 proto_tx.amount.unwrap(),
                 proto_tx.balance.unwrap(),
+                timestamp,
+                proto_tx.amount_source(),
+                proto_tx.balance_source(),
+                proto_tx.description_source(),
+                proto_tx.category.clone().unwrap_or_default(),
             ))?;
 
             py_transactions.append(py_transaction)?;
@@ -115,6 +340,88 @@ pub fn rust_statement_data_to_py_statement_data(
         let py_statement_data =
             statement_data_class.call1((key, account_number, py_transactions))?;
 
+        // Attach parse metadata: warnings and per-page coverage statistics
+        let metadata = pyo3::types::PyDict::new(py);
+        let py_page_coverage = PyList::empty(py);
+        for coverage in &rust_statement_data.page_coverage {
+            let dict = pyo3::types::PyDict::new(py);
+            dict.set_item("page", coverage.page)?;
+            dict.set_item("text_items", coverage.text_items)?;
+            dict.set_item("consumed_items", coverage.consumed_items)?;
+            dict.set_item("transactions", coverage.transactions)?;
+            py_page_coverage.append(dict)?;
+        }
+        metadata.set_item("page_coverage", py_page_coverage)?;
+        metadata.set_item("warnings", &rust_statement_data.warnings)?;
+        metadata.set_item(
+            "balances_computed",
+            rust_statement_data.computed_balance_count(),
+        )?;
+        metadata.set_item(
+            "amounts_inferred",
+            rust_statement_data.inferred_amount_count(),
+        )?;
+        metadata.set_item(
+            "config_status",
+            rust_statement_data
+                .config_status
+                .clone()
+                .unwrap_or_default(),
+        )?;
+        metadata.set_item(
+            "fix_text_order_applied",
+            rust_statement_data.fix_text_order_applied,
+        )?;
+        metadata.set_item(
+            "no_transactions_declared",
+            rust_statement_data.no_transactions_declared,
+        )?;
+        metadata.set_item("coordinate_scale", rust_statement_data.coordinate_scale)?;
+        metadata.set_item("account_section", rust_statement_data.account_section)?;
+        metadata.set_item("statement_section", rust_statement_data.statement_section)?;
+        metadata.set_item("currency", &rust_statement_data.currency)?;
+        // Statement dates are taken in the statement's date timezone, as for transactions
+        let to_py_date = |timestamp: Option<i64>, name: &str| -> PyResult<Option<Bound<PyAny>>> {
+            match timestamp {
+                Some(timestamp) => {
+                    let date = rust_statement_data
+                        .to_naive_date(timestamp)
+                        .ok_or_else(|| {
+                            PyRuntimeError::new_err(format!(
+                                "Invalid {} timestamp: {}",
+                                name, timestamp
+                            ))
+                        })?;
+                    Ok(Some(date_class.call1((
+                        date.year(),
+                        date.month(),
+                        date.day(),
+                    ))?))
+                }
+                None => Ok(None),
+            }
+        };
+        metadata.set_item("opening_balance", rust_statement_data.opening_balance)?;
+        metadata.set_item("closing_balance", rust_statement_data.closing_balance)?;
+        metadata.set_item(
+            "start_date",
+            to_py_date(rust_statement_data.start_date, "start date")?,
+        )?;
+        metadata.set_item(
+            "end_date",
+            to_py_date(rust_statement_data.end_date, "end date")?,
+        )?;
+        metadata.set_item("minimum_payment", rust_statement_data.minimum_payment)?;
+        metadata.set_item(
+            "payment_due_date",
+            to_py_date(rust_statement_data.payment_due_date, "payment due date")?,
+        )?;
+        metadata.set_item(
+            "timings",
+            rust_parse_timings_to_py_timings(py, &rust_statement_data.timings)?,
+        )?;
+        py_statement_data.call_method1("set_metadata", (metadata,))?;
+
         Ok(py_statement_data.into())
     })
 }
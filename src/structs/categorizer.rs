@@ -0,0 +1,145 @@
+use crate::structs::StatementData;
+use regex::{Regex, RegexBuilder};
+use serde_json::Value;
+
+/// Tagging of transactions with a category (e.g., "groceries") by patterns of their
+/// descriptions. Rules are tried in order, and the first whose pattern matches the
+/// description sets the category, so more specific rules go first.
+///
+/// Applied after parsing, fixing and checking, to the descriptions as read.
+#[derive(Debug, Clone, Default)]
+pub struct Categorizer {
+    /// Category and description pattern of each rule, in order
+    pub rules: Vec<(String, Regex)>,
+}
+
+impl Categorizer {
+    /// Create a categorizer with the (category, regex pattern) rules, matching case
+    /// insensitively if set. Fails if a category is empty or a pattern is invalid.
+    pub fn new(rules: &[(String, String)], case_insensitive: bool) -> Result<Self, String> {
+        let rules = rules
+            .iter()
+            .map(|(category, pattern)| {
+                if category.trim().is_empty() {
+                    return Err(format!("Empty category for pattern '{}'", pattern));
+                }
+                let regex = RegexBuilder::new(pattern)
+                    .case_insensitive(case_insensitive)
+                    .build()
+                    .map_err(|e| format!("Invalid category pattern '{}': {}", pattern, e))?;
+                Ok((category.clone(), regex))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Create a categorizer from a JSON array of [category, pattern] pairs, like
+    /// [["groceries", "WOOLWORTHS|COLES"], ["fuel", "AMPOL|BP "]], as for new
+    pub fn from_json_str(json_str: &str, case_insensitive: bool) -> Result<Self, String> {
+        let value: Value =
+            serde_json::from_str(json_str).map_err(|e| format!("JSON parse error: {}", e))?;
+        let pairs = value
+            .as_array()
+            .ok_or("Categories must be a JSON array of [category, pattern] pairs")?;
+        let rules = pairs
+            .iter()
+            .map(|pair| match pair.as_array().map(Vec::as_slice) {
+                Some([Value::String(category), Value::String(pattern)]) => {
+                    Ok((category.clone(), pattern.clone()))
+                }
+                _ => Err(format!(
+                    "Invalid category rule {}: must be a [category, pattern] pair of strings",
+                    pair
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(&rules, case_insensitive)
+    }
+
+    /// Get the category of the first rule matching the description, if any
+    pub fn category(&self, description: &str) -> Option<&str> {
+        self.rules
+            .iter()
+            .find(|(_, pattern)| pattern.is_match(description))
+            .map(|(category, _)| category.as_str())
+    }
+
+    /// Set the category of every transaction of the statement data, None if no rule
+    /// matches
+    pub fn apply(&self, sd: &mut StatementData) {
+        for tx in &mut sd.proto_transactions {
+            tx.category = self.category(&tx.description).map(str::to_string);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::ProtoTransaction;
+
+    fn rules() -> Vec<(String, String)> {
+        vec![
+            ("salary".to_string(), r"^SALARY\b".to_string()),
+            ("fuel".to_string(), "AMPOL|SHELL".to_string()),
+            ("groceries".to_string(), "WOOLWORTHS|COLES".to_string()),
+            ("shopping".to_string(), "WOOLWORTHS".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_first_matching_rule_wins() {
+        let categorizer = Categorizer::new(&rules(), false).unwrap();
+        assert_eq!(categorizer.category("SALARY ACME PTY LTD"), Some("salary"));
+        assert_eq!(categorizer.category("WOOLWORTHS 1234"), Some("groceries"));
+        assert_eq!(categorizer.category("Woolworths 1234"), None);
+        assert_eq!(categorizer.category("RENT PAYMENT"), None);
+
+        let categorizer = Categorizer::new(&rules(), true).unwrap();
+        assert_eq!(categorizer.category("Woolworths 1234"), Some("groceries"));
+        assert_eq!(categorizer.category("Shell Coles Express"), Some("fuel"));
+    }
+
+    #[test]
+    fn test_apply_sets_category_of_every_transaction() {
+        let mut sd = StatementData::new();
+        for description in ["SALARY ACME", "COLES 5678", "RENT"] {
+            let mut tx = ProtoTransaction::new();
+            tx.description = description.to_string();
+            tx.category = Some("stale".to_string());
+            sd.add_proto_transaction(tx);
+        }
+
+        Categorizer::new(&rules(), true).unwrap().apply(&mut sd);
+
+        let categories: Vec<Option<&str>> = sd
+            .proto_transactions
+            .iter()
+            .map(|tx| tx.category.as_deref())
+            .collect();
+        assert_eq!(categories, vec![Some("salary"), Some("groceries"), None]);
+    }
+
+    #[test]
+    fn test_from_json_str() {
+        let categorizer =
+            Categorizer::from_json_str(r#"[["fuel", "ampol"], ["groceries", "coles"]]"#, true)
+                .unwrap();
+        assert_eq!(categorizer.category("AMPOL FOODARY"), Some("fuel"));
+
+        for (json_str, error) in [
+            (r#"{"fuel": "ampol"}"#, "must be a JSON array"),
+            (r#"[["fuel"]]"#, "Invalid category rule [\"fuel\"]"),
+            (r#"[["fuel", "("]]"#, "Invalid category pattern '('"),
+            (r#"[[" ", "ampol"]]"#, "Empty category for pattern 'ampol'"),
+        ] {
+            let result = Categorizer::from_json_str(json_str, true);
+            assert!(
+                result.as_ref().is_err_and(|e| e.contains(error)),
+                "{}: {:?}",
+                json_str,
+                result.map(|c| c.rules.len())
+            );
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::structs::TextItem;
+
+/// Record of a contiguous range of text items claimed by a parser while
+/// scanning a statement. Each range is normally claimed by a single parser;
+/// a statement-level parser and the transaction parser claiming the same
+/// items points to a config bug (e.g. the opening balance also lying within
+/// the transaction table columns).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemClaim {
+    /// Name of the claiming parser (e.g. "opening_balance", "transaction_amount")
+    pub parser: String,
+    /// Index of the first claimed text item
+    pub start: usize,
+    /// Index after the last claimed text item
+    pub end: usize,
+    /// Page of the first claimed text item
+    pub page: i32,
+    /// Text of the claimed items, space separated
+    pub text: String,
+}
+
+impl ItemClaim {
+    /// Create a claim by a parser over text_items[start..end].
+    pub fn new(parser: &str, text_items: &[TextItem], start: usize, end: usize) -> Self {
+        let claimed = &text_items[start..end];
+        Self {
+            parser: parser.to_string(),
+            start,
+            end,
+            page: claimed.first().map(|item| item.page).unwrap_or(0),
+            text: claimed
+                .iter()
+                .map(|item| item.text.as_str())
+                .collect::<Vec<&str>>()
+                .join(" "),
+        }
+    }
+
+    /// Whether this claim shares any text items with another claim.
+    pub fn overlaps(&self, other: &ItemClaim) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+
+    /// Whether the claim was made by the transaction parser.
+    pub fn is_transaction(&self) -> bool {
+        self.parser.starts_with("transaction")
+    }
+}
@@ -0,0 +1,185 @@
+use crate::structs::TextItem;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+/// Minimum number of letters for the script of a document to be judged
+const MIN_LETTERS: usize = 200;
+
+/// Minimum share of letters in a single script for it to be the document script
+const MIN_SCRIPT_SHARE: f64 = 0.95;
+
+/// Currency symbols indicative of a locale
+const CURRENCY_SYMBOLS: [char; 5] = ['$', '€', '£', '₹', '¥'];
+
+/// Writing script of letters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Script {
+    Latin,
+    Cjk,
+    Arabic,
+    Cyrillic,
+    Other,
+}
+
+impl Script {
+    fn of(c: char) -> Script {
+        match c as u32 {
+            0x0041..=0x024F | 0x1E00..=0x1EFF => Script::Latin,
+            0x0400..=0x052F => Script::Cyrillic,
+            0x0600..=0x06FF | 0x0750..=0x077F | 0xFB50..=0xFDFF | 0xFE70..=0xFEFF => Script::Arabic,
+            0x1100..=0x11FF
+            | 0x3040..=0x30FF
+            | 0x3130..=0x318F
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xAC00..=0xD7AF => Script::Cjk,
+            _ => Script::Other,
+        }
+    }
+
+    /// Script of the statements of a country (ISO 3166-1 alpha-2 code, any case)
+    pub fn of_country(country: &str) -> Script {
+        match country.to_uppercase().as_str() {
+            "CN" | "HK" | "JP" | "KR" | "MO" | "TW" => Script::Cjk,
+            "AE" | "BH" | "DZ" | "EG" | "IQ" | "JO" | "KW" | "LB" | "LY" | "MA" | "OM" | "QA"
+            | "SA" | "SY" | "TN" | "YE" => Script::Arabic,
+            "BG" | "BY" | "KG" | "KZ" | "MK" | "MN" | "RS" | "RU" | "TJ" | "UA" => Script::Cyrillic,
+            _ => Script::Latin,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Script::Latin => "Latin",
+            Script::Cjk => "CJK",
+            Script::Arabic => "Arabic",
+            Script::Cyrillic => "Cyrillic",
+            Script::Other => "Other",
+        }
+    }
+}
+
+/// Amounts with a decimal comma (e.g., "1.234,56") or point (e.g., "1,234.56")
+static DECIMAL_COMMA: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^-?\d{1,3}(\.\d{3})*,\d{2}$").unwrap());
+static DECIMAL_POINT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^-?\d{1,3}(,\d{3})*\.\d{2}$").unwrap());
+
+/// Cheap statistics of the language and locale of a document's text items: the number
+/// of letters in each script, the locale-indicative currency symbols and the number of
+/// amounts written with a decimal comma or decimal point.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LocaleProfile {
+    pub script_counts: BTreeMap<Script, usize>,
+    pub currency_symbols: BTreeMap<char, usize>,
+    pub decimal_commas: usize,
+    pub decimal_points: usize,
+}
+
+impl LocaleProfile {
+    pub fn from_text_items(text_items: &[TextItem]) -> Self {
+        let mut profile = LocaleProfile::default();
+        for item in text_items {
+            for c in item.text.chars() {
+                if CURRENCY_SYMBOLS.contains(&c) {
+                    *profile.currency_symbols.entry(c).or_insert(0) += 1;
+                } else if c.is_alphabetic() {
+                    *profile.script_counts.entry(Script::of(c)).or_insert(0) += 1;
+                }
+            }
+            for word in item.text.split_whitespace() {
+                let word = word.trim_start_matches(CURRENCY_SYMBOLS);
+                if DECIMAL_COMMA.is_match(word) {
+                    profile.decimal_commas += 1;
+                } else if DECIMAL_POINT.is_match(word) {
+                    profile.decimal_points += 1;
+                }
+            }
+        }
+        profile
+    }
+
+    /// Script of the document, if there are enough letters and almost all of them are
+    /// in one script. None if uncertain.
+    pub fn script(&self) -> Option<Script> {
+        let letters: usize = self.script_counts.values().sum();
+        if letters < MIN_LETTERS {
+            return None;
+        }
+        self.script_counts
+            .iter()
+            .find(|(script, count)| {
+                **script != Script::Other && **count as f64 >= MIN_SCRIPT_SHARE * letters as f64
+            })
+            .map(|(script, _)| *script)
+    }
+
+    /// Most common locale-indicative currency symbol of the document, if any. Ties are
+    /// broken by the order of the symbols in the map.
+    pub fn currency_symbol(&self) -> Option<char> {
+        let mut best: Option<(char, usize)> = None;
+        for (symbol, count) in &self.currency_symbols {
+            if best.is_none_or(|(_, best_count)| *count > best_count) {
+                best = Some((*symbol, *count));
+            }
+        }
+        best.map(|(symbol, _)| symbol)
+    }
+
+    /// Returns false only if the country of a config key (e.g., "cn" of
+    /// "cn__bank__savings__1") is implausible for the document: its statements are in
+    /// a different script from the document's. Always true if the script is uncertain.
+    pub fn is_plausible_key(&self, key: &str) -> bool {
+        let Some(script) = self.script() else {
+            return true;
+        };
+        let country = key.split("__").next().unwrap_or_default();
+        Script::of_country(country) == script
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_items(text: &str, repeat: usize) -> Vec<TextItem> {
+        (0..repeat)
+            .map(|i| TextItem::new(text.to_string(), 0, i as i32 * 10, 50, i as i32 * 10 + 8, 0))
+            .collect()
+    }
+
+    #[test]
+    fn test_latin_statement_profile() {
+        let mut items = make_items("Opening balance $1,234.56", 20);
+        items.extend(make_items("€12,50", 1));
+
+        let profile = LocaleProfile::from_text_items(&items);
+
+        assert_eq!(profile.script(), Some(Script::Latin));
+        assert_eq!(profile.currency_symbols.get(&'$'), Some(&20));
+        assert_eq!(profile.currency_symbols.get(&'€'), Some(&1));
+        assert_eq!((profile.decimal_points, profile.decimal_commas), (20, 1));
+        assert!(profile.is_plausible_key("au__cba__savings__1"));
+        assert!(!profile.is_plausible_key("cn__icbc__savings__1"));
+        assert!(!profile.is_plausible_key("ru__sber__savings__1"));
+    }
+
+    #[test]
+    fn test_uncertain_profile_keeps_all_keys() {
+        let profile = LocaleProfile::from_text_items(&make_items("Balance", 3));
+
+        assert_eq!(profile.script(), None);
+        assert!(profile.is_plausible_key("cn__icbc__savings__1"));
+    }
+
+    #[test]
+    fn test_mixed_script_profile_is_uncertain() {
+        let mut items = make_items("Opening balance", 15);
+        items.extend(make_items("期初余额期初余额", 5));
+
+        let profile = LocaleProfile::from_text_items(&items);
+
+        assert_eq!(profile.script(), None);
+    }
+}
@@ -1,12 +1,32 @@
+pub mod categorizer;
+pub mod item_claim;
+pub mod locale_profile;
+pub mod money;
+pub mod orphan_line;
+pub mod page_coverage;
+pub mod parse_timings;
 pub mod proto_transaction;
+pub mod redaction;
 pub mod statement_config;
 pub mod statement_data;
+pub mod table_lines;
 pub mod text_item;
+pub mod text_item_cache;
 pub mod text_items;
 pub mod transaction;
 
+pub use categorizer::Categorizer;
+pub use item_claim::ItemClaim;
+pub use locale_profile::{LocaleProfile, Script};
+pub use money::Money;
+pub use orphan_line::OrphanLine;
+pub use page_coverage::PageCoverage;
+pub use parse_timings::ParseTimings;
 pub use proto_transaction::ProtoTransaction;
-pub use statement_config::StatementConfig;
+pub use redaction::Redaction;
+pub use statement_config::{StatementConfig, TransactionColumnPosition};
 pub use statement_data::StatementData;
+pub use table_lines::TableLines;
 pub use text_item::TextItem;
+pub use text_item_cache::{ContentKey, TextItemCache};
 pub use transaction::Transaction;
@@ -0,0 +1,115 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Neg, Sub};
+
+/// Amount of money in integer cents. Amounts and balances are stored as f64, as read
+/// by the amount formats and written to Python, JSON and CSV, but are summed and
+/// compared in cents so that the balance checks and fixers are exact however many
+/// transactions are summed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    pub fn from_cents(cents: i64) -> Self {
+        Money(cents)
+    }
+
+    /// Nearest amount in cents of an f64 amount (e.g., 999.899999 is 999.90)
+    pub fn from_f64(value: f64) -> Self {
+        Money((value * 100.0).round() as i64)
+    }
+
+    pub fn cents(self) -> i64 {
+        self.0
+    }
+
+    /// Amount as the f64 nearest to its value, as parsed from the same amount text
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / 100.0
+    }
+
+    pub fn abs(self) -> Self {
+        Money(self.0.abs())
+    }
+
+    /// Check if the amounts are at most the tolerance apart
+    pub fn within(self, other: Money, tolerance: Money) -> bool {
+        (self - other).abs() <= tolerance
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        Money(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        self.0 += other.0;
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        Money(self.0 - other.0)
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money(-self.0)
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        Money(iter.map(|money| money.0).sum())
+    }
+}
+
+/// Format with 2 decimal places, as "{:.2}" formats the f64 amount
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        write!(
+            f,
+            "{}{}.{:02}",
+            sign,
+            self.0.unsigned_abs() / 100,
+            self.0.unsigned_abs() % 100
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_money_sums_are_exact() {
+        // 0.1 added 1000 times drifts from 100.0 as f64, but not in cents
+        let float_sum: f64 = (0..1000).map(|_| 0.1).sum();
+        assert_ne!(float_sum, 100.0);
+        let sum: Money = (0..1000).map(|_| Money::from_f64(0.1)).sum();
+        assert_eq!(sum, Money::from_cents(10_000));
+        assert_eq!(sum.to_f64(), 100.0);
+        assert_eq!(Money::from_f64(999.899999), Money::from_cents(99_990));
+    }
+
+    #[test]
+    fn test_money_display() {
+        assert_eq!(Money::from_cents(123_456).to_string(), "1234.56");
+        assert_eq!(Money::from_cents(-5).to_string(), "-0.05");
+        assert_eq!(Money::ZERO.to_string(), "0.00");
+        assert_eq!((-Money::from_f64(20.5)).to_string(), "-20.50");
+    }
+}
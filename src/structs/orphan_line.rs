@@ -0,0 +1,68 @@
+use crate::structs::TextItem;
+use crate::structs::table_lines::SAME_LINE_TOL;
+use std::fmt;
+
+/// Printed line of the transaction table with text not read by any parser (e.g., a
+/// repeated page header, or a row whose values are outside the column bounds)
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrphanLine {
+    pub page: i32,
+    /// Position of the first unread item of the line
+    pub x1: i32,
+    pub y1: i32,
+    /// Unread text of the line, space-separated
+    pub text: String,
+}
+
+impl fmt::Display for OrphanLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "page {} at ({}, {}): \"{}\"",
+            self.page, self.x1, self.y1, self.text
+        )
+    }
+}
+
+/// Group unread text items of the transaction table, in parse order, into the printed
+/// lines they are on
+pub fn get_orphan_lines(items: &[TextItem]) -> Vec<OrphanLine> {
+    let mut lines: Vec<OrphanLine> = Vec::new();
+    for item in items {
+        match lines.last_mut() {
+            Some(line) if line.page == item.page && (item.y1 - line.y1).abs() <= SAME_LINE_TOL => {
+                line.text.push(' ');
+                line.text.push_str(&item.text);
+            }
+            _ => lines.push(OrphanLine {
+                page: item.page,
+                x1: item.x1,
+                y1: item.y1,
+                text: item.text.clone(),
+            }),
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_orphan_lines() {
+        let items = vec![
+            TextItem::new("Page".to_string(), 10, 100, 30, 92, 0),
+            TextItem::new("2".to_string(), 32, 101, 36, 93, 0),
+            TextItem::new("Carried".to_string(), 10, 140, 40, 132, 0),
+            TextItem::new("Page".to_string(), 10, 100, 30, 92, 1),
+        ];
+
+        let lines = get_orphan_lines(&items);
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].to_string(), "page 0 at (10, 100): \"Page 2\"");
+        assert_eq!(lines[1].text, "Carried");
+        assert_eq!(lines[2].page, 1);
+    }
+}
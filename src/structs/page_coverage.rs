@@ -0,0 +1,135 @@
+use crate::structs::{ItemClaim, ProtoTransaction, TextItem};
+use std::collections::BTreeMap;
+
+/// Parse coverage statistics for a single page of a statement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageCoverage {
+    /// Page number, as in the text items
+    pub page: i32,
+    /// Number of text items on the page
+    pub text_items: usize,
+    /// Number of text items on the page consumed by any parser
+    pub consumed_items: usize,
+    /// Number of transactions that started on the page
+    pub transactions: usize,
+}
+
+impl PageCoverage {
+    pub fn new(page: i32) -> Self {
+        Self {
+            page,
+            text_items: 0,
+            consumed_items: 0,
+            transactions: 0,
+        }
+    }
+}
+
+/// Get parse coverage statistics for each page of the text items, ordered by page.
+pub fn get_page_coverage(
+    text_items: &[TextItem],
+    claims: &[ItemClaim],
+    transactions: &[ProtoTransaction],
+) -> Vec<PageCoverage> {
+    let mut consumed = vec![false; text_items.len()];
+    for claim in claims {
+        for flag in consumed
+            .iter_mut()
+            .take(claim.end.min(text_items.len()))
+            .skip(claim.start)
+        {
+            *flag = true;
+        }
+    }
+
+    let mut pages: BTreeMap<i32, PageCoverage> = BTreeMap::new();
+    for (item, is_consumed) in text_items.iter().zip(consumed) {
+        let coverage = pages
+            .entry(item.page)
+            .or_insert_with(|| PageCoverage::new(item.page));
+        coverage.text_items += 1;
+        if is_consumed {
+            coverage.consumed_items += 1;
+        }
+    }
+    for page in transactions.iter().filter_map(|tx| tx.page) {
+        pages
+            .entry(page)
+            .or_insert_with(|| PageCoverage::new(page))
+            .transactions += 1;
+    }
+    pages.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_text_item(text: &str, page: i32) -> TextItem {
+        TextItem::new(text.to_string(), 0, 0, 10, 10, page)
+    }
+
+    fn make_transaction(page: Option<i32>) -> ProtoTransaction {
+        ProtoTransaction {
+            page,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_get_page_coverage() {
+        let items = vec![
+            make_text_item("Opening", 0),
+            make_text_item("balance", 0),
+            make_text_item("Payment", 1),
+            make_text_item("10.00", 1),
+            make_text_item("Footer", 1),
+            make_text_item("Terms", 2),
+        ];
+        let claims = vec![
+            ItemClaim::new("opening_balance", &items, 0, 2),
+            ItemClaim::new("transaction_description", &items, 2, 3),
+            ItemClaim::new("transaction_amount", &items, 3, 4),
+        ];
+        let transactions = vec![make_transaction(Some(1)), make_transaction(None)];
+
+        let coverage = get_page_coverage(&items, &claims, &transactions);
+
+        assert_eq!(
+            coverage,
+            vec![
+                PageCoverage {
+                    page: 0,
+                    text_items: 2,
+                    consumed_items: 2,
+                    transactions: 0,
+                },
+                PageCoverage {
+                    page: 1,
+                    text_items: 3,
+                    consumed_items: 2,
+                    transactions: 1,
+                },
+                PageCoverage {
+                    page: 2,
+                    text_items: 1,
+                    consumed_items: 0,
+                    transactions: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_page_coverage_overlapping_claims_counted_once() {
+        let items = vec![make_text_item("1,000.00", 0)];
+        let claims = vec![
+            ItemClaim::new("opening_balance", &items, 0, 1),
+            ItemClaim::new("transaction_amount", &items, 0, 1),
+        ];
+
+        let coverage = get_page_coverage(&items, &claims, &[]);
+
+        assert_eq!(coverage[0].consumed_items, 1);
+    }
+}
@@ -0,0 +1,166 @@
+use std::fmt;
+use std::time::Duration;
+
+/// Cumulative durations of each stage of parsing a statement with a single config.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParseTimings {
+    /// Extracting text items from the PDF (or layout) file
+    pub text_extraction: Duration,
+    /// Identifying the statement configs applicable to the text items
+    pub statement_typing: Duration,
+    /// Sorting (fix_text_order) and tokenising the text items
+    pub text_ordering: Duration,
+    /// Account number parser
+    pub account_number: Duration,
+    /// Opening balance parser
+    pub opening_balance: Duration,
+    /// Closing balance parser
+    pub closing_balance: Duration,
+    /// Start date parser
+    pub start_date: Duration,
+    /// End date parser
+    pub end_date: Duration,
+    /// Minimum payment parser
+    pub minimum_payment: Duration,
+    /// Payment due date parser
+    pub payment_due_date: Duration,
+    /// Transaction parser loop
+    pub transactions: Duration,
+    /// Fixers applied after parsing
+    pub fixers: Duration,
+    /// Checkers applied after fixing
+    pub checkers: Duration,
+}
+
+impl ParseTimings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the name and duration of each stage, in parse order
+    pub fn stages(&self) -> Vec<(&'static str, Duration)> {
+        vec![
+            ("text_extraction", self.text_extraction),
+            ("statement_typing", self.statement_typing),
+            ("text_ordering", self.text_ordering),
+            ("account_number", self.account_number),
+            ("opening_balance", self.opening_balance),
+            ("closing_balance", self.closing_balance),
+            ("start_date", self.start_date),
+            ("end_date", self.end_date),
+            ("minimum_payment", self.minimum_payment),
+            ("payment_due_date", self.payment_due_date),
+            ("transactions", self.transactions),
+            ("fixers", self.fixers),
+            ("checkers", self.checkers),
+        ]
+    }
+
+    /// Set the duration of a stage by name. Returns an error for an unknown stage.
+    pub fn set_stage(&mut self, stage: &str, duration: Duration) -> Result<(), String> {
+        let field = match stage {
+            "text_extraction" => &mut self.text_extraction,
+            "statement_typing" => &mut self.statement_typing,
+            "text_ordering" => &mut self.text_ordering,
+            "account_number" => &mut self.account_number,
+            "opening_balance" => &mut self.opening_balance,
+            "closing_balance" => &mut self.closing_balance,
+            "start_date" => &mut self.start_date,
+            "end_date" => &mut self.end_date,
+            "minimum_payment" => &mut self.minimum_payment,
+            "payment_due_date" => &mut self.payment_due_date,
+            "transactions" => &mut self.transactions,
+            "fixers" => &mut self.fixers,
+            "checkers" => &mut self.checkers,
+            _ => return Err(format!("Unknown parse stage: {}", stage)),
+        };
+        *field = duration;
+        Ok(())
+    }
+
+    /// Add the durations of another set of timings to these, stage by stage
+    pub fn add(&mut self, other: &ParseTimings) {
+        self.text_extraction += other.text_extraction;
+        self.statement_typing += other.statement_typing;
+        self.text_ordering += other.text_ordering;
+        self.account_number += other.account_number;
+        self.opening_balance += other.opening_balance;
+        self.closing_balance += other.closing_balance;
+        self.start_date += other.start_date;
+        self.end_date += other.end_date;
+        self.minimum_payment += other.minimum_payment;
+        self.payment_due_date += other.payment_due_date;
+        self.transactions += other.transactions;
+        self.fixers += other.fixers;
+        self.checkers += other.checkers;
+    }
+
+    /// Total duration of all stages
+    pub fn total(&self) -> Duration {
+        self.stages().iter().map(|(_, duration)| *duration).sum()
+    }
+}
+
+impl fmt::Display for ParseTimings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (stage, duration) in self.stages() {
+            writeln!(
+                f,
+                "    {}: {:.3} ms",
+                stage,
+                duration.as_secs_f64() * 1000.0
+            )?;
+        }
+        writeln!(
+            f,
+            "    total: {:.3} ms",
+            self.total().as_secs_f64() * 1000.0
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_and_total() {
+        let mut timings = ParseTimings::new();
+        timings.transactions = Duration::from_millis(5);
+        let mut other = ParseTimings::new();
+        other.transactions = Duration::from_millis(3);
+        other.fixers = Duration::from_millis(2);
+
+        timings.add(&other);
+
+        assert_eq!(timings.transactions, Duration::from_millis(8));
+        assert_eq!(timings.fixers, Duration::from_millis(2));
+        assert_eq!(timings.total(), Duration::from_millis(10));
+    }
+
+    #[test]
+    fn test_set_stage() {
+        let mut timings = ParseTimings::new();
+        timings
+            .set_stage("text_extraction", Duration::from_millis(7))
+            .unwrap();
+
+        assert_eq!(timings.text_extraction, Duration::from_millis(7));
+        assert!(
+            timings
+                .set_stage("unknown", Duration::from_millis(1))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let mut timings = ParseTimings::new();
+        timings.checkers = Duration::from_micros(1500);
+
+        let text = timings.to_string();
+
+        assert!(text.contains("    checkers: 1.500 ms\n"));
+        assert!(text.ends_with("    total: 1.500 ms\n"));
+    }
+}
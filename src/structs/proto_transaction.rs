@@ -1,3 +1,5 @@
+use crate::structs::Money;
+use crate::structs::TextItem;
 use crate::structs::transaction::Transaction;
 use regex::Regex;
 /// Represents an incomplete transaction.
@@ -14,6 +16,27 @@ pub struct ProtoTransaction {
     pub amount: Option<f64>,
     /// Balance after the transaction
     pub balance: Option<f64>,
+    /// Page on which the transaction starts
+    pub page: Option<i32>,
+    /// Vertical position (y1) of the first item read into the transaction, on its page
+    pub y1: Option<i32>,
+    /// The description may be empty (see transaction_description_optional)
+    pub description_optional: bool,
+    /// The sign of the amount was inferred from the balances, reversing the amount
+    /// read from the statement (see fix_amounts and fix_opening_balance)
+    pub amount_inferred: bool,
+    /// The balance was computed from the previous balance and the amount, not read
+    /// from the statement (see fix_implicit_balances)
+    pub balance_computed: bool,
+    /// Fields manually corrected after parsing ("description", "amount" or "balance",
+    /// see apply_csv_corrections)
+    pub corrected_fields: Vec<String>,
+    /// Category of the first matching rule of the categorizer, if any (see
+    /// Categorizer)
+    pub category: Option<String>,
+    /// Text items read into the fields of the transaction, in parse order, if recorded
+    /// (see TransactionParser::with_source_items)
+    pub source_items: Vec<TextItem>,
 }
 
 impl ProtoTransaction {
@@ -22,12 +45,18 @@ impl ProtoTransaction {
         Self::default()
     }
 
-    /// Returns true if all required fields are present and description is not empty.
+    /// Returns true if all required fields are present and description is not empty,
+    /// unless the description is optional.
     pub fn is_ready(&self) -> bool {
         self.date.is_some()
             && self.amount.is_some()
             && self.balance.is_some()
-            && !self.description.is_empty()
+            && self.has_description()
+    }
+
+    /// Returns true if the description is set, or is optional.
+    pub fn has_description(&self) -> bool {
+        self.description_optional || !self.description.is_empty()
     }
 
     /// Converts to a Transaction if all fields are present.
@@ -35,12 +64,23 @@ impl ProtoTransaction {
         if !self.is_ready() {
             return Err("Cannot convert to Transaction: fields are missing".to_string());
         }
-        Ok(Transaction::new(
-            self.date.unwrap(),
-            self.description.clone(),
-            self.amount.unwrap(),
-            self.balance.unwrap(),
-        ))
+        Ok(Transaction {
+            category: self.category.clone(),
+            ..Transaction::new(
+                self.date.unwrap(),
+                self.description.clone(),
+                self.amount.unwrap(),
+                self.balance.unwrap(),
+            )
+        })
+    }
+
+    /// Returns true if any field has been set.
+    pub fn has_any_field_set(&self) -> bool {
+        self.date.is_some()
+            || self.amount.is_some()
+            || self.balance.is_some()
+            || !self.description.is_empty()
     }
 
     /// Checks if all specified required fields are set.
@@ -48,7 +88,7 @@ impl ProtoTransaction {
         for field in required_fields {
             match field.as_str() {
                 "date" if self.date.is_none() => return false,
-                "description" if self.description.is_empty() => return false,
+                "description" if !self.has_description() => return false,
                 "amount" if self.amount.is_none() => return false,
                 "balance" if self.balance.is_none() => return false,
                 _ => {}
@@ -67,11 +107,94 @@ impl ProtoTransaction {
         self.balance = Some(balance);
     }
 
+    /// Amount in cents, for exact sums and comparisons
+    pub fn amount_money(&self) -> Option<Money> {
+        self.amount.map(Money::from_f64)
+    }
+
+    /// Balance in cents, for exact sums and comparisons
+    pub fn balance_money(&self) -> Option<Money> {
+        self.balance.map(Money::from_f64)
+    }
+
+    /// Returns true if the transaction repeats the previous transaction: the same date,
+    /// description, amount and stated balance. Transactions missing a date, amount or
+    /// balance, or whose balance was computed rather than read from the statement (see
+    /// fix_implicit_balances), are never duplicates.
+    pub fn is_duplicate_of(&self, prev: &ProtoTransaction) -> bool {
+        self.date.is_some()
+            && self.amount.is_some()
+            && self.balance.is_some()
+            && !self.balance_computed
+            && !prev.balance_computed
+            && self.date == prev.date
+            && self.description == prev.description
+            && self.amount_money() == prev.amount_money()
+            && self.balance_money() == prev.balance_money()
+    }
+
     /// Set the date for this transaction.
     pub fn set_date(&mut self, date: i64) {
         self.date = Some(date);
     }
 
+    /// Location of the transaction in the statement, like "page 3, y≈412", if its page
+    /// is known
+    pub fn location(&self) -> Option<String> {
+        let page = self.page?;
+        Some(match self.y1 {
+            Some(y1) => format!("page {}, y≈{}", page, y1),
+            None => format!("page {}", page),
+        })
+    }
+
+    /// Label of the transaction in messages, by its 1-based number and location if
+    /// known, like "Transaction 7 (page 3, y≈412)"
+    pub fn label(&self, number: usize) -> String {
+        match self.location() {
+            Some(location) => format!("Transaction {} ({})", number, location),
+            None => format!("Transaction {}", number),
+        }
+    }
+
+    /// Returns true if the field was manually corrected after parsing.
+    pub fn is_corrected(&self, field: &str) -> bool {
+        self.corrected_fields.iter().any(|f| f == field)
+    }
+
+    /// Source of the description: "corrected" if manually corrected, else "parsed".
+    pub fn description_source(&self) -> &'static str {
+        if self.is_corrected("description") {
+            "corrected"
+        } else {
+            "parsed"
+        }
+    }
+
+    /// Source of the amount: "corrected" if manually corrected, "inferred" if its sign
+    /// was reversed to agree with the balances, else "parsed".
+    pub fn amount_source(&self) -> &'static str {
+        if self.is_corrected("amount") {
+            "corrected"
+        } else if self.amount_inferred {
+            "inferred"
+        } else {
+            "parsed"
+        }
+    }
+
+    /// Source of the balance: "corrected" if manually corrected, "computed" if computed
+    /// from the previous balance, else "parsed".
+    pub fn balance_source(&self) -> &'static str {
+        if self.is_corrected("balance") {
+            "corrected"
+        } else if self.balance_computed {
+            "computed"
+        } else {
+            "parsed"
+        }
+    }
+
     /// Set index for this transaction.
     pub fn set_index(&mut self, index: usize) {
         self.index = index;
@@ -79,9 +202,20 @@ impl ProtoTransaction {
 
     /// Cleans the description by trimming whitespace and removing unwanted patterns.
     pub fn clean_description(&mut self, exclude_patterns: &[Regex]) {
+        self.replace_description_patterns(exclude_patterns, "");
+    }
+
+    /// Redacts the description by replacing text matching the patterns with the mask
+    /// (see Redaction).
+    pub fn redact_description(&mut self, redact_patterns: &[Regex], mask: &str) {
+        self.replace_description_patterns(redact_patterns, mask);
+    }
+
+    /// Replaces text matching the patterns in the trimmed description.
+    fn replace_description_patterns(&mut self, patterns: &[Regex], replacement: &str) {
         let mut desc = self.description.trim().to_string();
-        for pattern in exclude_patterns {
-            desc = pattern.replace_all(&desc, "").to_string();
+        for pattern in patterns {
+            desc = pattern.replace_all(&desc, replacement).to_string();
         }
         self.description = desc.trim().to_string();
     }
@@ -92,6 +226,18 @@ mod tests {
     use super::*;
     use regex::Regex;
 
+    #[test]
+    fn test_label_names_location_if_known() {
+        let mut tx = ProtoTransaction::new();
+        assert_eq!(tx.label(7), "Transaction 7");
+
+        tx.page = Some(3);
+        assert_eq!(tx.label(7), "Transaction 7 (page 3)");
+
+        tx.y1 = Some(412);
+        assert_eq!(tx.label(7), "Transaction 7 (page 3, y≈412)");
+    }
+
     #[test]
     fn test_clean_description_trims_whitespace() {
         let mut tx = ProtoTransaction::new();
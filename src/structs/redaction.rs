@@ -0,0 +1,196 @@
+use crate::structs::StatementData;
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Text replacing redacted text
+pub const REDACTION_MASK: &str = "****";
+
+/// Runs of six or more digits, optionally grouped by single spaces or hyphens, masked
+/// as account numbers in document text whose account number is unknown
+static DIGIT_RUN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b\d(?:[ -]?\d){5,}\b").unwrap());
+
+/// Redaction of statement data to be shared with third parties (e.g., a debug output
+/// attached to an issue): the account number is masked but for its last four
+/// characters (see mask_account_number), and text matching the redaction patterns is
+/// replaced by "****" in the transaction descriptions. Both are also redacted in the
+/// messages and the text items recorded in the statement data, as is the expected
+/// account number of the parse, if any.
+///
+/// Applied after parsing and checking, so that the balances are checked as read.
+#[derive(Debug, Clone, Default)]
+pub struct Redaction {
+    /// Patterns of the text redacted from descriptions (e.g., names or card numbers)
+    pub patterns: Vec<Regex>,
+}
+
+impl Redaction {
+    /// Create a redaction with the regex patterns. Fails if a pattern is invalid.
+    pub fn new(patterns: &[String]) -> Result<Self, String> {
+        let patterns = patterns
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern)
+                    .map_err(|e| format!("Invalid redaction pattern '{}': {}", pattern, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { patterns })
+    }
+
+    /// Redact text: mask the account numbers, each given with its mask, and replace
+    /// text matching the patterns
+    fn redact_text(&self, text: &str, account_numbers: &[(String, String)]) -> String {
+        let mut text = text.to_string();
+        for (number, masked) in account_numbers {
+            text = text.replace(number.as_str(), masked);
+        }
+        for pattern in &self.patterns {
+            text = pattern.replace_all(&text, REDACTION_MASK).to_string();
+        }
+        text
+    }
+
+    /// Redact text of the document not read into statement data (e.g., the nearest
+    /// text of identification diagnostics), whose account number is unknown: runs of
+    /// six or more digits are masked as account numbers (see mask_account_number), and
+    /// text matching the patterns replaced
+    pub fn redact_document_text(&self, text: &str) -> String {
+        let text =
+            DIGIT_RUN.replace_all(text, |caps: &regex::Captures| mask_account_number(&caps[0]));
+        self.redact_text(&text, &[])
+    }
+
+    /// Redact the statement data, parsed with the expected account number, if any
+    /// (see check_expected_account_number)
+    pub fn apply(&self, sd: &mut StatementData, expected_account_number: Option<&str>) {
+        let account_number = sd
+            .account_number
+            .take()
+            .filter(|number| !number.is_empty())
+            .map(|number| {
+                let masked = mask_account_number(&number);
+                (number, masked)
+            });
+        sd.account_number = account_number.as_ref().map(|(_, masked)| masked.clone());
+        let expected = expected_account_number
+            .filter(|number| !number.is_empty())
+            .map(|number| (number.to_string(), mask_account_number(number)));
+        let account_number: Vec<(String, String)> =
+            account_number.into_iter().chain(expected).collect();
+        let account_number = account_number.as_slice();
+
+        for tx in &mut sd.proto_transactions {
+            tx.redact_description(&self.patterns, REDACTION_MASK);
+            for item in &mut tx.source_items {
+                item.text = self.redact_text(&item.text, account_number);
+            }
+        }
+        for messages in [
+            &mut sd.errors,
+            &mut sd.warnings,
+            &mut sd.excluded_descriptions,
+            &mut sd.dropped_rows,
+            &mut sd.closing_balance_candidates,
+        ] {
+            for message in messages.iter_mut() {
+                *message = self.redact_text(message, account_number);
+            }
+        }
+        for (field, items) in &mut sd.field_sources {
+            for item in items {
+                // The account number may be split over several items
+                item.text = if field == "account_number" {
+                    REDACTION_MASK.to_string()
+                } else {
+                    self.redact_text(&item.text, account_number)
+                };
+            }
+        }
+        for claim in &mut sd.item_claims {
+            claim.text = self.redact_text(&claim.text, account_number);
+        }
+        for line in &mut sd.orphan_lines {
+            line.text = self.redact_text(&line.text, account_number);
+        }
+    }
+}
+
+/// Mask all but the last four characters of an account number, like "****5678" for
+/// "062-000 1234 5678". An account number of four characters or fewer is masked whole.
+pub fn mask_account_number(number: &str) -> String {
+    let chars: Vec<char> = number.trim().chars().collect();
+    if chars.len() <= 4 {
+        return REDACTION_MASK.to_string();
+    }
+    let last_four: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}{}", REDACTION_MASK, last_four)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::{OrphanLine, ProtoTransaction};
+
+    #[test]
+    fn test_mask_account_number() {
+        assert_eq!(mask_account_number("062-000 1234 5678"), "****5678");
+        assert_eq!(mask_account_number("12345"), "****2345");
+        assert_eq!(mask_account_number("1234"), "****");
+        assert_eq!(mask_account_number(""), "****");
+    }
+
+    #[test]
+    fn test_redaction_apply() {
+        let mut sd = StatementData::new();
+        sd.set_account_number("062-000 1234 5678".to_string());
+        let mut tx = ProtoTransaction::new();
+        tx.description = "TRANSFER TO JANE CITIZEN REF 998877".to_string();
+        sd.add_proto_transaction(tx);
+        sd.add_error("Account number 062-000 1234 5678 does not match".to_string());
+        sd.add_warning("Transaction 1 ('TRANSFER TO JANE CITIZEN') is large".to_string());
+        sd.orphan_lines.push(OrphanLine {
+            page: 0,
+            x1: 72,
+            y1: 300,
+            text: "Paid to JANE CITIZEN".to_string(),
+        });
+
+        let redaction =
+            Redaction::new(&["JANE CITIZEN".to_string(), r"REF \d+".to_string()]).unwrap();
+        redaction.apply(&mut sd, None);
+
+        assert_eq!(sd.account_number, Some("****5678".to_string()));
+        assert_eq!(
+            sd.proto_transactions[0].description,
+            "TRANSFER TO **** ****"
+        );
+        assert_eq!(sd.errors, vec!["Account number ****5678 does not match"]);
+        assert_eq!(
+            sd.warnings,
+            vec!["Transaction 1 ('TRANSFER TO ****') is large"]
+        );
+        assert_eq!(sd.orphan_lines[0].text, "Paid to ****");
+    }
+
+    #[test]
+    fn test_redact_document_text() {
+        let redaction = Redaction::new(&["JANE CITIZEN".to_string()]).unwrap();
+        assert_eq!(
+            redaction.redact_document_text("JANE CITIZEN Account 062-000 1234 5678"),
+            "**** Account ****5678"
+        );
+        // Amounts, dates and short numbers are kept
+        assert_eq!(
+            redaction.redact_document_text("Balance $1,234.56 on 01/02/2024, ref 12345"),
+            "Balance $1,234.56 on 01/02/2024, ref 12345"
+        );
+    }
+
+    #[test]
+    fn test_redaction_invalid_pattern() {
+        assert!(
+            Redaction::new(&["(".to_string()])
+                .unwrap_err()
+                .starts_with("Invalid redaction pattern '(':")
+        );
+    }
+}
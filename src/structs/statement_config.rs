@@ -1,7 +1,28 @@
+use crate::formats::AmountHints;
 use regex::Regex;
+use serde::{Deserialize, Serialize, Serializer};
+use std::hash::{DefaultHasher, Hash, Hasher};
 
-/// Configuration for parsing a bank statement layout.
-#[derive(Debug, Clone)]
+/// Fixed horizontal position of a transaction column, for statements without column headers.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransactionColumnPosition {
+    /// Transaction field in the column ("date", "description", "amount" or "balance")
+    pub field: String,
+    /// Left edge of the column
+    pub x1: i32,
+    /// Right edge of the column
+    pub x2: i32,
+}
+
+/// Serialize regex patterns as their pattern strings, as in config JSON
+fn serialize_regexes<S: Serializer>(regexes: &[Regex], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(regexes.iter().map(Regex::as_str))
+}
+
+/// Configuration for parsing a bank statement layout. Serializes to config JSON with
+/// every field set (see to_json_str).
+#[derive(Debug, Clone, Serialize)]
 pub struct StatementConfig {
     // BANK & ACCOUNT DETAILS
     /// Unique key identifying this layout configuration.
@@ -16,15 +37,51 @@ pub struct StatementConfig {
     pub account_terms: Vec<String>,
     /// Account types that should work with this layout (e.g., "Streamline", "Everyday Offset")
     pub account_examples: Vec<String>,
+    /// How well tested the layout is: "None" (untested), "Limited" (tested on few
+    /// statements) or "Full" (tested on many statements), or "Example" for the
+    /// synthetic example configs, which are not listed as supported.
+    pub status: String,
     /// Enforce that text extracted is sorted by Y, then X and optionally merged by specifying
     /// [y_bin, x_gap] values. Word/items will be binned by Y coordinate into bins of size y_bin,
     /// then sorted by X within each bin, and merged if within x_gap * avg_char_width. Set
     /// y_bin to 0.0 to disable Y binning (and X sorting by extension). Set x_gap to 0.0 to disable merging.
     pub fix_text_order: Vec<f32>,
+    /// IANA timezone of the statement dates (e.g., "Australia/Sydney"). Date timestamps
+    /// represent local midnight in this timezone. Empty for UTC midnight.
+    pub date_timezone: String,
+    /// Pivot year of 2-digit years of dates read without the statement year (e.g., the
+    /// statement start date): "99" is read in the window from 89 years before to 10 years
+    /// after it (see expand_two_digit_year). Not set, they are read as 20xx.
+    pub two_digit_year_pivot: Option<u32>,
+    /// Currency symbol of the statement amounts (e.g., "£"), read by the amount formats
+    /// with a symbol in place of their default. Empty for the format defaults.
+    pub currency_symbol: String,
+    /// Decimal separator of the statement amounts ("." or ","). Amount formats with a
+    /// different separator are skipped. Empty to try all listed formats.
+    pub decimal_separator: String,
+    /// Scale the alignment tolerances, new line tolerance, y_bin and column positions by the
+    /// detected coordinate scale of the document (e.g., 10 for coordinates in 10x PDF points).
+    pub scale_tolerances: bool,
+    /// The statement contains several accounts, each with its own account number,
+    /// balances and transactions, parsed separately into one StatementData per account.
+    pub multi_account: bool,
+    /// Array of terms starting each account section of a multi-account statement
+    /// (e.g., "Account summary")
+    pub account_section_terms: Vec<String>,
+    /// The document may contain several consecutive statements of the account (e.g.,
+    /// monthly statements combined into one PDF), parsed into one StatementData each
+    pub multi_statement: bool,
+    /// Match all terms (including account_terms) ignoring case
+    pub terms_case_insensitive: bool,
+    /// Statement fields whose absence is an error ("account_number", "opening_balance",
+    /// "closing_balance", "start_date", "minimum_payment", "payment_due_date"). Other
+    /// missing fields are warnings.
+    pub required_fields: Vec<String>,
     // ACCOUNT NUMBER READ PARAMS
     /// Array of terms to identify the account number line (e.g., "Account Number", "Acct No")
     pub account_number_terms: Vec<String>,
     /// Array of regex patterns to extract the account number
+    #[serde(serialize_with = "serialize_regexes")]
     pub account_number_patterns: Vec<Regex>,
     /// Alignment of the account number relative to the term ("x1", "x2", "y1", "y2", "")
     pub account_number_alignment: String,
@@ -54,6 +111,23 @@ pub struct StatementConfig {
     pub closing_balance_alignment_tol: i32,
     /// Invert the sign of the closing balance amount
     pub closing_balance_invert: bool,
+    /// Closing balance read when the closing balance terms occur more than once: "first"
+    /// or "last" of the occurrences not skipped
+    pub closing_balance_occurrence: String,
+    /// Only read the closing balance from this page (0 for the first page), if set
+    pub closing_balance_page: Option<i32>,
+    /// Terms near an occurrence of the closing balance terms that mark it as another
+    /// figure (e.g., "Projected balance" in a footnote table), so it is skipped
+    pub closing_balance_exclude_terms: Vec<String>,
+    /// Distance (in points) from the closing balance term within which an exclude term
+    /// skips the occurrence
+    pub closing_balance_exclude_distance: i32,
+    /// Largest difference in cents between a calculated and a stated balance that is not
+    /// a balance error. Balances are compared exactly in cents.
+    pub balance_tolerance_cents: i32,
+    /// Multiple of the median transaction amount (ignoring sign) above which an amount
+    /// is reported as a possible misread decimal point. 0 disables the check.
+    pub amount_outlier_multiple: i32,
 
     // START DATE READ PARAMS
     /// Array of terms to identify the statement start date line (e.g., "Statement Period", "From")
@@ -65,17 +139,58 @@ pub struct StatementConfig {
     /// Tolerance for alignment matching of start date
     pub start_date_alignment_tol: i32,
 
+    // END DATE READ PARAMS
+    /// Array of terms to identify the statement end (closing) date (e.g., "To", "Closing Date")
+    pub end_date_terms: Vec<String>,
+    /// Array of accepted formats to parse the statement end date
+    pub end_date_formats: Vec<String>,
+    /// Alignment of the end date relative to the term ("x1", "x2", "y1", "y2", "")
+    pub end_date_alignment: String,
+    /// Tolerance for alignment matching of end date
+    pub end_date_alignment_tol: i32,
+
+    // MINIMUM PAYMENT READ PARAMS (credit card statements)
+    /// Array of terms to identify the minimum payment line (e.g., "Minimum Payment Due")
+    pub minimum_payment_terms: Vec<String>,
+    /// Array of accepted formats to parse the minimum payment amount
+    pub minimum_payment_formats: Vec<String>,
+    /// Alignment of the minimum payment relative to the term ("x1", "x2", "y1", "y2", "")
+    pub minimum_payment_alignment: String,
+    /// Tolerance for alignment matching of minimum payment
+    pub minimum_payment_alignment_tol: i32,
+
+    // PAYMENT DUE DATE READ PARAMS (credit card statements)
+    /// Array of terms to identify the payment due date line (e.g., "Payment Due Date")
+    pub payment_due_date_terms: Vec<String>,
+    /// Array of accepted formats to parse the payment due date
+    pub payment_due_date_formats: Vec<String>,
+    /// Alignment of the payment due date relative to the term ("x1", "x2", "y1", "y2", "")
+    pub payment_due_date_alignment: String,
+    /// Tolerance for alignment matching of payment due date
+    pub payment_due_date_alignment_tol: i32,
+
     // GENERAL TRANSACTION READ PARAMS
     /// Array of terms that can indicate start, or nearing the start of transaction table
     /// (e.g., "Transactions").
     pub transaction_terms: Vec<String>,
     /// Array of terms that indicate the end, or close after the end of the transaction table.
     pub transaction_terms_stop: Vec<String>,
+    /// Extent of a stop term: "statement" (default) to stop reading transactions for
+    /// the rest of the statement, or "page" to stop only until the next page (e.g.,
+    /// for a "Continued on next page" footer repeated on every page)
+    pub transaction_terms_stop_scope: String,
+    /// Array of terms declaring that the statement has no transactions
+    /// (e.g., "There were no transactions during this period").
+    pub no_transaction_terms: Vec<String>,
     /// Fields expected for a complete transaction line, in order.
     /// E.g., [["date", "description", "amount"], ["description", "amount"]]
     /// Is a common format for credit card statements where the date is only specified
     /// on the first transaction of each day.
     pub transaction_formats: Vec<Vec<String>>,
+    /// Arrangement of the transactions: "table" (default) for one row per transaction
+    /// below column headers, or "labelled_blocks" for one block of labelled lines per
+    /// transaction (e.g., "Date: 03 Mar 2024"), with the field headers as labels
+    pub transaction_layout: String,
     /// Y-coordinate tolerance to identify a new line in the transaction list
     pub transaction_new_line_tol: i32,
     /// Parsing transaction requires the start date value to have been read
@@ -83,6 +198,26 @@ pub struct StatementConfig {
     pub transaction_start_date_required: bool,
     /// Tolerance for X alignment mismatch between value and header
     pub transaction_alignment_tol: i32,
+    /// Fixed column x-ranges used instead of header matching, for statements without
+    /// column headers. A value is read only if its x-range overlaps its field's column.
+    pub transaction_columns_by_position: Vec<TransactionColumnPosition>,
+    /// Drop a transaction repeating the previous one (same date, description, amount
+    /// and balance) instead of flagging it as an error, e.g., for rows repeated at the
+    /// top of the next page (see fix_duplicates)
+    pub drop_duplicate_transactions: bool,
+    /// Regex patterns of the descriptions of summary rows read as transactions below the
+    /// transaction table (e.g., "^Total debits"), which are removed
+    #[serde(serialize_with = "serialize_regexes")]
+    pub transaction_summary_exclude: Vec<Regex>,
+    /// Ditto marks meaning "same as above" (e.g., "\"" or "〃"). A ditto mark in the date
+    /// or description column copies the field from the previous transaction.
+    pub ditto_tokens: Vec<String>,
+    /// Report an error if more than transaction_orphan_lines_max lines of the
+    /// transaction table were not read by any field (see StatementData::orphan_lines)
+    pub transaction_strict: bool,
+    /// Largest number of unread lines in the transaction table (e.g., repeated page
+    /// headers) accepted if transaction_strict is set
+    pub transaction_orphan_lines_max: i32,
 
     // TRANSACTION DATE READ PARAMS
     /// Array of accepted formats to parse the transaction date
@@ -91,15 +226,43 @@ pub struct StatementConfig {
     pub transaction_date_headers: Vec<String>,
     /// Alignment of the transaction date column ("x1, "x2")
     pub transaction_date_alignment: String,
+    /// Tolerance for X alignment mismatch between date and header, instead of
+    /// transaction_alignment_tol
+    pub transaction_date_alignment_tol: Option<i32>,
+    /// Day of the month for month-only transaction dates (format14): "1" to "31", clamped
+    /// to the last day of short months, or "last"
+    pub month_only_default_day: String,
 
     // TRANSACTION DESCRIPTION READ PARAMS
     /// Headers that identify the transaction description column
     pub transaction_description_headers: Vec<String>,
     /// Alignment of the transaction description column ("x1, "x2")
     pub transaction_description_alignment: String,
+    /// Tolerance for X alignment mismatch between description and header, instead of
+    /// transaction_alignment_tol
+    pub transaction_description_alignment_tol: Option<i32>,
     /// Regex patterns to exclude from being considered as part of the description.
     /// E.g., [/\.\./g] to exclude "......." patterns.
+    #[serde(serialize_with = "serialize_regexes")]
     pub transaction_description_exclude: Vec<Regex>,
+    /// Allow date-like text within the description column to remain part of the description
+    /// instead of being parsed as a transaction date.
+    pub transaction_description_allows_dates: bool,
+    /// Allow amount-like text within the description column to remain part of the description
+    /// instead of being parsed as a transaction amount or balance.
+    pub transaction_description_allows_amounts: bool,
+    /// Allow transactions with an empty description (e.g., once exclude patterns remove
+    /// filler text) instead of treating them as incomplete.
+    pub transaction_description_optional: bool,
+    /// Description given to transactions with an empty description if
+    /// transaction_description_optional is set. Descriptions are left empty if empty.
+    pub transaction_description_placeholder: String,
+    /// Append the text of description-only lines below a transaction to its description,
+    /// for descriptions wrapped onto several lines
+    pub transaction_description_multiline: bool,
+    /// Maximum number of wrapped lines appended to a description if
+    /// transaction_description_multiline is set
+    pub transaction_description_continuation_max_lines: i32,
 
     // TRANSACTION AMOUNT READ PARAMS
     /// Array of accepted formats to parse the transaction amount
@@ -108,12 +271,21 @@ pub struct StatementConfig {
     pub transaction_amount_headers: Vec<String>,
     /// Alignment of the transaction amount column ("x1, "x2")
     pub transaction_amount_alignment: String,
+    /// Tolerance for X alignment mismatch between amount and header, instead of
+    /// transaction_alignment_tol
+    pub transaction_amount_alignment_tol: Option<i32>,
     /// Headers that identify when to invert the transaction amount sign
     pub transaction_amount_invert_headers: Vec<String>,
     /// Alignment of the transaction amount invert column ("x1, "x2")
     pub transaction_amount_invert_alignment: String,
     /// Invert the sign of all transaction amounts. Often needed for credit card statements.
     pub transaction_amount_invert: bool,
+    /// Headers of the debit column, for statements with separate debit and credit columns
+    /// instead of a signed amount column. Amounts in the debit column are negative.
+    pub transaction_debit_headers: Vec<String>,
+    /// Headers of the credit column (see transaction_debit_headers). Amounts in the
+    /// credit column are positive.
+    pub transaction_credit_headers: Vec<String>,
 
     // TRANSACTION BALANCE READ PARAMS
     /// Array of accepted formats to parse the transaction balance amount
@@ -122,8 +294,98 @@ pub struct StatementConfig {
     pub transaction_balance_headers: Vec<String>,
     /// Alignment of the transaction balance column ("x1, "x2")
     pub transaction_balance_alignment: String,
+    /// Tolerance for X alignment mismatch between balance and header, instead of
+    /// transaction_alignment_tol
+    pub transaction_balance_alignment_tol: Option<i32>,
     /// Invert the sign of all transaction balance amounts.
     pub transaction_balance_invert: bool,
+
+    // PARSING CACHE
+    /// Hash of the config fields computed once when the config is read from JSON (see
+    /// fields_hash), keying the config prepared for parsing along with the key. Reset it
+    /// to None after changing the fields of a config read from JSON. None for configs
+    /// built in code, which are hashed whenever they are prepared.
+    #[serde(skip)]
+    pub config_hash: Option<u64>,
+}
+
+impl StatementConfig {
+    /// Get the configured (x1, x2) column position of a transaction field, if any
+    pub fn get_transaction_column_position(&self, field: &str) -> Option<(i32, i32)> {
+        self.transaction_columns_by_position
+            .iter()
+            .find(|column| column.field == field)
+            .map(|column| (column.x1, column.x2))
+    }
+
+    /// Get the X alignment tolerance of a transaction field ("date", "description",
+    /// "amount" or "balance"): its own tolerance if set, else transaction_alignment_tol
+    pub fn get_transaction_alignment_tol(&self, field: &str) -> i32 {
+        let tol = match field {
+            "date" => self.transaction_date_alignment_tol,
+            "description" => self.transaction_description_alignment_tol,
+            "amount" => self.transaction_amount_alignment_tol,
+            "balance" => self.transaction_balance_alignment_tol,
+            _ => None,
+        };
+        tol.unwrap_or(self.transaction_alignment_tol)
+    }
+
+    /// Serialize the config to pretty-printed config JSON, with every field including
+    /// those left at their defaults
+    pub fn to_json_str(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| format!("JSON write error: {}", e))
+    }
+
+    /// Get the currency and separator hints of the amount formats
+    pub fn amount_hints(&self) -> AmountHints {
+        AmountHints {
+            currency_symbol: self.currency_symbol.clone(),
+            decimal_separator: self.decimal_separator.clone(),
+        }
+    }
+
+    /// Get the config_hash of the config if set, else hash its fields
+    pub fn fields_hash(&self) -> u64 {
+        self.config_hash.unwrap_or_else(|| {
+            let mut hasher = DefaultHasher::new();
+            format!("{:?}", self).hash(&mut hasher);
+            hasher.finish()
+        })
+    }
+
+    /// Get a copy of the config with the coordinate-based tolerances and positions
+    /// multiplied by the given coordinate scale factor
+    pub fn scaled(&self, factor: f32) -> Self {
+        let scale = |value: i32| (value as f32 * factor).round() as i32;
+        let mut cfg = self.clone();
+        cfg.account_number_alignment_tol = scale(cfg.account_number_alignment_tol);
+        cfg.opening_balance_alignment_tol = scale(cfg.opening_balance_alignment_tol);
+        cfg.closing_balance_alignment_tol = scale(cfg.closing_balance_alignment_tol);
+        cfg.closing_balance_exclude_distance = scale(cfg.closing_balance_exclude_distance);
+        cfg.start_date_alignment_tol = scale(cfg.start_date_alignment_tol);
+        cfg.end_date_alignment_tol = scale(cfg.end_date_alignment_tol);
+        cfg.minimum_payment_alignment_tol = scale(cfg.minimum_payment_alignment_tol);
+        cfg.payment_due_date_alignment_tol = scale(cfg.payment_due_date_alignment_tol);
+        cfg.transaction_new_line_tol = scale(cfg.transaction_new_line_tol);
+        cfg.transaction_alignment_tol = scale(cfg.transaction_alignment_tol);
+        for tol in [
+            &mut cfg.transaction_date_alignment_tol,
+            &mut cfg.transaction_description_alignment_tol,
+            &mut cfg.transaction_amount_alignment_tol,
+            &mut cfg.transaction_balance_alignment_tol,
+        ] {
+            *tol = tol.map(scale);
+        }
+        if let Some(y_bin) = cfg.fix_text_order.first_mut() {
+            *y_bin *= factor;
+        }
+        for column in &mut cfg.transaction_columns_by_position {
+            column.x1 = scale(column.x1);
+            column.x2 = scale(column.x2);
+        }
+        cfg
+    }
 }
 
 impl Default for StatementConfig {
@@ -134,7 +396,22 @@ impl Default for StatementConfig {
             account_type: "Generic Account".to_string(),
             account_terms: vec![],
             account_examples: vec![],
+            status: "None".to_string(),
             fix_text_order: vec![0.0, 0.0],
+            date_timezone: "".to_string(),
+            two_digit_year_pivot: None,
+            currency_symbol: "".to_string(),
+            decimal_separator: "".to_string(),
+            scale_tolerances: false,
+            multi_account: false,
+            account_section_terms: vec![],
+            multi_statement: false,
+            terms_case_insensitive: false,
+            required_fields: vec![
+                "account_number".to_string(),
+                "opening_balance".to_string(),
+                "closing_balance".to_string(),
+            ],
 
             account_number_terms: vec![],
             account_number_patterns: vec![],
@@ -152,38 +429,82 @@ impl Default for StatementConfig {
             closing_balance_alignment: "y1".to_string(),
             closing_balance_alignment_tol: 5,
             closing_balance_invert: false,
+            closing_balance_occurrence: "first".to_string(),
+            closing_balance_page: None,
+            closing_balance_exclude_terms: vec![],
+            closing_balance_exclude_distance: 20,
+            balance_tolerance_cents: 0,
+            amount_outlier_multiple: 200,
 
             start_date_terms: vec![],
             start_date_formats: vec![],
             start_date_alignment: "y1".to_string(),
             start_date_alignment_tol: 5,
+            end_date_terms: vec![],
+            end_date_formats: vec![],
+            end_date_alignment: "y1".to_string(),
+            end_date_alignment_tol: 5,
+
+            minimum_payment_terms: vec![],
+            minimum_payment_formats: vec![],
+            minimum_payment_alignment: "y1".to_string(),
+            minimum_payment_alignment_tol: 5,
+
+            payment_due_date_terms: vec![],
+            payment_due_date_formats: vec![],
+            payment_due_date_alignment: "y1".to_string(),
+            payment_due_date_alignment_tol: 5,
 
             transaction_terms: vec![],
             transaction_terms_stop: vec![],
+            transaction_terms_stop_scope: "statement".to_string(),
+            no_transaction_terms: vec![],
             transaction_formats: vec![],
+            transaction_layout: "table".to_string(),
             transaction_new_line_tol: 5,
             transaction_start_date_required: false,
             transaction_alignment_tol: 10,
+            transaction_columns_by_position: vec![],
+            drop_duplicate_transactions: false,
+            transaction_summary_exclude: vec![],
+            ditto_tokens: vec!["\"".to_string(), "〃".to_string(), "''".to_string()],
+            transaction_strict: false,
+            transaction_orphan_lines_max: 0,
 
             transaction_date_formats: vec![],
             transaction_date_headers: vec![],
             transaction_date_alignment: "x1".to_string(),
+            transaction_date_alignment_tol: None,
+            month_only_default_day: "1".to_string(),
 
             transaction_description_headers: vec![],
             transaction_description_alignment: "x1".to_string(),
+            transaction_description_alignment_tol: None,
             transaction_description_exclude: vec![],
+            transaction_description_allows_dates: false,
+            transaction_description_allows_amounts: false,
+            transaction_description_optional: false,
+            transaction_description_placeholder: String::new(),
+            transaction_description_multiline: false,
+            transaction_description_continuation_max_lines: 2,
 
             transaction_amount_formats: vec![],
             transaction_amount_headers: vec![],
             transaction_amount_alignment: "x1".to_string(),
+            transaction_amount_alignment_tol: None,
             transaction_amount_invert_headers: vec![],
             transaction_amount_invert_alignment: "x1".to_string(),
             transaction_amount_invert: false,
+            transaction_debit_headers: vec![],
+            transaction_credit_headers: vec![],
 
             transaction_balance_formats: vec![],
             transaction_balance_headers: vec![],
             transaction_balance_alignment: "x1".to_string(),
+            transaction_balance_alignment_tol: None,
             transaction_balance_invert: false,
+
+            config_hash: None,
         }
     }
 }
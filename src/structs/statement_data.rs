@@ -1,30 +1,106 @@
-use crate::structs::ProtoTransaction;
-use chrono::{DateTime, Datelike, TimeZone, Utc};
+use crate::formats::date::to_local_midnight_timestamp;
+use crate::structs::{
+    ItemClaim, Money, OrphanLine, PageCoverage, ParseTimings, ProtoTransaction, TableLines,
+    TextItem,
+};
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::collections::BTreeMap;
 use std::fmt;
 
 #[derive(Clone, Debug)]
 pub struct StatementData {
     pub key: Option<String>,
+    pub config_status: Option<String>,
     pub account_number: Option<String>,
     pub start_date: Option<i64>,
     pub start_date_year: Option<i32>,
+    pub end_date: Option<i64>,
     pub opening_balance: Option<f64>,
     pub closing_balance: Option<f64>,
+    pub minimum_payment: Option<f64>,
+    pub payment_due_date: Option<i64>,
     pub proto_transactions: Vec<ProtoTransaction>,
     pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+    pub item_claims: Vec<ItemClaim>,
+    /// Text items consumed by each statement-level parser (e.g., "opening_balance"),
+    /// if recorded (see StatementDataParser::with_source_items)
+    pub field_sources: BTreeMap<String, Vec<TextItem>>,
+    pub page_coverage: Vec<PageCoverage>,
+    /// Original text of descriptions reduced to empty by transaction_description_exclude
+    pub excluded_descriptions: Vec<String>,
+    /// Transactions dropped with only some of the compulsory fields of the transaction
+    /// formats set, each described by its page, the fields read and those missing
+    pub dropped_rows: Vec<String>,
+    /// Closing balances read at each occurrence of the closing balance terms, each
+    /// described by its page, value and whether it was used or why it was skipped.
+    /// Only recorded if the config reads every occurrence (see ClosingBalanceParser).
+    pub closing_balance_candidates: Vec<String>,
+    /// Effective x bounds of each transaction column whose header was read
+    pub column_bounds: BTreeMap<String, (i32, i32)>,
+    pub transaction_stop_page: Option<i32>,
+    /// Printed lines of the transaction table (see get_table_lines)
+    pub table_lines: TableLines,
+    /// Lines of the transaction table with text not read by any parser (see
+    /// get_orphan_lines)
+    pub orphan_lines: Vec<OrphanLine>,
+    pub date_timezone: Option<Tz>,
+    /// Currency symbol of the amounts: the config currency_symbol if set, otherwise the
+    /// most common currency symbol of the document
+    pub currency: Option<String>,
+    /// Largest difference between a calculated and stated balance that is not an error
+    /// (see checkers::balances), from the config balance_tolerance_cents
+    pub balance_tolerance: Money,
+    /// Fraction of the account terms of the config found in the statement, if
+    /// identified by its terms (see StatementTyper::scores_filtered)
+    pub identification_score: Option<f64>,
+    pub fix_text_order_applied: bool,
+    pub no_transactions_declared: bool,
+    pub coordinate_scale: f32,
+    pub account_section: Option<usize>,
+    /// Number of the statement in a document of several statements (multi_statement
+    /// config), in start date order
+    pub statement_section: Option<usize>,
+    pub timings: ParseTimings,
 }
 
 impl StatementData {
     pub fn new() -> Self {
         Self {
             key: None,
+            config_status: None,
             account_number: None,
             start_date: None,
             start_date_year: None,
+            end_date: None,
             opening_balance: None,
             closing_balance: None,
+            minimum_payment: None,
+            payment_due_date: None,
             proto_transactions: Vec::new(),
             errors: Vec::new(),
+            warnings: Vec::new(),
+            item_claims: Vec::new(),
+            field_sources: BTreeMap::new(),
+            page_coverage: Vec::new(),
+            excluded_descriptions: Vec::new(),
+            dropped_rows: Vec::new(),
+            closing_balance_candidates: Vec::new(),
+            column_bounds: BTreeMap::new(),
+            transaction_stop_page: None,
+            table_lines: TableLines::default(),
+            orphan_lines: Vec::new(),
+            date_timezone: None,
+            currency: None,
+            balance_tolerance: Money::ZERO,
+            identification_score: None,
+            fix_text_order_applied: false,
+            no_transactions_declared: false,
+            coordinate_scale: 1.0,
+            account_section: None,
+            statement_section: None,
+            timings: ParseTimings::new(),
         }
     }
 
@@ -37,18 +113,39 @@ impl StatementData {
     pub fn closing_balance(&self) -> Option<f64> {
         self.closing_balance
     }
+    /// Opening balance in cents, for exact sums and comparisons
+    pub fn opening_balance_money(&self) -> Option<Money> {
+        self.opening_balance.map(Money::from_f64)
+    }
+    /// Closing balance in cents, for exact sums and comparisons
+    pub fn closing_balance_money(&self) -> Option<Money> {
+        self.closing_balance.map(Money::from_f64)
+    }
     pub fn start_date(&self) -> Option<i64> {
         self.start_date
     }
     pub fn start_date_year(&self) -> Option<i32> {
         self.start_date_year
     }
+    pub fn end_date(&self) -> Option<i64> {
+        self.end_date
+    }
+    pub fn minimum_payment(&self) -> Option<f64> {
+        self.minimum_payment
+    }
+    pub fn payment_due_date(&self) -> Option<i64> {
+        self.payment_due_date
+    }
 
     // Setters for the fields
     pub fn set_key(&mut self, key: String) {
         self.key = Some(key);
     }
 
+    pub fn set_config_status(&mut self, status: String) {
+        self.config_status = Some(status);
+    }
+
     pub fn set_account_number(&mut self, account_number: String) {
         self.account_number = Some(account_number);
     }
@@ -58,6 +155,10 @@ impl StatementData {
         self.start_date_year = Utc.timestamp_millis_opt(date).single().map(|dt| dt.year());
     }
 
+    pub fn set_end_date(&mut self, date: i64) {
+        self.end_date = Some(date);
+    }
+
     pub fn set_opening_balance(&mut self, balance: f64) {
         self.opening_balance = Some(balance);
     }
@@ -66,86 +167,302 @@ impl StatementData {
         self.closing_balance = Some(balance);
     }
 
+    pub fn set_minimum_payment(&mut self, amount: f64) {
+        self.minimum_payment = Some(amount);
+    }
+
+    pub fn set_payment_due_date(&mut self, date: i64) {
+        self.payment_due_date = Some(date);
+    }
+
     pub fn add_proto_transaction(&mut self, proto_tx: ProtoTransaction) {
         self.proto_transactions.push(proto_tx);
     }
 
+    /// Number of transactions whose balance was computed rather than parsed
+    pub fn computed_balance_count(&self) -> usize {
+        self.proto_transactions
+            .iter()
+            .filter(|tx| tx.balance_computed)
+            .count()
+    }
+
+    /// Number of transactions whose amount sign was inferred from the balances
+    pub fn inferred_amount_count(&self) -> usize {
+        self.proto_transactions
+            .iter()
+            .filter(|tx| tx.amount_inferred)
+            .count()
+    }
+
     pub fn add_error(&mut self, error: String) {
         self.errors.push(error);
     }
 
+    pub fn add_warning(&mut self, warning: String) {
+        self.warnings.push(warning);
+    }
+
+    pub fn add_item_claim(&mut self, claim: ItemClaim) {
+        self.item_claims.push(claim);
+    }
+
+    /// Convert the start date, end date, payment due date and transaction dates from UTC
+    /// midnight to local midnight in the given timezone. Calendar dates are unchanged.
+    pub fn localise_dates(&mut self, tz: Tz) {
+        if let Some(ms) = self.start_date {
+            self.start_date = to_local_midnight_timestamp(ms, tz).or(Some(ms));
+        }
+        if let Some(ms) = self.end_date {
+            self.end_date = to_local_midnight_timestamp(ms, tz).or(Some(ms));
+        }
+        if let Some(ms) = self.payment_due_date {
+            self.payment_due_date = to_local_midnight_timestamp(ms, tz).or(Some(ms));
+        }
+        for tx in &mut self.proto_transactions {
+            if let Some(ms) = tx.date {
+                tx.date = to_local_midnight_timestamp(ms, tz).or(Some(ms));
+            }
+        }
+        self.date_timezone = Some(tz);
+    }
+
+    /// Get the calendar date of a timestamp in the statement's date timezone (UTC if unset).
+    pub fn to_naive_date(&self, ms: i64) -> Option<NaiveDate> {
+        let dt = DateTime::<Utc>::from_timestamp_millis(ms)?;
+        match self.date_timezone {
+            Some(tz) => Some(dt.with_timezone(&tz).date_naive()),
+            None => Some(dt.date_naive()),
+        }
+    }
+
     pub fn print(&self) {
         println!("{}", self);
     }
 }
 
-impl fmt::Display for StatementData {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut result = String::new();
-        result.push_str("Statement Data:\n");
+impl StatementData {
+    /// Write the human-readable report of the statement data (as in Display) line by
+    /// line, without building it in memory. Sections with more than max_section_lines
+    /// lines (transactions, errors, page coverage, orphan lines, sources and warnings)
+    /// are truncated with a marker giving the number of lines left out.
+    pub fn write_report<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        max_section_lines: usize,
+    ) -> fmt::Result {
+        out.write_str("Statement Data:\n")?;
         match &self.key {
-            Some(k) => result.push_str(&format!("  Key: {}\n", k)),
-            None => result.push_str("  Key: Not set\n"),
+            Some(k) => writeln!(out, "  Key: {}", k)?,
+            None => out.write_str("  Key: Not set\n")?,
+        }
+        match &self.config_status {
+            Some(s) => writeln!(out, "  Config Status: {}", s)?,
+            None => out.write_str("  Config Status: Not set\n")?,
         }
         match &self.account_number {
-            Some(an) => result.push_str(&format!("  Account Number: {}\n", an)),
-            None => result.push_str("  Account Number: Not set\n"),
+            Some(an) => writeln!(out, "  Account Number: {}", an)?,
+            None => out.write_str("  Account Number: Not set\n")?,
+        }
+        if let Some(section) = self.account_section {
+            writeln!(out, "  Account Section: {}", section)?;
+        }
+        if let Some(section) = self.statement_section {
+            writeln!(out, "  Statement Section: {}", section)?;
+        }
+        // Only near-miss identifications are shown, with some account terms missing
+        if let Some(score) = self.identification_score.filter(|score| *score < 1.0) {
+            writeln!(out, "  Identification Score: {:.2}", score)?;
         }
         if let Some(ms) = self.start_date {
-            if let Some(dt) = DateTime::<Utc>::from_timestamp_millis(ms) {
-                result.push_str(&format!("  Start Date: {}\n", dt.format("%d %b %Y")));
+            if let Some(date) = self.to_naive_date(ms) {
+                writeln!(out, "  Start Date: {}", date.format("%d %b %Y"))?;
             } else {
-                result.push_str(&format!("  Start Date: {}\n", ms));
+                writeln!(out, "  Start Date: {}", ms)?;
             }
         } else {
-            result.push_str("  Start Date: Not set\n");
+            out.write_str("  Start Date: Not set\n")?;
+        }
+        // The end date is optional, so only shown if set
+        if let Some(ms) = self.end_date {
+            match self.to_naive_date(ms) {
+                Some(date) => writeln!(out, "  End Date: {}", date.format("%d %b %Y"))?,
+                None => writeln!(out, "  End Date: {}", ms)?,
+            }
         }
         if let Some(balance) = self.opening_balance {
-            result.push_str(&format!("  Opening Balance: {:.2}\n", balance));
+            writeln!(out, "  Opening Balance: {:.2}", balance)?;
         } else {
-            result.push_str("  Opening Balance: Not set\n");
+            out.write_str("  Opening Balance: Not set\n")?;
         }
         if let Some(balance) = self.closing_balance {
-            result.push_str(&format!("  Closing Balance: {:.2}\n", balance));
+            writeln!(out, "  Closing Balance: {:.2}", balance)?;
         } else {
-            result.push_str("  Closing Balance: Not set\n");
-        }
-        result.push_str("  Proto Transactions:\n");
-        for (i, tx) in self.proto_transactions.iter().enumerate() {
-            let date_str = match tx.date {
-                Some(ms) => match DateTime::<Utc>::from_timestamp_millis(ms) {
-                    Some(dt) => dt.format("%d %b %Y").to_string(),
-                    None => ms.to_string(),
-                },
-                None => "Not set".to_string(),
-            };
-            let amount_str = match tx.amount {
-                Some(a) => format!("{:.2}", a),
-                None => "Not set".to_string(),
-            };
-            let balance_str = match tx.balance {
-                Some(b) => format!("{:.2}", b),
-                None => "Not set".to_string(),
-            };
-            result.push_str(&format!(
-                "    {}: {}, \"{}\", {}, {}\n",
-                i + 1,
-                date_str,
-                tx.description,
-                amount_str,
-                balance_str
-            ));
+            out.write_str("  Closing Balance: Not set\n")?;
         }
-        if !self.errors.is_empty() {
-            result.push_str("  Errors:\n");
-            for error in &self.errors {
-                result.push_str(&format!("    - {}\n", error));
+        // Credit card fields are only shown if set
+        if let Some(amount) = self.minimum_payment {
+            writeln!(out, "  Minimum Payment: {:.2}", amount)?;
+        }
+        if let Some(ms) = self.payment_due_date {
+            match self.to_naive_date(ms) {
+                Some(date) => writeln!(out, "  Payment Due Date: {}", date.format("%d %b %Y"))?,
+                None => writeln!(out, "  Payment Due Date: {}", ms)?,
             }
+        }
+        if self.no_transactions_declared {
+            out.write_str("  No Transactions Declared: Yes\n")?;
+        }
+        out.write_str("  Proto Transactions:\n")?;
+        write_section_lines(
+            out,
+            &self.proto_transactions,
+            max_section_lines,
+            |out, i, tx| {
+                let date_str = match tx.date {
+                    Some(ms) => match self.to_naive_date(ms) {
+                        Some(date) => date.format("%d %b %Y").to_string(),
+                        None => ms.to_string(),
+                    },
+                    None => "Not set".to_string(),
+                };
+                let amount_str = match tx.amount {
+                    Some(a) => format!("{:.2}", a),
+                    None => "Not set".to_string(),
+                };
+                let balance_str = match tx.balance {
+                    Some(b) => format!("{:.2}", b),
+                    None => "Not set".to_string(),
+                };
+                writeln!(
+                    out,
+                    "    {}: {}, \"{}\", {}, {}",
+                    i + 1,
+                    date_str,
+                    tx.description,
+                    amount_str,
+                    balance_str
+                )
+            },
+        )?;
+        if !self.errors.is_empty() {
+            out.write_str("  Errors:\n")?;
+            write_section_lines(out, &self.errors, max_section_lines, |out, _, error| {
+                writeln!(out, "    - {}", error)
+            })?;
         } else {
-            result.push_str("  Errors: None\n");
+            out.write_str("  Errors: None\n")?;
+        }
+        if !self.page_coverage.is_empty() {
+            out.write_str("  Page Coverage:\n")?;
+            write_section_lines(
+                out,
+                &self.page_coverage,
+                max_section_lines,
+                |out, _, coverage| {
+                    writeln!(
+                        out,
+                        "    Page {}: {} text items, {} consumed, {} transactions",
+                        coverage.page,
+                        coverage.text_items,
+                        coverage.consumed_items,
+                        coverage.transactions
+                    )
+                },
+            )?;
+        }
+        if !self.orphan_lines.is_empty() {
+            out.write_str("  Orphan Lines:\n")?;
+            write_section_lines(
+                out,
+                &self.orphan_lines,
+                max_section_lines,
+                |out, _, line| writeln!(out, "    - {}", line),
+            )?;
         }
-        write!(f, "{}", result)
+        // Sources are only recorded on request, so only shown if set
+        if !self.field_sources.is_empty() {
+            out.write_str("  Field Sources:\n")?;
+            let field_sources: Vec<_> = self.field_sources.iter().collect();
+            write_section_lines(
+                out,
+                &field_sources,
+                max_section_lines,
+                |out, _, (field, items)| {
+                    writeln!(out, "    {}: {}", field, format_source_items(items))
+                },
+            )?;
+        }
+        if self
+            .proto_transactions
+            .iter()
+            .any(|tx| !tx.source_items.is_empty())
+        {
+            out.write_str("  Transaction Sources:\n")?;
+            write_section_lines(
+                out,
+                &self.proto_transactions,
+                max_section_lines,
+                |out, i, tx| {
+                    writeln!(
+                        out,
+                        "    {}: {}",
+                        i + 1,
+                        format_source_items(&tx.source_items)
+                    )
+                },
+            )?;
+        }
+        if !self.warnings.is_empty() {
+            out.write_str("  Warnings:\n")?;
+            write_section_lines(out, &self.warnings, max_section_lines, |out, _, warning| {
+                writeln!(out, "    - {}", warning)
+            })?;
+        }
+        out.write_str("  Timings:\n")?;
+        write!(out, "{}", self.timings)
+    }
+}
+
+impl fmt::Display for StatementData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_report(f, usize::MAX)
+    }
+}
+
+/// Write a line for each of the first max_lines items of a report section, then a
+/// marker with the number of lines left out
+fn write_section_lines<W, T, F>(
+    out: &mut W,
+    items: &[T],
+    max_lines: usize,
+    write_line: F,
+) -> fmt::Result
+where
+    W: fmt::Write,
+    F: Fn(&mut W, usize, &T) -> fmt::Result,
+{
+    for (i, item) in items.iter().take(max_lines).enumerate() {
+        write_line(out, i, item)?;
+    }
+    if items.len() > max_lines {
+        writeln!(
+            out,
+            "    ... {} more line(s) truncated",
+            items.len() - max_lines
+        )?;
     }
+    Ok(())
+}
+
+/// Format text items as in the layout text (["text",x1,x2,y1,y2]), preceded by the page
+/// of the first item
+fn format_source_items(items: &[TextItem]) -> String {
+    let Some(first) = items.first() else {
+        return "None".to_string();
+    };
+    let items: Vec<String> = items.iter().map(TextItem::to_layout_block).collect();
+    format!("page {} {}", first.page, items.join(""))
 }
 
 impl Default for StatementData {
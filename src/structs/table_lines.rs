@@ -0,0 +1,105 @@
+use crate::structs::{ItemClaim, TextItem};
+use std::collections::HashMap;
+
+/// Largest difference between the y1 of text items on the same printed line
+pub(crate) const SAME_LINE_TOL: i32 = 2;
+
+/// Printed lines of the transaction table, measured from the text items
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableLines {
+    /// Number of printed lines from the first transaction value to the end of the table
+    pub lines: usize,
+    /// Most common vertical distance between consecutive lines on a page, if any
+    pub line_spacing: Option<i32>,
+}
+
+/// Get the printed lines of the transaction table: the lines of the text items from
+/// the first transaction claim with a digit (i.e., a date, amount or balance rather
+/// than a column header) to the table end (the position of the stop term), or to the
+/// last such claim if no stop term was found
+pub fn get_table_lines(
+    text_items: &[TextItem],
+    claims: &[ItemClaim],
+    table_end: Option<usize>,
+) -> TableLines {
+    let values: Vec<&ItemClaim> = claims
+        .iter()
+        .filter(|claim| claim.is_transaction() && claim.text.chars().any(|c| c.is_ascii_digit()))
+        .collect();
+    let (Some(first), Some(last)) = (values.first(), values.last()) else {
+        return TableLines::default();
+    };
+    let end = table_end.unwrap_or(last.end).min(text_items.len());
+    if first.start >= end {
+        return TableLines::default();
+    }
+
+    let mut positions: Vec<(i32, i32)> = text_items[first.start..end]
+        .iter()
+        .map(|item| (item.page, item.y1))
+        .collect();
+    positions.sort_unstable();
+    let mut lines: Vec<(i32, i32)> = Vec::new();
+    for (page, y1) in positions {
+        match lines.last() {
+            Some(&(last_page, last_y1)) if last_page == page && y1 - last_y1 <= SAME_LINE_TOL => {}
+            _ => lines.push((page, y1)),
+        }
+    }
+
+    let mut counts: HashMap<i32, usize> = HashMap::new();
+    for pair in lines.windows(2) {
+        if pair[0].0 == pair[1].0 {
+            *counts.entry(pair[1].1 - pair[0].1).or_default() += 1;
+        }
+    }
+    // Ties go to the smallest spacing
+    let line_spacing = counts
+        .into_iter()
+        .max_by_key(|&(spacing, count)| (count, -spacing))
+        .map(|(spacing, _)| spacing);
+    TableLines {
+        lines: lines.len(),
+        line_spacing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_text_item(text: &str, y1: i32, page: i32) -> TextItem {
+        TextItem::new(text.to_string(), 0, y1, 10, y1 - 8, page)
+    }
+
+    #[test]
+    fn test_get_table_lines() {
+        let items = vec![
+            make_text_item("Date", 100, 0),
+            make_text_item("01/04", 111, 0),
+            make_text_item("5.00", 112, 0),
+            make_text_item("02/04", 122, 0),
+            make_text_item("03/04", 133, 0),
+            make_text_item("04/04", 150, 0),
+            make_text_item("05/04", 40, 1),
+            make_text_item("Total", 60, 1),
+        ];
+        let claims = vec![
+            ItemClaim::new("transaction_date", &items, 0, 1),
+            ItemClaim::new("transaction_date", &items, 1, 2),
+            ItemClaim::new("transaction_amount", &items, 2, 3),
+            ItemClaim::new("transaction_date", &items, 6, 7),
+        ];
+
+        let table = get_table_lines(&items, &claims, None);
+        assert_eq!(table.lines, 5);
+        assert_eq!(table.line_spacing, Some(11));
+
+        // Lines up to the stop term, beyond the last transaction value
+        assert_eq!(get_table_lines(&items, &claims[..3], Some(7)).lines, 5);
+        assert_eq!(
+            get_table_lines(&items, &claims[..1], None),
+            TableLines::default()
+        );
+    }
+}
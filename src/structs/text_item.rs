@@ -53,56 +53,74 @@ impl TextItem {
             && self.page == other.page
     }
 
-    /// Merge the text of this TextItem with another TextItem
+    /// Merge the text of this TextItem with another TextItem, taking the union of their
+    /// bounding boxes in the y-axis orientation of this item (or of the other item if
+    /// this one has no height)
     pub fn merge(&mut self, other: &TextItem) {
         self.text = format!("{} {}", self.text, other.text);
-        // take the smallest x1 and y1 from self and other
         self.x1 = self.x1.min(other.x1);
         self.x2 = self.x2.max(other.x2);
 
-        // If y-axis is inverted (0 at top), take largest y1 and smallest y2
-        if (self.y1 > self.y2) && (other.y1 > other.y2) {
-            self.y1 = self.y1.max(other.y1);
-            self.y2 = self.y2.min(other.y2);
-            return;
-        }
-        if (self.y1 < self.y2) && (other.y1 < other.y2) {
-            self.y1 = self.y1.min(other.y1);
-            self.y2 = self.y2.max(other.y2);
-            return;
-        }
-        // Mixed y-axis orientation, raise panic
-        panic!("Inconsistent y-axis orientation when merging TextItems");
+        let inverted = if self.y1 == self.y2 {
+            other.y1 > other.y2
+        } else {
+            self.y1 > self.y2
+        };
+        let y_low = self.y1.min(self.y2).min(other.y1.min(other.y2));
+        let y_high = self.y1.max(self.y2).max(other.y1.max(other.y2));
+        // If y-axis is inverted (0 at top), y1 is the largest
+        (self.y1, self.y2) = if inverted {
+            (y_high, y_low)
+        } else {
+            (y_low, y_high)
+        };
     }
 
-    /// Create merged TextItem from a slice of TextItems
+    /// Create merged TextItem from a slice of TextItems, with the text joined by spaces
+    /// and the union of their bounding boxes (in the y-axis orientation of the first
+    /// item). None if the slice is empty or the items are on different pages, as a
+    /// join across a page break has no meaningful position.
     pub fn from_items(items: &[TextItem]) -> Option<TextItem> {
-        if items.is_empty() {
+        let first = items.first()?;
+        if items.iter().any(|it| it.page != first.page) {
             return None;
         }
-        let first = &items[0];
-        let last = &items[items.len() - 1];
         let merged_text = items
             .iter()
             .map(|it| it.text.clone())
             .collect::<Vec<_>>()
             .join(" ");
+        let x1 = items.iter().map(|it| it.x1.min(it.x2)).min()?;
+        let x2 = items.iter().map(|it| it.x1.max(it.x2)).max()?;
+        let y_low = items.iter().map(|it| it.y1.min(it.y2)).min()?;
+        let y_high = items.iter().map(|it| it.y1.max(it.y2)).max()?;
+        let (y1, y2) = if first.y1 > first.y2 {
+            (y_high, y_low)
+        } else {
+            (y_low, y_high)
+        };
         Some(TextItem {
             text: merged_text,
-            x1: first.x1,
-            y1: last.y1,
-            x2: last.x2,
-            y2: first.y2,
-            page: first.page, // Just take the page of the first item
+            x1,
+            y1,
+            x2,
+            y2,
+            page: first.page,
         })
     }
 
     /// Return a string of format ["text",x1,x2,y1,y2] with raw integer coordinates.
+    /// Quotes and backslashes in the text are escaped by a backslash, so that the
+    /// block reads back as the same text (see layout_to_text_items).
     pub fn to_layout_block(&self) -> String {
         // Keeping page excluded from list for backward compatibility; add if needed.
         format!(
             "[\"{}\",{},{},{},{}]",
-            self.text, self.x1, self.x2, self.y1, self.y2
+            self.text.replace('\\', "\\\\").replace('"', "\\\""),
+            self.x1,
+            self.x2,
+            self.y1,
+            self.y2
         )
     }
 
@@ -131,3 +149,56 @@ impl TextItem {
         self.page
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_items_unions_bounding_boxes() {
+        let items = vec![
+            TextItem::new("SALARY".to_string(), 140, 235, 176, 223, 0),
+            TextItem::new("ACME".to_string(), 179, 237, 203, 221, 0),
+            TextItem::new("LTD".to_string(), 138, 252, 160, 240, 0),
+        ];
+
+        let merged = TextItem::from_items(&items).unwrap();
+
+        assert_eq!(
+            merged,
+            TextItem::new("SALARY ACME LTD".to_string(), 138, 252, 203, 221, 0)
+        );
+    }
+
+    #[test]
+    fn test_merge_zero_height_item() {
+        // A zero-height item has no y-axis orientation of its own
+        let mut item = TextItem::new("-".to_string(), 337, 116, 343, 116, 0);
+        item.merge(&TextItem::new("30".to_string(), 346, 116, 358, 104, 0));
+
+        assert_eq!(
+            item,
+            TextItem::new("- 30".to_string(), 337, 116, 358, 104, 0)
+        );
+
+        let mut item = TextItem::new("April".to_string(), 277, 116, 307, 104, 0);
+        item.merge(&TextItem::new("-".to_string(), 337, 110, 343, 110, 0));
+
+        assert_eq!(
+            item,
+            TextItem::new("April -".to_string(), 277, 116, 343, 104, 0)
+        );
+    }
+
+    #[test]
+    fn test_from_items_rejects_cross_page_joins() {
+        let items = vec![
+            TextItem::new("062-000".to_string(), 268, 780, 310, 768, 0),
+            TextItem::new("1234".to_string(), 72, 49, 96, 37, 1),
+        ];
+
+        assert_eq!(TextItem::from_items(&items), None);
+        assert_eq!(TextItem::from_items(&items[..1]), Some(items[0].clone()));
+        assert_eq!(TextItem::from_items(&[]), None);
+    }
+}
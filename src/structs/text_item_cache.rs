@@ -0,0 +1,223 @@
+use crate::structs::TextItem;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+
+/// Cache key of a document: the SHA-256 digest of its content
+pub type ContentKey = [u8; 32];
+
+/// Cached extraction and identification results of a single document
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    text_items: Vec<TextItem>,
+    config_keys: Option<Vec<String>>,
+}
+
+/// Least recently used cache of the text items extracted from documents, and the
+/// config keys identified for them, keyed by the SHA-256 digest of the document
+/// content, so that different documents are not mistaken for each other. Avoids
+/// extracting and identifying the same document again on repeated calls (e.g.,
+/// identifying a document, then parsing it). A capacity of 0 disables the cache.
+#[derive(Debug, Clone, Default)]
+pub struct TextItemCache {
+    capacity: usize,
+    entries: HashMap<ContentKey, CacheEntry>,
+    /// Keys from least to most recently used
+    order: VecDeque<ContentKey>,
+    /// Number of lookups that found a cached result
+    pub hits: usize,
+    /// Number of lookups that did not find a cached result
+    pub misses: usize,
+}
+
+impl TextItemCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..Default::default()
+        }
+    }
+
+    /// Get the cache key of the document content
+    pub fn content_key(content: &[u8]) -> ContentKey {
+        Sha256::digest(content).into()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Set the maximum number of cached documents, evicting the least recently used
+    /// documents if there are more
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.order.len() > capacity {
+            self.evict();
+        }
+    }
+
+    /// Number of cached documents
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove all cached documents. The hit and miss counts are kept.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    /// Forget the config keys identified for every cached document, keeping their text
+    /// items, e.g., after the configs change
+    pub fn clear_config_keys(&mut self) {
+        for entry in self.entries.values_mut() {
+            entry.config_keys = None;
+        }
+    }
+
+    /// Get the cached text items of a document
+    pub fn get_text_items(&mut self, key: ContentKey) -> Option<Vec<TextItem>> {
+        let text_items = self.entries.get(&key).map(|entry| entry.text_items.clone());
+        self.record_lookup(key, text_items.is_some());
+        text_items
+    }
+
+    /// Cache the text items of a document, replacing any cached results
+    pub fn put_text_items(&mut self, key: ContentKey, text_items: Vec<TextItem>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.contains_key(&key) {
+            self.touch(key);
+        } else {
+            if self.order.len() >= self.capacity {
+                self.evict();
+            }
+            self.order.push_back(key);
+        }
+        self.entries.insert(
+            key,
+            CacheEntry {
+                text_items,
+                config_keys: None,
+            },
+        );
+    }
+
+    /// Get the cached config keys identified for a document
+    pub fn get_config_keys(&mut self, key: ContentKey) -> Option<Vec<String>> {
+        let config_keys = self
+            .entries
+            .get(&key)
+            .and_then(|entry| entry.config_keys.clone());
+        self.record_lookup(key, config_keys.is_some());
+        config_keys
+    }
+
+    /// Cache the config keys identified for a document. Ignored unless the text items
+    /// of the document are cached.
+    pub fn put_config_keys(&mut self, key: ContentKey, config_keys: Vec<String>) {
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.config_keys = Some(config_keys);
+        }
+    }
+
+    fn record_lookup(&mut self, key: ContentKey, hit: bool) {
+        if hit {
+            self.hits += 1;
+            self.touch(key);
+        } else {
+            self.misses += 1;
+        }
+    }
+
+    /// Mark a document as the most recently used
+    fn touch(&mut self, key: ContentKey) {
+        if let Some(position) = self.order.iter().position(|k| *k == key) {
+            self.order.remove(position);
+            self.order.push_back(key);
+        }
+    }
+
+    /// Remove the least recently used document
+    fn evict(&mut self) {
+        if let Some(key) = self.order.pop_front() {
+            self.entries.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_text_items(text: &str) -> Vec<TextItem> {
+        vec![TextItem::new(text.to_string(), 0, 10, 10, 0, 0)]
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        let mut cache = TextItemCache::default();
+        let key = TextItemCache::content_key(b"document");
+        cache.put_text_items(key, make_text_items("A"));
+
+        assert!(cache.is_empty());
+        assert!(cache.get_text_items(key).is_none());
+        assert_eq!((cache.hits, cache.misses), (0, 1));
+    }
+
+    #[test]
+    fn test_text_items_and_config_keys() {
+        let mut cache = TextItemCache::new(2);
+        let key = TextItemCache::content_key(b"document");
+        assert_ne!(key, TextItemCache::content_key(b"other document"));
+        // SHA-256 of "abc"
+        assert_eq!(
+            TextItemCache::content_key(b"abc")[..4],
+            [0xba, 0x78, 0x16, 0xbf]
+        );
+
+        assert!(cache.get_text_items(key).is_none());
+        cache.put_text_items(key, make_text_items("A"));
+        assert!(cache.get_config_keys(key).is_none());
+        cache.put_config_keys(key, vec!["au__test__1".to_string()]);
+
+        assert_eq!(cache.get_text_items(key), Some(make_text_items("A")));
+        assert_eq!(
+            cache.get_config_keys(key),
+            Some(vec!["au__test__1".to_string()])
+        );
+        assert_eq!((cache.hits, cache.misses), (2, 2));
+
+        cache.clear_config_keys();
+        assert!(cache.get_config_keys(key).is_none());
+        assert_eq!(cache.get_text_items(key), Some(make_text_items("A")));
+        assert_eq!((cache.hits, cache.misses), (3, 3));
+
+        cache.clear();
+        assert!(cache.get_text_items(key).is_none());
+        assert_eq!((cache.hits, cache.misses), (3, 4));
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = TextItemCache::new(2);
+        cache.put_text_items([1; 32], make_text_items("A"));
+        cache.put_text_items([2; 32], make_text_items("B"));
+        // Using 1 makes 2 the least recently used
+        cache.get_text_items([1; 32]);
+        cache.put_text_items([3; 32], make_text_items("C"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get_text_items([2; 32]).is_none());
+        assert!(cache.get_text_items([1; 32]).is_some());
+        assert!(cache.get_text_items([3; 32]).is_some());
+
+        cache.set_capacity(1);
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get_text_items([3; 32]).is_some());
+    }
+}
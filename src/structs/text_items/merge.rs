@@ -0,0 +1,146 @@
+use crate::structs::text_item::TextItem;
+use regex::Regex;
+
+/// Find the first number captured by a pattern in the joined text of the items.
+fn find_marker_number(items: &[TextItem], pattern: &Regex) -> Option<usize> {
+    let text = items
+        .iter()
+        .map(|item| item.text.as_str())
+        .collect::<Vec<&str>>()
+        .join(" ");
+    pattern
+        .captures(&text)
+        .and_then(|caps| caps.get(1))
+        .and_then(|m| m.as_str().parse::<usize>().ok())
+}
+
+/// Get sort keys for each part from a marker pattern. Returns None unless
+/// every part has a marker and all markers are distinct.
+fn get_marker_keys(parts: &[Vec<TextItem>], pattern: &Regex) -> Option<Vec<usize>> {
+    let keys: Vec<usize> = parts
+        .iter()
+        .map(|part| find_marker_number(part, pattern))
+        .collect::<Option<Vec<usize>>>()?;
+    let mut unique = keys.clone();
+    unique.sort_unstable();
+    unique.dedup();
+    if unique.len() != keys.len() {
+        return None;
+    }
+    Some(keys)
+}
+
+/// Order parts of a statement split across multiple files. Parts are ordered by
+/// "part X of Y" markers if present in every part, otherwise by the first
+/// "page X of Y" marker in each part (page continuity), otherwise as supplied.
+pub fn order_text_item_parts(parts: &[Vec<TextItem>]) -> Vec<usize> {
+    let part_pattern = Regex::new(r"(?i)\bpart\s+(\d+)\s+of\s+\d+\b").unwrap();
+    let page_pattern = Regex::new(r"(?i)\bpage\s+(\d+)\s+of\s+\d+\b").unwrap();
+    let mut order: Vec<usize> = (0..parts.len()).collect();
+    let keys =
+        get_marker_keys(parts, &part_pattern).or_else(|| get_marker_keys(parts, &page_pattern));
+    if let Some(keys) = keys {
+        order.sort_by_key(|&i| keys[i]);
+    }
+    order
+}
+
+/// Merge text items from a statement split across multiple files into a single
+/// stream of text items. Parts are ordered (see order_text_item_parts), then
+/// pages are renumbered so that each part follows on from the previous one.
+pub fn merge_text_item_parts(parts: &[Vec<TextItem>]) -> Vec<TextItem> {
+    let mut merged: Vec<TextItem> = Vec::new();
+    let mut page_offset = 0;
+    for i in order_text_item_parts(parts) {
+        let part = &parts[i];
+        if part.is_empty() {
+            continue;
+        }
+        let first_page = part.iter().map(|item| item.page).min().unwrap();
+        let last_page = part.iter().map(|item| item.page).max().unwrap();
+        for item in part {
+            let mut item = item.clone();
+            item.page = item.page - first_page + page_offset;
+            merged.push(item);
+        }
+        page_offset += last_page - first_page + 1;
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_text_item(text: &str, page: i32) -> TextItem {
+        TextItem::new(text.to_string(), 0, 0, 10, 10, page)
+    }
+
+    fn make_part(texts: &[(&str, i32)]) -> Vec<TextItem> {
+        texts
+            .iter()
+            .map(|(text, page)| make_text_item(text, *page))
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_renumbers_pages() {
+        let part1 = make_part(&[("Opening", 0), ("Balance", 1)]);
+        let part2 = make_part(&[("Closing", 0), ("Balance", 1)]);
+
+        let merged = merge_text_item_parts(&[part1, part2]);
+
+        let pages: Vec<i32> = merged.iter().map(|item| item.page).collect();
+        let texts: Vec<&str> = merged.iter().map(|item| item.text.as_str()).collect();
+        assert_eq!(pages, vec![0, 1, 2, 3]);
+        assert_eq!(texts, vec!["Opening", "Balance", "Closing", "Balance"]);
+    }
+
+    #[test]
+    fn test_merge_orders_by_part_markers() {
+        let part1 = make_part(&[("Part", 0), ("1", 0), ("of", 0), ("2", 0), ("First", 0)]);
+        let part2 = make_part(&[("PART", 0), ("2", 0), ("OF", 0), ("2", 0), ("Second", 0)]);
+
+        let merged = merge_text_item_parts(&[part2, part1]);
+
+        assert_eq!(merged[4].text, "First");
+        assert_eq!(merged[4].page, 0);
+        assert_eq!(merged[9].text, "Second");
+        assert_eq!(merged[9].page, 1);
+    }
+
+    #[test]
+    fn test_merge_orders_by_page_markers() {
+        let part1 = make_part(&[("Page", 0), ("1", 0), ("of", 0), ("3", 0), ("First", 0)]);
+        let part2 = make_part(&[("Page", 0), ("3", 0), ("of", 0), ("3", 0), ("Second", 0)]);
+
+        let merged = merge_text_item_parts(&[part2, part1]);
+
+        assert_eq!(merged[4].text, "First");
+        assert_eq!(merged[9].text, "Second");
+    }
+
+    #[test]
+    fn test_merge_keeps_order_without_markers() {
+        let part1 = make_part(&[("First", 0)]);
+        let part2 = make_part(&[("Part", 0), ("1", 0), ("of", 0), ("2", 0), ("Second", 0)]);
+
+        let merged = merge_text_item_parts(&[part1, part2]);
+
+        assert_eq!(merged[0].text, "First");
+        assert_eq!(merged[5].text, "Second");
+    }
+
+    #[test]
+    fn test_merge_skips_empty_parts() {
+        let part1 = make_part(&[("First", 2)]);
+        let part2: Vec<TextItem> = Vec::new();
+        let part3 = make_part(&[("Third", 5)]);
+
+        let merged = merge_text_item_parts(&[part1, part2, part3]);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].page, 0);
+        assert_eq!(merged[1].page, 1);
+    }
+}
@@ -1,7 +1,14 @@
 pub mod buffer;
+pub mod merge;
+pub mod normalise;
+pub mod scale;
 pub mod sort;
+pub mod synth;
 pub mod tokenise;
 
 pub use buffer::get_text_item_buffer;
-pub use sort::sort_items;
+pub use merge::merge_text_item_parts;
+pub use normalise::normalise_text;
+pub use scale::get_coordinate_scale;
+pub use sort::{merge_adjacent, sort_items};
 pub use tokenise::tokenise_items;
@@ -0,0 +1,67 @@
+use std::borrow::Cow;
+
+/// Normalise the text of a PDF text item before it is tokenised and parsed: the unicode
+/// minus sign and dashes used as negatives or ranges (e.g., "−123.45" or "1 Mar – 31 Mar")
+/// become an ASCII "-", no-break and other fixed-width spaces become a space, zero-width
+/// characters are removed, and ligatures (e.g., "ﬁ") are expanded to their letters, so
+/// that amounts parse and terms match. The em dash, often a blank cell, is kept. ASCII
+/// text is returned unchanged without copying.
+pub fn normalise_text(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    let mut normalised = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            // Hyphen, non-breaking hyphen, figure dash, en dash, minus sign, small and
+            // fullwidth hyphen-minus
+            '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2212}' | '\u{FE63}'
+            | '\u{FF0D}' => normalised.push('-'),
+            // No-break, en to hair, narrow no-break and ideographic spaces
+            '\u{00A0}' | '\u{2002}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}' => {
+                normalised.push(' ')
+            }
+            // Zero-width space and byte order mark
+            '\u{200B}' | '\u{FEFF}' => {}
+            '\u{FB00}' => normalised.push_str("ff"),
+            '\u{FB01}' => normalised.push_str("fi"),
+            '\u{FB02}' => normalised.push_str("fl"),
+            '\u{FB03}' => normalised.push_str("ffi"),
+            '\u{FB04}' => normalised.push_str("ffl"),
+            '\u{FB05}' | '\u{FB06}' => normalised.push_str("st"),
+            other => normalised.push(other),
+        }
+    }
+    Cow::Owned(normalised)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalise_text() {
+        let cases = [
+            ("\u{2212}123.45", "-123.45"),
+            ("123.45\u{2212}", "123.45-"),
+            ("\u{2013}$1,234.56", "-$1,234.56"),
+            ("\u{2012}5.00", "-5.00"),
+            ("\u{FF0D}5.00", "-5.00"),
+            ("1 Mar \u{2013} 31 Mar", "1 Mar - 31 Mar"),
+            ("1\u{00A0}234,56\u{202F}€", "1 234,56 €"),
+            ("1\u{2009}234.56", "1 234.56"),
+            ("Fixed \u{FB01}nance charge", "Fixed finance charge"),
+            ("O\u{FB03}ce \u{FB02}ights", "Office flights"),
+            ("Sta\u{FB00} \u{FB04}e", "Staff ffle"),
+            ("\u{FEFF}Date\u{200B}", "Date"),
+            // Kept as is
+            ("\u{2014}", "\u{2014}"),
+            ("£12.00 Café", "£12.00 Café"),
+            ("-123.45", "-123.45"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(normalise_text(text), expected, "{:?}", text);
+        }
+        assert!(matches!(normalise_text("plain"), Cow::Borrowed("plain")));
+    }
+}
@@ -0,0 +1,76 @@
+use crate::structs::text_item::TextItem;
+
+/// Width in PDF points of the widest expected page (A4 landscape)
+const MAX_PAGE_WIDTH: i32 = 842;
+/// Typical height in PDF points of statement text
+const TYPICAL_TEXT_HEIGHT: f64 = 10.0;
+
+/// Detect the scale of the text item coordinates relative to PDF points, as a power
+/// of ten. Some PDF generators emit coordinates in scaled user space units (e.g., 10x
+/// PDF points). Coordinates are scaled if they extend beyond the widest expected page,
+/// and the scale is then the median text height relative to typical text height,
+/// rounded to the nearest power of ten. Returns 1.0 for unscaled coordinates.
+pub fn get_coordinate_scale(items: &[TextItem]) -> f32 {
+    let max_x = items.iter().map(|item| item.x2).max().unwrap_or(0);
+    if max_x <= MAX_PAGE_WIDTH {
+        return 1.0;
+    }
+    let mut heights: Vec<i32> = items
+        .iter()
+        .map(|item| (item.y1 - item.y2).abs())
+        .filter(|height| *height > 0)
+        .collect();
+    if heights.is_empty() {
+        return 1.0;
+    }
+    heights.sort_unstable();
+    let median_height = heights[heights.len() / 2] as f64;
+    let exponent = (median_height / TYPICAL_TEXT_HEIGHT)
+        .log10()
+        .round()
+        .max(0.0);
+    10f64.powf(exponent) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_text_item(x1: i32, x2: i32, height: i32) -> TextItem {
+        TextItem::new("Text".to_string(), x1, 100 + height, x2, 100, 0)
+    }
+
+    #[test]
+    fn test_get_coordinate_scale_points() {
+        let items = vec![
+            make_text_item(72, 120, 12),
+            make_text_item(400, 530, 9),
+            make_text_item(72, 200, 11),
+        ];
+
+        assert_eq!(get_coordinate_scale(&items), 1.0);
+    }
+
+    #[test]
+    fn test_get_coordinate_scale_10x() {
+        let items = vec![
+            make_text_item(720, 1200, 120),
+            make_text_item(4000, 5300, 90),
+            make_text_item(720, 2000, 110),
+        ];
+
+        assert_eq!(get_coordinate_scale(&items), 10.0);
+    }
+
+    #[test]
+    fn test_get_coordinate_scale_wide_page_with_normal_text() {
+        let items = vec![make_text_item(72, 120, 12), make_text_item(900, 1100, 10)];
+
+        assert_eq!(get_coordinate_scale(&items), 1.0);
+    }
+
+    #[test]
+    fn test_get_coordinate_scale_empty() {
+        assert_eq!(get_coordinate_scale(&[]), 1.0);
+    }
+}
@@ -1,46 +1,66 @@
 use crate::structs::text_item::TextItem;
 use std::collections::HashMap;
 
-/// Calculate average character width for a single TextItem
+/// Calculate the average width of the non-whitespace characters of a TextItem
 fn average_char_width(item: &TextItem) -> f32 {
-    let width = item.x2 - item.x1;
-    let num_chars = item.text.len() as i32;
+    let num_chars = item.text.chars().filter(|c| !c.is_whitespace()).count();
     if num_chars == 0 {
         0.0
     } else {
-        width as f32 / num_chars as f32
+        (item.x2 - item.x1) as f32 / num_chars as f32
     }
 }
 
-// Return new array of items sorted by x position with close items merged
-fn fix_by_x(items: &mut Vec<TextItem>, x_gap: f32) -> Vec<TextItem> {
-    // Sort items by increasing X position
-    items.sort_by_key(|a| a.x1);
-    // Return if x_gap is zero, no merging needed
-    if x_gap == 0.0 {
-        return items.clone();
-    }
-    let mut fixed_items = Vec::new();
+/// Merge items of a line, sorted by increasing x1, into the previous item if they
+/// start before its x2 plus x_gap times its average character width (and not before
+/// its x1 by more than that)
+fn merge_line(items: Vec<TextItem>, x_gap: f32) -> Vec<TextItem> {
+    let mut merged: Vec<TextItem> = Vec::with_capacity(items.len());
     for item in items {
-        if let Some(last_item) = fixed_items.last_mut() {
-            // Merge new line into last item if close enough
-            let avg_char_width = average_char_width(last_item);
-            let x_merge_tol = (avg_char_width * x_gap) as i32;
-            // x1 of next item overlaps within x range of last item
-            let x1_within_tol =
-                item.x1 >= last_item.x1 - x_merge_tol && item.x1 <= last_item.x2 + x_merge_tol;
-            if x1_within_tol {
-                // Merge into last item
-                last_item.merge(item);
+        if let Some(last_item) = merged.last_mut() {
+            let x_merge_tol = average_char_width(last_item) * x_gap;
+            let x1 = item.x1 as f32;
+            if x1 >= last_item.x1 as f32 - x_merge_tol && x1 <= last_item.x2 as f32 + x_merge_tol {
+                last_item.merge(&item);
                 continue;
             }
         }
-        fixed_items.push(item.clone());
+        merged.push(item);
+    }
+    merged
+}
+
+/// Join adjacent items on the same line into single items, keeping the order of the
+/// lines. Consecutive items on the same page whose y1 fall in the same bin of size
+/// y_bin (equal y1 if y_bin is 0.0) form a line, which is sorted by x1. An item is
+/// joined to the previous one (text separated by a space, with the combined x1 to x2)
+/// if the horizontal gap between them is at most x_gap times the average character
+/// width of the previous item. This rejoins text split by the PDF extraction, such as
+/// amounts with space-separated thousands ("1 234.56") or account numbers
+/// ("12-3456 7890123"). Returns the items unchanged if x_gap is 0.0.
+pub fn merge_adjacent(items: &[TextItem], y_bin: f32, x_gap: f32) -> Vec<TextItem> {
+    if x_gap == 0.0 {
+        return items.to_vec();
     }
-    fixed_items
+    let line_key = |item: &TextItem| {
+        let y = if y_bin == 0.0 {
+            item.y1
+        } else {
+            (item.y1 as f32 / y_bin) as i32
+        };
+        (item.page, y)
+    };
+    let mut merged = Vec::with_capacity(items.len());
+    for line in items.chunk_by(|a, b| line_key(a) == line_key(b)) {
+        let mut line = line.to_vec();
+        line.sort_by_key(|item| item.x1);
+        merged.extend(merge_line(line, x_gap));
+    }
+    merged
 }
 
-/// Ensure items are sorted by page, y position, and x position
+/// Ensure items are sorted by page, y position, and x position, with adjacent items
+/// of each line merged (see merge_adjacent)
 pub fn sort_items(items: &Vec<TextItem>, x_gap: f32, y_bin: f32) -> Vec<TextItem> {
     // Return if no items or t_bin is zero
     if items.is_empty() || y_bin == 0.0 {
@@ -77,11 +97,63 @@ pub fn sort_items(items: &Vec<TextItem>, x_gap: f32, y_bin: f32) -> Vec<TextItem
             }
             for y_bin in y_bin_keys {
                 if let Some(mut bin_items) = y_bin_map.get(&y_bin).cloned() {
-                    let fixed_items = fix_by_x(&mut bin_items, x_gap);
-                    sorted_items.extend(fixed_items);
+                    bin_items.sort_by_key(|a| a.x1);
+                    if x_gap == 0.0 {
+                        sorted_items.extend(bin_items);
+                    } else {
+                        sorted_items.extend(merge_line(bin_items, x_gap));
+                    }
                 }
             }
         }
     }
     sorted_items
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_text_item(text: &str, x1: i32, x2: i32, y1: i32) -> TextItem {
+        TextItem::new(text.to_string(), x1, y1, x2, y1 - 10, 0)
+    }
+
+    #[test]
+    fn test_merge_adjacent_joins_split_amount() {
+        // Extracted out of order, with a 3 unit gap (half a character)
+        let items = vec![
+            make_text_item("Balance", 100, 142, 50),
+            make_text_item("250.00", 491, 530, 50),
+            make_text_item("1", 482, 488, 50),
+            make_text_item("Next", 100, 124, 70),
+        ];
+
+        let merged = merge_adjacent(&items, 3.0, 1.0);
+
+        let texts: Vec<(&str, i32, i32)> = merged
+            .iter()
+            .map(|item| (item.text.as_str(), item.x1, item.x2))
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                ("Balance", 100, 142),
+                ("1 250.00", 482, 530),
+                ("Next", 100, 124)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_adjacent_keeps_distant_items() {
+        let items = vec![
+            make_text_item("12-3456", 100, 142, 50),
+            make_text_item("7890123", 148, 190, 50),
+        ];
+
+        // Gap of 6 units, one character at x_gap 1.0
+        assert_eq!(merge_adjacent(&items, 0.0, 1.0)[0].text, "12-3456 7890123");
+        assert_eq!(merge_adjacent(&items, 0.0, 0.5).len(), 2);
+        assert_eq!(merge_adjacent(&items, 0.0, 0.0), items);
+    }
+}
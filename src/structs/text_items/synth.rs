@@ -0,0 +1,387 @@
+use chrono::{Days, NaiveDate};
+
+/// Layout text of the synthetic statement header, on the first page. Matches the
+/// tests/fixtures/test3_config.json config.
+const HEADER: &str = r#"["Sample",72,108,49,37]["Savings",111,153,49,37]["Bank",156,180,49,37]
+["Everyday",77,125,86,74]["Account",128,170,86,74]["Statement",173,227,86,74]
+["Statement",77,131,116,104]["Period:",134,176,116,104]["1",268,274,116,104]["January",277,319,116,104]["2024",322,346,116,104]
+["Account",77,119,131,119]["number:",122,164,131,119]["062-000",268,310,131,119]["1234",313,337,131,119]["5678",340,364,131,119]"#;
+
+/// Descriptions of the synthetic transactions, in turn
+const DESCRIPTIONS: [&str; 6] = [
+    "SALARY ACME PTY LTD",
+    "RENT PAYMENT",
+    "GROCERIES",
+    "FUEL",
+    "PHARMACY",
+    "REFUND",
+];
+
+/// Opening balance of the synthetic statement in cents, large enough that the
+/// balance stays positive
+const OPENING_CENTS: i64 = 100_000_000;
+
+/// Transaction rows per page
+const ROWS_PER_PAGE: usize = 40;
+
+/// Width in points of a character of the synthetic text
+const CHAR_WIDTH: i32 = 6;
+
+/// Generate the layout text (see layout_to_text_items) of a synthetic savings
+/// statement with n_rows transactions over the year 2024, for benchmarks and tests.
+/// The statement is parsed without errors by the tests/fixtures/test3_config.json
+/// config. Amounts and descriptions follow a fixed sequence, so the statement is the
+/// same for the same number of rows.
+pub fn generate_statement(n_rows: usize) -> String {
+    let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let amounts: Vec<i64> = (0..n_rows).map(amount_cents).collect();
+    let closing = OPENING_CENTS + amounts.iter().sum::<i64>();
+
+    let mut lines = vec!["[Page 0]".to_string(), HEADER.to_string()];
+    lines.push(line(&[
+        ("Opening", 77, 146),
+        ("balance:", 122, 146),
+        (&format!("${}", format_cents(OPENING_CENTS)), 268, 146),
+    ]));
+    lines.push(line(&[
+        ("Closing", 77, 161),
+        ("balance:", 122, 161),
+        (&format!("${}", format_cents(closing)), 268, 161),
+    ]));
+    lines.push(line(&[("Transactions", 77, 197)]));
+    lines.push(line(&[
+        ("Date", 72, 218),
+        ("Description", 140, 218),
+        ("Amount", 414, 218),
+        ("Balance", 488, 218),
+    ]));
+
+    let mut balance = OPENING_CENTS;
+    let mut y1 = 218;
+    for (row, amount) in amounts.iter().enumerate() {
+        if row > 0 && row.is_multiple_of(ROWS_PER_PAGE) {
+            lines.push(format!("[Page {}]", row / ROWS_PER_PAGE));
+            y1 = 43;
+        }
+        y1 += 17;
+        balance += amount;
+        let date = start + Days::new((row * 366 / n_rows.max(1)) as u64);
+        let date = date.format("%d/%m/%Y").to_string();
+        let amount = format_cents(*amount);
+        let balance = format_cents(balance);
+
+        let mut items = vec![(date.as_str(), 72, y1)];
+        let mut x1 = 140;
+        for word in DESCRIPTIONS[row % DESCRIPTIONS.len()].split(' ') {
+            items.push((word, x1, y1));
+            x1 += width(word) + 3;
+        }
+        items.push((amount.as_str(), 450 - width(&amount), y1));
+        items.push((balance.as_str(), 530 - width(&balance), y1));
+        lines.push(line(&items));
+    }
+
+    let y1 = y1 + 27;
+    lines.push(line(&[
+        ("End", 77, y1),
+        ("of", 98, y1),
+        ("transactions", 113, y1),
+    ]));
+    lines.join("\n")
+}
+
+/// Horizontal alignment of a column of a synthetic transaction table
+#[derive(Clone, Copy)]
+enum Align {
+    /// Text starts at the x of the column
+    Left,
+    /// Text ends at the x of the column
+    Right,
+}
+
+/// Column of a synthetic transaction table: its header and the edge and alignment of
+/// its text
+struct Column {
+    header: &'static str,
+    x: i32,
+    align: Align,
+}
+
+/// Synthetic transaction: days after the statement start, description and amount in
+/// cents
+type ExampleTransaction = (u64, &'static str, i64);
+
+/// Transactions of the example checking statement
+const CHECKING_TRANSACTIONS: [ExampleTransaction; 4] = [
+    (0, "SALARY DEPOSIT", 120_000),
+    (2, "ELECTRICITY BILL", -8_540),
+    (9, "SUPERMARKET", -6_215),
+    (19, "INTEREST", 55),
+];
+
+/// Transactions of the example credit card statement, purchases positive as printed
+const CREDIT_CARD_TRANSACTIONS: [ExampleTransaction; 4] = [
+    (1, "CORNER CAFE", 480),
+    (5, "BOOKSHOP", 3_220),
+    (11, "PAYMENT RECEIVED", -25_000),
+    (17, "FUEL STATION", 6_100),
+];
+
+/// Generate the layout text of the example checking statement, a month of an account
+/// with a balance column. Parsed without errors by the
+/// examples/data/zz__example__checking__1.json config.
+pub fn generate_example_checking() -> String {
+    let start = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+    let end = NaiveDate::from_ymd_opt(2024, 5, 31).unwrap();
+    let opening = 50_000;
+    let mut balance = opening;
+    let rows: Vec<Vec<String>> = CHECKING_TRANSACTIONS
+        .iter()
+        .map(|(days, description, amount)| {
+            balance += amount;
+            vec![
+                (start + Days::new(*days)).format("%d/%m/%Y").to_string(),
+                description.to_string(),
+                format_cents(*amount),
+                format_cents(balance),
+            ]
+        })
+        .collect();
+    let period = format!(
+        "{} - {}",
+        start.format("%-d %B %Y"),
+        end.format("%-d %B %Y")
+    );
+    example_layout(
+        "Example Everyday Bank",
+        "Example Checking Account Statement",
+        &[
+            ("Statement period:", period),
+            ("Account number:", "123-456 00012345".to_string()),
+            ("Opening balance:", format!("${}", format_cents(opening))),
+            ("Closing balance:", format!("${}", format_cents(balance))),
+        ],
+        "Account activity",
+        &[
+            Column {
+                header: "Date",
+                x: 72,
+                align: Align::Left,
+            },
+            Column {
+                header: "Description",
+                x: 140,
+                align: Align::Left,
+            },
+            Column {
+                header: "Amount",
+                x: 450,
+                align: Align::Right,
+            },
+            Column {
+                header: "Balance",
+                x: 530,
+                align: Align::Right,
+            },
+        ],
+        &rows,
+    )
+}
+
+/// Generate the layout text of the example credit card statement, a month of a card
+/// without a balance column, so that balances are computed from the amounts. Parsed
+/// without errors by the examples/data/zz__example__credit_card__1.json config.
+pub fn generate_example_credit_card() -> String {
+    let start = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+    let end = NaiveDate::from_ymd_opt(2024, 5, 31).unwrap();
+    let previous = 25_000;
+    let new = previous + CREDIT_CARD_TRANSACTIONS.iter().map(|tx| tx.2).sum::<i64>();
+    let rows: Vec<Vec<String>> = CREDIT_CARD_TRANSACTIONS
+        .iter()
+        .map(|(days, description, amount)| {
+            vec![
+                (start + Days::new(*days)).format("%m/%d").to_string(),
+                description.to_string(),
+                format_cents(*amount),
+            ]
+        })
+        .collect();
+    let period = format!(
+        "{} - {}",
+        start.format("%B %-d, %Y"),
+        end.format("%B %-d, %Y")
+    );
+    example_layout(
+        "Example Card Company",
+        "Example Credit Card Statement",
+        &[
+            ("Statement period:", period),
+            ("Card number:", "XXXX XXXX XXXX 7310".to_string()),
+            ("Previous balance:", format!("${}", format_cents(previous))),
+            ("New balance:", format!("${}", format_cents(new))),
+        ],
+        "Card activity",
+        &[
+            Column {
+                header: "Date",
+                x: 72,
+                align: Align::Left,
+            },
+            Column {
+                header: "Description",
+                x: 110,
+                align: Align::Left,
+            },
+            Column {
+                header: "Amount",
+                x: 490,
+                align: Align::Right,
+            },
+        ],
+        &rows,
+    )
+}
+
+/// Layout text of a one-page example statement: the bank name and statement title,
+/// a line per labelled field, then the transaction table between its title and an
+/// "End of" line, laid out as by generate_statement
+fn example_layout(
+    bank_name: &str,
+    title: &str,
+    fields: &[(&str, String)],
+    table_title: &str,
+    columns: &[Column],
+    rows: &[Vec<String>],
+) -> String {
+    let mut lines = vec![
+        "[Page 0]".to_string(),
+        line(&words(bank_name, 72, 49)),
+        line(&words(title, 77, 86)),
+    ];
+    let mut y1 = 101;
+    for (label, value) in fields {
+        y1 += 15;
+        let mut items = words(label, 77, y1);
+        items.extend(words(value, 268, y1));
+        lines.push(line(&items));
+    }
+
+    y1 += 36;
+    lines.push(line(&words(table_title, 77, y1)));
+    y1 += 21;
+    let headers: Vec<String> = columns.iter().map(|c| c.header.to_string()).collect();
+    for cells in std::iter::once(&headers).chain(rows) {
+        let mut items = Vec::new();
+        for (column, cell) in columns.iter().zip(cells) {
+            let x1 = match column.align {
+                Align::Left => column.x,
+                Align::Right => column.x - width(cell),
+            };
+            items.extend(words(cell, x1, y1));
+        }
+        lines.push(line(&items));
+        y1 += 17;
+    }
+
+    let end = format!("End of {}", table_title.to_lowercase());
+    lines.push(line(&words(&end, 77, y1 + 10)));
+    lines.join("\n")
+}
+
+/// Items of the words of the text, starting at x1 and separated by a space
+fn words(text: &str, x1: i32, y1: i32) -> Vec<(&str, i32, i32)> {
+    let mut x1 = x1;
+    text.split(' ')
+        .map(|word| {
+            let item = (word, x1, y1);
+            x1 += width(word) + CHAR_WIDTH;
+            item
+        })
+        .collect()
+}
+
+/// Amount of a synthetic transaction in cents: a monthly salary, else a debit
+fn amount_cents(row: usize) -> i64 {
+    if row.is_multiple_of(DESCRIPTIONS.len()) {
+        250_000 + (row % 7) as i64 * 1_000
+    } else {
+        -(((row * 7_919) % 40_000) as i64 + 100)
+    }
+}
+
+/// Format cents with thousands separators, like "-1,234.56"
+fn format_cents(cents: i64) -> String {
+    let units = (cents.abs() / 100).to_string();
+    let mut grouped = String::new();
+    for (i, c) in units.chars().enumerate() {
+        if i > 0 && (units.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    let sign = if cents < 0 { "-" } else { "" };
+    format!("{}{}.{:02}", sign, grouped, cents.abs() % 100)
+}
+
+fn width(text: &str) -> i32 {
+    CHAR_WIDTH * text.chars().count() as i32
+}
+
+/// Layout text of a line of items, each given by its text, x1 and y1
+fn line(items: &[(&str, i32, i32)]) -> String {
+    items
+        .iter()
+        .map(|(text, x1, y1)| {
+            format!(
+                "[\"{}\",{},{},{},{}]",
+                text,
+                x1,
+                x1 + width(text),
+                y1,
+                y1 - 12
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixers::FixersMode;
+    use crate::parsers::flows::config_json_file_to_config::from_json_str;
+    use crate::parsers::flows::layout_to_text_items::layout_to_text_items;
+    use crate::parsers::flows::text_items_to_statement_datas::text_items_to_statement_datas;
+
+    #[test]
+    fn test_format_cents() {
+        assert_eq!(format_cents(0), "0.00");
+        assert_eq!(format_cents(-2_450), "-24.50");
+        assert_eq!(format_cents(123_456_789), "1,234,567.89");
+    }
+
+    #[test]
+    fn test_generated_statement_parses_without_errors() {
+        let cfg = from_json_str(include_str!("../../../tests/fixtures/test3_config.json")).unwrap();
+        let items = layout_to_text_items(&generate_statement(100)).unwrap();
+
+        let results =
+            text_items_to_statement_datas(&items, &vec![cfg], FixersMode::All, false, false)
+                .unwrap();
+
+        assert!(results[0].errors.is_empty(), "{:?}", results[0].errors);
+        assert_eq!(results[0].proto_transactions.len(), 100);
+    }
+
+    #[test]
+    fn test_example_layouts_are_generated() {
+        // Regenerate with `cargo run --example generate_example_layouts`
+        assert_eq!(
+            include_str!("../../../examples/data/zz__example__checking__1_layout.txt"),
+            generate_example_checking() + "\n"
+        );
+        assert_eq!(
+            include_str!("../../../examples/data/zz__example__credit_card__1_layout.txt"),
+            generate_example_credit_card() + "\n"
+        );
+    }
+}
@@ -1,12 +1,15 @@
 use crate::structs::text_item::TextItem;
+use crate::structs::text_items::normalise_text;
 
 /// Splits each TextItem's text into separate tokens based on whitespace,
 /// creating a new TextItem for each token while preserving the original
-/// positional and page information.
+/// positional and page information. The text is normalised first (see
+/// normalise_text).
 pub fn tokenise_items(items: &Vec<TextItem>) -> Vec<TextItem> {
     let mut tokenised_items: Vec<TextItem> = Vec::new();
     for item in items {
-        let parts = item.text.split_whitespace();
+        let text = normalise_text(&item.text);
+        let parts = text.split_whitespace();
         for part in parts {
             let token_item = TextItem {
                 text: part.to_string(),
@@ -21,3 +24,34 @@ pub fn tokenise_items(items: &Vec<TextItem>) -> Vec<TextItem> {
     }
     tokenised_items
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenise_items_normalises_text() {
+        let items = vec![
+            TextItem::new(
+                "062-000\u{a0}1234\u{a0}5678".to_string(),
+                268,
+                119,
+                364,
+                131,
+                0,
+            ),
+            TextItem::new("O\u{fb03}ce supplies".to_string(), 140, 257, 215, 269, 0),
+            TextItem::new("\u{2212}24.50".to_string(), 414, 257, 450, 269, 0),
+        ];
+
+        let tokens: Vec<String> = tokenise_items(&items)
+            .into_iter()
+            .map(|item| item.text)
+            .collect();
+
+        assert_eq!(
+            tokens,
+            vec!["062-000", "1234", "5678", "Office", "supplies", "-24.50"]
+        );
+    }
+}
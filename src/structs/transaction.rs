@@ -9,6 +9,8 @@ pub struct Transaction {
     pub amount: f64,
     /// Balance after the transaction
     pub balance: f64,
+    /// Category of the transaction, if tagged (see Categorizer)
+    pub category: Option<String>,
 }
 
 impl Transaction {
@@ -18,6 +20,7 @@ impl Transaction {
             description,
             amount,
             balance,
+            category: None,
         }
     }
 }
@@ -0,0 +1,177 @@
+/// Apply the replacements to a fixture in turn, checking that each one changes it, so
+/// that a test cannot pass on the unchanged fixture
+pub fn patch(fixture: &str, replacements: &[(&str, &str)]) -> String {
+    replacements
+        .iter()
+        .fold(fixture.to_string(), |text, (from, to)| {
+            assert!(text.contains(from), "Fixture does not contain {:?}", from);
+            text.replace(from, to)
+        })
+}
+
+/// Layout text of tests/fixtures/test3_layout.txt down to the opening balance
+const TEST3_HEADER: &str = r#"["Sample",72,108,49,37]["Savings",111,153,49,37]["Bank",156,180,49,37]
+["Everyday",77,125,86,74]["Account",128,170,86,74]["Statement",173,227,86,74]
+["Statement",77,131,116,104]["Period:",134,176,116,104]["1",268,274,116,104]["April",277,307,116,104]["2024",310,334,116,104]["-",337,343,116,104]["30",346,358,116,104]["April",361,391,116,104]["2024",394,418,116,104]
+["Account",77,119,131,119]["number:",122,164,131,119]["062-000",268,310,131,119]["1234",313,337,131,119]["5678",340,364,131,119]
+["Opening",77,119,146,134]["balance:",122,170,146,134]["$1,000.00",268,322,146,134]"#;
+
+/// Width in points of a character of a built layout
+const CHAR_WIDTH: i32 = 6;
+
+/// Builder of layout text (see layout_to_text_items) like
+/// tests/fixtures/test3_layout.txt, read by tests/fixtures/test3_config.json, with the
+/// lines of a test in place of its transactions. Words are 12 points high, 6 points
+/// wide per character and 3 points apart.
+pub struct Test3Layout {
+    lines: Vec<String>,
+    /// y1 of the last line
+    y1: i32,
+    /// Distance between the y1 of consecutive lines
+    line_spacing: i32,
+}
+
+impl Test3Layout {
+    /// Start the statement of April 2024, with an opening balance of $1,000.00 and the
+    /// given closing balance (e.g., "$1,105.50"), down to the "Transactions" title
+    pub fn new(closing_balance: &str) -> Self {
+        let layout = Test3Layout {
+            lines: vec!["[Page 0]".to_string(), TEST3_HEADER.to_string()],
+            y1: 146,
+            line_spacing: 17,
+        };
+        let closing_x1 = 322 - width(closing_balance);
+        layout
+            .line_at(
+                161,
+                &[("Closing balance:", 77), (closing_balance, closing_x1)],
+            )
+            .line_at(197, &[("Transactions", 77)])
+    }
+
+    /// Set the distance between the y1 of the following lines
+    pub fn with_line_spacing(mut self, line_spacing: i32) -> Self {
+        self.line_spacing = line_spacing;
+        self
+    }
+
+    /// Add a line of items, each given by its words and x1, one line spacing below the
+    /// last line
+    pub fn line(self, items: &[(&str, i32)]) -> Self {
+        let y1 = self.y1 + self.line_spacing;
+        self.line_at(y1, items)
+    }
+
+    fn line_at(mut self, y1: i32, items: &[(&str, i32)]) -> Self {
+        let mut line = String::new();
+        for &(text, x1) in items {
+            let mut x1 = x1;
+            for word in text.split(' ') {
+                let x2 = x1 + width(word);
+                line.push_str(&format!(
+                    "[\"{}\",{},{},{},{}]",
+                    word.replace('"', "\\\""),
+                    x1,
+                    x2,
+                    y1,
+                    y1 - 12
+                ));
+                x1 = x2 + 3;
+            }
+        }
+        self.lines.push(line);
+        self.y1 = y1;
+        self
+    }
+
+    /// Add the column headers of the transaction table
+    pub fn table_header(self) -> Self {
+        let y1 = self.y1 + 21;
+        self.line_at(
+            y1,
+            &[
+                ("Date", 72),
+                ("Description", 140),
+                ("Amount", 414),
+                ("Balance", 488),
+            ],
+        )
+    }
+
+    /// Add a transaction row, with the amount and the balance right-aligned with their
+    /// headers. Empty fields are left out.
+    pub fn row(self, date: &str, description: &str, amount: &str, balance: &str) -> Self {
+        let items: Vec<(&str, i32)> = [
+            (date, 72),
+            (description, 140),
+            (amount, 450 - width(amount)),
+            (balance, 530 - width(balance)),
+        ]
+        .into_iter()
+        .filter(|(text, _)| !text.is_empty())
+        .collect();
+        self.line(&items)
+    }
+
+    /// Leave a blank line
+    pub fn blank_line(mut self) -> Self {
+        self.y1 += self.line_spacing;
+        self
+    }
+
+    /// Add a line of words at the foot of the page
+    pub fn footer(self, text: &str) -> Self {
+        self.line_at(780, &[(text, 77)])
+    }
+
+    /// Start a new page, headed by the bank name
+    pub fn page(mut self) -> Self {
+        let page = self
+            .lines
+            .iter()
+            .filter(|line| line.starts_with("[Page "))
+            .count();
+        self.lines.push(format!("[Page {}]", page));
+        self.line_at(49, &[("Sample Savings Bank", 72)])
+    }
+
+    /// Add the end of the transactions and get the layout text
+    pub fn build(self) -> String {
+        let y1 = self.y1 + 27;
+        self.line_at(y1, &[("End of transactions", 77)])
+            .lines
+            .join("\n")
+    }
+}
+
+fn width(text: &str) -> i32 {
+    CHAR_WIDTH * text.chars().count() as i32
+}
+
+/// Layout with ditto marks under the date of the rent payment and under the date and
+/// description of the last two groceries rows
+pub fn ditto_layout() -> String {
+    Test3Layout::new("$1,085.50")
+        .table_header()
+        .row("02/04/2024", "SALARY ACME PTY LTD", "250.00", "1,250.00")
+        .row("〃", "RENT PAYMENT", "-120.00", "1,130.00")
+        .row("05/04/2024", "GROCERIES", "-24.50", "1,105.50")
+        .row("\"", "\"", "-10.00", "1,095.50")
+        .row("''", "''", "-10.00", "1,085.50")
+        .build()
+}
+
+/// Layout with the salary description wrapped onto two lines and rent onto three,
+/// followed by a groceries transaction starting with a date
+pub fn multiline_layout() -> String {
+    Test3Layout::new("$1,105.50")
+        .table_header()
+        .with_line_spacing(12)
+        .row("02/04/2024", "SALARY ACME PTY LTD", "250.00", "1,250.00")
+        .row("", "SYDNEY BRANCH", "", "")
+        .row("03/04/2024", "RENT PAYMENT", "-120.00", "1,130.00")
+        .row("", "REF 1042", "", "")
+        .row("", "FLAT 3", "", "")
+        .row("05/04/2024", "GROCERIES", "-24.50", "1,105.50")
+        .build()
+}
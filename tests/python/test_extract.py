@@ -0,0 +1,192 @@
+"""Tests for extracting text items from PDFs, including mapping rotated text to the
+orientation of upright pages."""
+
+import pytest
+from pdfminer.pdfdocument import PDFPasswordIncorrect
+from pdfplumber.utils.exceptions import PdfminerException
+from transtractor.exceptions import (
+    PdfEncrypted,
+    PdfExtractError,
+    PdfMalformed,
+    PdfNoTextContent,
+)
+from transtractor.utils import extract
+from transtractor.utils.extract import (
+    flipped_text,
+    input_kind,
+    pdf_to_text_items,
+    rotated_text_direction,
+    to_reading_orientation,
+)
+
+# Landscape statement page (842 x 595) drawn sideways on a portrait page (595 x 842)
+PAGE_WIDTH = 595.0
+PAGE_HEIGHT = 842.0
+
+
+def make_rotated_line(direction: str, line: int) -> list[dict]:
+    """Words of a line of rotated text, "Opening balance 1,000.00", as extracted by
+    pdfplumber. Lines are 15 points apart, starting at the edge of the page where
+    the text would be on the page turned upright."""
+    words = []
+    start = 50.0
+    for text in ("Opening", "balance", "1,000.00"):
+        length = 6.0 * len(text)
+        if direction == "btt":
+            # Reading up the page, first line on the left
+            x0 = 40.0 + 15 * line
+            top, bottom = PAGE_HEIGHT - start - length, PAGE_HEIGHT - start
+        else:
+            # Reading down the page, first line on the right
+            x0 = PAGE_WIDTH - 50.0 - 15 * line
+            top, bottom = start, start + length
+        words.append(
+            {"text": text, "x0": x0, "x1": x0 + 10, "top": top, "bottom": bottom}
+        )
+        start += length + 4
+    return words
+
+
+def test_rotated_text_direction():
+    """Test that the direction of the rotated text of a page is detected from the
+    text matrix of most of its characters."""
+    upright = {"matrix": (1, 0, 0, 1, 100, 700)}
+    anticlockwise = {"matrix": (0, 1, -1, 0, 100, 700)}
+    clockwise = {"matrix": (0, -1, 1, 0, 100, 700)}
+
+    assert rotated_text_direction([upright] * 5 + [anticlockwise]) is None
+    assert rotated_text_direction([upright] + [anticlockwise] * 5) == "btt"
+    assert rotated_text_direction([clockwise] * 3) == "ttb"
+    assert rotated_text_direction([]) is None
+
+
+def test_to_reading_orientation_groups_lines():
+    """Test that words of rotated lines map to shared y values in reading order,
+    with x increasing along each line."""
+    for direction in ("btt", "ttb"):
+        lines = []
+        for line in range(2):
+            lines.append(
+                [
+                    to_reading_orientation(word, direction, PAGE_WIDTH, PAGE_HEIGHT)
+                    for word in make_rotated_line(direction, line)
+                ]
+            )
+
+        for boxes in lines:
+            # Same line: shared y, x increasing, y1 below y2
+            assert len({(y1, y2) for _x1, y1, _x2, y2 in boxes}) == 1
+            assert [x1 for x1, _y1, _x2, _y2 in boxes] == sorted(
+                x1 for x1, _y1, _x2, _y2 in boxes
+            )
+            assert all(x1 < x2 and y1 > y2 for x1, y1, x2, y2 in boxes)
+        # First line above the second, 15 points apart
+        assert lines[1][0][1] - lines[0][0][1] == 15
+        assert lines[0][0][0] == 50.0
+
+
+# Single empty page, as in a scanned statement whose pages are only images
+EMPTY_PAGE_PDF = (
+    b"%PDF-1.4\n"
+    b"1 0 obj << /Type /Catalog /Pages 2 0 R >> endobj\n"
+    b"2 0 obj << /Type /Pages /Kids [3 0 R] /Count 1 >> endobj\n"
+    b"3 0 obj << /Type /Page /Parent 2 0 R /MediaBox [0 0 595 842] >> endobj\n"
+    b"trailer << /Root 1 0 R >>\n"
+    b"%%EOF\n"
+)
+
+
+def test_pdf_to_text_items_reports_unreadable_pdfs(tmp_path, monkeypatch):
+    """Test that PDFs without extractable text raise the specific PdfExtractError."""
+    corrupt = tmp_path / "corrupt.pdf"
+    corrupt.write_bytes(b"not a pdf")
+    with pytest.raises(PdfMalformed):
+        pdf_to_text_items(str(corrupt))
+
+    scanned = tmp_path / "scanned.pdf"
+    scanned.write_bytes(EMPTY_PAGE_PDF)
+    with pytest.raises(PdfNoTextContent, match="no text on any page"):
+        pdf_to_text_items(str(scanned))
+
+    def open_encrypted(path):
+        """Fail as pdfplumber does for a password-protected PDF."""
+        try:
+            raise PDFPasswordIncorrect()
+        except PDFPasswordIncorrect as e:
+            raise PdfminerException(e) from None
+
+    monkeypatch.setattr(extract.pdfplumber, "open", open_encrypted)
+    with pytest.raises(PdfEncrypted, match="password-protected"):
+        pdf_to_text_items(str(scanned))
+    assert issubclass(PdfEncrypted, PdfExtractError)
+
+
+# Page whose content stream flips the y axis, so that the second line, drawn at a
+# larger y, is below the first
+FLIPPED_CONTENT = (
+    b"1 0 0 -1 0 842 cm BT /F1 12 Tf 72 100 Td (Opening balance) Tj "
+    b"0 20 Td (Closing balance) Tj ET"
+)
+FLIPPED_PAGE_PDF = (
+    b"%PDF-1.4\n"
+    b"1 0 obj << /Type /Catalog /Pages 2 0 R >> endobj\n"
+    b"2 0 obj << /Type /Pages /Kids [3 0 R] /Count 1 >> endobj\n"
+    b"3 0 obj << /Type /Page /Parent 2 0 R /MediaBox [0 0 595 842] "
+    b"/Resources << /Font << /F1 5 0 R >> >> /Contents 4 0 R >> endobj\n"
+    b"4 0 obj << /Length "
+    + str(len(FLIPPED_CONTENT)).encode()
+    + b" >> stream\n"
+    + FLIPPED_CONTENT
+    + b"\nendstream endobj\n"
+    b"5 0 obj << /Type /Font /Subtype /Type1 /BaseFont /Helvetica >> endobj\n"
+    b"trailer << /Root 1 0 R >>\n"
+    b"%%EOF\n"
+)
+
+
+def test_flipped_text():
+    """Test that pages drawn in a vertically flipped coordinate space are detected
+    from the text matrix of most of their characters."""
+    upright = {"matrix": (1, 0, 0, 1, 100, 700)}
+    flipped = {"matrix": (12, 0, 0, -12, 72, 742)}
+
+    assert not flipped_text([upright] * 5 + [flipped])
+    assert flipped_text([upright] + [flipped] * 5)
+    assert not flipped_text([])
+
+
+def test_pdf_to_text_items_reads_flipped_pages_top_down(tmp_path):
+    """Test that the words of a page with a flipped coordinate space are read as
+    horizontal lines, with y increasing down the page."""
+    pdf = tmp_path / "flipped.pdf"
+    pdf.write_bytes(FLIPPED_PAGE_PDF)
+
+    items = pdf_to_text_items(str(pdf))
+
+    assert [item["text"] for item in items] == [
+        "Opening",
+        "balance",
+        "Closing",
+        "balance",
+    ]
+    opening, closing = items[0], items[2]
+    assert items[1]["y1"] == opening["y1"] and items[3]["y1"] == closing["y1"]
+    assert closing["y1"] - opening["y1"] == 20
+    assert all(item["y1"] > item["y2"] for item in items)
+
+
+@pytest.mark.parametrize(
+    ("file_path", "kind"),
+    [
+        ("statement.txt", "layout"),
+        ("STATEMENT.TXT", "layout"),
+        ("statement.pdf.txt", "layout"),
+        ("statement.pdf", "pdf"),
+        ("STATEMENT.PDF", "pdf"),
+        ("statement.txt.pdf", "pdf"),
+        ("statement", "pdf"),
+    ],
+)
+def test_input_kind(file_path, kind):
+    """Test that layout files are told from PDFs by their extension in any case."""
+    assert input_kind(file_path) == kind
@@ -1,5 +1,6 @@
 """Tests for the Parser debug functionality."""
 
+import re
 import tempfile
 from pathlib import Path
 
@@ -8,6 +9,11 @@ from transtractor.exceptions import StatementNotSupported
 from transtractor.parser import Parser
 
 
+def _strip_timings(debug: str) -> str:
+    """Remove the timings section of each result, which varies between runs."""
+    return re.sub(r"  Timings:\n(    .*\n)*", "", debug)
+
+
 def test_debug_generates_correct_output():
     """Test that debug for test1.pdf generates output matching test1_debug.txt."""
     parser = Parser()
@@ -33,7 +39,9 @@ def test_debug_generates_correct_output():
         with open(expected_debug, encoding="utf-8") as expected:
             expected_content = expected.read()
 
-        # Compare content
+        # Compare content, apart from the timings
+        assert "  Timings:\n    text_extraction: " in generated_content
+        generated_content = _strip_timings(generated_content)
         assert generated_content == expected_content, (
             "Debug output mismatch:\n"
             f"Generated length: {len(generated_content)}\n"
@@ -62,3 +70,58 @@ def test_debug_raises_statement_not_supported_without_config():
     finally:
         # Clean up temporary file
         Path(tmp_debug_path).unlink(missing_ok=True)
+
+
+def test_debug_lists_sources(tmp_path):
+    """Test that debug lists the text items read into each field if requested."""
+    parser = Parser()
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test1_config.json"))
+    test_pdf = str(fixtures_dir / "test1.pdf")
+
+    result = parser.debug(test_pdf, str(tmp_path / "debug.txt"), sources=True)
+
+    assert "  Field Sources:\n    account_number: page 0 " in result
+    assert "  Transaction Sources:\n    1: page 0 " in result
+
+    result = parser.debug(test_pdf, str(tmp_path / "debug.txt"))
+    assert "Sources:" not in result
+
+
+def test_debug_output_is_bounded(tmp_path):
+    """Test that debug truncates long sections and the whole output at the limits."""
+    parser = Parser()
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test1_config.json"))
+    test_pdf = str(fixtures_dir / "test1.pdf")
+    output_file = tmp_path / "debug.txt"
+
+    result = parser.debug(test_pdf, str(output_file), max_section_lines=5)
+    assert "    5: " in result
+    assert "    6: " not in result
+    assert " more line(s) truncated\n" in result
+
+    result = parser.debug(test_pdf, str(output_file), max_size=500)
+    assert result == output_file.read_text(encoding="utf-8")
+    assert result.endswith("\n... debug output truncated at 500 bytes\n")
+
+
+def test_debug_dict_matches_debug_output():
+    """Test that debug_dict reports the same result as the debug text output."""
+    parser = Parser()
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test1_config.json"))
+
+    report = parser.debug_dict(str(fixtures_dir / "test1.pdf"))
+
+    assert report["error"] is None
+    assert len(report["results"]) == 1
+    result = report["results"][0]
+    assert result["key"] == "au__gtb__fake_account__1"
+    assert result["account_number"] == "1234 5678 9123 4567"
+    assert result["start_date"] == "2025-01-01"
+    assert result["opening_balance"] == 50000.0
+    assert result["closing_balance"] == 11663.82
+    assert result["errors"] == []
+    assert result["transaction_count"] == 62
+    assert "text_extraction" in result["timings_ms"]
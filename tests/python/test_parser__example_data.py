@@ -0,0 +1,41 @@
+"""Tests that the example configs and layouts in examples/data parse end to end."""
+
+import csv
+from pathlib import Path
+
+import pytest
+from transtractor.parser import Parser
+
+EXAMPLES_DIR = Path(__file__).parent.parent.parent / "examples" / "data"
+
+
+@pytest.mark.parametrize(
+    ("key", "closing_balance"),
+    [("zz__example__checking__1", 1553.0), ("zz__example__credit_card__1", -98.0)],
+)
+def test_example_parses_to_csv(tmp_path, key, closing_balance):
+    """Test that each example layout parses with its example config and exports a
+    CSV with a row per transaction."""
+    parser = Parser()
+    parser.load(str(EXAMPLES_DIR / f"{key}.json"))
+
+    statement_data = parser.parse_layout(str(EXAMPLES_DIR / f"{key}_layout.txt"))
+
+    assert statement_data.key == key
+    assert statement_data.metadata["closing_balance"] == closing_balance
+    output = tmp_path / f"{key}.csv"
+    statement_data.to_csv(str(output))
+    with open(output, newline="", encoding="utf-8") as fh:
+        rows = list(csv.reader(fh))
+    assert len(rows) == 1 + len(statement_data.transactions) == 5
+
+
+def test_examples_are_not_listed_as_supported():
+    """Test that loaded example configs are left out of the supported statements."""
+    parser = Parser()
+    for key in ("zz__example__checking__1", "zz__example__credit_card__1"):
+        parser.load(str(EXAMPLES_DIR / f"{key}.json"))
+
+    assert "ZZ" not in parser.catalog()
+    assert all(not c["key"].startswith("zz__") for c in parser.list_configs())
+    assert "Example" not in parser.supported_statements()
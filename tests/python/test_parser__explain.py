@@ -0,0 +1,81 @@
+"""Tests for the Parser explain functionality."""
+
+import json
+from pathlib import Path
+
+import pytest
+from transtractor.exceptions import StatementNotSupported
+from transtractor.parser import Parser
+
+
+def test_explain_writes_text_and_json_reports(tmp_path):
+    """Test that explain reports the identification evidence and each parse."""
+    parser = Parser()
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test1_config.json"))
+    output_file = tmp_path / "explain.txt"
+    json_file = tmp_path / "explain.json"
+
+    result = parser.explain(
+        str(fixtures_dir / "test1.pdf"), str(output_file), str(json_file)
+    )
+
+    assert result == output_file.read_text()
+    assert "  au__gtb__fake_account__1: identified\n" in result
+    assert "Found 1 StatementData result(s)" in result
+    assert "Column Bounds:\n" in result
+    report = json.loads(json_file.read_text())
+    identified = [e["key"] for e in report["identification"] if e["identified"]]
+    assert identified == ["au__gtb__fake_account__1"]
+    assert len(report["results"]) == 1
+    assert report["results"][0]["statement_data"]["key"] == "au__gtb__fake_account__1"
+    assert report["results"][0]["page_coverage"]
+
+
+def test_explain_reports_unidentified_statement(tmp_path):
+    """Test that explain reports rather than raises for an unidentified statement."""
+    parser = Parser()
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+
+    result = parser.explain(
+        str(fixtures_dir / "test1.pdf"), str(tmp_path / "explain.txt")
+    )
+
+    assert "Found 0 StatementData result(s)" in result
+    assert not (tmp_path / "explain.json").exists()
+
+
+def test_explain_terms_shows_nearest_text_of_missing_terms(tmp_path):
+    """Test that explain_terms reports a term printed in a different case in the
+    layout as missing, with the layout text nearest to it."""
+    parser = Parser()
+    examples_dir = Path(__file__).parent.parent.parent / "examples" / "data"
+    parser.load(str(examples_dir / "zz__example__checking__1.json"))
+    layout = (examples_dir / "zz__example__checking__1_layout.txt").read_text()
+    layout_file = tmp_path / "layout.txt"
+    layout_file.write_text(layout.replace('"Everyday"', '"EVERYDAY"'))
+
+    result = parser.explain_terms(str(layout_file))
+
+    assert "  zz__example__checking__1: 1 of 2 account term(s) found\n" in result
+    assert (
+        '    Missing: "Example Everyday Bank", nearest text on page 0: '
+        '"Example EVERYDAY Bank"\n'
+    ) in result
+
+
+def test_debug_writes_term_diagnostics_when_not_identified(tmp_path):
+    """Test that debug writes the account terms of the loaded config before raising
+    StatementNotSupported."""
+    parser = Parser()
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test1_config.json"))
+    parser._inner.add_account_terms("au__gtb__fake_account__1", ["Not In The PDF"])
+    output_file = tmp_path / "debug.txt"
+
+    with pytest.raises(StatementNotSupported):
+        parser.debug(str(fixtures_dir / "test1.pdf"), str(output_file))
+
+    debug = output_file.read_text()
+    assert "No config identified" in debug
+    assert '    Missing: "Not In The PDF"' in debug
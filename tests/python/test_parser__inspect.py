@@ -0,0 +1,31 @@
+"""Tests for Parser.inspect() and the locale filter."""
+
+from pathlib import Path
+
+from transtractor.parser import Parser
+
+FIXTURES_DIR = Path(__file__).parent.parent / "fixtures"
+
+
+def test_inspect_reports_latin_profile():
+    """Test that an Australian statement is profiled as Latin script with $."""
+    profile = Parser().inspect(str(FIXTURES_DIR / "test1.pdf"))
+
+    assert profile["script"] == "Latin"
+    assert profile["scripts"]["Latin"] > 0
+    assert profile["currency_symbols"].get("$", 0) > 0
+    assert profile["decimal_points"] > profile["decimal_commas"]
+
+
+def test_locale_filter_gives_identical_results():
+    """Test that the locale filter does not change identification or parsing."""
+    pdf_path = str(FIXTURES_DIR / "test1.pdf")
+    config_path = str(FIXTURES_DIR / "test1_config.json")
+    parser = Parser()
+    filtered_parser = Parser(locale_filter=True)
+    parser.load(config_path)
+    filtered_parser.load(config_path)
+
+    assert filtered_parser.identify(pdf_path) == parser.identify(pdf_path)
+    expected = parser.parse(pdf_path).transactions
+    assert filtered_parser.parse(pdf_path).transactions == expected
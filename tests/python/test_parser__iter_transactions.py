@@ -0,0 +1,38 @@
+"""Tests for the Parser iter_layout_transactions method."""
+
+from pathlib import Path
+
+from transtractor.parser import Parser
+
+FIXTURES_DIR = Path(__file__).parent.parent / "fixtures"
+
+
+def test_iter_layout_transactions_matches_parse_layout():
+    """Test that streamed transactions match those of a full parse."""
+    parser = Parser()
+    parser.load(str(FIXTURES_DIR / "test3_config.json"))
+    layout = str(FIXTURES_DIR / "test3_layout.txt")
+
+    streamed = list(parser.iter_layout_transactions(layout))
+
+    assert streamed == parser.parse_layout(layout).transactions
+    assert [tx.description for tx in streamed] == [
+        "SALARY ACME PTY LTD",
+        "RENT PAYMENT",
+        "GROCERIES",
+    ]
+
+
+def test_iter_layout_transactions_is_lazy():
+    """Test that transactions are parsed as the iterator is advanced."""
+    parser = Parser()
+    parser.load(str(FIXTURES_DIR / "test3_config.json"))
+
+    transactions = parser.iter_layout_transactions(
+        str(FIXTURES_DIR / "test3_layout.txt")
+    )
+
+    first = next(transactions)
+    assert first.balance == 1250.0
+    assert first.date_index == 0
+    assert len(list(transactions)) == 2
@@ -1,5 +1,6 @@
 """Tests for Parser.load() method."""
 
+import json
 from pathlib import Path
 
 import pytest
@@ -18,3 +19,94 @@ def test_load_raises_config_load_error_with_invalid_config():
     # Should raise ConfigLoadError since the config has an invalid country code
     with pytest.raises(ConfigLoadError):
         parser.load(str(invalid_config))
+
+
+def test_load_adds_config_to_catalog():
+    """Test that a loaded config appears in the catalog immediately, alongside the
+    default configs."""
+    parser = Parser()
+    assert "Commonwealth Bank of Australia" in parser.catalog()["AU"]
+    assert "Gravy Toast Bank" not in parser.catalog()["AU"]
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test1_config.json"))
+
+    catalog = parser.catalog()
+    entries = catalog["AU"]["Gravy Toast Bank"]
+    assert [entry["key"] for entry in entries] == ["au__gtb__fake_account__1"]
+    assert entries[0]["account_type"] == "Savings"
+    assert list(catalog["AU"]) == sorted(catalog["AU"])
+    assert "  Gravy Toast Bank (Savings)" in parser.supported_statements()
+
+
+def test_list_configs_marks_loaded_configs():
+    """Test that list_configs gives the packaged configs as embedded and a loaded
+    config as user, sorted by key."""
+    parser = Parser()
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test1_config.json"))
+
+    configs = parser.list_configs()
+
+    keys = [config["key"] for config in configs]
+    assert keys == sorted(keys)
+    by_key = {config["key"]: config for config in configs}
+    assert by_key["au__cba__debit__1"]["source"] == "embedded"
+    assert by_key["au__cba__debit__1"]["country"] == "AU"
+    loaded = by_key["au__gtb__fake_account__1"]
+    assert loaded["source"] == "user"
+    assert loaded["bank_name"] == "Gravy Toast Bank"
+    assert loaded["account_type"] == "Savings"
+
+
+def test_get_config_json_loads_as_config(tmp_path):
+    """Test that get_config_json gives the full config, which loads as the same
+    config."""
+    parser = Parser()
+    config = json.loads(parser.get_config_json("au__cba__debit__1"))
+    assert config["key"] == "au__cba__debit__1"
+    # Fields left at their defaults are included
+    assert config["transaction_strict"] is False
+
+    config["key"] = "au__cba__debit__99"
+    config_path = tmp_path / "config.json"
+    config_path.write_text(json.dumps(config))
+    parser.load(str(config_path))
+    assert json.loads(parser.get_config_json("au__cba__debit__99")) == config
+
+
+def test_load_rejects_term_with_zero_width_character(tmp_path):
+    """Test that an account term ending in an invisible zero-width space is rejected
+    with a message pointing at the character."""
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    config = json.loads((fixtures_dir / "test1_config.json").read_text())
+    config["account_terms"][0] += "\u200b"
+    config_path = tmp_path / "config.json"
+    config_path.write_text(json.dumps(config))
+
+    with pytest.raises(ConfigLoadError, match="zero-width character U\\+200B"):
+        Parser().load(str(config_path))
+
+
+def test_lint_reports_cross_field_warnings(tmp_path):
+    """Test that lint reports a transaction field without headers, and nothing for
+    a consistent config."""
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser = Parser()
+    parser.load(str(fixtures_dir / "test3_config.json"))
+    assert parser.lint("au__ssb__savings__1") == []
+
+    config = json.loads((fixtures_dir / "test3_config.json").read_text())
+    config["key"] = "au__ssb__savings__2"
+    config["account_terms"].append("Bonus Saver")
+    config["transaction_balance_headers"] = []
+    config_path = tmp_path / "config.json"
+    config_path.write_text(json.dumps(config))
+    parser.load(str(config_path))
+
+    warnings = parser.lint("au__ssb__savings__2")
+
+    assert [warning.split(":")[0] for warning in warnings] == [
+        "transaction_balance_headers"
+    ]
+    assert parser.lint("au__ssb__savings__1")[0].startswith("account_terms:")
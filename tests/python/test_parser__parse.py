@@ -1,5 +1,6 @@
 """Tests for the Parser parse method."""
 
+import json
 import tempfile
 from pathlib import Path
 
@@ -83,3 +84,108 @@ def test_parse_raises_no_error_free_statement_data_with_misconfigured_config():
     # Should raise NoErrorFreeStatementData since the config is misconfigured
     with pytest.raises(NoErrorFreeStatementData):
         parser.parse(str(test_pdf))
+
+
+def test_parse_uses_cache_after_identify():
+    """Test that identifying then parsing the same PDF with the cache enabled skips
+    re-extraction and gives the same result as without the cache.
+    """
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    test_pdf = str(fixtures_dir / "test1.pdf")
+    config = str(fixtures_dir / "test1_config.json")
+
+    uncached_parser = Parser()
+    uncached_parser.load(config)
+    expected = uncached_parser.parse(test_pdf)
+    assert uncached_parser.cache_stats()["size"] == 0
+
+    parser = Parser(cache_capacity=1)
+    parser.load(config)
+    keys = parser.identify(test_pdf)
+    assert parser.cache_stats() == {"capacity": 1, "size": 1, "hits": 0, "misses": 2}
+
+    statement_data = parser.parse(test_pdf)
+    stats = parser.cache_stats()
+    assert stats["hits"] == 2
+    assert stats["misses"] == 2
+    assert statement_data.key in keys
+    assert statement_data.to_pandas_dict() == expected.to_pandas_dict()
+
+    # Loading a config keeps the text items but identifies the statement again
+    parser.load(config)
+    assert parser.cache_stats()["size"] == 1
+    assert parser.identify(test_pdf) == keys
+    assert parser.cache_stats() == {"capacity": 1, "size": 1, "hits": 3, "misses": 3}
+
+
+def test_to_json_matches_parse():
+    """Test that to_json writes the same statement data as parse."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    test_pdf = str(fixtures_dir / "test1.pdf")
+    parser.load(str(fixtures_dir / "test1_config.json"))
+    statement_data = parser.parse(test_pdf)
+
+    with tempfile.TemporaryDirectory() as tmp_dir:
+        output_file = Path(tmp_dir) / "test1.json"
+        json_str = parser.to_json(test_pdf, str(output_file))
+        assert output_file.read_text(encoding="utf-8") == json_str
+
+    result = json.loads(json_str)
+    assert result["key"] == statement_data.key
+    assert result["account_number"] == statement_data.account_number
+    assert result["opening_balance"] == statement_data.metadata["opening_balance"]
+    assert result["closing_balance"] == statement_data.metadata["closing_balance"]
+    assert result["errors"] == []
+    assert len(result["transactions"]) == len(statement_data.transactions)
+    for tx, expected in zip(
+        result["transactions"], statement_data.transactions, strict=True
+    ):
+        assert tx["date"] == expected.date.isoformat()
+        assert tx["description"] == expected.description
+        assert tx["amount"] == expected.amount
+        assert tx["balance"] == expected.balance
+
+
+def test_parse_identifies_near_miss_with_match_threshold(tmp_path):
+    """Test that a statement missing one of its account terms is only identified
+    below the default match threshold, with its score shown by debug."""
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    config = json.loads((fixtures_dir / "test1_config.json").read_text())
+    config["account_terms"].append("Reworded Footer")
+    config_file = tmp_path / "config.json"
+    config_file.write_text(json.dumps(config))
+    test_pdf = str(fixtures_dir / "test1.pdf")
+
+    parser = Parser()
+    parser.load(str(config_file))
+    with pytest.raises(StatementNotSupported):
+        parser.parse(test_pdf)
+
+    parser = Parser(match_threshold=0.6)
+    parser.load(str(config_file))
+    assert parser.parse(test_pdf).key == "au__gtb__fake_account__1"
+    debug = parser.debug(test_pdf, str(tmp_path / "debug.txt"))
+    assert "  Identification Score: 0.67\n" in debug
+
+    with pytest.raises(ValueError):
+        Parser(match_threshold=0.0)
+
+
+def test_parse_reads_layout_files(tmp_path):
+    """Test that parse reads a layout file in place of a PDF by its extension, in
+    any case, as parse_layout does."""
+    parser = Parser()
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test1_config.json"))
+    layout = fixtures_dir / "test1_layout.txt"
+    upper_layout = tmp_path / "TEST1_LAYOUT.TXT"
+    upper_layout.write_bytes(layout.read_bytes())
+    expected = parser.parse_layout(str(layout))
+
+    for path in (layout, upper_layout):
+        statement_data = parser.parse(str(path))
+        assert statement_data.key == expected.key
+        assert statement_data.transactions == expected.transactions
+        assert parser.identify(str(path)) == [expected.key]
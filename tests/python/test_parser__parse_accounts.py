@@ -0,0 +1,57 @@
+"""Tests for parsing statements with several accounts."""
+
+from pathlib import Path
+
+from transtractor.parser import Parser
+from transtractor.utils.accounts import account_csv_path, accounts_to_csv
+
+FIXTURES_DIR = Path(__file__).parent.parent / "fixtures"
+
+
+def test_parse_layout_accounts_splits_accounts():
+    """Test that each account section of a multi-account statement gives its own
+    StatementData, keyed by account number."""
+    parser = Parser()
+    parser.load(str(FIXTURES_DIR / "test7_config.json"))
+
+    accounts = parser.parse_layout_accounts(str(FIXTURES_DIR / "test7_layout.txt"))
+
+    assert list(accounts) == ["062-000 1234 5678", "062-000 8765 4321"]
+    everyday, savings = accounts.values()
+    assert len(everyday.transactions) == 3
+    assert everyday.metadata["opening_balance"] == 1000.0
+    assert everyday.metadata["account_section"] == 1
+    assert len(savings.transactions) == 1
+    assert savings.metadata["closing_balance"] == 5012.3
+    assert savings.metadata["account_section"] == 2
+
+
+def test_parse_layout_accounts_single_account():
+    """Test that a statement with a single account gives a single StatementData."""
+    parser = Parser()
+    parser.load(str(FIXTURES_DIR / "test3_config.json"))
+
+    accounts = parser.parse_layout_accounts(str(FIXTURES_DIR / "test3_layout.txt"))
+
+    assert list(accounts) == ["062-000 1234 5678"]
+    assert accounts["062-000 1234 5678"].metadata["account_section"] is None
+
+
+def test_accounts_to_csv_writes_csv_per_account(tmp_path):
+    """Test that each account is exported to a CSV suffixed with its account."""
+    parser = Parser()
+    parser.load(str(FIXTURES_DIR / "test7_config.json"))
+    accounts = parser.parse_layout_accounts(str(FIXTURES_DIR / "test7_layout.txt"))
+
+    paths = accounts_to_csv(accounts, str(tmp_path / "statement.csv"))
+
+    assert [Path(p).name for p in paths] == [
+        "statement_062-000_1234_5678.csv",
+        "statement_062-000_8765_4321.csv",
+    ]
+    savings_lines = Path(paths[1]).read_text(encoding="utf-8").splitlines()
+    assert len(savings_lines) == 2
+    assert "INTEREST" in savings_lines[1]
+    assert account_csv_path("out/a.csv", "section_2") == str(
+        Path("out/a_section_2.csv")
+    )
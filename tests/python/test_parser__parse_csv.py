@@ -0,0 +1,60 @@
+"""Tests for the Parser parse_csv functionality."""
+
+import pytest
+from transtractor.parser import Parser
+from transtractor.transtractor import NoErrorFreeStatementData
+
+CSV = """Date,Description,Amount,Balance
+2024-04-02,SALARY ACME PTY LTD,250.00,"1,250.00"
+2024-04-03,RENT PAYMENT,-120.00,"1,130.00"
+2024-04-05,GROCERIES,-24.50,"1,105.50"
+"""
+
+
+def test_parse_csv_with_balances(tmp_path):
+    """Test that a CSV export is read into statement data."""
+    csv_file = tmp_path / "export.csv"
+    csv_file.write_text(CSV)
+
+    sd = Parser().parse_csv(str(csv_file), account_number="062-000 1234 5678")
+
+    assert sd.key == "csv"
+    assert sd.filename == str(csv_file)
+    assert [tx.amount for tx in sd.transactions] == [250.0, -120.0, -24.5]
+    assert sd.metadata["opening_balance"] == 1000.0
+    assert sd.metadata["closing_balance"] == 1105.5
+
+
+def test_parse_csv_without_balances(tmp_path):
+    """Test that balances are computed from the opening balance."""
+    csv_file = tmp_path / "export.csv"
+    csv_file.write_text(
+        "Posted,Details,Value\n"
+        "02/04/2024,SALARY,250.00\n"
+        "03/04/2024,RENT,-120.00\n"
+    )
+
+    sd = Parser().parse_csv(
+        str(csv_file),
+        account_number="062-000 1234 5678",
+        opening_balance=1000.0,
+        columns={
+            "date": "Posted",
+            "description": "Details",
+            "amount": "Value",
+            "balance": "",
+        },
+    )
+
+    assert [tx.balance for tx in sd.transactions] == [1250.0, 1130.0]
+
+
+def test_parse_csv_balance_mismatch_raises(tmp_path):
+    """Test that a CSV failing the quality checks raises."""
+    csv_file = tmp_path / "export.csv"
+    csv_file.write_text(CSV)
+
+    with pytest.raises(NoErrorFreeStatementData):
+        Parser().parse_csv(
+            str(csv_file), account_number="062-000 1234 5678", closing_balance=1.0
+        )
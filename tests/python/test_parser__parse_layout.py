@@ -1,13 +1,15 @@
 """Tests for the Parser parse_layout method."""
 
+import json
 import tempfile
+from datetime import date
 from pathlib import Path
 
 import pytest
 from transtractor.exceptions import StatementNotSupported
 from transtractor.parser import Parser
 from transtractor.structs.statement_data import StatementData
-from transtractor.transtractor import NoErrorFreeStatementData
+from transtractor.transtractor import ConfigAccessError, NoErrorFreeStatementData
 
 
 def test_parse_layout_generates_correct_csv():
@@ -85,3 +87,569 @@ def test_parse_layout_raises_no_error_free_statement_data_with_misconfigured_con
     # Should raise NoErrorFreeStatementData since the config is misconfigured
     with pytest.raises(NoErrorFreeStatementData):
         parser.parse_layout(str(test_layout))
+
+
+def test_parse_layout_keeps_dates_and_amounts_in_descriptions():
+    """Test that descriptions containing date and amount-like text are kept
+    intact when the config allows it, and amounts come from the amount column."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    test_layout = fixtures_dir / "test2_layout.txt"
+    config = fixtures_dir / "test2_config.json"
+    parser.load(str(config))
+    expected_csv = fixtures_dir / "test2_parsed.csv"
+
+    statement_data: StatementData = parser.parse_layout(str(test_layout))
+
+    with tempfile.NamedTemporaryFile(
+        mode="w", suffix=".csv", delete=False, newline=""
+    ) as tmp_file:
+        tmp_csv_path = tmp_file.name
+        statement_data.to_csv(tmp_csv_path)
+
+    try:
+        with open(tmp_csv_path, encoding="utf-8") as generated:
+            generated_lines = generated.readlines()
+
+        with open(expected_csv, encoding="utf-8") as expected:
+            expected_lines = expected.readlines()
+
+        assert generated_lines == expected_lines
+    finally:
+        Path(tmp_csv_path).unlink(missing_ok=True)
+
+
+def test_parse_layout_no_error_free_statement_data_includes_breakdown():
+    """Test that NoErrorFreeStatementData carries a per-config error breakdown."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    test_layout = fixtures_dir / "test1_layout.txt"
+    misconfigured_config = fixtures_dir / "test1_config_misconfigured.json"
+    parser.load(str(misconfigured_config))
+
+    with pytest.raises(NoErrorFreeStatementData) as exc_info:
+        parser.parse_layout(str(test_layout))
+
+    message = str(exc_info.value)
+    breakdown = exc_info.value.breakdown
+    assert exc_info.value.args == (message,)
+    assert "Best candidate: au__gtb__fake_account__1" in message
+    assert len(breakdown) == 1
+    assert breakdown[0]["key"] == "au__gtb__fake_account__1"
+    assert breakdown[0]["config_status"] == "None"
+    assert breakdown[0]["error_count"] > 0
+    assert 0 < len(breakdown[0]["errors"]) <= 3
+    assert breakdown[0]["timings"]["transactions"] > 0
+
+
+def test_parse_layout_checks_expected_account_number():
+    """Test that a mismatched expected account number rejects the statement."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    test_layout = fixtures_dir / "test1_layout.txt"
+    config = fixtures_dir / "test1_config.json"
+    parser.load(str(config))
+
+    statement_data = parser.parse_layout(
+        str(test_layout), expected_account_number="1234-5678-9123-4567"
+    )
+    assert statement_data.account_number == "1234 5678 9123 4567"
+
+    parser.parse_layout(str(test_layout), expected_account_number=r"re:\d{12}4567")
+
+    with pytest.raises(NoErrorFreeStatementData) as exc_info:
+        parser.parse_layout(str(test_layout), expected_account_number="9999 9999")
+
+    message = str(exc_info.value)
+    assert "Account number mismatch" in message
+
+
+def test_parse_layout_reports_page_coverage():
+    """Test that pages ignored within the transaction table are reported."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    config = fixtures_dir / "test1_config.json"
+    parser.load(str(config))
+
+    statement_data = parser.parse_layout(str(fixtures_dir / "test1_layout.txt"))
+    assert statement_data.metadata["warnings"] == []
+    assert [p["transactions"] for p in statement_data.metadata["page_coverage"]] == [
+        31,
+        31,
+        0,
+    ]
+
+    partial_layout = fixtures_dir / "test1_layout_partial_coverage.txt"
+    statement_data = parser.parse_layout(str(partial_layout))
+    assert statement_data.metadata["warnings"] == [
+        "Pages 3-6 within the transaction table had no text items read by any parser"
+    ]
+    page_coverage = statement_data.metadata["page_coverage"]
+    assert [p["page"] for p in page_coverage] == [0, 1, 2, 3, 4, 5, 6]
+    assert [p["consumed_items"] for p in page_coverage[3:]] == [0, 0, 0, 0]
+    assert all(p["text_items"] > 0 for p in page_coverage)
+
+
+def test_parse_layout_with_date_timezone():
+    """Test that a date timezone shifts timestamps to local midnight only."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    test_layout = fixtures_dir / "test1_layout.txt"
+    config = fixtures_dir / "test1_config.json"
+    parser.load(str(config))
+
+    utc_data = parser.parse_layout(str(test_layout))
+    sydney_data = parser.parse_layout(
+        str(test_layout), date_timezone="Australia/Sydney"
+    )
+
+    hour_ms = 3_600_000
+    utc_transactions = utc_data.transactions
+    sydney_transactions = sydney_data.transactions
+    assert len(utc_transactions) == len(sydney_transactions)
+    for utc_tx, sydney_tx in zip(utc_transactions, sydney_transactions):
+        assert sydney_tx.date.isoformat() == utc_tx.date.isoformat()
+        assert utc_tx.timestamp - sydney_tx.timestamp in (10 * hour_ms, 11 * hour_ms)
+
+    with pytest.raises(ValueError):
+        parser.parse_layout(str(test_layout), date_timezone="Mars/Olympus_Mons")
+
+
+def test_parse_layout_fixers_mode():
+    """Test that checks run on unfixed data when fixers are disabled."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    pristine_layout = fixtures_dir / "test3_layout.txt"
+    unsigned_layout = fixtures_dir / "test3_layout_unsigned_opening.txt"
+    parser.load(str(fixtures_dir / "test3_config.json"))
+
+    for fixers in ("all", "safe", "none"):
+        statement_data = parser.parse_layout(str(pristine_layout), fixers=fixers)
+        assert len(statement_data.transactions) == 3
+
+    # The unsigned opening balance is only fixed by the sign flip
+    statement_data = parser.parse_layout(str(unsigned_layout), fixers="all")
+    assert statement_data.transactions[-1].balance == -894.50
+    with pytest.raises(NoErrorFreeStatementData) as exc_info:
+        parser.parse_layout(str(unsigned_layout), fixers="none")
+    message = str(exc_info.value)
+    assert "balance mismatch" in message
+    assert "(page 0, y≈" in message
+
+    with pytest.raises(ValueError):
+        parser.parse_layout(str(pristine_layout), fixers="some")
+
+
+def test_parse_layout_retry_text_order():
+    """Test that a config whose text reordering fails is retried unordered."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    test_layout = fixtures_dir / "test3_layout.txt"
+    parser.load(str(fixtures_dir / "test3_config_text_order.json"))
+
+    with pytest.raises(NoErrorFreeStatementData):
+        parser.parse_layout(str(test_layout))
+
+    statement_data = parser.parse_layout(str(test_layout), retry_text_order=True)
+    assert len(statement_data.transactions) == 3
+    assert statement_data.metadata["fix_text_order_applied"] is False
+    warnings = statement_data.metadata["warnings"]
+    assert len(warnings) == 1
+    assert "fix_text_order [40.0, 0.0] not applied" in warnings[0]
+
+
+def test_parse_layout_columns_by_position():
+    """Test that a statement without column headers parses by column position."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test4_config.json"))
+
+    statement_data = parser.parse_layout(str(fixtures_dir / "test4_layout.txt"))
+    transactions = statement_data.transactions
+    assert [tx.description for tx in transactions] == [
+        "Coffee Shop",
+        "Split bill 30.00 each",
+        "Salary ACME PTY LTD",
+        "Groceries",
+    ]
+    assert [tx.amount for tx in transactions] == [-4.50, -30.00, 2500.00, -84.20]
+    assert transactions[-1].balance == 3587.13
+
+
+def test_parse_layout_records_timings():
+    """Test that the duration of each parse stage is recorded in the metadata."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test3_config.json"))
+
+    statement_data = parser.parse_layout(str(fixtures_dir / "test3_layout.txt"))
+    timings = statement_data.metadata["timings"]
+    for stage in (
+        "text_extraction",
+        "statement_typing",
+        "account_number",
+        "opening_balance",
+        "closing_balance",
+        "start_date",
+        "transactions",
+        "fixers",
+        "checkers",
+    ):
+        assert timings[stage] >= 0
+    assert timings["text_extraction"] > 0
+    assert timings["transactions"] > 0
+    assert timings["total"] >= timings["transactions"]
+
+
+def test_parse_layout_reports_config_status():
+    """Test that the status of the matched config is reported in the metadata."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test1_config.json"))
+    parser.load(str(fixtures_dir / "test3_config.json"))
+
+    # test1_config.json omits the status, so it defaults to "None"
+    statement_data = parser.parse_layout(str(fixtures_dir / "test1_layout.txt"))
+    assert statement_data.metadata["config_status"] == "None"
+
+    statement_data = parser.parse_layout(str(fixtures_dir / "test3_layout.txt"))
+    assert statement_data.metadata["config_status"] == "Limited"
+
+
+def test_parse_layout_no_transactions_declared():
+    """Test that a statement declaring no transactions parses as empty, and that
+    the same phrase outside the transaction table is ignored."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test5_config.json"))
+
+    statement_data = parser.parse_layout(
+        str(fixtures_dir / "test5_layout_inactive.txt")
+    )
+    assert statement_data.transactions == []
+    assert statement_data.metadata["no_transactions_declared"] is True
+    assert statement_data.metadata["warnings"] == []
+
+    statement_data = parser.parse_layout(
+        str(fixtures_dir / "test5_layout_marketing.txt")
+    )
+    assert len(statement_data.transactions) == 3
+    assert statement_data.metadata["no_transactions_declared"] is False
+    assert statement_data.metadata["warnings"] == []
+
+
+def test_parse_layout_scale_tolerances():
+    """Test that a statement with 10x scaled coordinates parses with the same config
+    once its tolerances are scaled by the detected coordinate scale."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test3_config.json"))
+
+    expected = parser.parse_layout(str(fixtures_dir / "test3_layout.txt"))
+    assert expected.metadata["coordinate_scale"] == 1.0
+    assert expected.metadata["currency"] == "$"
+
+    statement_data = parser.parse_layout(str(fixtures_dir / "test3_layout_scaled.txt"))
+    assert statement_data.metadata["coordinate_scale"] == 10.0
+    assert statement_data.account_number == expected.account_number
+    assert [
+        (tx.date, tx.description, tx.amount, tx.balance)
+        for tx in statement_data.transactions
+    ] == [
+        (tx.date, tx.description, tx.amount, tx.balance)
+        for tx in expected.transactions
+    ]
+    warnings = statement_data.metadata["warnings"]
+    assert warnings == [
+        "Coordinate scale 10 detected: config tolerances scaled by 10"
+    ]
+
+
+def test_parse_layout_credit_card_fields():
+    """Test that the minimum payment and payment due date are read from a credit
+    card statement, and are None for a config without them."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test2_config.json"))
+    parser.load(str(fixtures_dir / "test3_config.json"))
+
+    statement_data = parser.parse_layout(str(fixtures_dir / "test2_layout.txt"))
+    assert statement_data.metadata["minimum_payment"] == 25.00
+    assert statement_data.metadata["payment_due_date"] == date(2024, 4, 21)
+
+    statement_data = parser.parse_layout(str(fixtures_dir / "test3_layout.txt"))
+    assert statement_data.metadata["minimum_payment"] is None
+    assert statement_data.metadata["payment_due_date"] is None
+
+
+def test_parse_layout_to_csv_statement_fields():
+    """Test that statement-level fields can be exported as extra CSV columns, with
+    empty values for unset fields."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test3_config.json"))
+    statement_data = parser.parse_layout(str(fixtures_dir / "test3_layout.txt"))
+    fields = [
+        "key",
+        "account_number",
+        "start_date",
+        "opening_balance",
+        "closing_balance",
+        "amount",
+    ]
+
+    with tempfile.NamedTemporaryFile(
+        mode="w", suffix=".csv", delete=False, newline=""
+    ) as tmp_file:
+        tmp_csv_path = tmp_file.name
+
+    try:
+        statement_data.to_csv(tmp_csv_path, fields=fields)
+        with open(tmp_csv_path, encoding="utf-8") as generated:
+            lines = generated.read().splitlines()
+        assert lines[0] == ",".join(fields)
+        assert lines[1] == (
+            "au__ssb__savings__1,062-000 1234 5678,2024-04-01,1000.0,1105.5,250.0"
+        )
+        assert len(lines) == 4
+
+        empty_statement_data = StatementData("key", "123", [])
+        empty_statement_data.set_transactions(statement_data.transactions[:1])
+        empty_statement_data.to_csv(tmp_csv_path, fields=fields)
+        with open(tmp_csv_path, encoding="utf-8") as generated:
+            lines = generated.read().splitlines()
+        assert lines[1] == "key,123,,,,250.0"
+    finally:
+        Path(tmp_csv_path).unlink(missing_ok=True)
+
+
+def test_parse_layout_all_returns_failed_candidates():
+    """Test that parse_layout_all returns candidates that fail the quality checks,
+    with their errors and transaction columns."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    test_layout = fixtures_dir / "test1_layout.txt"
+    misconfigured_config = fixtures_dir / "test1_config_misconfigured.json"
+    parser.load(str(misconfigured_config))
+
+    candidates = parser.parse_layout_all(str(test_layout))
+
+    assert len(candidates) == 1
+    candidate = candidates[0]
+    assert candidate["key"] == "au__gtb__fake_account__1"
+    assert candidate["passed"] is False
+    assert len(candidate["errors"]) > 0
+    num_transactions = len(candidate["description"])
+    for column in ("date", "amount", "balance"):
+        assert len(candidate[column]) == num_transactions
+    metadata = candidate["metadata"]
+    assert metadata["key"] == candidate["key"]
+    assert metadata["errors"] == candidate["errors"]
+
+
+def test_parse_layout_all_matches_parse_layout():
+    """Test that the error-free candidate of parse_layout_all matches parse_layout."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    test_layout = fixtures_dir / "test1_layout.txt"
+    parser.load(str(fixtures_dir / "test1_config.json"))
+
+    statement_data = parser.parse_layout(str(test_layout))
+    candidates = parser.parse_layout_all(str(test_layout))
+
+    passed = [candidate for candidate in candidates if candidate["passed"]]
+    assert passed[0]["key"] == statement_data.key
+    assert passed[0]["errors"] == []
+    expected = statement_data.to_pandas_dict()
+    for column in ("date", "description", "amount", "balance"):
+        assert passed[0][column] == expected[column]
+    assert passed[0]["metadata"] == {
+        "key": statement_data.key,
+        "account_number": statement_data.account_number,
+        "opening_balance": statement_data.metadata["opening_balance"],
+        "closing_balance": statement_data.metadata["closing_balance"],
+        "start_date": statement_data.metadata["start_date"],
+        "errors": [],
+    }
+
+
+def test_parse_layout_month_only_dates():
+    """Test that month-only recurring entries land on the configured day of the
+    right month and year instead of inheriting the previous date."""
+    parser = Parser()
+
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    test_layout = fixtures_dir / "test6_layout_month_only.txt"
+    parser.load(str(fixtures_dir / "test6_config.json"))
+
+    statement_data = parser.parse_layout(str(test_layout))
+
+    assert [tx.date for tx in statement_data.transactions] == [
+        date(2024, 1, 31),
+        date(2024, 2, 5),
+        date(2024, 2, 29),
+        date(2024, 3, 31),
+    ]
+
+
+def test_parse_layout_keeps_rows_with_empty_descriptions(tmp_path):
+    """Test that rows with filler or missing descriptions are kept, with empty
+    description cells in the CSV, when descriptions are optional."""
+    parser = Parser()
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test8_config.json"))
+
+    statement_data = parser.parse_layout(str(fixtures_dir / "test8_layout.txt"))
+    csv_path = tmp_path / "filler.csv"
+    statement_data.to_csv(str(csv_path))
+
+    assert len(statement_data.transactions) == 5
+    assert statement_data.transactions[-1].balance == 1095.0
+    lines = csv_path.read_text(encoding="utf-8").splitlines()
+    assert lines[4:] == ["2024-04-06,,-10.0,1095.5", "2024-04-07,,-0.5,1095.0"]
+
+
+def test_parse_layout_with_keys_uses_only_given_configs():
+    """Test that given config keys are used instead of identifying the statement."""
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser = Parser()
+    parser.load(str(fixtures_dir / "test1_config.json"))
+    parser.load(str(fixtures_dir / "test3_config.json"))
+    layout = str(fixtures_dir / "test1_layout.txt")
+
+    statement_data = parser.parse_layout(layout, keys=["au__gtb__fake_account__1"])
+    assert statement_data.key == "au__gtb__fake_account__1"
+
+    # The other loaded config is forced, so no error-free result is found
+    with pytest.raises(NoErrorFreeStatementData):
+        parser.parse_layout(layout, keys=["au__ssb__savings__1"])
+
+
+def test_parse_layout_all_with_keys_uses_only_given_configs():
+    """Test that the layout variants of the parse flows use the given config keys."""
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser = Parser()
+    parser.load(str(fixtures_dir / "test1_config.json"))
+    parser.load(str(fixtures_dir / "test3_config.json"))
+    layout = str(fixtures_dir / "test1_layout.txt")
+
+    candidates = parser.parse_layout_all(layout, keys=["au__ssb__savings__1"])
+
+    assert [candidate["key"] for candidate in candidates] == ["au__ssb__savings__1"]
+
+
+def test_parse_layout_with_unknown_key_raises():
+    """Test that an unknown config key is rejected before parsing."""
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser = Parser()
+
+    with pytest.raises(ConfigAccessError, match="au__xyz__savings__9"):
+        parser.parse_layout(
+            str(fixtures_dir / "test1_layout.txt"), keys=["au__xyz__savings__9"]
+        )
+
+
+def test_parse_layout_reports_balance_sources():
+    """Test that each balance is reported as parsed from the statement or computed
+    from the opening balance and amounts."""
+    parser = Parser()
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test2_config.json"))
+    parser.load(str(fixtures_dir / "test3_config.json"))
+
+    # Credit card statement without a balance column
+    statement_data = parser.parse_layout(str(fixtures_dir / "test2_layout.txt"))
+    assert statement_data.metadata["balances_computed"] == 3
+    assert all(tx.balance_source == "computed" for tx in statement_data.transactions)
+
+    # Debit statement with a balance on every row
+    statement_data = parser.parse_layout(str(fixtures_dir / "test3_layout.txt"))
+    assert statement_data.metadata["balances_computed"] == 0
+    assert statement_data.metadata["amounts_inferred"] == 0
+    assert all(tx.balance_source == "parsed" for tx in statement_data.transactions)
+    assert all(tx.amount_source == "parsed" for tx in statement_data.transactions)
+
+
+def test_parse_layout_per_column_alignment_tolerances():
+    """Test that a wide A3 statement is parsed with per-column alignment tolerances,
+    while the shared tolerance is used for columns without one."""
+    parser = Parser()
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test9_config.json"))
+
+    statement_data = parser.parse_layout(str(fixtures_dir / "test9_layout_a3.txt"))
+
+    assert [tx.description for tx in statement_data.transactions] == [
+        "INVOICE 1042",
+        "RENT PAYMENT",
+        "BANK FEE",
+    ]
+    assert [tx.amount for tx in statement_data.transactions] == [2500.0, -400.0, -18.8]
+
+
+def test_parse_layout_keeps_rows_omitting_optional_balance(tmp_path):
+    """Test that fee rows printed without a balance are kept when the balance is
+    marked optional in the transaction formats, with a computed balance in the CSV."""
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    config = json.loads((fixtures_dir / "test3_config.json").read_text())
+    config["transaction_formats"] = [["date", "description", "amount", "balance?"]]
+    config_path = tmp_path / "config.json"
+    config_path.write_text(json.dumps(config))
+    parser = Parser()
+    parser.load(str(config_path))
+
+    layout = fixtures_dir / "test3_layout_fee_rows.txt"
+    statement_data = parser.parse_layout(str(layout))
+    csv_path = tmp_path / "fees.csv"
+    statement_data.to_csv(str(csv_path))
+
+    lines = csv_path.read_text(encoding="utf-8").splitlines()
+    assert lines[3] == "2024-04-04,ACCOUNT FEE,-5.0,1125.0"
+    assert lines[5] == "2024-04-06,CARD FEE,-1.0,1099.5"
+    assert statement_data.metadata["balances_computed"] == 2
+
+
+def test_parse_layout_drops_duplicates_only_with_fixers(tmp_path):
+    """Test that a row repeated at the top of the next page is dropped when fixers
+    are applied, but only reported with fixers="none", leaving the data unchanged."""
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    config = json.loads((fixtures_dir / "test3_config.json").read_text())
+    config["drop_duplicate_transactions"] = True
+    config_path = tmp_path / "config.json"
+    config_path.write_text(json.dumps(config))
+    parser = Parser()
+    parser.load(str(config_path))
+
+    # Repeat the rent row, moving the rows below it down one line
+    layout = (fixtures_dir / "test3_layout.txt").read_text(encoding="utf-8")
+    rent_row = next(line for line in layout.splitlines() if "RENT" in line)
+    layout = layout.replace(",296,284]", ",313,301]").replace(",269,257]", ",286,274]")
+    layout = layout.replace(
+        rent_row, rent_row + "\n" + rent_row.replace(",252,240]", ",269,257]")
+    )
+    layout_path = tmp_path / "duplicate_layout.txt"
+    layout_path.write_text(layout, encoding="utf-8")
+
+    statement_data = parser.parse_layout(str(layout_path))
+    assert len(statement_data.transactions) == 3
+
+    candidates = parser.parse_layout_all(str(layout_path), fixers="none")
+    assert candidates[0]["passed"] is False
+    assert candidates[0]["description"].count("RENT PAYMENT") == 2
+    assert any("duplicates the previous" in error for error in candidates[0]["errors"])
@@ -0,0 +1,46 @@
+"""Tests for parsing statements split across multiple files."""
+
+from pathlib import Path
+
+import pytest
+from transtractor.parser import Parser
+from transtractor.structs.statement_data import StatementData
+from transtractor.transtractor import NoErrorFreeStatementData
+
+
+def _expected_rows(fixtures_dir: Path) -> list[list[str]]:
+    with open(fixtures_dir / "test1_parsed.csv", encoding="utf-8") as expected:
+        return [line.strip().split(",") for line in expected.readlines()[1:]]
+
+
+@pytest.mark.parametrize(
+    "part_names",
+    [
+        ["test1_layout_part1.txt", "test1_layout_part2.txt"],
+        ["test1_layout_part2.txt", "test1_layout_part1.txt"],
+    ],
+)
+def test_parse_layout_parts_merges_into_single_statement(part_names):
+    """Test that both parts parse as one statement, in either order."""
+    parser = Parser()
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test1_config.json"))
+
+    statement_data: StatementData = parser.parse_layout_parts(
+        [str(fixtures_dir / name) for name in part_names]
+    )
+
+    assert statement_data.account_number == "1234 5678 9123 4567"
+    assert len(statement_data.transactions) == len(_expected_rows(fixtures_dir))
+    assert statement_data.transactions[0].description == "Transaction 1"
+    assert statement_data.transactions[-1].description == "Transaction 62"
+
+
+def test_parse_layout_parts_single_part_fails():
+    """Test that a single part is missing fields and fails quality checks."""
+    parser = Parser()
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser.load(str(fixtures_dir / "test1_config.json"))
+
+    with pytest.raises(NoErrorFreeStatementData):
+        parser.parse_layout_parts([str(fixtures_dir / "test1_layout_part1.txt")])
@@ -0,0 +1,34 @@
+"""Tests for parsing documents with several consecutive statements."""
+
+from pathlib import Path
+
+from transtractor.parser import Parser
+
+FIXTURES_DIR = Path(__file__).parent.parent / "fixtures"
+
+
+def test_parse_layout_statements_splits_statements():
+    """Test that each statement of a combined document gives its own StatementData,
+    in start date order, with balances that chain from one statement to the next."""
+    parser = Parser()
+    parser.load(str(FIXTURES_DIR / "test3_config_multi_statement.json"))
+
+    sds = parser.parse_layout_statements(
+        str(FIXTURES_DIR / "test3_layout_three_statements.txt")
+    )
+
+    assert [sd.metadata["statement_section"] for sd in sds] == [1, 2, 3]
+    assert [sd.metadata["closing_balance"] for sd in sds] == [1105.5, 1211.0, 1316.5]
+    for previous, sd in zip(sds, sds[1:]):
+        assert sd.metadata["opening_balance"] == previous.metadata["closing_balance"]
+
+
+def test_parse_layout_statements_single_statement():
+    """Test that a document with a single statement gives a single StatementData."""
+    parser = Parser()
+    parser.load(str(FIXTURES_DIR / "test3_config_multi_statement.json"))
+
+    sds = parser.parse_layout_statements(str(FIXTURES_DIR / "test3_layout.txt"))
+
+    assert len(sds) == 1
+    assert sds[0].metadata["statement_section"] is None
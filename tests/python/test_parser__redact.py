@@ -0,0 +1,82 @@
+"""Tests for the redaction of the account number and descriptions in outputs."""
+
+import csv
+import tempfile
+from pathlib import Path
+
+import pytest
+from transtractor.parser import Parser
+
+FIXTURES_DIR = Path(__file__).parent.parent / "fixtures"
+
+
+def _parse_test3():
+    parser = Parser()
+    parser.load(str(FIXTURES_DIR / "test3_config.json"))
+    return parser.parse_layout(str(FIXTURES_DIR / "test3_layout.txt"))
+
+
+def test_to_pandas_dict_redacts_account_number_and_descriptions():
+    """Test that redaction masks the account number and description patterns."""
+    statement_data = _parse_test3()
+    fields = ("account_number", "description", "amount")
+
+    data = statement_data.to_pandas_dict(
+        fields, redact=True, redact_patterns=[r"ACME PTY LTD"]
+    )
+
+    assert set(data["account_number"]) == {"****5678"}
+    assert "SALARY ****" in data["description"]
+    assert not any("ACME" in d for d in data["description"])
+    # Amounts are unchanged
+    assert data["amount"] == statement_data.to_pandas_dict(fields)["amount"]
+
+
+def test_to_pandas_dict_not_redacted_by_default():
+    """Test that the account number is only masked when requested."""
+    statement_data = _parse_test3()
+
+    data = statement_data.to_pandas_dict(("account_number", "description"))
+
+    assert set(data["account_number"]) == {"062-000 1234 5678"}
+    assert "SALARY ACME PTY LTD" in data["description"]
+
+
+def test_to_csv_redacts_account_number():
+    """Test that to_csv writes the masked account number."""
+    statement_data = _parse_test3()
+
+    with tempfile.TemporaryDirectory() as tmp_dir:
+        csv_path = Path(tmp_dir) / "redacted.csv"
+        statement_data.to_csv(
+            str(csv_path), fields=("account_number", "description"), redact=True
+        )
+        with open(csv_path, encoding="utf-8", newline="") as f:
+            rows = list(csv.DictReader(f))
+
+    assert rows
+    assert all(row["account_number"] == "****5678" for row in rows)
+
+
+def test_invalid_redaction_pattern_raises_value_error():
+    """Test that an invalid redaction pattern raises a ValueError."""
+    statement_data = _parse_test3()
+
+    with pytest.raises(ValueError, match="Invalid redaction pattern"):
+        statement_data.to_pandas_dict(redact_patterns=["("])
+
+
+def test_debug_redacts_account_number():
+    """Test that debug with redact masks the account number everywhere."""
+    parser = Parser()
+    parser.load(str(FIXTURES_DIR / "test1_config.json"))
+
+    with tempfile.TemporaryDirectory() as tmp_dir:
+        debug_path = Path(tmp_dir) / "debug.txt"
+        parser.debug(
+            str(FIXTURES_DIR / "test1.pdf"), str(debug_path), sources=True, redact=True
+        )
+        debug = debug_path.read_text(encoding="utf-8")
+
+    assert "Account Number: ****4567" in debug
+    assert "1234 5678 9123 4567" not in debug
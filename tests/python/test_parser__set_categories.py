@@ -0,0 +1,58 @@
+"""Tests for Parser.set_categories() method."""
+
+import csv
+import tempfile
+from pathlib import Path
+
+import pytest
+from transtractor.parser import Parser
+
+FIXTURES_DIR = Path(__file__).parent.parent / "fixtures"
+LAYOUT_PATH = str(FIXTURES_DIR / "test3_layout.txt")
+
+
+def _parser() -> Parser:
+    parser = Parser()
+    parser.load(str(FIXTURES_DIR / "test3_config.json"))
+    return parser
+
+
+def test_set_categories_tags_transactions():
+    """Test that the first matching rule sets the category, ignoring case."""
+    parser = _parser()
+    parser.set_categories(
+        [("income", r"^salary"), ("housing", "rent"), ("other", "rent|salary")]
+    )
+
+    statement_data = parser.parse_layout(LAYOUT_PATH)
+
+    categories = [tx.category for tx in statement_data.transactions]
+    assert categories == ["income", "housing", ""]
+    data = statement_data.to_pandas_dict(fields=("description", "category"))
+    assert data["category"] == ["income", "housing", ""]
+    with tempfile.TemporaryDirectory() as tmp_dir:
+        csv_path = Path(tmp_dir) / "tagged.csv"
+        statement_data.to_csv(str(csv_path), fields=("amount", "category"))
+        with open(csv_path, newline="") as fh:
+            rows = list(csv.reader(fh))
+    assert rows[0] == ["amount", "category"]
+    assert [row[1] for row in rows[1:]] == ["income", "housing", ""]
+
+
+def test_set_categories_from_json_str_and_candidates():
+    """Test that categories load from JSON and appear in the candidate columns."""
+    parser = _parser()
+    parser.set_categories('[["food", "GROCERIES"]]', case_insensitive=False)
+
+    candidates = parser.parse_layout_all(LAYOUT_PATH)
+    assert candidates[0]["category"] == ["", "", "food"]
+
+    parser.set_categories(None)
+    statement_data = parser.parse_layout(LAYOUT_PATH)
+    assert all(tx.category == "" for tx in statement_data.transactions)
+
+
+def test_set_categories_invalid_pattern():
+    """Test that an invalid pattern raises ValueError."""
+    with pytest.raises(ValueError, match="Invalid category pattern"):
+        _parser().set_categories([("broken", "(")])
@@ -0,0 +1,44 @@
+"""Tests for strict parsing of the transaction table."""
+
+import json
+from pathlib import Path
+
+import pytest
+from transtractor.parser import Parser
+from transtractor.transtractor import NoErrorFreeStatementData
+
+FIXTURES_DIR = Path(__file__).parent.parent / "fixtures"
+
+
+def test_strict_fails_on_unread_table_lines():
+    """Test that strict parsing fails on the unread lines of the test1 transaction
+    table (e.g., its page footer), which a default parse ignores."""
+    parser = Parser()
+    parser.load(str(FIXTURES_DIR / "test1_config.json"))
+    layout_file = str(FIXTURES_DIR / "test1_layout.txt")
+
+    assert parser.parse_layout(layout_file).transactions
+
+    with pytest.raises(NoErrorFreeStatementData, match="not read by any parser"):
+        parser.parse_layout(layout_file, strict=True)
+
+
+def test_strict_config_accepts_orphan_lines_max(tmp_path):
+    """Test that a strict config accepts up to transaction_orphan_lines_max unread
+    lines."""
+    config = json.loads((FIXTURES_DIR / "test1_config.json").read_text())
+    config["transaction_strict"] = True
+    config_file = tmp_path / "config.json"
+    layout_file = str(FIXTURES_DIR / "test1_layout.txt")
+
+    config_file.write_text(json.dumps(config))
+    parser = Parser()
+    parser.load(str(config_file))
+    with pytest.raises(NoErrorFreeStatementData):
+        parser.parse_layout(layout_file)
+
+    config["transaction_orphan_lines_max"] = 5
+    config_file.write_text(json.dumps(config))
+    parser = Parser()
+    parser.load(str(config_file))
+    assert parser.parse_layout(layout_file).transactions
@@ -1,11 +1,15 @@
 """Tests for the Parser test method."""
 
 import csv
+import logging
+import os
+import shutil
 import tempfile
 from pathlib import Path
 
 import pytest
 from transtractor.parser import Parser
+from transtractor.utils.testing import _run_tests_in_workers
 
 
 def normalize_csv_for_comparison(csv_path: str, fixtures_dir: Path) -> list[list[str]]:
@@ -20,14 +24,14 @@ def normalize_csv_for_comparison(csv_path: str, fixtures_dir: Path) -> list[list
     # project_root is the parent of tests_dir (e.g., /path/to/project)
     project_root = tests_dir.parent
 
-    # Zero out timing columns (indices 4, 5, 6, 7 based on header:
-    # "PDF File", "Pages", "Transactions", "Config Keys",
+    # Zero out timing columns (indices 5, 6, 7, 8 based on header:
+    # "PDF File", "Pages", "Transactions", "Config Keys", "Config Status",
     # "Extract Time (ms)", "Identify Time (ms)", "Parse Time (ms)", "Total Time (ms)",
     # "Status", "Reason Failed"
     for i, row in enumerate(rows):
         if i == 0:  # Skip header
             continue
-        if len(row) >= 8:
+        if len(row) >= 9:
             # Convert absolute path to relative path from project root
             if row[0]:  # PDF File column
                 pdf_path = Path(row[0])
@@ -40,7 +44,7 @@ def normalize_csv_for_comparison(csv_path: str, fixtures_dir: Path) -> list[list
                     pass
 
             # Zero out timing columns if they contain non-zero values
-            for timing_idx in [4, 5, 6, 7]:
+            for timing_idx in [5, 6, 7, 8]:
                 if row[timing_idx] and row[timing_idx] != "0":
                     row[timing_idx] = "0"
 
@@ -194,3 +198,158 @@ def test_test_raises_value_error_with_invalid_log_level():
     finally:
         # Clean up temporary file
         Path(tmp_csv_path).unlink(missing_ok=True)
+
+
+def test_test_report_summarises_results(tmp_path):
+    """Test that test_report returns the results of each config and the files that
+    matched no config, tested in worker processes or one at a time."""
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser = Parser()
+    parser.load(str(fixtures_dir / "test1_config.json"))
+
+    for max_workers in [None, 1]:
+        report = parser.test_report(
+            str(fixtures_dir), log_level="WARNING", max_workers=max_workers
+        )
+
+        assert report.num_files == 1
+        assert report.num_passed == 1
+        assert report.key_counts == {"au__gtb__fake_account__1": {"PASS": 1, "FAIL": 0}}
+        assert report.unmatched_files == []
+        assert report.average_parse_time == report.total_parse_time
+        summary = report.summary()
+        assert summary.startswith("Summary: 1 passed, 0 failed out of 1 files.")
+
+    report = Parser().test_report(str(fixtures_dir), log_level="WARNING")
+    assert report.num_failed == 1
+    assert report.key_counts == {}
+    assert [Path(path).name for path in report.unmatched_files] == ["test1.pdf"]
+
+
+def test_test_report_gives_the_reason_of_unreadable_files(tmp_path):
+    """Test that test_report fails a file whose text cannot be extracted with the
+    specific reason, rather than as matching no config."""
+    (tmp_path / "corrupt.pdf").write_bytes(b"not a pdf")
+
+    report = Parser().test_report(str(tmp_path), log_level="WARNING", max_workers=1)
+
+    assert report.num_failed == 1
+    assert report.unmatched_files == []
+    assert [(Path(path).name, reason) for path, reason in report.unreadable_files] == [
+        ("corrupt.pdf", "PdfMalformed")
+    ]
+    assert "Could not read 1 files:\n  " in report.summary()
+    assert report.summary().endswith("corrupt.pdf (PdfMalformed)")
+
+
+def test_test_report_skips_symlink_cycles(tmp_path):
+    """Test that a symlink cycle in the tested directory is searched once and
+    reported as skipped."""
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    nested = tmp_path / "synced" / "nested"
+    nested.mkdir(parents=True)
+    shutil.copy(fixtures_dir / "test1.pdf", nested / "test1.pdf")
+    try:
+        os.symlink(tmp_path, nested / "loop", target_is_directory=True)
+    except (OSError, NotImplementedError):
+        pytest.skip("Creating symlinks needs privileges on this platform")
+    parser = Parser()
+    parser.load(str(fixtures_dir / "test1_config.json"))
+
+    report = parser.test_report(str(tmp_path), log_level="WARNING", max_workers=1)
+
+    assert report.num_files == 1
+    assert len(report.skipped_directories) == 1
+    assert "symlink cycle" in report.skipped_directories[0]
+    assert "Skipped 1 directories:" in report.summary()
+
+
+def test_test_report_reports_progress_and_cancels(tmp_path):
+    """Test that the progress callback is called once each file is tested, and
+    that returning False cancels the run."""
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    for name in ["a.pdf", "b.pdf", "c.pdf"]:
+        shutil.copy(fixtures_dir / "test1.pdf", tmp_path / name)
+    parser = Parser()
+    parser.load(str(fixtures_dir / "test1_config.json"))
+    events = []
+
+    report = parser.test_report(
+        str(tmp_path), log_level="WARNING", max_workers=1, progress=events.append
+    )
+
+    assert [(e.index, e.total, e.status) for e in events] == [
+        (1, 3, "PASS"),
+        (2, 3, "PASS"),
+        (3, 3, "PASS"),
+    ]
+    assert [Path(e.pdf_file_path).name for e in events] == ["a.pdf", "b.pdf", "c.pdf"]
+    assert events[0].elapsed <= events[2].elapsed
+    assert not report.cancelled
+
+    for max_workers in [1, 2]:
+        events = []
+
+        def cancel_after_first(event):
+            events.append(event)
+            return False
+
+        report = parser.test_report(
+            str(tmp_path),
+            log_level="WARNING",
+            max_workers=max_workers,
+            progress=cancel_after_first,
+        )
+
+        assert len(events) == 1
+        assert report.cancelled
+        assert report.num_files == 1
+        assert "Cancelled after 1 files." in report.summary()
+
+
+def test_test_report_workers_share_the_parser_state(tmp_path):
+    """Test that worker processes test with the configs and categories of the
+    parser, however imported, giving the same results as testing one at a time."""
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    for name in ["a.pdf", "b.pdf"]:
+        shutil.copy(fixtures_dir / "test1.pdf", tmp_path / name)
+    parser = Parser()
+    json_str = (fixtures_dir / "test1_config.json").read_text(encoding="utf-8")
+    parser._inner.import_config_from_json_str(json_str)
+    parser.set_categories([("transfers", "TRANSFER")])
+
+    reports = [
+        parser.test_report(str(tmp_path), log_level="WARNING", max_workers=max_workers)
+        for max_workers in [1, 2]
+    ]
+
+    serial, parallel = [
+        [(r.status, r.key, r.num_transactions) for r in report.results]
+        for report in reports
+    ]
+    assert serial == parallel
+    assert reports[1].key_counts == {"au__gtb__fake_account__1": {"PASS": 2, "FAIL": 0}}
+
+
+def test_test_report_falls_back_when_workers_break(tmp_path):
+    """Test that files are tested one at a time with the parser when the worker
+    processes cannot be created from its state."""
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    pdf_files = []
+    for name in ["a.pdf", "b.pdf"]:
+        shutil.copy(fixtures_dir / "test1.pdf", tmp_path / name)
+        pdf_files.append(str(tmp_path / name))
+    parser = Parser()
+    parser.load(str(fixtures_dir / "test1_config.json"))
+    # A config that cannot be imported in the workers
+    state = parser._worker_state()
+    state["configs"] = ["{"]
+
+    results = list(
+        _run_tests_in_workers(pdf_files, parser, state, 2, logging.getLogger())
+    )
+
+    assert [(r.pdf_file_path, r.status) for r in results] == [
+        (pdf_files[0], "PASS"),
+        (pdf_files[1], "PASS"),
+    ]
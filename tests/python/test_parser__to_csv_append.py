@@ -0,0 +1,79 @@
+"""Tests for the combined CSV of several statements."""
+
+import csv
+from pathlib import Path
+
+import pytest
+from transtractor.parser import Parser
+from transtractor.utils.combined import (
+    COMBINED_FIELDS,
+    append_statements_to_csv,
+    statements_to_csv,
+)
+
+FIXTURES_DIR = Path(__file__).parent.parent / "fixtures"
+
+
+def parse_monthly_statements():
+    """Parse the April, May and June statements of the three statement fixture, each
+    as if from its own file."""
+    parser = Parser()
+    parser.load(str(FIXTURES_DIR / "test3_config_multi_statement.json"))
+    sds = parser.parse_layout_statements(
+        str(FIXTURES_DIR / "test3_layout_three_statements.txt")
+    )
+    for month, sd in zip(["april", "may", "june"], sds):
+        sd.set_filename(f"{month}.pdf")
+    return sds
+
+
+def read_rows(path: Path) -> list[list[str]]:
+    with open(path, newline="", encoding="utf-8") as fh:
+        return list(csv.reader(fh))
+
+
+def test_append_writes_header_once_in_date_order(tmp_path):
+    """Test that appending statements out of order gives a single header, rows in
+    date order with their source file and key, and no gaps for consecutive months."""
+    april, may, june = parse_monthly_statements()
+    path = tmp_path / "combined.csv"
+
+    assert append_statements_to_csv([june], str(path)) == []
+    assert append_statements_to_csv([april, may], str(path)) == []
+    # Appending a statement again replaces its rows
+    assert append_statements_to_csv([may], str(path)) == []
+
+    rows = read_rows(path)
+    assert rows[0] == list(COMBINED_FIELDS)
+    assert len(rows) == 1 + 3 * len(april.transactions)
+    dates = [row[0] for row in rows[1:]]
+    assert dates == sorted(dates)
+    assert rows[1][4:] == ["april.pdf", april.key]
+    assert rows[-1][4:] == ["june.pdf", june.key]
+
+
+def test_missing_month_is_a_gap(tmp_path):
+    """Test that a missing statement is reported as a balance gap rather than merged
+    silently, and that a gap within the tolerance is not."""
+    april, _, june = parse_monthly_statements()
+    path = tmp_path / "combined.csv"
+
+    warnings = statements_to_csv([april, june], str(path))
+
+    assert warnings == [
+        f"Balance gap between april.pdf ({april.key}), closing balance 1105.50, "
+        f"and june.pdf ({june.key}), opening balance 1211.00: difference 105.50"
+    ]
+    assert statements_to_csv([april, june], str(path), tolerance=200.0) == []
+
+
+def test_append_to_other_csv_fails(tmp_path):
+    """Test that appending to a CSV with other columns fails, leaving it unchanged."""
+    april, _, _ = parse_monthly_statements()
+    path = tmp_path / "other.csv"
+    path.write_text("date,amount\n2024-04-01,1.0\n")
+
+    with pytest.raises(ValueError, match="combined CSV header"):
+        append_statements_to_csv([april], str(path))
+
+    assert path.read_text() == "date,amount\n2024-04-01,1.0\n"
@@ -0,0 +1,104 @@
+"""Tests for Parser.try_config() method."""
+
+from pathlib import Path
+
+import pytest
+from transtractor.exceptions import StatementNotSupported
+from transtractor.parser import Parser
+from transtractor.transtractor import ConfigLoadError
+
+FIXTURES_DIR = Path(__file__).parent.parent / "fixtures"
+
+
+def test_try_config_parses_without_registering():
+    """Test that a draft config parses the statement without being registered."""
+    parser = Parser()
+    draft = (FIXTURES_DIR / "test3_config.json").read_text(encoding="utf-8")
+    layout_path = str(FIXTURES_DIR / "test3_layout.txt")
+
+    result = parser.try_config(draft, layout_path)
+
+    assert result["key"] == "au__ssb__savings__1"
+    assert result["passed"]
+    assert result["errors"] == []
+    assert len(result["statement_datas"]) == 1
+    assert len(result["statement_datas"][0].transactions) == 3
+    assert "=== StatementData Result 1 ===" in result["trace"]
+    with pytest.raises(StatementNotSupported):
+        parser.parse_layout(layout_path)
+
+
+def test_try_config_reflects_latest_draft():
+    """Test that each call uses only the given draft."""
+    parser = Parser()
+    draft = (FIXTURES_DIR / "test3_config.json").read_text(encoding="utf-8")
+    layout_path = str(FIXTURES_DIR / "test3_layout.txt")
+
+    failing = parser.try_config(
+        draft.replace("Closing balance:", "Final balance:"), layout_path
+    )
+    passing = parser.try_config(draft, layout_path)
+
+    assert not failing["passed"]
+    assert failing["errors"]
+    assert passing["passed"]
+
+
+def test_try_config_raises_config_load_error_with_invalid_draft():
+    """Test that an invalid draft raises ConfigLoadError."""
+    parser = Parser()
+    draft = (FIXTURES_DIR / "test1_config_invalid.json").read_text(encoding="utf-8")
+
+    with pytest.raises(ConfigLoadError):
+        parser.try_config(draft, str(FIXTURES_DIR / "test1_layout.txt"))
+
+
+def test_try_config_rejects_universal_description_exclude():
+    """Test that an exclude pattern matching every description is rejected."""
+    parser = Parser()
+    draft = (FIXTURES_DIR / "test3_config.json").read_text(encoding="utf-8")
+    draft = draft.replace(
+        '"transaction_description_exclude": []',
+        '"transaction_description_exclude": [".*"]',
+    )
+
+    with pytest.raises(ConfigLoadError, match="transaction_description_exclude"):
+        parser.try_config(draft, str(FIXTURES_DIR / "test3_layout.txt"))
+
+
+def test_try_config_errors_when_exclusions_empty_descriptions():
+    """Test that a greedy exclude pattern emptying most descriptions is an error."""
+    parser = Parser()
+    draft = (FIXTURES_DIR / "test3_config.json").read_text(encoding="utf-8")
+    draft = draft.replace(
+        '"transaction_description_exclude": []',
+        '"transaction_description_exclude": ["^[A-Z ]+$"]',
+    )
+
+    result = parser.try_config(draft, str(FIXTURES_DIR / "test3_layout.txt"))
+
+    assert not result["passed"]
+    assert result["errors"] == [
+        "3 of 3 transaction descriptions reduced to empty by "
+        "transaction_description_exclude (e.g., 'SALARY ACME PTY LTD', "
+        "'RENT PAYMENT', 'GROCERIES')"
+    ]
+
+
+def test_try_config_reads_pdfs_and_layouts_alike(tmp_path):
+    """Test that the statement file is read as by parse: a PDF, or a layout file by
+    its extension in any case."""
+    parser = Parser()
+    draft = (FIXTURES_DIR / "test1_config.json").read_text(encoding="utf-8")
+    upper_layout = tmp_path / "TEST1_LAYOUT.TXT"
+    upper_layout.write_bytes((FIXTURES_DIR / "test1_layout.txt").read_bytes())
+
+    from_pdf = parser.try_config(draft, str(FIXTURES_DIR / "test1.pdf"))
+    from_layout = parser.try_config(draft, str(upper_layout))
+
+    assert from_pdf["passed"]
+    assert from_layout["passed"]
+    assert (
+        from_pdf["statement_datas"][0].transactions
+        == from_layout["statement_datas"][0].transactions
+    )
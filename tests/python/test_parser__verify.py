@@ -0,0 +1,73 @@
+"""Tests for Parser.verify() method."""
+
+import tempfile
+from pathlib import Path
+
+import pytest
+from transtractor.parser import Parser
+
+FIXTURES_DIR = Path(__file__).parent.parent / "fixtures"
+
+EXPECTED_CSV = """date,description,amount,balance
+2024-04-02,SALARY ACME PTY LTD,250.0,1250.0
+2024-04-03,Rent  Payment,-120.0,1130.0
+2024-04-05,GROCERIES,-24.50,1105.50
+"""
+
+
+def _verify(expected_csv: str, **kwargs) -> dict:
+    parser = Parser()
+    parser.load(str(FIXTURES_DIR / "test3_config.json"))
+    with tempfile.TemporaryDirectory() as tmp_dir:
+        csv_path = Path(tmp_dir) / "expected.csv"
+        csv_path.write_text(expected_csv, encoding="utf-8")
+        return parser.verify(
+            str(FIXTURES_DIR / "test3_layout.txt"), str(csv_path), **kwargs
+        )
+
+
+def test_verify_passes_for_expected_transactions():
+    """Test that the parsed transactions match the expected CSV."""
+    report = _verify(EXPECTED_CSV)
+
+    assert report["passed"]
+    assert report["expected_count"] == report["actual_count"] == 3
+    assert report["text"] == "Verified 3 transactions\n"
+
+
+def test_verify_reports_mismatched_and_missing_rows():
+    """Test that differences are reported by row, without cascading."""
+    expected_csv = EXPECTED_CSV.replace("-24.50", "-24.55") + (
+        "2024-04-06,FUEL,-60.00,1045.50\n"
+    )
+
+    report = _verify(expected_csv, normalise_descriptions=False)
+
+    assert not report["passed"]
+    assert [m["expected_number"] for m in report["mismatched"]] == [2, 3]
+    assert report["mismatched"][1]["mismatches"] == [
+        {"field": "amount", "expected": -24.55, "actual": -24.5}
+    ]
+    assert report["missing"][0]["number"] == 4
+    assert report["extra"] == []
+    assert "Row 4 missing" in report["text"]
+    assert _verify(expected_csv, tolerance=0.1)["mismatched"][0]["expected_number"] == 2
+
+
+def test_verify_invalid_expected_csv():
+    """Test that an expected CSV without transaction columns raises ValueError."""
+    with pytest.raises(ValueError, match="none of the columns"):
+        _verify("notes\nfoo\n")
+
+
+def test_verify_reads_pdfs_and_layouts_alike(tmp_path):
+    """Test that the statement file is read as by parse: a PDF, or a layout file by
+    its extension in any case."""
+    parser = Parser()
+    parser.load(str(FIXTURES_DIR / "test1_config.json"))
+    upper_layout = tmp_path / "TEST1_LAYOUT.TXT"
+    upper_layout.write_bytes((FIXTURES_DIR / "test1_layout.txt").read_bytes())
+    expected_csv = str(FIXTURES_DIR / "test1_parsed.csv")
+
+    for path in (FIXTURES_DIR / "test1.pdf", upper_layout):
+        assert parser.verify(str(path), expected_csv)["passed"]
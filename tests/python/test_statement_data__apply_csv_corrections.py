@@ -0,0 +1,78 @@
+"""Tests for the StatementData apply_csv_corrections method."""
+
+import csv
+from pathlib import Path
+
+from transtractor.parser import Parser
+from transtractor.structs.statement_data import StatementData
+from transtractor.utils.diff import diff
+
+FIELDS = ["date", "date_index", "description", "amount", "balance"]
+
+
+def parse_test3() -> StatementData:
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser = Parser()
+    parser.load(str(fixtures_dir / "test3_config.json"))
+    return parser.parse_layout(str(fixtures_dir / "test3_layout.txt"))
+
+
+def edit_csv(path: Path, edits: dict[tuple[int, str], str]) -> None:
+    """Edit cells of a CSV by (0-based data row, column name)."""
+    with open(path, newline="", encoding="utf-8") as csvfile:
+        rows = list(csv.DictReader(csvfile))
+    for (row, column), value in edits.items():
+        rows[row][column] = value
+    with open(path, "w", newline="", encoding="utf-8") as csvfile:
+        writer = csv.DictWriter(csvfile, fieldnames=FIELDS)
+        writer.writeheader()
+        writer.writerows(rows)
+
+
+def test_apply_csv_corrections_applies_two_edited_cells(tmp_path):
+    """Test that exactly the edited cells are applied and flagged as corrected."""
+    statement_data = parse_test3()
+    original = parse_test3()
+    path = tmp_path / "review.csv"
+    statement_data.to_csv(str(path), fields=FIELDS)
+    edit_csv(path, {(0, "description"): "SALARY ACME", (2, "amount"): "-25.5"})
+
+    summary = statement_data.apply_csv_corrections(str(path))
+
+    assert [c["row"] for c in summary["corrections"]] == [1, 3]
+    assert summary["corrections"][0]["changes"][0]["new"] == "SALARY ACME"
+    assert summary["unmatched_rows"] == []
+    # The corrected amount no longer agrees with the balances
+    assert summary["errors_added"]
+    changes = diff(original, statement_data)["transactions_changed"]
+    changed_fields = [
+        (c["old_number"], sorted(f["field"] for f in c["changes"])) for c in changes
+    ]
+    assert changed_fields == [
+        (1, ["description", "description_source"]),
+        (3, ["amount", "amount_source"]),
+    ]
+    transactions = statement_data.transactions
+    assert transactions[0].description_source == "corrected"
+    assert transactions[0].amount_source == "parsed"
+    assert transactions[2].amount == -25.5
+    assert transactions[2].amount_source == "corrected"
+
+    regenerated = tmp_path / "corrected.csv"
+    statement_data.to_csv(str(regenerated), fields=["description", "amount"])
+    lines = regenerated.read_text(encoding="utf-8").splitlines()
+    assert lines[1].startswith("SALARY ACME,")
+    assert lines[3].endswith(",-25.5")
+
+
+def test_apply_csv_corrections_reports_unmatched_rows(tmp_path):
+    """Test that rows not matching a transaction by date and index are reported."""
+    statement_data = parse_test3()
+    path = tmp_path / "review.csv"
+    statement_data.to_csv(str(path), fields=FIELDS)
+    edit_csv(path, {(1, "date_index"): "5"})
+
+    summary = statement_data.apply_csv_corrections(str(path))
+
+    assert summary["corrections"] == []
+    assert summary["unmatched_rows"] == [2]
@@ -0,0 +1,55 @@
+"""Tests for comparing two parses of the same statement."""
+
+import copy
+import json
+from pathlib import Path
+
+from transtractor.parser import Parser
+from transtractor.structs.statement_data import StatementData
+from transtractor.utils.diff import diff, diff_to_text, statement_data_to_export
+
+
+def parse_test1() -> StatementData:
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser = Parser()
+    parser.load(str(fixtures_dir / "test1_config.json"))
+    return parser.parse_layout(str(fixtures_dir / "test1_layout.txt"))
+
+
+def test_diff_identical_parses_is_empty():
+    """Test that identical parses have no differences."""
+    result = parse_test1().diff(parse_test1())
+
+    assert all(changes == [] for changes in result.values())
+    assert diff_to_text(parse_test1(), parse_test1()) == "No differences\n"
+
+
+def test_diff_reports_one_cent_change(tmp_path):
+    """Test that a one-cent change is reported at the exact transaction and field,
+    comparing a JSON export file with a parse."""
+    old = statement_data_to_export(parse_test1())
+    new = copy.deepcopy(old)
+    new["transactions"][4]["amount"] = round(new["transactions"][4]["amount"] + 0.01, 2)
+    old_path = tmp_path / "old.json"
+    old_path.write_text(json.dumps(old))
+
+    result = diff(str(old_path), new)
+
+    assert result["metadata"] == []
+    assert result["transactions_added"] == []
+    assert result["transactions_removed"] == []
+    assert result["transactions_changed"] == [
+        {
+            "old_number": 5,
+            "new_number": 5,
+            "changes": [
+                {
+                    "field": "amount",
+                    "old": old["transactions"][4]["amount"],
+                    "new": new["transactions"][4]["amount"],
+                }
+            ],
+        }
+    ]
+    text = diff_to_text(old_path, new)
+    assert text.startswith("Transaction 5 (now 5) changed: amount")
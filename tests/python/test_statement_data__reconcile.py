@@ -0,0 +1,123 @@
+"""Tests for the StatementData reconcile method."""
+
+import csv
+import tempfile
+from datetime import date
+from pathlib import Path
+
+from transtractor.parser import Parser
+from transtractor.structs.statement_data import StatementData
+from transtractor.structs.transaction import Transaction
+from transtractor.utils.reconcile import (
+    REPORT_CSV_HEADER,
+    reconciliation_to_csv,
+    reconciliation_to_text,
+)
+
+EXTERNAL_COLUMNS = {"date": "Posted", "description": "Details", "amount": "Value"}
+
+
+def parse_test1() -> StatementData:
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    parser = Parser()
+    parser.load(str(fixtures_dir / "test1_config.json"))
+    return parser.parse_layout(str(fixtures_dir / "test1_layout.txt"))
+
+
+def test_reconcile_produces_three_buckets():
+    """Test matched, statement-only and external-only transactions."""
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    statement_data = parse_test1()
+
+    report = statement_data.reconcile(
+        str(fixtures_dir / "test1_external.csv"),
+        columns=EXTERNAL_COLUMNS,
+        date_format="%d/%m/%Y",
+    )
+
+    assert len(report["matched"]) == 60
+    assert [tx["description"] for tx in report["statement_only"]] == [
+        "Transaction 22",
+        "Transaction 39",
+    ]
+    assert [
+        (tx["date"], tx["description"], tx["amount"])
+        for tx in report["external_only"]
+    ] == [
+        (date(2025, 1, 18), "TRANSACTION 39", -32.0),
+        (date(2025, 1, 30), "BANK FEE", -5.0),
+    ]
+
+    # Date shifted by one day in the external CSV is still matched
+    pair = next(
+        p for p in report["matched"] if p["statement"]["description"] == "Transaction 5"
+    )
+    assert pair["external"]["date"] == date(2025, 1, 4)
+
+    # Duplicate amounts on adjacent days are matched to the closest date
+    for description in ("Transaction 51", "Transaction 53"):
+        pair = next(
+            p for p in report["matched"] if p["statement"]["description"] == description
+        )
+        assert pair["external"]["description"] == description.upper()
+
+
+def test_reconcile_duplicate_amounts_same_day_match_in_order():
+    """Test duplicate amounts on the same day are matched by order."""
+    statement_data = StatementData(
+        "key",
+        "1234",
+        [
+            Transaction(date(2025, 1, 1), 0, "First", -10.0, 90.0),
+            Transaction(date(2025, 1, 1), 1, "Second", -10.0, 80.0),
+        ],
+    )
+    with tempfile.NamedTemporaryFile(
+        mode="w", suffix=".csv", delete=False, newline="", encoding="utf-8"
+    ) as tmp_file:
+        tmp_file.write("date,description,amount\n")
+        tmp_file.write("2025-01-01,A,-10.00\n")
+        tmp_file.write("2025-01-01,B,-10.00\n")
+        tmp_csv_path = tmp_file.name
+
+    try:
+        report = statement_data.reconcile(tmp_csv_path)
+    finally:
+        Path(tmp_csv_path).unlink(missing_ok=True)
+
+    assert [
+        (p["statement"]["description"], p["external"]["description"])
+        for p in report["matched"]
+    ] == [("First", "A"), ("Second", "B")]
+    assert report["statement_only"] == []
+    assert report["external_only"] == []
+
+
+def test_reconciliation_report_outputs():
+    """Test writing the reconciliation report to CSV and text."""
+    fixtures_dir = Path(__file__).parent.parent / "fixtures"
+    report = parse_test1().reconcile(
+        str(fixtures_dir / "test1_external.csv"),
+        columns=EXTERNAL_COLUMNS,
+        date_format="%d/%m/%Y",
+    )
+
+    with tempfile.NamedTemporaryFile(suffix=".csv", delete=False) as tmp_file:
+        tmp_csv_path = tmp_file.name
+
+    try:
+        reconciliation_to_csv(report, tmp_csv_path)
+        with open(tmp_csv_path, encoding="utf-8") as f:
+            rows = list(csv.reader(f))
+    finally:
+        Path(tmp_csv_path).unlink(missing_ok=True)
+
+    assert rows[0] == REPORT_CSV_HEADER
+    statuses = [row[0] for row in rows[1:]]
+    assert statuses.count("matched") == 60
+    assert statuses.count("statement_only") == 2
+    assert statuses.count("external_only") == 2
+
+    text = reconciliation_to_text(report)
+    assert text.startswith("Matched: 60, statement only: 2, external only: 2")
+    assert "BANK FEE (row 60)" in text
@@ -0,0 +1,69 @@
+"""Tests for the StatementData to_csv method."""
+
+from datetime import date
+
+import pytest
+from transtractor.structs.statement_data import StatementData
+from transtractor.structs.transaction import Transaction
+
+
+class InvalidTimestamp:
+    """Timestamp that fails when written."""
+
+    def __str__(self) -> str:
+        raise ValueError("Invalid timestamp")
+
+
+def make_statement_data() -> StatementData:
+    transactions = [
+        Transaction(date(2024, 4, day), 0, f"Transaction {day}", 10.0, 10.0 * day)
+        for day in range(1, 4)
+    ]
+    return StatementData("au__test__1", "123456", transactions)
+
+
+def test_to_csv_failure_leaves_output_untouched(tmp_path):
+    """Test that a failure midway through writing leaves no partial CSV, and any
+    existing file at the output path unchanged."""
+    statement_data = make_statement_data()
+    statement_data.transactions[2].timestamp = InvalidTimestamp()
+    new_path = tmp_path / "new.csv"
+    existing_path = tmp_path / "existing.csv"
+    existing_path.write_text("previous\n")
+
+    for path in (new_path, existing_path):
+        with pytest.raises(ValueError, match="Invalid timestamp"):
+            statement_data.to_csv(str(path), fields=["date", "timestamp"])
+
+    assert not new_path.exists()
+    assert existing_path.read_text() == "previous\n"
+    assert sorted(p.name for p in tmp_path.iterdir()) == ["existing.csv"]
+
+
+def test_to_csv_success_replaces_output(tmp_path):
+    """Test that a successful write replaces the output file."""
+    path = tmp_path / "out.csv"
+    path.write_text("previous\n")
+
+    make_statement_data().to_csv(str(path), fields=["date", "description"])
+
+    lines = path.read_text().splitlines()
+    assert lines == [
+        "date,description",
+        "2024-04-01,Transaction 1",
+        "2024-04-02,Transaction 2",
+        "2024-04-03,Transaction 3",
+    ]
+    assert sorted(p.name for p in tmp_path.iterdir()) == ["out.csv"]
+
+
+def test_to_csv_writes_currency(tmp_path):
+    """Test that the currency metadata value is written as a statement-level field."""
+    path = tmp_path / "out.csv"
+    statement_data = make_statement_data()
+    statement_data.set_metadata({"currency": "£"})
+
+    statement_data.to_csv(str(path), fields=["currency", "date"])
+
+    lines = path.read_text(encoding="utf-8").splitlines()
+    assert lines[:2] == ["currency,date", "£,2024-04-01"]